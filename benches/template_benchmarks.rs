@@ -2,9 +2,81 @@
 //!
 //! Run with: `cargo bench`
 
+use cli_frontend::template_engine::naming::{
+    process_smart_names, to_camel_case, to_kebab_case, to_pascal_case, to_snake_case,
+};
+use cli_frontend::template_engine::renderer::{
+    create_handlebars, create_template_data, render_preview, render_template_chunked,
+    RENDER_CHUNK_SIZE,
+};
 use cli_frontend::template_engine::HandlebarsRenderer;
-use cli_frontend::template_engine::TemplateRenderer;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use cli_frontend::template_engine::{TemplateConfig, TemplateEngine, TemplateRenderer};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A `.conf` resembling the bundled component template
+const COMPONENT_CONF: &str = r#"
+[metadata]
+name=React Component
+description=Functional component with optional styles, tests and stories
+
+[options]
+style=scss
+style_options=css,scss,styled-components,none
+style_description=Styling approach
+with_tests=true
+with_tests_type=boolean
+with_stories=false
+with_stories_type=boolean
+
+[files]
+$FILE_NAME.spec.tsx=with_tests
+$FILE_NAME.stories.tsx=with_stories
+$FILE_NAME.module.scss=style == scss
+"#;
+
+/// Benchmark the naming functions directly (no Handlebars involved)
+fn benchmark_name_conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("name_conversion");
+
+    for name in ["UserProfile", "user_profile_settings", "useAuthState"] {
+        group.bench_with_input(BenchmarkId::new("all_cases", name), name, |b, name| {
+            b.iter(|| {
+                (
+                    to_pascal_case(black_box(name)).len(),
+                    to_camel_case(black_box(name)).len(),
+                    to_snake_case(black_box(name)).len(),
+                    to_kebab_case(black_box(name)).len(),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("smart_names", name), name, |b, name| {
+            b.iter(|| process_smart_names(black_box(name)))
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark `.conf` parsing
+fn benchmark_conf_parsing(c: &mut Criterion) {
+    let engine = TemplateEngine::new(PathBuf::from("templates"), PathBuf::from("out")).unwrap();
+
+    c.bench_function("conf_parsing/component", |b| {
+        b.iter(|| engine.parse_template_config(black_box(COMPONENT_CONF)))
+    });
+}
+
+/// Benchmark building the Handlebars data object for a template
+fn benchmark_data_building(c: &mut Criterion) {
+    let engine = TemplateEngine::new(PathBuf::from("templates"), PathBuf::from("out")).unwrap();
+    let config = engine.parse_template_config(COMPONENT_CONF).unwrap();
+
+    c.bench_function("data_building/component", |b| {
+        b.iter(|| create_template_data(black_box("UserProfile"), black_box(&config)))
+    });
+}
 
 /// Benchmark case conversion through Handlebars helpers
 fn benchmark_case_conversions(c: &mut Criterion) {
@@ -65,6 +137,15 @@ export const {{pascal_case name}}: React.FC<{{pascal_case name}}Props> = ({ clas
         b.iter(|| renderer.render(black_box(template), black_box(&data)))
     });
 
+    // Whole per-file pipeline: substitutions, data building and rendering
+    let config = TemplateConfig {
+        variables: HashMap::from([("style".to_string(), "scss".to_string())]),
+        ..Default::default()
+    };
+    group.bench_function("react_component_pipeline", |b| {
+        b.iter(|| render_preview(black_box(template), black_box("UserProfile"), &config))
+    });
+
     group.finish();
 }
 
@@ -100,10 +181,54 @@ fn benchmark_handlebars_render(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark rendering of large templates (e.g. embedded fixture data)
+///
+/// Reports byte throughput per size for single-pass and chunked rendering;
+/// single-pass throughput drops as the template grows, chunked stays flat.
+fn benchmark_large_template_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_template_render");
+    group.sample_size(10);
+
+    let renderer = HandlebarsRenderer::new();
+    let handlebars = create_handlebars();
+    let data = serde_json::json!({"name": "UserProfile"});
+    let row = "  { \"id\": 1, \"owner\": \"{{pascal_case name}}\", \"payload\": \"lorem ipsum dolor sit amet\" },\n";
+
+    for size_kb in [16usize, 64, 256] {
+        let template = row.repeat(size_kb * 1024 / row.len());
+        group.throughput(Throughput::Bytes(template.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("single_pass", format!("{}KB", size_kb)),
+            &template,
+            |b, template| b.iter(|| renderer.render(black_box(template), black_box(&data))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("chunked", format!("{}KB", size_kb)),
+            &template,
+            |b, template| {
+                b.iter(|| {
+                    render_template_chunked(
+                        &handlebars,
+                        black_box(template),
+                        black_box(&data),
+                        RENDER_CHUNK_SIZE,
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    benchmark_name_conversion,
+    benchmark_conf_parsing,
+    benchmark_data_building,
     benchmark_case_conversions,
     benchmark_full_template_render,
-    benchmark_handlebars_render
+    benchmark_handlebars_render,
+    benchmark_large_template_render
 );
 criterion_main!(benches);
@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+fn main() {
+    // Linker setup for Node.js addons (only needed with the `napi` feature)
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    embed_default_templates();
+}
+
+/// Embed `templates/` so `cli-frontend bootstrap` can install it anywhere
+fn embed_default_templates() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("templates");
+    // Cargo watches the whole directory tree
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut files = Vec::new();
+    collect_files(&root, &mut files);
+    files.sort();
+
+    let mut code =
+        String::from("/// Files of the built-in templates, by path relative to `templates/`\n");
+    code.push_str("pub const DEFAULT_TEMPLATES: &[(&str, &[u8])] = &[\n");
+    for path in files {
+        let relative = path
+            .strip_prefix(&root)
+            .expect("template file outside templates/")
+            .to_string_lossy()
+            .replace('\\', "/");
+        code.push_str(&format!(
+            "    ({:?}, include_bytes!({:?})),\n",
+            relative,
+            path.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    std::fs::write(out_dir.join("default_templates.rs"), code)
+        .expect("could not write the embedded templates");
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+}
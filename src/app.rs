@@ -0,0 +1,933 @@
+//! The CLI flow behind `main`, as a reusable function.
+//!
+//! [`run`] takes parsed [`Args`] and a [`RunIo`] and does what the
+//! `cli-frontend` binary does: dispatch subcommands and listings, fall back
+//! to the wizard, and generate a feature or a template. The binary only
+//! parses arguments and reports errors, so other binaries can embed the
+//! same behavior and tests can drive it end to end with captured output.
+//!
+//! Everything a run prints goes to `io`: the flow's own messages, the
+//! subcommands, `--describe` and the engine's progress and warnings. The
+//! run enters a [`ui::Context`] with its streams, `--quiet`, `--no-emoji`
+//! and language, so concurrent runs don't share settings.
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::archive::ArchiveFileSystem;
+use crate::audit::{AuditLog, AuditedFileSystem};
+use crate::bootstrap;
+use crate::cli::{Args, Command};
+use crate::commands;
+use crate::config::Config;
+use crate::error::CliError;
+use crate::file_system::TokioFileSystem;
+use crate::template_engine::cancel::Cancellation;
+use crate::template_engine::figma::FigmaComponent;
+use crate::template_engine::tokens::DesignTokens;
+use crate::template_engine::tree::render_tree;
+use crate::template_engine::usage::{TemplateUse, UsageHistory};
+use crate::template_engine::{clipboard, editor};
+use crate::template_engine::{
+    primary_file, verify_files, ConflictPolicy, GenerationPlan, IssueSeverity, RenderTrace,
+    RunProfile, TemplateDescription, TemplateEngine,
+};
+use crate::ui::{self, i18n, symbols, t};
+use crate::wizard;
+
+/// Where [`run`] writes its output, whether it may ask questions, and
+/// whether it leaves per-user state behind
+pub struct RunIo {
+    pub stdout: ui::Output,
+    pub stderr: ui::Output,
+    /// Whether the wizard and other prompts may be shown; without it
+    /// conflicts are overwritten and missing values are reported as errors
+    pub interactive: bool,
+    /// Whether the use is remembered in the configured `usage_file`, as the
+    /// binary does for `--describe`; embedders opt in
+    pub record_usage: bool,
+}
+
+/// What a [`run`] generated
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunReport {
+    /// Name the item was generated with
+    pub name: Option<String>,
+    /// Template type, or `feature`
+    pub template_type: Option<String>,
+    /// Files written (into the archive with `--output-archive`); empty when
+    /// nothing was generated or the output was printed or copied
+    pub written: Vec<PathBuf>,
+    /// The `--output-archive` file
+    pub archive: Option<PathBuf>,
+}
+
+/// Run the CLI for `args`
+///
+/// # Errors
+///
+/// Returns the failure `main` reports; [`CliError::classify`] gives its
+/// exit code and hint.
+///
+/// # Example
+///
+/// ```no_run
+/// use clap::Parser;
+/// use cli_frontend::cli::Args;
+/// use cli_frontend::ui::{Buffer, Output};
+/// use cli_frontend::{run, RunIo};
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let args = Args::parse_from(["cli-frontend", "Button", "--type", "component"]);
+/// let stdout = Buffer::default();
+/// let mut io = RunIo {
+///     stdout: Output::new(stdout.clone()),
+///     stderr: Output::stderr(),
+///     interactive: false,
+///     record_usage: false,
+/// };
+/// let report = run(args, &mut io).await?;
+/// println!("{} file(s) written:\n{}", report.written.len(), stdout.contents());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run(args: Args, io: &mut RunIo) -> Result<RunReport> {
+    let context = ui::Context::new(
+        args.quiet,
+        args.no_emoji,
+        io.stdout.clone(),
+        io.stderr.clone(),
+    );
+    let result = context.clone().scope(run_once(args.clone(), io)).await;
+
+    // On a first run with no templates, offer to set them up and run again
+    if let Some(CliError::NoTemplates {
+        templates_dir,
+        starter,
+    }) = result.as_ref().err().and_then(CliError::classify)
+    {
+        if io.interactive {
+            let offer = async { wizard::offer_bootstrap(&templates_dir, starter.as_deref()) };
+            if context.clone().scope(offer).await? {
+                return context.scope(run_once(args, io)).await;
+            }
+        }
+    }
+    result
+}
+
+/// One pass of [`run`], inside its [`ui::Context`]
+async fn run_once(args: Args, io: &mut RunIo) -> Result<RunReport> {
+    let run_start = Instant::now();
+
+    // Load configuration first to get templates directory
+    let config = Config::load(&args.config)
+        .await
+        .context(CliError::ConfigError {
+            message: "Could not load configuration".to_string(),
+        })?;
+    let config_elapsed = run_start.elapsed();
+
+    ui::Context::current()
+        .with_lang(i18n::detect(config.lang()))
+        .scope(run_with_config(args, config, run_start, config_elapsed, io))
+        .await
+}
+
+/// The rest of [`run`] once the configuration is loaded
+async fn run_with_config(
+    mut args: Args,
+    config: Config,
+    run_start: Instant,
+    config_elapsed: Duration,
+    io: &mut RunIo,
+) -> Result<RunReport> {
+    // `feature A B C` generates through the feature flow below
+    let mut feature_names = Vec::new();
+    if let Some(Command::Feature {
+        names,
+        architecture,
+        tree,
+        resume,
+        output_dir,
+        vars,
+    }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Feature { .. }))
+    {
+        args.name = names.first().cloned();
+        args.template_type = Some("feature".to_string());
+        args.architecture = architecture.or(args.architecture);
+        args.tree |= tree;
+        args.resume |= resume;
+        args.output_dir = output_dir.or(args.output_dir);
+        args.vars.extend(vars);
+        feature_names = names;
+    }
+
+    if let Some(command) = &args.command {
+        commands::run(command, &config, &args.config).await?;
+        return Ok(RunReport::default());
+    }
+
+    if let (true, Some(tag)) = (args.list, &args.tag) {
+        commands::search_templates(&config, "", Some(tag)).await?;
+        return Ok(RunReport::default());
+    }
+    if args.list {
+        Args::write_simple_list(
+            &mut io.stdout,
+            config.templates_dir(),
+            config.packs(),
+            config.architectures_dir(),
+            args.include_internal,
+        )?;
+        return Ok(RunReport::default());
+    }
+
+    // Handle --describe and --describe-all flags
+    if args.describe.is_some() || args.describe_all {
+        let template_engine =
+            TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+                .with_defaults(config.template_defaults())
+                .with_packs(config.packs().clone())
+                .with_include_internal(args.include_internal)
+                .with_usage_file(config.usage_file().cloned())
+                .with_environment(args.env.clone());
+
+        match (&args.describe, args.json) {
+            (Some(template_name), false) => {
+                template_engine.describe_template(template_name).await?
+            }
+            (Some(template_name), true) => ui::data_to!(
+                &mut io.stdout,
+                "{}",
+                serde_json::to_string_pretty(
+                    &template_engine.template_description(template_name).await?
+                )?
+            )?,
+            (None, false) => template_engine.describe_all_templates().await?,
+            (None, true) => ui::data_to!(
+                &mut io.stdout,
+                "{}",
+                serde_json::to_string_pretty(&template_engine.template_descriptions().await?)?
+            )?,
+        }
+        return Ok(RunReport::default());
+    }
+
+    let strict = args.strict;
+    let environment = args.env.clone();
+    let keep_going = args.keep_going;
+    let all_errors = args.all_errors;
+    let cancellation = Cancellation::default()
+        .with_interrupt(true)
+        .with_timeout(args.timeout.map(Duration::from_secs));
+    let stamp = args.stamp || config.stamp_files();
+
+    // Seed the name, variables and template data from a Figma component export
+    let figma = match &args.figma_json {
+        Some(path) => Some(load_figma_component(path).await?),
+        None => None,
+    };
+    if let Some(component) = &figma {
+        args.name.get_or_insert_with(|| component.item_name());
+        if !args.stdout {
+            ui::status_to!(
+                &mut io.stdout,
+                "{} {}",
+                symbols::DESIGN.as_str().bold(),
+                t!(
+                    "generate.figma_imported",
+                    name = component.name.bold(),
+                    count = component.props.len()
+                )
+            )?;
+        }
+    }
+
+    // Review changes to existing files interactively unless told otherwise
+    let conflict_policy = args.on_conflict.unwrap_or(if io.interactive {
+        ConflictPolicy::Prompt
+    } else {
+        ConflictPolicy::Overwrite
+    });
+
+    // Time each generation phase when requested
+    let profile = args.profile_run.then(|| {
+        let profile = Arc::new(RunProfile::new());
+        profile.record("load cli config", config_elapsed);
+        profile
+    });
+    // Record helper calls when asked to explain the run
+    let explain = args.explain.clone();
+    let trace = explain.is_some().then(|| Arc::new(RenderTrace::new()));
+
+    // A first run with nothing in templates_dir can set it up instead
+    if config.packs().is_empty() && !bootstrap::has_templates(config.templates_dir()) {
+        return Err(CliError::NoTemplates {
+            templates_dir: config.templates_dir().clone(),
+            starter: config.starter_templates().map(str::to_string),
+        }
+        .into());
+    }
+
+    // Check if we should run wizard (no name and no template type provided)
+    let final_args = if args.name.is_none() && args.template_type.is_none() {
+        if !io.interactive {
+            anyhow::bail!("No name was provided. Pass a name and --type, or run in a terminal for the wizard.");
+        }
+        // Run interactive wizard
+        let wizard_config = wizard::run_wizard(&config, args.include_internal).await?;
+        // The wizard only offers internal templates with --include-internal
+        Args {
+            include_internal: args.include_internal,
+            ..Args::from(wizard_config)
+        }
+    } else {
+        args
+    };
+
+    // Parse CLI variables first (before moving fields from final_args)
+    let mut cli_vars = final_args.parse_vars();
+
+    // Validate arguments (either from CLI or wizard)
+    let name = final_args
+        .name
+        .ok_or_else(|| anyhow::anyhow!("No name was provided."))?;
+    let template_type = match final_args.template_type {
+        Some(t) => t,
+        None => config.default_type().to_string(),
+    };
+    let mut report = RunReport {
+        name: Some(name.clone()),
+        template_type: Some(template_type.clone()),
+        ..Default::default()
+    };
+
+    // Determine output directory (CLI arg overrides config)
+    let output_dir = match final_args.output_dir {
+        Some(dir) => dir,
+        None => config.output_dir().clone(),
+    };
+
+    // Collect output in an archive instead of writing it when requested
+    let archive = match &final_args.output_archive {
+        Some(path) => Some((
+            path.clone(),
+            Arc::new(ArchiveFileSystem::for_archive(output_dir.clone(), path)?),
+        )),
+        None => None,
+    };
+
+    // Record every change on disk when auditing
+    let audit = match &final_args.audit_log {
+        Some(path) => Some(Arc::new(AuditLog::open(path, std::env::args().collect())?)),
+        None => None,
+    };
+
+    // Initialize template engine
+    let mut defaults = config.template_defaults();
+    defaults.env_policy.disabled = final_args.no_env_helpers;
+    if let Some(component) = &figma {
+        defaults
+            .data
+            .insert("figma".to_string(), component.to_data());
+    }
+    if let Some(path) = &final_args.tokens {
+        let tokens = load_design_tokens(path).await?;
+        defaults.data.insert("tokens".to_string(), tokens.to_data());
+    }
+    let mut template_engine =
+        TemplateEngine::new(config.templates_dir().clone(), output_dir.clone())?
+            .with_defaults(defaults)
+            .with_packs(config.packs().clone())
+            .with_output_roots(config.roots().clone())
+            .with_strict_variables(strict)
+            .with_environment(environment)
+            .with_conflict_policy(conflict_policy)
+            .with_keep_going(keep_going)
+            .with_all_errors(all_errors)
+            .with_cancellation(cancellation)
+            .with_stamp(stamp)
+            .with_resume(final_args.resume)
+            .with_feature_variables(cli_vars.clone())
+            .with_license_file(final_args.license_file)
+            .with_show_all(final_args.show_all);
+    if let Some((_, fs)) = &archive {
+        template_engine = template_engine.with_file_system(fs.clone());
+    } else if let Some(log) = &audit {
+        let fs = AuditedFileSystem::new(Arc::new(TokioFileSystem), log.clone());
+        template_engine = template_engine.with_file_system(Arc::new(fs));
+    }
+    if let Some(profile) = &profile {
+        template_engine = template_engine.with_profile(profile.clone());
+    }
+    if let Some(trace) = &trace {
+        template_engine = template_engine.with_trace(trace.clone());
+    }
+
+    // Surface a misconfigured templates_dir before anything is planned
+    if config.check_templates() {
+        let requested = (template_type != "feature").then_some(template_type.as_str());
+        for warning in template_engine.startup_warnings(requested) {
+            writeln!(io.stderr, "Warning: {}", warning.message)?;
+        }
+    }
+
+    let create_folder = !final_args.no_folder && config.create_folder();
+
+    // Handle feature type specially
+    if template_type == "feature" {
+        if final_args.stdout {
+            anyhow::bail!("--stdout is not supported for feature generation");
+        }
+        if final_args.copy {
+            anyhow::bail!("--copy is not supported for feature generation");
+        }
+        if final_args.as_package {
+            anyhow::bail!("--as-package is not supported for feature generation");
+        }
+
+        let architecture = final_args
+            .architecture
+            .as_deref()
+            .unwrap_or(config.default_architecture());
+
+        if feature_names.is_empty() {
+            feature_names.push(name.clone());
+        }
+
+        // Show where every file would go before anything is rendered
+        if final_args.tree {
+            let files = template_engine
+                .plan_features(&feature_names, Some(architecture), create_folder, &config)
+                .await?;
+            ui::show_to!(
+                &mut io.stdout,
+                "{}",
+                render_tree(&output_dir, &files).trim_end()
+            )?;
+            let confirmed = final_args.yes || (io.interactive && wizard::confirm_generation()?);
+            if !confirmed {
+                ui::status_to!(
+                    &mut io.stdout,
+                    "{} {}",
+                    symbols::GOODBYE.as_str().yellow(),
+                    t!("generate.nothing_written")
+                )?;
+                return Ok(report);
+            }
+        }
+        let start = if let [name] = feature_names.as_slice() {
+            t!(
+                "generate.feature_start",
+                name = name.bold(),
+                architecture = architecture
+            )
+        } else {
+            t!(
+                "generate.features_start",
+                count = feature_names.len(),
+                names = feature_names.join(", ").bold(),
+                architecture = architecture
+            )
+        };
+        ui::status_to!(
+            &mut io.stdout,
+            "{} {}",
+            symbols::LAUNCH.as_str().bold(),
+            start
+        )?;
+
+        let result = template_engine
+            .generate_features(&feature_names, Some(architecture), create_folder, &config)
+            .await;
+        write_trace(io, explain.as_deref(), trace.as_deref())?;
+        let written = result?;
+
+        let done = if let [name] = feature_names.as_slice() {
+            t!(
+                "generate.feature_done",
+                name = name.bold(),
+                architecture = architecture
+            )
+        } else {
+            t!(
+                "generate.features_done",
+                count = feature_names.len(),
+                architecture = architecture
+            )
+        };
+        ui::status_to!(
+            &mut io.stdout,
+            "{} {}",
+            symbols::SUCCESS.as_str().green(),
+            done
+        )?;
+
+        if final_args.verify {
+            verify_files(&written, config.verify_command()).await?;
+        }
+        if archive.is_none() && (final_args.open || config.open_after_generate().is_some()) {
+            open_generated(io, &written, create_folder, config.open_after_generate()).await?;
+        }
+
+        report.archive = finish_archive(io, archive, audit.as_deref(), profile.as_deref())?;
+        write_profile(io, profile.as_deref(), run_start)?;
+        report.written = written;
+        return Ok(report);
+    }
+
+    if final_args.tree || final_args.resume {
+        let flag = if final_args.tree {
+            "--tree"
+        } else {
+            "--resume"
+        };
+        anyhow::bail!("{} is only supported for feature generation", flag);
+    }
+
+    // Validate template type exists, choosing a pack when several provide it
+    let candidates = template_engine.template_candidates(&template_type).await?;
+    let template_type = match candidates.as_slice() {
+        [_] => template_type,
+        _ if io.interactive => wizard::select_template_candidate(&template_type, &candidates)?,
+        _ => {
+            return Err(CliError::AmbiguousTemplate {
+                name: template_type,
+                candidates,
+            }
+            .into())
+        }
+    };
+
+    // Reject --var values the template declares as invalid, warn about unknown keys
+    let description = template_engine.template_description(&template_type).await?;
+    if description.internal && !final_args.include_internal {
+        return Err(CliError::InternalTemplate {
+            name: template_type,
+        }
+        .into());
+    }
+    for issue in description.validate(&cli_vars) {
+        if issue.severity == IssueSeverity::Error {
+            return Err(CliError::InvalidVariable {
+                name: issue.variable,
+                message: issue.message,
+            }
+            .into());
+        }
+        writeln!(
+            io.stderr,
+            "Warning: --var {}: {}",
+            issue.variable, issue.message
+        )?;
+    }
+
+    if let Some(component) = &figma {
+        let figma_vars = figma_variables(io, component, &description, &cli_vars)?;
+        cli_vars.extend(figma_vars);
+    }
+
+    // Ask for required variables interactively; otherwise generation reports them
+    let missing = description.missing_required(&cli_vars);
+    if !missing.is_empty() && io.interactive {
+        cli_vars.extend(wizard::prompt_required_variables(&description, &missing)?);
+    }
+
+    if !final_args.stdout && io.interactive {
+        resolve_empty_generation(io, &template_engine, &template_type, &mut cli_vars).await?;
+    }
+
+    if final_args.stdout {
+        print_to_stdout(io, &template_engine, &name, &template_type, cli_vars).await?;
+        return Ok(report);
+    }
+    if final_args.copy {
+        copy_to_clipboard(io, &template_engine, &name, &template_type, cli_vars).await?;
+        return Ok(report);
+    }
+
+    if !final_args.as_package {
+        let plan = template_engine
+            .plan_generation(&name, &template_type, create_folder, &cli_vars)
+            .await?;
+        write_plan(io, &template_type, &plan)?;
+        if io.interactive && !final_args.yes && !wizard::confirm_generation()? {
+            ui::status_to!(
+                &mut io.stdout,
+                "{} {}",
+                symbols::GOODBYE.as_str().yellow(),
+                t!("generate.nothing_written")
+            )?;
+            return Ok(report);
+        }
+    }
+
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::LAUNCH.as_str().bold(),
+        t!("generate.start", kind = template_type, name = name.bold())
+    )?;
+
+    let used = TemplateUse {
+        name: name.clone(),
+        variables: cli_vars.clone().into_iter().collect(),
+    };
+    let result = if final_args.as_package {
+        template_engine
+            .generate_package(&name, &template_type, cli_vars)
+            .await
+    } else {
+        template_engine
+            .generate(&name, &template_type, create_folder, cli_vars)
+            .await
+    };
+    // The trace helps most when rendering failed, so it comes first
+    write_trace(io, explain.as_deref(), trace.as_deref())?;
+    let written = result?;
+
+    // Remember this use for `--describe`; losing it isn't worth failing the run
+    let usage_file = config.usage_file().filter(|_| io.record_usage);
+    if let (Some(usage_file), false) = (usage_file, final_args.as_package) {
+        let mut history = UsageHistory::load(usage_file);
+        history.record(&template_type, used);
+        if let Err(e) = history.save(usage_file) {
+            writeln!(io.stderr, "Warning: {:#}", e)?;
+        }
+    }
+
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::SUCCESS.as_str().green(),
+        t!("generate.done", kind = template_type, name = name.bold())
+    )?;
+
+    if final_args.verify {
+        verify_files(&written, config.verify_command()).await?;
+    }
+    if archive.is_none() && (final_args.open || config.open_after_generate().is_some()) {
+        open_generated(io, &written, create_folder, config.open_after_generate()).await?;
+    }
+
+    report.archive = finish_archive(io, archive, audit.as_deref(), profile.as_deref())?;
+    write_profile(io, profile.as_deref(), run_start)?;
+    report.written = written;
+    Ok(report)
+}
+
+/// Print the pre-flight summary of a generation
+fn write_plan(io: &mut RunIo, template_type: &str, plan: &GenerationPlan) -> Result<()> {
+    let variables = if plan.variables.is_empty() {
+        "none".dimmed().to_string()
+    } else {
+        plan.variables
+            .iter()
+            .map(|(key, value)| format!("{}={}", key.cyan(), value.green()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::LIST,
+        t!("generate.preflight").bold()
+    )?;
+    let rows = [
+        (t!("label.template"), template_type.to_string()),
+        (t!("label.output"), plan.output_path.display().to_string()),
+        (t!("label.files"), plan.file_count.to_string()),
+        (t!("label.variables"), variables),
+    ];
+    for (label, value) in rows {
+        ui::status_to!(&mut io.stdout, "  {:<10} {}", label, value)?;
+    }
+    Ok(())
+}
+
+/// When the `[files]` conditions leave nothing to generate, explain why and
+/// let the user pick a variable change instead of creating an empty folder
+async fn resolve_empty_generation(
+    io: &mut RunIo,
+    template_engine: &TemplateEngine,
+    template_type: &str,
+    cli_vars: &mut HashMap<String, String>,
+) -> Result<()> {
+    loop {
+        let Err(error) = template_engine
+            .check_files_to_generate(template_type, cli_vars)
+            .await
+        else {
+            return Ok(());
+        };
+        let suggestions = match error.downcast_ref::<CliError>() {
+            Some(CliError::NothingToGenerate { suggestions, .. }) if !suggestions.is_empty() => {
+                suggestions.clone()
+            }
+            _ => return Err(error),
+        };
+
+        ui::show_to!(
+            &mut io.stderr,
+            "{} {}",
+            symbols::WARNING.as_str().yellow(),
+            error
+        )?;
+        let Some(change) = wizard::prompt_variable_change(&suggestions)? else {
+            return Err(error);
+        };
+        if let Some((name, value)) = change.split_once('=') {
+            cli_vars.insert(name.to_string(), value.to_string());
+        }
+    }
+}
+
+/// Read and parse the `--figma-json` export
+async fn load_figma_component(path: &Path) -> Result<FigmaComponent> {
+    let json = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Could not read Figma JSON {}", path.display()))?;
+    FigmaComponent::parse(&json)
+        .with_context(|| format!("Could not import Figma JSON {}", path.display()))
+}
+
+/// Read and parse the `--tokens` design tokens file
+async fn load_design_tokens(path: &Path) -> Result<DesignTokens> {
+    let json = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Could not read design tokens {}", path.display()))?;
+    DesignTokens::parse(&json)
+        .with_context(|| format!("Could not import design tokens {}", path.display()))
+}
+
+/// Figma property defaults for variables the template declares and `--var` didn't set
+///
+/// Values are matched case-insensitively against the variable's options
+/// (`Primary` selects `primary`); values the template rejects are skipped.
+fn figma_variables(
+    io: &mut RunIo,
+    component: &FigmaComponent,
+    description: &TemplateDescription,
+    cli_vars: &HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    let mut vars: HashMap<String, String> = component
+        .variables()
+        .into_iter()
+        .filter(|(name, _)| !cli_vars.contains_key(name))
+        .filter_map(|(name, value)| {
+            let variable = description.variables.get(&name)?;
+            let value = variable
+                .values
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(&value))
+                .cloned()
+                .unwrap_or(value);
+            Some((name, value))
+        })
+        .collect();
+
+    for issue in description.validate(&vars) {
+        writeln!(
+            io.stderr,
+            "Warning: --figma-json {}: {}, ignoring",
+            issue.variable, issue.message
+        )?;
+        vars.remove(&issue.variable);
+    }
+
+    Ok(vars)
+}
+
+/// Render a single-file template and print it, keeping stdout free of progress output
+async fn print_to_stdout(
+    io: &mut RunIo,
+    engine: &TemplateEngine,
+    name: &str,
+    template_type: &str,
+    cli_vars: HashMap<String, String>,
+) -> Result<()> {
+    let files = engine
+        .render_to_memory(name, template_type, cli_vars)
+        .await?;
+
+    let [file] = files.as_slice() else {
+        anyhow::bail!(
+            "--stdout requires a template that generates exactly one file, but '{}' generates {}",
+            template_type,
+            files.len()
+        );
+    };
+    let content = file.as_text().ok_or_else(|| {
+        anyhow::anyhow!("--stdout cannot print binary file: {}", file.path.display())
+    })?;
+
+    write!(io.stdout, "{}", content)?;
+    Ok(())
+}
+
+/// Render a template and copy its main file to the system clipboard
+async fn copy_to_clipboard(
+    io: &mut RunIo,
+    engine: &TemplateEngine,
+    name: &str,
+    template_type: &str,
+    cli_vars: HashMap<String, String>,
+) -> Result<()> {
+    let files = engine
+        .render_to_memory(name, template_type, cli_vars)
+        .await?;
+
+    let paths: Vec<PathBuf> = files.iter().map(|file| file.path.clone()).collect();
+    let Some(path) = primary_file(&paths) else {
+        anyhow::bail!("Template '{}' generated no files to copy", template_type);
+    };
+    let file = &files[paths.iter().position(|p| p == path).unwrap_or(0)];
+    let content = file.as_text().ok_or_else(|| {
+        anyhow::anyhow!("--copy cannot copy binary file: {}", file.path.display())
+    })?;
+
+    let Some(system_clipboard) = clipboard::system_clipboard() else {
+        writeln!(
+            io.stderr,
+            "Warning: --copy found no clipboard tool (install wl-clipboard, xclip or xsel); printing {} instead",
+            file.path.display()
+        )?;
+        write!(io.stdout, "{}", content)?;
+        return Ok(());
+    };
+    clipboard::copy_text(system_clipboard, content).await?;
+
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::LIST.as_str().bold(),
+        t!(
+            "generate.copied",
+            path = file.path.display().to_string().green(),
+            count = content.lines().count()
+        )
+    )?;
+    if files.len() > 1 {
+        ui::status_to!(
+            &mut io.stdout,
+            "{}",
+            t!("generate.not_copied", count = files.len() - 1).dimmed()
+        )?;
+    }
+    Ok(())
+}
+
+/// Open what a run generated in the user's editor
+///
+/// The files are already written, so a missing or failing editor is only a
+/// warning.
+async fn open_generated(
+    io: &mut RunIo,
+    written: &[PathBuf],
+    create_folder: bool,
+    configured: Option<&str>,
+) -> Result<()> {
+    let Some(target) = editor::open_target(written, create_folder) else {
+        return Ok(());
+    };
+    let Some(command) = editor::editor_command(configured) else {
+        writeln!(
+            io.stderr,
+            "Warning: --open needs an editor: set open_after_generate in the config, or $VISUAL / $EDITOR"
+        )?;
+        return Ok(());
+    };
+
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::NOTE.as_str().bold(),
+        t!(
+            "generate.opening",
+            path = target.display().to_string().green(),
+            command = command
+        )
+    )?;
+    // The editor shares the terminal, so flush what was written before it
+    io.stdout.flush()?;
+    if let Err(error) = editor::open_in_editor(&command, &target).await {
+        writeln!(io.stderr, "Warning: {:#}", error)?;
+    }
+    Ok(())
+}
+
+/// Pack the collected files when generating with --output-archive, returning
+/// the archive path
+fn finish_archive(
+    io: &mut RunIo,
+    archive: Option<(PathBuf, Arc<ArchiveFileSystem>)>,
+    audit: Option<&AuditLog>,
+    profile: Option<&RunProfile>,
+) -> Result<Option<PathBuf>> {
+    let Some((path, fs)) = archive else {
+        return Ok(None);
+    };
+
+    let start = Instant::now();
+    let count = fs.finish(&path)?;
+    if let Some(log) = audit {
+        let contents = std::fs::read(&path)
+            .with_context(|| format!("Could not read archive: {}", path.display()))?;
+        log.record("write", &path, Some(&contents))?;
+    }
+    if let Some(profile) = profile {
+        profile.record_since("pack archive", start);
+    }
+    ui::status_to!(
+        &mut io.stdout,
+        "{} {}",
+        symbols::PACKAGE.as_str().bold(),
+        t!(
+            "generate.archive_written",
+            path = path.display().to_string().green(),
+            count = count
+        )
+    )?;
+
+    Ok(Some(path))
+}
+
+/// Write the --profile-run timing breakdown
+fn write_profile(io: &mut RunIo, profile: Option<&RunProfile>, run_start: Instant) -> Result<()> {
+    if let Some(profile) = profile {
+        profile.write_report(&mut io.stdout, run_start.elapsed())?;
+    }
+    Ok(())
+}
+
+/// Print the --explain helper trace, or save it as JSON when given a file
+fn write_trace(io: &mut RunIo, explain: Option<&Path>, trace: Option<&RenderTrace>) -> Result<()> {
+    let (Some(path), Some(trace)) = (explain, trace) else {
+        return Ok(());
+    };
+    if path == Path::new("-") {
+        trace.write_report(&mut io.stdout)?;
+        return Ok(());
+    }
+
+    let json = serde_json::to_string_pretty(&trace.files())?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Could not write helper trace: {}", path.display()))?;
+    ui::status_to!(
+        &mut io.stdout,
+        "{} Helper trace written to {}",
+        symbols::INSPECT.as_str().bold(),
+        path.display().to_string().green()
+    )?;
+    Ok(())
+}
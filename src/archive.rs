@@ -0,0 +1,257 @@
+//! Archive output target.
+//!
+//! [`ArchiveFileSystem`] collects generated files in memory through the
+//! [`FileSystem`] trait and packs them into a `.zip` or tarball once
+//! generation is done (`--output-archive`).
+
+use anyhow::{bail, Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::file_system::{FileSystem, FsFuture, MemoryFileSystem};
+
+/// Supported archive formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from an archive file name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::archive::ArchiveFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(ArchiveFormat::from_path(Path::new("scaffold.zip")), Some(ArchiveFormat::Zip));
+    /// assert_eq!(ArchiveFormat::from_path(Path::new("scaffold.tgz")), Some(ArchiveFormat::TarGz));
+    /// assert_eq!(ArchiveFormat::from_path(Path::new("scaffold.rar")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// File system backend that packs generated files into an archive
+///
+/// Paths are stored relative to `root` (the engine's output directory).
+pub struct ArchiveFileSystem {
+    root: PathBuf,
+    format: ArchiveFormat,
+    files: MemoryFileSystem,
+}
+
+impl ArchiveFileSystem {
+    pub fn new(root: PathBuf, format: ArchiveFormat) -> Self {
+        Self {
+            root,
+            format,
+            files: MemoryFileSystem::new(),
+        }
+    }
+
+    /// Create a backend for `archive`, detecting the format from its extension
+    pub fn for_archive(root: PathBuf, archive: &Path) -> Result<Self> {
+        match ArchiveFormat::from_path(archive) {
+            Some(format) => Ok(Self::new(root, format)),
+            None => bail!(
+                "Unsupported archive format: {} (expected .zip, .tar, .tar.gz or .tgz)",
+                archive.display()
+            ),
+        }
+    }
+
+    /// Write the collected files to `archive`, returning the number of entries
+    pub fn finish(&self, archive: &Path) -> Result<usize> {
+        let entries = self.entries()?;
+
+        if let Some(parent) = archive.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Could not create parent directory: {}", parent.display())
+            })?;
+        }
+        let file = std::fs::File::create(archive)
+            .with_context(|| format!("Could not create archive: {}", archive.display()))?;
+
+        write_archive(file, self.format, &entries)
+            .with_context(|| format!("Could not write archive: {}", archive.display()))?;
+
+        Ok(entries.len())
+    }
+
+    /// Collected files as `/`-separated archive paths, sorted
+    fn entries(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        self.files
+            .files()
+            .into_iter()
+            .map(|(path, contents)| {
+                let relative = path.strip_prefix(&self.root).with_context(|| {
+                    format!(
+                        "Generated file is outside the output directory: {}",
+                        path.display()
+                    )
+                })?;
+                Ok((relative.to_string_lossy().replace('\\', "/"), contents))
+            })
+            .collect()
+    }
+}
+
+impl FileSystem for ArchiveFileSystem {
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        self.files.create_dir_all(path)
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> FsFuture<'a, ()> {
+        self.files.write(path, contents)
+    }
+
+    fn copy_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        self.files.copy_file(source, path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.exists(path)
+    }
+}
+
+/// Write `(path, contents)` entries as an archive of the given format
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::archive::{write_archive, ArchiveFormat};
+/// use std::io::Cursor;
+///
+/// let mut buffer = Cursor::new(Vec::new());
+/// let entries = vec![("Button/Button.tsx".to_string(), b"export {}".to_vec())];
+/// write_archive(&mut buffer, ArchiveFormat::Zip, &entries)?;
+/// assert!(buffer.get_ref().starts_with(b"PK"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn write_archive<W: Write + Seek>(
+    writer: W,
+    format: ArchiveFormat,
+    entries: &[(String, Vec<u8>)],
+) -> Result<()> {
+    match format {
+        ArchiveFormat::Zip => write_zip(writer, entries),
+        ArchiveFormat::Tar => write_tar(writer, entries).map(|_| ()),
+        ArchiveFormat::TarGz => {
+            let encoder = write_tar(GzEncoder::new(writer, Compression::default()), entries)?;
+            encoder.finish()?;
+            Ok(())
+        }
+    }
+}
+
+fn write_zip<W: Write + Seek>(writer: W, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    for (name, contents) in entries {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(contents)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, entries: &[(String, Vec<u8>)]) -> Result<W> {
+    let mut tar = tar::Builder::new(writer);
+
+    for (name, contents) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, name, contents.as_slice())?;
+    }
+
+    Ok(tar.into_inner()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    async fn sample(format: ArchiveFormat) -> ArchiveFileSystem {
+        let fs = ArchiveFileSystem::new(PathBuf::from("out"), format);
+        fs.write(Path::new("out/Button/Button.tsx"), b"export {}")
+            .await
+            .unwrap();
+        fs.write(
+            Path::new("out/Button/index.ts"),
+            b"export * from './Button';",
+        )
+        .await
+        .unwrap();
+        fs
+    }
+
+    #[tokio::test]
+    async fn test_finish_writes_zip() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("scaffold.zip");
+
+        let fs = sample(ArchiveFormat::Zip).await;
+        assert_eq!(fs.finish(&archive).unwrap(), 2);
+
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+        let mut content = String::new();
+        zip.by_name("Button/Button.tsx")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "export {}");
+    }
+
+    #[tokio::test]
+    async fn test_finish_writes_tar_gz() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("scaffold.tar.gz");
+
+        let fs = sample(ArchiveFormat::TarGz).await;
+        fs.finish(&archive).unwrap();
+
+        let decoder = flate2::read::GzDecoder::new(std::fs::File::open(&archive).unwrap());
+        let mut tar = tar::Archive::new(decoder);
+        let names: Vec<String> = tar
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, vec!["Button/Button.tsx", "Button/index.ts"]);
+    }
+
+    #[test]
+    fn test_for_archive_rejects_unknown_format() {
+        let err = ArchiveFileSystem::for_archive(PathBuf::from("out"), Path::new("out.7z"))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("Unsupported archive format"));
+    }
+}
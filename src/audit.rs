@@ -0,0 +1,258 @@
+//! Write audit trail (`--audit-log`).
+//!
+//! [`AuditedFileSystem`] wraps the engine's output backend and appends one
+//! JSON line to an [`AuditLog`] for every change it makes on disk: the
+//! action, the path, the size and hash of what was written, when, and the
+//! arguments the CLI was invoked with. The log is only ever appended to, so
+//! several runs can share one file.
+//!
+//! Only generation writes through that backend, so the log covers the
+//! generated output and its rollbacks. The CLI's own bookkeeping (the usage
+//! history, the `--explain` file, the temporary tsconfig of `--verify`) and
+//! subcommands such as `upgrade` and `sync` write directly and aren't
+//! recorded.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::file_system::{FileSystem, FsFuture};
+use crate::template_engine::stamp::content_hash;
+
+/// One file system change
+#[derive(Debug, Serialize)]
+pub struct AuditRecord<'a> {
+    /// RFC 3339 time of the change
+    pub timestamp: String,
+    /// `write`, `move`, `copy`, `chmod`, `create_dir`, `remove` or `remove_dir`
+    pub action: &'a str,
+    pub path: &'a Path,
+    /// Size of the contents written, for actions that write
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<usize>,
+    /// Hash of the contents written (64-bit FNV-1a, as in stamps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Unix mode set by `chmod`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<u32>,
+    /// Arguments of the invocation that made the change
+    pub args: &'a [String],
+}
+
+/// Append-only JSONL log of file system changes
+pub struct AuditLog {
+    file: Mutex<File>,
+    args: Vec<String>,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it if needed; records carry `args`
+    pub fn open(path: &Path, args: Vec<String>) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Could not create parent directory: {}", parent.display())
+            })?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Could not open audit log: {}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+            args,
+        })
+    }
+
+    /// Append a record of `action` on `path`, with the `contents` it wrote
+    pub fn record(&self, action: &str, path: &Path, contents: Option<&[u8]>) -> Result<()> {
+        self.append(AuditRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action,
+            path,
+            bytes: contents.map(<[u8]>::len),
+            hash: contents.map(content_hash),
+            mode: None,
+            args: &self.args,
+        })
+    }
+
+    /// Append `record` as one line
+    fn append(&self, record: AuditRecord<'_>) -> Result<()> {
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Audit log lock was poisoned"))?;
+        // One write per record, so appends from other runs don't interleave
+        file.write_all(line.as_bytes())
+            .context("Could not write to the audit log")
+    }
+}
+
+/// Backend that records every change made through `inner` in an [`AuditLog`]
+///
+/// Changes are recorded once they succeeded; a failed record fails the
+/// change, so nothing is written without a trace.
+pub struct AuditedFileSystem {
+    inner: Arc<dyn FileSystem>,
+    log: Arc<AuditLog>,
+}
+
+impl AuditedFileSystem {
+    pub fn new(inner: Arc<dyn FileSystem>, log: Arc<AuditLog>) -> Self {
+        Self { inner, log }
+    }
+}
+
+impl FileSystem for AuditedFileSystem {
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let existed = self.inner.exists(path);
+            self.inner.create_dir_all(path).await?;
+            if existed {
+                return Ok(());
+            }
+            self.log.record("create_dir", path, None)
+        })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.inner.write(path, contents).await?;
+            self.log.record("write", path, Some(contents))
+        })
+    }
+
+    fn copy_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let contents = read_source(source).await?;
+            self.inner.copy_file(source, path).await?;
+            self.log.record("copy", path, Some(&contents))
+        })
+    }
+
+    fn move_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let contents = read_source(source).await?;
+            self.inner.move_file(source, path).await?;
+            self.log.record("move", path, Some(&contents))
+        })
+    }
+
+    fn set_permissions<'a>(&'a self, path: &'a Path, mode: u32) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.inner.set_permissions(path, mode).await?;
+            self.log.append(AuditRecord {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                action: "chmod",
+                path,
+                bytes: None,
+                hash: None,
+                mode: Some(mode),
+                args: &self.log.args,
+            })
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.inner.exists(path)
+    }
+
+    fn read_existing<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Option<Vec<u8>>> {
+        self.inner.read_existing(path)
+    }
+
+    fn remove_file<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            if !self.inner.exists(path) {
+                return Ok(());
+            }
+            self.inner.remove_file(path).await?;
+            self.log.record("remove", path, None)
+        })
+    }
+
+    fn remove_empty_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.inner.remove_empty_dir(path).await?;
+            if self.inner.exists(path) {
+                return Ok(());
+            }
+            self.log.record("remove_dir", path, None)
+        })
+    }
+}
+
+/// Contents of a local source file, for its size and hash
+async fn read_source(source: &Path) -> Result<Vec<u8>> {
+    tokio::fs::read(source)
+        .await
+        .with_context(|| format!("Could not read file: {}", source.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::TokioFileSystem;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Action and path of each record in `log`
+    fn recorded_paths(log: &Path) -> Vec<(String, PathBuf)> {
+        std::fs::read_to_string(log)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                (
+                    record["action"].as_str().unwrap().to_string(),
+                    PathBuf::from(record["path"].as_str().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_audited_file_system_appends_each_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.jsonl");
+        let log = Arc::new(AuditLog::open(&log_path, vec!["cli-frontend".to_string()]).unwrap());
+        let fs = AuditedFileSystem::new(Arc::new(TokioFileSystem), log);
+
+        let dir = temp_dir.path().join("Button");
+        let file = dir.join("Button.tsx");
+        fs.create_dir_all(&dir).await.unwrap();
+        fs.create_dir_all(&dir).await.unwrap();
+        fs.write(&file, b"export {};").await.unwrap();
+        fs.remove_file(&file).await.unwrap();
+        fs.remove_file(&file).await.unwrap();
+        fs.remove_empty_dir(&dir).await.unwrap();
+
+        assert_eq!(
+            recorded_paths(&log_path),
+            vec![
+                ("create_dir".to_string(), dir.clone()),
+                ("write".to_string(), file.clone()),
+                ("remove".to_string(), file),
+                ("remove_dir".to_string(), dir),
+            ]
+        );
+        let first_write: serde_json::Value = serde_json::from_str(
+            std::fs::read_to_string(&log_path)
+                .unwrap()
+                .lines()
+                .nth(1)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first_write["bytes"], 10);
+        assert_eq!(first_write["hash"], content_hash(b"export {};"));
+        assert_eq!(first_write["args"], serde_json::json!(["cli-frontend"]));
+    }
+}
@@ -0,0 +1,196 @@
+//! First-run setup of an empty templates directory (`cli-frontend bootstrap`).
+//!
+//! The built-in templates are embedded in the binary at build time, so a
+//! fresh install can fill `templates_dir` without network access. Teams can
+//! set `starter_templates` in the config to a git repository of their own
+//! templates, which is offered as well and cloned with `git`.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+include!(concat!(env!("OUT_DIR"), "/default_templates.rs"));
+
+/// Whether `templates_dir` holds at least one template folder
+///
+/// Hidden directories and `architectures` don't count.
+pub fn has_templates(templates_dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(templates_dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        entry.path().is_dir() && !name.starts_with('.') && name != "architectures"
+    })
+}
+
+/// Write the built-in templates into `templates_dir`, returning how many
+/// files were written
+///
+/// # Errors
+///
+/// Fails when `templates_dir` already has templates or a file can't be
+/// written.
+pub fn install_default_templates(templates_dir: &Path) -> Result<usize> {
+    ensure_no_templates(templates_dir)?;
+    for (relative, content) in DEFAULT_TEMPLATES {
+        let path = templates_dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Could not create directory: {}", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Could not write file: {}", path.display()))?;
+    }
+    Ok(DEFAULT_TEMPLATES.len())
+}
+
+/// Clone the git repository `repo` into `templates_dir`
+///
+/// Only the latest commit is fetched. The clone goes to a hidden directory
+/// inside `templates_dir` first and is then moved up, so directories that
+/// don't count as templates (`architectures`) can already be there.
+///
+/// # Errors
+///
+/// Fails when `templates_dir` already has templates, `git` can't be run,
+/// the clone fails or the repository has entries `templates_dir` already
+/// has; then nothing is moved.
+pub fn clone_starter_templates(repo: &str, templates_dir: &Path) -> Result<()> {
+    ensure_no_templates(templates_dir)?;
+    std::fs::create_dir_all(templates_dir)
+        .with_context(|| format!("Could not create directory: {}", templates_dir.display()))?;
+    let clone = tempfile::Builder::new()
+        .prefix(".cli-frontend-clone-")
+        .tempdir_in(templates_dir)
+        .context("Could not create a directory to clone into")?;
+
+    let status = Command::new("git")
+        .args(["clone", "--depth", "1", "--", repo])
+        .arg(clone.path())
+        .status()
+        .context("Could not run git; is it installed?")?;
+    if !status.success() {
+        anyhow::bail!("git clone {} failed ({})", repo, status);
+    }
+
+    // The clone's history stays behind and goes with the temporary directory
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(clone.path())? {
+        let name = entry?.file_name();
+        if name != ".git" {
+            entries.push(name);
+        }
+    }
+    // Nothing is moved unless everything can be, so a conflict leaves templates_dir as it was
+    let conflicts: Vec<String> = entries
+        .iter()
+        .map(|name| templates_dir.join(name))
+        .filter(|target| target.exists())
+        .map(|target| target.display().to_string())
+        .collect();
+    if !conflicts.is_empty() {
+        anyhow::bail!(
+            "{} already exist; move them away and run bootstrap again",
+            conflicts.join(", ")
+        );
+    }
+
+    for name in entries {
+        let target = templates_dir.join(&name);
+        std::fs::rename(clone.path().join(&name), &target)
+            .with_context(|| format!("Could not move cloned files to {}", target.display()))?;
+    }
+    Ok(())
+}
+
+fn ensure_no_templates(templates_dir: &Path) -> Result<()> {
+    if has_templates(templates_dir) {
+        anyhow::bail!(
+            "{} already has templates; bootstrap only fills an empty templates_dir",
+            templates_dir.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_default_templates_fills_an_empty_dir_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("templates");
+        assert!(!has_templates(&templates_dir));
+
+        let written = install_default_templates(&templates_dir).unwrap();
+        assert_eq!(written, DEFAULT_TEMPLATES.len());
+        assert!(has_templates(&templates_dir));
+        assert!(templates_dir.join("component").join(".conf").is_file());
+
+        assert!(install_default_templates(&templates_dir).is_err());
+    }
+
+    #[test]
+    fn test_clone_starter_templates_next_to_architectures() {
+        let git = |dir: &Path, args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = temp_dir.path().join("starter");
+        std::fs::create_dir_all(repo.join("card")).unwrap();
+        std::fs::write(repo.join("card").join("$FILE_NAME.tsx"), "card").unwrap();
+        if !git(&repo, &["init", "--quiet"])
+            || !git(&repo, &["add", "."])
+            || !git(&repo, &["commit", "--quiet", "-m", "Add card"])
+        {
+            eprintln!("git is not available, skipping");
+            return;
+        }
+
+        let templates_dir = temp_dir.path().join("templates");
+        std::fs::create_dir_all(templates_dir.join("architectures")).unwrap();
+        let repo_url = format!("file://{}", repo.display());
+        clone_starter_templates(&repo_url, &templates_dir).unwrap();
+
+        assert!(templates_dir.join("card").join("$FILE_NAME.tsx").is_file());
+        assert!(templates_dir.join("architectures").is_dir());
+        assert!(!templates_dir.join(".git").exists());
+        let leftovers = std::fs::read_dir(&templates_dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(".cli-frontend-clone-")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+
+        assert!(
+            clone_starter_templates("--upload-pack=touch", &temp_dir.path().join("other")).is_err()
+        );
+
+        // A repository with an entry templates_dir already has moves nothing
+        std::fs::create_dir_all(repo.join("architectures")).unwrap();
+        std::fs::write(repo.join("architectures").join("flat.json"), "{}").unwrap();
+        assert!(git(&repo, &["add", "."]) && git(&repo, &["commit", "--quiet", "-m", "Add"]));
+        let templates_dir = temp_dir.path().join("conflicting");
+        std::fs::create_dir_all(templates_dir.join("architectures")).unwrap();
+        let error = clone_starter_templates(&repo_url, &templates_dir).unwrap_err();
+        assert!(error.to_string().contains("architectures"));
+        assert!(!templates_dir.join("card").exists());
+        assert!(!templates_dir
+            .join("architectures")
+            .join("flat.json")
+            .exists());
+    }
+}
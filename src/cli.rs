@@ -1,8 +1,19 @@
-use clap::Parser;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use clap::{ArgGroup, Parser, Subcommand};
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-#[derive(Parser, Debug)]
+use crate::completion::Shell;
+use crate::config::ArchitectureConfig;
+use crate::template_engine::config::Framework;
+use crate::template_engine::import::ImportSource;
+use crate::template_engine::toml_config::is_internal_template;
+use crate::template_engine::ConflictPolicy;
+use crate::ui::{self, symbols};
+
+#[derive(Parser, Debug, Clone)]
+#[command(group = ArgGroup::new("describing").args(["describe", "describe_all"]))]
 #[command(
     name = "cli-frontend",
     version,
@@ -21,31 +32,430 @@ pub struct Args {
     #[arg(short = 'a', long = "architecture")]
     pub architecture: Option<String>,
 
+    /// Print the directory tree a feature would generate; nothing is written
+    /// unless confirmed on an interactive terminal or with --yes
+    #[arg(long = "tree", conflicts_with_all = ["stdout", "copy", "output_archive"])]
+    pub tree: bool,
+
+    /// Continue a feature run that failed with --keep-going, skipping the
+    /// structures it completed
+    #[arg(long = "resume")]
+    pub resume: bool,
+
     /// Generate files without creating a folder
-    #[arg(long = "no-folder")]
+    #[arg(long = "no-folder", global = true)]
     pub no_folder: bool,
 
+    /// Generate a standalone, publishable package: package.json, tsconfig.json and
+    /// build config in a kebab-case folder, with the template's files in src/
+    /// Example: cli-frontend DatePicker --type component --as-package -o packages
+    #[arg(long = "as-package", conflicts_with_all = ["no_folder", "stdout"])]
+    pub as_package: bool,
+
+    /// Also write a LICENSE file with the text of the configured `license`
+    /// into the package (MIT, ISC and 0BSD texts are bundled)
+    #[arg(long = "license-file", requires = "as_package")]
+    pub license_file: bool,
+
     /// Output directory for generated files (overrides config)
     #[arg(short = 'o', long = "output-dir")]
     pub output_dir: Option<PathBuf>,
 
+    /// Write generated files into an archive (.zip, .tar, .tar.gz) instead of the output directory
+    /// Example: --output-archive scaffold.zip
+    #[arg(long = "output-archive", global = true, value_name = "FILE")]
+    pub output_archive: Option<PathBuf>,
+
+    /// Append a JSON line for every generated file or folder the run writes,
+    /// moves or removes (path, size, hash, time and arguments) to FILE
+    #[arg(long = "audit-log", global = true, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+
+    /// Print the rendered file to stdout instead of writing it (single-file templates only)
+    /// Example: cli-frontend Auth --type hook --stdout > useAuth.ts
+    #[arg(
+        long = "stdout",
+        conflicts_with_all = ["output_archive", "verify", "open", "profile_run", "explain"]
+    )]
+    pub stdout: bool,
+
+    /// Copy the main rendered file to the clipboard instead of writing files,
+    /// to paste a snippet into an existing file
+    #[arg(long = "copy", conflicts_with_all = ["stdout", "output_archive", "as_package", "verify", "open", "explain"])]
+    pub copy: bool,
+
+    /// Fail when a template references an undefined variable instead of rendering it empty
+    #[arg(long = "strict", global = true)]
+    pub strict: bool,
+
+    /// Fail any template that reads an environment variable with `env`,
+    /// instead of limiting it to `env_allow`/`env_deny` from the config file
+    #[arg(long = "no-env-helpers", global = true)]
+    pub no_env_helpers: bool,
+
+    /// Environment to generate for, replacing `NODE_ENV` and the template's
+    /// `environment`; selects its `[options.ENVIRONMENT]` overrides
+    /// Example: --env production
+    #[arg(long = "env", global = true, value_name = "ENVIRONMENT")]
+    pub env: Option<String>,
+
+    /// What to do with existing files that would change: overwrite, skip, prompt
+    /// (shows a diff; the default on an interactive terminal) or merge (three-way
+    /// merge with the content recorded when the file was generated)
+    #[arg(long = "on-conflict", global = true, value_name = "POLICY")]
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// Skip files that fail to render or write and keep the rest, instead of
+    /// rolling back everything the run wrote
+    #[arg(long = "keep-going", global = true)]
+    pub keep_going: bool,
+
+    /// Render every file before failing and list each file that failed with
+    /// its error, instead of stopping at the first one
+    #[arg(long = "all-errors", global = true)]
+    pub all_errors: bool,
+
+    /// Give up and roll back a generation still running after SECONDS,
+    /// counted from the start of the command
+    #[arg(long = "timeout", global = true, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Mark generated files with a comment naming their template and hashes,
+    /// so `drift` can find edited or outdated files (also `stamp_files=true`)
+    #[arg(long = "stamp", global = true)]
+    pub stamp: bool,
+
+    /// Generate without asking to confirm the pre-flight summary on an
+    /// interactive terminal
+    #[arg(short = 'y', long = "yes", global = true)]
+    pub yes: bool,
+
+    /// Print only errors and warnings, plus output that was asked for
+    /// (`--describe`, `--list`, `search`)
+    #[arg(short = 'q', long = "quiet", global = true)]
+    pub quiet: bool,
+
+    /// Print without emoji (also when `NO_COLOR` is set)
+    #[arg(long = "no-emoji", global = true)]
+    pub no_emoji: bool,
+
+    /// Type-check the generated files with `tsc --noEmit` (or `verify_command` from
+    /// the config) and fail if they have errors
+    #[arg(long = "verify", global = true, conflicts_with = "output_archive")]
+    pub verify: bool,
+
+    /// Open the generated folder (or main file) in your editor afterwards:
+    /// `open_after_generate` from the config, else $VISUAL or $EDITOR
+    #[arg(long = "open", global = true, conflicts_with = "output_archive")]
+    pub open: bool,
+
+    /// List generated files matched by .gitignore too
+    #[arg(long = "show-all", global = true)]
+    pub show_all: bool,
+
+    /// Print a per-phase timing breakdown after generating
+    #[arg(long = "profile-run", global = true)]
+    pub profile_run: bool,
+
+    /// Record every helper call (helper, inputs, output) per file and print the
+    /// trace after generating, or save it as JSON with --explain=FILE
+    #[arg(
+        long = "explain",
+        global = true,
+        value_name = "FILE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "-"
+    )]
+    pub explain: Option<PathBuf>,
+
     /// Path to custom configuration file
-    #[arg(short = 'c', long = "config")]
+    #[arg(short = 'c', long = "config", global = true)]
     pub config: Option<PathBuf>,
 
     /// Show detailed help with templates and architectures
     #[arg(long = "list")]
     pub list: bool,
 
+    /// With --list, show only templates tagged TAG in `[metadata] tags`
+    /// Example: --list --tag form
+    #[arg(long = "tag", value_name = "TAG", requires = "list")]
+    pub tag: Option<String>,
+
+    /// List, offer and generate templates marked `internal=true`, which are
+    /// otherwise only used by architectures
+    #[arg(long = "include-internal", global = true)]
+    pub include_internal: bool,
+
     /// Template variables in KEY=VALUE format (can be used multiple times)
     /// Example: --var style=scss --var with_tests=false
     #[arg(long = "var", value_name = "KEY=VALUE")]
     pub vars: Vec<String>,
 
+    /// Seed generation from a Figma component export: variants fill matching
+    /// template variables and all properties are available as `figma.props`
+    /// Example: --figma-json button.json --type component
+    #[arg(long = "figma-json", global = true, value_name = "FILE")]
+    pub figma_json: Option<PathBuf>,
+
+    /// W3C design tokens file exposed to templates as `tokens` (e.g. for SCSS variables)
+    /// Example: --tokens tokens.json
+    #[arg(long = "tokens", global = true, value_name = "FILE")]
+    pub tokens: Option<PathBuf>,
+
     /// Show detailed information about a template including variables and file generation rules
     /// Example: --describe component
     #[arg(long = "describe", value_name = "TEMPLATE")]
     pub describe: Option<String>,
+
+    /// Show a condensed summary of every template in the pack
+    #[arg(long = "describe-all", conflicts_with = "describe")]
+    pub describe_all: bool,
+
+    /// Print --describe or --describe-all output as JSON
+    #[arg(long = "json", requires = "describing")]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Auxiliary commands that operate on the template pack instead of generating code
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Generate several features with one architecture in a single run
+    /// Example: cli-frontend feature Auth Billing Orders --architecture clean-architecture
+    Feature {
+        /// Names of the features to generate
+        #[arg(required = true)]
+        names: Vec<String>,
+
+        /// Architecture pattern shared by the features
+        #[arg(short = 'a', long = "architecture")]
+        architecture: Option<String>,
+
+        /// Print the directory tree the features would generate
+        #[arg(long = "tree")]
+        tree: bool,
+
+        /// Continue the run that failed, skipping the structures it completed
+        #[arg(long = "resume")]
+        resume: bool,
+
+        /// Output directory for generated files (overrides config)
+        #[arg(short = 'o', long = "output-dir")]
+        output_dir: Option<PathBuf>,
+
+        /// Template variables in KEY=VALUE format (can be used multiple times)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+
+    /// Generate a Markdown catalog (TEMPLATES.md) of all templates and architectures
+    Docs {
+        /// Output file for the catalog
+        #[arg(short = 'o', long = "output", default_value = "TEMPLATES.md")]
+        output: PathBuf,
+    },
+
+    /// Print a JSON manifest of every template (variables, [files] filters) and architecture
+    ExportManifest {
+        /// Write the manifest to this file instead of stdout
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+    },
+
+    /// Serve a JSON API (list templates, describe, generate-to-zip) over HTTP
+    Serve {
+        /// Port to listen on
+        #[arg(short = 'p', long = "port", default_value_t = 8080)]
+        port: u16,
+
+        /// Address to bind to
+        #[arg(long = "host", default_value = "127.0.0.1")]
+        host: String,
+    },
+
+    /// Find templates whose name, title, description or tags match QUERY
+    Search {
+        /// Words that must all appear (case-insensitive); empty lists every template
+        #[arg(default_value = "")]
+        query: String,
+
+        /// Only templates with this tag
+        #[arg(long = "tag", value_name = "TAG")]
+        tag: Option<String>,
+    },
+
+    /// Check a template for unused or undeclared variables and broken [files] filters
+    LintTemplate {
+        /// Template to lint
+        name: String,
+    },
+
+    /// Manage a template's files
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommand,
+    },
+
+    /// Render a template for every combination of its boolean and enum variables
+    Matrix {
+        /// Template to render
+        template: String,
+
+        /// Directory for the combination folders (a temporary directory by default)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+
+        /// Name to generate with
+        #[arg(long = "name", default_value = "Example")]
+        name: String,
+
+        /// Most combinations to render
+        #[arg(long = "limit", default_value_t = 64)]
+        limit: usize,
+    },
+
+    /// Compare frozen renders of a template with a committed golden directory
+    VerifyGolden {
+        /// Template to render
+        template: String,
+
+        /// Golden directory, laid out like `matrix` output
+        golden_dir: PathBuf,
+
+        /// Name to generate with
+        #[arg(long = "name", default_value = "Example")]
+        name: String,
+
+        /// Most combinations to render
+        #[arg(long = "limit", default_value_t = 64)]
+        limit: usize,
+
+        /// Write the current renders to the golden directory instead of comparing
+        #[arg(long = "update")]
+        update: bool,
+    },
+
+    /// Show where the config file, templates and architectures are loaded from
+    Doctor,
+
+    /// Fill an empty templates_dir with the built-in templates
+    /// Example: cli-frontend bootstrap --from https://github.com/acme/templates.git
+    Bootstrap {
+        /// Clone this git repository instead of installing the built-in templates
+        #[arg(long = "from", value_name = "REPO")]
+        from: Option<String>,
+    },
+
+    /// Print a shell completion script that completes templates and --var values
+    /// Example: source <(cli-frontend completions bash)
+    Completions {
+        /// Shell to print the script for: bash, zsh or fish
+        shell: Shell,
+    },
+
+    /// Complete the command line up to the cursor (called by the completion scripts)
+    #[command(hide = true)]
+    Complete {
+        #[arg(long = "shell", default_value = "bash")]
+        shell: Shell,
+
+        /// Command line up to the cursor, program name included
+        #[arg(default_value = "", allow_hyphen_values = true)]
+        line: String,
+    },
+
+    /// Convert Plop, Hygen or Yeoman generators into templates
+    Import {
+        /// Generator format: plop, hygen or yeoman
+        source: ImportSource,
+
+        /// Directory with the plopfile, Hygen's _templates directory, or the Yeoman generator package
+        dir: PathBuf,
+
+        /// Directory to write the templates to (the templates directory by default)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+
+        /// Replace templates that already exist
+        #[arg(long = "force")]
+        force: bool,
+    },
+
+    /// Generate every component described by the YAML specs in a directory
+    SyncComponents {
+        /// Directory with one YAML spec (name, type, vars) per component
+        spec_dir: PathBuf,
+
+        /// Regenerate every component, even if its spec is unchanged
+        #[arg(long = "force")]
+        force: bool,
+    },
+
+    /// Report stamped files that were edited by hand or whose template changed
+    Drift {
+        /// Directory to scan (the output directory by default)
+        path: Option<PathBuf>,
+
+        /// Print the report as JSON
+        #[arg(long = "json")]
+        json: bool,
+    },
+
+    /// Regenerate stamped files whose template changed, across a workspace
+    Upgrade {
+        /// Upgrade every stamped file (the only mode for now)
+        #[arg(long = "all", required = true)]
+        all: bool,
+
+        /// Directory to scan (the enclosing workspace root by default)
+        path: Option<PathBuf>,
+
+        /// Print the patch set instead of writing the files
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Save the patch set, grouped by package, to this file
+        #[arg(long = "report", value_name = "FILE")]
+        report: Option<PathBuf>,
+    },
+
+    /// Evaluate Handlebars expressions interactively against a template's data
+    Repl {
+        /// Template whose data to load
+        template: String,
+
+        /// Name to load the data for
+        #[arg(long = "name", default_value = "Example")]
+        name: String,
+
+        /// Template variables in KEY=VALUE format (can be used multiple times)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+
+    /// Serve JSON-RPC for editor integrations (Content-Length framed, like LSP)
+    LspLike {
+        /// Communicate over stdin/stdout (the only supported transport)
+        #[arg(long = "stdio")]
+        stdio: bool,
+    },
+}
+
+/// Subcommands of `cli-frontend template`
+#[derive(Subcommand, Debug, Clone)]
+pub enum TemplateCommand {
+    /// Convert a template's .conf into template.toml
+    MigrateConf {
+        /// Template to migrate
+        template: String,
+
+        /// Replace an existing template.toml
+        #[arg(long = "force")]
+        force: bool,
+    },
 }
 
 impl Args {
@@ -70,8 +480,12 @@ impl Args {
     }
 
     /// Discovers available templates from the templates directory
+    ///
+    /// Framework namespaces are expanded, so `templates/rn/component` is
+    /// listed as `rn/component`.
     pub fn discover_templates(templates_dir: &PathBuf) -> Vec<String> {
-        let mut templates = Self::discover_items(templates_dir, |entry| {
+        let mut templates = Vec::new();
+        for name in Self::discover_items(templates_dir, |entry| {
             if entry.file_type().ok()?.is_dir() {
                 let name = entry.file_name().to_str()?.to_string();
                 if !name.starts_with('.') && name != "architectures" {
@@ -79,7 +493,19 @@ impl Args {
                 }
             }
             None
-        });
+        }) {
+            if Framework::of_namespace(&templates_dir.join(&name)).is_some() {
+                let namespaced = Self::discover_templates(&templates_dir.join(&name));
+                templates.extend(
+                    namespaced
+                        .into_iter()
+                        .filter(|template| template != "feature")
+                        .map(|template| format!("{}/{}", name, template)),
+                );
+            } else {
+                templates.push(name);
+            }
+        }
 
         // Add the special "feature" type which uses architecture configurations
         templates.push("feature".to_string());
@@ -103,50 +529,111 @@ impl Args {
         })
     }
 
+    /// Why the architecture `name` can't generate features, if it can't:
+    /// its file doesn't parse or it uses templates that don't exist
+    fn architecture_problem(
+        architectures_dir: &Path,
+        templates_dir: &Path,
+        name: &str,
+    ) -> Option<String> {
+        let path = architectures_dir.join(format!("{}.json", name));
+        let architecture = match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| ArchitectureConfig::parse_json(&content))
+        {
+            Ok(architecture) => architecture,
+            Err(_) => return Some("invalid architecture file".to_string()),
+        };
+        let missing =
+            architecture.missing_templates(|template| templates_dir.join(template).is_dir());
+        (!missing.is_empty()).then(|| format!("missing templates: {}", missing.join(", ")))
+    }
+
     /// Parse --var arguments into a HashMap
     /// Example: ["style=scss", "with_tests=false"] -> {"style": "scss", "with_tests": "false"}
     pub fn parse_vars(&self) -> HashMap<String, String> {
-        let mut vars = HashMap::new();
-
-        for var_arg in &self.vars {
-            if let Some((key, value)) = var_arg.split_once('=') {
-                vars.insert(key.trim().to_string(), value.trim().to_string());
-            } else {
-                eprintln!(
-                    "Warning: Invalid --var format '{}', expected KEY=VALUE",
-                    var_arg
-                );
-            }
-        }
-
-        vars
+        parse_var_args(&self.vars)
     }
 
-    /// Print simple list of available templates and architectures
-    pub fn print_simple_list(templates_dir: &PathBuf, architectures_dir: &PathBuf) {
-        let templates = Self::discover_templates(templates_dir);
+    /// Write a simple list of available templates and architectures to `out`
+    ///
+    /// The templates of each pack follow as `pack:template`. Internal
+    /// templates are left out unless `include_internal` is set.
+    pub fn write_simple_list(
+        out: &mut dyn Write,
+        templates_dir: &PathBuf,
+        packs: &BTreeMap<String, PathBuf>,
+        architectures_dir: &PathBuf,
+        include_internal: bool,
+    ) -> std::io::Result<()> {
+        let listed = |dir: &PathBuf, template: &str| {
+            include_internal || !is_internal_template(&dir.join(template))
+        };
+        let mut templates: Vec<String> = Self::discover_templates(templates_dir)
+            .into_iter()
+            .filter(|template| listed(templates_dir, template))
+            .collect();
+        for (pack, dir) in packs {
+            templates.extend(
+                Self::discover_templates(dir)
+                    .into_iter()
+                    .filter(|template| template != "feature" && listed(dir, template))
+                    .map(|template| format!("{}:{}", pack, template)),
+            );
+        }
         let architectures = Self::discover_architectures(architectures_dir);
 
-        println!("📋 Available Templates:");
+        ui::show_to!(out, "{} Available Templates:", symbols::LIST)?;
         if templates.is_empty() {
-            println!("  No templates found");
+            ui::show_to!(out, "  No templates found")?;
         } else {
             for template in &templates {
-                println!("  • {}", template);
+                ui::show_to!(out, "  {} {}", symbols::BULLET, template)?;
             }
         }
 
-        println!();
-        println!("🏗️  Available Architectures:");
+        ui::show_to!(out)?;
+        ui::show_to!(out, "{}  Available Architectures:", symbols::ARCHITECTURE)?;
         if architectures.is_empty() {
-            println!("  No architectures found");
+            ui::show_to!(out, "  No architectures found")?;
         } else {
             for arch in &architectures {
-                println!("  • {}", arch);
+                match Self::architecture_problem(architectures_dir, templates_dir, arch) {
+                    None => ui::show_to!(out, "  {} {}", symbols::BULLET, arch)?,
+                    Some(problem) => ui::show_to!(
+                        out,
+                        "  {} {} {}",
+                        symbols::BULLET,
+                        arch,
+                        format!("({})", problem).yellow()
+                    )?,
+                }
             }
         }
 
-        println!();
-        println!("💡 Usage: cli-frontend <name> --type <template> [--architecture <arch>]");
+        ui::show_to!(out)?;
+        ui::show_to!(
+            out,
+            "{} Usage: cli-frontend <name> --type <template> [--architecture <arch>]",
+            symbols::HINT
+        )
+    }
+}
+
+/// Parse `KEY=VALUE` arguments into a map, warning about malformed ones
+pub fn parse_var_args(args: &[String]) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+
+    for var_arg in args {
+        if let Some((key, value)) = var_arg.split_once('=') {
+            vars.insert(key.trim().to_string(), value.trim().to_string());
+        } else {
+            ui::warning!(
+                "Warning: Invalid --var format '{}', expected KEY=VALUE",
+                var_arg
+            );
+        }
     }
+
+    vars
 }
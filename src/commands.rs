@@ -0,0 +1,1175 @@
+//! Handlers for auxiliary subcommands
+//!
+//! Each subcommand operates on the template pack (documentation, inspection, ...)
+//! rather than generating code for a single name, so they are dispatched here
+//! to keep `main.rs` focused on the generation flow.
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use walkdir::WalkDir;
+
+use crate::bootstrap::{clone_starter_templates, install_default_templates};
+use crate::cli::{parse_var_args, Args, Command, TemplateCommand};
+use crate::completion::{self, Shell};
+use crate::config::Config;
+use crate::error::CliError;
+use crate::template_engine::golden::{compare_golden, Drift};
+use crate::template_engine::import::{
+    convert_hygen_action, convert_plop_generator, convert_yeoman_generator, parse_plopfile,
+    ImportSource, ImportedTemplate,
+};
+use crate::template_engine::lint::LintKind;
+use crate::template_engine::matrix::Combination;
+use crate::template_engine::sync::{read_specs, SyncState, SYNC_STATE_FILE};
+use crate::template_engine::toml_config::TEMPLATE_TOML_FILE;
+use crate::template_engine::upgrade::{by_package, package_label, write_patch_set, UpgradeOutcome};
+use crate::template_engine::workspace::Workspace;
+use crate::template_engine::{DriftedFile, GeneratedFile, TemplateEngine, TemplateStatus};
+use crate::ui::{self, symbols, t};
+use crate::{repl, rpc, server};
+
+/// Run an auxiliary subcommand
+///
+/// `config_path` is the `--config` argument, if any.
+pub async fn run(command: &Command, config: &Config, config_path: &Option<PathBuf>) -> Result<()> {
+    match command {
+        Command::Feature { .. } => {
+            anyhow::bail!("The feature command runs through the generation flow")
+        }
+        Command::Docs { output } => generate_docs(config, output).await,
+        Command::ExportManifest { output } => export_manifest(config, output.as_deref()).await,
+        Command::Serve { port, host } => serve(config, host, *port).await,
+        Command::Search { query, tag } => search_templates(config, query, tag.as_deref()).await,
+        Command::LintTemplate { name } => lint_template(config, name).await,
+        Command::Template {
+            command: TemplateCommand::MigrateConf { template, force },
+        } => migrate_conf(config, template, *force).await,
+        Command::Matrix {
+            template,
+            output,
+            name,
+            limit,
+        } => render_matrix(config, template, output.as_deref(), name, *limit).await,
+        Command::VerifyGolden {
+            template,
+            golden_dir,
+            name,
+            limit,
+            update,
+        } => verify_golden(config, template, golden_dir, name, *limit, *update).await,
+        Command::Doctor => doctor(config, config_path),
+        Command::Bootstrap { from } => bootstrap_templates(config, from.as_deref()),
+        Command::Completions { shell } => {
+            ui::data!("{}", completion::script(*shell).trim_end());
+            Ok(())
+        }
+        Command::Complete { shell, line } => complete_line(line, *shell, config).await,
+        Command::Import {
+            source,
+            dir,
+            output,
+            force,
+        } => import_generators(config, *source, dir, output.as_deref(), *force).await,
+        Command::SyncComponents { spec_dir, force } => {
+            sync_components(config, spec_dir, *force).await
+        }
+        Command::Drift { path, json } => drift(config, path.as_deref(), *json),
+        Command::Upgrade {
+            all: _,
+            path,
+            dry_run,
+            report,
+        } => upgrade(config, path.as_deref(), *dry_run, report.as_deref()).await,
+        Command::Repl {
+            template,
+            name,
+            vars,
+        } => repl(config, template, name, vars).await,
+        Command::LspLike { stdio } => serve_rpc(config, *stdio).await,
+    }
+}
+
+/// Write the template catalog to `output`
+async fn generate_docs(config: &Config, output: &Path) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let catalog = engine.generate_docs(config).await?;
+
+    fs::write(output, catalog)
+        .await
+        .with_context(|| format!("Could not write catalog: {}", output.display()))?;
+
+    ui::status!(
+        "{} Template catalog written to {}",
+        symbols::DOCS.as_str().bold(),
+        output.display().to_string().green()
+    );
+
+    Ok(())
+}
+
+/// Print the pack manifest, or write it to `output`
+async fn export_manifest(config: &Config, output: Option<&Path>) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let manifest = serde_json::to_string_pretty(&engine.pack_manifest(config).await?)?;
+
+    let Some(output) = output else {
+        ui::data!("{}", manifest);
+        return Ok(());
+    };
+    fs::write(output, format!("{}\n", manifest))
+        .await
+        .with_context(|| format!("Could not write manifest: {}", output.display()))?;
+    ui::status!(
+        "{} Template manifest written to {}",
+        symbols::DOCS.as_str().bold(),
+        output.display().to_string().green()
+    );
+
+    Ok(())
+}
+
+/// Print a summary of the templates matching `query` and `tag`
+///
+/// Also backs `--list --tag`, which searches with an empty query.
+pub async fn search_templates(config: &Config, query: &str, tag: Option<&str>) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    if engine.describe_matching_templates(query, tag).await? == 0 {
+        let mut criteria = Vec::new();
+        if !query.trim().is_empty() {
+            criteria.push(format!("'{}'", query.trim()));
+        }
+        if let Some(tag) = tag {
+            criteria.push(format!("tag '{}'", tag.trim()));
+        }
+        match criteria.as_slice() {
+            [] => ui::show!("{} No templates found", symbols::SEARCH.as_str().bold()),
+            _ => ui::show!(
+                "{} No templates match {}",
+                symbols::SEARCH.as_str().bold(),
+                criteria.join(" with ")
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Print lint findings for a template, failing when there are any
+async fn lint_template(config: &Config, name: &str) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let findings = engine.lint_template(name).await?;
+
+    if findings.is_empty() {
+        ui::status!(
+            "{} Template '{}' has no lint issues",
+            symbols::SUCCESS.as_str().green(),
+            name.bold()
+        );
+        return Ok(());
+    }
+
+    ui::show!(
+        "{} Template '{}':",
+        symbols::SEARCH.as_str().bold(),
+        name.bold()
+    );
+    for finding in &findings {
+        let label = match finding.kind {
+            LintKind::UnusedVariable => "unused variable".yellow(),
+            LintKind::UndeclaredVariable => "undeclared variable".red(),
+            LintKind::MissingFilterFile => "missing file".red(),
+            LintKind::InvalidFilterCondition => "invalid condition".red(),
+        };
+        ui::show!(
+            "  {} [{}] {}",
+            finding.location.dimmed(),
+            label,
+            finding.message
+        );
+    }
+
+    anyhow::bail!("{} lint issue(s) found in '{}'", findings.len(), name)
+}
+
+/// Write `template.toml` next to a template's `.conf`
+async fn migrate_conf(config: &Config, template: &str, force: bool) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let toml = engine.migrate_conf(template).await?;
+
+    let target = config
+        .templates_dir()
+        .join(template)
+        .join(TEMPLATE_TOML_FILE);
+    if target.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (use --force to replace it)",
+            target.display()
+        );
+    }
+    fs::write(&target, toml)
+        .await
+        .with_context(|| format!("Could not write {}", target.display()))?;
+
+    ui::status!(
+        "{} Migrated '{}' to {}",
+        symbols::SUCCESS.as_str().green(),
+        template.bold(),
+        target.display().to_string().green()
+    );
+    ui::status!(
+        "  {} template.toml now takes precedence; delete .conf once you have checked it",
+        symbols::HINT
+    );
+    Ok(())
+}
+
+/// Render every variable combination of a template into its own folder
+///
+/// Combinations the template's rules or required variables don't allow are
+/// listed as skipped; any other render error fails the command once every
+/// combination has been tried.
+async fn render_matrix(
+    config: &Config,
+    template: &str,
+    output: Option<&Path>,
+    name: &str,
+    limit: usize,
+) -> Result<()> {
+    let root = match output {
+        Some(output) => {
+            if let Ok(mut entries) = fs::read_dir(output).await {
+                if entries.next_entry().await?.is_some() {
+                    anyhow::bail!("Matrix output directory is not empty: {}", output.display());
+                }
+            }
+            output.to_path_buf()
+        }
+        None => {
+            let root = std::env::temp_dir()
+                .join("cli-frontend-matrix")
+                .join(template.replace('/', "-"));
+            // Left over from an earlier run for the same template
+            if fs::try_exists(&root).await? {
+                fs::remove_dir_all(&root)
+                    .await
+                    .with_context(|| format!("Could not clear {}", root.display()))?;
+            }
+            root
+        }
+    };
+
+    let engine = TemplateEngine::new(config.templates_dir().clone(), root.clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let matrix = engine.render_matrix(name, template, limit).await?;
+
+    ui::status!(
+        "{} Rendering '{}' across {} combination(s)",
+        symbols::TEST.as_str().bold(),
+        template.bold(),
+        matrix.renders.len()
+    );
+
+    let mut failed = 0;
+    for (dir_name, files) in combination_dirs(matrix.renders) {
+        let files = match files {
+            Ok(files) => files,
+            Err(e) if is_forbidden(&e) => {
+                ui::status!(
+                    "  {} {} {}",
+                    symbols::DASH.as_str().dimmed(),
+                    dir_name,
+                    format!("skipped: {}", e).dimmed()
+                );
+                continue;
+            }
+            Err(e) => {
+                failed += 1;
+                ui::show!(
+                    "  {} {} {:#}",
+                    symbols::CROSS.as_str().red(),
+                    dir_name.bold(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        let dir: PathBuf = root.join(&dir_name);
+        for file in &files {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("Could not create {}", parent.display()))?;
+            }
+            fs::write(&path, &file.contents)
+                .await
+                .with_context(|| format!("Could not write {}", path.display()))?;
+        }
+        ui::status!(
+            "  {} {} {}",
+            symbols::CHECK.as_str().green(),
+            dir_name,
+            format!("({} file(s))", files.len()).dimmed()
+        );
+    }
+
+    ui::status!(
+        "{} Matrix written to {}",
+        symbols::DIRECTORY.as_str().bold(),
+        root.display().to_string().green()
+    );
+    if limit < matrix.total {
+        ui::status!(
+            "  {}",
+            format!(
+                "Rendered {} of {} combinations, raise --limit to render more",
+                limit, matrix.total
+            )
+            .dimmed()
+        );
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} combination(s) of '{}' failed to render",
+            failed,
+            template
+        );
+    }
+    Ok(())
+}
+
+/// Compare frozen renders of a template with its golden directory, or
+/// refresh the golden directory with `update`
+async fn verify_golden(
+    config: &Config,
+    template: &str,
+    golden_dir: &Path,
+    name: &str,
+    limit: usize,
+    update: bool,
+) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), golden_dir.to_path_buf())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone())
+        .with_frozen_output(true);
+    let matrix = engine.render_matrix(name, template, limit).await?;
+
+    let mut rendered = BTreeMap::new();
+    let mut failed = 0;
+    for (dir_name, files) in combination_dirs(matrix.renders) {
+        match files {
+            Ok(files) => {
+                for file in files {
+                    let path = file.path.to_string_lossy().replace('\\', "/");
+                    rendered.insert(format!("{}/{}", dir_name, path), file.contents);
+                }
+            }
+            Err(e) if is_forbidden(&e) => {}
+            Err(e) => {
+                failed += 1;
+                ui::show!(
+                    "  {} {} {:#}",
+                    symbols::CROSS.as_str().red(),
+                    dir_name.bold(),
+                    e
+                );
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{} combination(s) of '{}' failed to render",
+            failed,
+            template
+        );
+    }
+    if limit < matrix.total {
+        ui::status!(
+            "{}",
+            format!(
+                "Comparing {} of {} combinations, raise --limit to cover more",
+                limit, matrix.total
+            )
+            .dimmed()
+        );
+    }
+
+    if !update && !fs::try_exists(golden_dir).await? {
+        anyhow::bail!(
+            "Golden directory {} does not exist; create it with --update",
+            golden_dir.display()
+        );
+    }
+    let golden = read_tree(golden_dir)?;
+    let drift = compare_golden(&rendered, &golden);
+
+    if update {
+        for change in &drift {
+            let path = golden_dir.join(change.path());
+            match change {
+                Drift::Removed(_) => {
+                    fs::remove_file(&path)
+                        .await
+                        .with_context(|| format!("Could not remove {}", path.display()))?;
+                    remove_empty_parents(&path, golden_dir).await;
+                }
+                Drift::Added(file) | Drift::Changed { path: file, .. } => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)
+                            .await
+                            .with_context(|| format!("Could not create {}", parent.display()))?;
+                    }
+                    fs::write(&path, &rendered[file])
+                        .await
+                        .with_context(|| format!("Could not write {}", path.display()))?;
+                }
+            }
+        }
+        ui::status!(
+            "{} Updated {} golden file(s) in {}",
+            symbols::SNAPSHOT.as_str().bold(),
+            drift.len(),
+            golden_dir.display().to_string().green()
+        );
+        return Ok(());
+    }
+
+    if drift.is_empty() {
+        ui::status!(
+            "{} Template '{}' matches its golden output ({} file(s))",
+            symbols::SUCCESS.as_str().green(),
+            template.bold(),
+            rendered.len()
+        );
+        return Ok(());
+    }
+
+    ui::show!(
+        "{} Template '{}' drifted:",
+        symbols::SEARCH.as_str().bold(),
+        template.bold()
+    );
+    for change in &drift {
+        match change {
+            Drift::Added(path) => ui::show!("  {} {} {}", "+".green(), path, "(new file)".dimmed()),
+            Drift::Removed(path) => {
+                ui::show!(
+                    "  {} {} {}",
+                    "-".red(),
+                    path,
+                    "(no longer generated)".dimmed()
+                )
+            }
+            Drift::Changed { path, diff } => {
+                ui::show!("  {} {}", "~".yellow(), path);
+                if diff.is_empty() {
+                    ui::show!("      {}", "contents differ".dimmed());
+                }
+                for line in diff.lines() {
+                    ui::show!("      {}", line);
+                }
+            }
+        }
+    }
+
+    Err(CliError::GoldenDrift {
+        template: template.to_string(),
+        files: drift.len(),
+    }
+    .into())
+}
+
+/// Numbered folder name for each rendered combination, e.g. `02-with_tests-false`
+fn combination_dirs(
+    renders: Vec<(Combination, Result<Vec<GeneratedFile>>)>,
+) -> impl Iterator<Item = (String, Result<Vec<GeneratedFile>>)> {
+    renders
+        .into_iter()
+        .enumerate()
+        .map(|(index, (combination, files))| {
+            (
+                format!("{:02}-{}", index + 1, combination.dir_name()),
+                files,
+            )
+        })
+}
+
+/// Whether a combination failed because the template doesn't allow it
+/// (`[rules]` or required variables) rather than because it is broken
+fn is_forbidden(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<CliError>(),
+        Some(CliError::InvalidVariable { .. })
+    )
+}
+
+/// Every file below `root` by `/`-separated relative path
+fn read_tree(root: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+
+    for entry in WalkDir::new(root) {
+        let entry = entry.context("Error walking golden directory")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root)?;
+        let contents = std::fs::read(entry.path())
+            .with_context(|| format!("Could not read {}", entry.path().display()))?;
+        files.insert(relative.to_string_lossy().replace('\\', "/"), contents);
+    }
+
+    Ok(files)
+}
+
+/// Remove directories emptied by deleting `path`, up to `root`
+async fn remove_empty_parents(path: &Path, root: &Path) {
+    for dir in path.ancestors().skip(1) {
+        if dir == root || fs::remove_dir(dir).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Print where configuration, templates and architectures come from, and
+/// the order directories are searched in, failing when no templates are found
+/// Print the completions for `line`, one per line
+///
+/// A `--config` on the line being completed picks the templates to look at.
+async fn complete_line(line: &str, shell: Shell, config: &Config) -> Result<()> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let config_path = words
+        .windows(2)
+        .find(|pair| pair[0] == "-c" || pair[0] == "--config")
+        .map(|pair| PathBuf::from(pair[1]));
+    let loaded = match config_path {
+        Some(path) => Config::load(&Some(path)).await.ok(),
+        None => None,
+    };
+
+    for candidate in completion::complete(line, shell, loaded.as_ref().unwrap_or(config)).await {
+        ui::data!("{}", candidate);
+    }
+    Ok(())
+}
+
+/// Install the built-in templates, or clone `from`, into an empty templates_dir
+fn bootstrap_templates(config: &Config, from: Option<&str>) -> Result<()> {
+    let templates_dir = config.templates_dir();
+    let message = match from {
+        Some(repo) => {
+            clone_starter_templates(repo, templates_dir)?;
+            t!(
+                "bootstrap.cloned",
+                repo = repo,
+                dir = templates_dir.display()
+            )
+        }
+        None => {
+            let count = install_default_templates(templates_dir)?;
+            t!(
+                "bootstrap.installed",
+                count = count,
+                dir = templates_dir.display()
+            )
+        }
+    };
+    ui::show!("{} {}", symbols::SUCCESS, message);
+    Ok(())
+}
+
+fn doctor(config: &Config, config_path: &Option<PathBuf>) -> Result<()> {
+    ui::show!(
+        "{} cli-frontend {}",
+        symbols::DOCTOR.as_str().bold(),
+        env!("CARGO_PKG_VERSION")
+    );
+    ui::show!();
+
+    let config_file = Config::locate(config_path)?;
+    if config_file.exists() {
+        ui::show!("{} {}", "Config file:".bold(), config_file.display());
+    } else {
+        ui::show!(
+            "{} {} {}",
+            "Config file:".bold(),
+            config_file.display(),
+            "(not found, using defaults)".yellow()
+        );
+    }
+
+    // `feature` is always listed; it is built from the architectures
+    let templates = Args::discover_templates(config.templates_dir()).len() - 1;
+    let architectures = Args::discover_architectures(config.architectures_dir()).len();
+    print_resolved_dir(
+        "Templates",
+        config.templates_dir(),
+        &Config::find_templates_directory(),
+        templates,
+    );
+    print_resolved_dir(
+        "Architectures",
+        config.architectures_dir(),
+        &Config::find_architectures_directory(),
+        architectures,
+    );
+
+    print_search_order(
+        "Template search order",
+        config.templates_dir(),
+        &Config::templates_search_paths(),
+    );
+    print_search_order(
+        "Architecture search order",
+        config.architectures_dir(),
+        &Config::architectures_search_paths(),
+    );
+
+    if templates == 0 {
+        return Err(CliError::ConfigError {
+            message: format!("No templates found in {}", config.templates_dir().display()),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Print the directory in use and whether the config file or the search chose it
+fn print_resolved_dir(label: &str, dir: &Path, searched: &Path, count: usize) {
+    let source = if dir == searched {
+        "found by search"
+    } else {
+        "set in config file"
+    };
+    let count = if dir.is_dir() {
+        format!("{} found", count).green()
+    } else {
+        "directory missing".red()
+    };
+    ui::show!(
+        "{} {} ({}, {})",
+        format!("{}:", label).bold(),
+        dir.display(),
+        count,
+        source.dimmed()
+    );
+}
+
+/// Print `paths` in search order, marking the one in use and those that exist
+fn print_search_order(title: &str, in_use: &Path, paths: &[PathBuf]) {
+    ui::show!();
+    ui::show!("{}", format!("{}:", title).bold());
+    for path in paths {
+        if path == in_use {
+            ui::show!(
+                "  {} {} {}",
+                symbols::ARROW.as_str().green(),
+                path.display(),
+                "(in use)".green()
+            );
+        } else if path.is_dir() {
+            ui::show!("  {} {}", symbols::CHECK.as_str().yellow(), path.display());
+        } else {
+            ui::show!(
+                "  {} {}",
+                symbols::DOT.as_str().dimmed(),
+                path.display().to_string().dimmed()
+            );
+        }
+    }
+}
+
+/// Plopfile names looked for in the `import plop` directory
+const PLOPFILES: [&str; 4] = ["plopfile.js", "plopfile.cjs", "plopfile.mjs", "plopfile.ts"];
+
+/// Convert Plop, Hygen or Yeoman generators into templates, listing what needs a manual edit
+async fn import_generators(
+    config: &Config,
+    source: ImportSource,
+    dir: &Path,
+    output: Option<&Path>,
+    force: bool,
+) -> Result<()> {
+    let templates = match source {
+        ImportSource::Plop => import_plop(dir)?,
+        ImportSource::Hygen => import_hygen(dir)?,
+        ImportSource::Yeoman => import_yeoman(dir)?,
+    };
+    if templates.is_empty() {
+        anyhow::bail!("No {} generators found in {}", source, dir.display());
+    }
+
+    let output = output.unwrap_or(config.templates_dir());
+    let mut imported = 0;
+    let mut todos = 0;
+    for template in &templates {
+        let target = output.join(&template.name);
+        if target.exists() && !force {
+            ui::status!(
+                "{} Skipping '{}': {} exists (use --force to replace it)",
+                symbols::WARNING.as_str().yellow(),
+                template.name,
+                target.display()
+            );
+            continue;
+        }
+
+        for (path, content) in &template.files {
+            let file = target.join(path);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&file, content)
+                .await
+                .with_context(|| format!("Could not write {}", file.display()))?;
+        }
+        fs::create_dir_all(&target).await?;
+        fs::write(target.join(".conf"), template.conf())
+            .await
+            .with_context(|| format!("Could not write {}", target.join(".conf").display()))?;
+
+        ui::status!(
+            "{} Imported '{}' ({} files) into {}",
+            symbols::IMPORT.as_str().bold(),
+            template.name.bold(),
+            template.files.len(),
+            target.display().to_string().green()
+        );
+        for todo in &template.todos {
+            ui::status!("  {} {}", todo.location.dimmed(), todo.message);
+        }
+        imported += 1;
+        todos += template.todos.len();
+    }
+
+    ui::status!();
+    ui::status!(
+        "{} {} template(s) imported, {} TODO(s) to review",
+        symbols::SUCCESS.as_str().green(),
+        imported,
+        todos
+    );
+    Ok(())
+}
+
+/// Convert every `setGenerator` of the plopfile in `dir` (or the plopfile `dir` names)
+fn import_plop(dir: &Path) -> Result<Vec<ImportedTemplate>> {
+    let plopfile = if dir.is_file() {
+        dir.to_path_buf()
+    } else {
+        PLOPFILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| anyhow::anyhow!("No plopfile found in {}", dir.display()))?
+    };
+    let root = plopfile.parent().unwrap_or(Path::new("."));
+    let source = std::fs::read_to_string(&plopfile)
+        .with_context(|| format!("Could not read {}", plopfile.display()))?;
+
+    Ok(parse_plopfile(&source)
+        .iter()
+        .map(|generator| {
+            convert_plop_generator(generator, |file| {
+                std::fs::read_to_string(root.join(file)).ok()
+            })
+        })
+        .collect())
+}
+
+/// Convert every `<generator>/<action>` of a Hygen `_templates` directory
+///
+/// An action called `new` is imported as its generator's name, others as
+/// `<generator>-<action>`.
+fn import_hygen(dir: &Path) -> Result<Vec<ImportedTemplate>> {
+    let root = match dir.join("_templates") {
+        nested if nested.is_dir() => nested,
+        _ => dir.to_path_buf(),
+    };
+    let mut templates = Vec::new();
+
+    for generator in sorted_dirs(&root)? {
+        for action in sorted_dirs(&generator)? {
+            let mut files = Vec::new();
+            let mut prompt = None;
+            for entry in WalkDir::new(&action)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+            {
+                let path = entry.path();
+                let relative = path.strip_prefix(&generator).unwrap_or(path);
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Could not read {}", path.display()))?;
+                match path.file_name().and_then(|name| name.to_str()) {
+                    Some("prompt.js" | "prompt.cjs") => prompt = Some(content),
+                    Some(name) if name.ends_with(".t") => {
+                        files.push((relative.to_string_lossy().replace('\\', "/"), content))
+                    }
+                    _ => {}
+                }
+            }
+            if files.is_empty() {
+                continue;
+            }
+
+            let generator_name = generator.file_name().unwrap_or_default().to_string_lossy();
+            let action_name = action.file_name().unwrap_or_default().to_string_lossy();
+            let name = if action_name == "new" {
+                generator_name.to_string()
+            } else {
+                format!("{}-{}", generator_name, action_name)
+            };
+            templates.push(convert_hygen_action(&name, &files, prompt.as_deref()));
+        }
+    }
+    Ok(templates)
+}
+
+/// Convert every generator of a Yeoman package (or the one generator `dir` is)
+///
+/// The `app` generator is imported under the package name without its
+/// `generator-` prefix.
+fn import_yeoman(dir: &Path) -> Result<Vec<ImportedTemplate>> {
+    let generators = if dir.join("index.js").is_file() {
+        vec![dir.to_path_buf()]
+    } else {
+        match dir.join("generators") {
+            nested if nested.is_dir() => sorted_dirs(&nested)?,
+            _ => sorted_dirs(dir)?,
+        }
+    };
+    let package_name = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|package| package["name"].as_str().map(str::to_string))
+        .map(|name| {
+            let name = name.rsplit('/').next().unwrap_or(&name);
+            name.strip_prefix("generator-").unwrap_or(name).to_string()
+        });
+
+    let mut templates = Vec::new();
+    for generator in generators {
+        let Ok(index_js) = std::fs::read_to_string(generator.join("index.js")) else {
+            continue;
+        };
+        let dir_name = generator.file_name().unwrap_or_default().to_string_lossy();
+        let name = match (dir_name.as_ref(), &package_name) {
+            ("app", Some(package_name)) => package_name.clone(),
+            _ => dir_name.to_string(),
+        };
+        let templates_dir = generator.join("templates");
+        templates.push(convert_yeoman_generator(&name, &index_js, |file| {
+            std::fs::read_to_string(templates_dir.join(file)).ok()
+        }));
+    }
+    Ok(templates)
+}
+
+/// Subdirectories of `dir`, sorted
+fn sorted_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Generate the components of a spec directory whose spec changed since the last sync
+///
+/// Components that fail are reported and retried on the next sync; specs
+/// that were removed are dropped from the state without deleting files.
+async fn sync_components(config: &Config, spec_dir: &Path, force: bool) -> Result<()> {
+    let specs = read_specs(spec_dir)?;
+    if specs.is_empty() {
+        anyhow::bail!(
+            "No component specs (*.yaml) found in {}",
+            spec_dir.display()
+        );
+    }
+
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let mut state = SyncState::load(spec_dir);
+    let (mut synced, mut unchanged, mut failed) = (0, 0, 0);
+
+    for (key, spec) in &specs {
+        if !force && state.is_current(key, spec) {
+            unchanged += 1;
+            continue;
+        }
+
+        let create_folder = spec.create_folder.unwrap_or(config.create_folder());
+        match engine
+            .generate(
+                &spec.name,
+                &spec.template_type,
+                create_folder,
+                spec.cli_vars(),
+            )
+            .await
+        {
+            Ok(written) => {
+                ui::status!(
+                    "{} {} {} ({} files)",
+                    symbols::SYNC.as_str().bold(),
+                    spec.name.bold(),
+                    format!("[{}]", spec.template_type).dimmed(),
+                    written.len()
+                );
+                state.specs.insert(key.clone(), spec.hash());
+                synced += 1;
+            }
+            Err(e) => {
+                ui::show!("{} {} {:#}", symbols::CROSS.as_str().red(), key.bold(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    let removed: Vec<String> = state
+        .specs
+        .keys()
+        .filter(|key| !specs.iter().any(|(k, _)| k == *key))
+        .cloned()
+        .collect();
+    for key in removed {
+        ui::status!(
+            "{} {} was removed; its generated files were left in place",
+            symbols::WARNING.as_str().yellow(),
+            key
+        );
+        state.specs.remove(&key);
+    }
+    state.save(spec_dir)?;
+
+    ui::status!();
+    ui::status!(
+        "{} {} synced, {} unchanged (state in {})",
+        symbols::SUCCESS.as_str().green(),
+        synced,
+        unchanged,
+        SYNC_STATE_FILE
+    );
+    if failed > 0 {
+        anyhow::bail!("{} component(s) failed to sync", failed);
+    }
+    Ok(())
+}
+
+/// Serve the template pack over HTTP
+async fn serve(config: &Config, host: &str, port: u16) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+
+    server::serve(Arc::new(engine), &format!("{}:{}", host, port)).await
+}
+
+/// Report stamped files under `path` that drifted from their templates,
+/// failing when any did
+fn drift(config: &Config, path: Option<&Path>, json: bool) -> Result<()> {
+    let root = path.unwrap_or(config.output_dir());
+    let engine = TemplateEngine::new(config.templates_dir().clone(), root.to_path_buf())?
+        .with_packs(config.packs().clone());
+    let files = engine.drift(root)?;
+    let drifted: Vec<&DriftedFile> = files.iter().filter(|file| file.has_drifted()).collect();
+
+    if json {
+        ui::data!("{}", serde_json::to_string_pretty(&files)?);
+    } else if files.is_empty() {
+        ui::status!(
+            "{} No stamped files found in {} (generate with --stamp)",
+            symbols::INFO.as_str(),
+            root.display()
+        );
+    } else if drifted.is_empty() {
+        ui::status!(
+            "{} {} stamped file(s) match their templates",
+            symbols::SUCCESS.as_str().green(),
+            files.len()
+        );
+    } else {
+        ui::show!(
+            "{} Drift in {}:",
+            symbols::SEARCH.as_str().bold(),
+            root.display().to_string().bold()
+        );
+        for file in &drifted {
+            let mut reasons = Vec::new();
+            if file.modified {
+                reasons.push("modified".yellow());
+            }
+            match file.template {
+                TemplateStatus::Current => {}
+                TemplateStatus::Outdated => reasons.push("outdated template".yellow()),
+                TemplateStatus::Missing => reasons.push("template missing".red()),
+            }
+            let reasons: Vec<String> = reasons.iter().map(ToString::to_string).collect();
+            ui::show!(
+                "  {} [{}] {}",
+                file.path.display().to_string().bold(),
+                reasons.join(", "),
+                format!("(from {})", file.stamp.template).dimmed()
+            );
+        }
+    }
+
+    if !drifted.is_empty() {
+        anyhow::bail!(
+            "{} of {} stamped file(s) drifted",
+            drifted.len(),
+            files.len()
+        );
+    }
+    Ok(())
+}
+
+/// Regenerate the stamped files under `path` whose template changed,
+/// reporting the changes grouped by package
+///
+/// Without `path`, the enclosing workspace root (or the current directory)
+/// is scanned. Fails when any file couldn't be regenerated.
+async fn upgrade(
+    config: &Config,
+    path: Option<&Path>,
+    dry_run: bool,
+    report: Option<&Path>,
+) -> Result<()> {
+    let root = match path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let cwd = std::env::current_dir().context("Could not read current directory")?;
+            Workspace::detect(&cwd).map_or(cwd, |workspace| workspace.root)
+        }
+    };
+    let engine = TemplateEngine::new(config.templates_dir().clone(), root.clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+    let upgrades = engine.plan_upgrade(&root).await?;
+
+    if upgrades.is_empty() {
+        ui::status!(
+            "{} No stamped files found in {} (generate with --stamp)",
+            symbols::INFO.as_str(),
+            root.display()
+        );
+        return Ok(());
+    }
+
+    let mut upgraded = 0;
+    let mut failed = 0;
+    for (package, upgrades) in by_package(&upgrades) {
+        ui::show!(
+            "{} Package {}:",
+            symbols::SEARCH.as_str().bold(),
+            package_label(package).bold()
+        );
+        for upgrade in upgrades {
+            let status = match &upgrade.outcome {
+                UpgradeOutcome::Upgraded { contents, .. } => {
+                    if !dry_run {
+                        let target = root.join(&upgrade.path);
+                        fs::write(&target, contents)
+                            .await
+                            .with_context(|| format!("Could not write {}", target.display()))?;
+                    }
+                    upgraded += 1;
+                    "upgraded".green()
+                }
+                UpgradeOutcome::Current => "current".dimmed(),
+                UpgradeOutcome::Modified => "skipped, modified".yellow(),
+                UpgradeOutcome::TemplateMissing => "skipped, template missing".yellow(),
+                UpgradeOutcome::NotGenerated => "skipped, no longer generated".yellow(),
+                UpgradeOutcome::Failed(error) => {
+                    failed += 1;
+                    format!("failed: {}", error).red()
+                }
+            };
+            ui::show!(
+                "  {} [{}] {}",
+                upgrade.path.display().to_string().bold(),
+                status,
+                format!("(from {})", upgrade.template).dimmed()
+            );
+        }
+    }
+
+    if let Some(report) = report {
+        let mut patch = Vec::new();
+        write_patch_set(&upgrades, &mut patch)?;
+        fs::write(report, patch)
+            .await
+            .with_context(|| format!("Could not write report: {}", report.display()))?;
+        ui::status!(
+            "{} Patch set written to {}",
+            symbols::DOCS.as_str().bold(),
+            report.display().to_string().green()
+        );
+    } else if dry_run && upgraded > 0 {
+        let mut patch = Vec::new();
+        write_patch_set(&upgrades, &mut patch)?;
+        ui::data!("{}", String::from_utf8_lossy(&patch).trim_end());
+    }
+
+    ui::status!(
+        "{} {} of {} stamped file(s) {}",
+        symbols::SUCCESS.as_str().green(),
+        upgraded,
+        upgrades.len(),
+        if dry_run {
+            "would be upgraded"
+        } else {
+            "upgraded"
+        }
+    );
+    if failed > 0 {
+        anyhow::bail!("{} stamped file(s) failed to upgrade", failed);
+    }
+    Ok(())
+}
+
+/// Evaluate expressions against a template's data interactively
+async fn repl(config: &Config, template: &str, name: &str, vars: &[String]) -> Result<()> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+
+    repl::run_stdio(&engine, template, name, parse_var_args(vars)).await
+}
+
+/// Serve JSON-RPC for editor integrations
+async fn serve_rpc(config: &Config, stdio: bool) -> Result<()> {
+    if !stdio {
+        anyhow::bail!("Only the stdio transport is supported, pass --stdio");
+    }
+
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults())
+        .with_packs(config.packs().clone());
+
+    rpc::run_stdio(&engine, config.output_dir()).await
+}
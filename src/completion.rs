@@ -0,0 +1,260 @@
+//! Shell completions that know about the templates (`cli-frontend completions`).
+//!
+//! The scripts printed for each shell are thin: on every TAB they pass the
+//! command line up to the cursor to the hidden `cli-frontend complete`
+//! command, which answers from the templates on disk. That is how
+//! `--type <TAB>` offers the templates and architectures that exist right
+//! now, and `--var style=<TAB>` offers the values the selected template
+//! declares in `style_options` (or `true`/`false` for boolean variables).
+
+use clap::CommandFactory;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cli::Args;
+use crate::config::Config;
+use crate::template_engine::TemplateEngine;
+
+/// Shell to print a completion script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            other => Err(format!(
+                "unsupported shell '{}', expected bash, zsh or fish",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+        })
+    }
+}
+
+/// The completion script for `shell`, to be sourced from its startup file
+pub fn script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => {
+            r#"_cli_frontend() {
+    local IFS=$'\n'
+    COMPREPLY=($(cli-frontend complete --shell bash -- "${COMP_LINE:0:COMP_POINT}" 2>/dev/null))
+    # `--var key=` still needs its value
+    if [[ ${#COMPREPLY[@]} -eq 1 && ${COMPREPLY[0]} == *= ]]; then
+        compopt -o nospace
+    fi
+}
+complete -o default -F _cli_frontend cli-frontend
+"#
+        }
+        Shell::Zsh => {
+            r#"#compdef cli-frontend
+_cli_frontend() {
+    local -a candidates
+    candidates=(${(f)"$(cli-frontend complete --shell zsh -- "${BUFFER[1,CURSOR]}" 2>/dev/null)"})
+    # `--var key=` still needs its value
+    compadd -S '' -- ${(M)candidates:#*=}
+    compadd -- ${candidates:#*=}
+}
+compdef _cli_frontend cli-frontend
+"#
+        }
+        Shell::Fish => {
+            r#"function __cli_frontend_complete
+    cli-frontend complete --shell fish -- (commandline -cp) 2>/dev/null
+end
+complete -c cli-frontend -f -a '(__cli_frontend_complete)'
+"#
+        }
+    }
+}
+
+/// Completions for the word under the cursor at the end of `line`
+///
+/// `line` is the command line up to the cursor, program name included.
+/// Anything that can't be looked up (a missing template, an unreadable
+/// templates directory) just completes to nothing.
+pub async fn complete(line: &str, shell: Shell, config: &Config) -> Vec<String> {
+    let mut words: Vec<&str> = line.split_whitespace().skip(1).collect();
+    let current = match line.ends_with(char::is_whitespace) {
+        true => "",
+        false => words.pop().unwrap_or(""),
+    };
+
+    let engine =
+        match TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone()) {
+            Ok(engine) => engine
+                .with_defaults(config.template_defaults())
+                .with_packs(config.packs().clone())
+                .with_include_internal(words.contains(&"--include-internal")),
+            Err(_) => return Vec::new(),
+        };
+
+    let candidates = match words.last().copied() {
+        Some("-t" | "--type") => {
+            let mut templates = engine.list_templates().unwrap_or_default();
+            templates.push("feature".to_string());
+            templates
+        }
+        Some("-a" | "--architecture") => config.list_architectures().unwrap_or_default(),
+        Some("--var") => {
+            let template = flag_value(&words, &["-t", "--type"]).unwrap_or(config.default_type());
+            return variable_candidates(&engine, template, current, shell).await;
+        }
+        _ if current.starts_with('-') => flags(&words),
+        None => subcommands(),
+        _ => Vec::new(),
+    };
+    matching(candidates, current)
+}
+
+/// `key=` for each variable of `template`, or the values of the variable in
+/// `current` once it has an `=`
+async fn variable_candidates(
+    engine: &TemplateEngine,
+    template: &str,
+    current: &str,
+    shell: Shell,
+) -> Vec<String> {
+    let Ok(description) = engine.template_description(template).await else {
+        return Vec::new();
+    };
+    let Some((key, value)) = current.split_once('=') else {
+        let keys = description.variables.keys().map(|key| format!("{}=", key));
+        return matching(keys.collect(), current);
+    };
+    let Some(variable) = description.variables.get(key) else {
+        return Vec::new();
+    };
+
+    let values = if !variable.values.is_empty() {
+        variable.values.clone()
+    } else if variable.var_type == "boolean" {
+        vec!["true".to_string(), "false".to_string()]
+    } else {
+        Vec::new()
+    };
+    matching(values, value)
+        .into_iter()
+        // Bash splits words at `=`, so it only replaces the value
+        .map(|value| match shell {
+            Shell::Bash => value,
+            Shell::Zsh | Shell::Fish => format!("{}={}", key, value),
+        })
+        .collect()
+}
+
+/// The value given to the first of `flags` in `words`
+fn flag_value<'a>(words: &[&'a str], flags: &[&str]) -> Option<&'a str> {
+    words
+        .windows(2)
+        .find(|pair| flags.contains(&pair[0]))
+        .map(|pair| pair[1])
+}
+
+/// Long and short flags of the subcommand in `words`, or of the CLI itself
+fn flags(words: &[&str]) -> Vec<String> {
+    let command = Args::command();
+    let command = words
+        .iter()
+        .find_map(|word| command.find_subcommand(word))
+        .unwrap_or(&command);
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .flat_map(|arg| {
+            let long = arg.get_long().map(|long| format!("--{}", long));
+            let short = arg.get_short().map(|short| format!("-{}", short));
+            long.into_iter().chain(short)
+        })
+        .collect()
+}
+
+fn subcommands() -> Vec<String> {
+    Args::command()
+        .get_subcommands()
+        .filter(|command| !command.is_hide_set())
+        .map(|command| command.get_name().to_string())
+        .collect()
+}
+
+fn matching(candidates: Vec<String>, prefix: &str) -> Vec<String> {
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_complete_variable_values_of_the_selected_template() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let templates = temp_dir.path().join("templates");
+        std::fs::create_dir_all(templates.join("widget")).unwrap();
+        std::fs::write(
+            templates.join("widget").join(".conf"),
+            "[options]\nstyle=css\nstyle_options=css,scss,styled-components\nwith_tests=true\nwith_tests_type=boolean\n",
+        )
+        .unwrap();
+        let config_file = temp_dir.path().join("test.conf");
+        std::fs::write(
+            &config_file,
+            format!("templates_dir={}\n", templates.display()),
+        )
+        .unwrap();
+        let config = Config::load(&Some(config_file)).await.unwrap();
+
+        let complete = |line: &'static str, shell| {
+            let config = &config;
+            async move { complete(line, shell, config).await }
+        };
+        assert_eq!(
+            complete("cli-frontend Card --type wid", Shell::Zsh).await,
+            ["widget"]
+        );
+        assert_eq!(
+            complete("cli-frontend Card -t widget --var ", Shell::Zsh).await,
+            ["style=", "with_tests="]
+        );
+        assert_eq!(
+            complete("cli-frontend Card -t widget --var style=s", Shell::Zsh).await,
+            ["style=scss", "style=styled-components"]
+        );
+        assert_eq!(
+            complete("cli-frontend Card -t widget --var style=s", Shell::Bash).await,
+            ["scss", "styled-components"]
+        );
+        assert_eq!(
+            complete("cli-frontend Card -t widget --var with_tests=", Shell::Fish).await,
+            ["with_tests=true", "with_tests=false"]
+        );
+        assert!(
+            complete("cli-frontend Card -t nope --var style=", Shell::Zsh)
+                .await
+                .is_empty()
+        );
+        assert!(complete("cli-frontend ", Shell::Bash)
+            .await
+            .contains(&"completions".to_string()));
+    }
+}
@@ -1,8 +1,15 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use tokio::fs;
 
+use crate::template_engine::naming::{interpolate_name_pattern, process_smart_names};
+use crate::template_engine::patch::InsertPosition;
+
+/// File at the feature root re-exporting the structures' `exports`
+pub const FEATURE_INDEX_FILE: &str = "index.ts";
+
 /// Configuration for a feature architecture pattern
 ///
 /// Defines the structure, benefits, and limitations of an architectural approach.
@@ -13,6 +20,14 @@ pub struct ArchitectureConfig {
     pub benefits: Vec<String>,
     pub limitations: Vec<String>,
     pub structure: Vec<ArchitectureStructure>,
+    /// Insertions into existing project files, applied after the structure
+    #[serde(default)]
+    pub patches: Vec<ArchitecturePatch>,
+    /// Template variables derived once per feature and passed to every
+    /// structure, as `{name}` patterns by variable name (e.g.
+    /// `"api_base_path": "/api/{kebab_name}"`)
+    #[serde(default)]
+    pub shared: BTreeMap<String, String>,
 }
 
 /// A single component of an architecture structure
@@ -24,6 +39,72 @@ pub struct ArchitectureStructure {
     pub template: String,
     pub filename_pattern: String,
     pub description: String,
+    /// Symbols the structure's files export, as `{name}` patterns by module
+    /// relative to the structure folder (e.g. `"{name}": ["{name}Service"]`),
+    /// re-exported from the feature's [`FEATURE_INDEX_FILE`]
+    #[serde(default)]
+    pub exports: BTreeMap<String, Vec<String>>,
+    /// Template variables for this structure only, as `{name}` patterns by
+    /// variable name (e.g. `"layer": "domain"`), so one template can serve
+    /// several layers. They override `shared` variables of the same name.
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    /// Named output root (`[roots]` in the config) the structure generates
+    /// into instead of the output directory, e.g. `tests`. The feature
+    /// folder is recreated under it.
+    #[serde(default)]
+    pub output_root: Option<String>,
+}
+
+/// An anchored insertion into an existing project file
+///
+/// Registers the new feature in files such as a module federation config.
+/// The file is looked up in the feature folder and then in each parent
+/// directory; architectures can list alternatives (`webpack.config.js`,
+/// `vite.config.ts`) and only the ones found are patched.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchitecturePatch {
+    pub file: String,
+    /// Text on the line to insert next to
+    pub anchor: String,
+    /// Lines to insert, with `{name}` patterns and `{feature_path}` (the
+    /// feature folder relative to the patched file) interpolated
+    pub insert: String,
+    #[serde(default)]
+    pub position: InsertPosition,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl ArchitectureStructure {
+    /// The `params` of the structure for a feature named `name`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::config::ArchitectureConfig;
+    ///
+    /// let architecture = ArchitectureConfig::parse_json(
+    ///     r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [],
+    ///         "structure": [{"path": "domain", "template": "barrel",
+    ///             "filename_pattern": "index", "description": "",
+    ///             "params": {"layer": "domain", "entity": "{name}Entity"}}]}"#,
+    /// )
+    /// .unwrap();
+    /// let params = architecture.structure[0].params_for("User");
+    /// assert_eq!(params["layer"], "domain");
+    /// assert_eq!(params["entity"], "UserEntity");
+    /// ```
+    pub fn params_for(&self, name: &str) -> HashMap<String, String> {
+        let smart_names = process_smart_names(name);
+        self.params
+            .iter()
+            .map(|(variable, pattern)| {
+                let value = interpolate_name_pattern(pattern, name, &smart_names);
+                (variable.clone(), value)
+            })
+            .collect()
+    }
 }
 
 impl ArchitectureConfig {
@@ -74,8 +155,99 @@ impl ArchitectureConfig {
         Self::parse_json(&content)
     }
 
+    /// The `shared` variables of a feature named `name`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::config::ArchitectureConfig;
+    ///
+    /// let architecture = ArchitectureConfig::parse_json(
+    ///     r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [],
+    ///         "structure": [], "shared": {"api_base_path": "/api/{kebab_name}"}}"#,
+    /// )
+    /// .unwrap();
+    /// let shared = architecture.shared_variables("UserProfile");
+    /// assert_eq!(shared["api_base_path"], "/api/user-profile");
+    /// ```
+    pub fn shared_variables(&self, name: &str) -> HashMap<String, String> {
+        let smart_names = process_smart_names(name);
+        self.shared
+            .iter()
+            .map(|(variable, pattern)| {
+                let value = interpolate_name_pattern(pattern, name, &smart_names);
+                (variable.clone(), value)
+            })
+            .collect()
+    }
+
+    /// The [`FEATURE_INDEX_FILE`] of a feature named `name`, or `None` when
+    /// no structure declares `exports`
+    ///
+    /// Structures generated into another `output_root` are left out, as
+    /// they aren't under the feature folder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::config::ArchitectureConfig;
+    ///
+    /// let architecture = ArchitectureConfig::parse_json(
+    ///     r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [],
+    ///         "structure": [{"path": "services", "template": "service",
+    ///             "filename_pattern": "{name}", "description": "",
+    ///             "exports": {"{name}Service": ["{name}Service", "type {name}Client"]}}]}"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     architecture.feature_index("Auth").unwrap(),
+    ///     "export { AuthService, type AuthClient } from './services/AuthService';\n"
+    /// );
+    /// ```
+    pub fn feature_index(&self, name: &str) -> Option<String> {
+        let smart_names = process_smart_names(name);
+        let interpolate = |pattern: &str| interpolate_name_pattern(pattern, name, &smart_names);
+        let mut index = String::new();
+        for structure in self.structure.iter().filter(|s| s.output_root.is_none()) {
+            for (module, symbols) in &structure.exports {
+                if symbols.is_empty() {
+                    continue;
+                }
+                let module = if structure.path.is_empty() {
+                    format!("./{}", interpolate(module))
+                } else {
+                    format!(
+                        "./{}/{}",
+                        structure.path.trim_matches('/'),
+                        interpolate(module)
+                    )
+                };
+                let symbols: Vec<String> =
+                    symbols.iter().map(|symbol| interpolate(symbol)).collect();
+                index.push_str(&format!(
+                    "export {{ {} }} from '{}';\n",
+                    symbols.join(", "),
+                    module
+                ));
+            }
+        }
+        (!index.is_empty()).then_some(index)
+    }
+
+    /// Templates the structure uses for which `exists` is false, in
+    /// structure order without repeats
+    pub fn missing_templates(&self, exists: impl Fn(&str) -> bool) -> Vec<&str> {
+        let mut missing: Vec<&str> = Vec::new();
+        for structure in &self.structure {
+            let template = structure.template.as_str();
+            if !exists(template) && !missing.contains(&template) {
+                missing.push(template);
+            }
+        }
+        missing
+    }
+
     /// List all available architectures in a directory
-    #[allow(dead_code)]
     pub fn list_in_directory(architectures_dir: &Path) -> Result<Vec<String>> {
         let mut architectures = Vec::new();
 
@@ -4,6 +4,44 @@ use tokio::fs;
 
 use super::parser::{expand_path, parse_ini, to_ini};
 use super::Config;
+use crate::template_engine::config::{DEFAULT_MAX_TEMPLATE_DEPTH, DEFAULT_MAX_TEMPLATE_FILES};
+use crate::template_engine::formatting::LineEndings;
+use crate::template_engine::naming::CaseStyle;
+
+/// Parse a case policy setting, warning about unknown values
+fn parse_case_setting(key: &str, value: &str) -> Option<CaseStyle> {
+    let style = CaseStyle::parse(value);
+    if style.is_none() {
+        crate::ui::warning!(
+            "Warning: Unknown {} '{}', expected pascal, camel, kebab, snake or route",
+            key,
+            value
+        );
+    }
+    style
+}
+
+/// Parse a line ending setting, warning about unknown values
+fn parse_line_endings_setting(value: &str) -> Option<LineEndings> {
+    let endings = LineEndings::parse(value);
+    if endings.is_none() {
+        crate::ui::warning!(
+            "Warning: Unknown line_endings '{}', expected lf, crlf or auto",
+            value
+        );
+    }
+    endings
+}
+
+/// Parse a comma-separated list setting, dropping empty entries
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
 
 impl Config {
     /// Generic function to find directory with customizable search paths
@@ -13,6 +51,18 @@ impl Config {
         system_paths: Vec<PathBuf>,
         fallback: PathBuf,
     ) -> PathBuf {
+        Self::search_paths(local_paths, home_subpaths, system_paths)
+            .into_iter()
+            .find(|path| path.exists() && path.is_dir())
+            .unwrap_or(fallback)
+    }
+
+    /// The directories [`find_directory`](Self::find_directory) checks, in order
+    pub fn search_paths(
+        local_paths: Vec<PathBuf>,
+        home_subpaths: Vec<&str>,
+        system_paths: Vec<PathBuf>,
+    ) -> Vec<PathBuf> {
         let mut search_paths = local_paths;
 
         if let Some(home_dir) = dirs::home_dir() {
@@ -28,13 +78,53 @@ impl Config {
         }
 
         search_paths
-            .into_iter()
-            .find(|path| path.exists() && path.is_dir())
-            .unwrap_or(fallback)
     }
 
-    /// Find templates directory in order of preference
-    pub fn find_templates_directory() -> PathBuf {
+    /// Data directories of package managers that may have installed `kind`
+    /// (`templates` or `architectures`)
+    ///
+    /// Covers `$XDG_DATA_HOME` (or `~/.local/share`), the Homebrew prefix on
+    /// Unix (`$HOMEBREW_PREFIX`, else its default locations) and the Scoop
+    /// persist directory on Windows (`$SCOOP`, else `~/scoop`).
+    fn package_data_dirs(kind: &str) -> Vec<PathBuf> {
+        let env_dir = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        let home_dir = dirs::home_dir();
+        let mut dirs = Vec::new();
+
+        if let Some(data_home) =
+            env_dir("XDG_DATA_HOME").or_else(|| home_dir.as_ref().map(|h| h.join(".local/share")))
+        {
+            dirs.push(data_home.join("cli-frontend").join(kind));
+        }
+
+        #[cfg(unix)]
+        {
+            let brew_prefixes = match env_dir("HOMEBREW_PREFIX") {
+                Some(prefix) => vec![prefix],
+                None => vec![
+                    PathBuf::from("/opt/homebrew"),
+                    PathBuf::from("/home/linuxbrew/.linuxbrew"),
+                ],
+            };
+            for prefix in brew_prefixes {
+                dirs.push(prefix.join("share").join("cli-frontend").join(kind));
+            }
+        }
+
+        #[cfg(windows)]
+        if let Some(scoop) = env_dir("SCOOP").or_else(|| home_dir.map(|h| h.join("scoop"))) {
+            dirs.push(scoop.join("persist").join("cli-frontend").join(kind));
+        }
+
+        dirs
+    }
+
+    /// Templates directories in order of preference
+    pub fn templates_search_paths() -> Vec<PathBuf> {
         let local_paths = vec![
             PathBuf::from("./templates"),
             PathBuf::from("./.cli-template"),
@@ -42,22 +132,33 @@ impl Config {
 
         let home_subpaths = vec![".cli-template", ".config/cli-frontend/templates"];
 
-        let system_paths = vec![
+        let mut system_paths = Self::package_data_dirs("templates");
+        system_paths.extend([
             PathBuf::from("/usr/local/share/cli-frontend/templates"),
             PathBuf::from("/usr/share/cli-frontend/templates"),
             PathBuf::from("C:\\Program Files\\cli-frontend\\templates"),
             PathBuf::from("C:\\cli-frontend\\templates"),
-        ];
+        ]);
+
+        Self::search_paths(local_paths, home_subpaths, system_paths)
+    }
 
+    /// Find templates directory in order of preference
+    pub fn find_templates_directory() -> PathBuf {
         let fallback = dirs::home_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join(".cli-template");
 
-        Self::find_directory(local_paths, home_subpaths, system_paths, fallback)
+        Self::find_directory(
+            Self::templates_search_paths(),
+            Vec::new(),
+            Vec::new(),
+            fallback,
+        )
     }
 
-    /// Find architectures directory in order of preference
-    pub fn find_architectures_directory() -> PathBuf {
+    /// Architectures directories in order of preference
+    pub fn architectures_search_paths() -> Vec<PathBuf> {
         let local_paths = vec![
             PathBuf::from("./architectures"),
             PathBuf::from("./.cli-architectures"),
@@ -65,69 +166,62 @@ impl Config {
 
         let home_subpaths = vec![".cli-architectures", ".config/cli-frontend/architectures"];
 
-        let system_paths = vec![
+        let mut system_paths = Self::package_data_dirs("architectures");
+        system_paths.extend([
             PathBuf::from("/usr/local/share/cli-frontend/architectures"),
             PathBuf::from("/usr/share/cli-frontend/architectures"),
             PathBuf::from("C:\\Program Files\\cli-frontend\\architectures"),
             PathBuf::from("C:\\cli-frontend\\architectures"),
-        ];
+        ]);
 
+        Self::search_paths(local_paths, home_subpaths, system_paths)
+    }
+
+    /// Find architectures directory in order of preference
+    pub fn find_architectures_directory() -> PathBuf {
         let fallback = PathBuf::from("./architectures");
 
-        Self::find_directory(local_paths, home_subpaths, system_paths, fallback)
+        Self::find_directory(
+            Self::architectures_search_paths(),
+            Vec::new(),
+            Vec::new(),
+            fallback,
+        )
     }
 
-    /// Load configuration from file or create default
-    pub async fn load(config_path: &Option<PathBuf>) -> Result<Self> {
-        let config_file: std::borrow::Cow<'_, Path> = match config_path {
-            Some(path) => std::borrow::Cow::Borrowed(path.as_path()),
-            None => {
-                // Try multiple locations for config file
-                let locations = vec![
-                    PathBuf::from(".cli-frontend.conf"),   // Current directory first
-                    PathBuf::from("./.cli-frontend.conf"), // Explicit current directory
-                ];
-
-                let mut found_config = None;
-                for location in locations {
-                    if location.exists() {
-                        found_config = Some(location);
-                        break;
-                    }
-                }
+    /// The configuration file [`load`](Self::load) reads (or creates)
+    ///
+    /// `--config` wins; otherwise `.cli-frontend.conf` in the current
+    /// directory, then in the home directory.
+    pub fn locate(config_path: &Option<PathBuf>) -> Result<PathBuf> {
+        if let Some(path) = config_path {
+            return Ok(path.clone());
+        }
 
-                // If not found locally, try home directory
-                if found_config.is_none() {
-                    if let Some(home_dir) = dirs::home_dir() {
-                        let home_config = home_dir.join(".cli-frontend.conf");
-                        if home_config.exists() {
-                            found_config = Some(home_config);
-                        }
-                    }
-                }
+        let local = PathBuf::from(".cli-frontend.conf");
+        if local.exists() {
+            return Ok(local);
+        }
 
-                // Use found config or default to home directory config
-                let path = match found_config {
-                    Some(config) => config,
-                    None => {
-                        let home_dir = dirs::home_dir().context("Could not find home directory")?;
-                        home_dir.join(".cli-frontend.conf")
-                    }
-                };
-                std::borrow::Cow::Owned(path)
-            }
-        };
+        // Use the home directory config whether or not it exists yet
+        let home_dir = dirs::home_dir().context("Could not find home directory")?;
+        Ok(home_dir.join(".cli-frontend.conf"))
+    }
+
+    /// Load configuration from file or create default
+    pub async fn load(config_path: &Option<PathBuf>) -> Result<Self> {
+        let config_file = Self::locate(config_path)?;
 
         if !config_file.exists() {
             // Create default config if it doesn't exist
             let default_config = Self::default();
             if config_path.is_none() {
-                default_config.save(config_file.as_ref()).await?;
+                default_config.save(&config_file).await?;
             }
             return Ok(default_config);
         }
 
-        let content = fs::read_to_string(config_file.as_ref())
+        let content = fs::read_to_string(&config_file)
             .await
             .with_context(|| format!("Could not read config file: {}", config_file.display()))?;
 
@@ -136,15 +230,7 @@ impl Config {
 
     /// Save configuration to file
     pub async fn save(&self, path: &Path) -> Result<()> {
-        let content = to_ini(
-            &self.default_type,
-            self.create_folder,
-            self.enable_hooks,
-            &self.templates_dir,
-            &self.output_dir,
-            &self.architectures_dir,
-            &self.default_architecture,
-        );
+        let content = to_ini(self);
 
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).await.with_context(|| {
@@ -160,7 +246,7 @@ impl Config {
     }
 
     /// Parse configuration from INI format
-    fn from_ini(content: &str) -> Result<Self> {
+    pub(super) fn from_ini(content: &str) -> Result<Self> {
         let mut config = Self::default();
 
         let pairs = parse_ini(content);
@@ -173,7 +259,42 @@ impl Config {
                 "output_dir" => config.output_dir = PathBuf::from(value),
                 "architectures_dir" => config.architectures_dir = expand_path(&value)?,
                 "default_architecture" => config.default_architecture = value,
-                _ => {} // Ignore unknown keys
+                "filename_case" => config.filename_case = parse_case_setting(&key, &value),
+                "folder_case" => config.folder_case = parse_case_setting(&key, &value),
+                "editorconfig" => config.editorconfig = value.parse().unwrap_or(true),
+                "check_templates" => config.check_templates = value.parse().unwrap_or(true),
+                "file_header" if !value.is_empty() => config.file_header = Some(value),
+                "license" if !value.is_empty() => config.license = Some(value),
+                "author_name" if !value.is_empty() => config.author_name = Some(value),
+                "author_email" if !value.is_empty() => config.author_email = Some(value),
+                "stamp_files" => config.stamp_files = value.parse().unwrap_or(false),
+                "max_template_depth" => {
+                    config.max_template_depth = value.parse().unwrap_or(DEFAULT_MAX_TEMPLATE_DEPTH)
+                }
+                "max_template_files" => {
+                    config.max_template_files = value.parse().unwrap_or(DEFAULT_MAX_TEMPLATE_FILES)
+                }
+                "line_endings" => config.line_endings = parse_line_endings_setting(&value),
+                "verify_command" if !value.is_empty() => config.verify_command = Some(value),
+                "starter_templates" if !value.is_empty() => config.starter_templates = Some(value),
+                "usage_file" if value.is_empty() => config.usage_file = None,
+                "usage_file" => config.usage_file = Some(expand_path(&value)?),
+                "open_after_generate" if !value.is_empty() => {
+                    config.open_after_generate = Some(value)
+                }
+                "lang" if !value.is_empty() => config.lang = Some(value),
+                "env_allow" => config.env_allow = parse_list(&value),
+                "env_deny" => config.env_deny = parse_list(&value),
+                _ => {
+                    if let Some(name) = key.strip_prefix("variables.") {
+                        config.variables.insert(name.to_string(), value);
+                    } else if let Some(name) = key.strip_prefix("packs.") {
+                        config.packs.insert(name.to_string(), expand_path(&value)?);
+                    } else if let Some(name) = key.strip_prefix("roots.") {
+                        config.roots.insert(name.to_string(), expand_path(&value)?);
+                    }
+                    // Ignore unknown keys
+                }
             }
         }
 
@@ -4,10 +4,21 @@ mod parser;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use crate::template_engine::config::{
+    WalkLimits, DEFAULT_MAX_TEMPLATE_DEPTH, DEFAULT_MAX_TEMPLATE_FILES,
+};
+use crate::template_engine::formatting::LineEndings;
+use crate::template_engine::helpers::EnvPolicy;
+use crate::template_engine::naming::CaseStyle;
+use crate::template_engine::TemplateConfig;
+
 // Re-export public types
-pub use architecture::{ArchitectureConfig, ArchitectureStructure};
+pub use architecture::{
+    ArchitectureConfig, ArchitecturePatch, ArchitectureStructure, FEATURE_INDEX_FILE,
+};
 
 /// Global configuration for the CLI tool
 ///
@@ -21,6 +32,49 @@ pub struct Config {
     output_dir: PathBuf,
     architectures_dir: PathBuf,
     default_architecture: String,
+    filename_case: Option<CaseStyle>,
+    folder_case: Option<CaseStyle>,
+    line_endings: Option<LineEndings>,
+    editorconfig: bool,
+    /// Check templates_dir and the requested template's config before generating
+    check_templates: bool,
+    /// Deepest directory level walked inside a template
+    max_template_depth: usize,
+    /// Most files a template directory may hold
+    max_template_files: usize,
+    /// Banner prepended to every generated source file, with `\n` for line
+    /// breaks
+    file_header: Option<String>,
+    /// SPDX identifier of the generated code's license
+    license: Option<String>,
+    /// `{{author_name}}` and `{{author_email}}`, instead of the git user's
+    author_name: Option<String>,
+    author_email: Option<String>,
+    /// Stamp generated files with their template (`--stamp`)
+    stamp_files: bool,
+    /// Command run by `--verify` instead of `tsc --noEmit`
+    verify_command: Option<String>,
+    /// Editor to open generated output with after every run (`--open` uses
+    /// it too, falling back to `$VISUAL` / `$EDITOR`)
+    open_after_generate: Option<String>,
+    /// Git repository `bootstrap` offers to clone into an empty templates_dir
+    starter_templates: Option<String>,
+    /// Where recent uses of each template are recorded for `--describe`;
+    /// `None` records nothing
+    usage_file: Option<PathBuf>,
+    /// Language of the CLI's messages (`en`, `es`); the locale decides when unset
+    lang: Option<String>,
+    /// Named template directories (`[packs]`), used as `pack:template`
+    packs: BTreeMap<String, PathBuf>,
+    /// Named output directories (`[roots]`) architecture structures can
+    /// generate into with `output_root`
+    roots: BTreeMap<String, PathBuf>,
+    /// Values every template can use (`[variables]`), beneath template defaults
+    variables: BTreeMap<String, String>,
+    /// Environment variables the `env` helper may read; any when empty
+    env_allow: Vec<String>,
+    /// Environment variables the `env` helper may never read
+    env_deny: Vec<String>,
 }
 
 impl Default for Config {
@@ -37,6 +91,28 @@ impl Default for Config {
             output_dir: PathBuf::from("."),
             architectures_dir,
             default_architecture: "screaming-architecture".to_string(),
+            filename_case: None,
+            folder_case: None,
+            line_endings: None,
+            editorconfig: true,
+            check_templates: true,
+            max_template_depth: DEFAULT_MAX_TEMPLATE_DEPTH,
+            max_template_files: DEFAULT_MAX_TEMPLATE_FILES,
+            file_header: None,
+            license: None,
+            author_name: None,
+            author_email: None,
+            stamp_files: false,
+            verify_command: None,
+            open_after_generate: None,
+            starter_templates: None,
+            usage_file: dirs::home_dir().map(|home| home.join(".cli-template").join("usage.json")),
+            lang: None,
+            packs: BTreeMap::new(),
+            roots: BTreeMap::new(),
+            variables: BTreeMap::new(),
+            env_allow: Vec::new(),
+            env_deny: Vec::new(),
         }
     }
 }
@@ -72,18 +148,168 @@ impl Config {
         &self.default_architecture
     }
 
+    pub fn filename_case(&self) -> Option<CaseStyle> {
+        self.filename_case
+    }
+
+    pub fn folder_case(&self) -> Option<CaseStyle> {
+        self.folder_case
+    }
+
+    pub fn line_endings(&self) -> Option<LineEndings> {
+        self.line_endings
+    }
+
+    pub fn editorconfig(&self) -> bool {
+        self.editorconfig
+    }
+
+    /// Whether to sanity-check templates_dir before generating (`check_templates`)
+    pub fn check_templates(&self) -> bool {
+        self.check_templates
+    }
+
+    /// Banner set by `file_header`, as written in the config file
+    pub fn file_header(&self) -> Option<&str> {
+        self.file_header.as_deref()
+    }
+
+    /// SPDX identifier set by `license`
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    /// Author name set by `author_name`
+    pub fn author_name(&self) -> Option<&str> {
+        self.author_name.as_deref()
+    }
+
+    /// Author email set by `author_email`
+    pub fn author_email(&self) -> Option<&str> {
+        self.author_email.as_deref()
+    }
+
+    /// Whether every run stamps its files, as `--stamp` does
+    pub fn stamp_files(&self) -> bool {
+        self.stamp_files
+    }
+
+    /// Custom `--verify` command, with `{files}` standing for the generated files
+    pub fn verify_command(&self) -> Option<&str> {
+        self.verify_command.as_deref()
+    }
+
+    /// Starter templates repository set by `starter_templates`
+    pub fn starter_templates(&self) -> Option<&str> {
+        self.starter_templates.as_deref()
+    }
+
+    /// File recent template uses are recorded in (`usage_file`)
+    pub fn usage_file(&self) -> Option<&PathBuf> {
+        self.usage_file.as_ref()
+    }
+
+    /// Editor command set by `open_after_generate`
+    pub fn open_after_generate(&self) -> Option<&str> {
+        self.open_after_generate.as_deref()
+    }
+
+    /// Message language set by `lang`
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    /// Template packs by name (`[packs]`)
+    pub fn packs(&self) -> &BTreeMap<String, PathBuf> {
+        &self.packs
+    }
+
+    /// Output roots by name (`[roots]`)
+    pub fn roots(&self) -> &BTreeMap<String, PathBuf> {
+        &self.roots
+    }
+
+    /// Variables shared by every template (`[variables]`)
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// Patterns of the environment variables templates may read (`env_allow`)
+    pub fn env_allow(&self) -> &[String] {
+        &self.env_allow
+    }
+
+    /// Patterns of the environment variables templates may never read (`env_deny`)
+    pub fn env_deny(&self) -> &[String] {
+        &self.env_deny
+    }
+
+    /// Template settings every generation starts from before applying `.conf` overrides
+    ///
+    /// `[variables]` go into the template data rather than its variables,
+    /// so template defaults and `--var` values win and `--describe` doesn't
+    /// list them for every template. `license` is `{{license}}` there too,
+    /// and so are `author_name` and `author_email` when set; the engine
+    /// resolves the rest of the author when it first renders (see
+    /// [`Author`](crate::template_engine::author::Author)).
+    pub fn template_defaults(&self) -> TemplateConfig {
+        let author = [
+            ("author_name", &self.author_name),
+            ("author_email", &self.author_email),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = value.clone()?;
+            Some((key.to_string(), serde_json::Value::String(value)))
+        });
+        let license = self.license.iter().map(|license| {
+            (
+                "license".to_string(),
+                serde_json::Value::String(license.clone()),
+            )
+        });
+        TemplateConfig {
+            filename_case: self.filename_case,
+            folder_case: self.folder_case,
+            line_endings: self.line_endings,
+            editorconfig: self.editorconfig,
+            file_header: self.file_header.as_deref().map(header_lines),
+            data: self
+                .variables
+                .iter()
+                .map(|(name, value)| (name.clone(), serde_json::Value::String(value.clone())))
+                .chain(license)
+                .chain(author)
+                .collect(),
+            env_policy: EnvPolicy {
+                disabled: false,
+                allow: self.env_allow.clone(),
+                deny: self.env_deny.clone(),
+            },
+            walk_limits: WalkLimits {
+                max_depth: self.max_template_depth,
+                max_files: self.max_template_files,
+            },
+            ..TemplateConfig::default()
+        }
+    }
+
     /// Load architecture configuration from JSON file
     pub async fn load_architecture(&self, architecture_name: &str) -> Result<ArchitectureConfig> {
         ArchitectureConfig::load_from_file(&self.architectures_dir, architecture_name).await
     }
 
     /// List all available architectures
-    #[allow(dead_code)]
     pub fn list_architectures(&self) -> Result<Vec<String>> {
         ArchitectureConfig::list_in_directory(&self.architectures_dir)
     }
 }
 
+/// A one-line `file_header` setting with `\n` turned into line breaks
+pub(crate) fn header_lines(value: &str) -> String {
+    value.replace("\\n", "\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +334,38 @@ mod tests {
         let _od: &PathBuf = config.output_dir();
         let _ad: &PathBuf = config.architectures_dir();
         let _da: &str = config.default_architecture();
+        let _fc: Option<CaseStyle> = config.filename_case();
+        let _dc: Option<CaseStyle> = config.folder_case();
+    }
+
+    #[test]
+    fn test_template_defaults_carry_filename_case() {
+        let config = Config {
+            filename_case: Some(CaseStyle::Kebab),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.template_defaults().filename_case,
+            Some(CaseStyle::Kebab)
+        );
+        assert!(Config::default()
+            .template_defaults()
+            .filename_case
+            .is_none());
+    }
+
+    #[test]
+    fn test_template_defaults_carry_the_author() {
+        let config = Config {
+            author_name: Some("Ana Díaz".to_string()),
+            author_email: Some("ana@acme.dev".to_string()),
+            ..Config::default()
+        };
+
+        let data = config.template_defaults().data;
+        assert_eq!(data["author_name"], "Ana Díaz");
+        assert_eq!(data["author_email"], "ana@acme.dev");
     }
 
     #[test]
@@ -116,4 +374,20 @@ mod tests {
         // Should return a PathBuf (existence not guaranteed in test environment)
         assert!(templates_dir.to_str().is_some());
     }
+
+    #[test]
+    fn test_search_paths_include_package_manager_dirs() {
+        let paths = Config::templates_search_paths();
+        assert_eq!(paths[0], PathBuf::from("./templates"));
+
+        let data_dir = std::path::Path::new("cli-frontend").join("templates");
+        assert!(paths.iter().any(|path| path.ends_with(&data_dir)));
+        #[cfg(unix)]
+        if std::env::var_os("HOMEBREW_PREFIX").is_none() {
+            assert!(paths.iter().any(|path| path.starts_with("/opt/homebrew")));
+        }
+
+        let custom = Some(PathBuf::from("team.conf"));
+        assert_eq!(Config::locate(&custom).unwrap(), PathBuf::from("team.conf"));
+    }
 }
@@ -1,5 +1,8 @@
 use anyhow::{Context, Result};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+
+use super::Config;
+use crate::template_engine::naming::CaseStyle;
 
 /// Helper function to expand tilde in paths
 pub fn expand_path(value: &str) -> Result<PathBuf> {
@@ -13,9 +16,11 @@ pub fn expand_path(value: &str) -> Result<PathBuf> {
 
 /// Parse INI-like configuration format
 ///
-/// Returns a vector of (key, value) tuples
+/// Returns a vector of (key, value) tuples. Keys below a `[section]` header
+/// are prefixed with the section name, e.g. `variables.company`.
 pub fn parse_ini(content: &str) -> Vec<(String, String)> {
     let mut pairs = Vec::new();
+    let mut section = String::new();
 
     for line in content.lines() {
         let line = line.trim();
@@ -25,9 +30,17 @@ pub fn parse_ini(content: &str) -> Vec<(String, String)> {
             continue;
         }
 
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].trim().to_string();
+            continue;
+        }
+
         // Parse key=value pairs
         if let Some((key, value)) = line.split_once('=') {
-            let key = key.trim().to_string();
+            let key = match key.trim() {
+                key if section.is_empty() => key.to_string(),
+                key => format!("{}.{}", section, key),
+            };
             let value = value
                 .trim()
                 .trim_matches('"')
@@ -41,24 +54,116 @@ pub fn parse_ini(content: &str) -> Vec<(String, String)> {
 }
 
 /// Convert configuration to INI format string
-pub fn to_ini(
-    default_type: &str,
-    create_folder: bool,
-    enable_hooks: bool,
-    templates_dir: &Path,
-    output_dir: &Path,
-    architectures_dir: &Path,
-    default_architecture: &str,
-) -> String {
-    let templates_dir = templates_dir
+pub fn to_ini(config: &Config) -> String {
+    let templates_dir = config
+        .templates_dir
         .canonicalize()
-        .unwrap_or_else(|_| templates_dir.to_path_buf());
-    let output_dir = output_dir
+        .unwrap_or_else(|_| config.templates_dir.clone());
+    let output_dir = config
+        .output_dir
         .canonicalize()
-        .unwrap_or_else(|_| output_dir.to_path_buf());
-    let architectures_dir = architectures_dir
+        .unwrap_or_else(|_| config.output_dir.clone());
+    let architectures_dir = config
+        .architectures_dir
         .canonicalize()
-        .unwrap_or_else(|_| architectures_dir.to_path_buf());
+        .unwrap_or_else(|_| config.architectures_dir.clone());
+    let case_setting = |key: &str, style: Option<CaseStyle>| match style {
+        Some(style) => format!("{}={}", key, style.as_str()),
+        None => format!(
+            "# {} is pascal, camel, kebab or snake\n# {}=kebab",
+            key, key
+        ),
+    };
+    let filename_case = case_setting("filename_case", config.filename_case);
+    let folder_case = case_setting("folder_case", config.folder_case);
+    let line_endings = match config.line_endings {
+        Some(endings) => format!("line_endings={}", endings.as_str()),
+        None => "# line_endings is lf, crlf or auto (.editorconfig/.gitattributes)\n\
+                 # line_endings=auto"
+            .to_string(),
+    };
+
+    let verify_command = match &config.verify_command {
+        Some(command) => format!("verify_command={}", command),
+        None => "# Default command: tsc --noEmit\n# verify_command=npx eslint {files}".to_string(),
+    };
+    let file_header = match &config.file_header {
+        Some(header) => format!("file_header={}", header),
+        None => "# file_header=Copyright {{year}} Acme\\nSPDX-License-Identifier: MIT".to_string(),
+    };
+    let license = match &config.license {
+        Some(license) => format!("license={}", license),
+        None => "# SPDX identifier ({{license}}, spdx_header, --license-file)\n# license=MIT"
+            .to_string(),
+    };
+    let author = |key: &str, value: &Option<String>, default: &str, example: &str| match value {
+        Some(value) => format!("{}={}", key, value),
+        None => format!("# Default: {}\n# {}={}", default, key, example),
+    };
+    let author_name = author(
+        "author_name",
+        &config.author_name,
+        "git config user.name, then the OS user, then Frontend Team",
+        "Ana Díaz",
+    );
+    let author_email = author(
+        "author_email",
+        &config.author_email,
+        "git config user.email",
+        "ana@acme.dev",
+    );
+    let lang = match &config.lang {
+        Some(lang) => format!("lang={}", lang),
+        None => "# en or es (default: from $LANG)\n# lang=es".to_string(),
+    };
+    let starter_templates = match &config.starter_templates {
+        Some(repo) => format!("starter_templates={}", repo),
+        None => "# starter_templates=https://github.com/acme/frontend-templates.git".to_string(),
+    };
+    let usage_file = config
+        .usage_file
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    let open_after_generate = match &config.open_after_generate {
+        Some(command) => format!("open_after_generate={}", command),
+        None => "# Open generated output after every run\n# open_after_generate=code".to_string(),
+    };
+    let env_list = |key: &str, patterns: &[String], example: &str| match patterns {
+        [] => format!("# {}={}", key, example),
+        patterns => format!("{}={}", key, patterns.join(",")),
+    };
+    let env_allow = env_list("env_allow", &config.env_allow, "NODE_ENV,PUBLIC_*");
+    let env_deny = env_list("env_deny", &config.env_deny, "*SECRET*,*TOKEN*,*PASSWORD*");
+    // Sections run to the end of the file, so they come after the settings
+    let packs = if config.packs.is_empty() {
+        "# [packs]\n# acme=~/acme-templates\n".to_string()
+    } else {
+        let mut section = "[packs]\n".to_string();
+        for (name, dir) in &config.packs {
+            section.push_str(&format!("{}={}\n", name, dir.display()));
+        }
+        section
+    };
+    let roots = if config.roots.is_empty() {
+        "# [roots]\n# tests=./tests\n# storybook=./stories\n".to_string()
+    } else {
+        let mut section = "[roots]\n".to_string();
+        for (name, dir) in &config.roots {
+            section.push_str(&format!("{}={}\n", name, dir.display()));
+        }
+        section
+    };
+    // [variables] comes last
+    let variables = if config.variables.is_empty() {
+        "# [variables]\n# company=Acme\n# license=MIT\n".to_string()
+    } else {
+        let mut section = "[variables]\n".to_string();
+        for (name, value) in &config.variables {
+            section.push_str(&format!("{}={}\n", name, value));
+        }
+        section
+    };
 
     format!(
         "# CLI Frontend Generator Configuration\n\
@@ -68,32 +173,98 @@ pub fn to_ini(
          default_type={}\n\
          create_folder={}\n\
          enable_hooks={}\n\
+         editorconfig={}\n\
+         stamp_files={}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         \n\
+         # Banner commented into every generated source file (\\n breaks lines)\n\
+         {}\n\
+         {}\n\
+         \n\
+         # Author of generated files ({{{{author_name}}}}, {{{{author_email}}}})\n\
+         {}\n\
+         {}\n\
          \n\
          # Paths configuration\n\
          templates_dir={}\n\
          output_dir={}\n\
          architectures_dir={}\n\
          \n\
+         # Repository `cli-frontend bootstrap` offers to clone into an empty templates_dir\n\
+         {}\n\
+         \n\
+         # Recent uses of each template shown by --describe (empty records nothing)\n\
+         usage_file={}\n\
+         \n\
+         # Sanity checks on templates, in case templates_dir points at a project\n\
+         check_templates={}\n\
+         max_template_depth={}\n\
+         max_template_files={}\n\
+         \n\
          # Feature settings\n\
          default_architecture={}\n\
          \n\
+         # Verification (--verify)\n\
+         {}\n\
+         \n\
+         # Editor (--open)\n\
+         {}\n\
+         \n\
+         # Environment variables the env helper may read (--no-env-helpers: none)\n\
+         # With env_allow set, only those; env_deny always wins\n\
+         {}\n\
+         {}\n\
+         \n\
          # Available template types are determined by the directories in templates_dir\n\
          # Available architectures are determined by JSON files in architectures_dir\n\
          # You can add new templates by creating new directories in templates_dir\n\
-         # You can add new architectures by creating new JSON files in architectures_dir\n",
-        default_type,
-        create_folder,
-        enable_hooks,
+         # You can add new architectures by creating new JSON files in architectures_dir\n\
+         \n\
+         # Template packs: more template directories, used as pack:template\n\
+         {}\n\
+         # Output roots architecture structures can generate into (output_root)\n\
+         {}\n\
+         # Variables available to every template, overridden by template defaults and --var\n\
+         {}",
+        config.default_type,
+        config.create_folder,
+        config.enable_hooks,
+        config.editorconfig,
+        config.stamp_files,
+        filename_case,
+        folder_case,
+        line_endings,
+        lang,
+        file_header,
+        license,
+        author_name,
+        author_email,
         templates_dir.display(),
         output_dir.display(),
         architectures_dir.display(),
-        default_architecture
+        starter_templates,
+        usage_file,
+        config.check_templates,
+        config.max_template_depth,
+        config.max_template_files,
+        config.default_architecture,
+        verify_command,
+        open_after_generate,
+        env_allow,
+        env_deny,
+        packs,
+        roots,
+        variables
     )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::template_engine::formatting::LineEndings;
 
     #[test]
     fn test_parse_ini_basic() {
@@ -125,6 +296,242 @@ templates_dir='/path/to/templates'
         assert_eq!(pairs[1].1, "/path/to/templates");
     }
 
+    #[test]
+    fn test_parse_ini_sections() {
+        let content = "default_type=hook\n\n[variables]\ncompany=Acme\n";
+
+        let pairs = parse_ini(content);
+        assert_eq!(pairs[0], ("default_type".to_string(), "hook".to_string()));
+        assert_eq!(
+            pairs[1],
+            ("variables.company".to_string(), "Acme".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_ini_examples_parse_when_uncommented() {
+        let is_example = |line: &str| match line.split_once('=') {
+            Some((key, _)) => key.chars().all(|c| c.is_ascii_lowercase() || c == '_'),
+            None => line.starts_with('[') && line.ends_with(']'),
+        };
+        let uncommented = to_ini(&Config::default())
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(example) if is_example(example) => example,
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for (key, value) in parse_ini(&uncommented) {
+            assert!(!value.contains('#'), "{}={} kept a comment", key, value);
+        }
+        let config = Config::from_ini(&uncommented).unwrap();
+        assert_eq!(config.filename_case(), Some(CaseStyle::Kebab));
+        assert_eq!(config.folder_case(), Some(CaseStyle::Kebab));
+        assert_eq!(config.line_endings(), Some(LineEndings::Auto));
+        assert_eq!(config.verify_command.as_deref(), Some("npx eslint {files}"));
+        assert_eq!(config.license.as_deref(), Some("MIT"));
+        assert_eq!(config.author_name.as_deref(), Some("Ana Díaz"));
+        assert_eq!(config.author_email.as_deref(), Some("ana@acme.dev"));
+        assert_eq!(config.lang.as_deref(), Some("es"));
+        assert_eq!(config.open_after_generate.as_deref(), Some("code"));
+        assert_eq!(config.env_allow(), ["NODE_ENV", "PUBLIC_*"]);
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_variables() {
+        let mut config = Config::default();
+        assert!(to_ini(&config).contains("# [variables]"));
+
+        config
+            .variables
+            .insert("company".to_string(), "Acme".to_string());
+        config
+            .variables
+            .insert("license".to_string(), "MIT".to_string());
+        let ini = to_ini(&config);
+        assert!(ini.ends_with("[variables]\ncompany=Acme\nlicense=MIT\n"));
+
+        let parsed = Config::from_ini(&ini).unwrap();
+        assert_eq!(parsed.variables(), config.variables());
+        assert_eq!(parsed.default_type(), config.default_type());
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_packs() {
+        let mut config = Config::default();
+        assert!(to_ini(&config).contains("# [packs]"));
+
+        config
+            .packs
+            .insert("acme".to_string(), PathBuf::from("/opt/acme-templates"));
+        config
+            .roots
+            .insert("tests".to_string(), PathBuf::from("/work/tests"));
+        config
+            .variables
+            .insert("company".to_string(), "Acme".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.packs(), config.packs());
+        assert_eq!(parsed.roots(), config.roots());
+        assert_eq!(parsed.variables(), config.variables());
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_env_lists() {
+        let mut config = Config::default();
+        let uncommented = to_ini(&config)
+            .replace("# env_allow=", "env_allow=")
+            .replace("# env_deny=", "env_deny=");
+        let examples = Config::from_ini(&uncommented).unwrap();
+        assert_eq!(examples.env_allow(), ["NODE_ENV", "PUBLIC_*"]);
+        assert_eq!(examples.env_deny(), ["*SECRET*", "*TOKEN*", "*PASSWORD*"]);
+
+        config.env_allow = vec!["NODE_ENV".to_string(), "PUBLIC_*".to_string()];
+        config.env_deny = vec!["*SECRET*".to_string()];
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.env_allow(), config.env_allow());
+        assert_eq!(parsed.env_deny(), config.env_deny());
+        assert_eq!(parsed.template_defaults().env_policy.deny, ["*SECRET*"]);
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_case_settings() {
+        let mut config = Config::default();
+        assert!(to_ini(&config).contains("# filename_case="));
+
+        config.filename_case = Some(CaseStyle::Kebab);
+        config.folder_case = Some(CaseStyle::Snake);
+        let ini = to_ini(&config);
+        assert!(ini.contains("\nfilename_case=kebab\n"));
+
+        let parsed = Config::from_ini(&ini).unwrap();
+        assert_eq!(parsed.filename_case(), config.filename_case());
+        assert_eq!(parsed.folder_case(), config.folder_case());
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_line_endings() {
+        let mut config = Config::default();
+        assert!(to_ini(&config).contains("# line_endings="));
+
+        config.line_endings = Some(LineEndings::Crlf);
+        let ini = to_ini(&config);
+        assert!(ini.contains("\nline_endings=crlf\n"));
+
+        let parsed = Config::from_ini(&ini).unwrap();
+        assert_eq!(parsed.line_endings(), Some(LineEndings::Crlf));
+
+        config.editorconfig = false;
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert!(!parsed.editorconfig());
+        assert!(!parsed.stamp_files());
+
+        config.stamp_files = true;
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert!(parsed.stamp_files());
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_template_checks() {
+        let config = Config {
+            check_templates: false,
+            max_template_depth: 4,
+            max_template_files: 50,
+            ..Default::default()
+        };
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert!(!parsed.check_templates());
+        let limits = parsed.template_defaults().walk_limits;
+        assert_eq!(limits.max_depth, 4);
+        assert_eq!(limits.max_files, 50);
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_verify_command() {
+        let mut config = Config::default();
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.verify_command(), None);
+
+        config.verify_command = Some("npx eslint {files}".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.verify_command(), Some("npx eslint {files}"));
+        assert_eq!(parsed.open_after_generate(), None);
+
+        config.open_after_generate = Some("code -n".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.open_after_generate(), Some("code -n"));
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_usage_file() {
+        let mut config = Config {
+            usage_file: Some(PathBuf::from("/tmp/usage.json")),
+            ..Default::default()
+        };
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.usage_file(), Some(&PathBuf::from("/tmp/usage.json")));
+
+        config.usage_file = None;
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.usage_file(), None);
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_starter_templates() {
+        let mut config = Config::default();
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.starter_templates(), None);
+
+        config.starter_templates = Some("https://example.com/templates.git".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(
+            parsed.starter_templates(),
+            Some("https://example.com/templates.git")
+        );
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_file_header() {
+        let mut config = Config::default();
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.file_header(), None);
+
+        config.file_header = Some(r"Copyright {{year}} Acme\nMIT".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.file_header(), Some(r"Copyright {{year}} Acme\nMIT"));
+        assert_eq!(
+            parsed.template_defaults().file_header.as_deref(),
+            Some("Copyright {{year}} Acme\nMIT")
+        );
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_license() {
+        let mut config = Config::default();
+        assert_eq!(Config::from_ini(&to_ini(&config)).unwrap().license(), None);
+
+        config.license = Some("Apache-2.0".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.license(), Some("Apache-2.0"));
+        assert_eq!(parsed.template_defaults().data["license"], "Apache-2.0");
+    }
+
+    #[test]
+    fn test_to_ini_roundtrip_author() {
+        let mut config = Config::default();
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.author_name(), None);
+        assert_eq!(parsed.author_email(), None);
+
+        config.author_name = Some("Ana Díaz".to_string());
+        config.author_email = Some("ana@acme.dev".to_string());
+        let parsed = Config::from_ini(&to_ini(&config)).unwrap();
+        assert_eq!(parsed.author_name(), Some("Ana Díaz"));
+        assert_eq!(parsed.author_email(), Some("ana@acme.dev"));
+    }
+
     #[test]
     fn test_expand_path_regular() {
         let path = expand_path("/usr/local/templates").unwrap();
@@ -0,0 +1,527 @@
+//! Structured errors for the CLI boundary.
+//!
+//! Internals use `anyhow` with context; failures that scripts may want to
+//! branch on are raised as a [`CliError`], which `main` finds in the error
+//! chain to pick the exit code and a remediation hint.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::template_engine::helpers::{EnvDenied, EnvSetting};
+use crate::template_engine::renderer::RenderFailure;
+use crate::template_engine::suggest::did_you_mean;
+
+/// Exit code for failures without a more specific [`CliError`]
+pub const EXIT_FAILURE: i32 = 1;
+
+/// One of the templates an ambiguous name could refer to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateCandidate {
+    /// The `pack:template` name that selects it
+    pub name: String,
+    /// The template directory
+    pub path: PathBuf,
+    /// `[metadata] description`, empty when unknown
+    pub description: String,
+}
+
+impl fmt::Display for TemplateCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.path.display())?;
+        if !self.description.is_empty() {
+            write!(f, ": {}", self.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// A classified failure with a distinct exit code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// The requested template directory doesn't exist
+    TemplateNotFound {
+        name: String,
+        available: Vec<String>,
+    },
+    /// Several template packs provide a template asked for without a pack
+    AmbiguousTemplate {
+        name: String,
+        /// The `pack:template` templates that match
+        candidates: Vec<TemplateCandidate>,
+    },
+    /// The template is marked `internal=true` and `--include-internal` wasn't given
+    InternalTemplate { name: String },
+    /// A `--var` value is not accepted by the template
+    InvalidVariable { name: String, message: String },
+    /// Handlebars failed to parse or render a template file
+    RenderError {
+        file: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+    /// A template read an environment variable the env policy refuses
+    EnvDenied {
+        file: PathBuf,
+        line: Option<usize>,
+        /// The variable and the setting that refused it
+        denied: EnvDenied,
+    },
+    /// Several files failed to render (`--all-errors`)
+    RenderErrors {
+        /// Each failing file's error, in generation order
+        errors: Vec<String>,
+    },
+    /// Reading or writing files failed
+    IoError { message: String },
+    /// A configuration or architecture file is missing or invalid
+    ConfigError { message: String },
+    /// `templates_dir` has no templates yet (first run)
+    NoTemplates {
+        templates_dir: PathBuf,
+        /// `starter_templates` repository from the config, offered too
+        starter: Option<String>,
+    },
+    /// `--verify` found errors in the generated files
+    VerifyFailed { command: String, errors: usize },
+    /// The `[files]` conditions leave nothing to generate with the given variables
+    NothingToGenerate {
+        template: String,
+        /// Each file left out, with the condition that excluded it
+        filtered: Vec<String>,
+        /// `NAME=VALUE` changes that would include files
+        suggestions: Vec<String>,
+    },
+    /// `verify-golden` found renders that differ from the golden directory
+    GoldenDrift { template: String, files: usize },
+    /// The template's `requires_cli` is newer than the running binary
+    UnsupportedTemplate {
+        template: String,
+        required: String,
+        current: String,
+    },
+    /// The run was stopped with Ctrl-C
+    Interrupted,
+    /// The run took longer than `--timeout`
+    TimedOut { seconds: u64 },
+}
+
+impl CliError {
+    /// Process exit code for this error (2 is left to clap's usage errors)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::error::CliError;
+    ///
+    /// let error = CliError::TemplateNotFound { name: "widget".into(), available: vec![] };
+    /// assert_eq!(error.exit_code(), 3);
+    /// ```
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::TemplateNotFound { .. }
+            | Self::AmbiguousTemplate { .. }
+            | Self::InternalTemplate { .. } => 3,
+            Self::InvalidVariable { .. } | Self::NothingToGenerate { .. } => 4,
+            Self::RenderError { .. } | Self::EnvDenied { .. } | Self::RenderErrors { .. } => 5,
+            Self::IoError { .. } => 6,
+            Self::ConfigError { .. } | Self::NoTemplates { .. } => 7,
+            Self::VerifyFailed { .. } => 8,
+            Self::GoldenDrift { .. } => 9,
+            Self::UnsupportedTemplate { .. } => 10,
+            Self::TimedOut { .. } => 124,
+            Self::Interrupted => 130,
+        }
+    }
+
+    /// A suggestion for fixing the error
+    pub fn hint(&self) -> String {
+        match self {
+            Self::TemplateNotFound { name, available } if !available.is_empty() => {
+                let listing = format!(
+                    "Available templates: {}. Run `cli-frontend --list` for details.",
+                    available.join(", ")
+                );
+                match did_you_mean(name, available.iter().map(String::as_str)) {
+                    Some(suggestion) => format!("Did you mean '{}'? {}", suggestion, listing),
+                    None => listing,
+                }
+            }
+            Self::TemplateNotFound { .. } => {
+                "Check `templates_dir` in your configuration or run `cli-frontend --list`."
+                    .to_string()
+            }
+            Self::AmbiguousTemplate { candidates, .. } => format!(
+                "Name the pack: {}.",
+                candidates
+                    .iter()
+                    .map(|candidate| format!("--type {}", candidate.name))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+            Self::InternalTemplate { .. } => {
+                "It is meant to be used by architectures; pass --include-internal to generate it directly."
+                    .to_string()
+            }
+            Self::InvalidVariable { .. } => {
+                "Run `cli-frontend --describe <template>` to see accepted values.".to_string()
+            }
+            Self::RenderError { message, .. } if message.contains("strict mode") => {
+                "Declare the variable under [options] in the template's .conf or pass --var NAME=VALUE."
+                    .to_string()
+            }
+            Self::RenderError { .. } => {
+                "Check the Handlebars syntax near the reported line of the template file."
+                    .to_string()
+            }
+            Self::EnvDenied { denied, .. } => match denied.setting {
+                EnvSetting::Disabled => {
+                    "Rerun without --no-env-helpers, or stop reading the variable in the template."
+                        .to_string()
+                }
+                EnvSetting::Deny => format!(
+                    "Remove the pattern matching {} from env_deny in your configuration, or stop reading it in the template.",
+                    denied.name
+                ),
+                EnvSetting::Allow => format!(
+                    "Add {} to env_allow in your configuration, or stop reading it in the template.",
+                    denied.name
+                ),
+            },
+            Self::RenderErrors { .. } => {
+                "Fix the reported template files and rerun with --all-errors until none are left."
+                    .to_string()
+            }
+            Self::IoError { .. } => {
+                "Check that the output directory exists and is writable.".to_string()
+            }
+            Self::ConfigError { .. } => {
+                "Check the configuration file passed with --config (or .cli-frontend.conf)."
+                    .to_string()
+            }
+            Self::NoTemplates {
+                starter: Some(starter),
+                ..
+            } => format!(
+                "Run `cli-frontend bootstrap` to install the built-in templates or `cli-frontend bootstrap --from {}` to clone your starter templates.",
+                starter
+            ),
+            Self::NoTemplates { .. } => {
+                "Run `cli-frontend bootstrap` to install the built-in templates, or point templates_dir at your templates."
+                    .to_string()
+            }
+            Self::VerifyFailed { .. } => {
+                "The generated files were kept; fix the template they came from or the reported variables and regenerate."
+                    .to_string()
+            }
+            Self::NothingToGenerate { suggestions, .. } if !suggestions.is_empty() => format!(
+                "Pass {} to include files.",
+                suggestions
+                    .iter()
+                    .map(|suggestion| format!("--var {}", suggestion))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+            Self::NothingToGenerate { .. } => {
+                "Check the [files] conditions in the template's .conf.".to_string()
+            }
+            Self::GoldenDrift { .. } => {
+                "Review the diff; if the change is intended, rerun with --update to refresh the golden files."
+                    .to_string()
+            }
+            Self::UnsupportedTemplate { required, .. } => format!(
+                "Upgrade cli-frontend to {} or newer (rerun the install script or `cargo install --path .` from an updated checkout).",
+                required
+            ),
+            Self::Interrupted => "The run was rolled back; nothing it generated was kept.".to_string(),
+            Self::TimedOut { .. } => {
+                "The run was rolled back; raise --timeout or look for a slow template or helper."
+                    .to_string()
+            }
+        }
+    }
+
+    /// Wrap a Handlebars parse or render failure of `file`
+    ///
+    /// Keeps the line and source snippet from a [`RenderFailure`] in the
+    /// chain, or locates the error in `content` when there is none. A
+    /// variable refused by the env policy becomes [`CliError::EnvDenied`].
+    pub fn render(file: &Path, content: &str, error: &anyhow::Error) -> Self {
+        let failure = error
+            .downcast_ref::<RenderFailure>()
+            .cloned()
+            .unwrap_or_else(|| RenderFailure::new(content, error.as_ref(), 1));
+
+        if let Some(denied) = failure.env_denied {
+            return Self::EnvDenied {
+                file: file.to_path_buf(),
+                line: failure.line,
+                denied,
+            };
+        }
+
+        let message = if failure.snippet.is_empty() {
+            failure.reason
+        } else {
+            format!("{}\n{}", failure.reason, failure.snippet)
+        };
+
+        Self::RenderError {
+            file: file.to_path_buf(),
+            line: failure.line,
+            message,
+        }
+    }
+
+    /// Classify an `anyhow` error chain
+    ///
+    /// Returns the first [`CliError`] in the chain; otherwise I/O failures
+    /// become [`CliError::IoError`], and anything else is unclassified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::error::CliError;
+    /// use anyhow::Context;
+    ///
+    /// let error = std::fs::read("/nonexistent/file.txt")
+    ///     .context("Could not read template")
+    ///     .unwrap_err();
+    /// assert!(matches!(CliError::classify(&error), Some(CliError::IoError { .. })));
+    /// ```
+    pub fn classify(error: &anyhow::Error) -> Option<Self> {
+        if let Some(cli_error) = error.downcast_ref::<CliError>() {
+            return Some(cli_error.clone());
+        }
+
+        if error.chain().any(|e| e.is::<std::io::Error>()) {
+            return Some(Self::IoError {
+                message: format!("{:#}", error),
+            });
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TemplateNotFound { name, .. } => write!(f, "Unknown type '{}'", name),
+            Self::AmbiguousTemplate { name, candidates } => {
+                write!(f, "Template '{}' is provided by several packs:", name)?;
+                for candidate in candidates {
+                    write!(f, "\n  {}", candidate)?;
+                }
+                Ok(())
+            }
+            Self::InternalTemplate { name } => write!(f, "Template '{}' is internal", name),
+            Self::InvalidVariable { name, message } => {
+                write!(f, "Invalid variable '{}': {}", name, message)
+            }
+            Self::RenderError {
+                file,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{}: {}", file.display(), line, message),
+            Self::RenderError {
+                file,
+                line: None,
+                message,
+            } => write!(f, "{}: {}", file.display(), message),
+            Self::EnvDenied {
+                file,
+                line: Some(line),
+                denied,
+            } => write!(f, "{}:{}: {}", file.display(), line, denied),
+            Self::EnvDenied {
+                file,
+                line: None,
+                denied,
+            } => write!(f, "{}: {}", file.display(), denied),
+            Self::RenderErrors { errors } => {
+                write!(f, "{} files failed to render:", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  {}", error.replace('\n', "\n    "))?;
+                }
+                Ok(())
+            }
+            Self::IoError { message } | Self::ConfigError { message } => write!(f, "{}", message),
+            Self::NoTemplates { templates_dir, .. } => {
+                write!(f, "No templates found in {}", templates_dir.display())
+            }
+            Self::VerifyFailed { command, errors: 0 } => {
+                write!(f, "Verification failed: `{}` reported errors", command)
+            }
+            Self::VerifyFailed { command, errors } => write!(
+                f,
+                "Verification failed: `{}` reported {} type error(s)",
+                command, errors
+            ),
+            Self::NothingToGenerate {
+                template, filtered, ..
+            } => {
+                write!(
+                    f,
+                    "Template '{}' generates no files with these variables:",
+                    template
+                )?;
+                for file in filtered {
+                    write!(f, "\n  {}", file)?;
+                }
+                Ok(())
+            }
+            Self::GoldenDrift { template, files } => write!(
+                f,
+                "Template '{}' drifted from its golden output in {} file(s)",
+                template, files
+            ),
+            Self::UnsupportedTemplate {
+                template,
+                required,
+                current,
+            } => write!(
+                f,
+                "Template '{}' requires cli-frontend {} or newer, but this is {}",
+                template, required, current
+            ),
+            Self::Interrupted => write!(f, "Interrupted"),
+            Self::TimedOut { seconds } => write!(f, "Timed out after {}s", seconds),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_classify_finds_cli_error_under_context() {
+        let error = Err::<(), _>(CliError::InvalidVariable {
+            name: "style".to_string(),
+            message: "expected one of: css, scss".to_string(),
+        })
+        .context("Generation failed")
+        .unwrap_err();
+
+        let classified = CliError::classify(&error).unwrap();
+        assert_eq!(classified.exit_code(), 4);
+        assert_eq!(
+            classified.to_string(),
+            "Invalid variable 'style': expected one of: css, scss"
+        );
+        assert!(CliError::classify(&anyhow::anyhow!("plain failure")).is_none());
+    }
+
+    #[test]
+    fn test_template_not_found_hint_suggests_closest_name() {
+        let error = CliError::TemplateNotFound {
+            name: "compnent".to_string(),
+            available: vec!["component".to_string(), "hook".to_string()],
+        };
+        assert!(error.hint().starts_with("Did you mean 'component'?"));
+    }
+
+    #[test]
+    fn test_ambiguous_template_lists_each_candidate() {
+        let candidate = |pack: &str, description: &str| TemplateCandidate {
+            name: format!("{}:card", pack),
+            path: PathBuf::from(pack).join("card"),
+            description: description.to_string(),
+        };
+        let error = CliError::AmbiguousTemplate {
+            name: "card".to_string(),
+            candidates: vec![candidate("acme", "Product card"), candidate("ui", "")],
+        };
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Template 'card' is provided by several packs:\n  acme:card ({}): Product card\n  ui:card ({})",
+                Path::new("acme").join("card").display(),
+                Path::new("ui").join("card").display()
+            )
+        );
+        assert_eq!(
+            error.hint(),
+            "Name the pack: --type acme:card or --type ui:card."
+        );
+    }
+
+    #[test]
+    fn test_render_error_display_includes_location() {
+        let error = CliError::RenderError {
+            file: PathBuf::from("templates/hook/use$FILE_NAME.ts"),
+            line: Some(3),
+            message: "unclosed block".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "templates/hook/use$FILE_NAME.ts:3: unclosed block"
+        );
+    }
+
+    #[test]
+    fn test_render_classifies_env_policy_denials() {
+        let handlebars = crate::template_engine::renderer::create_handlebars_for(
+            &crate::template_engine::config::TemplateConfig {
+                env_policy: crate::template_engine::helpers::EnvPolicy {
+                    allow: vec!["PUBLIC_*".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let content = "const url = 1;\nconst key = \"{{env \"AWS_SECRET_KEY\"}}\";\n";
+        let error = crate::template_engine::renderer::render_template(
+            &handlebars,
+            content,
+            &serde_json::json!({}),
+        )
+        .unwrap_err();
+
+        let error = CliError::render(Path::new("api/config.ts"), content, &error);
+        let CliError::EnvDenied { line, denied, .. } = &error else {
+            panic!("expected an env policy error, got {:?}", error);
+        };
+        assert_eq!(*line, Some(2));
+        assert_eq!(denied.name, "AWS_SECRET_KEY");
+        assert_eq!(denied.setting, EnvSetting::Allow);
+        assert_eq!(error.exit_code(), 5);
+        assert!(error.hint().contains("env_allow"));
+        assert!(!error.hint().contains("Handlebars"));
+    }
+
+    #[test]
+    fn test_render_errors_lists_each_file() {
+        let error = CliError::RenderErrors {
+            errors: vec![
+                "hook/use.ts:3: unclosed block\n 3 | {{#if}}".to_string(),
+                "hook/use.test.ts: missing helper".to_string(),
+            ],
+        };
+        assert_eq!(error.exit_code(), 5);
+        assert_eq!(
+            error.to_string(),
+            "2 files failed to render:\n  hook/use.ts:3: unclosed block\n     3 | {{#if}}\n  hook/use.test.ts: missing helper"
+        );
+    }
+
+    #[test]
+    fn test_render_extracts_handlebars_line() {
+        let handlebars = handlebars::Handlebars::new();
+        let error = handlebars
+            .render_template("first\nsecond {{#if flag}}\n", &serde_json::json!({}))
+            .context("Template rendering failed")
+            .unwrap_err();
+
+        let CliError::RenderError { line, message, .. } =
+            CliError::render(Path::new("hook/use.ts"), "", &error)
+        else {
+            panic!("expected a render error");
+        };
+        assert!(line.is_some());
+        assert!(!message.contains("Template rendering failed"));
+    }
+}
@@ -0,0 +1,410 @@
+//! Output backends for generated files.
+//!
+//! The template engine writes everything it generates through the
+//! [`FileSystem`] trait. [`TokioFileSystem`] writes to disk and is the default;
+//! [`MemoryFileSystem`] keeps files in memory for hermetic tests, and other
+//! backends (archives, remote storage) can be plugged in with
+//! `TemplateEngine::with_file_system`.
+//!
+//! Templates themselves are always read from the local disk; only output
+//! goes through the backend.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Mutex;
+use tokio::fs;
+
+/// Boxed future returned by [`FileSystem`] methods
+pub type FsFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Destination for generated files
+///
+/// Methods return boxed futures so backends can be used as
+/// `Arc<dyn FileSystem>`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::file_system::{FileSystem, MemoryFileSystem};
+/// use std::path::Path;
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let fs = MemoryFileSystem::new();
+/// fs.write(Path::new("out/Button.tsx"), b"export {}").await?;
+///
+/// assert!(fs.exists(Path::new("out")));
+/// assert_eq!(fs.read(Path::new("out/Button.tsx")), Some(b"export {}".to_vec()));
+/// # Ok(())
+/// # }
+/// ```
+#[allow(dead_code)] // Public API trait
+pub trait FileSystem: Send + Sync {
+    /// Create a directory and all missing parents
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()>;
+
+    /// Write a file, creating parent directories as needed
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> FsFuture<'a, ()>;
+
+    /// Copy a file from the local disk (a template file) to `path`
+    fn copy_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()>;
+
+    /// Move a file from the local disk (a staged file) to `path`
+    fn move_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.copy_file(source, path).await?;
+            fs::remove_file(source)
+                .await
+                .with_context(|| format!("Could not remove file: {}", source.display()))
+        })
+    }
+
+    /// Set the Unix mode of a written file
+    ///
+    /// Backends without file modes (archives, non-Unix disks) keep the
+    /// default no-op.
+    fn set_permissions<'a>(&'a self, _path: &'a Path, _mode: u32) -> FsFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Whether a file or directory exists
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Contents of an existing file, or `None` when there is none
+    ///
+    /// Used to compare regenerated files with what is already there;
+    /// backends that always start empty (archives) keep the default.
+    fn read_existing<'a>(&'a self, _path: &'a Path) -> FsFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Remove a file, if it exists
+    ///
+    /// Used to roll back a failed generation; backends whose output is
+    /// discarded on failure (archives) keep the default no-op.
+    fn remove_file<'a>(&'a self, _path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Remove a directory if it exists and is empty
+    fn remove_empty_dir<'a>(&'a self, _path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Writes generated files to disk with tokio
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioFileSystem;
+
+impl FileSystem for TokioFileSystem {
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            fs::create_dir_all(path)
+                .await
+                .with_context(|| format!("Could not create directory: {}", path.display()))
+        })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await.with_context(|| {
+                    format!("Could not create parent directory: {}", parent.display())
+                })?;
+            }
+
+            fs::write(path, contents)
+                .await
+                .with_context(|| format!("Could not write output file: {}", path.display()))
+        })
+    }
+
+    fn copy_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await.with_context(|| {
+                    format!("Could not create parent directory: {}", parent.display())
+                })?;
+            }
+
+            fs::copy(source, path)
+                .await
+                .with_context(|| format!("Could not copy file to: {}", path.display()))?;
+            Ok(())
+        })
+    }
+
+    fn move_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await.with_context(|| {
+                    format!("Could not create parent directory: {}", parent.display())
+                })?;
+            }
+
+            // Renaming fails across file systems (a separate /tmp); copy instead
+            if fs::rename(source, path).await.is_err() {
+                let (from, to) = (source.to_path_buf(), path.to_path_buf());
+                tokio::task::spawn_blocking(move || copy_atomically(&from, &to)).await??;
+                fs::remove_file(source)
+                    .await
+                    .with_context(|| format!("Could not remove file: {}", source.display()))?;
+            }
+            Ok(())
+        })
+    }
+
+    #[cfg(unix)]
+    fn set_permissions<'a>(&'a self, path: &'a Path, mode: u32) -> FsFuture<'a, ()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Box::pin(async move {
+            fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+                .await
+                .with_context(|| format!("Could not set permissions of: {}", path.display()))
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read_existing<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            match fs::read(path).await {
+                Ok(contents) => Ok(Some(contents)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => {
+                    Err(e).with_context(|| format!("Could not read file: {}", path.display()))
+                }
+            }
+        })
+    }
+
+    fn remove_file<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            match fs::remove_file(path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(e).with_context(|| format!("Could not remove file: {}", path.display()))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn remove_empty_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let mut entries = match fs::read_dir(path).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("Could not read directory: {}", path.display()))
+                }
+            };
+            if entries.next_entry().await?.is_some() {
+                return Ok(());
+            }
+            fs::remove_dir(path)
+                .await
+                .with_context(|| format!("Could not remove directory: {}", path.display()))
+        })
+    }
+}
+
+/// Keeps generated files in memory
+#[allow(dead_code)] // Public API
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    files: Mutex<BTreeMap<PathBuf, Vec<u8>>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+#[allow(dead_code)] // Public API
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Contents of a written file
+    pub fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.lock().unwrap().get(path).cloned()
+    }
+
+    /// Snapshot of every written file, sorted by path
+    pub fn files(&self) -> BTreeMap<PathBuf, Vec<u8>> {
+        self.files.lock().unwrap().clone()
+    }
+
+    fn add_dir(&self, path: &Path) {
+        let mut dirs = self.dirs.lock().unwrap();
+        for ancestor in path.ancestors() {
+            if ancestor.as_os_str().is_empty() || !dirs.insert(ancestor.to_path_buf()) {
+                break;
+            }
+        }
+    }
+
+    fn add_file(&self, path: &Path, contents: Vec<u8>) {
+        if let Some(parent) = path.parent() {
+            self.add_dir(parent);
+        }
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), contents);
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.add_dir(path);
+            Ok(())
+        })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.add_file(path, contents.to_vec());
+            Ok(())
+        })
+    }
+
+    fn copy_file<'a>(&'a self, source: &'a Path, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let contents = fs::read(source)
+                .await
+                .with_context(|| format!("Could not read file: {}", source.display()))?;
+            self.add_file(path, contents);
+            Ok(())
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.lock().unwrap().contains_key(path) || self.dirs.lock().unwrap().contains(path)
+    }
+
+    fn read_existing<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move { Ok(self.read(path)) })
+    }
+
+    fn remove_file<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            self.files.lock().unwrap().remove(path);
+            Ok(())
+        })
+    }
+
+    fn remove_empty_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let in_use = self
+                .files
+                .lock()
+                .unwrap()
+                .keys()
+                .any(|f| f.starts_with(path))
+                || self
+                    .dirs
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|d| d != path && d.starts_with(path));
+            if !in_use {
+                self.dirs.lock().unwrap().remove(path);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Copy `source` to a temp file next to `path`, then rename it over `path`
+///
+/// A copy interrupted by a crash or panic leaves `path` as it was instead
+/// of half-written.
+fn copy_atomically(source: &Path, path: &Path) -> Result<()> {
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut copy = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Could not create a file in: {}", dir.display()))?;
+    let mut original = std::fs::File::open(source)
+        .with_context(|| format!("Could not read file: {}", source.display()))?;
+    std::io::copy(&mut original, copy.as_file_mut())
+        .and_then(|_| {
+            copy.as_file()
+                .set_permissions(original.metadata()?.permissions())
+        })
+        .with_context(|| format!("Could not copy file to: {}", path.display()))?;
+    copy.persist(path)
+        .with_context(|| format!("Could not move file to: {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_tokio_file_system_writes_to_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join("file.txt");
+
+        TokioFileSystem.write(&path, b"hello").await.unwrap();
+
+        assert!(TokioFileSystem.exists(&path));
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+
+        let nested = temp_dir.path().join("nested");
+        TokioFileSystem.remove_empty_dir(&nested).await.unwrap();
+        assert!(nested.is_dir());
+        TokioFileSystem.remove_file(&path).await.unwrap();
+        TokioFileSystem.remove_file(&path).await.unwrap();
+        TokioFileSystem.remove_empty_dir(&nested).await.unwrap();
+        assert!(!nested.exists());
+    }
+
+    #[test]
+    fn test_copy_atomically_replaces_target_whole() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, target) = (
+            temp_dir.path().join("0"),
+            temp_dir.path().join("Button.tsx"),
+        );
+        std::fs::write(&source, "new").unwrap();
+        std::fs::write(&target, "old").unwrap();
+
+        copy_atomically(&source, &target).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_memory_file_system_tracks_files_and_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("icon.png");
+        std::fs::write(&source, [0u8, 1, 2]).unwrap();
+
+        let fs = MemoryFileSystem::new();
+        fs.create_dir_all(Path::new("out/empty")).await.unwrap();
+        fs.copy_file(&source, Path::new("out/assets/icon.png"))
+            .await
+            .unwrap();
+
+        assert!(fs.exists(Path::new("out/empty")));
+        assert!(fs.exists(Path::new("out/assets")));
+        assert!(!fs.exists(Path::new("elsewhere")));
+        assert_eq!(
+            fs.read(Path::new("out/assets/icon.png")),
+            Some(vec![0, 1, 2])
+        );
+        assert_eq!(fs.files().len(), 1);
+        assert!(!temp_dir.path().join("out").exists());
+    }
+}
@@ -2,10 +2,50 @@
 //!
 //! This library provides template-based code generation for React applications,
 //! including components, hooks, contexts, services, and various architectural patterns.
+//!
+//! Without the default `runtime` feature only the pure rendering core is
+//! built (naming, helpers, template data, Handlebars rendering), which
+//! compiles to wasm32 for in-browser previews.
 
+#[cfg(feature = "runtime")]
+mod app;
+#[cfg(feature = "runtime")]
+pub mod archive;
+#[cfg(feature = "runtime")]
+pub mod audit;
+#[cfg(feature = "runtime")]
+pub mod bootstrap;
+#[cfg(feature = "runtime")]
+pub mod cli;
+#[cfg(feature = "runtime")]
+mod commands;
+#[cfg(feature = "runtime")]
+pub mod completion;
+#[cfg(feature = "runtime")]
 pub mod config;
+pub mod error;
+#[cfg(feature = "runtime")]
+pub mod file_system;
+#[cfg(feature = "napi")]
+pub mod node;
+#[cfg(feature = "runtime")]
+pub mod repl;
+#[cfg(feature = "runtime")]
+pub mod rpc;
+#[cfg(feature = "runtime")]
+pub mod server;
 pub mod template_engine;
 pub mod types;
+#[cfg(feature = "runtime")]
+pub mod ui;
+#[cfg(feature = "runtime")]
+mod wizard;
+
+#[cfg(all(test, feature = "runtime"))]
+mod tests;
 
 // Re-export commonly used types for convenience
 pub use template_engine::{HandlebarsRenderer, TemplateConfig, TemplateRenderer};
+
+#[cfg(feature = "runtime")]
+pub use app::{run, RunIo, RunReport};
@@ -0,0 +1,216 @@
+//! Node.js bindings (`napi` feature).
+//!
+//! Exposes the library API through N-API so JS tooling (Nx plugins, npm
+//! scripts) can list, describe and generate templates in-process instead of
+//! spawning the binary. Build the addon with:
+//!
+//! ```text
+//! cargo rustc --lib --release --features napi --crate-type cdylib
+//! ```
+//!
+//! and load the resulting library as a `.node` file:
+//!
+//! ```js
+//! const cli = require('./cli-frontend.node');
+//! const files = await cli.generate({ name: 'Button', template: 'component', write: false });
+//! ```
+//!
+//! The exported functions are thin wrappers over plain Rust ones, which are
+//! tested without Node.js against napi's no-op bindings:
+//!
+//! ```text
+//! cargo test --lib --features napi,napi/noop,napi-derive/noop node::
+//! ```
+
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::file_system::{FileSystem, TokioFileSystem};
+use crate::template_engine::{GeneratedFile, TemplateEngine};
+
+/// Where templates come from and where files go
+#[napi(object)]
+#[derive(Default)]
+pub struct EngineOptions {
+    /// Path to a `.cli-frontend.conf` (defaults to the CLI's lookup)
+    pub config: Option<String>,
+    /// Overrides the configured templates directory
+    pub templates_dir: Option<String>,
+    /// Overrides the configured output directory
+    pub output_dir: Option<String>,
+}
+
+/// Options for `generate`
+#[napi(object)]
+pub struct GenerateOptions {
+    pub name: String,
+    pub template: String,
+    pub vars: Option<HashMap<String, String>>,
+    /// Write the files under the output directory (default `true`)
+    pub write: Option<bool>,
+    pub config: Option<String>,
+    pub templates_dir: Option<String>,
+    pub output_dir: Option<String>,
+}
+
+/// A generated file
+#[napi(object)]
+pub struct NodeGeneratedFile {
+    /// Path relative to the output directory
+    pub path: String,
+    pub contents: Buffer,
+}
+
+fn to_napi_error(error: anyhow::Error) -> napi::Error {
+    napi::Error::from_reason(format!("{:#}", error))
+}
+
+/// Build an engine the same way the CLI does, applying overrides
+async fn engine(options: EngineOptions) -> anyhow::Result<(TemplateEngine, PathBuf)> {
+    let config = Config::load(&options.config.map(PathBuf::from)).await?;
+
+    let templates_dir = options
+        .templates_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config.templates_dir().clone());
+    let output_dir = options
+        .output_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config.output_dir().clone());
+
+    let engine = TemplateEngine::new(templates_dir, output_dir.clone())?
+        .with_defaults(config.template_defaults());
+    Ok((engine, output_dir))
+}
+
+/// List available template names
+#[napi]
+pub async fn list_templates(options: Option<EngineOptions>) -> napi::Result<Vec<String>> {
+    let (engine, _) = engine(options.unwrap_or_default())
+        .await
+        .map_err(to_napi_error)?;
+    engine.list_templates().map_err(to_napi_error)
+}
+
+/// Describe a template's metadata, variables and files
+#[napi]
+pub async fn describe_template(
+    template: String,
+    options: Option<EngineOptions>,
+) -> napi::Result<serde_json::Value> {
+    let (engine, _) = engine(options.unwrap_or_default())
+        .await
+        .map_err(to_napi_error)?;
+    let description = engine
+        .template_description(&template)
+        .await
+        .map_err(to_napi_error)?;
+
+    serde_json::to_value(description).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Render a template, writing the files unless `write` is `false`
+#[napi]
+pub async fn generate(options: GenerateOptions) -> napi::Result<Vec<NodeGeneratedFile>> {
+    let files = render(options).await.map_err(to_napi_error)?;
+
+    Ok(files
+        .into_iter()
+        .map(|file| NodeGeneratedFile {
+            path: file.path.to_string_lossy().replace('\\', "/"),
+            contents: file.contents.into(),
+        })
+        .collect())
+}
+
+/// `generate` without the N-API types
+async fn render(options: GenerateOptions) -> anyhow::Result<Vec<GeneratedFile>> {
+    let (engine, output_dir) = engine(EngineOptions {
+        config: options.config,
+        templates_dir: options.templates_dir,
+        output_dir: options.output_dir,
+    })
+    .await?;
+
+    let files = engine
+        .render_to_memory(
+            &options.name,
+            &options.template,
+            options.vars.unwrap_or_default(),
+        )
+        .await?;
+
+    if options.write.unwrap_or(true) {
+        for file in &files {
+            TokioFileSystem
+                .write(&output_dir.join(&file.path), &file.contents)
+                .await?;
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_render_lists_and_writes_like_the_cli() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let templates = temp_dir.path().join("templates");
+        std::fs::create_dir_all(templates.join("widget")).unwrap();
+        std::fs::write(
+            templates.join("widget").join("$FILE_NAME.ts"),
+            "// {{name}} by {{author_name}} <{{author_email}}>, {{style}}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            templates.join("widget").join(".conf"),
+            "[options]\nstyle=css\n",
+        )
+        .unwrap();
+        let config = temp_dir.path().join("test.conf");
+        std::fs::write(
+            &config,
+            format!(
+                "templates_dir={}\nauthor_name=Ana Díaz\nauthor_email=ana@acme.dev\n",
+                templates.display()
+            ),
+        )
+        .unwrap();
+        let config = Some(config.display().to_string());
+        let output = temp_dir.path().join("out");
+
+        let (engine, _) = engine(EngineOptions {
+            config: config.clone(),
+            ..EngineOptions::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(engine.list_templates().unwrap(), ["widget"]);
+
+        let options = |write| GenerateOptions {
+            name: "Card".to_string(),
+            template: "widget".to_string(),
+            vars: Some(HashMap::from([("style".to_string(), "scss".to_string())])),
+            write: Some(write),
+            config: config.clone(),
+            templates_dir: None,
+            output_dir: Some(output.display().to_string()),
+        };
+        let files = render(options(false)).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, PathBuf::from("Card").join("Card.ts"));
+        assert_eq!(
+            files[0].as_text(),
+            Some("// Card by Ana Díaz <ana@acme.dev>, scss\n")
+        );
+        assert!(!output.exists());
+
+        render(options(true)).await.unwrap();
+        assert!(output.join("Card").join("Card.ts").is_file());
+    }
+}
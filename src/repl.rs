@@ -0,0 +1,194 @@
+//! Interactive expression evaluation (`cli-frontend repl <template>`).
+//!
+//! Loads the data a template renders a sample name with — variables,
+//! `{var}_is_{value}` helpers, derived names — and renders each line read
+//! against it with the template's engine, so complex conditionals can be
+//! debugged one expression at a time. A line without `{{` is wrapped in
+//! `{{ }}`, so `pascal_case name` works as well as `{{pascal_case name}}`.
+//!
+//! Lines starting with `:` are commands:
+//!
+//! - `:data` - print the data model as JSON
+//! - `:set KEY=VALUE` - change a variable and reload the data
+//! - `:help` - list the commands
+//! - `:quit` - leave (so does end of input)
+
+use anyhow::Result;
+use colored::*;
+use std::collections::HashMap;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::template_engine::renderer::render_with_data;
+use crate::template_engine::{TemplateConfig, TemplateEngine};
+use crate::ui::symbols;
+
+const HELP: &str = "\
+Type an expression such as {{pascal_case name}} or style_is_scss to render it.
+  :data            print the data model as JSON
+  :set KEY=VALUE   change a variable and reload the data
+  :help            show this help
+  :quit            leave";
+
+/// Evaluate expressions read from stdin
+pub async fn run_stdio(
+    engine: &TemplateEngine,
+    template: &str,
+    name: &str,
+    vars: HashMap<String, String>,
+) -> Result<()> {
+    let mut reader = tokio::io::BufReader::new(tokio::io::stdin());
+    let mut writer = tokio::io::stdout();
+    run(engine, template, name, vars, &mut reader, &mut writer).await
+}
+
+/// Evaluate expressions read from `reader` until `:quit` or end of input
+pub async fn run<R, W>(
+    engine: &TemplateEngine,
+    template: &str,
+    name: &str,
+    mut vars: HashMap<String, String>,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let (mut config, mut data) = engine.template_data(name, template, &vars).await?;
+    let intro = format!(
+        "{} Evaluating against '{}' with name '{}' (:help for commands)\n",
+        symbols::INSPECT.as_str().bold(),
+        template.bold(),
+        name
+    );
+    writer.write_all(intro.as_bytes()).await?;
+
+    let prompt = format!("{}> ", template);
+    let mut line = String::new();
+    loop {
+        writer.write_all(prompt.as_bytes()).await?;
+        writer.flush().await?;
+
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            writer.write_all(b"\n").await?;
+            break;
+        }
+
+        let input = line.trim();
+        let reply = match input {
+            "" => continue,
+            ":quit" | ":q" | ":exit" => break,
+            ":help" => HELP.to_string(),
+            ":data" => serde_json::to_string_pretty(&data)?,
+            _ if input.starts_with(":set ") => {
+                match set_variable(engine, template, name, &mut vars, &input[5..]).await {
+                    Ok((new_config, new_data)) => {
+                        (config, data) = (new_config, new_data);
+                        format!("{} {}", symbols::CHECK.as_str().green(), input[5..].trim())
+                    }
+                    Err(error) => failure(&error),
+                }
+            }
+            _ if input.starts_with(':') => {
+                format!("Unknown command '{}', try :help", input)
+            }
+            _ => match render_with_data(&expression(input), &config, &data) {
+                Ok(rendered) => rendered,
+                Err(error) => failure(&error),
+            },
+        };
+        writer.write_all(format!("{}\n", reply).as_bytes()).await?;
+    }
+
+    writer.flush().await?;
+    Ok(())
+}
+
+/// `input` as a template: wrapped in `{{ }}` unless it already has an expression
+fn expression(input: &str) -> String {
+    if input.contains("{{") || input.contains("{%") {
+        input.to_string()
+    } else {
+        format!("{{{{{}}}}}", input)
+    }
+}
+
+/// Apply `KEY=VALUE` to `vars` and reload the data, leaving `vars` unchanged on error
+async fn set_variable(
+    engine: &TemplateEngine,
+    template: &str,
+    name: &str,
+    vars: &mut HashMap<String, String>,
+    assignment: &str,
+) -> Result<(TemplateConfig, serde_json::Value)> {
+    let Some((key, value)) = assignment.split_once('=') else {
+        anyhow::bail!("Expected KEY=VALUE, got '{}'", assignment.trim());
+    };
+
+    let mut updated = vars.clone();
+    updated.insert(key.trim().to_string(), value.trim().to_string());
+    let loaded = engine.template_data(name, template, &updated).await?;
+    *vars = updated;
+    Ok(loaded)
+}
+
+fn failure(error: &anyhow::Error) -> String {
+    format!("{} {:#}", symbols::CROSS.as_str().red(), error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_expression_wraps_bare_input() {
+        assert_eq!(expression("pascal_case name"), "{{pascal_case name}}");
+        assert_eq!(expression("{{#if a}}x{{/if}}"), "{{#if a}}x{{/if}}");
+    }
+
+    #[tokio::test]
+    async fn test_repl_evaluates_expressions() {
+        colored::control::set_override(false);
+        let temp_dir = TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("widget");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "").unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[options]\nstyle=scss\nstyle_options=scss,css\n",
+        )
+        .unwrap();
+        let engine =
+            TemplateEngine::new(temp_dir.path().join("templates"), PathBuf::from(".")).unwrap();
+
+        let input =
+            "pascal_case name\n{{style_is_scss}}\n:set style=css\nstyle_is_scss\n{{#if}}\n:quit\n";
+        let mut reader = tokio::io::BufReader::new(input.as_bytes());
+        let mut output = Vec::new();
+        run(
+            &engine,
+            "widget",
+            "user_card",
+            HashMap::new(),
+            &mut reader,
+            &mut output,
+        )
+        .await
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let replies: Vec<&str> = output
+            .lines()
+            .skip(1)
+            .map(|line| line.trim_start_matches("widget> "))
+            .collect();
+        assert_eq!(replies[..2], ["UserCard", "true"]);
+        assert!(replies[2].ends_with("style=css"));
+        assert_eq!(replies[3], "false");
+        assert!(replies[4].contains("Template rendering failed at line 1"));
+        assert_eq!(replies[5], "1 | {{#if}}");
+    }
+}
@@ -0,0 +1,398 @@
+//! Editor integration over stdio JSON-RPC (`cli-frontend lsp-like --stdio`).
+//!
+//! Messages are JSON-RPC 2.0, framed with `Content-Length` headers like the
+//! Language Server Protocol, so editor extensions can reuse their LSP
+//! transport. Supported methods:
+//!
+//! - `initialize` - server name, version and method list
+//! - `templates/list` - template names
+//! - `templates/describe` - `{template}` → `TemplateDescription`
+//! - `variables/validate` - `{template, vars}` → `{valid, issues}`, valid
+//!   unless an issue is an error
+//! - `generate` - `{name, template, vars?, outputDir?, dryRun?}` → `{files}`
+//! - `shutdown` / `exit` - stop the server
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::file_system::{FileSystem, TokioFileSystem};
+use crate::template_engine::{IssueSeverity, TemplateEngine};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// Methods advertised by `initialize`
+const METHODS: &[&str] = &[
+    "initialize",
+    "templates/list",
+    "templates/describe",
+    "variables/validate",
+    "generate",
+    "shutdown",
+    "exit",
+];
+
+/// A JSON-RPC error object
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(SERVER_ERROR, format!("{:#}", error))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TemplateParams {
+    template: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateParams {
+    template: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateParams {
+    name: String,
+    template: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+    /// Overrides the configured output directory
+    output_dir: Option<PathBuf>,
+    /// Return file contents instead of writing them
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Serve JSON-RPC on stdin/stdout until `exit` or end of input
+pub async fn run_stdio(engine: &TemplateEngine, output_dir: &Path) -> Result<()> {
+    // stdout carries the protocol, so error messages must not contain ANSI codes
+    colored::control::set_override(false);
+
+    let mut reader = tokio::io::BufReader::new(tokio::io::stdin());
+    let mut writer = tokio::io::stdout();
+    run(engine, output_dir, &mut reader, &mut writer).await
+}
+
+/// Serve JSON-RPC over arbitrary streams
+pub async fn run<R, W>(
+    engine: &TemplateEngine,
+    output_dir: &Path,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    while let Some(body) = read_message(reader).await? {
+        let (response, exit) = handle_message(engine, output_dir, &body).await;
+
+        if let Some(response) = response {
+            write_message(writer, &response).await?;
+        }
+        if exit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read one `Content-Length` framed message, or `None` at end of input
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            if content_length.is_some() {
+                bail!("Unexpected end of input in message header");
+            }
+            return Ok(None);
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse().context("Invalid Content-Length")?);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or(0)];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(body))
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &Value) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Handle one message, returning the response (if any) and whether to exit
+async fn handle_message(
+    engine: &TemplateEngine,
+    output_dir: &Path,
+    body: &[u8],
+) -> (Option<Value>, bool) {
+    let message: Value = match serde_json::from_slice(body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, e.to_string());
+            return (Some(error_response(Value::Null, error)), false);
+        }
+    };
+
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        let error = RpcError::new(INVALID_REQUEST, "Missing method");
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        return (Some(error_response(id, error)), false);
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let exit = method == "exit";
+
+    let result = dispatch(engine, output_dir, method, params).await;
+
+    // Requests without an id are notifications and get no response
+    let response = message.get("id").cloned().map(|id| match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    });
+
+    (response, exit)
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+async fn dispatch(
+    engine: &TemplateEngine,
+    output_dir: &Path,
+    method: &str,
+    params: Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(json!({
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "methods": METHODS,
+        })),
+        "templates/list" => Ok(json!(engine.list_templates()?)),
+        "templates/describe" => {
+            let params: TemplateParams = parse_params(params)?;
+            ensure_template(engine, &params.template)?;
+            let description = engine.template_description(&params.template).await?;
+            Ok(json!(description))
+        }
+        "variables/validate" => {
+            let params: ValidateParams = parse_params(params)?;
+            ensure_template(engine, &params.template)?;
+            let description = engine.template_description(&params.template).await?;
+            let issues = description.validate(&params.vars);
+            let valid = !issues
+                .iter()
+                .any(|issue| issue.severity == IssueSeverity::Error);
+            Ok(json!({ "valid": valid, "issues": issues }))
+        }
+        "generate" => generate(engine, output_dir, parse_params(params)?).await,
+        "shutdown" | "exit" => Ok(Value::Null),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {}", method),
+        )),
+    }
+}
+
+fn ensure_template(engine: &TemplateEngine, template: &str) -> Result<(), RpcError> {
+    if engine.list_templates()?.iter().any(|t| t == template) {
+        Ok(())
+    } else {
+        Err(RpcError::new(
+            INVALID_PARAMS,
+            format!("Unknown template: {}", template),
+        ))
+    }
+}
+
+async fn generate(
+    engine: &TemplateEngine,
+    output_dir: &Path,
+    params: GenerateParams,
+) -> Result<Value, RpcError> {
+    ensure_template(engine, &params.template)?;
+
+    let files = engine
+        .render_to_memory(&params.name, &params.template, params.vars)
+        .await?;
+    let output_dir = params.output_dir.as_deref().unwrap_or(output_dir);
+
+    let mut results = Vec::with_capacity(files.len());
+    for file in files {
+        let path = output_dir.join(&file.path);
+
+        if params.dry_run {
+            results.push(json!({ "path": path, "content": file.as_text() }));
+        } else {
+            TokioFileSystem.write(&path, &file.contents).await?;
+            results.push(json!({ "path": path }));
+        }
+    }
+
+    Ok(json!({ "files": results }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn engine(temp_dir: &TempDir) -> TemplateEngine {
+        let template_dir = temp_dir.path().join("templates").join("hook");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("use$FILE_NAME.ts"),
+            "export function use$FILE_NAME() {}",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[options]\nwith_state=false\nwith_state_type=boolean\n",
+        )
+        .unwrap();
+
+        TemplateEngine::new(temp_dir.path().join("templates"), PathBuf::from(".")).unwrap()
+    }
+
+    fn frame(messages: &[Value]) -> Vec<u8> {
+        let mut input = Vec::new();
+        for message in messages {
+            let body = serde_json::to_vec(message).unwrap();
+            input.extend_from_slice(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes());
+            input.extend_from_slice(&body);
+        }
+        input
+    }
+
+    async fn exchange(
+        engine: &TemplateEngine,
+        output_dir: &Path,
+        messages: &[Value],
+    ) -> Vec<Value> {
+        let mut reader = std::io::Cursor::new(frame(messages));
+        let mut output = Vec::new();
+        run(engine, output_dir, &mut reader, &mut output)
+            .await
+            .unwrap();
+
+        let mut reader = std::io::Cursor::new(output);
+        let mut responses = Vec::new();
+        while let Some(body) = read_message(&mut reader).await.unwrap() {
+            responses.push(serde_json::from_slice(&body).unwrap());
+        }
+        responses
+    }
+
+    #[tokio::test]
+    async fn test_list_validate_and_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+
+        let responses = exchange(
+            &engine,
+            temp_dir.path(),
+            &[
+                json!({"jsonrpc": "2.0", "id": 1, "method": "templates/list"}),
+                json!({"jsonrpc": "2.0", "method": "initialized"}),
+                json!({"jsonrpc": "2.0", "id": 2, "method": "variables/validate",
+                       "params": {"template": "hook", "vars": {"with_state": "maybe"}}}),
+                json!({"jsonrpc": "2.0", "id": 3, "method": "templates/describe",
+                       "params": {"template": "nope"}}),
+                json!({"jsonrpc": "2.0", "id": 4, "method": "bogus"}),
+                json!({"jsonrpc": "2.0", "method": "exit"}),
+                json!({"jsonrpc": "2.0", "id": 5, "method": "templates/list"}),
+            ],
+        )
+        .await;
+
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["result"], json!(["hook"]));
+        assert_eq!(responses[1]["result"]["valid"], false);
+        assert_eq!(
+            responses[1]["result"]["issues"][0]["variable"],
+            "with_state"
+        );
+        assert_eq!(responses[2]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[3]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_generate_dry_run_and_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+        let output_dir = temp_dir.path().join("out");
+
+        let responses = exchange(
+            &engine,
+            &output_dir,
+            &[
+                json!({"jsonrpc": "2.0", "id": 1, "method": "generate",
+                       "params": {"name": "Auth", "template": "hook", "dryRun": true}}),
+                json!({"jsonrpc": "2.0", "id": 2, "method": "generate",
+                       "params": {"name": "Auth", "template": "hook"}}),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            responses[0]["result"]["files"][0]["content"],
+            "export function useAuth() {}"
+        );
+        assert_eq!(
+            responses[1]["result"]["files"][0]["path"],
+            json!(output_dir.join("Auth").join("useAuth.ts"))
+        );
+        assert!(output_dir.join("Auth").join("useAuth.ts").exists());
+    }
+}
@@ -0,0 +1,410 @@
+//! HTTP server mode (`cli-frontend serve`).
+//!
+//! Exposes a small JSON API on top of the library API so internal portals
+//! can offer scaffolding backed by this crate:
+//!
+//! - `GET /templates` - template names
+//! - `GET /templates/{name}` - a [`TemplateDescription`] of the template
+//! - `POST /generate` - `{"name", "template", "vars"}`, answered with a zip
+//!   of the generated files
+//!
+//! Requests are parsed by a minimal HTTP/1.1 implementation (one request per
+//! connection), which is all internal tooling needs.
+
+use anyhow::{bail, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::archive::{write_archive, ArchiveFormat};
+use crate::template_engine::{TemplateDescription, TemplateEngine};
+use crate::ui::{self, symbols};
+
+/// Largest accepted request head (request line and headers)
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+/// Largest accepted request body
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+/// How long a client gets to send its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed HTTP request
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// An HTTP response
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            headers: Vec::new(),
+            body: serde_json::to_vec(value).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        #[derive(Serialize)]
+        struct Error {
+            error: String,
+        }
+
+        Self::json(
+            status,
+            &Error {
+                error: message.into(),
+            },
+        )
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            _ => "Internal Server Error",
+        }
+    }
+
+    /// Serialize the status line, headers and body
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.reason(),
+            self.content_type,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+/// Body of `POST /generate`
+#[derive(Debug, Deserialize)]
+struct GenerateRequest {
+    name: String,
+    template: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+}
+
+/// Serve the API on `addr` until the process is stopped
+pub async fn serve(engine: Arc<TemplateEngine>, addr: &str) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Could not bind to {}", addr))?;
+
+    ui::status!(
+        "{} Serving templates on http://{}",
+        symbols::SERVER.as_str().bold(),
+        listener.local_addr()?.to_string().green()
+    );
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let engine = Arc::clone(&engine);
+
+        ui::spawn(async move {
+            if let Err(e) = handle_connection(&engine, stream, REQUEST_TIMEOUT).await {
+                ui::warning!("Warning: {:#}", e);
+            }
+        });
+    }
+}
+
+/// Read one request from `stream`, answer it and close the connection
+///
+/// A client that has not sent its whole request within `timeout` gets a
+/// 408, so a stalled connection does not hold its task forever.
+async fn handle_connection(
+    engine: &TemplateEngine,
+    mut stream: TcpStream,
+    timeout: Duration,
+) -> Result<()> {
+    let response = match tokio::time::timeout(timeout, read_request(&mut stream)).await {
+        Ok(Ok(request)) => {
+            let response = route(engine, &request).await;
+            ui::status!("{} {} -> {}", request.method, request.path, response.status);
+            response
+        }
+        Ok(Err(e)) => Response::error(400, format!("{:#}", e)),
+        Err(_) => Response::error(408, "Request timed out"),
+    };
+
+    stream.write_all(&response.to_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Parse a request head and read its `Content-Length` body
+async fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut head_size = 0;
+
+    let mut request_line = String::new();
+    head_size += reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        bail!("Malformed request line");
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line).await?;
+        head_size += read;
+        if head_size > MAX_HEAD_SIZE {
+            bail!("Request head too large");
+        }
+        if read == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().context("Invalid Content-Length")?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        bail!("Request body too large");
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(Request { method, path, body })
+}
+
+/// Dispatch a request to its handler
+pub async fn route(engine: &TemplateEngine, request: &Request) -> Response {
+    let segments: Vec<&str> = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["templates"]) => list_templates(engine),
+        ("GET", ["templates", name]) => describe(engine, name).await,
+        ("POST", ["generate"]) => generate(engine, &request.body).await,
+        (_, ["templates"]) | (_, ["templates", _]) | (_, ["generate"]) => {
+            Ok(Response::error(405, "Method not allowed"))
+        }
+        _ => Ok(Response::error(404, "Not found")),
+    };
+
+    result.unwrap_or_else(|e| Response::error(500, format!("{:#}", e)))
+}
+
+fn list_templates(engine: &TemplateEngine) -> Result<Response> {
+    Ok(Response::json(200, &engine.list_templates()?))
+}
+
+async fn describe(engine: &TemplateEngine, name: &str) -> Result<Response> {
+    if !is_known_template(engine, name)? {
+        return Ok(Response::error(404, format!("Unknown template: {}", name)));
+    }
+
+    let description: TemplateDescription = engine.template_description(name).await?;
+    Ok(Response::json(200, &description))
+}
+
+async fn generate(engine: &TemplateEngine, body: &[u8]) -> Result<Response> {
+    let request: GenerateRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return Ok(Response::error(400, format!("Invalid request body: {}", e))),
+    };
+
+    if !is_valid_name(&request.name) {
+        return Ok(Response::error(
+            400,
+            format!("Invalid name: {:?}", request.name),
+        ));
+    }
+    if !is_known_template(engine, &request.template)? {
+        return Ok(Response::error(
+            404,
+            format!("Unknown template: {}", request.template),
+        ));
+    }
+
+    let files = engine
+        .render_to_memory(&request.name, &request.template, request.vars)
+        .await?;
+    let entries: Vec<(String, Vec<u8>)> = files
+        .into_iter()
+        .map(|file| {
+            (
+                file.path.to_string_lossy().replace('\\', "/"),
+                file.contents,
+            )
+        })
+        .collect();
+
+    let mut zip = Cursor::new(Vec::new());
+    write_archive(&mut zip, ArchiveFormat::Zip, &entries)?;
+
+    Ok(Response {
+        status: 200,
+        content_type: "application/zip",
+        headers: vec![(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}.zip\"", request.name),
+        )],
+        body: zip.into_inner(),
+    })
+}
+
+/// Only accept templates the engine lists, so paths can't escape `templates_dir`
+fn is_known_template(engine: &TemplateEngine, name: &str) -> Result<bool> {
+    Ok(engine.list_templates()?.iter().any(|t| t == name))
+}
+
+/// Names become file names, so reject empty names and path components
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(['/', '\\', '"'])
+        && name != "."
+        && name != ".."
+        && !name.chars().any(char::is_control)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn engine(temp_dir: &TempDir) -> TemplateEngine {
+        let template_dir = temp_dir.path().join("hook");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("use$FILE_NAME.ts"),
+            "export function use$FILE_NAME() {}",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[metadata]\nname=Hook\n\n[options]\nwith_state=false\n",
+        )
+        .unwrap();
+
+        TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from(".")).unwrap()
+    }
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn json(response: &Response) -> serde_json::Value {
+        serde_json::from_slice(&response.body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_and_describe_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+
+        let response = route(&engine, &request("GET", "/templates", "")).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(json(&response), serde_json::json!(["hook"]));
+
+        let response = route(&engine, &request("GET", "/templates/hook", "")).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(json(&response)["title"], "Hook");
+        assert_eq!(
+            json(&response)["variables"]["with_state"]["default"],
+            "false"
+        );
+
+        let response = route(&engine, &request("GET", "/templates/..", "")).await;
+        assert_eq!(response.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_generate_returns_zip() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+
+        let body = r#"{"name": "Auth", "template": "hook", "vars": {"with_state": "true"}}"#;
+        let response = route(&engine, &request("POST", "/generate", body)).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/zip");
+
+        let zip = zip::ZipArchive::new(Cursor::new(response.body)).unwrap();
+        assert_eq!(
+            zip.file_names().collect::<Vec<_>>(),
+            vec!["Auth/useAuth.ts"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_rejects_bad_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+
+        let response = route(&engine, &request("POST", "/generate", "not json")).await;
+        assert_eq!(response.status, 400);
+
+        let body = r#"{"name": "../Auth", "template": "hook"}"#;
+        let response = route(&engine, &request("POST", "/generate", body)).await;
+        assert_eq!(response.status, 400);
+
+        let response = route(&engine, &request("GET", "/generate", "")).await;
+        assert_eq!(response.status, 405);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_request_times_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = engine(&temp_dir);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        client
+            .write_all(b"GET /templates HTTP/1.1\r\n")
+            .await
+            .unwrap();
+
+        let (stream, _) = listener.accept().await.unwrap();
+        handle_connection(&engine, stream, Duration::from_millis(50))
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout"));
+    }
+}
@@ -0,0 +1,433 @@
+//! Accessibility hints for templates with `with_a11y`.
+//!
+//! Guesses the ARIA role a component will need from the words in its name,
+//! reading from the last word (`UserMenuButton` is a button before it is a
+//! menu). Templates receive the guess as `a11y` to fill accessibility
+//! checklists and tests:
+//!
+//! - `a11y.role` / `a11y.element`: the primary role and the native element
+//!   that provides it, if any (`button` / `button`)
+//! - `a11y.keyboard` / `a11y.attributes`: what the role needs to support
+//! - `a11y.roles`: every role suggested by the name, primary first
+//!
+//! An `aria_role` variable overrides the guess.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::naming::to_kebab_case;
+
+/// What a WAI-ARIA role expects from a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RoleHint {
+    pub role: &'static str,
+    /// Native element with this role, e.g. `button`; empty when none fits
+    pub element: &'static str,
+    /// Whether users operate it (as opposed to landmarks and content)
+    pub interactive: bool,
+    pub keyboard: &'static [&'static str],
+    pub attributes: &'static [&'static str],
+}
+
+/// Name words hinting at each role
+const ROLE_HINTS: &[(&[&str], RoleHint)] = &[
+    (
+        &["button", "btn", "cta"],
+        RoleHint {
+            role: "button",
+            element: "button",
+            interactive: true,
+            keyboard: &["Enter and Space activate it"],
+            attributes: &[
+                "aria-label when it only shows an icon",
+                "aria-pressed when it toggles",
+            ],
+        },
+    ),
+    (
+        &["link", "anchor"],
+        RoleHint {
+            role: "link",
+            element: "a",
+            interactive: true,
+            keyboard: &["Enter follows the link"],
+            attributes: &["href, so it is focusable and announced as a link"],
+        },
+    ),
+    (
+        &["modal", "dialog", "drawer", "lightbox", "popup"],
+        RoleHint {
+            role: "dialog",
+            element: "dialog",
+            interactive: true,
+            keyboard: &[
+                "Escape closes it",
+                "Tab stays inside while it is open",
+                "Focus returns to the trigger when it closes",
+            ],
+            attributes: &[
+                "aria-modal=\"true\"",
+                "aria-labelledby pointing at its title",
+            ],
+        },
+    ),
+    (
+        &["alert"],
+        RoleHint {
+            role: "alert",
+            element: "",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["Rendered empty first, then filled, so it is announced"],
+        },
+    ),
+    (
+        &["toast", "snackbar", "notification", "status"],
+        RoleHint {
+            role: "status",
+            element: "output",
+            interactive: false,
+            keyboard: &["Actions inside are reachable without a mouse before it hides"],
+            attributes: &["aria-live=\"polite\"", "aria-atomic=\"true\""],
+        },
+    ),
+    (
+        &["tabs", "tab"],
+        RoleHint {
+            role: "tablist",
+            element: "",
+            interactive: true,
+            keyboard: &[
+                "Left and Right arrows move between tabs",
+                "Home and End jump to the first and last tab",
+            ],
+            attributes: &[
+                "role=\"tab\" with aria-selected on each tab",
+                "aria-controls linking each tab to its tabpanel",
+            ],
+        },
+    ),
+    (
+        &["menu", "dropdown"],
+        RoleHint {
+            role: "menu",
+            element: "",
+            interactive: true,
+            keyboard: &[
+                "Up and Down arrows move between items",
+                "Escape closes it and returns focus to the trigger",
+            ],
+            attributes: &[
+                "aria-haspopup and aria-expanded on the trigger",
+                "role=\"menuitem\" on each item",
+            ],
+        },
+    ),
+    (
+        &["nav", "navigation", "navbar", "breadcrumb", "breadcrumbs"],
+        RoleHint {
+            role: "navigation",
+            element: "nav",
+            interactive: false,
+            keyboard: &[],
+            attributes: &[
+                "aria-label when the page has more than one navigation",
+                "aria-current=\"page\" on the active link",
+            ],
+        },
+    ),
+    (
+        &["checkbox"],
+        RoleHint {
+            role: "checkbox",
+            element: "input type=\"checkbox\"",
+            interactive: true,
+            keyboard: &["Space toggles it"],
+            attributes: &[
+                "A visible <label>",
+                "aria-checked=\"mixed\" when indeterminate",
+            ],
+        },
+    ),
+    (
+        &["switch", "toggle"],
+        RoleHint {
+            role: "switch",
+            element: "",
+            interactive: true,
+            keyboard: &["Space and Enter toggle it"],
+            attributes: &["aria-checked reflecting the state", "A visible label"],
+        },
+    ),
+    (
+        &["slider", "range"],
+        RoleHint {
+            role: "slider",
+            element: "input type=\"range\"",
+            interactive: true,
+            keyboard: &[
+                "Arrow keys change the value",
+                "Home and End set the minimum and maximum",
+            ],
+            attributes: &[
+                "aria-valuemin, aria-valuemax and aria-valuenow",
+                "aria-valuetext for units",
+            ],
+        },
+    ),
+    (
+        &["select", "combobox", "autocomplete", "typeahead", "picker"],
+        RoleHint {
+            role: "combobox",
+            element: "select",
+            interactive: true,
+            keyboard: &[
+                "Down arrow opens the options",
+                "Enter selects, Escape closes",
+            ],
+            attributes: &[
+                "aria-expanded and aria-controls",
+                "aria-activedescendant for the highlighted option",
+            ],
+        },
+    ),
+    (
+        &["input", "textfield", "field", "textarea"],
+        RoleHint {
+            role: "textbox",
+            element: "input",
+            interactive: true,
+            keyboard: &[],
+            attributes: &[
+                "A visible <label>",
+                "aria-invalid and aria-describedby for errors",
+            ],
+        },
+    ),
+    (
+        &["search", "searchbar"],
+        RoleHint {
+            role: "search",
+            element: "search",
+            interactive: false,
+            keyboard: &["Enter submits the query"],
+            attributes: &["A labelled search input"],
+        },
+    ),
+    (
+        &["tooltip"],
+        RoleHint {
+            role: "tooltip",
+            element: "",
+            interactive: false,
+            keyboard: &["Shown on focus as well as hover", "Escape dismisses it"],
+            attributes: &["aria-describedby on the trigger"],
+        },
+    ),
+    (
+        &["progress", "loader", "spinner"],
+        RoleHint {
+            role: "progressbar",
+            element: "progress",
+            interactive: false,
+            keyboard: &[],
+            attributes: &[
+                "aria-valuenow, or none when indeterminate",
+                "aria-busy on the region being loaded",
+            ],
+        },
+    ),
+    (
+        &["accordion", "collapsible", "disclosure"],
+        RoleHint {
+            role: "region",
+            element: "details",
+            interactive: true,
+            keyboard: &["Enter and Space expand and collapse each section"],
+            attributes: &["aria-expanded and aria-controls on each header button"],
+        },
+    ),
+    (
+        &["table", "datatable"],
+        RoleHint {
+            role: "table",
+            element: "table",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["<caption> or aria-label", "<th scope> on header cells"],
+        },
+    ),
+    (
+        &["grid", "datagrid"],
+        RoleHint {
+            role: "grid",
+            element: "",
+            interactive: true,
+            keyboard: &["Arrow keys move between cells"],
+            attributes: &[
+                "role=\"row\" and role=\"gridcell\"",
+                "aria-rowcount for virtualized rows",
+            ],
+        },
+    ),
+    (
+        &["list"],
+        RoleHint {
+            role: "list",
+            element: "ul",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["<li> children (role=\"listitem\")"],
+        },
+    ),
+    (
+        &["form"],
+        RoleHint {
+            role: "form",
+            element: "form",
+            interactive: true,
+            keyboard: &["Enter submits from any field"],
+            attributes: &[
+                "aria-labelledby or aria-label",
+                "Errors linked with aria-describedby",
+            ],
+        },
+    ),
+    (
+        &["card", "article", "post"],
+        RoleHint {
+            role: "article",
+            element: "article",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["A heading naming the card"],
+        },
+    ),
+    (
+        &["image", "img", "avatar", "logo", "icon"],
+        RoleHint {
+            role: "img",
+            element: "img",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["alt text, or alt=\"\" and aria-hidden when decorative"],
+        },
+    ),
+    (
+        &["header"],
+        RoleHint {
+            role: "banner",
+            element: "header",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["Only one banner per page"],
+        },
+    ),
+    (
+        &["footer"],
+        RoleHint {
+            role: "contentinfo",
+            element: "footer",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["Only one contentinfo per page"],
+        },
+    ),
+    (
+        &["sidebar", "aside"],
+        RoleHint {
+            role: "complementary",
+            element: "aside",
+            interactive: false,
+            keyboard: &[],
+            attributes: &["aria-label when there are several"],
+        },
+    ),
+];
+
+/// Roles suggested by the words of `name`, from the last word to the first
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::a11y::suggest_roles;
+///
+/// let roles: Vec<&str> = suggest_roles("UserMenuButton").iter().map(|hint| hint.role).collect();
+/// assert_eq!(roles, ["button", "menu"]);
+/// assert!(suggest_roles("Profile").is_empty());
+/// ```
+pub fn suggest_roles(name: &str) -> Vec<&'static RoleHint> {
+    let kebab = to_kebab_case(name);
+    let mut roles: Vec<&'static RoleHint> = Vec::new();
+
+    for word in kebab.rsplit('-') {
+        let hint = ROLE_HINTS
+            .iter()
+            .find(|(words, _)| words.contains(&word))
+            .map(|(_, hint)| hint);
+        if let Some(hint) = hint.filter(|hint| !roles.contains(hint)) {
+            roles.push(hint);
+        }
+    }
+
+    roles
+}
+
+/// Hints for a role given by name, e.g. from `--var aria_role=dialog`
+pub fn role_hint(role: &str) -> Option<&'static RoleHint> {
+    ROLE_HINTS
+        .iter()
+        .map(|(_, hint)| hint)
+        .find(|hint| hint.role == role)
+}
+
+/// Template data exposed as `a11y`
+///
+/// `role` overrides the role guessed from `name` when not empty.
+pub fn a11y_data(name: &str, role: Option<&str>) -> Value {
+    let role = role.filter(|role| !role.is_empty());
+    let mut roles = suggest_roles(name);
+    if let Some(role) = role {
+        roles.retain(|hint| hint.role != role);
+        if let Some(hint) = role_hint(role) {
+            roles.insert(0, hint);
+        }
+    }
+    // Roles missing from the hint table come without hints
+    let primary = roles
+        .first()
+        .filter(|hint| role.is_none_or(|role| hint.role == role));
+
+    json!({
+        "role": role.or(primary.map(|hint| hint.role)).unwrap_or_default(),
+        "element": primary.map_or("", |hint| hint.element),
+        "interactive": primary.is_some_and(|hint| hint.interactive),
+        "keyboard": primary.map_or(&[][..], |hint| hint.keyboard),
+        "attributes": primary.map_or(&[][..], |hint| hint.attributes),
+        "roles": roles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a11y_data_guesses_and_overrides_role() {
+        let data = a11y_data("ConfirmModal", None);
+        assert_eq!(data["role"], "dialog");
+        assert_eq!(data["element"], "dialog");
+        assert_eq!(data["keyboard"][0], "Escape closes it");
+
+        let data = a11y_data("SettingsTabs", Some("navigation"));
+        assert_eq!(data["role"], "navigation");
+        assert_eq!(data["roles"][1]["role"], "tablist");
+
+        let data = a11y_data("Widget", Some("feed"));
+        assert_eq!(
+            (data["role"].as_str(), data["element"].as_str()),
+            (Some("feed"), Some(""))
+        );
+        assert_eq!(a11y_data("Widget", None)["role"], "");
+    }
+}
@@ -0,0 +1,113 @@
+//! Who generated the files: `author_name` and `author_email` in the
+//! template data.
+//!
+//! Each value comes from the first of:
+//! 1. `--var author_name=...` / `--var author_email=...`
+//! 2. `author_name` / `author_email` in the config file
+//! 3. `git config user.name` / `user.email`, read in the output directory
+//! 4. The OS user name (`$USER`, `$USERNAME`), for the name only
+//! 5. [`DEFAULT_AUTHOR_NAME`] for the name, an empty email
+//!
+//! `--var` values win because variables are merged over the template data;
+//! `--var author=...`, the variable templates used before `author_name`,
+//! counts as the name. This module resolves the rest. Resolving runs `git`,
+//! so the engine does it once, when it first renders.
+
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+
+/// Name of the author when neither the config, git nor the OS name one
+pub const DEFAULT_AUTHOR_NAME: &str = "Frontend Team";
+
+/// The resolved author of a generation
+///
+/// `name` is empty when nothing named an author; the template data then
+/// gets [`DEFAULT_AUTHOR_NAME`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Author {
+    pub name: String,
+    pub email: String,
+}
+
+impl Author {
+    /// The author of files generated into `dir`, preferring the `configured`
+    /// name and email
+    pub fn resolve(name: Option<&str>, email: Option<&str>, dir: &Path) -> Self {
+        let name = name
+            .map(str::to_string)
+            .or_else(|| git_config(dir, "user.name"))
+            .or_else(os_user)
+            .unwrap_or_default();
+        let email = email
+            .map(str::to_string)
+            .or_else(|| git_config(dir, "user.email"))
+            .unwrap_or_default();
+        Self { name, email }
+    }
+
+    /// The author as template data
+    pub fn to_data(&self) -> [(String, Value); 2] {
+        let name = match self.name.as_str() {
+            "" => DEFAULT_AUTHOR_NAME,
+            name => name,
+        };
+        [
+            ("author_name".to_string(), name.into()),
+            ("author_email".to_string(), self.email.clone().into()),
+        ]
+    }
+}
+
+/// A non-empty git config value, as git sees it from `dir`
+///
+/// Falls back to the current directory when `dir` doesn't exist yet.
+fn git_config(dir: &Path, key: &str) -> Option<String> {
+    let dir = dir
+        .ancestors()
+        .find(|dir| dir.is_dir())
+        .unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// The name of the user running the CLI
+fn os_user() -> Option<String> {
+    ["USER", "USERNAME"]
+        .into_iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|user| !user.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_author_wins() {
+        let author = Author::resolve(Some("Ana"), Some("ana@acme.dev"), Path::new("."));
+        assert_eq!(
+            author,
+            Author {
+                name: "Ana".to_string(),
+                email: "ana@acme.dev".to_string()
+            }
+        );
+        assert_eq!(
+            author.to_data()[0],
+            ("author_name".to_string(), "Ana".into())
+        );
+    }
+
+    #[test]
+    fn test_unnamed_author_renders_the_default_name() {
+        let data = Author::default().to_data();
+        assert_eq!(data[0].1, DEFAULT_AUTHOR_NAME);
+        assert_eq!(data[1].1, "");
+    }
+}
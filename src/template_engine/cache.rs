@@ -0,0 +1,324 @@
+//! In-process cache for template configs, sources and compiled templates.
+//!
+//! Every entry is keyed by file path and validated against the file's
+//! modification time and size, so edits to a template are picked up on the
+//! next generation while repeated generations (batch and watch workflows)
+//! skip re-reading and re-parsing unchanged files. Entries also record the
+//! other inputs of what they hold: the defaults a config was parsed on top
+//! of, and the name and folder case a template was compiled for.
+//!
+//! The cache lives in memory only. Compiled Handlebars templates can't be
+//! written out, so a cache on disk would only save parsing `.conf` files,
+//! which is not where repeated generations spend their time.
+
+use anyhow::{Context, Result};
+use handlebars::Template;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::fs;
+
+use super::config::TemplateConfig;
+use super::naming::CaseStyle;
+use super::renderer::{read_template_with_limit, RenderFailure};
+
+/// Identity of a file version: modification time plus size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Read the stamp of a file, or `None` if its mtime is unavailable
+    pub async fn of(path: &Path) -> Result<Option<Self>> {
+        let metadata = fs::metadata(path)
+            .await
+            .with_context(|| format!("Could not get file metadata: {}", path.display()))?;
+
+        Ok(metadata.modified().ok().map(|modified| Self {
+            modified,
+            len: metadata.len(),
+        }))
+    }
+}
+
+/// Number of entries held by a [`TemplateCache`]
+#[allow(dead_code)] // Public API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub configs: usize,
+    pub sources: usize,
+    pub compiled: usize,
+}
+
+/// Cache shared by a `TemplateEngine` and its generation tasks.
+///
+/// Engines with different defaults can share one: a config parsed on top
+/// of other defaults is parsed again.
+///
+/// # Example
+///
+/// ```no_run
+/// # use cli_frontend::template_engine::{cache::TemplateCache, TemplateEngine};
+/// # use std::path::PathBuf;
+/// # use std::sync::Arc;
+/// // Share one cache between engines that generate into different directories
+/// let cache = Arc::new(TemplateCache::new());
+/// let components = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src/components"))?
+///     .with_cache(Arc::clone(&cache));
+/// let hooks = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src/hooks"))?
+///     .with_cache(cache);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct TemplateCache {
+    /// Parsed configs, with the defaults they were parsed on top of
+    configs: Mutex<HashMap<PathBuf, (FileStamp, TemplateConfig, TemplateConfig)>>,
+    sources: Mutex<HashMap<PathBuf, (FileStamp, Arc<String>)>>,
+    /// Compiled templates depend on the generated name and the folder case
+    /// (`$FILE_NAME` and `$FOLDER_NAME` are substituted before compiling),
+    /// so both are part of the key
+    compiled: Mutex<HashMap<CompiledKey, (FileStamp, Template)>>,
+}
+
+type CompiledKey = (PathBuf, String, Option<CaseStyle>);
+
+impl TemplateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a `.conf` or `template.toml` file, parsing it on top of
+    /// `defaults` with `parse` only when it or `defaults` changed
+    pub async fn load_config<F>(
+        &self,
+        path: &Path,
+        defaults: &TemplateConfig,
+        parse: F,
+    ) -> Result<TemplateConfig>
+    where
+        F: FnOnce(&str) -> Result<TemplateConfig>,
+    {
+        let stamp = FileStamp::of(path).await?;
+
+        if let Some(stamp) = stamp {
+            let configs = self.configs.lock().unwrap();
+            if let Some((cached_stamp, cached_defaults, config)) = configs.get(path) {
+                if *cached_stamp == stamp && cached_defaults == defaults {
+                    return Ok(config.clone());
+                }
+            }
+        }
+
+        let content = fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Could not read template config: {}", path.display()))?;
+        let config = parse(&content)?;
+
+        if let Some(stamp) = stamp {
+            self.configs.lock().unwrap().insert(
+                path.to_path_buf(),
+                (stamp, defaults.clone(), config.clone()),
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// Read a template source, enforcing `max_size` on cache misses
+    ///
+    /// Returns the stamp alongside the content so callers can key derived
+    /// entries (compiled templates) on the same file version.
+    pub async fn read_template(
+        &self,
+        path: &Path,
+        max_size: u64,
+    ) -> Result<(Arc<String>, Option<FileStamp>)> {
+        let stamp = FileStamp::of(path).await?;
+
+        if let Some(stamp) = stamp {
+            let sources = self.sources.lock().unwrap();
+            if let Some((cached_stamp, content)) = sources.get(path) {
+                if *cached_stamp == stamp && stamp.len <= max_size {
+                    return Ok((Arc::clone(content), Some(stamp)));
+                }
+            }
+        }
+
+        let content = Arc::new(read_template_with_limit(path, max_size).await?);
+
+        if let Some(stamp) = stamp {
+            self.sources
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), (stamp, Arc::clone(&content)));
+        }
+
+        Ok((content, stamp))
+    }
+
+    /// Get the compiled form of `source`, the processed content of `path`
+    /// for `name` in folders cased with `folder_case`
+    pub fn compiled_template(
+        &self,
+        path: &Path,
+        stamp: Option<FileStamp>,
+        name: &str,
+        folder_case: Option<CaseStyle>,
+        source: &str,
+    ) -> Result<Template> {
+        let key = (path.to_path_buf(), name.to_string(), folder_case);
+
+        if let Some(stamp) = stamp {
+            let compiled = self.compiled.lock().unwrap();
+            if let Some((cached_stamp, template)) = compiled.get(&key) {
+                if *cached_stamp == stamp {
+                    return Ok(template.clone());
+                }
+            }
+        }
+
+        let template = Template::compile(source)
+            .map_err(|e| anyhow::Error::new(RenderFailure::new(source, &e, 1)))
+            .with_context(|| format!("Template compilation failed: {}", path.display()))?;
+
+        if let Some(stamp) = stamp {
+            self.compiled
+                .lock()
+                .unwrap()
+                .insert(key, (stamp, template.clone()));
+        }
+
+        Ok(template)
+    }
+
+    /// Number of cached entries of each kind
+    #[allow(dead_code)] // Public API
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            configs: self.configs.lock().unwrap().len(),
+            sources: self.sources.lock().unwrap().len(),
+            compiled: self.compiled.lock().unwrap().len(),
+        }
+    }
+
+    /// Drop every cached entry
+    #[allow(dead_code)] // Public API
+    pub fn clear(&self) {
+        self.configs.lock().unwrap().clear();
+        self.sources.lock().unwrap().clear();
+        self.compiled.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn set_mtime(path: &Path, offset_secs: u64) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000 + offset_secs),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_config_reuses_until_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".conf");
+        std::fs::write(&path, "environment=one\n").unwrap();
+        set_mtime(&path, 0);
+
+        let cache = TemplateCache::new();
+        let parse = |content: &str| {
+            Ok(TemplateConfig {
+                environment: content
+                    .trim()
+                    .trim_start_matches("environment=")
+                    .to_string(),
+                ..Default::default()
+            })
+        };
+
+        let defaults = TemplateConfig::default();
+        assert_eq!(
+            cache
+                .load_config(&path, &defaults, parse)
+                .await
+                .unwrap()
+                .environment,
+            "one"
+        );
+        let cached = cache
+            .load_config(&path, &defaults, |_| anyhow::bail!("should not re-parse"))
+            .await
+            .unwrap();
+        assert_eq!(cached.environment, "one");
+
+        // Other defaults parse again, as another engine sharing the cache would
+        let strict = TemplateConfig {
+            strict_variables: true,
+            ..Default::default()
+        };
+        let reparsed = cache
+            .load_config(&path, &strict, |_| anyhow::bail!("re-parsed"))
+            .await;
+        assert!(reparsed.is_err());
+
+        std::fs::write(&path, "environment=two\n").unwrap();
+        set_mtime(&path, 60);
+        assert_eq!(
+            cache
+                .load_config(&path, &defaults, parse)
+                .await
+                .unwrap()
+                .environment,
+            "two"
+        );
+        assert_eq!(cache.stats().configs, 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_template_and_compile_are_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("$FILE_NAME.tsx");
+        std::fs::write(&path, "Hello {{name}}").unwrap();
+        set_mtime(&path, 0);
+
+        let cache = TemplateCache::new();
+        let (first, stamp) = cache.read_template(&path, 1024).await.unwrap();
+        let (second, _) = cache.read_template(&path, 1024).await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        cache
+            .compiled_template(&path, stamp, "Button", None, &first)
+            .unwrap();
+        cache
+            .compiled_template(&path, stamp, "Card", None, &first)
+            .unwrap();
+        cache
+            .compiled_template(&path, stamp, "Card", Some(CaseStyle::Kebab), &first)
+            .unwrap();
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                configs: 0,
+                sources: 1,
+                compiled: 3
+            }
+        );
+
+        std::fs::write(&path, "Bye {{name}}").unwrap();
+        set_mtime(&path, 60);
+        let (changed, _) = cache.read_template(&path, 1024).await.unwrap();
+        assert_eq!(changed.as_str(), "Bye {{name}}");
+
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
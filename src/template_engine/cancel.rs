@@ -0,0 +1,106 @@
+//! Stopping a generation run early: Ctrl-C and `--timeout`.
+//!
+//! A cancelled run is dropped at its next await point. Render tasks it
+//! spawned are aborted, the staging directory is removed, and files it
+//! already moved into the project are rolled back, as for any failed run,
+//! even with `--keep-going`.
+
+use anyhow::Result;
+use std::future::{pending, Future};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::CliError;
+
+/// When a generation run gives up
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cancellation {
+    /// The run's deadline and the timeout it was computed from
+    deadline: Option<(Instant, Duration)>,
+    /// Stop on Ctrl-C
+    interrupt: bool,
+}
+
+impl Cancellation {
+    /// Stops runs once `timeout` has passed from now, if given
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.deadline = timeout.map(|timeout| (Instant::now() + timeout, timeout));
+        self
+    }
+
+    /// Stops runs on Ctrl-C
+    ///
+    /// The CLI's own handler replaces the default one for the rest of the
+    /// process, so library hosts leave this off and handle signals themselves.
+    pub fn with_interrupt(mut self, interrupt: bool) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Run `future` unless it is cancelled first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::Interrupted`] or [`CliError::TimedOut`] when the
+    /// run is cancelled, and `future`'s own errors otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::cancel::{is_cancellation, Cancellation};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let cancellation = Cancellation::default().with_timeout(Some(Duration::from_millis(10)));
+    /// let error = cancellation
+    ///     .run(std::future::pending::<anyhow::Result<()>>())
+    ///     .await
+    ///     .unwrap_err();
+    /// assert!(is_cancellation(&error));
+    ///
+    /// let never = Cancellation::default();
+    /// assert_eq!(never.run(async { Ok(1) }).await.unwrap(), 1);
+    /// # }
+    /// ```
+    pub async fn run<T>(&self, future: impl Future<Output = Result<T>>) -> Result<T> {
+        if self.deadline.is_none() && !self.interrupt {
+            return future.await;
+        }
+        tokio::select! {
+            result = future => result,
+            error = self.cancelled() => Err(error.into()),
+        }
+    }
+
+    /// Resolves with the reason once the run should stop
+    async fn cancelled(&self) -> CliError {
+        let timeout = async {
+            match self.deadline {
+                Some((deadline, timeout)) => {
+                    tokio::time::sleep_until(deadline).await;
+                    timeout
+                }
+                None => pending().await,
+            }
+        };
+        let interrupt = async {
+            // Without a signal handler, only the timeout can cancel
+            if !self.interrupt || tokio::signal::ctrl_c().await.is_err() {
+                pending::<()>().await;
+            }
+        };
+        tokio::select! {
+            timeout = timeout => CliError::TimedOut { seconds: timeout.as_secs() },
+            () = interrupt => CliError::Interrupted,
+        }
+    }
+}
+
+/// Whether `error` is a cancelled run rather than a failure
+pub fn is_cancellation(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<CliError>(),
+        Some(CliError::Interrupted | CliError::TimedOut { .. })
+    )
+}
@@ -0,0 +1,145 @@
+//! Copying rendered output to the system clipboard (`--copy`).
+//!
+//! macOS and Windows have a clipboard API that is always there. Elsewhere the
+//! clipboard belongs to the process that set it and is gone when the CLI
+//! exits, so the text is handed to a clipboard tool that keeps serving it:
+//! `wl-copy` in a Wayland session, otherwise `xclip` or `xsel`. Without any
+//! of them there is no clipboard to copy to and the caller falls back to
+//! printing the text.
+
+use anyhow::{Context, Result};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// A command that puts the text it reads on stdin on the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipboardTool {
+    pub program: &'static str,
+    pub args: &'static [&'static str],
+}
+
+const WL_COPY: ClipboardTool = ClipboardTool {
+    program: "wl-copy",
+    args: &[],
+};
+const XCLIP: ClipboardTool = ClipboardTool {
+    program: "xclip",
+    args: &["-selection", "clipboard"],
+};
+const XSEL: ClipboardTool = ClipboardTool {
+    program: "xsel",
+    args: &["--clipboard", "--input"],
+};
+/// Windows' own tool, reachable from WSL
+const CLIP: ClipboardTool = ClipboardTool {
+    program: "clip.exe",
+    args: &[],
+};
+
+/// How `--copy` reaches the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clipboard {
+    /// The operating system's clipboard API
+    Native,
+    /// A clipboard tool found on `PATH`
+    Tool(ClipboardTool),
+}
+
+/// The clipboard to copy to on `os` (as in [`std::env::consts::OS`]), or
+/// `None` when none of the tools it needs `is_installed`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::clipboard::{clipboard_for, Clipboard};
+///
+/// assert_eq!(clipboard_for("macos", false, |_| false), Some(Clipboard::Native));
+/// assert_eq!(clipboard_for("linux", false, |_| false), None);
+/// ```
+pub fn clipboard_for(
+    os: &str,
+    wayland: bool,
+    is_installed: impl Fn(&str) -> bool,
+) -> Option<Clipboard> {
+    let tools: &[ClipboardTool] = match os {
+        "macos" | "windows" => return Some(Clipboard::Native),
+        _ if wayland => &[WL_COPY, XCLIP, XSEL, CLIP],
+        _ => &[XCLIP, XSEL, WL_COPY, CLIP],
+    };
+    tools
+        .iter()
+        .find(|tool| is_installed(tool.program))
+        .map(|tool| Clipboard::Tool(*tool))
+}
+
+/// The clipboard of the running system
+pub fn system_clipboard() -> Option<Clipboard> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    clipboard_for(std::env::consts::OS, wayland, is_installed)
+}
+
+/// Whether `program` is an executable file in one of the `PATH` directories
+fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Put `text` on `clipboard`
+pub async fn copy_text(clipboard: Clipboard, text: &str) -> Result<()> {
+    let tool = match clipboard {
+        Clipboard::Native => {
+            return arboard::Clipboard::new()
+                .and_then(|mut clipboard| clipboard.set_text(text))
+                .context("Could not access the system clipboard");
+        }
+        Clipboard::Tool(tool) => tool,
+    };
+
+    let mut child = Command::new(tool.program)
+        .args(tool.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Could not run {}", tool.program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .with_context(|| format!("Could not write to {}", tool.program))?;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", tool.program, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_for_picks_an_installed_tool() {
+        let installed =
+            |programs: &'static [&'static str]| move |program: &str| programs.contains(&program);
+
+        assert_eq!(
+            clipboard_for("linux", false, installed(&["xsel", "wl-copy"])),
+            Some(Clipboard::Tool(XSEL))
+        );
+        assert_eq!(
+            clipboard_for("linux", true, installed(&["xsel", "wl-copy"])),
+            Some(Clipboard::Tool(WL_COPY))
+        );
+        assert_eq!(
+            clipboard_for("linux", false, installed(&["clip.exe"])),
+            Some(Clipboard::Tool(CLIP))
+        );
+        assert_eq!(
+            clipboard_for("windows", false, installed(&[])),
+            Some(Clipboard::Native)
+        );
+        assert_eq!(clipboard_for("freebsd", true, installed(&[])), None);
+    }
+}
@@ -7,7 +7,8 @@
 //!
 //! Templates can be configured using `.conf` files in INI format. These files
 //! define metadata, variables with types and defaults, and conditional file
-//! generation rules.
+//! generation rules. A `template.toml` can be used instead, see
+//! [`super::toml_config`].
 //!
 //! # Example .conf file
 //!
@@ -15,20 +16,55 @@
 //! [metadata]
 //! name=React Component
 //! description=Functional component with TypeScript
+//! tags=component,ui
+//! requires_cli>=1.4
 //!
 //! [options]
 //! style=scss
 //! style_options=scss,css,styled-components,none
 //! with_tests=true
 //! with_tests_type=boolean
+//! style_group=Styling
+//! style_prompt=How should the component be styled?
+//! api_url_required=true
 //!
 //! [files]
 //! $FILE_NAME.tsx=always
 //! $FILE_NAME.spec.tsx=var_with_tests
 //! $FILE_NAME.module.scss=var_style_scss
+//!
+//! [raw]
+//! assets/
+//! $FILE_NAME.fixture.json
+//!
+//! [rules]
+//! style=none implies !with_css_module
+//!
+//! [options.production]
+//! api_url=https://api.acme.com
+//!
+//! [examples]
+//! Styled with tests=cli-frontend Button --type component --var style=scss
+//! Styled with tests.files=Button/Button.tsx, Button/Button.module.scss
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+/// Default upper bound for a single rendered template file (10 MB)
+pub const DEFAULT_MAX_TEMPLATE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Default deepest directory level walked inside a template
+pub const DEFAULT_MAX_TEMPLATE_DEPTH: usize = 16;
+
+/// Default most files a single template directory may hold
+pub const DEFAULT_MAX_TEMPLATE_FILES: usize = 2000;
+
+use super::formatting::LineEndings;
+use super::helpers::EnvPolicy;
+use super::naming::CaseStyle;
+use super::rules::VariableRule;
 
 /// Configuration for template generation, loaded from .conf files.
 ///
@@ -40,11 +76,27 @@ use std::collections::HashMap;
 ///
 /// * `variables` - Key-value pairs for template variables
 /// * `environment` - Current environment (development/production)
+/// * `environment_overrides` - Variable defaults per environment (`[options.production]`)
 /// * `enable_timestamps` - Whether to include timestamp variables
 /// * `enable_uuid` - Whether to generate UUID variables
 /// * `file_filters` - Conditional file generation rules
 /// * `metadata` - Template name and description
 /// * `options_metadata` - Type information for variables
+/// * `filename_case` - Case policy for generated filenames (PascalCase when unset)
+/// * `folder_case` - Case policy for the generated folder (raw name when unset)
+/// * `output_subdir` - Pattern for a subdirectory of the output dir to generate into
+/// * `line_endings` - Line ending policy for written files (template endings when unset)
+/// * `editorconfig` - Whether `.editorconfig` rules are applied to rendered output
+/// * `raw_files` - Files or directories copied verbatim instead of rendered
+/// * `permissions` - Unix modes for generated files, by template path
+/// * `max_template_size` - Largest file (in bytes) that will be loaded and rendered
+/// * `strict_variables` - Whether referencing an undefined variable fails rendering
+/// * `env_policy` - Environment variables the `env` helper may read
+/// * `rules` - `requires`/`implies`/`conflicts` rules checked after merging `--var` values
+/// * `examples` - Named example invocations that replace the synthesized ones in `--describe`
+/// * `data` - Structured values from importers (`--figma-json`, `--tokens`) added to template data
+/// * `framework` - Target framework, from `framework` in .conf or the `rn/` template namespace
+/// * `create_folder` - Whether the template generates into a folder named after the item
 ///
 /// # Example
 ///
@@ -55,10 +107,13 @@ use std::collections::HashMap;
 /// config.variables.insert("style".to_string(), "scss".to_string());
 /// config.variables.insert("with_tests".to_string(), "true".to_string());
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TemplateConfig {
     pub variables: HashMap<String, String>,
     pub environment: String,
+    /// Variable defaults that replace `variables` when `environment` matches,
+    /// by environment (`[options.production]` in .conf)
+    pub environment_overrides: HashMap<String, HashMap<String, String>>,
     pub enable_timestamps: bool,
     pub enable_uuid: bool,
     /// Maps filename pattern to condition (e.g., "$FILE_NAME.spec.tsx" -> "var_with_tests")
@@ -67,9 +122,74 @@ pub struct TemplateConfig {
     pub metadata: TemplateMetadata,
     /// Metadata about each variable option (for dynamic boolean helper generation)
     pub options_metadata: HashMap<String, VariableOption>,
+    /// Case policy applied to generated filenames (`filename_case` in .conf)
+    pub filename_case: Option<CaseStyle>,
+    /// Case policy applied to the generated folder name (`folder_case` in .conf)
+    pub folder_case: Option<CaseStyle>,
+    /// Subdirectory pattern relative to the output dir (`output_subdir` in .conf),
+    /// e.g. `{kebab_name}/components`
+    pub output_subdir: Option<String>,
+    /// Line ending policy for generated files (`line_endings` in .conf)
+    pub line_endings: Option<LineEndings>,
+    /// Apply the project's `.editorconfig` to rendered output (`editorconfig` in .conf)
+    pub editorconfig: bool,
+    /// Banner prepended to generated files as a comment (`file_header` in the
+    /// config file or .conf), rendered with Handlebars
+    pub file_header: Option<String>,
+    /// Template paths copied verbatim (`[raw]` in .conf); entries ending in `/` match directories
+    pub raw_files: Vec<String>,
+    /// Unix modes for generated files (`[permissions]` in .conf, e.g. `bin/setup.sh=755`),
+    /// keyed by template path; entries ending in `/` match directories
+    pub permissions: HashMap<String, u32>,
+    /// Size limit for rendered files (`max_template_size` in .conf, e.g. `25MB`)
+    pub max_template_size: u64,
+    /// Fail on undefined variables instead of rendering them empty (`strict_variables` in .conf)
+    pub strict_variables: bool,
+    /// Environment variables the `env` helper may read (`env_allow`/`env_deny`
+    /// in the config file, `--no-env-helpers`); templates can't loosen it
+    pub env_policy: EnvPolicy,
+    /// Dependency rules between variables (`[rules]` in .conf)
+    pub rules: Vec<VariableRule>,
+    /// Hand-written usage examples shown by `--describe` (`[examples]` in .conf)
+    pub examples: Vec<TemplateExample>,
+    /// Structured values added to the template data by importers, e.g. `figma`
+    /// from `--figma-json` and `tokens` from `--tokens`
+    pub data: serde_json::Map<String, serde_json::Value>,
+    /// Framework the template targets (`framework` in .conf, or its namespace)
+    pub framework: Framework,
+    /// Template language the files are written in (`engine` in .conf)
+    pub engine: RenderEngine,
+    /// Generate into a folder named after the item (`create_folder` in .conf);
+    /// flat-file templates such as routes turn it off
+    pub create_folder: bool,
+    /// Bounds on walking the template directory (`max_template_depth` and
+    /// `max_template_files` in the config file)
+    pub walk_limits: WalkLimits,
+}
+
+/// How far a template directory is walked before generation gives up
+///
+/// Guards against a `templates_dir` that points at a project root, where
+/// walking `node_modules` would otherwise take minutes and render
+/// thousands of files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkLimits {
+    /// Deepest directory level below the template directory
+    pub max_depth: usize,
+    /// Most files the template may hold
+    pub max_files: usize,
+}
+
+impl Default for WalkLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_TEMPLATE_DEPTH,
+            max_files: DEFAULT_MAX_TEMPLATE_FILES,
+        }
+    }
 }
 
-/// Metadata about a template (name and description).
+/// Metadata about a template (name, description, tags and required CLI version).
 ///
 /// Provides human-readable information about what a template does
 /// and what it generates.
@@ -81,12 +201,52 @@ pub struct TemplateConfig {
 /// let metadata = TemplateMetadata {
 ///     name: "React Component".to_string(),
 ///     description: "Functional component with TypeScript".to_string(),
+///     tags: vec!["component".to_string(), "ui".to_string()],
+///     requires_cli: Some("1.4".to_string()),
+///     internal: false,
 /// };
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TemplateMetadata {
     pub name: String,
     pub description: String,
+    /// Categories for browsing large catalogs (`tags=form,data-display`),
+    /// lowercase
+    pub tags: Vec<String>,
+    /// Oldest cli-frontend version the template works with (`requires_cli>=1.4`)
+    pub requires_cli: Option<String>,
+    /// Only meant to be used by architectures (`internal=true`): hidden
+    /// from listings, the wizard and `--type` without `--include-internal`
+    pub internal: bool,
+}
+
+/// Whether the dotted version `version` is at least `minimum`
+///
+/// Missing components count as zero and pre-release or build suffixes
+/// (`-beta.1`, `+abc`) are ignored. Returns `None` if either isn't a
+/// version.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::config::version_at_least;
+///
+/// assert_eq!(version_at_least("1.4.0", "1.4"), Some(true));
+/// assert_eq!(version_at_least("1.10.0", "1.9"), Some(true));
+/// assert_eq!(version_at_least("0.5.2", "1.0"), Some(false));
+/// assert_eq!(version_at_least("1.4.0", "next"), None);
+/// ```
+pub fn version_at_least(version: &str, minimum: &str) -> Option<bool> {
+    let parse = |version: &str| -> Option<Vec<u64>> {
+        let core = version.trim().split(['-', '+']).next()?;
+        core.split('.').map(|part| part.parse().ok()).collect()
+    };
+    let (mut version, mut minimum) = (parse(version)?, parse(minimum)?);
+
+    let len = version.len().max(minimum.len());
+    version.resize(len, 0);
+    minimum.resize(len, 0);
+    Some(version >= minimum)
 }
 
 /// Metadata about a variable option from the .conf file.
@@ -97,9 +257,12 @@ pub struct TemplateMetadata {
 ///
 /// # Fields
 ///
-/// * `var_type` - Type of variable: "boolean", "string", "enum", etc.
+/// * `var_type` - Type of variable: "boolean", "string", "enum", "path", etc.
 /// * `possible_values` - Valid values for enum types (from `{var}_options` in .conf)
 /// * `description` - Human-readable description of what the variable controls
+/// * `required` - Whether a value must come from the defaults or `--var`
+/// * `group` - Heading used to group variables in `--describe` and the wizard
+/// * `prompt` - Custom question shown by the wizard
 ///
 /// # Example
 ///
@@ -109,9 +272,10 @@ pub struct TemplateMetadata {
 ///     var_type: "enum".to_string(),
 ///     possible_values: vec!["scss".to_string(), "css".to_string()],
 ///     description: "Styling approach for the component".to_string(),
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct VariableOption {
     /// Type of variable: "boolean", "string", "enum", etc.
     pub var_type: String,
@@ -119,6 +283,37 @@ pub struct VariableOption {
     pub possible_values: Vec<String>,
     /// Description of the variable
     pub description: String,
+    /// Generation fails when the variable has no value (`{var}_required` in .conf)
+    pub required: bool,
+    /// Heading the variable is listed under, e.g. "Styling" (`{var}_group` in .conf)
+    pub group: String,
+    /// Question the wizard asks for the variable (`{var}_prompt` in .conf)
+    pub prompt: String,
+}
+
+/// A named example invocation from the `[examples]` section.
+///
+/// `Name=command` sets the command and `Name.files=a, b` the files it is
+/// expected to generate. `--describe` prints both verbatim.
+///
+/// # Example
+///
+/// ```
+/// # use cli_frontend::template_engine::config::TemplateExample;
+/// let example = TemplateExample {
+///     name: "Styled with tests".to_string(),
+///     command: "cli-frontend Button --type component --var style=scss".to_string(),
+///     files: vec!["Button/Button.tsx".to_string()],
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateExample {
+    pub name: String,
+    pub command: String,
+    /// Files the command is expected to generate
+    #[serde(default)]
+    pub files: Vec<String>,
 }
 
 impl Default for TemplateConfig {
@@ -126,15 +321,272 @@ impl Default for TemplateConfig {
         Self {
             variables: HashMap::new(),
             environment: std::env::var("NODE_ENV").unwrap_or_else(|_| "development".to_string()),
+            environment_overrides: HashMap::new(),
             enable_timestamps: true,
             enable_uuid: true,
             file_filters: HashMap::new(),
             metadata: TemplateMetadata::default(),
             options_metadata: HashMap::new(),
+            filename_case: None,
+            folder_case: None,
+            output_subdir: None,
+            line_endings: None,
+            editorconfig: true,
+            file_header: None,
+            raw_files: Vec::new(),
+            permissions: HashMap::new(),
+            max_template_size: DEFAULT_MAX_TEMPLATE_SIZE,
+            strict_variables: false,
+            env_policy: EnvPolicy::default(),
+            rules: Vec::new(),
+            examples: Vec::new(),
+            data: serde_json::Map::new(),
+            framework: Framework::default(),
+            engine: RenderEngine::default(),
+            create_folder: true,
+            walk_limits: WalkLimits::default(),
         }
     }
 }
 
+impl TemplateConfig {
+    /// Replace variable defaults with the overrides for the current `environment`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::TemplateConfig;
+    /// # use std::collections::HashMap;
+    /// let mut config = TemplateConfig {
+    ///     environment: "production".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// config.variables.insert("api_url".to_string(), "http://localhost:3000".to_string());
+    /// config.environment_overrides.insert(
+    ///     "production".to_string(),
+    ///     HashMap::from([("api_url".to_string(), "https://api.acme.com".to_string())]),
+    /// );
+    ///
+    /// config.apply_environment_overrides();
+    /// assert_eq!(config.variables["api_url"], "https://api.acme.com");
+    /// ```
+    pub fn apply_environment_overrides(&mut self) {
+        if let Some(overrides) = self.environment_overrides.get(&self.environment) {
+            self.variables.extend(overrides.clone());
+        }
+    }
+
+    /// Required variables (`{var}_required=true`) that have no value, sorted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::config::VariableOption;
+    /// # use cli_frontend::template_engine::TemplateConfig;
+    /// let mut config = TemplateConfig::default();
+    /// config.options_metadata.insert(
+    ///     "api_url".to_string(),
+    ///     VariableOption { required: true, ..Default::default() },
+    /// );
+    /// assert_eq!(config.missing_required_variables(), vec!["api_url"]);
+    ///
+    /// config.variables.insert("api_url".to_string(), "/api".to_string());
+    /// assert!(config.missing_required_variables().is_empty());
+    /// ```
+    pub fn missing_required_variables(&self) -> Vec<&str> {
+        let mut missing: Vec<&str> = self
+            .options_metadata
+            .iter()
+            .filter(|(name, option)| {
+                option.required
+                    && self
+                        .variables
+                        .get(*name)
+                        .is_none_or(|value| value.is_empty())
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        missing.sort_unstable();
+        missing
+    }
+}
+
+/// Template language a template's files are written in
+///
+/// Handlebars is the default; `engine=minijinja` in `.conf` renders the
+/// template with MiniJinja (Jinja2 syntax) instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderEngine {
+    #[default]
+    Handlebars,
+    MiniJinja,
+}
+
+impl RenderEngine {
+    /// Parse an engine name (`handlebars`, `minijinja`, `jinja`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::config::RenderEngine;
+    /// assert_eq!(RenderEngine::parse("Jinja2"), Some(RenderEngine::MiniJinja));
+    /// assert_eq!(RenderEngine::parse("hbs"), Some(RenderEngine::Handlebars));
+    /// assert_eq!(RenderEngine::parse("erb"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "handlebars" | "hbs" => Some(Self::Handlebars),
+            "minijinja" | "jinja" | "jinja2" => Some(Self::MiniJinja),
+            _ => None,
+        }
+    }
+
+    /// Name shown by `--describe`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Handlebars => "handlebars",
+            Self::MiniJinja => "minijinja",
+        }
+    }
+}
+
+/// Framework a template generates code for
+///
+/// Templates in a framework namespace (`templates/rn/component`, used as
+/// `--type rn/component`) target that framework; others can set `framework`
+/// in their `.conf`. Templates see it as `framework`, `is_native` and
+/// `platform`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Framework {
+    #[default]
+    React,
+    ReactNative,
+}
+
+/// File marking a directory of `templates_dir` as a framework namespace;
+/// it holds the framework's name, e.g. `rn`
+pub const NAMESPACE_FILE: &str = ".namespace";
+
+impl Framework {
+    /// Parse a framework name (`react`, `rn`, `react-native`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "react" | "web" => Some(Self::React),
+            "rn" | "react-native" => Some(Self::ReactNative),
+            _ => None,
+        }
+    }
+
+    /// Framework of the namespace directory `dir`, `None` unless it has a
+    /// [`NAMESPACE_FILE`] naming one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::config::{Framework, NAMESPACE_FILE};
+    /// let dir = tempfile::TempDir::new().unwrap();
+    /// assert_eq!(Framework::of_namespace(dir.path()), None);
+    ///
+    /// std::fs::write(dir.path().join(NAMESPACE_FILE), "rn\n").unwrap();
+    /// assert_eq!(Framework::of_namespace(dir.path()), Some(Framework::ReactNative));
+    /// ```
+    pub fn of_namespace(dir: &Path) -> Option<Self> {
+        let name = std::fs::read_to_string(dir.join(NAMESPACE_FILE)).ok()?;
+        Self::parse(&name)
+    }
+
+    /// Name exposed to templates as `framework`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::React => "react",
+            Self::ReactNative => "rn",
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        *self == Self::ReactNative
+    }
+
+    /// `platform` for files without a platform suffix: `native` or `web`
+    pub fn default_platform(&self) -> &'static str {
+        match self {
+            Self::React => "web",
+            Self::ReactNative => "native",
+        }
+    }
+}
+
+/// Parse a byte size such as `512`, `200KB` or `25MB`
+///
+/// # Example
+///
+/// ```
+/// # use cli_frontend::template_engine::config::parse_byte_size;
+/// assert_eq!(parse_byte_size("200KB"), Some(200 * 1024));
+/// assert_eq!(parse_byte_size("25mb"), Some(25 * 1024 * 1024));
+/// assert_eq!(parse_byte_size("lots"), None);
+/// ```
+pub fn parse_byte_size(value: &str) -> Option<u64> {
+    let value = value.trim().to_uppercase();
+    let (number, multiplier) = if let Some(n) = value.strip_suffix("GB") {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = value.strip_suffix("MB") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = value.strip_suffix("KB") {
+        (n, 1024)
+    } else {
+        (value.strip_suffix('B').unwrap_or(&value), 1)
+    };
+
+    number.trim().parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+impl TemplateConfig {
+    /// Whether a template file (relative, `/`-separated) is listed under `[raw]`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::TemplateConfig;
+    /// let mut config = TemplateConfig::default();
+    /// config.raw_files.push("assets/".to_string());
+    /// assert!(config.is_raw_file("assets/icon.png"));
+    /// assert!(!config.is_raw_file("$FILE_NAME.tsx"));
+    /// ```
+    pub fn is_raw_file(&self, relative_path: &str) -> bool {
+        self.raw_files.iter().any(|entry| {
+            entry == relative_path || (entry.ends_with('/') && relative_path.starts_with(entry))
+        })
+    }
+
+    /// Mode from `[permissions]` for a template file (relative, `/`-separated)
+    ///
+    /// An entry for the file itself wins over its directories, and deeper
+    /// directories over shallower ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::TemplateConfig;
+    /// let mut config = TemplateConfig::default();
+    /// config.permissions.insert("bin/".to_string(), 0o755);
+    /// config.permissions.insert("bin/README.md".to_string(), 0o644);
+    /// assert_eq!(config.permission_for("bin/setup.sh"), Some(0o755));
+    /// assert_eq!(config.permission_for("bin/README.md"), Some(0o644));
+    /// assert_eq!(config.permission_for("$FILE_NAME.tsx"), None);
+    /// ```
+    pub fn permission_for(&self, relative_path: &str) -> Option<u32> {
+        if let Some(mode) = self.permissions.get(relative_path) {
+            return Some(*mode);
+        }
+        self.permissions
+            .iter()
+            .filter(|(entry, _)| entry.ends_with('/') && relative_path.starts_with(entry.as_str()))
+            .max_by_key(|(entry, _)| entry.len())
+            .map(|(_, mode)| *mode)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +600,8 @@ mod tests {
         assert!(config.enable_uuid);
         assert!(config.file_filters.is_empty());
         assert!(config.options_metadata.is_empty());
+        assert!(config.filename_case.is_none());
+        assert!(config.folder_case.is_none());
         // environment should be "development" or actual NODE_ENV
         assert!(!config.environment.is_empty());
     }
@@ -166,6 +620,7 @@ mod tests {
             var_type: "boolean".to_string(),
             possible_values: vec!["true".to_string(), "false".to_string()],
             description: "Enable tests".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(option.var_type, "boolean");
@@ -232,10 +687,14 @@ mod tests {
         let metadata = TemplateMetadata {
             name: "Component Template".to_string(),
             description: "React component template with tests".to_string(),
+            tags: vec!["component".to_string()],
+            requires_cli: None,
+            internal: false,
         };
 
         assert_eq!(metadata.name, "Component Template");
         assert_eq!(metadata.description, "React component template with tests");
+        assert_eq!(metadata.tags, ["component"]);
     }
 
     #[test]
@@ -248,6 +707,7 @@ mod tests {
                 "styled-components".to_string(),
             ],
             description: "Styling approach".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(option.var_type, "enum");
@@ -257,4 +717,25 @@ mod tests {
             .possible_values
             .contains(&"styled-components".to_string()));
     }
+
+    #[test]
+    fn test_is_raw_file() {
+        let mut config = TemplateConfig::default();
+        config.raw_files.push("assets/".to_string());
+        config.raw_files.push("logo.png".to_string());
+
+        assert!(config.is_raw_file("assets/fonts/Inter.woff2"));
+        assert!(config.is_raw_file("logo.png"));
+        assert!(!config.is_raw_file("nested/logo.png"));
+        assert!(!config.is_raw_file("assets.ts"));
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("1024"), Some(1024));
+        assert_eq!(parse_byte_size("2 MB"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_byte_size("1gb"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_byte_size("-1"), None);
+        assert_eq!(parse_byte_size(""), None);
+    }
 }
@@ -0,0 +1,290 @@
+//! What to do when a generated file already exists with different content.
+//!
+//! With [`ConflictPolicy::Prompt`] the engine prints a colored unified diff
+//! of the existing file against the new rendering and asks before
+//! overwriting, so template upgrades over existing components can be
+//! reviewed inline.
+//!
+//! [`ConflictPolicy::Merge`] records every generated file in a
+//! [`GenerationManifest`] in the output directory. On regeneration the
+//! recorded original, the user-edited file and the new rendering are merged
+//! three ways, with conflict markers where both changed the same lines.
+
+use anyhow::{Context, Result};
+use colored::*;
+use inquire::{Confirm, InquireError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::diff::{merge3, unified_diff};
+use super::stamp::content_hash;
+use crate::ui::{self, symbols, t};
+
+/// Manifest of generated files, relative to the output directory
+pub const MANIFEST_FILE: &str = ".cli-frontend-manifest.json";
+
+/// Handling of existing files whose content would change (`--on-conflict`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Replace existing files without asking
+    #[default]
+    Overwrite,
+    /// Keep existing files that differ
+    Skip,
+    /// Show a diff and ask for each file that differs
+    Prompt,
+    /// Three-way merge with the originally generated content
+    Merge,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "overwrite" => Ok(Self::Overwrite),
+            "skip" => Ok(Self::Skip),
+            "prompt" => Ok(Self::Prompt),
+            "merge" => Ok(Self::Merge),
+            other => Err(format!(
+                "unknown conflict policy '{}', expected overwrite, skip, prompt or merge",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overwrite => "overwrite",
+            Self::Skip => "skip",
+            Self::Prompt => "prompt",
+            Self::Merge => "merge",
+        })
+    }
+}
+
+impl ConflictPolicy {
+    /// Contents to write over the differing `existing` file at `path`, or
+    /// `None` to keep it
+    ///
+    /// `original` is the content recorded when the file was last generated,
+    /// used by [`ConflictPolicy::Merge`].
+    pub fn resolve(
+        &self,
+        path: &Path,
+        original: Option<&str>,
+        existing: &[u8],
+        new: &[u8],
+    ) -> Result<Option<Vec<u8>>> {
+        match self {
+            Self::Overwrite => Ok(Some(new.to_vec())),
+            Self::Skip => {
+                ui::show!(
+                    "{} {}",
+                    symbols::SKIP.as_str().yellow(),
+                    t!("conflict.kept", path = path.display())
+                );
+                Ok(None)
+            }
+            Self::Prompt => {
+                print_diff(path, existing, new);
+                let answer = Confirm::new(&t!("conflict.overwrite", path = path.display()))
+                    .with_default(false)
+                    .prompt();
+                match answer {
+                    Ok(overwrite) => Ok(overwrite.then(|| new.to_vec())),
+                    Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+                        Ok(None)
+                    }
+                    Err(e) => Err(e.into()),
+                }
+            }
+            Self::Merge => Ok(merge_file(path, original, existing, new)),
+        }
+    }
+}
+
+/// Three-way merge of a regenerated text file
+fn merge_file(path: &Path, original: Option<&str>, existing: &[u8], new: &[u8]) -> Option<Vec<u8>> {
+    let (Ok(existing), Ok(new)) = (std::str::from_utf8(existing), std::str::from_utf8(new)) else {
+        ui::show!(
+            "{} {}",
+            symbols::SKIP.as_str().yellow(),
+            t!("conflict.kept_binary", path = path.display())
+        );
+        return None;
+    };
+
+    match original {
+        // Not edited since it was generated: take the new rendering
+        Some(original) if original == existing => return Some(new.as_bytes().to_vec()),
+        // Template output unchanged: keep the user's edits
+        Some(original) if original == new => return None,
+        Some(_) => {}
+        None => ui::warning!(
+            "Warning: No recorded original for {}, marking the whole file as a conflict",
+            path.display()
+        ),
+    }
+
+    let merge = merge3(original.unwrap_or_default(), existing, new);
+    if merge.conflicts == 0 {
+        ui::show!(
+            "{} {}",
+            symbols::MERGE.as_str().bold(),
+            t!("conflict.merged", path = path.display())
+        );
+    } else {
+        ui::show!(
+            "{} {}",
+            symbols::WARNING.as_str().yellow(),
+            t!(
+                "conflict.merged_with_conflicts",
+                path = path.display(),
+                count = merge.conflicts
+            )
+        );
+    }
+    Some(merge.text.into_bytes())
+}
+
+/// Originally generated content of each file, keyed by path relative to the
+/// output directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// A recorded generated file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// FNV-1a hash of the content, for tools that only need to detect edits
+    pub hash: String,
+    pub content: String,
+}
+
+impl GenerationManifest {
+    /// Parse a manifest, starting over if it can't be read
+    pub fn parse(bytes: &[u8]) -> Self {
+        serde_json::from_slice(bytes).unwrap_or_else(|e| {
+            ui::warning!("Warning: Ignoring invalid {}: {}", MANIFEST_FILE, e);
+            Self::default()
+        })
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self).context("Could not serialize generation manifest")
+    }
+
+    /// Content recorded for `key` when it was last generated
+    pub fn original(&self, key: &str) -> Option<&str> {
+        self.files.get(key).map(|entry| entry.content.as_str())
+    }
+
+    /// Record `content` as the generated version of `key` (text files only)
+    pub fn record(&mut self, key: String, content: &[u8]) {
+        if let Ok(content) = std::str::from_utf8(content) {
+            self.files.insert(
+                key,
+                ManifestEntry {
+                    hash: content_hash(content.as_bytes()),
+                    content: content.to_string(),
+                },
+            );
+        }
+    }
+}
+
+/// Print a colored unified diff of `existing` against `new`
+fn print_diff(path: &Path, existing: &[u8], new: &[u8]) {
+    ui::show!();
+    ui::show!(
+        "{} {}",
+        symbols::EDIT.as_str().bold(),
+        path.display().to_string().bold()
+    );
+
+    let (Ok(existing), Ok(new)) = (std::str::from_utf8(existing), std::str::from_utf8(new)) else {
+        ui::show!("  {}", t!("conflict.binary_differs").dimmed());
+        return;
+    };
+
+    let label = path.display().to_string();
+    let diff = unified_diff(
+        existing,
+        new,
+        &format!("{} (existing)", label),
+        &format!("{} (new)", label),
+    );
+    if diff.is_empty() {
+        ui::show!("  {}", t!("conflict.only_line_endings").dimmed());
+        return;
+    }
+
+    for line in diff.lines() {
+        let colored = if line.starts_with("---") || line.starts_with("+++") {
+            line.bold()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else {
+            line.normal()
+        };
+        ui::show!("{}", colored);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_policy_parsing_and_skip() {
+        assert_eq!("Prompt".parse(), Ok(ConflictPolicy::Prompt));
+        assert!("rebase".parse::<ConflictPolicy>().is_err());
+
+        let path = Path::new("Button.tsx");
+        assert_eq!(
+            ConflictPolicy::Overwrite
+                .resolve(path, None, b"old", b"new")
+                .unwrap(),
+            Some(b"new".to_vec())
+        );
+        assert_eq!(
+            ConflictPolicy::Skip
+                .resolve(path, None, b"old", b"new")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_uses_recorded_original() {
+        let path = Path::new("Button.tsx");
+        let merge = |original, existing: &str, new: &str| {
+            ConflictPolicy::Merge
+                .resolve(path, original, existing.as_bytes(), new.as_bytes())
+                .unwrap()
+        };
+
+        assert_eq!(merge(Some("v1\n"), "v1\n", "v2\n"), Some(b"v2\n".to_vec()));
+        assert_eq!(merge(Some("v1\n"), "edited\n", "v1\n"), None);
+        assert!(String::from_utf8(merge(None, "edited\n", "v2\n").unwrap())
+            .unwrap()
+            .starts_with("<<<<<<< current\nedited\n"));
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(b""), "cbf29ce484222325");
+        assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,406 @@
+//! Structured template descriptions for machine consumers.
+//!
+//! `--describe` prints a template for humans; [`TemplateDescription`] carries
+//! the same information as serializable data for the HTTP and editor
+//! integrations.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::config::{TemplateConfig, TemplateExample};
+use super::suggest::did_you_mean;
+
+/// A template's metadata, variables and files
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateDescription {
+    /// Template directory name (what users pass to `--type`)
+    pub name: String,
+    /// Human-readable title from `[metadata] name`
+    pub title: String,
+    pub description: String,
+    /// `[metadata] tags`, lowercase
+    pub tags: Vec<String>,
+    /// `[metadata] requires_cli`, the oldest CLI version the template supports
+    pub requires_cli: Option<String>,
+    /// `[metadata] internal`: only meant to be used by architectures
+    pub internal: bool,
+    /// Template language from `engine`, e.g. `handlebars` or `minijinja`
+    pub engine: String,
+    pub variables: BTreeMap<String, VariableDescription>,
+    /// Template files relative to the template directory
+    pub files: Vec<String>,
+    /// `[files]` conditions keyed by file name
+    pub file_filters: BTreeMap<String, String>,
+    /// `[rules]` entries, e.g. `with_stories requires with_tests`
+    pub rules: Vec<String>,
+    /// Hand-written `[examples]`, empty when the template has none
+    pub examples: Vec<TemplateExample>,
+}
+
+/// A template variable, merged from `[options]` defaults and option metadata
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VariableDescription {
+    /// Default value, if the template defines one
+    pub default: Option<String>,
+    /// Type from `{var}_type` ("boolean", "enum", ...), empty when unspecified
+    #[serde(rename = "type")]
+    pub var_type: String,
+    /// Allowed values from `{var}_options`
+    pub values: Vec<String>,
+    pub description: String,
+    /// Set by `{var}_required`: generation fails without a value
+    pub required: bool,
+    /// Heading from `{var}_group`, empty when ungrouped
+    pub group: String,
+    /// Wizard question from `{var}_prompt`, empty for the default prompt
+    pub prompt: String,
+}
+
+/// How serious a [`VariableIssue`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// The value will not behave as intended
+    Error,
+    /// The value is probably a mistake (e.g. an unknown variable)
+    Warning,
+}
+
+/// A problem with a variable passed to a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VariableIssue {
+    pub variable: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl TemplateDescription {
+    /// Build a description from a parsed template config
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::{TemplateConfig, TemplateDescription};
+    ///
+    /// let mut config = TemplateConfig::default();
+    /// config.variables.insert("style".to_string(), "scss".to_string());
+    ///
+    /// let description = TemplateDescription::new("component", &config, vec![]);
+    /// assert_eq!(description.variables["style"].default.as_deref(), Some("scss"));
+    /// ```
+    pub fn new(name: &str, config: &TemplateConfig, files: Vec<String>) -> Self {
+        let names: BTreeSet<&String> = config
+            .variables
+            .keys()
+            .chain(config.options_metadata.keys())
+            .collect();
+
+        let variables = names
+            .into_iter()
+            .map(|var| {
+                let option = config.options_metadata.get(var);
+                let description = VariableDescription {
+                    default: config.variables.get(var).cloned(),
+                    var_type: option.map(|o| o.var_type.clone()).unwrap_or_default(),
+                    values: option
+                        .map(|o| o.possible_values.clone())
+                        .unwrap_or_default(),
+                    description: option.map(|o| o.description.clone()).unwrap_or_default(),
+                    required: option.is_some_and(|o| o.required),
+                    group: option.map(|o| o.group.clone()).unwrap_or_default(),
+                    prompt: option.map(|o| o.prompt.clone()).unwrap_or_default(),
+                };
+                (var.clone(), description)
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            title: config.metadata.name.clone(),
+            description: config.metadata.description.clone(),
+            tags: config.metadata.tags.clone(),
+            requires_cli: config.metadata.requires_cli.clone(),
+            internal: config.metadata.internal,
+            engine: config.engine.as_str().to_string(),
+            variables,
+            files,
+            file_filters: config
+                .file_filters
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            rules: config.rules.iter().map(ToString::to_string).collect(),
+            examples: config.examples.clone(),
+        }
+    }
+
+    /// Variables by `{var}_group`, groups sorted by name with ungrouped ones last
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::config::VariableOption;
+    /// use cli_frontend::template_engine::{TemplateConfig, TemplateDescription};
+    ///
+    /// let mut config = TemplateConfig::default();
+    /// config.variables.insert("author".to_string(), "me".to_string());
+    /// config.options_metadata.insert(
+    ///     "style".to_string(),
+    ///     VariableOption { group: "Styling".to_string(), ..Default::default() },
+    /// );
+    /// let description = TemplateDescription::new("component", &config, vec![]);
+    ///
+    /// let groups: Vec<(&str, usize)> = description
+    ///     .grouped_variables()
+    ///     .iter()
+    ///     .map(|(group, vars)| (*group, vars.len()))
+    ///     .collect();
+    /// assert_eq!(groups, vec![("Styling", 1), ("", 1)]);
+    /// ```
+    pub fn grouped_variables(&self) -> Vec<(&str, Vec<(&str, &VariableDescription)>)> {
+        let mut groups: BTreeMap<(bool, &str), Vec<(&str, &VariableDescription)>> = BTreeMap::new();
+        for (name, variable) in &self.variables {
+            let group = variable.group.as_str();
+            groups
+                .entry((group.is_empty(), group))
+                .or_default()
+                .push((name.as_str(), variable));
+        }
+
+        groups
+            .into_iter()
+            .map(|((_, group), variables)| (group, variables))
+            .collect()
+    }
+
+    /// Whether the template matches a `search` query and, if given, a tag
+    ///
+    /// Every word of `query` must appear (case-insensitively) in the
+    /// template's name, title, description or tags.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::{TemplateConfig, TemplateDescription};
+    ///
+    /// let mut config = TemplateConfig::default();
+    /// config.metadata.description = "Controlled text input".to_string();
+    /// config.metadata.tags = vec!["form".to_string()];
+    /// let description = TemplateDescription::new("input", &config, vec![]);
+    ///
+    /// assert!(description.matches("text form", None));
+    /// assert!(description.matches("", Some("Form")));
+    /// assert!(!description.matches("table", None));
+    /// assert!(!description.matches("input", Some("data-display")));
+    /// ```
+    pub fn matches(&self, query: &str, tag: Option<&str>) -> bool {
+        if let Some(tag) = tag {
+            if !self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim())) {
+                return false;
+            }
+        }
+
+        let haystack = [&self.name, &self.title, &self.description]
+            .into_iter()
+            .chain(&self.tags)
+            .map(|field| field.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("\n");
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| haystack.contains(word))
+    }
+
+    /// Required variables with neither a default nor a value in `vars`, sorted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::config::VariableOption;
+    /// use cli_frontend::template_engine::{TemplateConfig, TemplateDescription};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut config = TemplateConfig::default();
+    /// config.options_metadata.insert(
+    ///     "api_url".to_string(),
+    ///     VariableOption { required: true, ..Default::default() },
+    /// );
+    /// let description = TemplateDescription::new("service", &config, vec![]);
+    ///
+    /// assert_eq!(description.missing_required(&HashMap::new()), vec!["api_url"]);
+    /// ```
+    pub fn missing_required(&self, vars: &HashMap<String, String>) -> Vec<String> {
+        self.variables
+            .iter()
+            .filter(|(name, variable)| {
+                let value = vars.get(*name).or(variable.default.as_ref());
+                variable.required && value.is_none_or(|value| value.is_empty())
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Check `vars` against the template's declared variables
+    ///
+    /// Unknown variables are warnings; values outside `{var}_options` and
+    /// non-boolean values for boolean variables are errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::{TemplateConfig, TemplateDescription};
+    /// use std::collections::HashMap;
+    ///
+    /// let description = TemplateDescription::new("component", &TemplateConfig::default(), vec![]);
+    /// let vars = HashMap::from([("sytle".to_string(), "scss".to_string())]);
+    ///
+    /// let issues = description.validate(&vars);
+    /// assert_eq!(issues[0].variable, "sytle");
+    /// ```
+    pub fn validate(&self, vars: &HashMap<String, String>) -> Vec<VariableIssue> {
+        let mut names: Vec<&String> = vars.keys().collect();
+        names.sort();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let value = &vars[name];
+                let issue = |severity, message: String| VariableIssue {
+                    variable: name.clone(),
+                    severity,
+                    message,
+                };
+
+                let Some(variable) = self.variables.get(name) else {
+                    let known = self.variables.keys().map(String::as_str);
+                    let message = match did_you_mean(name, known) {
+                        Some(suggestion) => format!(
+                            "Unknown variable for template '{}', did you mean '{}'?",
+                            self.name, suggestion
+                        ),
+                        None => format!("Unknown variable for template '{}'", self.name),
+                    };
+                    return Some(issue(IssueSeverity::Warning, message));
+                };
+
+                if !variable.values.is_empty() && !variable.values.contains(value) {
+                    return Some(issue(
+                        IssueSeverity::Error,
+                        format!(
+                            "Invalid value '{}', expected one of: {}",
+                            value,
+                            variable.values.join(", ")
+                        ),
+                    ));
+                }
+
+                let is_boolean = matches!(
+                    value.to_lowercase().as_str(),
+                    "true" | "false" | "yes" | "no" | "1" | "0"
+                );
+                if variable.var_type == "boolean" && !is_boolean {
+                    return Some(issue(
+                        IssueSeverity::Error,
+                        format!("Invalid boolean '{}', expected true or false", value),
+                    ));
+                }
+
+                None
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::config::VariableOption;
+
+    #[test]
+    fn test_merges_defaults_and_option_metadata() {
+        let mut config = TemplateConfig::default();
+        config
+            .variables
+            .insert("with_tests".to_string(), "true".to_string());
+        config.options_metadata.insert(
+            "style".to_string(),
+            VariableOption {
+                var_type: "enum".to_string(),
+                possible_values: vec!["css".to_string(), "scss".to_string()],
+                description: "Styling".to_string(),
+                ..Default::default()
+            },
+        );
+        config
+            .file_filters
+            .insert("$FILE_NAME.spec.tsx".to_string(), "with_tests".to_string());
+
+        let description = TemplateDescription::new("component", &config, vec!["a.tsx".into()]);
+
+        assert_eq!(description.variables.len(), 2);
+        assert_eq!(description.variables["style"].default, None);
+        assert_eq!(description.variables["style"].values, vec!["css", "scss"]);
+        assert_eq!(
+            description.variables["with_tests"].default.as_deref(),
+            Some("true")
+        );
+
+        let json = serde_json::to_value(&description).unwrap();
+        assert_eq!(json["variables"]["style"]["type"], "enum");
+        assert_eq!(json["file_filters"]["$FILE_NAME.spec.tsx"], "with_tests");
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_and_invalid_values() {
+        let mut config = TemplateConfig::default();
+        config.options_metadata.insert(
+            "style".to_string(),
+            VariableOption {
+                var_type: "enum".to_string(),
+                possible_values: vec!["css".to_string(), "scss".to_string()],
+                description: String::new(),
+                ..Default::default()
+            },
+        );
+        config.options_metadata.insert(
+            "with_tests".to_string(),
+            VariableOption {
+                var_type: "boolean".to_string(),
+                ..Default::default()
+            },
+        );
+        let description = TemplateDescription::new("component", &config, vec![]);
+
+        let vars = HashMap::from([
+            ("style".to_string(), "less".to_string()),
+            ("with_tests".to_string(), "maybe".to_string()),
+            ("colour".to_string(), "red".to_string()),
+        ]);
+        let issues = description.validate(&vars);
+
+        let summary: Vec<(&str, IssueSeverity)> = issues
+            .iter()
+            .map(|i| (i.variable.as_str(), i.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("colour", IssueSeverity::Warning),
+                ("style", IssueSeverity::Error),
+                ("with_tests", IssueSeverity::Error),
+            ]
+        );
+
+        let valid = HashMap::from([
+            ("style".to_string(), "scss".to_string()),
+            ("with_tests".to_string(), "no".to_string()),
+        ]);
+        assert!(description.validate(&valid).is_empty());
+
+        let typo = HashMap::from([("stlye".to_string(), "css".to_string())]);
+        assert!(description.validate(&typo)[0]
+            .message
+            .ends_with("did you mean 'style'?"));
+    }
+}
@@ -0,0 +1,386 @@
+//! Line diffs between an existing file and its new rendering.
+//!
+//! Used to show what regenerating over an existing file would change, and
+//! to three-way merge a user-edited file with a new rendering of its
+//! template. The diff is a plain LCS over lines after trimming the common
+//! prefix and suffix, which is plenty for template-sized files.
+
+/// Lines of context around each change in [`unified_diff`]
+pub const CONTEXT_LINES: usize = 3;
+
+/// Beyond this many line pairs the changed middle is shown as replaced wholesale
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// One line of a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Context(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line-by-line diff turning `old` into `new`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::diff::{diff_lines, DiffLine};
+///
+/// let lines = diff_lines("a\nb\nc\n", "a\nB\nc\n");
+/// assert_eq!(
+///     lines,
+///     vec![
+///         DiffLine::Context("a"),
+///         DiffLine::Removed("b"),
+///         DiffLine::Added("B"),
+///         DiffLine::Context("c"),
+///     ]
+/// );
+/// ```
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<DiffLine> = old[..prefix].iter().map(|l| DiffLine::Context(l)).collect();
+    if old_middle.len() * new_middle.len() > MAX_LCS_CELLS {
+        lines.extend(old_middle.iter().map(|l| DiffLine::Removed(l)));
+        lines.extend(new_middle.iter().map(|l| DiffLine::Added(l)));
+    } else {
+        lines.extend(lcs_diff(old_middle, new_middle));
+    }
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Context(l)),
+    );
+    lines
+}
+
+/// Unified diff (`---`/`+++` header and `@@` hunks), empty when the lines match
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::diff::unified_diff;
+///
+/// let diff = unified_diff("a\nb\n", "a\nc\n", "Button.tsx (existing)", "Button.tsx (new)");
+/// assert_eq!(
+///     diff,
+///     "--- Button.tsx (existing)\n+++ Button.tsx (new)\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+/// );
+/// assert!(unified_diff("same\n", "same\n", "a", "b").is_empty());
+/// ```
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let lines = diff_lines(old, new);
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Context(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut i = 0;
+    while i < changes.len() {
+        // Changes whose context would overlap share a hunk
+        let mut j = i;
+        while j + 1 < changes.len() && changes[j + 1] - changes[j] <= 2 * CONTEXT_LINES + 1 {
+            j += 1;
+        }
+
+        let start = changes[i].saturating_sub(CONTEXT_LINES);
+        let end = (changes[j] + CONTEXT_LINES + 1).min(lines.len());
+        let hunk = &lines[start..end];
+
+        let old_start = 1 + lines[..start]
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Added(_)))
+            .count();
+        let new_start = 1 + lines[..start]
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Removed(_)))
+            .count();
+        let old_len = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Added(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|l| !matches!(l, DiffLine::Removed(_)))
+            .count();
+
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_len == 0 {
+                old_start - 1
+            } else {
+                old_start
+            },
+            old_len,
+            if new_len == 0 {
+                new_start - 1
+            } else {
+                new_start
+            },
+            new_len
+        ));
+        for line in hunk {
+            let (prefix, text) = match line {
+                DiffLine::Context(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            out.push(prefix);
+            out.push_str(text);
+            out.push('\n');
+        }
+
+        i = j + 1;
+    }
+
+    out
+}
+
+/// Result of [`merge3`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    pub text: String,
+    /// Number of regions written with conflict markers
+    pub conflicts: usize,
+}
+
+/// A replacement of `base[start..end]` made by one side of a merge
+struct Change<'a> {
+    start: usize,
+    end: usize,
+    lines: Vec<&'a str>,
+}
+
+/// Three-way merge of `current` and `new`, which both derive from `base`
+///
+/// Changes made by only one side are applied. Overlapping (or adjacent)
+/// changes that differ are written between `<<<<<<< current`,
+/// `||||||| original`, `=======` and `>>>>>>> new` markers.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::diff::merge3;
+///
+/// let base = "import a;\n\nfn one() {}\n\nfn two() {}\n";
+/// let current = "import a;\nimport b;\n\nfn one() {}\n\nfn two() {}\n";
+/// let new = "import a;\n\nfn one() {}\n\nfn two(x: u8) {}\n";
+///
+/// let merge = merge3(base, current, new);
+/// assert_eq!(merge.conflicts, 0);
+/// assert_eq!(merge.text, "import a;\nimport b;\n\nfn one() {}\n\nfn two(x: u8) {}\n");
+/// ```
+pub fn merge3(base: &str, current: &str, new: &str) -> Merge {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let ours = changes(&diff_lines(base, current));
+    let theirs = changes(&diff_lines(base, new));
+
+    let mut out: Vec<&str> = Vec::new();
+    let mut conflicts = 0;
+    let mut pos = 0;
+    let (mut a, mut b) = (0, 0);
+
+    while a < ours.len() || b < theirs.len() {
+        let start = match (ours.get(a), theirs.get(b)) {
+            (Some(x), Some(y)) => x.start.min(y.start),
+            (Some(x), None) => x.start,
+            (None, Some(y)) => y.start,
+            (None, None) => unreachable!(),
+        };
+
+        // Grow the region while changes from either side touch it
+        let (first_a, first_b) = (a, b);
+        let mut end = start;
+        loop {
+            if let Some(change) = ours.get(a).filter(|c| c.start <= end) {
+                end = end.max(change.end);
+                a += 1;
+            } else if let Some(change) = theirs.get(b).filter(|c| c.start <= end) {
+                end = end.max(change.end);
+                b += 1;
+            } else {
+                break;
+            }
+        }
+
+        out.extend(&base_lines[pos..start]);
+        let ours_region = apply(&base_lines, start, end, &ours[first_a..a]);
+        let theirs_region = apply(&base_lines, start, end, &theirs[first_b..b]);
+
+        if first_b == b || ours_region == theirs_region {
+            out.extend(ours_region);
+        } else if first_a == a {
+            out.extend(theirs_region);
+        } else {
+            conflicts += 1;
+            out.push("<<<<<<< current");
+            out.extend(ours_region);
+            out.push("||||||| original");
+            out.extend(&base_lines[start..end]);
+            out.push("=======");
+            out.extend(theirs_region);
+            out.push(">>>>>>> new");
+        }
+        pos = end;
+    }
+    out.extend(&base_lines[pos..]);
+
+    let mut text = out.join("\n");
+    if !text.is_empty() && (new.ends_with('\n') || current.ends_with('\n')) {
+        text.push('\n');
+    }
+    Merge { text, conflicts }
+}
+
+/// Group a diff into replacements of base line ranges
+fn changes<'a>(lines: &[DiffLine<'a>]) -> Vec<Change<'a>> {
+    let mut changes: Vec<Change> = Vec::new();
+    let mut base_index = 0;
+    let mut in_change = false;
+
+    for line in lines {
+        match line {
+            DiffLine::Context(_) => {
+                base_index += 1;
+                in_change = false;
+                continue;
+            }
+            _ if !in_change => {
+                changes.push(Change {
+                    start: base_index,
+                    end: base_index,
+                    lines: Vec::new(),
+                });
+                in_change = true;
+            }
+            _ => {}
+        }
+
+        let change = changes.last_mut().expect("change was just started");
+        match line {
+            DiffLine::Removed(_) => {
+                base_index += 1;
+                change.end = base_index;
+            }
+            DiffLine::Added(text) => change.lines.push(text),
+            DiffLine::Context(_) => unreachable!(),
+        }
+    }
+
+    changes
+}
+
+/// `base[start..end]` with one side's changes applied
+fn apply<'a>(base: &[&'a str], start: usize, end: usize, changes: &[Change<'a>]) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    let mut index = start;
+    for change in changes {
+        lines.extend(&base[index..change.start]);
+        lines.extend(&change.lines);
+        index = change.end;
+    }
+    lines.extend(&base[index..end]);
+    lines
+}
+
+/// Diff two line slices through their longest common subsequence
+fn lcs_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // lengths[i][j]: LCS length of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Context(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed(l)));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added(l)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_splits_distant_changes_into_hunks() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "");
+
+        let diff = unified_diff(&old, &new, "old", "new");
+        let hunks: Vec<&str> = diff.lines().filter(|l| l.starts_with("@@")).collect();
+        assert_eq!(hunks, vec!["@@ -1,5 +1,5 @@", "@@ -15,6 +15,5 @@"]);
+        assert!(diff.contains("-line 2\n+line two\n"));
+        assert!(diff.contains("-line 18\n"));
+    }
+
+    #[test]
+    fn test_merge3_marks_conflicting_edits() {
+        let base = "a\nb\nc\n";
+        let merge = merge3(base, "a\nB (mine)\nc\n", "a\nB (template)\nc\n");
+        assert_eq!(merge.conflicts, 1);
+        assert_eq!(
+            merge.text,
+            "a\n<<<<<<< current\nB (mine)\n||||||| original\nb\n=======\nB (template)\n>>>>>>> new\nc\n"
+        );
+
+        // Identical edits on both sides are not a conflict
+        let merge = merge3(base, "a\nx\nc\n", "a\nx\nc\n");
+        assert_eq!((merge.text.as_str(), merge.conflicts), ("a\nx\nc\n", 0));
+    }
+
+    #[test]
+    fn test_diff_lines_handles_insertions_at_the_edges() {
+        assert_eq!(
+            diff_lines("b\n", "a\nb\nc\n"),
+            vec![
+                DiffLine::Added("a"),
+                DiffLine::Context("b"),
+                DiffLine::Added("c"),
+            ]
+        );
+        assert_eq!(diff_lines("", "x"), vec![DiffLine::Added("x")]);
+    }
+}
@@ -0,0 +1,272 @@
+//! Markdown catalog generation for template packs
+//!
+//! Builds a `TEMPLATES.md` document describing every template (metadata,
+//! variables, generated files, example invocations) and every architecture,
+//! so template packs can ship documentation that never drifts from the
+//! actual `.conf` files.
+
+use std::fmt::Write;
+
+use super::config::TemplateConfig;
+use super::inspector::format_condition;
+use crate::config::ArchitectureConfig;
+
+/// Documentation input for a single template
+pub struct TemplateDoc {
+    /// Template directory name (what users pass to `--type`)
+    pub name: String,
+    /// Parsed `.conf` (or defaults when the template has none)
+    pub config: TemplateConfig,
+    /// Template files relative to the template directory
+    pub files: Vec<String>,
+}
+
+/// Render the full catalog as Markdown
+pub fn render_catalog(
+    templates: &[TemplateDoc],
+    architectures: &[(String, ArchitectureConfig)],
+) -> String {
+    let mut out = String::new();
+
+    out.push_str("# Template Catalog\n\n");
+    out.push_str("> Generated by `cli-frontend docs`. Do not edit by hand.\n\n");
+
+    if !templates.is_empty() {
+        out.push_str("## Templates\n\n");
+        for template in templates {
+            let _ = writeln!(out, "- [{}](#{})", template.name, template.name);
+        }
+        out.push('\n');
+
+        for template in templates {
+            render_template_section(&mut out, template);
+        }
+    }
+
+    if !architectures.is_empty() {
+        out.push_str("## Architectures\n\n");
+        for (name, arch) in architectures {
+            render_architecture_section(&mut out, name, arch);
+        }
+    }
+
+    out
+}
+
+/// Render the section describing one template
+fn render_template_section(out: &mut String, template: &TemplateDoc) {
+    let config = &template.config;
+
+    let _ = writeln!(out, "### {}\n", template.name);
+
+    if !config.metadata.name.is_empty() {
+        let _ = writeln!(out, "**{}**\n", config.metadata.name);
+    }
+    if !config.metadata.description.is_empty() {
+        let _ = writeln!(out, "{}\n", config.metadata.description);
+    }
+    if !config.metadata.tags.is_empty() {
+        let _ = writeln!(out, "Tags: {}\n", config.metadata.tags.join(", "));
+    }
+
+    if !config.variables.is_empty() || !config.options_metadata.is_empty() {
+        out.push_str("#### Variables\n\n");
+        out.push_str("| Variable | Type | Default | Options | Description |\n");
+        out.push_str("|----------|------|---------|---------|-------------|\n");
+
+        let mut names: Vec<&String> = config
+            .variables
+            .keys()
+            .chain(config.options_metadata.keys())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        for name in names {
+            let option = config.options_metadata.get(name);
+            let var_type = match option {
+                Some(opt) if !opt.var_type.is_empty() => opt.var_type.as_str(),
+                Some(opt) if !opt.possible_values.is_empty() => "enum",
+                _ => "string",
+            };
+            let default = config.variables.get(name).map(String::as_str).unwrap_or("");
+            let options = option
+                .map(|opt| opt.possible_values.join(", "))
+                .unwrap_or_default();
+            let description = option.map(|opt| opt.description.as_str()).unwrap_or("");
+
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                name,
+                var_type,
+                code_or_empty(default),
+                options,
+                description
+            );
+        }
+        out.push('\n');
+    }
+
+    if !template.files.is_empty() {
+        out.push_str("#### Generated files\n\n");
+        for file in &template.files {
+            let condition = config
+                .file_filters
+                .get(file)
+                .map(|c| format_condition(c))
+                .unwrap_or_else(|| "(always)".to_string());
+            let _ = writeln!(out, "- `{}` {}", file, condition);
+        }
+        out.push('\n');
+    }
+
+    out.push_str("#### Examples\n\n```bash\n");
+    if !config.examples.is_empty() {
+        for example in &config.examples {
+            let _ = writeln!(out, "# {}", example.name);
+            let _ = writeln!(out, "{}", example.command);
+            for file in &example.files {
+                let _ = writeln!(out, "#   -> {}", file);
+            }
+        }
+        out.push_str("```\n\n");
+        return;
+    }
+
+    let _ = writeln!(out, "cli-frontend ComponentName --type {}", template.name);
+
+    let mut enum_vars: Vec<_> = config
+        .options_metadata
+        .iter()
+        .filter(|(_, opt)| opt.possible_values.len() > 1)
+        .collect();
+    enum_vars.sort_by_key(|(name, _)| *name);
+    if let Some((var_name, opt)) = enum_vars.first() {
+        let current = config.variables.get(*var_name);
+        let example = opt
+            .possible_values
+            .iter()
+            .find(|v| Some(*v) != current)
+            .unwrap_or(&opt.possible_values[0]);
+        let _ = writeln!(
+            out,
+            "cli-frontend ComponentName --type {} --var {}={}",
+            template.name, var_name, example
+        );
+    }
+    out.push_str("```\n\n");
+}
+
+/// Render the section describing one architecture
+fn render_architecture_section(out: &mut String, name: &str, arch: &ArchitectureConfig) {
+    let _ = writeln!(out, "### {}\n", name);
+    let _ = writeln!(out, "**{}**\n", arch.name);
+    let _ = writeln!(out, "{}\n", arch.description);
+
+    if !arch.structure.is_empty() {
+        out.push_str("| Path | Template | Filename | Description |\n");
+        out.push_str("|------|----------|----------|-------------|\n");
+        for structure in &arch.structure {
+            let path = if structure.path.is_empty() {
+                "."
+            } else {
+                structure.path.as_str()
+            };
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | `{}` | {} |",
+                path, structure.template, structure.filename_pattern, structure.description
+            );
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(
+        out,
+        "```bash\ncli-frontend FeatureName --type feature --architecture {}\n```\n",
+        name
+    );
+}
+
+/// Wrap a value in backticks unless it is empty
+fn code_or_empty(value: &str) -> String {
+    if value.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::config::VariableOption;
+
+    fn sample_template() -> TemplateDoc {
+        let mut config = TemplateConfig::default();
+        config.metadata.name = "React Component".to_string();
+        config.metadata.description = "Functional component".to_string();
+        config
+            .variables
+            .insert("style".to_string(), "scss".to_string());
+        config.options_metadata.insert(
+            "style".to_string(),
+            VariableOption {
+                var_type: String::new(),
+                possible_values: vec!["scss".to_string(), "css".to_string()],
+                description: "Styling approach".to_string(),
+                ..Default::default()
+            },
+        );
+        config.file_filters.insert(
+            "$FILE_NAME.module.scss".to_string(),
+            "var_style_scss".to_string(),
+        );
+
+        TemplateDoc {
+            name: "component".to_string(),
+            config,
+            files: vec![
+                "$FILE_NAME.module.scss".to_string(),
+                "$FILE_NAME.tsx".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_catalog_template_section() {
+        let doc = render_catalog(&[sample_template()], &[]);
+
+        assert!(doc.contains("# Template Catalog"));
+        assert!(doc.contains("### component"));
+        assert!(doc.contains("**React Component**"));
+        assert!(doc.contains("| `style` | enum | `scss` | scss, css | Styling approach |"));
+        assert!(doc.contains("- `$FILE_NAME.module.scss` (--var style=scss)"));
+        assert!(doc.contains("- `$FILE_NAME.tsx` (always)"));
+        assert!(doc.contains("cli-frontend ComponentName --type component --var style=css"));
+    }
+
+    #[test]
+    fn test_render_catalog_architecture_section() {
+        let arch = ArchitectureConfig::parse_json(
+            r#"{
+                "name": "Clean",
+                "description": "Layers",
+                "benefits": [],
+                "limitations": [],
+                "structure": [
+                    {"path": "", "template": "types", "filename_pattern": "types", "description": "Types"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let doc = render_catalog(&[], &[("clean".to_string(), arch)]);
+
+        assert!(doc.contains("## Architectures"));
+        assert!(doc.contains("| `.` | types | `types` | Types |"));
+        assert!(doc.contains("--architecture clean"));
+        assert!(!doc.contains("## Templates"));
+    }
+}
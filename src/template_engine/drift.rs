@@ -0,0 +1,192 @@
+//! Stamped files that drifted from their template (`cli-frontend drift`).
+//!
+//! Scans a project for files carrying a [`Stamp`] and checks each one
+//! against its own content hash, to find hand edits, and against the
+//! current hash of its template directory, to find files generated from an
+//! older version of the template pack.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::config::WalkLimits;
+use super::generator::{template_hash, validate_template_exists};
+use super::stamp::{read_stamp, Stamp};
+
+/// Directories never scanned for stamped files, besides hidden ones
+const SKIPPED_DIRS: [&str; 2] = ["node_modules", "target"];
+
+/// Files larger than this aren't read; generated sources are far smaller
+const MAX_SCANNED_SIZE: u64 = 1024 * 1024;
+
+/// How a stamped file's template compares with the template pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateStatus {
+    /// Unchanged since the file was generated
+    Current,
+    /// Changed since the file was generated
+    Outdated,
+    /// No longer in the template pack
+    Missing,
+}
+
+/// A stamped file and how it drifted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftedFile {
+    /// Path relative to the scanned directory
+    pub path: PathBuf,
+    pub stamp: Stamp,
+    /// Edited since it was generated
+    pub modified: bool,
+    pub template: TemplateStatus,
+}
+
+impl DriftedFile {
+    /// Whether the file was edited or its template changed
+    pub fn has_drifted(&self) -> bool {
+        self.modified || self.template != TemplateStatus::Current
+    }
+}
+
+/// Every stamped file under `root`, ordered by path, checked against the
+/// templates in `templates_dir` and `packs`, walked within `limits`
+///
+/// Hidden directories, `node_modules` and `target` are skipped.
+pub fn scan(
+    root: &Path,
+    templates_dir: &Path,
+    packs: &BTreeMap<String, PathBuf>,
+    limits: WalkLimits,
+) -> Result<Vec<DriftedFile>> {
+    let mut hashes: HashMap<String, Option<String>> = HashMap::new();
+    let mut files = Vec::new();
+
+    let entries = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| !name.starts_with('.') && !SKIPPED_DIRS.contains(&name))
+        });
+    for entry in entries {
+        let entry = entry.context("Error walking project directory")?;
+        if !entry.file_type().is_file()
+            || entry
+                .metadata()
+                .map_or(true, |m| m.len() > MAX_SCANNED_SIZE)
+        {
+            continue;
+        }
+        // Binary and unreadable files can't carry a stamp
+        let Ok(content) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let Some((stamp, body)) = read_stamp(&content) else {
+            continue;
+        };
+
+        let current_hash = match hashes.get(&stamp.template) {
+            Some(hash) => hash.clone(),
+            None => {
+                let hash = match validate_template_exists(templates_dir, packs, &stamp.template) {
+                    Ok(template_dir) => Some(template_hash(&template_dir, limits)?),
+                    Err(_) => None,
+                };
+                hashes.insert(stamp.template.clone(), hash.clone());
+                hash
+            }
+        };
+        let template = match current_hash {
+            None => TemplateStatus::Missing,
+            Some(hash) if hash == stamp.template_hash => TemplateStatus::Current,
+            Some(_) => TemplateStatus::Outdated,
+        };
+
+        files.push(DriftedFile {
+            path: entry
+                .path()
+                .strip_prefix(root)
+                .unwrap_or(entry.path())
+                .to_path_buf(),
+            modified: stamp.is_modified(&body),
+            stamp,
+            template,
+        });
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::stamp::stamp_content;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_reports_edits_and_template_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = temp_dir.path().join("templates");
+        std::fs::create_dir_all(templates.join("component")).unwrap();
+        std::fs::write(templates.join("component").join("$FILE_NAME.tsx"), "v1").unwrap();
+        let hash = template_hash(&templates.join("component"), WalkLimits::default()).unwrap();
+
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir_all(project.join("node_modules")).unwrap();
+        let stamped = |file: &str, template: &str| {
+            let stamp = Stamp::new(template, "Button", &hash);
+            stamp_content(Path::new(file), "v1\n", &stamp).unwrap()
+        };
+        std::fs::write(project.join("a.tsx"), stamped("a.tsx", "component")).unwrap();
+        std::fs::write(
+            project.join("b.tsx"),
+            stamped("b.tsx", "component").replace("v1", "edited"),
+        )
+        .unwrap();
+        std::fs::write(project.join("c.tsx"), stamped("c.tsx", "gone")).unwrap();
+        std::fs::write(project.join("d.tsx"), "v1\n").unwrap();
+        std::fs::write(
+            project.join("node_modules").join("e.tsx"),
+            stamped("e.tsx", "component"),
+        )
+        .unwrap();
+
+        let files = scan(
+            &project,
+            &templates,
+            &BTreeMap::new(),
+            WalkLimits::default(),
+        )
+        .unwrap();
+        let summary: Vec<(&Path, bool, TemplateStatus)> = files
+            .iter()
+            .map(|file| (file.path.as_path(), file.modified, file.template))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Path::new("a.tsx"), false, TemplateStatus::Current),
+                (Path::new("b.tsx"), true, TemplateStatus::Current),
+                (Path::new("c.tsx"), false, TemplateStatus::Missing),
+            ]
+        );
+
+        std::fs::write(templates.join("component").join("$FILE_NAME.tsx"), "v2").unwrap();
+        let files = scan(
+            &project,
+            &templates,
+            &BTreeMap::new(),
+            WalkLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(files[0].template, TemplateStatus::Outdated);
+        assert!(files[0].has_drifted());
+    }
+}
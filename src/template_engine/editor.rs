@@ -0,0 +1,91 @@
+//! Opening generated output in an editor (`--open`).
+//!
+//! The editor command comes from `open_after_generate` in the configuration,
+//! then `$VISUAL`, then `$EDITOR`. A run that generated into a folder opens
+//! the folder; otherwise the main generated file is opened.
+
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+use super::generated::primary_file;
+use super::process::{quote_paths, shell};
+
+/// Editor to open generated files with: `configured`, then `$VISUAL`, then `$EDITOR`
+pub fn editor_command(configured: Option<&str>) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty())
+}
+
+/// What `--open` opens: the folder the files were generated into when
+/// `folder` is set and there are several files, otherwise the main file
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::editor::open_target;
+/// use std::path::PathBuf;
+///
+/// let paths = ["src/Button/Button.tsx", "src/Button/styles/Button.css"].map(PathBuf::from);
+/// assert_eq!(open_target(&paths, true), Some(PathBuf::from("src/Button")));
+/// assert_eq!(open_target(&paths, false), Some(PathBuf::from("src/Button/Button.tsx")));
+/// ```
+pub fn open_target(paths: &[PathBuf], folder: bool) -> Option<PathBuf> {
+    if folder && paths.len() > 1 {
+        let mut common: Vec<Component> = paths[0].parent()?.components().collect();
+        for path in &paths[1..] {
+            let shared = path
+                .components()
+                .zip(&common)
+                .take_while(|(a, b)| a == *b)
+                .count();
+            common.truncate(shared);
+        }
+        if !common.is_empty() {
+            return Some(common.iter().collect());
+        }
+    }
+    primary_file(paths).cloned()
+}
+
+/// Open `target` with the editor `command`, waiting for terminal editors to exit
+pub async fn open_in_editor(command: &str, target: &Path) -> Result<()> {
+    let command_line = format!("{} {}", command, quote_paths(&[target.to_path_buf()]));
+    let status = shell(&command_line)
+        .status()
+        .await
+        .with_context(|| format!("Could not run editor: {}", command))?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with {}: {}", status, command_line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_target_prefers_folder_then_main_file() {
+        let paths = [
+            "out/Button/index.ts",
+            "out/Button/Button.stories.tsx",
+            "out/Button/Button.tsx",
+        ]
+        .map(PathBuf::from);
+        assert_eq!(open_target(&paths, true), Some(PathBuf::from("out/Button")));
+        assert_eq!(
+            open_target(&paths, false),
+            Some(PathBuf::from("out/Button/Button.tsx"))
+        );
+
+        let single = [PathBuf::from("routes/users.$id.tsx")];
+        assert_eq!(open_target(&single, true), Some(single[0].clone()));
+        assert_eq!(open_target(&[], true), None);
+
+        assert_eq!(editor_command(Some(" code ")), Some("code".to_string()));
+    }
+}
@@ -0,0 +1,222 @@
+//! Helper invocation traces (`--explain`).
+//!
+//! When a run explains itself, every file is rendered with its helpers
+//! wrapped in a [`TracedHelper`], which records the helper's name, the
+//! arguments it received and what it wrote. The calls are collected per
+//! file into a shared [`RenderTrace`] that the CLI prints or saves as JSON
+//! once the run is done, so a surprising output can be traced back to the
+//! helper that produced it.
+//!
+//! Only the CLI's helpers (`pascal_case`, `eq`, `env`, ...) are traced;
+//! Handlebars' built-in block helpers such as `if` and `each` aren't.
+//! MiniJinja templates record their filters and functions the same way.
+
+use handlebars::{Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext};
+use handlebars::{RenderError, StringOutput};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// One helper invocation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HelperCall {
+    pub helper: String,
+    /// Positional arguments, after variable lookup
+    pub inputs: Vec<Value>,
+    /// What the helper wrote into the file
+    pub output: String,
+}
+
+/// Calls recorded while rendering one file, shared with its traced helpers
+pub type HelperCalls = Arc<Mutex<Vec<HelperCall>>>;
+
+/// Add a call of `helper` to `calls`
+pub fn record_call(calls: &HelperCalls, helper: &str, inputs: Vec<Value>, output: &str) {
+    calls.lock().unwrap().push(HelperCall {
+        helper: helper.to_string(),
+        inputs,
+        output: output.to_string(),
+    });
+}
+
+/// A helper that records every call into [`HelperCalls`] before writing its output
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::explain::{HelperCalls, TracedHelper};
+/// use cli_frontend::template_engine::helpers::pascal_case_helper;
+/// use handlebars::Handlebars;
+/// use serde_json::json;
+///
+/// let calls = HelperCalls::default();
+/// let mut handlebars = Handlebars::new();
+/// handlebars.register_helper(
+///     "pascal_case",
+///     Box::new(TracedHelper::new("pascal_case", Box::new(pascal_case_helper), calls.clone())),
+/// );
+///
+/// let output = handlebars
+///     .render_template("{{pascal_case name}}", &json!({"name": "user_card"}))
+///     .unwrap();
+/// assert_eq!(output, "UserCard");
+///
+/// let calls = calls.lock().unwrap();
+/// assert_eq!(calls[0].helper, "pascal_case");
+/// assert_eq!(calls[0].inputs, [json!("user_card")]);
+/// assert_eq!(calls[0].output, "UserCard");
+/// ```
+pub struct TracedHelper {
+    name: &'static str,
+    inner: Box<dyn HelperDef + Send + Sync>,
+    calls: HelperCalls,
+}
+
+impl TracedHelper {
+    pub fn new(
+        name: &'static str,
+        inner: Box<dyn HelperDef + Send + Sync>,
+        calls: HelperCalls,
+    ) -> Self {
+        Self { name, inner, calls }
+    }
+}
+
+impl HelperDef for TracedHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        r: &'reg Handlebars<'reg>,
+        ctx: &'rc handlebars::Context,
+        rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let mut buffer = StringOutput::new();
+        self.inner.call(h, r, ctx, rc, &mut buffer)?;
+        let output = buffer.into_string().map_err(RenderError::from)?;
+
+        let inputs = h
+            .params()
+            .iter()
+            .map(|param| param.value().clone())
+            .collect();
+        record_call(&self.calls, self.name, inputs, &output);
+        out.write(&output)?;
+        Ok(())
+    }
+}
+
+/// The helper calls made while rendering one file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileTrace {
+    /// The file in the template directory
+    pub template: PathBuf,
+    /// Where its rendered output goes
+    pub output: PathBuf,
+    pub calls: Vec<HelperCall>,
+}
+
+/// Helper calls of every file rendered during a run
+#[derive(Debug, Default)]
+pub struct RenderTrace {
+    files: Mutex<Vec<FileTrace>>,
+}
+
+impl RenderTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the calls made while rendering `template` into `output`
+    pub fn record(&self, template: PathBuf, output: PathBuf, calls: &HelperCalls) {
+        let calls = std::mem::take(&mut *calls.lock().unwrap());
+        self.files.lock().unwrap().push(FileTrace {
+            template,
+            output,
+            calls,
+        });
+    }
+
+    /// Recorded files, ordered by output path
+    pub fn files(&self) -> Vec<FileTrace> {
+        let mut files = self.files.lock().unwrap().clone();
+        files.sort_by(|a, b| a.output.cmp(&b.output));
+        files
+    }
+}
+
+#[cfg(feature = "runtime")]
+impl RenderTrace {
+    /// Write every file's helper calls to `out`, one call per line
+    pub fn write_report(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        use crate::ui::{self, symbols};
+        use colored::*;
+
+        ui::show_to!(out)?;
+        ui::show_to!(out, "{} Helper trace:", symbols::INSPECT.as_str().bold())?;
+        for file in self.files() {
+            ui::show_to!(
+                out,
+                "  {} {}",
+                file.output.display().to_string().bold(),
+                format!("(from {})", file.template.display()).dimmed()
+            )?;
+            if file.calls.is_empty() {
+                ui::show_to!(out, "    {}", "no helper calls".dimmed())?;
+            }
+            for call in &file.calls {
+                let inputs: Vec<String> = call.inputs.iter().map(Value::to_string).collect();
+                ui::show_to!(
+                    out,
+                    "    {}({}) {} {}",
+                    call.helper.cyan(),
+                    inputs.join(", "),
+                    symbols::ARROW,
+                    Value::String(call.output.clone())
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::helpers::eq_helper;
+    use serde_json::json;
+
+    #[test]
+    fn test_traced_helper_in_subexpression() {
+        let calls = HelperCalls::default();
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper(
+            "eq",
+            Box::new(TracedHelper::new("eq", Box::new(eq_helper), calls.clone())),
+        );
+
+        let output = handlebars
+            .render_template(
+                "{{#if (eq style \"scss\")}}scss{{else}}css{{/if}}",
+                &json!({"style": "scss"}),
+            )
+            .unwrap();
+        assert_eq!(output, "scss");
+
+        let trace = RenderTrace::new();
+        trace.record(PathBuf::from("b.ts"), PathBuf::from("out/b.ts"), &calls);
+        trace.record(PathBuf::from("a.ts"), PathBuf::from("out/a.ts"), &calls);
+        let files = trace.files();
+        assert_eq!(files[0].output, PathBuf::from("out/a.ts"));
+        assert!(files[0].calls.is_empty());
+        assert_eq!(
+            files[1].calls,
+            [HelperCall {
+                helper: "eq".to_string(),
+                inputs: vec![json!("scss"), json!("scss")],
+                output: "true".to_string(),
+            }]
+        );
+    }
+}
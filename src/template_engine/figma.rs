@@ -0,0 +1,290 @@
+//! Figma component metadata (`--figma-json`).
+//!
+//! Reads a component or component set exported from Figma, either the
+//! response of the REST API (`GET /v1/files/:key/nodes?ids=...`) or the bare
+//! node JSON, and turns its component properties into template input:
+//!
+//! - every property becomes an entry of `figma.props` in the template data,
+//!   ready to render a props interface or Storybook `argTypes`
+//! - property defaults are offered as variables named after the property in
+//!   snake_case, so a `Size` variant can fill a template's `size` option
+//!
+//! Component sets exported without `componentPropertyDefinitions` fall back
+//! to the variant names of their children (`Size=sm, State=Hover`).
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::naming::{to_camel_case, to_pascal_case, to_snake_case};
+
+/// A Figma component (or component set) and its properties
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FigmaComponent {
+    /// Component name as written in Figma, e.g. `Forms/Text Input`
+    pub name: String,
+    pub props: Vec<FigmaProp>,
+}
+
+/// Kind of a Figma component property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FigmaPropKind {
+    Variant,
+    Boolean,
+    Text,
+    InstanceSwap,
+}
+
+/// A component property, as exposed to templates in `figma.props`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FigmaProp {
+    /// camelCase name for code, e.g. `hasIcon`
+    pub name: String,
+    /// Name as written in Figma without the `#id` suffix, e.g. `Has Icon`
+    pub figma_name: String,
+    pub kind: FigmaPropKind,
+    /// Default value, empty for instance swaps
+    pub default: String,
+    /// Variant options, empty for other kinds
+    pub options: Vec<String>,
+    /// TypeScript type, e.g. `"sm" | "md" | "lg"` (render with `{{{ts_type}}}`)
+    pub ts_type: String,
+}
+
+impl FigmaComponent {
+    /// Parse exported Figma JSON, using the first component or component set found
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::figma::FigmaComponent;
+    ///
+    /// let component = FigmaComponent::parse(r#"{
+    ///     "name": "Button",
+    ///     "type": "COMPONENT_SET",
+    ///     "componentPropertyDefinitions": {
+    ///         "Size": {"type": "VARIANT", "defaultValue": "md", "variantOptions": ["sm", "md", "lg"]},
+    ///         "Disabled#4:1": {"type": "BOOLEAN", "defaultValue": false}
+    ///     }
+    /// }"#)?;
+    ///
+    /// assert_eq!(component.props[1].ts_type, r#""sm" | "md" | "lg""#);
+    /// assert_eq!(component.variables()[0], ("disabled".to_string(), "false".to_string()));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(json: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(json).context("Invalid Figma JSON")?;
+        let node = find_component(&value)
+            .ok_or_else(|| anyhow!("No component or component set found in Figma JSON"))?;
+
+        let props = match node
+            .get("componentPropertyDefinitions")
+            .and_then(Value::as_object)
+        {
+            Some(definitions) => definitions
+                .iter()
+                .map(|(key, definition)| FigmaProp::from_definition(key, definition))
+                .collect(),
+            None => variants_from_children(node),
+        };
+
+        Ok(Self {
+            name: node["name"].as_str().unwrap_or_default().to_string(),
+            props,
+        })
+    }
+
+    /// PascalCase name to generate, from the last segment of the Figma name
+    pub fn item_name(&self) -> String {
+        let last = self.name.rsplit('/').next().unwrap_or_default();
+        to_pascal_case(last).into_owned()
+    }
+
+    /// Property defaults as `(snake_case name, value)`, skipping instance swaps
+    pub fn variables(&self) -> Vec<(String, String)> {
+        self.props
+            .iter()
+            .filter(|prop| prop.kind != FigmaPropKind::InstanceSwap)
+            .map(|prop| (variable_name(&prop.figma_name), prop.default.clone()))
+            .collect()
+    }
+
+    /// Template data exposed as `figma` (`{{figma.name}}`, `{{#each figma.props}}`)
+    pub fn to_data(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+impl FigmaProp {
+    fn new(figma_name: &str, kind: FigmaPropKind, default: String, options: Vec<String>) -> Self {
+        let ts_type = match kind {
+            FigmaPropKind::Variant => options
+                .iter()
+                .map(|option| format!("{:?}", option))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            FigmaPropKind::Boolean => "boolean".to_string(),
+            FigmaPropKind::Text => "string".to_string(),
+            FigmaPropKind::InstanceSwap => "React.ReactNode".to_string(),
+        };
+
+        Self {
+            name: to_camel_case(figma_name).into_owned(),
+            figma_name: figma_name.to_string(),
+            kind,
+            default,
+            options,
+            ts_type,
+        }
+    }
+
+    /// Build a prop from a `componentPropertyDefinitions` entry
+    fn from_definition(key: &str, definition: &Value) -> Self {
+        // Non-variant property keys carry a node id suffix: `Label#12:0`
+        let figma_name = key.split_once('#').map_or(key, |(name, _)| name).trim();
+        let kind = match definition["type"].as_str().unwrap_or_default() {
+            "BOOLEAN" => FigmaPropKind::Boolean,
+            "TEXT" => FigmaPropKind::Text,
+            "INSTANCE_SWAP" => FigmaPropKind::InstanceSwap,
+            _ => FigmaPropKind::Variant,
+        };
+
+        let default = match (&definition["defaultValue"], kind) {
+            (_, FigmaPropKind::InstanceSwap) => String::new(),
+            (Value::String(value), _) => value.clone(),
+            (Value::Bool(value), _) => value.to_string(),
+            _ => String::new(),
+        };
+        let options = definition["variantOptions"]
+            .as_array()
+            .map(|options| {
+                options
+                    .iter()
+                    .filter_map(|option| option.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self::new(figma_name, kind, default, options)
+    }
+}
+
+/// snake_case variable name for a Figma property name
+fn variable_name(figma_name: &str) -> String {
+    to_snake_case(&to_pascal_case(figma_name)).into_owned()
+}
+
+/// Depth-first search for the first component set, component, or node with
+/// property definitions
+fn find_component(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Object(map) => {
+            let is_component = matches!(
+                map.get("type").and_then(Value::as_str),
+                Some("COMPONENT_SET" | "COMPONENT")
+            );
+            if is_component || map.contains_key("componentPropertyDefinitions") {
+                return Some(value);
+            }
+            map.values().find_map(find_component)
+        }
+        Value::Array(items) => items.iter().find_map(find_component),
+        _ => None,
+    }
+}
+
+/// Variant props from child names like `Size=sm, State=Hover`, defaulting
+/// to the first child's values
+fn variants_from_children(node: &Value) -> Vec<FigmaProp> {
+    let mut variants: Vec<(String, Vec<String>)> = Vec::new();
+
+    for child in node["children"].as_array().into_iter().flatten() {
+        let name = child["name"].as_str().unwrap_or_default();
+        for (property, value) in name.split(',').filter_map(|pair| pair.split_once('=')) {
+            let (property, value) = (property.trim(), value.trim().to_string());
+            match variants.iter_mut().find(|(name, _)| name == property) {
+                Some((_, values)) if !values.contains(&value) => values.push(value),
+                Some(_) => {}
+                None => variants.push((property.to_string(), vec![value])),
+            }
+        }
+    }
+
+    variants
+        .into_iter()
+        .map(|(property, options)| {
+            FigmaProp::new(
+                &property,
+                FigmaPropKind::Variant,
+                options[0].clone(),
+                options,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rest_api_nodes_response() {
+        let json = r#"{
+            "name": "Design System",
+            "nodes": {
+                "1:2": {
+                    "document": {
+                        "id": "1:2",
+                        "name": "Forms/Text Input",
+                        "type": "COMPONENT_SET",
+                        "componentPropertyDefinitions": {
+                            "State": {"type": "VARIANT", "defaultValue": "Default", "variantOptions": ["Default", "Error"]},
+                            "Label#3:0": {"type": "TEXT", "defaultValue": "Email"},
+                            "Has Icon#3:1": {"type": "BOOLEAN", "defaultValue": true},
+                            "Icon#3:2": {"type": "INSTANCE_SWAP", "defaultValue": "5:10"}
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let component = FigmaComponent::parse(json).unwrap();
+        assert_eq!(component.item_name(), "TextInput");
+
+        let names: Vec<&str> = component.props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["hasIcon", "icon", "label", "state"]);
+        assert_eq!(component.props[1].ts_type, "React.ReactNode");
+        assert_eq!(component.props[1].default, "");
+
+        assert_eq!(
+            component.variables(),
+            [
+                ("has_icon".to_string(), "true".to_string()),
+                ("label".to_string(), "Email".to_string()),
+                ("state".to_string(), "Default".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variants_from_child_names() {
+        let json = r#"{
+            "name": "Button",
+            "type": "COMPONENT_SET",
+            "children": [
+                {"name": "Size=md, Variant=Primary", "type": "COMPONENT"},
+                {"name": "Size=sm, Variant=Primary", "type": "COMPONENT"},
+                {"name": "Size=sm, Variant=Ghost", "type": "COMPONENT"}
+            ]
+        }"#;
+
+        let component = FigmaComponent::parse(json).unwrap();
+        assert_eq!(component.props.len(), 2);
+        assert_eq!(component.props[0].options, ["md", "sm"]);
+        assert_eq!(component.props[0].default, "md");
+        assert_eq!(component.props[1].ts_type, r#""Primary" | "Ghost""#);
+
+        assert!(FigmaComponent::parse(r#"{"name": "Page", "type": "FRAME"}"#).is_err());
+    }
+}
@@ -0,0 +1,571 @@
+//! Post-render formatting of generated files.
+//!
+//! Rendered templates keep whatever line endings and indentation the template
+//! file was saved with. This module normalizes line endings according to the
+//! `line_endings` setting (optionally detecting the project's convention from
+//! `.editorconfig` or `.gitattributes`) and applies `.editorconfig` indentation,
+//! trailing whitespace and final newline rules.
+//!
+//! # Example
+//!
+//! ```
+//! use cli_frontend::template_engine::formatting::{EditorConfigRules, IndentStyle, LineEndings};
+//!
+//! assert_eq!(LineEndings::Crlf.apply("a\nb\n"), "a\r\nb\r\n");
+//! assert_eq!(LineEndings::Lf.apply("a\r\nb\r\n"), "a\nb\n");
+//!
+//! let rules = EditorConfigRules {
+//!     indent_style: Some(IndentStyle::Tab),
+//!     insert_final_newline: Some(true),
+//!     ..Default::default()
+//! };
+//! assert_eq!(rules.apply("if (a) {\n  b();\n}"), "if (a) {\n\tb();\n}\n");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Line ending policy for generated files (`line_endings` setting).
+///
+/// `Auto` is resolved against the output location with [`LineEndings::resolve`]
+/// before writing; when nothing is detected the template's endings are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEndings {
+    Lf,
+    Crlf,
+    Auto,
+}
+
+impl LineEndings {
+    /// Parse a line ending setting (`lf`, `crlf`, `auto`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "lf" | "unix" => Some(Self::Lf),
+            "crlf" | "windows" => Some(Self::Crlf),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    /// Name of the policy as written in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "lf",
+            Self::Crlf => "crlf",
+            Self::Auto => "auto",
+        }
+    }
+
+    /// Resolve `Auto` by looking for project settings above `dir`
+    ///
+    /// Returns `None` when `Auto` finds no convention, meaning the rendered
+    /// content should be written unchanged.
+    pub fn resolve(self, dir: &Path) -> Option<Self> {
+        match self {
+            Self::Auto => detect_line_endings(dir),
+            explicit => Some(explicit),
+        }
+    }
+
+    /// Convert every line ending in `content` to this policy
+    ///
+    /// `Auto` leaves the content untouched; resolve it first.
+    pub fn apply<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Lf if content.contains("\r\n") => Cow::Owned(content.replace("\r\n", "\n")),
+            Self::Crlf => {
+                let lf = content.replace("\r\n", "\n");
+                let crlf = lf.replace('\n', "\r\n");
+                if crlf == content {
+                    Cow::Borrowed(content)
+                } else {
+                    Cow::Owned(crlf)
+                }
+            }
+            _ => Cow::Borrowed(content),
+        }
+    }
+}
+
+/// Detect the project's line ending convention for files written to `dir`
+///
+/// Walks up from `dir` and returns the first explicit setting found:
+/// `end_of_line` in a `[*]` section of `.editorconfig`, or `eol=` on a `*`
+/// pattern in `.gitattributes`. Stops at an `.editorconfig` with `root = true`.
+pub fn detect_line_endings(dir: &Path) -> Option<LineEndings> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    for ancestor in dir.ancestors() {
+        let editorconfig = ancestor.join(".editorconfig");
+        let mut is_root = false;
+        if let Ok(content) = std::fs::read_to_string(&editorconfig) {
+            let (endings, root) = parse_editorconfig_eol(&content);
+            if endings.is_some() {
+                return endings;
+            }
+            is_root = root;
+        }
+
+        if let Ok(content) = std::fs::read_to_string(ancestor.join(".gitattributes")) {
+            if let Some(endings) = parse_gitattributes_eol(&content) {
+                return Some(endings);
+            }
+        }
+
+        if is_root {
+            break;
+        }
+    }
+
+    None
+}
+
+/// Read `end_of_line` from the `[*]` section and the `root` flag
+fn parse_editorconfig_eol(content: &str) -> (Option<LineEndings>, bool) {
+    let file = EditorConfigFile::parse(content);
+    let endings = file
+        .sections
+        .iter()
+        .filter(|(pattern, _)| pattern == "*")
+        .flat_map(|(_, properties)| properties)
+        .filter(|(key, _)| key == "end_of_line")
+        .filter_map(|(_, value)| LineEndings::parse(value))
+        .rfind(|e| *e != LineEndings::Auto);
+
+    (endings, file.root)
+}
+
+/// Read `eol=lf|crlf` from a `*` pattern in `.gitattributes`
+fn parse_gitattributes_eol(content: &str) -> Option<LineEndings> {
+    let mut endings = None;
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        if parts.next() != Some("*") {
+            continue;
+        }
+        for attr in parts {
+            match attr {
+                "eol=lf" => endings = Some(LineEndings::Lf),
+                "eol=crlf" => endings = Some(LineEndings::Crlf),
+                _ => {}
+            }
+        }
+    }
+
+    endings
+}
+
+/// Indentation style from `.editorconfig` (`indent_style`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Space,
+    Tab,
+}
+
+/// `.editorconfig` properties that apply to one generated file.
+///
+/// Unset properties leave the rendered content as the template produced it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorConfigRules {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_size: Option<usize>,
+    pub insert_final_newline: Option<bool>,
+    pub trim_trailing_whitespace: Option<bool>,
+}
+
+impl EditorConfigRules {
+    /// Collect the rules that apply to `path` from every `.editorconfig` above it
+    ///
+    /// Files closer to `path` take precedence, and the search stops at a file
+    /// declaring `root = true`, following the EditorConfig specification.
+    pub fn for_file(path: &Path) -> Self {
+        let mut rules = Self::default();
+        let Some(parent) = path.parent() else {
+            return rules;
+        };
+        let parent = parent
+            .canonicalize()
+            .unwrap_or_else(|_| parent.to_path_buf());
+        let file_name = path.file_name().unwrap_or_default();
+        let target = parent.join(file_name);
+
+        for ancestor in parent.ancestors() {
+            let Ok(content) = std::fs::read_to_string(ancestor.join(".editorconfig")) else {
+                continue;
+            };
+            let file = EditorConfigFile::parse(&content);
+            let relative = target
+                .strip_prefix(ancestor)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+
+            // Later sections override earlier ones within a file
+            let mut found = Self::default();
+            for (pattern, properties) in &file.sections {
+                if section_matches(pattern, &relative) {
+                    for (key, value) in properties {
+                        found.set(key, value);
+                    }
+                }
+            }
+            rules.fill_from(found);
+
+            if file.root {
+                break;
+            }
+        }
+
+        rules
+    }
+
+    /// Whether no property is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply the rules to rendered content
+    pub fn apply(&self, content: &str) -> String {
+        let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+
+        if let Some(target_style) = self.indent_style {
+            if let Some(source_unit) = detect_indent_unit(content) {
+                let target_unit = match target_style {
+                    IndentStyle::Tab => "\t".to_string(),
+                    IndentStyle::Space => {
+                        " ".repeat(self.indent_size.unwrap_or_else(|| source_unit.len().max(1)))
+                    }
+                };
+                if target_unit != source_unit {
+                    for line in &mut lines {
+                        *line = reindent_line(line, &source_unit, &target_unit);
+                    }
+                }
+            }
+        }
+
+        if self.trim_trailing_whitespace == Some(true) {
+            for line in &mut lines {
+                let has_cr = line.ends_with('\r');
+                let trimmed = line.trim_end_matches(['\r', ' ', '\t']).to_string();
+                *line = if has_cr { trimmed + "\r" } else { trimmed };
+            }
+        }
+
+        let mut result = lines.join("\n");
+
+        match self.insert_final_newline {
+            Some(true) if !result.is_empty() && !result.ends_with('\n') => result.push('\n'),
+            Some(false) => {
+                let trimmed_len = result.trim_end_matches(['\r', '\n']).len();
+                result.truncate(trimmed_len);
+            }
+            _ => {}
+        }
+
+        result
+    }
+
+    /// Set a property from an `.editorconfig` key/value pair
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.to_lowercase();
+        match key {
+            "indent_style" => {
+                self.indent_style = match value.as_str() {
+                    "space" => Some(IndentStyle::Space),
+                    "tab" => Some(IndentStyle::Tab),
+                    _ => None,
+                }
+            }
+            "indent_size" => self.indent_size = value.parse().ok(),
+            "insert_final_newline" => self.insert_final_newline = value.parse().ok(),
+            "trim_trailing_whitespace" => self.trim_trailing_whitespace = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    /// Fill properties that are still unset from a less specific file
+    fn fill_from(&mut self, other: Self) {
+        self.indent_style = self.indent_style.or(other.indent_style);
+        self.indent_size = self.indent_size.or(other.indent_size);
+        self.insert_final_newline = self.insert_final_newline.or(other.insert_final_newline);
+        self.trim_trailing_whitespace = self
+            .trim_trailing_whitespace
+            .or(other.trim_trailing_whitespace);
+    }
+}
+
+/// Sections of a parsed `.editorconfig` file
+struct EditorConfigFile {
+    root: bool,
+    /// Section glob with its lowercase-keyed properties, in file order
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl EditorConfigFile {
+    fn parse(content: &str) -> Self {
+        let mut root = false;
+        let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                sections.push((line[1..line.len() - 1].trim().to_string(), Vec::new()));
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_lowercase();
+                let value = value.trim().to_string();
+                match sections.last_mut() {
+                    Some((_, properties)) => properties.push((key, value)),
+                    None if key == "root" => root = value.eq_ignore_ascii_case("true"),
+                    None => {}
+                }
+            }
+        }
+
+        Self { root, sections }
+    }
+}
+
+/// Match an `.editorconfig` section glob against a `/`-separated relative path
+///
+/// Patterns without a `/` match the file name at any depth. Supports `*`,
+/// `**`, `?` and `{a,b}` alternatives.
+fn section_matches(pattern: &str, relative_path: &str) -> bool {
+    let (pattern, subject) = if pattern.contains('/') {
+        (pattern.trim_start_matches('/'), relative_path)
+    } else {
+        (
+            pattern,
+            relative_path.rsplit('/').next().unwrap_or(relative_path),
+        )
+    };
+
+    expand_braces(pattern)
+        .iter()
+        .any(|p| glob_match(p.as_bytes(), subject.as_bytes()))
+}
+
+/// Expand `{a,b}` alternatives into separate patterns
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let (Some(open), Some(close)) = (pattern.find('{'), pattern.find('}')) else {
+        return vec![pattern.to_string()];
+    };
+    if close < open {
+        return vec![pattern.to_string()];
+    }
+
+    let (prefix, rest) = pattern.split_at(open);
+    let alternatives = &rest[1..close - open];
+    let suffix = &rest[close - open + 1..];
+
+    alternatives
+        .split(',')
+        .flat_map(|alt| expand_braces(&format!("{}{}{}", prefix, alt, suffix)))
+        .collect()
+}
+
+/// Minimal glob matcher: `*` stays within a path segment, `**` crosses them
+fn glob_match(pattern: &[u8], subject: &[u8]) -> bool {
+    match pattern.first() {
+        None => subject.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = &pattern[2..];
+            (0..=subject.len()).any(|i| glob_match(rest, &subject[i..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            for i in 0..=subject.len() {
+                if glob_match(rest, &subject[i..]) {
+                    return true;
+                }
+                if subject.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => {
+            !subject.is_empty() && subject[0] != b'/' && glob_match(&pattern[1..], &subject[1..])
+        }
+        Some(c) => subject.first() == Some(c) && glob_match(&pattern[1..], &subject[1..]),
+    }
+}
+
+/// Detect the indentation unit used by rendered content
+///
+/// Returns `"\t"` if any line is tab-indented, otherwise the smallest
+/// non-zero run of leading spaces. Block comment continuations (` * ...`) are
+/// ignored since their single space is alignment, not indentation. `None`
+/// when nothing is indented.
+fn detect_indent_unit(content: &str) -> Option<String> {
+    let mut smallest: Option<usize> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('*') {
+            continue;
+        }
+        if line.starts_with('\t') {
+            return Some("\t".to_string());
+        }
+        let spaces = line.len() - line.trim_start_matches(' ').len();
+        if spaces > 0 {
+            smallest = Some(smallest.map_or(spaces, |s| s.min(spaces)));
+        }
+    }
+
+    smallest.map(|n| " ".repeat(n))
+}
+
+/// Replace each leading `source_unit` of a line with `target_unit`
+fn reindent_line(line: &str, source_unit: &str, target_unit: &str) -> String {
+    let mut rest = line;
+    let mut levels = 0;
+    while let Some(stripped) = rest.strip_prefix(source_unit) {
+        rest = stripped;
+        levels += 1;
+    }
+    format!("{}{}", target_unit.repeat(levels), rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_line_endings_parse() {
+        assert_eq!(LineEndings::parse("CRLF"), Some(LineEndings::Crlf));
+        assert_eq!(LineEndings::parse("lf"), Some(LineEndings::Lf));
+        assert_eq!(LineEndings::parse("auto"), Some(LineEndings::Auto));
+        assert_eq!(LineEndings::parse("cr"), None);
+    }
+
+    #[test]
+    fn test_line_endings_apply() {
+        assert_eq!(LineEndings::Crlf.apply("a\r\nb\nc"), "a\r\nb\r\nc");
+        assert_eq!(LineEndings::Lf.apply("a\r\nb\nc"), "a\nb\nc");
+        assert!(matches!(LineEndings::Lf.apply("a\nb"), Cow::Borrowed(_)));
+        assert_eq!(LineEndings::Auto.apply("a\r\nb\n"), "a\r\nb\n");
+    }
+
+    #[test]
+    fn test_detect_line_endings_from_editorconfig() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".editorconfig"),
+            "root = true\n\n[*]\nend_of_line = crlf\n\n[*.md]\nend_of_line = lf\n",
+        )
+        .unwrap();
+        let nested = temp_dir.path().join("src/components");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(detect_line_endings(&nested), Some(LineEndings::Crlf));
+        assert_eq!(LineEndings::Auto.resolve(&nested), Some(LineEndings::Crlf));
+    }
+
+    #[test]
+    fn test_detect_line_endings_from_gitattributes() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".editorconfig"), "root = true\n").unwrap();
+        std::fs::write(
+            temp_dir.path().join(".gitattributes"),
+            "*.png binary\n* text=auto eol=lf\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_line_endings(temp_dir.path()), Some(LineEndings::Lf));
+    }
+
+    #[test]
+    fn test_detect_line_endings_stops_at_root() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".editorconfig"), "root = true\n").unwrap();
+
+        assert_eq!(detect_line_endings(temp_dir.path()), None);
+        assert_eq!(LineEndings::Auto.resolve(temp_dir.path()), None);
+    }
+
+    #[test]
+    fn test_section_matches() {
+        assert!(section_matches("*", "src/Button.tsx"));
+        assert!(section_matches("*.{ts,tsx}", "src/Button.tsx"));
+        assert!(!section_matches("*.{ts,tsx}", "src/Button.scss"));
+        assert!(section_matches("src/**.tsx", "src/components/Button.tsx"));
+        assert!(!section_matches("lib/*.tsx", "src/Button.tsx"));
+        assert!(section_matches("Makefile", "Makefile"));
+    }
+
+    #[test]
+    fn test_editorconfig_rules_apply_indentation() {
+        let content = "/**\n * Doc\n */\nfunction a() {\n  if (b) {\n    c();\n  }\n}\n";
+
+        let tabs = EditorConfigRules {
+            indent_style: Some(IndentStyle::Tab),
+            ..Default::default()
+        };
+        assert_eq!(
+            tabs.apply(content),
+            "/**\n * Doc\n */\nfunction a() {\n\tif (b) {\n\t\tc();\n\t}\n}\n"
+        );
+
+        let four = EditorConfigRules {
+            indent_style: Some(IndentStyle::Space),
+            indent_size: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            four.apply(content),
+            "/**\n * Doc\n */\nfunction a() {\n    if (b) {\n        c();\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_editorconfig_rules_apply_whitespace_rules() {
+        let rules = EditorConfigRules {
+            trim_trailing_whitespace: Some(true),
+            insert_final_newline: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(rules.apply("a  \r\nb\t"), "a\r\nb\n");
+
+        let no_newline = EditorConfigRules {
+            insert_final_newline: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(no_newline.apply("a\n\n"), "a");
+        assert!(EditorConfigRules::default().is_empty());
+    }
+
+    #[test]
+    fn test_editorconfig_rules_for_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".editorconfig"),
+            "root = true\n\n[*]\nindent_style = space\nindent_size = 2\n\n[*.ts]\nindent_size = 4\n",
+        )
+        .unwrap();
+        let nested = temp_dir.path().join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            nested.join(".editorconfig"),
+            "[*]\ninsert_final_newline = true\n",
+        )
+        .unwrap();
+
+        let rules = EditorConfigRules::for_file(&nested.join("api.ts"));
+        assert_eq!(rules.indent_style, Some(IndentStyle::Space));
+        assert_eq!(rules.indent_size, Some(4));
+        assert_eq!(rules.insert_final_newline, Some(true));
+
+        let rules = EditorConfigRules::for_file(&nested.join("Button.tsx"));
+        assert_eq!(rules.indent_size, Some(2));
+    }
+}
@@ -0,0 +1,160 @@
+//! Per-file settings in a YAML front matter block.
+//!
+//! A template file may start with a block between `---` lines that
+//! overrides how that one file is generated, keeping the setting next to
+//! the content instead of in the `.conf` `[files]` map:
+//!
+//! ```text
+//! ---
+//! filename: $FILE_NAME.test.tsx
+//! on_conflict: skip
+//! condition: var_with_tests
+//! ---
+//! import { render } from '@testing-library/react';
+//! ```
+//!
+//! - `filename` replaces the file's output name; `$FILE_NAME` and path
+//!   tokens resolve as they do in template filenames.
+//! - `on_conflict` is the file's `--on-conflict` policy.
+//! - `condition` uses the `[files]` syntax and takes precedence over the
+//!   file's `[files]` entry.
+//!
+//! The block is stripped before the file is rendered.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::io::Read;
+use std::path::Path;
+
+use super::conflict::ConflictPolicy;
+
+const DELIMITER: &str = "---";
+
+/// Settings from a template file's front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrontMatter {
+    /// Output filename, replacing the template file's own name
+    pub filename: Option<String>,
+    /// Handling of an existing output file, overriding `--on-conflict`
+    #[serde(default, deserialize_with = "conflict_policy")]
+    pub on_conflict: Option<ConflictPolicy>,
+    /// Generation condition, overriding the `[files]` entry
+    pub condition: Option<String>,
+}
+
+impl FrontMatter {
+    /// Split `content` into its front matter and the template body
+    ///
+    /// Content without a leading `---` line has no front matter and is
+    /// returned whole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::front_matter::FrontMatter;
+    ///
+    /// let (front, body) = FrontMatter::split("---\ncondition: var_with_tests\n---\nit('renders');\n")?;
+    /// assert_eq!(front.unwrap().condition.as_deref(), Some("var_with_tests"));
+    /// assert_eq!(body, "it('renders');\n");
+    ///
+    /// assert_eq!(FrontMatter::split("plain")?, (None, "plain"));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn split(content: &str) -> Result<(Option<Self>, &str)> {
+        let Some((yaml, body)) = split_block(content) else {
+            return Ok((None, content));
+        };
+        let front: Self = if yaml.trim().is_empty() {
+            Self::default()
+        } else {
+            serde_yaml::from_str(yaml).context("Invalid front matter")?
+        };
+        if let Some(filename) = &front.filename {
+            if filename.is_empty() || filename.contains(['/', '\\']) {
+                anyhow::bail!(
+                    "Invalid front matter: filename '{}' must be a file name without folders",
+                    filename
+                );
+            }
+        }
+        Ok((Some(front), body))
+    }
+
+    /// Read the front matter of the template file at `path`, if it has one
+    ///
+    /// Only files starting with `---` are read whole.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("Could not read file: {}", path.display()))?;
+        let mut start = [0u8; DELIMITER.len()];
+        if file.read_exact(&mut start).is_err() || start != *DELIMITER.as_bytes() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read file: {}", path.display()))?;
+        let (front, _) = Self::split(&content).with_context(|| path.display().to_string())?;
+        Ok(front)
+    }
+}
+
+/// `content` without its front matter block
+pub fn strip(content: &str) -> &str {
+    split_block(content).map_or(content, |(_, body)| body)
+}
+
+/// The YAML between the leading `---` lines and the body after them
+fn split_block(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix(DELIMITER)?;
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == DELIMITER {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn conflict_policy<'de, D>(deserializer: D) -> Result<Option<ConflictPolicy>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|policy| policy.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_matter_split() {
+        let content = "---\r\nfilename: $FILE_NAME.test.tsx\r\non_conflict: Skip\r\n---\r\nbody\n";
+        let (front, body) = FrontMatter::split(content).unwrap();
+        assert_eq!(
+            front.unwrap(),
+            FrontMatter {
+                filename: Some("$FILE_NAME.test.tsx".to_string()),
+                on_conflict: Some(ConflictPolicy::Skip),
+                condition: None,
+            }
+        );
+        assert_eq!(body, "body\n");
+
+        // A lone separator or an unclosed block is content
+        assert_eq!(strip("---\nno end"), "---\nno end");
+        assert_eq!(strip("--- title"), "--- title");
+        assert_eq!(strip("---\n---\nbody"), "body");
+
+        assert!(FrontMatter::split("---\non_conflict: ask\n---\n").is_err());
+        assert!(FrontMatter::split("---\nfilename: a/b.ts\n---\n").is_err());
+        assert!(FrontMatter::split("---\nfilenme: b.ts\n---\n").is_err());
+    }
+}
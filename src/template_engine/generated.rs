@@ -0,0 +1,217 @@
+//! Rendered output of a generation, before (or instead of) writing it.
+//!
+//! [`GeneratedFile`] is what `TemplateEngine::render_to_memory` returns to
+//! library users. [`RenderedFile`] is the engine's internal form, which keeps
+//! raw files as a source path so they can be copied without loading them.
+//! [`MatrixRender`] holds the in-memory renders of every variable combination.
+//! [`GenerationPlan`] describes a generation before anything is rendered.
+
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use super::conflict::ConflictPolicy;
+use super::keep::preserve_kept_regions;
+use super::matrix::Combination;
+use super::staging::Staging;
+use super::stamp::{stamp_content, Stamp};
+
+/// A generated file held in memory.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::GeneratedFile;
+/// use std::path::PathBuf;
+///
+/// let file = GeneratedFile {
+///     path: PathBuf::from("Button/Button.tsx"),
+///     contents: b"export const Button = () => null;".to_vec(),
+/// };
+/// assert_eq!(file.as_text(), Some("export const Button = () => null;"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    /// Path relative to the engine's output directory
+    pub path: PathBuf,
+    /// Final file contents (rendered text, or the raw bytes of binary/`[raw]` files)
+    pub contents: Vec<u8>,
+}
+
+impl GeneratedFile {
+    /// Contents as text, or `None` for binary files
+    pub fn as_text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.contents).ok()
+    }
+}
+
+/// What a generation would do, for the pre-flight summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationPlan {
+    /// Directory the files are written to (the item folder, if one is created)
+    pub output_path: PathBuf,
+    /// Variable values after merging the defaults, `--var` values and `[rules]`
+    pub variables: BTreeMap<String, String>,
+    /// Number of files the `[files]` conditions leave to generate
+    pub file_count: usize,
+}
+
+/// Code extensions that make a file a candidate for the main file
+const CODE_EXTENSIONS: [&str; 7] = ["tsx", "ts", "jsx", "js", "vue", "svelte", "astro"];
+
+/// The main file among `paths`: the first code file without secondary
+/// suffixes (`Button.tsx` over `Button.spec.tsx` or `index.ts`), else the
+/// first file
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::primary_file;
+/// use std::path::PathBuf;
+///
+/// let paths = ["Button/Button.module.scss", "Button/Button.spec.tsx", "Button/Button.tsx"]
+///     .map(PathBuf::from);
+/// assert_eq!(primary_file(&paths), Some(&paths[2]));
+/// ```
+pub fn primary_file(paths: &[PathBuf]) -> Option<&PathBuf> {
+    paths
+        .iter()
+        .find(|path| {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                return false;
+            };
+            match name.split_once('.') {
+                Some((stem, extension)) => {
+                    stem != "index" && !stem.is_empty() && CODE_EXTENSIONS.contains(&extension)
+                }
+                None => false,
+            }
+        })
+        .or_else(|| paths.first())
+}
+
+/// Renders of a template across its variable matrix
+pub struct MatrixRender {
+    /// Combinations in the whole matrix, including those not rendered
+    pub total: usize,
+    /// Each rendered combination with its files, or why it can't be generated
+    pub renders: Vec<(Combination, Result<Vec<GeneratedFile>>)>,
+}
+
+/// A rendered template file with its final output path, Unix mode and conflict policy
+pub enum RenderedFile {
+    /// Rendered and formatted text
+    Text {
+        path: PathBuf,
+        content: String,
+        mode: Option<u32>,
+        /// Conflict policy from the template's front matter
+        conflict: Option<ConflictPolicy>,
+    },
+    /// File copied verbatim from `source`
+    Raw {
+        path: PathBuf,
+        source: PathBuf,
+        mode: Option<u32>,
+    },
+}
+
+impl RenderedFile {
+    /// Final output path
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Text { path, .. } | Self::Raw { path, .. } => path,
+        }
+    }
+
+    /// Mode to give the written file, `None` for the default
+    pub fn mode(&self) -> Option<u32> {
+        match self {
+            Self::Text { mode, .. } | Self::Raw { mode, .. } => *mode,
+        }
+    }
+
+    /// Conflict policy overriding the run's, `None` to use the run's
+    pub fn conflict(&self) -> Option<ConflictPolicy> {
+        match self {
+            Self::Text { conflict, .. } => *conflict,
+            Self::Raw { .. } => None,
+        }
+    }
+
+    /// Rendered text with the kept regions of `existing` carried over, or
+    /// `None` when nothing changes
+    pub fn with_kept_regions(&self, existing: &[u8]) -> Option<String> {
+        let Self::Text { content, .. } = self else {
+            return None;
+        };
+        preserve_kept_regions(std::str::from_utf8(existing).ok()?, content)
+    }
+
+    /// The file with an ownership stamp, if it's text of a type that can hold one
+    pub fn stamped(self, stamp: &Stamp) -> Self {
+        match self {
+            Self::Text {
+                path,
+                content,
+                mode,
+                conflict,
+            } => {
+                let content = stamp_content(&path, &content, stamp).unwrap_or(content);
+                Self::Text {
+                    path,
+                    content,
+                    mode,
+                    conflict,
+                }
+            }
+            raw => raw,
+        }
+    }
+
+    /// Stage the file for writing, with the previous contents of its path
+    pub async fn stage(&self, staging: &mut Staging, previous: Option<Vec<u8>>) -> Result<()> {
+        match self {
+            // Line endings were applied while rendering
+            Self::Text {
+                path,
+                content,
+                mode,
+                ..
+            } => {
+                staging
+                    .stage(path, content.as_bytes(), previous, *mode)
+                    .await
+            }
+            Self::Raw { path, source, mode } => {
+                staging.stage_copy(path, source, previous, *mode).await
+            }
+        }
+    }
+
+    /// Final contents, reading raw files from their source
+    pub async fn contents(&self) -> Result<Cow<'_, [u8]>> {
+        match self {
+            Self::Text { content, .. } => Ok(Cow::Borrowed(content.as_bytes())),
+            Self::Raw { source, .. } => fs::read(source)
+                .await
+                .map(Cow::Owned)
+                .with_context(|| format!("Could not read file: {}", source.display())),
+        }
+    }
+
+    /// Load the final contents into memory
+    pub async fn into_contents(self) -> Result<(PathBuf, Vec<u8>)> {
+        match self {
+            Self::Text { path, content, .. } => Ok((path, content.into_bytes())),
+            Self::Raw { path, source, .. } => {
+                let contents = fs::read(&source)
+                    .await
+                    .with_context(|| format!("Could not read file: {}", source.display()))?;
+                Ok((path, contents))
+            }
+        }
+    }
+}
@@ -5,40 +5,297 @@
 
 use anyhow::{Context, Result};
 use colored::*;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use tokio::fs;
+use walkdir::{DirEntry, WalkDir};
 
-use super::config::TemplateConfig;
+use super::config::{version_at_least, Framework, TemplateConfig, WalkLimits};
+use super::ignore::{TemplateIgnore, TEMPLATE_IGNORE_FILE};
+use super::naming::{folder_name, interpolate_name_pattern, process_smart_names, CaseStyle};
+use super::path_vars::{resolve_path_value, PATH_TYPE};
+use super::rules;
+use super::stamp::content_hash;
+use crate::error::{CliError, TemplateCandidate};
+use crate::ui::{self, symbols, t};
+
+/// Separates a template pack from a template name (`acme:component`)
+pub const PACK_SEPARATOR: char = ':';
 
 /// Validate that template exists and return its directory
-pub fn validate_template_exists(templates_dir: &Path, template_type: &str) -> Result<PathBuf> {
+///
+/// `pack:template` is looked up in that pack of `packs` only. Other names
+/// are looked up in `templates_dir` first, then in every pack; a name that
+/// several packs provide is ambiguous.
+pub fn validate_template_exists(
+    templates_dir: &Path,
+    packs: &BTreeMap<String, PathBuf>,
+    template_type: &str,
+) -> Result<PathBuf> {
+    let not_found = || {
+        anyhow::Error::from(CliError::TemplateNotFound {
+            name: template_type.to_string(),
+            available: list_all_template_names(templates_dir, packs).unwrap_or_default(),
+        })
+    };
+
+    if let Some((pack, template)) = template_type.split_once(PACK_SEPARATOR) {
+        return packs
+            .get(pack)
+            .map(|dir| dir.join(template))
+            .filter(|dir| dir.is_dir())
+            .ok_or_else(not_found);
+    }
+
     let template_dir = templates_dir.join(template_type);
-    if !template_dir.exists() {
-        anyhow::bail!("Template directory not found: {}", template_dir.display());
+    if template_dir.exists() {
+        return Ok(template_dir);
+    }
+    let mut candidates: Vec<TemplateCandidate> = packs
+        .iter()
+        .map(|(pack, dir)| TemplateCandidate {
+            name: format!("{}{}{}", pack, PACK_SEPARATOR, template_type),
+            path: dir.join(template_type),
+            description: String::new(),
+        })
+        .filter(|candidate| candidate.path.is_dir())
+        .collect();
+    match candidates.len() {
+        0 => Err(not_found()),
+        1 => Ok(candidates.remove(0).path),
+        _ => Err(CliError::AmbiguousTemplate {
+            name: template_type.to_string(),
+            candidates,
+        }
+        .into()),
     }
-    Ok(template_dir)
 }
 
-/// Prepare output directory for generation
-pub async fn prepare_output_directory(
+/// Names of the templates in `templates_dir`, then of each pack's as
+/// `pack:template`
+pub fn list_all_template_names(
+    templates_dir: &Path,
+    packs: &BTreeMap<String, PathBuf>,
+) -> Result<Vec<String>> {
+    let mut templates = list_template_names(templates_dir)?;
+    for (pack, dir) in packs {
+        templates.extend(
+            list_template_names(dir)?
+                .into_iter()
+                .map(|template| format!("{}{}{}", pack, PACK_SEPARATOR, template)),
+        );
+    }
+    Ok(templates)
+}
+
+/// Walk a template directory, following symlinks
+///
+/// Entries matched by the template's `.templateignore` (and the ignore file
+/// itself) are skipped, along with everything inside ignored directories.
+/// Symlinked files render like the files they point to and symlinked
+/// directories are walked. Broken links are skipped with a warning instead
+/// of failing the template; link loops are an error naming the link.
+///
+/// Walking fails once it goes deeper than `limits.max_depth` or finds more
+/// than `limits.max_files` files, so a directory that isn't really a
+/// template fails fast.
+pub fn walk_template_dir(
+    template_dir: &Path,
+    limits: WalkLimits,
+) -> impl Iterator<Item = Result<DirEntry>> {
+    let ignore = std::fs::read_to_string(template_dir.join(TEMPLATE_IGNORE_FILE))
+        .map(|content| TemplateIgnore::parse(&content))
+        .unwrap_or_default();
+    let root = template_dir.to_path_buf();
+    let dir = root.clone();
+    let mut file_count = 0;
+
+    WalkDir::new(template_dir)
+        .follow_links(true)
+        // One level past the limit, so going deeper is reported
+        .max_depth(limits.max_depth.saturating_add(1))
+        .into_iter()
+        .filter_entry(move |entry| {
+            let Ok(relative_path) = entry.path().strip_prefix(&root) else {
+                return true;
+            };
+            let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+            entry.depth() == 0
+                || !(relative_path == TEMPLATE_IGNORE_FILE
+                    || ignore.is_ignored(&relative_path, entry.file_type().is_dir()))
+        })
+        .filter_map(move |entry| match entry {
+            Ok(entry) if entry.depth() > limits.max_depth => Some(Err(anyhow::anyhow!(
+                "Template {} is nested deeper than {} levels at {}; check that templates_dir \
+                 points at your templates, or raise max_template_depth in the config",
+                dir.display(),
+                limits.max_depth,
+                entry.path().display()
+            ))),
+            Ok(entry) if entry.file_type().is_file() => {
+                file_count += 1;
+                if file_count > limits.max_files {
+                    return Some(Err(anyhow::anyhow!(
+                        "Template {} has more than {} files; check that templates_dir \
+                         points at your templates, or raise max_template_files in the config",
+                        dir.display(),
+                        limits.max_files
+                    )));
+                }
+                Some(Ok(entry))
+            }
+            Ok(entry) => Some(Ok(entry)),
+            Err(e) if e.loop_ancestor().is_some() => Some(Err(anyhow::anyhow!(
+                "Symlink loop in template: {} points back to {}",
+                e.path().unwrap_or(Path::new("")).display(),
+                e.loop_ancestor().unwrap_or(Path::new("")).display()
+            ))),
+            Err(e) if e.path().is_some_and(Path::is_symlink) => {
+                let path = e.path().unwrap_or(Path::new(""));
+                ui::warning!(
+                    "Warning: Skipping broken symlink in template: {}",
+                    path.display()
+                );
+                None
+            }
+            Err(e) => Some(Err(e).context("Error walking template directory")),
+        })
+}
+
+/// Hash of every file in a template directory, paths included
+///
+/// Files are hashed in path order, so the hash only changes when the
+/// template's files or configuration do.
+pub fn template_hash(template_dir: &Path, limits: WalkLimits) -> Result<String> {
+    let mut files = Vec::new();
+    for entry in walk_template_dir(template_dir, limits) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let relative_path = entry
+                .path()
+                .strip_prefix(template_dir)
+                .context("Could not get relative path")?
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.push((relative_path, entry.into_path()));
+        }
+    }
+    files.sort();
+
+    let mut bytes = Vec::new();
+    for (relative_path, path) in files {
+        bytes.extend_from_slice(relative_path.as_bytes());
+        bytes.push(0);
+        bytes.extend(
+            std::fs::read(&path)
+                .with_context(|| format!("Could not read file: {}", path.display()))?,
+        );
+        bytes.push(0);
+    }
+    Ok(content_hash(&bytes))
+}
+
+/// Unix mode for the file generated from `template_file`
+///
+/// The `[permissions]` entry for `relative_path` wins; otherwise executable
+/// template files (scripts) keep their mode. `None` leaves the default.
+pub fn output_mode(
+    template_file: &Path,
+    relative_path: &str,
+    config: &TemplateConfig,
+) -> Option<u32> {
+    config
+        .permission_for(relative_path)
+        .or_else(|| executable_mode(template_file))
+}
+
+#[cfg(unix)]
+fn executable_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode() & 0o7777;
+    (mode & 0o111 != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+fn executable_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// Sorted names of the template directories in `templates_dir`, excluding hidden ones
+///
+/// Templates inside a framework namespace directory (one with a
+/// `.namespace` file) are listed with its prefix, e.g. `rn/component`.
+pub fn list_template_names(templates_dir: &Path) -> Result<Vec<String>> {
+    let mut templates = Vec::new();
+
+    if !templates_dir.exists() {
+        return Ok(templates);
+    }
+
+    for entry in std::fs::read_dir(templates_dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if let Some(name) = entry.file_name().to_str() {
+                if Framework::of_namespace(&entry.path()).is_some() {
+                    let namespaced = list_template_names(&entry.path())?;
+                    templates.extend(namespaced.iter().map(|t| format!("{}/{}", name, t)));
+                } else if !name.starts_with('.') {
+                    templates.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    templates.sort();
+    Ok(templates)
+}
+
+/// Resolve the base directory for generation from a template's `output_subdir`
+///
+/// The pattern is interpolated like architecture filename patterns and must
+/// stay inside `output_dir`; absolute paths and `..` segments are rejected.
+pub fn resolve_output_subdir(
     output_dir: &Path,
+    output_subdir: Option<&str>,
     name: &str,
-    create_folder: bool,
 ) -> Result<PathBuf> {
-    let output_path = if create_folder {
-        output_dir.join(name)
-    } else {
-        output_dir.to_path_buf()
+    let Some(pattern) = output_subdir else {
+        return Ok(output_dir.to_path_buf());
     };
 
-    fs::create_dir_all(&output_path).await.with_context(|| {
-        format!(
-            "Could not create output directory: {}",
-            output_path.display()
-        )
-    })?;
+    let subdir =
+        interpolate_name_pattern(pattern, name, &process_smart_names(name)).replace('\\', "/");
+    let subdir_path = Path::new(&subdir);
 
-    Ok(output_path)
+    if subdir_path.is_absolute()
+        || subdir_path
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        anyhow::bail!(
+            "output_subdir '{}' must be a relative path inside the output directory",
+            pattern
+        );
+    }
+
+    Ok(output_dir.join(subdir_path))
+}
+
+/// Compute the directory generated files go into, without creating it
+///
+/// When `create_folder` is set the folder is named after `name`, converted
+/// with `folder_case` if the template or global config defines one.
+pub fn output_directory(
+    output_dir: &Path,
+    name: &str,
+    create_folder: bool,
+    folder_case: Option<CaseStyle>,
+) -> PathBuf {
+    if create_folder {
+        output_dir.join(folder_name(name, folder_case))
+    } else {
+        output_dir.to_path_buf()
+    }
 }
 
 /// Merge CLI variables into template config and display them
@@ -50,20 +307,181 @@ pub fn merge_variables(
         config.variables.insert(key, value);
     }
 
-    println!(
-        "{} Using template config: environment={}",
-        "⚙️".bold(),
-        config.environment.blue()
+    ui::status!(
+        "{} {}",
+        symbols::SETTINGS.as_str().bold(),
+        t!(
+            "generate.template_config",
+            environment = config.environment.blue()
+        )
     );
 
     if !config.variables.is_empty() {
-        println!("{} Active variables:", "🔧".bold());
+        ui::status!(
+            "{} {}",
+            symbols::TOOL.as_str().bold(),
+            t!("generate.active_variables")
+        );
         for (key, value) in &config.variables {
-            println!("  {} = {}", key.cyan(), value.green());
+            ui::status!("  {} = {}", key.cyan(), value.green());
         }
     }
 }
 
+/// Check the template's `[rules]` against the merged variables
+///
+/// `explicit` lists the variables passed by the caller, which `implies`
+/// rules report instead of overriding.
+pub fn apply_variable_rules(config: &mut TemplateConfig, explicit: &[String]) -> Result<()> {
+    let explicit: Vec<&str> = explicit.iter().map(String::as_str).collect();
+    rules::apply_rules(&config.rules, &mut config.variables, &explicit)?;
+    Ok(())
+}
+
+/// Fail when the template needs a newer cli-frontend (`requires_cli` in `[metadata]`)
+pub fn ensure_cli_version(template_type: &str, config: &TemplateConfig) -> Result<()> {
+    let Some(required) = &config.metadata.requires_cli else {
+        return Ok(());
+    };
+    match version_at_least(env!("CARGO_PKG_VERSION"), required) {
+        Some(false) => Err(CliError::UnsupportedTemplate {
+            template: template_type.to_string(),
+            required: required.clone(),
+            current: env!("CARGO_PKG_VERSION").to_string(),
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Check `path` variables (`{var}_type=path`) and add their derived values
+/// to the template data as `{var}_path`
+///
+/// Empty values are skipped; [`ensure_required_variables`] reports them
+/// when the variable is required.
+pub fn resolve_path_variables(config: &mut TemplateConfig, output_path: &Path) -> Result<()> {
+    let mut names: Vec<String> = config
+        .options_metadata
+        .iter()
+        .filter(|(_, option)| option.var_type == PATH_TYPE)
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+
+    for name in names {
+        let Some(value) = config.variables.get(&name).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        let path = resolve_path_value(value, output_path).map_err(|message| {
+            CliError::InvalidVariable {
+                name: name.clone(),
+                message,
+            }
+        })?;
+        config
+            .data
+            .insert(format!("{}_path", name), serde_json::to_value(path)?);
+    }
+    Ok(())
+}
+
+/// Fail on the first required variable left without a value after merging
+pub fn ensure_required_variables(config: &TemplateConfig) -> Result<()> {
+    match config.missing_required_variables().first() {
+        Some(name) => Err(CliError::InvalidVariable {
+            name: name.to_string(),
+            message: format!("required but not set (pass --var {}=VALUE)", name),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Fail when the `[files]` conditions leave none of `files` to generate
+///
+/// The error lists why each file was left out and the `--var` values that
+/// would bring files back, instead of generating an empty folder.
+pub fn ensure_files_to_generate(
+    template_type: &str,
+    files: &[String],
+    config: &TemplateConfig,
+) -> Result<()> {
+    let mut filtered = Vec::new();
+    let mut suggestions: Vec<String> = Vec::new();
+
+    for file in files {
+        let Some(condition) = config.file_filters.get(file) else {
+            return Ok(());
+        };
+        if evaluate_file_condition(condition, &config.variables) {
+            return Ok(());
+        }
+
+        let (reason, suggestion) = explain_file_condition(condition, config);
+        filtered.push(format!("{} ({})", file, reason));
+        if let Some(suggestion) = suggestion.filter(|s| !suggestions.contains(s)) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    if filtered.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::NothingToGenerate {
+        template: template_type.to_string(),
+        filtered,
+        suggestions,
+    }
+    .into())
+}
+
+/// Why a `[files]` condition doesn't hold, and the `NAME=VALUE` that makes it hold
+fn explain_file_condition(condition: &str, config: &TemplateConfig) -> (String, Option<String>) {
+    let declared = |name: &str| {
+        config.variables.contains_key(name) || config.options_metadata.contains_key(name)
+    };
+    let current = |name: &str| match config.variables.get(name) {
+        Some(value) if !value.is_empty() => format!("{} is '{}'", name, value),
+        _ => format!("{} is not set", name),
+    };
+
+    let Some(var_part) = condition.trim().strip_prefix("var_") else {
+        return (format!("unknown condition '{}'", condition), None);
+    };
+    if declared(var_part) {
+        return (
+            format!("{}: {}", condition, current(var_part)),
+            Some(format!("{}=true", var_part)),
+        );
+    }
+
+    // Value comparisons split at the first underscore, like evaluate_file_condition
+    match var_part.split_once('_').filter(|(name, _)| declared(name)) {
+        Some((name, raw)) => {
+            let hyphenated = raw.replace('_', "-");
+            let value = config
+                .options_metadata
+                .get(name)
+                .and_then(|option| {
+                    option
+                        .possible_values
+                        .iter()
+                        .find(|value| **value == raw || **value == hyphenated)
+                })
+                .cloned()
+                .unwrap_or(hyphenated);
+            (
+                format!("{}: {}", condition, current(name)),
+                Some(format!("{}={}", name, value)),
+            )
+        }
+        None => (
+            format!("{}: '{}' is not a template variable", condition, var_part),
+            None,
+        ),
+    }
+}
+
 /// Check if a value is truthy
 pub fn is_truthy(value: &str) -> bool {
     matches!(value.to_lowercase().as_str(), "true" | "yes" | "1")
@@ -107,7 +525,7 @@ pub fn evaluate_file_condition(
             false
         }
         _ => {
-            eprintln!(
+            ui::warning!(
                 "Warning: Unknown file condition '{}', skipping file",
                 condition
             );
@@ -121,6 +539,104 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_validate_template_exists_resolves_packs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "templates/hook",
+            "acme/component",
+            "acme/hook",
+            "ui/component",
+            "ui/card",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let templates = root.join("templates");
+        let packs = BTreeMap::from([
+            ("acme".to_string(), root.join("acme")),
+            ("ui".to_string(), root.join("ui")),
+        ]);
+
+        let resolve = |name: &str| validate_template_exists(&templates, &packs, name);
+        assert_eq!(
+            resolve("acme:component").unwrap(),
+            root.join("acme/component")
+        );
+        assert_eq!(resolve("hook").unwrap(), templates.join("hook"));
+        assert_eq!(resolve("card").unwrap(), root.join("ui/card"));
+
+        let error = resolve("component").unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CliError>(),
+            Some(&CliError::AmbiguousTemplate {
+                name: "component".to_string(),
+                candidates: vec![
+                    TemplateCandidate {
+                        name: "acme:component".to_string(),
+                        path: root.join("acme/component"),
+                        description: String::new(),
+                    },
+                    TemplateCandidate {
+                        name: "ui:component".to_string(),
+                        path: root.join("ui/component"),
+                        description: String::new(),
+                    },
+                ],
+            })
+        );
+        let Some(CliError::TemplateNotFound { available, .. }) = resolve("acme:card")
+            .unwrap_err()
+            .downcast_ref::<CliError>()
+            .cloned()
+        else {
+            panic!("expected an unknown template");
+        };
+        assert_eq!(
+            available,
+            [
+                "hook",
+                "acme:component",
+                "acme:hook",
+                "ui:card",
+                "ui:component"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_template_dir_enforces_limits() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("component");
+        std::fs::create_dir_all(template_dir.join("a/b/c")).unwrap();
+        for file in ["one.ts", "two.ts", "a/b/c/deep.ts"] {
+            std::fs::write(template_dir.join(file), "").unwrap();
+        }
+        let walk = |max_depth, max_files| {
+            walk_template_dir(
+                &template_dir,
+                WalkLimits {
+                    max_depth,
+                    max_files,
+                },
+            )
+            .collect::<Result<Vec<_>>>()
+        };
+
+        assert_eq!(walk(4, 3).unwrap().len(), 7);
+        let error = walk(3, 3).unwrap_err().to_string();
+        assert!(error.contains("nested deeper than 3 levels"), "{}", error);
+        let error = walk(4, 2).unwrap_err().to_string();
+        assert!(error.contains("more than 2 files"), "{}", error);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&template_dir, template_dir.join("a/loop")).unwrap();
+            let error = walk(8, 100).unwrap_err().to_string();
+            assert!(error.contains("Symlink loop in template"), "{}", error);
+        }
+    }
+
     #[test]
     fn test_is_truthy() {
         assert!(is_truthy("true"));
@@ -133,6 +649,60 @@ mod tests {
         assert!(!is_truthy("0"));
     }
 
+    #[test]
+    fn test_ensure_files_to_generate_suggests_variables() {
+        use crate::template_engine::config::VariableOption;
+
+        let mut config = TemplateConfig::default();
+        config
+            .variables
+            .insert("with_tests".to_string(), "false".to_string());
+        config
+            .variables
+            .insert("style".to_string(), "css".to_string());
+        config.options_metadata.insert(
+            "style".to_string(),
+            VariableOption {
+                possible_values: vec!["css".to_string(), "styled-components".to_string()],
+                ..Default::default()
+            },
+        );
+        for (file, condition) in [
+            ("Button.spec.tsx", "var_with_tests"),
+            ("Button.styled.ts", "var_style_styled_components"),
+            ("Button.md", "var_with_docs"),
+        ] {
+            config
+                .file_filters
+                .insert(file.to_string(), condition.to_string());
+        }
+        let files = ["Button.spec.tsx", "Button.styled.ts", "Button.md"].map(String::from);
+
+        let error = ensure_files_to_generate("component", &files, &config).unwrap_err();
+        let Some(CliError::NothingToGenerate {
+            filtered,
+            suggestions,
+            ..
+        }) = error.downcast_ref::<CliError>()
+        else {
+            panic!("expected NothingToGenerate, got {}", error);
+        };
+        assert_eq!(
+            filtered[0],
+            "Button.spec.tsx (var_with_tests: with_tests is 'false')"
+        );
+        assert_eq!(suggestions, &["with_tests=true", "style=styled-components"]);
+
+        // A file without a condition, or a condition that holds, is enough
+        let unfiltered = ["Button.tsx".to_string()];
+        assert!(ensure_files_to_generate("component", &unfiltered, &config).is_ok());
+        config
+            .variables
+            .insert("with_tests".to_string(), "true".to_string());
+        assert!(ensure_files_to_generate("component", &files, &config).is_ok());
+        assert!(ensure_files_to_generate("component", &[], &config).is_ok());
+    }
+
     #[test]
     fn test_evaluate_file_condition_always() {
         let variables = HashMap::new();
@@ -150,6 +720,33 @@ mod tests {
         assert!(!evaluate_file_condition("var_with_tests", &variables));
     }
 
+    #[test]
+    fn test_output_directory_folder_case() {
+        let output_dir = Path::new("src");
+
+        let path = output_directory(output_dir, "UserProfile", true, Some(CaseStyle::Kebab));
+        assert_eq!(path, output_dir.join("user-profile"));
+
+        let path = output_directory(output_dir, "UserProfile", false, None);
+        assert_eq!(path, output_dir);
+    }
+
+    #[test]
+    fn test_resolve_output_subdir() {
+        let base = Path::new("src");
+
+        assert_eq!(
+            resolve_output_subdir(base, None, "UserProfile").unwrap(),
+            base
+        );
+        assert_eq!(
+            resolve_output_subdir(base, Some("{kebab_name}/components"), "UserProfile").unwrap(),
+            base.join("user-profile/components")
+        );
+        assert!(resolve_output_subdir(base, Some("../outside"), "UserProfile").is_err());
+        assert!(resolve_output_subdir(base, Some("/abs/{name}"), "UserProfile").is_err());
+    }
+
     #[test]
     fn test_evaluate_file_condition_value_comparison() {
         let mut variables = HashMap::new();
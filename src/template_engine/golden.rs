@@ -0,0 +1,127 @@
+//! Golden-output comparison (`verify-golden`).
+//!
+//! A golden directory is a committed render of a template, laid out like
+//! `cli-frontend matrix` output with one numbered folder per variable
+//! combination. Comparing a fresh render against it catches template
+//! changes that alter generated code, including files that appear or
+//! disappear, without writing Rust tests for the template pack.
+
+use std::collections::BTreeMap;
+
+use super::diff::unified_diff;
+
+/// A difference between a fresh render and the golden directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// Rendered now but missing from the golden directory
+    Added(String),
+    /// In the golden directory but no longer rendered
+    Removed(String),
+    /// Rendered with different contents; `diff` is empty for binary files
+    Changed { path: String, diff: String },
+}
+
+impl Drift {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added(path) | Self::Removed(path) | Self::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// Differences between `rendered` and `golden` files, both keyed by
+/// `/`-separated path, in path order
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::golden::{compare_golden, Drift};
+/// use std::collections::BTreeMap;
+///
+/// let golden = BTreeMap::from([
+///     ("01-defaults/Button.tsx".to_string(), b"export {};\n".to_vec()),
+///     ("01-defaults/Button.css".to_string(), b".button {}\n".to_vec()),
+/// ]);
+/// let rendered = BTreeMap::from([
+///     ("01-defaults/Button.tsx".to_string(), b"export {};\n".to_vec()),
+/// ]);
+///
+/// assert_eq!(
+///     compare_golden(&rendered, &golden),
+///     [Drift::Removed("01-defaults/Button.css".to_string())]
+/// );
+/// ```
+pub fn compare_golden(
+    rendered: &BTreeMap<String, Vec<u8>>,
+    golden: &BTreeMap<String, Vec<u8>>,
+) -> Vec<Drift> {
+    let mut drift = Vec::new();
+
+    for (path, contents) in rendered {
+        match golden.get(path) {
+            None => drift.push(Drift::Added(path.clone())),
+            Some(expected) if expected == contents => {}
+            Some(expected) => {
+                let diff = match (std::str::from_utf8(expected), std::str::from_utf8(contents)) {
+                    (Ok(expected), Ok(contents)) => unified_diff(
+                        expected,
+                        contents,
+                        &format!("golden/{}", path),
+                        &format!("rendered/{}", path),
+                    ),
+                    _ => String::new(),
+                };
+                drift.push(Drift::Changed {
+                    path: path.clone(),
+                    diff,
+                });
+            }
+        }
+    }
+    drift.extend(
+        golden
+            .keys()
+            .filter(|path| !rendered.contains_key(*path))
+            .map(|path| Drift::Removed(path.clone())),
+    );
+
+    drift.sort_by(|a, b| a.path().cmp(b.path()));
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_golden_reports_changes_with_diff() {
+        let golden = BTreeMap::from([
+            ("a/Button.tsx".to_string(), b"one\ntwo\n".to_vec()),
+            ("a/logo.png".to_string(), vec![0xff, 0x00]),
+            ("b/Button.tsx".to_string(), b"same\n".to_vec()),
+        ]);
+        let rendered = BTreeMap::from([
+            ("a/Button.tsx".to_string(), b"one\n2\n".to_vec()),
+            ("a/logo.png".to_string(), vec![0xff, 0x01]),
+            ("b/Button.tsx".to_string(), b"same\n".to_vec()),
+            ("b/Button.spec.tsx".to_string(), b"test\n".to_vec()),
+        ]);
+
+        let drift = compare_golden(&rendered, &golden);
+        let paths: Vec<&str> = drift.iter().map(Drift::path).collect();
+        assert_eq!(paths, ["a/Button.tsx", "a/logo.png", "b/Button.spec.tsx"]);
+
+        let Drift::Changed { diff, .. } = &drift[0] else {
+            panic!("expected a change");
+        };
+        assert!(diff.contains("-two\n+2"));
+        assert_eq!(
+            drift[1],
+            Drift::Changed {
+                path: "a/logo.png".to_string(),
+                diff: String::new()
+            }
+        );
+        assert_eq!(drift[2], Drift::Added("b/Button.spec.tsx".to_string()));
+    }
+}
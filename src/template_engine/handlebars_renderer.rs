@@ -45,16 +45,9 @@ impl HandlebarsRenderer {
         let mut handlebars = Handlebars::new();
 
         // Register all custom helpers
-        handlebars.register_helper("pascal_case", Box::new(pascal_case_helper));
-        handlebars.register_helper("snake_case", Box::new(snake_case_helper));
-        handlebars.register_helper("kebab_case", Box::new(kebab_case_helper));
-        handlebars.register_helper("camel_case", Box::new(camel_case_helper));
-        handlebars.register_helper("upper_case", Box::new(upper_case_helper));
-        handlebars.register_helper("timestamp", Box::new(timestamp_helper));
-        handlebars.register_helper("uuid", Box::new(uuid_helper));
-        handlebars.register_helper("env", Box::new(env_helper));
-        handlebars.register_helper("eq", Box::new(eq_helper));
-        handlebars.register_helper("ne", Box::new(ne_helper));
+        for (name, helper) in cli_helpers() {
+            handlebars.register_helper(name, helper);
+        }
 
         Self { handlebars }
     }
@@ -71,6 +64,7 @@ impl HandlebarsRenderer {
     ///
     /// Returns a fresh Handlebars instance with all helpers registered.
     /// This is used by the legacy `create_handlebars()` function.
+    #[allow(dead_code)] // Public API
     pub fn create_handlebars_instance() -> Handlebars<'static> {
         Self::new().handlebars
     }
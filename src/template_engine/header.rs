@@ -0,0 +1,111 @@
+//! Banner comments prepended to generated files (`file_header` in the
+//! config file).
+//!
+//! The header is written once in the config instead of being copied into
+//! every template file. Each of its lines becomes a comment in the syntax of
+//! the generated file's type; files that can't hold a comment (JSON, unknown
+//! extensions) get no header.
+
+use std::path::Path;
+
+use super::stamp::comment_syntax;
+
+/// `content` with `header` as comment lines at the top, or `None` if the
+/// file type at `path` can't hold a comment
+///
+/// The header goes after a shebang line and follows the content's line
+/// endings. Content that already starts with the header is returned as is.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::header::prepend_header;
+/// use std::path::Path;
+///
+/// let header = "Copyright 2026 Acme\nSPDX-License-Identifier: MIT";
+/// let content = "export const Button = () => null;\n";
+/// assert_eq!(
+///     prepend_header(Path::new("Button.tsx"), content, header).unwrap(),
+///     "// Copyright 2026 Acme\n// SPDX-License-Identifier: MIT\nexport const Button = () => null;\n"
+/// );
+/// assert_eq!(
+///     prepend_header(Path::new("Button.css"), "", "Acme").unwrap(),
+///     "/* Acme */\n"
+/// );
+/// assert!(prepend_header(Path::new("package.json"), "{}", header).is_none());
+/// ```
+pub fn prepend_header(path: &Path, content: &str, header: &str) -> Option<String> {
+    let (open, close) = comment_syntax(path)?;
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut comment = comment_lines(header, open, close, line_ending);
+    if !comment.ends_with(line_ending) {
+        comment.push_str(line_ending);
+    }
+
+    let split = if content.starts_with("#!") {
+        content.find('\n').map_or(content.len(), |end| end + 1)
+    } else {
+        0
+    };
+    if content[split..].starts_with(&comment) {
+        return Some(content.to_string());
+    }
+    let mut output = String::with_capacity(content.len() + comment.len());
+    output.push_str(&content[..split]);
+    if split > 0 && !content[..split].ends_with('\n') {
+        output.push_str(line_ending);
+    }
+    output.push_str(&comment);
+    output.push_str(&content[split..]);
+    Some(output)
+}
+
+/// Each line of `text` between `open` and `close`, ending with
+/// `line_ending` where `text` ends with a newline
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::header::comment_lines;
+///
+/// assert_eq!(comment_lines("MIT\n\nAcme\n", "# ", "", "\n"), "# MIT\n#\n# Acme\n");
+/// assert_eq!(comment_lines("MIT", "/* ", " */", "\n"), "/* MIT */");
+/// ```
+pub fn comment_lines(text: &str, open: &str, close: &str, line_ending: &str) -> String {
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| match line.trim_end() {
+            "" => format!("{}{}", open.trim_end(), close),
+            line => format!("{}{}{}", open, line, close),
+        })
+        .collect();
+    let mut comment = lines.join(line_ending);
+    if text.ends_with('\n') {
+        comment.push_str(line_ending);
+    }
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_follows_shebang_and_is_not_repeated() {
+        let path = Path::new("bin/setup.sh");
+        let content = "#!/bin/sh\r\necho hi\r\n";
+        let with_header = prepend_header(path, content, "Acme\n\nInternal").unwrap();
+        assert_eq!(
+            with_header,
+            "#!/bin/sh\r\n# Acme\r\n#\r\n# Internal\r\necho hi\r\n"
+        );
+        assert_eq!(
+            prepend_header(path, &with_header, "Acme\n\nInternal").unwrap(),
+            with_header
+        );
+    }
+}
@@ -8,8 +8,10 @@
 //! - **Case transformations**: `pascal_case`, `snake_case`, `kebab_case`, `camel_case`, `upper_case`
 //! - **Timestamps**: `timestamp` with formats (ISO, date, time, datetime, unix)
 //! - **UUID**: `uuid` for generating unique identifiers
-//! - **Environment**: `env` for accessing environment variables
+//! - **Environment**: `env` for accessing environment variables, limited by an [`EnvPolicy`]
 //! - **Comparisons**: `eq` (equals), `ne` (not equals)
+//! - **Comments**: `comment`, a block helper commenting its content in the
+//!   generated file's syntax, and `spdx_header` for the license line
 //!
 //! # Example
 //!
@@ -30,13 +32,39 @@
 //! ```
 
 use chrono::{DateTime, Utc};
-use handlebars::{Handlebars, Helper, HelperResult, Output, RenderContext};
+use handlebars::{
+    Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError, Renderable,
+};
 use uuid::Uuid;
 
 use std::borrow::Cow;
 
+use super::header::comment_lines;
+use super::license::spdx_line;
 use super::naming::{to_camel_case, to_kebab_case, to_pascal_case, to_snake_case};
 
+/// Every helper the CLI adds to Handlebars, by name
+///
+/// Registered by [`crate::template_engine::HandlebarsRenderer::new`]; the
+/// renderer swaps some of them out per template (see
+/// [`crate::template_engine::renderer::create_handlebars_for`]).
+pub fn cli_helpers() -> Vec<(&'static str, Box<dyn HelperDef + Send + Sync>)> {
+    vec![
+        ("pascal_case", Box::new(pascal_case_helper)),
+        ("snake_case", Box::new(snake_case_helper)),
+        ("kebab_case", Box::new(kebab_case_helper)),
+        ("camel_case", Box::new(camel_case_helper)),
+        ("upper_case", Box::new(upper_case_helper)),
+        ("timestamp", Box::new(timestamp_helper)),
+        ("uuid", Box::new(uuid_helper)),
+        ("env", Box::new(env_helper)),
+        ("eq", Box::new(eq_helper)),
+        ("ne", Box::new(ne_helper)),
+        ("comment", Box::new(comment_helper)),
+        ("spdx_header", Box::new(spdx_header_helper)),
+    ]
+}
+
 /// Generic case transformation helper - DRY principle with Cow optimization
 fn case_transform_helper<F>(h: &Helper, out: &mut dyn Output, transform: F) -> HelperResult
 where
@@ -178,6 +206,20 @@ pub fn uuid_helper(
     Ok(())
 }
 
+/// Handlebars helper that renders nothing.
+///
+/// Stands in for `timestamp` and `uuid` when a template disables them with
+/// `enable_timestamps=false` or `enable_uuid=false`.
+pub fn disabled_helper(
+    _: &Helper,
+    _: &Handlebars,
+    _: &handlebars::Context,
+    _: &mut RenderContext,
+    _: &mut dyn Output,
+) -> HelperResult {
+    Ok(())
+}
+
 /// Handlebars helper for environment variable access.
 ///
 /// Reads an environment variable and returns its value.
@@ -205,6 +247,133 @@ pub fn env_helper(
     Ok(())
 }
 
+/// Which environment variables the `env` helper may read
+///
+/// Patterns are variable names with `*` wildcards, compared
+/// case-insensitively. A variable matching `deny` is refused even when it
+/// matches `allow`; an empty `allow` lets through anything not denied.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::helpers::EnvPolicy;
+///
+/// let policy = EnvPolicy {
+///     allow: vec!["NODE_ENV".to_string(), "PUBLIC_*".to_string()],
+///     deny: vec!["*_SECRET".to_string()],
+///     ..EnvPolicy::default()
+/// };
+/// assert!(policy.check("PUBLIC_API_URL").is_ok());
+/// assert!(policy.check("PUBLIC_SECRET").is_err());
+/// assert!(policy.check("AWS_ACCESS_KEY_ID").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvPolicy {
+    /// Refuse every variable (`--no-env-helpers`)
+    pub disabled: bool,
+    /// Variables templates may read (`env_allow` in the config file)
+    pub allow: Vec<String>,
+    /// Variables templates may never read (`env_deny` in the config file)
+    pub deny: Vec<String>,
+}
+
+impl EnvPolicy {
+    /// Whether templates can read every variable
+    pub fn is_unrestricted(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Refuse `name`, saying which setting forbids it
+    pub fn check(&self, name: &str) -> Result<(), EnvDenied> {
+        let upper = name.to_uppercase();
+        let matches =
+            |pattern: &String| super::ignore::matches_glob(&pattern.to_uppercase(), &upper);
+
+        let setting = if self.disabled {
+            EnvSetting::Disabled
+        } else if self.deny.iter().any(matches) {
+            EnvSetting::Deny
+        } else if !self.allow.is_empty() && !self.allow.iter().any(matches) {
+            EnvSetting::Allow
+        } else {
+            return Ok(());
+        };
+        Err(EnvDenied {
+            name: name.to_string(),
+            setting,
+        })
+    }
+
+    /// Value of `name`, empty when unset, if the policy allows reading it
+    pub fn read(&self, name: &str) -> Result<String, EnvDenied> {
+        self.check(name)?;
+        Ok(std::env::var(name).unwrap_or_default())
+    }
+}
+
+/// The [`EnvPolicy`] setting that refused a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvSetting {
+    /// `--no-env-helpers`
+    Disabled,
+    /// The variable matches `env_deny`
+    Deny,
+    /// The variable doesn't match `env_allow`
+    Allow,
+}
+
+/// A variable the `env` helper was not allowed to read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvDenied {
+    /// The variable the template asked for
+    pub name: String,
+    /// The setting that refused it
+    pub setting: EnvSetting,
+}
+
+impl std::fmt::Display for EnvDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.setting {
+            EnvSetting::Disabled => write!(
+                f,
+                "env \"{}\": env helpers are disabled (--no-env-helpers)",
+                self.name
+            ),
+            EnvSetting::Deny => write!(f, "env \"{}\": variable is listed in env_deny", self.name),
+            EnvSetting::Allow => write!(
+                f,
+                "env \"{}\": variable is not listed in env_allow",
+                self.name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EnvDenied {}
+
+/// [`env_helper`] restricted by `policy`; a disallowed variable fails the render
+pub fn restricted_env_helper(
+    policy: EnvPolicy,
+) -> impl Fn(
+    &Helper,
+    &Handlebars,
+    &handlebars::Context,
+    &mut RenderContext,
+    &mut dyn Output,
+) -> HelperResult
+       + Send
+       + Sync {
+    move |h, _, _, _, out| {
+        if let Some(var_name) = h.param(0).and_then(|param| param.value().as_str()) {
+            let value = policy
+                .read(var_name)
+                .map_err(|denied| RenderError::from_error(&denied.to_string(), denied))?;
+            out.write(&value)?;
+        }
+        Ok(())
+    }
+}
+
 /// Handlebars helper for equality comparison.
 ///
 /// Compares two values for equality. Useful for conditional rendering.
@@ -267,6 +436,86 @@ pub fn ne_helper(
     Ok(())
 }
 
+/// Handlebars block helper that comments out its content in the syntax of
+/// the file being generated (the `comment_prefix` and `comment_suffix` data)
+///
+/// Each line becomes its own comment. Files that can't hold a comment, such
+/// as JSON, get nothing, so shared partials like license headers work in
+/// any template.
+///
+/// # Template Usage
+///
+/// ```handlebars
+/// {{#comment}}
+/// Copyright {{year}} {{company}}
+/// {{/comment}}
+/// ```
+pub fn comment_helper<'reg, 'rc>(
+    h: &Helper<'reg, 'rc>,
+    r: &'reg Handlebars<'reg>,
+    ctx: &'rc handlebars::Context,
+    rc: &mut RenderContext<'reg, 'rc>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let (Some(template), Some((open, close))) = (h.template(), comment_delimiters(ctx.data()))
+    else {
+        return Ok(());
+    };
+
+    let content = template.renders(r, ctx, rc)?;
+    out.write(&comment_lines(&content, &open, &close, "\n"))?;
+    Ok(())
+}
+
+/// Handlebars helper for the SPDX identifier line of the configured
+/// `license`, or of the license given as its argument, as a comment in the
+/// syntax of the file being generated
+///
+/// Renders nothing without a license or in files that can't hold a comment.
+///
+/// # Template Usage
+///
+/// ```handlebars
+/// {{spdx_header}}
+/// {{spdx_header "Apache-2.0"}}
+/// ```
+pub fn spdx_header_helper(
+    h: &Helper,
+    _: &Handlebars,
+    ctx: &handlebars::Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let data = ctx.data();
+    let license = match h.param(0) {
+        Some(param) => param.value().as_str().unwrap_or_default(),
+        None => data["license"].as_str().unwrap_or_default(),
+    };
+    let Some((open, close)) = comment_delimiters(data) else {
+        return Ok(());
+    };
+    if !license.is_empty() {
+        out.write(&format!("{}{}{}", open, spdx_line(license), close))?;
+    }
+    Ok(())
+}
+
+/// Opening and closing comment delimiters from the template data, with the
+/// space around the comment's text, or `None` if the file has no comments
+pub fn comment_delimiters(data: &serde_json::Value) -> Option<(String, String)> {
+    let prefix = data["comment_prefix"].as_str().unwrap_or_default();
+    let suffix = data["comment_suffix"].as_str().unwrap_or_default();
+    if prefix.is_empty() {
+        return None;
+    }
+    let close = if suffix.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", suffix)
+    };
+    Some((format!("{} ", prefix), close))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,6 +684,34 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_restricted_env_helper() {
+        let mut handlebars = Handlebars::new();
+        let policy = EnvPolicy {
+            deny: vec!["*token*".to_string()],
+            ..EnvPolicy::default()
+        };
+        handlebars.register_helper("env", Box::new(restricted_env_helper(policy)));
+        std::env::set_var("TEST_RESTRICTED_VAR", "visible");
+
+        let result = handlebars
+            .render_template("{{env \"TEST_RESTRICTED_VAR\"}}", &serde_json::json!({}))
+            .unwrap();
+        assert_eq!(result, "visible");
+
+        let err = handlebars
+            .render_template("{{env \"GITHUB_TOKEN\"}}", &serde_json::json!({}))
+            .unwrap_err();
+        assert!(err.to_string().contains("env_deny"));
+
+        let disabled = EnvPolicy {
+            disabled: true,
+            ..EnvPolicy::default()
+        };
+        assert!(disabled.check("TEST_RESTRICTED_VAR").is_err());
+        std::env::remove_var("TEST_RESTRICTED_VAR");
+    }
+
     #[test]
     fn test_eq_helper_true() {
         let mut handlebars = Handlebars::new();
@@ -528,4 +805,50 @@ mod tests {
 
         assert_eq!(result, "HelloWorld and hello_world");
     }
+
+    #[test]
+    fn test_comment_helper_uses_file_syntax() {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("comment", Box::new(comment_helper));
+        let template = "{{#comment}}\nCopyright {{company}}\n\nMIT\n{{/comment}}\nbody";
+        let render = |prefix: &str, suffix: &str| {
+            handlebars
+                .render_template(
+                    template,
+                    &serde_json::json!({"company": "Acme", "comment_prefix": prefix, "comment_suffix": suffix}),
+                )
+                .unwrap()
+        };
+
+        assert_eq!(render("//", ""), "// Copyright Acme\n//\n// MIT\nbody");
+        assert_eq!(
+            render("<!--", "-->"),
+            "<!-- Copyright Acme -->\n<!-- -->\n<!-- MIT -->\nbody"
+        );
+        assert_eq!(render("", ""), "body");
+    }
+
+    #[test]
+    fn test_spdx_header_helper() {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("spdx_header", Box::new(spdx_header_helper));
+        let data =
+            serde_json::json!({"license": "MIT", "comment_prefix": "/*", "comment_suffix": "*/"});
+        let render = |template: &str, data: &serde_json::Value| {
+            handlebars.render_template(template, data).unwrap()
+        };
+
+        assert_eq!(
+            render("{{spdx_header}}", &data),
+            "/* SPDX-License-Identifier: MIT */"
+        );
+        assert_eq!(
+            render("{{spdx_header \"ISC\"}}", &data),
+            "/* SPDX-License-Identifier: ISC */"
+        );
+        assert_eq!(
+            render("{{spdx_header}}", &serde_json::json!({"license": "MIT"})),
+            ""
+        );
+    }
 }
@@ -0,0 +1,337 @@
+//! `.templateignore` rules.
+//!
+//! A template directory may contain a `.templateignore` file in gitignore
+//! syntax listing files that belong to the template's source but must
+//! never be generated: editor swap files, `__pycache__`, notes for template
+//! authors. Supported syntax:
+//!
+//! - blank lines and `#` comments are skipped; `\#` and `\!` escape them
+//! - `!pattern` re-includes files excluded by an earlier pattern
+//! - a trailing `/` matches directories only
+//! - a pattern containing another `/` is anchored to the template directory;
+//!   otherwise it matches a file or directory name at any depth
+//! - `*` and `?` match within one path segment, `**` across segments, and
+//!   `[a-z]` / `[!0-9]` match character classes
+//!
+//! As in git, the last matching pattern wins, and files inside an ignored
+//! directory stay ignored.
+//!
+//! [`GitIgnore`] applies the same rules to a project's `.gitignore` files,
+//! so the list of generated files can leave out build artifacts.
+
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file inside a template directory
+pub const TEMPLATE_IGNORE_FILE: &str = ".templateignore";
+
+/// Name of git's ignore file
+pub const GIT_IGNORE_FILE: &str = ".gitignore";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    glob: Vec<char>,
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+/// Parsed `.templateignore` patterns
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateIgnore {
+    patterns: Vec<Pattern>,
+}
+
+impl TemplateIgnore {
+    /// Parse the contents of a `.templateignore` file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::ignore::TemplateIgnore;
+    ///
+    /// let ignore = TemplateIgnore::parse("*.swp\n__pycache__/\n/docs/\n!docs/keep.md\n");
+    /// assert!(ignore.is_ignored(".Button.tsx.swp", false));
+    /// assert!(ignore.is_ignored("scripts/__pycache__", true));
+    /// assert!(ignore.is_ignored("docs", true));
+    /// assert!(!ignore.is_ignored("src/docs", true));
+    /// assert!(!ignore.is_ignored("$FILE_NAME.tsx", false));
+    /// ```
+    pub fn parse(content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let line = line.strip_prefix('/').unwrap_or(line);
+                if line.is_empty() {
+                    return None;
+                }
+
+                Some(Pattern {
+                    glob: line.chars().collect(),
+                    negated,
+                    dir_only,
+                    anchored,
+                })
+            })
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Whether a path (relative to the template directory, `/`-separated)
+    /// is ignored by the patterns themselves
+    ///
+    /// Callers walking the template skip ignored directories, so files
+    /// inside them are never checked.
+    pub fn is_ignored(&self, relative_path: &str, is_dir: bool) -> bool {
+        self.matched(relative_path, is_dir).unwrap_or(false)
+    }
+
+    /// Whether the last pattern matching the path ignores it, or `None`
+    /// when no pattern matches
+    fn matched(&self, relative_path: &str, is_dir: bool) -> Option<bool> {
+        let path: Vec<char> = relative_path.chars().collect();
+        let name_start = path
+            .iter()
+            .rposition(|&c| c == '/')
+            .map_or(0, |slash| slash + 1);
+
+        let mut ignored = None;
+        for pattern in &self.patterns {
+            if pattern.dir_only && !is_dir {
+                continue;
+            }
+            let target = if pattern.anchored {
+                &path[..]
+            } else {
+                &path[name_start..]
+            };
+            if glob_match(&pattern.glob, target) {
+                ignored = Some(!pattern.negated);
+            }
+        }
+        ignored
+    }
+}
+
+/// The `.gitignore` files that apply to a directory, from the root of its
+/// git repository down to the directory itself
+///
+/// Outside a git repository nothing is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct GitIgnore {
+    /// Each `.gitignore` with the directory it sits in, outermost first
+    files: Vec<(PathBuf, TemplateIgnore)>,
+}
+
+impl GitIgnore {
+    /// Read the `.gitignore` files that apply to `dir`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use cli_frontend::template_engine::ignore::GitIgnore;
+    /// use std::path::Path;
+    ///
+    /// let ignore = GitIgnore::load(Path::new("./src/components"));
+    /// if ignore.is_ignored(Path::new("./src/components/dist/Button.js"), false) {
+    ///     println!("build artifact");
+    /// }
+    /// ```
+    pub fn load(dir: &Path) -> Self {
+        let Ok(dir) = std::path::absolute(dir) else {
+            return Self::default();
+        };
+        let mut files = Vec::new();
+        for ancestor in dir.ancestors() {
+            if let Ok(content) = std::fs::read_to_string(ancestor.join(GIT_IGNORE_FILE)) {
+                files.push((ancestor.to_path_buf(), TemplateIgnore::parse(&content)));
+            }
+            if ancestor.join(".git").exists() {
+                files.reverse();
+                return Self { files };
+            }
+        }
+        Self::default()
+    }
+
+    /// Whether `path` is ignored, itself or through one of its directories
+    ///
+    /// Deeper `.gitignore` files override the ones above them.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.files.is_empty() {
+            return false;
+        }
+        let Ok(path) = std::path::absolute(path) else {
+            return false;
+        };
+        let mut checked: Vec<&Path> = path.ancestors().skip(1).collect();
+        checked.reverse();
+        let ignored = checked
+            .into_iter()
+            .map(|dir| (dir, true))
+            .chain([(path.as_path(), is_dir)])
+            .any(|(path, is_dir)| self.ignores(path, is_dir));
+        ignored
+    }
+
+    /// Whether `path` itself is ignored, the last matching pattern winning
+    fn ignores(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for (base, rules) in &self.files {
+            let Ok(relative) = path.strip_prefix(base) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if relative.is_empty() {
+                continue;
+            }
+            if let Some(matched) = rules.matched(&relative, is_dir) {
+                ignored = matched;
+            }
+        }
+        ignored
+    }
+}
+
+/// Whether the `/`-separated `path` matches a gitignore-style `pattern`
+///
+/// The whole path must match: `*` stays within a segment and `**` spans
+/// directories.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::ignore::matches_glob;
+///
+/// assert!(matches_glob("src/models/*.ts", "src/models/User.ts"));
+/// assert!(!matches_glob("src/*.ts", "src/models/User.ts"));
+/// assert!(matches_glob("src/**/*.ts", "src/models/User.ts"));
+/// ```
+pub fn matches_glob(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    glob_match(&pattern, &path)
+}
+
+/// Match `text` against a gitignore-style glob
+fn glob_match(glob: &[char], text: &[char]) -> bool {
+    match glob {
+        [] => text.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            // Zero or more whole directories
+            glob_match(rest, text)
+                || (0..text.len())
+                    .filter(|&i| text[i] == '/')
+                    .any(|i| glob_match(rest, &text[i + 1..]))
+        }
+        ['*', '*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        ['*', rest @ ..] => {
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if i < text.len() && text[i] == '/' {
+                    break;
+                }
+            }
+            false
+        }
+        ['?', rest @ ..] => {
+            matches!(text.first(), Some(&c) if c != '/') && glob_match(rest, &text[1..])
+        }
+        ['[', class @ ..] => match (text.first(), class_match(class, text.first().copied())) {
+            (Some(_), Some((true, rest))) => glob_match(rest, &text[1..]),
+            // An unterminated class is a literal `[`
+            (Some('['), None) => glob_match(class, &text[1..]),
+            _ => false,
+        },
+        ['\\', c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+/// Match a character class (after its `[`), returning whether `c` is in
+/// it and the glob after the closing `]`, or `None` if it isn't closed
+fn class_match(class: &[char], c: Option<char>) -> Option<(bool, &[char])> {
+    let (negated, body) = match class.first() {
+        Some('!' | '^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+    // A `]` right after the opening bracket is part of the class
+    let end = body.iter().skip(1).position(|&c| c == ']').map(|i| i + 1)?;
+    let (members, rest) = (&body[..end], &body[end + 1..]);
+
+    let c = c?;
+    let mut found = false;
+    let mut i = 0;
+    while i < members.len() {
+        if i + 2 < members.len() && members[i + 1] == '-' {
+            found |= (members[i]..=members[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            found |= members[i] == c;
+            i += 1;
+        }
+    }
+    Some((found != negated && c != '/', rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_ignore_patterns() {
+        let ignore = TemplateIgnore::parse(
+            "# editor files\n*.sw[op]\n*~\n\n**/notes/**\ndocs/*.md\n!docs/README.md\n\\#scratch\n",
+        );
+
+        assert!(ignore.is_ignored("$FILE_NAME.tsx.swp", false));
+        assert!(ignore.is_ignored("nested/.conf.swo", false));
+        assert!(!ignore.is_ignored("$FILE_NAME.swx", false));
+        assert!(ignore.is_ignored("index.ts~", false));
+        assert!(ignore.is_ignored("a/notes/todo.txt", false));
+        assert!(ignore.is_ignored("notes/todo.txt", false));
+        assert!(ignore.is_ignored("docs/usage.md", false));
+        assert!(!ignore.is_ignored("docs/README.md", false));
+        assert!(!ignore.is_ignored("docs/deep/usage.md", false));
+        assert!(!ignore.is_ignored("src/docs/usage.md", false));
+        assert!(ignore.is_ignored("#scratch", false));
+        assert!(TemplateIgnore::default().patterns.is_empty());
+    }
+
+    #[test]
+    fn test_git_ignore_applies_nested_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("src/Button")).unwrap();
+        std::fs::write(root.join(GIT_IGNORE_FILE), "dist/\n*.log\n").unwrap();
+        std::fs::write(root.join("src").join(GIT_IGNORE_FILE), "!keep.log\n").unwrap();
+
+        let ignore = GitIgnore::load(&root.join("src/Button"));
+        assert!(ignore.is_ignored(&root.join("src/Button/dist/Button.js"), false));
+        assert!(ignore.is_ignored(&root.join("src/Button/debug.log"), false));
+        assert!(!ignore.is_ignored(&root.join("src/Button/keep.log"), false));
+        assert!(!ignore.is_ignored(&root.join("src/Button/Button.tsx"), false));
+
+        // Outside a repository nothing is ignored
+        std::fs::remove_dir(root.join(".git")).unwrap();
+        let ignore = GitIgnore::load(&root.join("src/Button"));
+        assert!(!ignore.is_ignored(&root.join("src/Button/debug.log"), false));
+    }
+}
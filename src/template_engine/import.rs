@@ -0,0 +1,1733 @@
+//! Importing Plop, Hygen and Yeoman generators (`cli-frontend import`).
+//!
+//! Plop templates are Handlebars already, so only Plop's helper names are
+//! translated; Hygen's and Yeoman's EJS tags become Handlebars where they map
+//! onto names, variables and simple conditions. Prompts become `[options]` and output
+//! paths become `$FILE_NAME` filenames. Whatever can't be translated is kept
+//! in a `{{!-- TODO(import) --}}` comment and reported as an [`ImportTodo`].
+//!
+//! The conversion is purely textual: the `import` command reads the
+//! generator files and writes the templates.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use super::naming::CaseStyle;
+
+/// Generator format read by `cli-frontend import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    /// `plopfile.js` with Handlebars templates
+    Plop,
+    /// `_templates/<generator>/<action>/*.ejs.t` files
+    Hygen,
+    /// `generators/<generator>/index.js` with EJS files in `templates`
+    Yeoman,
+}
+
+impl FromStr for ImportSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plop" => Ok(Self::Plop),
+            "hygen" => Ok(Self::Hygen),
+            "yeoman" | "yo" => Ok(Self::Yeoman),
+            other => Err(format!(
+                "unknown generator format '{}', expected plop, hygen or yeoman",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Plop => "plop",
+            Self::Hygen => "hygen",
+            Self::Yeoman => "yeoman",
+        })
+    }
+}
+
+/// Something the import couldn't translate and left for a manual edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportTodo {
+    /// `file:line`, or the generator file the problem was found in
+    pub location: String,
+    pub message: String,
+}
+
+/// A generator prompt, imported as a template variable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedPrompt {
+    pub name: String,
+    /// The prompt's question, used as the wizard prompt
+    pub message: String,
+    pub default: String,
+    /// Choices of a `list` prompt
+    pub choices: Vec<String>,
+    /// A `confirm` prompt
+    pub boolean: bool,
+}
+
+/// A template converted from a Plop, Hygen or Yeoman generator
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedTemplate {
+    /// Template directory name
+    pub name: String,
+    pub description: String,
+    /// Files relative to the template directory with their Handlebars content
+    pub files: Vec<(String, String)>,
+    pub prompts: Vec<ImportedPrompt>,
+    /// Variables the files use that no prompt declares
+    pub extra_variables: BTreeSet<String>,
+    /// Whether the generator wrote into a folder named after the item
+    pub create_folder: bool,
+    pub filename_case: Option<CaseStyle>,
+    pub folder_case: Option<CaseStyle>,
+    pub todos: Vec<ImportTodo>,
+}
+
+impl ImportedTemplate {
+    /// The template's `.conf`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::import::{ImportedPrompt, ImportedTemplate};
+    ///
+    /// let template = ImportedTemplate {
+    ///     name: "component".to_string(),
+    ///     create_folder: true,
+    ///     prompts: vec![ImportedPrompt {
+    ///         name: "with_tests".to_string(),
+    ///         default: "true".to_string(),
+    ///         boolean: true,
+    ///         ..Default::default()
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// assert!(template.conf().contains("[options]\nwith_tests=true\nwith_tests_type=boolean\n"));
+    /// ```
+    pub fn conf(&self) -> String {
+        let mut conf = format!("# Imported from '{}' by cli-frontend import\n", self.name);
+        for todo in &self.todos {
+            conf.push_str(&format!("# TODO: {}: {}\n", todo.location, todo.message));
+        }
+        if !self.create_folder {
+            conf.push_str("create_folder=false\n");
+        }
+        if let Some(case) = self.filename_case {
+            conf.push_str(&format!("filename_case={}\n", case.as_str()));
+        }
+        if let Some(case) = self.folder_case {
+            conf.push_str(&format!("folder_case={}\n", case.as_str()));
+        }
+
+        conf.push_str(&format!("\n[metadata]\nname={}\n", self.name));
+        if !self.description.is_empty() {
+            conf.push_str(&format!("description={}\n", one_line(&self.description)));
+        }
+
+        if self.prompts.is_empty() && self.extra_variables.is_empty() {
+            return conf;
+        }
+        conf.push_str("\n[options]\n");
+        for prompt in &self.prompts {
+            conf.push_str(&format!("{}={}\n", prompt.name, one_line(&prompt.default)));
+            if prompt.boolean {
+                conf.push_str(&format!("{}_type=boolean\n", prompt.name));
+            }
+            if !prompt.choices.is_empty() {
+                conf.push_str(&format!(
+                    "{}_options={}\n",
+                    prompt.name,
+                    prompt.choices.join(",")
+                ));
+            }
+            if !prompt.message.is_empty() {
+                conf.push_str(&format!(
+                    "{}_prompt={}\n",
+                    prompt.name,
+                    one_line(&prompt.message)
+                ));
+            }
+        }
+        for variable in &self.extra_variables {
+            conf.push_str(&format!("{}=\n", variable));
+        }
+        conf
+    }
+}
+
+/// A `setGenerator` call from a plopfile
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlopGenerator {
+    pub name: String,
+    pub description: String,
+    pub prompts: Vec<ImportedPrompt>,
+    pub actions: Vec<PlopAction>,
+    /// Parts of the generator that couldn't be read
+    pub todos: Vec<ImportTodo>,
+}
+
+/// An action of a Plop generator
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlopAction {
+    /// `add`, `modify`, `append`, ...
+    pub kind: String,
+    pub path: String,
+    /// `templateFile`, relative to the plopfile
+    pub template_file: Option<String>,
+    /// Inline `template`
+    pub template: Option<String>,
+}
+
+/// Read the `setGenerator` calls of a plopfile
+///
+/// Only literal values are understood: generators whose prompts or actions
+/// are computed by functions are imported with a TODO.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::parse_plopfile;
+///
+/// let plopfile = r#"
+/// module.exports = function (plop) {
+///   plop.setGenerator('component', {
+///     description: 'React component',
+///     prompts: [{ type: 'input', name: 'name', message: 'Name?' }],
+///     actions: [{ type: 'add', path: 'src/{{pascalCase name}}.tsx', templateFile: 'plop/component.hbs' }],
+///   });
+/// };
+/// "#;
+/// let generators = parse_plopfile(plopfile);
+/// assert_eq!(generators[0].name, "component");
+/// assert_eq!(generators[0].actions[0].template_file.as_deref(), Some("plop/component.hbs"));
+/// ```
+pub fn parse_plopfile(source: &str) -> Vec<PlopGenerator> {
+    let mut generators = Vec::new();
+    let mut rest = source;
+
+    while let Some(index) = rest.find("setGenerator(") {
+        let call = &rest[index + "setGenerator(".len()..];
+        rest = call;
+
+        let Some(comma) = find_outside_strings(call, ',') else {
+            continue;
+        };
+        let Some(name) = string_literal(&call[..comma]) else {
+            continue;
+        };
+        let Some(open) = call[comma..].find('{').map(|i| i + comma) else {
+            continue;
+        };
+        let Some(close) = matching_close(call, open) else {
+            continue;
+        };
+        generators.push(parse_plop_generator(name, &call[open + 1..close]));
+        rest = &call[close..];
+    }
+
+    generators
+}
+
+fn parse_plop_generator(name: String, body: &str) -> PlopGenerator {
+    let mut generator = PlopGenerator {
+        name,
+        ..Default::default()
+    };
+    let location = format!("setGenerator('{}')", generator.name);
+
+    for (key, value) in properties(body) {
+        match key.as_str() {
+            "description" => generator.description = string_literal(value).unwrap_or_default(),
+            "prompts" => match array_items(value) {
+                Some(items) => generator.prompts = items.into_iter().filter_map(prompt).collect(),
+                None => generator.todos.push(ImportTodo {
+                    location: location.clone(),
+                    message: "prompts are computed by a function; add them to [options]"
+                        .to_string(),
+                }),
+            },
+            "actions" => match array_items(value) {
+                Some(items) => {
+                    for item in items {
+                        let action = plop_action(item);
+                        if action.path.is_empty() {
+                            generator.todos.push(ImportTodo {
+                                location: location.clone(),
+                                message: format!(
+                                    "skipped an action without a literal path: {}",
+                                    one_line(item)
+                                ),
+                            });
+                        } else {
+                            generator.actions.push(action);
+                        }
+                    }
+                }
+                None => generator.todos.push(ImportTodo {
+                    location: location.clone(),
+                    message: "actions are computed by a function; add the files by hand"
+                        .to_string(),
+                }),
+            },
+            _ => {}
+        }
+    }
+
+    generator
+}
+
+fn plop_action(object: &str) -> PlopAction {
+    let mut action = PlopAction {
+        kind: "add".to_string(),
+        ..Default::default()
+    };
+    let Some(body) = object_body(object) else {
+        return action;
+    };
+    for (key, value) in properties(body) {
+        match key.as_str() {
+            "type" => action.kind = string_literal(value).unwrap_or_default(),
+            "path" => action.path = string_literal(value).unwrap_or_default(),
+            "templateFile" => action.template_file = string_literal(value),
+            "template" => action.template = string_literal(value),
+            _ => {}
+        }
+    }
+    action
+}
+
+/// Read the prompts of a Hygen `prompt.js` (an array of inquirer questions)
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::parse_prompt_file;
+///
+/// let prompts = parse_prompt_file(
+///     "module.exports = [{ type: 'confirm', name: 'withTests', message: 'Tests?', default: true }]",
+/// );
+/// assert_eq!(prompts[0].name, "withTests");
+/// assert!(prompts[0].boolean);
+/// assert_eq!(prompts[0].default, "true");
+/// ```
+pub fn parse_prompt_file(source: &str) -> Vec<ImportedPrompt> {
+    let Some(open) = source.find('[') else {
+        return Vec::new();
+    };
+    let Some(close) = matching_close(source, open) else {
+        return Vec::new();
+    };
+    array_items(&source[open..=close])
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(prompt)
+        .collect()
+}
+
+/// An inquirer question object; the `name` prompt is the item name and is skipped
+fn prompt(object: &str) -> Option<ImportedPrompt> {
+    let mut prompt = ImportedPrompt::default();
+    let mut kind = String::from("input");
+
+    for (key, value) in properties(object_body(object)?) {
+        match key.as_str() {
+            "name" => prompt.name = string_literal(value)?,
+            "type" => kind = string_literal(value).unwrap_or(kind),
+            "message" => prompt.message = string_literal(value).unwrap_or_default(),
+            "default" => {
+                prompt.default = string_literal(value).unwrap_or_else(|| value.trim().to_string())
+            }
+            "choices" => {
+                prompt.choices = array_items(value)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(choice)
+                    .collect()
+            }
+            _ => {}
+        }
+    }
+
+    if prompt.name.is_empty() || prompt.name == "name" {
+        return None;
+    }
+    prompt.boolean = kind == "confirm";
+    if prompt.boolean && prompt.default.is_empty() {
+        prompt.default = "false".to_string();
+    }
+    if prompt.default.is_empty() {
+        prompt.default = prompt.choices.first().cloned().unwrap_or_default();
+    }
+    Some(prompt)
+}
+
+/// A list choice: a string, or an object with a `value` (or `name`)
+fn choice(item: &str) -> Option<String> {
+    if let Some(value) = string_literal(item) {
+        return Some(value);
+    }
+    let props = properties(object_body(item)?);
+    ["value", "name"].iter().find_map(|key| {
+        props
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| string_literal(v))
+    })
+}
+
+/// Convert a Plop generator, reading `templateFile`s with `read_template`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::{convert_plop_generator, PlopAction, PlopGenerator};
+///
+/// let generator = PlopGenerator {
+///     name: "component".to_string(),
+///     actions: vec![PlopAction {
+///         kind: "add".to_string(),
+///         path: "src/components/{{pascalCase name}}/{{pascalCase name}}.tsx".to_string(),
+///         template: Some("export const {{pascalCase name}} = '{{kebabCase name}}';".to_string()),
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+/// let template = convert_plop_generator(&generator, |_| None);
+/// assert_eq!(
+///     template.files,
+///     vec![("$FILE_NAME.tsx".to_string(), "export const {{pascal_case name}} = '{{kebab_case name}}';".to_string())]
+/// );
+/// ```
+pub fn convert_plop_generator(
+    generator: &PlopGenerator,
+    read_template: impl Fn(&str) -> Option<String>,
+) -> ImportedTemplate {
+    let mut template = ImportedTemplate {
+        name: generator.name.clone(),
+        description: generator.description.clone(),
+        prompts: generator.prompts.clone(),
+        todos: generator.todos.clone(),
+        ..Default::default()
+    };
+
+    let mut paths = Vec::new();
+    for action in &generator.actions {
+        if action.kind != "add" {
+            template.todos.push(ImportTodo {
+                location: action.path.clone(),
+                message: format!("'{}' actions aren't supported", action.kind),
+            });
+            continue;
+        }
+        let content = match (&action.template, &action.template_file) {
+            (Some(content), _) => content.clone(),
+            (None, Some(file)) => match read_template(file) {
+                Some(content) => content,
+                None => {
+                    template.todos.push(ImportTodo {
+                        location: file.clone(),
+                        message: "template file could not be read".to_string(),
+                    });
+                    continue;
+                }
+            },
+            (None, None) => String::new(),
+        };
+        paths.push((
+            parse_path(&action.path, plop_placeholder),
+            action.path.clone(),
+        ));
+
+        let source = action.template_file.as_deref().unwrap_or(&action.path);
+        let (content, todos) = convert_plop_template(&content, source);
+        template.files.push((String::new(), content));
+        template.todos.extend(todos);
+    }
+
+    layout_files(&mut template, paths);
+    template
+}
+
+/// Convert a Hygen action from its `*.t` files (path and content) and
+/// optional `prompt.js`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::convert_hygen_action;
+///
+/// let file = "---\nto: src/<%= h.changeCase.kebab(name) %>.ts\n---\n\
+///             export const <%= h.changeCase.camel(name) %> = 1;\n";
+/// let template = convert_hygen_action("util", &[("new/util.ejs.t".to_string(), file.to_string())], None);
+/// assert_eq!(template.filename_case.map(|c| c.as_str()), Some("kebab"));
+/// assert_eq!(
+///     template.files,
+///     vec![("$FILE_NAME.ts".to_string(), "export const {{camel_case name}} = 1;\n".to_string())]
+/// );
+/// ```
+pub fn convert_hygen_action(
+    name: &str,
+    files: &[(String, String)],
+    prompt_js: Option<&str>,
+) -> ImportedTemplate {
+    let mut template = ImportedTemplate {
+        name: name.to_string(),
+        prompts: prompt_js.map(parse_prompt_file).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let mut paths = Vec::new();
+    for (source, content) in files {
+        let Some(file) = split_frontmatter(content) else {
+            template.todos.push(ImportTodo {
+                location: source.clone(),
+                message: "no frontmatter; skipped".to_string(),
+            });
+            continue;
+        };
+
+        let mut to = None;
+        for (key, value) in file.fields {
+            match key.as_str() {
+                "to" => to = Some(value),
+                "inject" | "append" | "prepend" if value == "true" => {
+                    template.todos.push(ImportTodo {
+                        location: source.clone(),
+                        message: "injecting into existing files isn't supported; skipped"
+                            .to_string(),
+                    });
+                    to = None;
+                    break;
+                }
+                "force" | "unless_exists" => {}
+                _ => template.todos.push(ImportTodo {
+                    location: source.clone(),
+                    message: format!("frontmatter '{}: {}' was not imported", key, value),
+                }),
+            }
+        }
+        let Some(to) = to.filter(|to| !to.is_empty() && to != "null") else {
+            continue;
+        };
+
+        let (converted, variables, todos) = convert_ejs(file.body, source, file.body_line);
+        template.extra_variables.extend(variables);
+        template.todos.extend(todos);
+        paths.push((parse_path(&to, ejs_placeholder), to.clone()));
+        template.files.push((String::new(), converted));
+    }
+
+    for prompt in &template.prompts {
+        template.extra_variables.remove(&prompt.name);
+    }
+    layout_files(&mut template, paths);
+    template
+}
+
+/// Convert a Yeoman generator from its `index.js`, reading files of its
+/// `templates` folder with `read_template`
+///
+/// Prompts come from the `this.prompt([...])` call and files from the
+/// `this.fs.copyTpl` (EJS, translated) and `this.fs.copy` (copied as is)
+/// calls; other `this.fs` writes are reported.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::convert_yeoman_generator;
+///
+/// let index = r#"
+/// module.exports = class extends Generator {
+///   async prompting() {
+///     this.props = await this.prompt([{ type: 'confirm', name: 'withTests', message: 'Tests?' }]);
+///   }
+///   writing() {
+///     this.fs.copyTpl(
+///       this.templatePath('service.ts'),
+///       this.destinationPath(`src/${_.kebabCase(this.props.name)}.service.ts`),
+///       this.props
+///     );
+///   }
+/// };
+/// "#;
+/// let template = convert_yeoman_generator("service", index, |_| {
+///     Some("export class <%= name %>Service {}\n".to_string())
+/// });
+/// assert_eq!(template.prompts[0].name, "withTests");
+/// assert_eq!(template.filename_case.map(|c| c.as_str()), Some("kebab"));
+/// assert_eq!(
+///     template.files,
+///     vec![("$FILE_NAME.service.ts".to_string(), "export class {{name}}Service {}\n".to_string())]
+/// );
+/// ```
+pub fn convert_yeoman_generator(
+    name: &str,
+    index_js: &str,
+    read_template: impl Fn(&str) -> Option<String>,
+) -> ImportedTemplate {
+    let mut template = ImportedTemplate {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    let location = |index: usize| format!("{}/index.js:{}", name, line_at(index_js, index));
+
+    if let Some((index, _, arguments)) = method_calls(index_js, &["prompt"]).into_iter().next() {
+        let questions = split_top_level(arguments, ',')[0].trim();
+        let array = if questions.starts_with('[') {
+            Some(questions)
+        } else {
+            variable_name(questions).and_then(|variable| {
+                index_js
+                    .find(&format!("{} =", variable))
+                    .map(|start| &index_js[start..])
+            })
+        };
+        match array {
+            Some(array) => template.prompts = parse_prompt_file(array),
+            None => template.todos.push(ImportTodo {
+                location: location(index),
+                message: format!("prompts from '{}' were not imported", one_line(questions)),
+            }),
+        }
+    }
+
+    let mut paths = Vec::new();
+    for (index, method, arguments) in method_calls(
+        index_js,
+        &[
+            "copyTpl",
+            "copy",
+            "write",
+            "writeJSON",
+            "extendJSON",
+            "append",
+        ],
+    ) {
+        if !matches!(method, "copyTpl" | "copy") {
+            template.todos.push(ImportTodo {
+                location: location(index),
+                message: format!("'fs.{}' calls aren't supported", method),
+            });
+            continue;
+        }
+        let arguments = split_top_level(arguments, ',');
+        let (Some(from), Some(to)) = (arguments.first(), arguments.get(1)) else {
+            continue;
+        };
+        let file = js_path(from);
+        if file.contains("<%") || file.contains('*') {
+            template.todos.push(ImportTodo {
+                location: location(index),
+                message: format!("'{}' isn't a single template file; skipped", file),
+            });
+            continue;
+        }
+        let Some(content) = read_template(&file) else {
+            template.todos.push(ImportTodo {
+                location: format!("templates/{}", file),
+                message: "template file could not be read".to_string(),
+            });
+            continue;
+        };
+
+        if let Some(context) = arguments.get(2).and_then(|context| object_body(context)) {
+            for (key, value) in properties(context) {
+                if variable_name(value).as_deref() != Some(key.as_str()) {
+                    template.todos.push(ImportTodo {
+                        location: location(index),
+                        message: format!(
+                            "'{}' was computed as '{}'; it is imported as an option",
+                            key,
+                            one_line(value)
+                        ),
+                    });
+                }
+            }
+        }
+
+        let to = js_path(to);
+        paths.push((parse_path(&to, ejs_placeholder), to));
+        let source = format!("templates/{}", file);
+        let content = if method == "copyTpl" {
+            let (converted, variables, todos) = convert_ejs(&content, &source, 1);
+            template.extra_variables.extend(variables);
+            template.todos.extend(todos);
+            converted
+        } else {
+            escape_handlebars(&content)
+        };
+        template.files.push((String::new(), content));
+    }
+
+    for prompt in &template.prompts {
+        template.extra_variables.remove(&prompt.name);
+    }
+    layout_files(&mut template, paths);
+    template
+}
+
+/// Calls of `.method(…)` outside strings and comments, as the index of the
+/// call, the method and the text between the parentheses
+fn method_calls<'a>(
+    source: &'a str,
+    methods: &[&'static str],
+) -> Vec<(usize, &'static str, &'a str)> {
+    let mut calls = Vec::new();
+    let mut index = 0;
+    while index < source.len() {
+        if let Some(next) = skip_literal(source, index) {
+            index = next;
+            continue;
+        }
+        let rest = &source[index..];
+        let call = rest.strip_prefix('.').and_then(|rest| {
+            methods.iter().find(|method| {
+                rest.strip_prefix(**method)
+                    .is_some_and(|after| after.starts_with('('))
+            })
+        });
+        if let Some(method) = call {
+            let open = index + 1 + method.len();
+            if let Some(close) = matching_close(source, open) {
+                calls.push((index, *method, &source[open + 1..close]));
+                index = close + 1;
+                continue;
+            }
+        }
+        index += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    calls
+}
+
+/// A JS path expression as a path with `<%= … %>` placeholders
+///
+/// Understands string and template literals, `+` concatenation and the
+/// `templatePath` / `destinationPath` / `path.join` calls joining their
+/// arguments.
+fn js_path(expression: &str) -> String {
+    let expression = expression.trim();
+    let joined = [
+        "this.templatePath(",
+        "this.destinationPath(",
+        "path.join(",
+        "path.resolve(",
+    ]
+    .iter()
+    .find(|function| {
+        expression.starts_with(**function)
+            && matching_close(expression, function.len() - 1) == Some(expression.len() - 1)
+    });
+    if let Some(function) = joined {
+        return split_top_level(&expression[function.len()..expression.len() - 1], ',')
+            .into_iter()
+            .map(js_path)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("/");
+    }
+
+    split_top_level(expression, '+')
+        .into_iter()
+        .map(|part| {
+            let part = part.trim();
+            if let Some(text) = string_literal(part) {
+                return text;
+            }
+            match part
+                .strip_prefix('`')
+                .and_then(|part| part.strip_suffix('`'))
+            {
+                Some(inner) => template_literal(inner),
+                None => format!("<%= {} %>", part),
+            }
+        })
+        .collect()
+}
+
+/// The inside of a template literal with `${…}` as `<%= … %>`
+fn template_literal(inner: &str) -> String {
+    let mut output = String::new();
+    let mut rest = inner;
+    while let Some(start) = rest.find("${") {
+        let Some(close) = matching_close(rest, start + 1) else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        output.push_str(&format!("<%= {} %>", rest[start + 2..close].trim()));
+        rest = &rest[close + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The 1-based line of `index` in `source`
+fn line_at(source: &str, index: usize) -> usize {
+    source[..index].matches('\n').count() + 1
+}
+
+/// A Hygen template file split at its frontmatter
+struct HygenFile<'a> {
+    /// `key: value` lines between the `---` markers
+    fields: Vec<(String, String)>,
+    body: &'a str,
+    /// Line number of the body's first line
+    body_line: usize,
+}
+
+fn split_frontmatter(content: &str) -> Option<HygenFile<'_>> {
+    let rest = content.strip_prefix("---")?;
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))?;
+    let end = rest.find("\n---")?;
+    let header = &rest[..end];
+    let after = &rest[end + "\n---".len()..];
+    let body = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+
+    let fields = header
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some(HygenFile {
+        fields,
+        body,
+        body_line: header.lines().count() + 3,
+    })
+}
+
+/// A part of an output path
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathPart {
+    Text(String),
+    /// The item name, in a case (`None` as typed)
+    Name(Option<CaseStyle>),
+    /// Any other expression
+    Unknown(String),
+}
+
+/// Split a path template into `/`-separated segments of parts
+fn parse_path(
+    path: &str,
+    placeholder: fn(&str) -> Option<(usize, usize, PathPart)>,
+) -> Vec<Vec<PathPart>> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .map(|segment| {
+            let mut parts = Vec::new();
+            let mut rest = segment;
+            while let Some((start, end, part)) = placeholder(rest) {
+                if start > 0 {
+                    parts.push(PathPart::Text(rest[..start].to_string()));
+                }
+                parts.push(part);
+                rest = &rest[end..];
+            }
+            if !rest.is_empty() {
+                parts.push(PathPart::Text(rest.to_string()));
+            }
+            parts
+        })
+        .collect()
+}
+
+/// The next `{{...}}` in a Plop path
+fn plop_placeholder(text: &str) -> Option<(usize, usize, PathPart)> {
+    let start = text.find("{{")?;
+    let end = text[start..].find("}}")? + start + 2;
+    let words: Vec<&str> = text[start + 2..end - 2].split_whitespace().collect();
+    let part = match words.as_slice() {
+        ["name"] => PathPart::Name(None),
+        [helper, "name"] => match plop_case(helper) {
+            Some(case) => PathPart::Name(Some(case)),
+            None => PathPart::Unknown(text[start..end].to_string()),
+        },
+        _ => PathPart::Unknown(text[start..end].to_string()),
+    };
+    Some((start, end, part))
+}
+
+/// The next `<%= ... %>` in a Hygen path
+fn ejs_placeholder(text: &str) -> Option<(usize, usize, PathPart)> {
+    let start = text.find("<%")?;
+    let end = text[start..].find("%>")? + start + 2;
+    let expression = text[start + 2..end - 2]
+        .trim_start_matches(['=', '-'])
+        .trim_end_matches(['-', '_'])
+        .trim();
+    let part = match name_expression(expression) {
+        Some(case) => PathPart::Name(case),
+        None => PathPart::Unknown(text[start..end].to_string()),
+    };
+    Some((start, end, part))
+}
+
+/// Lay out converted files from their output paths
+///
+/// The first segment that is just the name becomes the item folder; files
+/// outside any such folder are generated flat. Directories before it are
+/// where the generator wrote to and are reported, since the output
+/// directory is chosen when generating.
+fn layout_files(template: &mut ImportedTemplate, paths: Vec<(Vec<Vec<PathPart>>, String)>) {
+    let mut prefixes = BTreeSet::new();
+    let mut file_cases = BTreeSet::new();
+    let mut folder_cases = BTreeSet::new();
+    let mut in_folder = 0;
+
+    for ((segments, original), (path, _)) in paths.iter().zip(template.files.iter_mut()) {
+        let folder = segments
+            .iter()
+            .position(|segment| matches!(segment.as_slice(), [PathPart::Name(_)]));
+        let (prefix, rest) = match folder {
+            Some(index) => {
+                in_folder += 1;
+                if let [PathPart::Name(case)] = segments[index].as_slice() {
+                    folder_cases.insert(case.map(|c| c.as_str()));
+                }
+                (&segments[..index], &segments[index + 1..])
+            }
+            None => segments.split_at(segments.len().saturating_sub(1)),
+        };
+
+        prefixes.insert(render_segments(
+            prefix,
+            &mut BTreeSet::new(),
+            &mut Vec::new(),
+        ));
+        let mut unknown = Vec::new();
+        *path = render_segments(rest, &mut file_cases, &mut unknown);
+        for expression in unknown {
+            template.todos.push(ImportTodo {
+                location: original.clone(),
+                message: format!("'{}' in the path was kept as written", expression),
+            });
+        }
+    }
+
+    template.create_folder = in_folder > 0;
+    if in_folder > 0 && in_folder < template.files.len() {
+        template.todos.push(ImportTodo {
+            location: template.name.clone(),
+            message:
+                "some files were written outside the item folder; they are generated inside it"
+                    .to_string(),
+        });
+    }
+
+    template.filename_case = single_case(&file_cases, template, "file names");
+    template.folder_case = single_case(&folder_cases, template, "folder names");
+    // PascalCase is the default for both
+    template.filename_case = template.filename_case.filter(|c| *c != CaseStyle::Pascal);
+    template.folder_case = template.folder_case.filter(|c| *c != CaseStyle::Pascal);
+
+    prefixes.remove("");
+    if !prefixes.is_empty() {
+        let prefixes: Vec<String> = prefixes.into_iter().collect();
+        template.todos.push(ImportTodo {
+            location: template.name.clone(),
+            message: format!(
+                "the generator wrote under {}; pass it as --output-dir",
+                prefixes.join(", ")
+            ),
+        });
+    }
+}
+
+/// The one case used for names, reporting mixed cases
+fn single_case(
+    cases: &BTreeSet<Option<&'static str>>,
+    template: &mut ImportedTemplate,
+    what: &str,
+) -> Option<CaseStyle> {
+    let named: Vec<&str> = cases.iter().flatten().copied().collect();
+    if named.len() > 1 {
+        template.todos.push(ImportTodo {
+            location: template.name.clone(),
+            message: format!(
+                "{} use several cases ({}); using {}",
+                what,
+                named.join(", "),
+                named[0]
+            ),
+        });
+    }
+    named.first().and_then(|case| CaseStyle::parse(case))
+}
+
+/// Join path segments, turning names into `$FILE_NAME`
+fn render_segments(
+    segments: &[Vec<PathPart>],
+    cases: &mut BTreeSet<Option<&'static str>>,
+    unknown: &mut Vec<String>,
+) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            segment
+                .iter()
+                .map(|part| match part {
+                    PathPart::Text(text) => text.clone(),
+                    PathPart::Name(case) => {
+                        cases.insert(case.map(|c| c.as_str()));
+                        "$FILE_NAME".to_string()
+                    }
+                    PathPart::Unknown(expression) => {
+                        unknown.push(expression.clone());
+                        expression.clone()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Plop case helper names and the case they produce
+fn plop_case(helper: &str) -> Option<CaseStyle> {
+    match helper {
+        "pascalCase" | "properCase" => Some(CaseStyle::Pascal),
+        "camelCase" => Some(CaseStyle::Camel),
+        "kebabCase" | "dashCase" => Some(CaseStyle::Kebab),
+        "snakeCase" => Some(CaseStyle::Snake),
+        _ => None,
+    }
+}
+
+/// Handlebars helpers templates can use after import
+const KNOWN_HELPERS: [&str; 15] = [
+    "if",
+    "unless",
+    "each",
+    "with",
+    "lookup",
+    "log",
+    "else",
+    "eq",
+    "ne",
+    "env",
+    "pascal_case",
+    "camel_case",
+    "kebab_case",
+    "snake_case",
+    "upper_case",
+];
+
+/// Translate Plop's helpers in a Handlebars template
+///
+/// Case helpers are renamed (`pascalCase` → `pascal_case`, `constantCase`
+/// → `upper_case (snake_case …)`); expressions using other Plop helpers or
+/// partials become TODO comments.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::convert_plop_template;
+///
+/// let (content, todos) = convert_plop_template(
+///     "{{#if withTests}}{{constantCase name}}{{/if}} {{titleCase name}}",
+///     "component.hbs",
+/// );
+/// assert_eq!(
+///     content,
+///     "{{#if withTests}}{{upper_case (snake_case name)}}{{/if}} {{!-- TODO(import): {{titleCase name}} --}}"
+/// );
+/// assert_eq!(todos[0].location, "component.hbs:1");
+/// ```
+pub fn convert_plop_template(content: &str, source: &str) -> (String, Vec<ImportTodo>) {
+    let mut output = String::with_capacity(content.len());
+    let mut todos = Vec::new();
+    let mut rest = content;
+    let mut consumed = 0;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let close = if after.starts_with("{{!--") {
+            "--}}"
+        } else {
+            "}}"
+        };
+        let Some(end) = after.find(close).map(|i| i + close.len()) else {
+            break;
+        };
+        let end = if after[end..].starts_with('}') && after.starts_with("{{{") {
+            end + 1
+        } else {
+            end
+        };
+        let mustache = &after[..end];
+
+        match convert_plop_mustache(mustache) {
+            Some(converted) => output.push_str(&converted),
+            None => {
+                let line = content[..consumed + start].matches('\n').count() + 1;
+                todos.push(ImportTodo {
+                    location: format!("{}:{}", source, line),
+                    message: format!("'{}' uses a helper or partial with no equivalent", mustache),
+                });
+                output.push_str(&format!("{{{{!-- TODO(import): {} --}}}}", mustache));
+            }
+        }
+        consumed += start + end;
+        rest = &after[end..];
+    }
+
+    output.push_str(rest);
+    (output, todos)
+}
+
+/// Rewrite the helpers of one `{{...}}`, or `None` when it can't be kept
+fn convert_plop_mustache(mustache: &str) -> Option<String> {
+    if mustache.starts_with("{{!") {
+        return Some(mustache.to_string());
+    }
+    if mustache
+        .trim_start_matches(['{', '~'])
+        .trim_start()
+        .starts_with('>')
+    {
+        return None;
+    }
+
+    let mut output = String::with_capacity(mustache.len());
+    let mut helper_position = true;
+    let mut chars = mustache.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c.is_alphabetic() || c == '_' {
+            let mut end = index + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek() {
+                if next.is_alphanumeric() || matches!(next, '_' | '.' | '-' | '@') {
+                    end = i + next.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let word = &mustache[index..end];
+
+            if !helper_position || !has_argument(&mustache[end..]) {
+                output.push_str(word);
+            } else if let Some(case) = plop_case(word) {
+                output.push_str(&format!("{}_case", case.as_str()));
+            } else if word == "upperCase" {
+                output.push_str("upper_case");
+            } else if word == "constantCase" {
+                let argument = mustache[end..].trim_start();
+                let length = argument
+                    .find(|c: char| c.is_whitespace() || c == ')' || c == '}')
+                    .unwrap_or(argument.len());
+                output.push_str(&format!("upper_case (snake_case {})", &argument[..length]));
+                let skip = mustache[end..].len() - argument.len() + length;
+                for _ in mustache[end..end + skip].chars() {
+                    chars.next();
+                }
+            } else if KNOWN_HELPERS.contains(&word) {
+                output.push_str(word);
+            } else {
+                return None;
+            }
+            helper_position = false;
+        } else {
+            if c == '(' {
+                helper_position = true;
+            } else if c == '"' || c == '\'' {
+                output.push(c);
+                for (_, next) in chars.by_ref() {
+                    output.push(next);
+                    if next == c {
+                        break;
+                    }
+                }
+                continue;
+            }
+            output.push(c);
+        }
+    }
+
+    Some(output)
+}
+
+/// Whether a helper-position word is followed by an argument (a bare word is a variable)
+fn has_argument(after: &str) -> bool {
+    let trimmed = after.trim_start();
+    trimmed.len() < after.len() && !trimmed.starts_with(['}', ')', '~'])
+}
+
+/// Translate an EJS template into Handlebars
+///
+/// Returns the content, the variables it reads besides the name, and TODOs
+/// for the tags that couldn't be translated. `first_line` is the line of
+/// `content` in `source`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::import::convert_ejs;
+///
+/// let (content, variables, todos) = convert_ejs(
+///     "<% if (withStyles) { -%>\nimport './<%= name %>.css';\n<% } -%>\n\
+///      const style = {{ margin: 0 }};\n<% items.forEach(i => { %>x<% }) %>\n",
+///     "new/component.ejs.t",
+///     4,
+/// );
+/// assert_eq!(
+///     content,
+///     "{{#if withStyles}}import './{{name}}.css';\n{{/if}}const style = \\{{ margin: 0 }};\n\
+///      {{!-- TODO(import): <% items.forEach(i => { %> --}}x{{!-- TODO(import): <% }) %> --}}\n"
+/// );
+/// assert!(variables.contains("withStyles"));
+/// assert_eq!(todos.len(), 2);
+/// assert_eq!(todos[0].location, "new/component.ejs.t:8");
+/// ```
+pub fn convert_ejs(
+    content: &str,
+    source: &str,
+    first_line: usize,
+) -> (String, BTreeSet<String>, Vec<ImportTodo>) {
+    let mut output = String::with_capacity(content.len());
+    let mut variables = BTreeSet::new();
+    let mut todos = Vec::new();
+    // Open blocks: Some(closing tag) for translated ones, None for TODO blocks
+    let mut blocks: Vec<Option<&str>> = Vec::new();
+    let mut rest = content;
+    let mut consumed = 0;
+
+    while let Some(start) = rest.find("<%") {
+        output.push_str(&escape_handlebars(&rest[..start]));
+        let after = &rest[start..];
+        let Some(end) = after.find("%>").map(|i| i + 2) else {
+            break;
+        };
+        let tag = &after[..end];
+        let line = first_line + content[..consumed + start].matches('\n').count();
+        let todo = |todos: &mut Vec<ImportTodo>| {
+            todos.push(ImportTodo {
+                location: format!("{}:{}", source, line),
+                message: format!("'{}' has no Handlebars equivalent", tag),
+            });
+            format!("{{{{!-- TODO(import): {} --}}}}", tag)
+        };
+
+        let trims_newline = tag.ends_with("-%>") || tag.ends_with("_%>");
+        let inner = tag[2..tag.len() - 2].trim_end_matches(['-', '_']);
+
+        let mut converted = if let Some(comment) = inner.strip_prefix('#') {
+            format!("{{{{!-- {} --}}}}", comment.trim())
+        } else if let Some(expression) = inner.strip_prefix(['=', '-']) {
+            match output_expression(expression.trim(), &mut variables) {
+                Some(expression) => format!("{{{{{}}}}}", expression),
+                None => todo(&mut todos),
+            }
+        } else {
+            let code = inner.trim_start_matches('_').trim();
+            match scriptlet(code, &mut blocks, &mut variables) {
+                Some(converted) => converted,
+                None => {
+                    if code.ends_with('{') {
+                        blocks.push(None);
+                    } else if code.starts_with('}') {
+                        blocks.pop();
+                    }
+                    todo(&mut todos)
+                }
+            }
+        };
+        // A literal `{` right before a tag would make it a `{{{` triple-stash:
+        // separate them with a space the tag's `~` strips again
+        if output.ends_with('{') {
+            output.push(' ');
+            if !converted.starts_with("{{!") && !converted.starts_with("{{else") {
+                converted.insert(2, '~');
+            }
+        }
+        output.push_str(&converted);
+
+        let mut end = end;
+        if trims_newline {
+            if after[end..].starts_with("\r\n") {
+                end += 2;
+            } else if after[end..].starts_with('\n') {
+                end += 1;
+            }
+        }
+        consumed += start + end;
+        rest = &after[end..];
+    }
+
+    output.push_str(&escape_handlebars(rest));
+    variables.remove("name");
+    (output, variables, todos)
+}
+
+/// Translate control flow: `if (…) {`, `} else if (…) {`, `} else {` and `}`
+fn scriptlet(
+    code: &str,
+    blocks: &mut Vec<Option<&'static str>>,
+    variables: &mut BTreeSet<String>,
+) -> Option<String> {
+    let compact: String = code.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if compact == "}" {
+        return match blocks.last() {
+            Some(Some(close)) => {
+                let close = *close;
+                blocks.pop();
+                Some(format!("{{{{/{}}}}}", close))
+            }
+            _ => None,
+        };
+    }
+    if compact == "} else {" {
+        return matches!(blocks.last(), Some(Some(_))).then(|| "{{else}}".to_string());
+    }
+    if let Some(condition) = compact
+        .strip_prefix("} else if (")
+        .and_then(|c| c.strip_suffix(") {"))
+    {
+        if blocks.last() != Some(&Some("if")) {
+            return None;
+        }
+        return match condition_expression(condition, variables)? {
+            (false, expression) => Some(format!("{{{{else if {}}}}}", expression)),
+            (true, _) => None,
+        };
+    }
+    if let Some(condition) = compact
+        .strip_prefix("if (")
+        .and_then(|c| c.strip_suffix(") {"))
+    {
+        let (negated, expression) = condition_expression(condition, variables)?;
+        let helper = if negated { "unless" } else { "if" };
+        blocks.push(Some(helper));
+        return Some(format!("{{{{#{} {}}}}}", helper, expression));
+    }
+    None
+}
+
+/// A condition as `(negated, expression)`: `x`, `!x`, `x === 'v'`, `x !== 'v'`
+fn condition_expression(
+    condition: &str,
+    variables: &mut BTreeSet<String>,
+) -> Option<(bool, String)> {
+    let condition = condition.trim();
+    for (operator, helper) in [("!==", "ne"), ("===", "eq"), ("!=", "ne"), ("==", "eq")] {
+        if let Some((left, right)) = condition.split_once(operator) {
+            let variable = variable_name(left.trim())?;
+            let value = string_literal(right.trim())?;
+            variables.insert(variable.clone());
+            return Some((false, format!("({} {} \"{}\")", helper, variable, value)));
+        }
+    }
+    if let Some(negated) = condition.strip_prefix('!') {
+        let variable = variable_name(negated.trim())?;
+        variables.insert(variable.clone());
+        return Some((true, variable));
+    }
+    let variable = variable_name(condition)?;
+    variables.insert(variable.clone());
+    Some((false, variable))
+}
+
+/// An output expression: a variable or a case conversion of one
+fn output_expression(expression: &str, variables: &mut BTreeSet<String>) -> Option<String> {
+    if let Some(case) = name_expression(expression) {
+        return Some(match case {
+            Some(case) => format!("{}_case name", case.as_str()),
+            None => "name".to_string(),
+        });
+    }
+    if let Some(variable) = expression
+        .strip_suffix(".toUpperCase()")
+        .and_then(variable_name)
+    {
+        variables.insert(variable.clone());
+        return Some(format!("upper_case {}", variable));
+    }
+    if let Some((helper, argument)) = change_case_call(expression) {
+        let variable = variable_name(argument)?;
+        variables.insert(variable.clone());
+        return Some(format!("{} {}", helper, variable));
+    }
+    let variable = variable_name(expression)?;
+    variables.insert(variable.clone());
+    Some(variable)
+}
+
+/// The case of a name expression: `name`, `Name`, `h.changeCase.pascal(name)`, ...
+fn name_expression(expression: &str) -> Option<Option<CaseStyle>> {
+    match expression.trim() {
+        // Hygen's capitalized name
+        "Name" => Some(Some(CaseStyle::Pascal)),
+        expression if variable_name(expression).as_deref() == Some("name") => Some(None),
+        expression => {
+            let (helper, argument) = change_case_call(expression)?;
+            if variable_name(argument).as_deref() != Some("name") {
+                return None;
+            }
+            CaseStyle::parse(helper.strip_suffix("_case")?).map(Some)
+        }
+    }
+}
+
+/// `h.changeCase.pascal(x)` / `h.changeCase.pascalCase(x)` / `_.kebabCase(x)`
+/// (lodash, in Yeoman generators) as our helper and `x`
+fn change_case_call(expression: &str) -> Option<(&'static str, &str)> {
+    let expression = expression.trim();
+    let call = expression
+        .strip_prefix("h.changeCase.")
+        .or_else(|| expression.strip_prefix("_."))?;
+    let (function, argument) = call.split_once('(')?;
+    let argument = argument.strip_suffix(')')?.trim();
+    let helper = match function.trim_end_matches("Case") {
+        "pascal" => "pascal_case",
+        "camel" => "camel_case",
+        "param" | "kebab" => "kebab_case",
+        "snake" => "snake_case",
+        "upper" => "upper_case",
+        _ => return None,
+    };
+    Some((helper, argument))
+}
+
+/// A plain variable (`locals.`, and Yeoman's `this.props.` / `this.options.`, stripped)
+fn variable_name(expression: &str) -> Option<String> {
+    let name = expression.trim();
+    let name = ["locals.", "this.props.", "props.", "this.options."]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    valid.then(|| name.to_string())
+}
+
+/// Escape `{{` in literal text so Handlebars prints it
+fn escape_handlebars(text: &str) -> String {
+    text.replace("{{", "\\{{")
+}
+
+/// Collapse a value onto one line for the `.conf`
+fn one_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The quoted string a JS value is, unescaped (template literals without `${}`)
+fn string_literal(value: &str) -> Option<String> {
+    let value = value.trim();
+    let quote = value
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let inner = value.strip_prefix(quote)?.strip_suffix(quote)?;
+    if quote == '`' && inner.contains("${") {
+        return None;
+    }
+
+    let mut output = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => output.push('\n'),
+                Some('t') => output.push('\t'),
+                Some(other) => output.push(other),
+                None => {}
+            }
+        } else if c == quote {
+            // Concatenations and other expressions aren't literals
+            return None;
+        } else {
+            output.push(c);
+        }
+    }
+    Some(output)
+}
+
+/// Index just past the string or comment starting at `index`, if one does
+fn skip_literal(source: &str, index: usize) -> Option<usize> {
+    let rest = &source[index..];
+    if rest.starts_with("//") {
+        return Some(rest.find('\n').map_or(source.len(), |i| index + i));
+    }
+    if rest.starts_with("/*") {
+        return Some(rest.find("*/").map_or(source.len(), |i| index + i + 2));
+    }
+    let quote = rest
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(index + i + 1);
+        }
+    }
+    Some(source.len())
+}
+
+/// Index of the bracket closing the one at `open`
+fn matching_close(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = open;
+    while index < source.len() {
+        if let Some(next) = skip_literal(source, index) {
+            index = next;
+            continue;
+        }
+        let c = source[index..].chars().next()?;
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    None
+}
+
+/// Index of the first `target` outside strings, comments and brackets
+fn find_outside_strings(source: &str, target: char) -> Option<usize> {
+    split_top_level(source, target)
+        .first()
+        .map(|part| part.len())
+        .filter(|&len| len < source.len())
+}
+
+/// Split `source` at `separator`s outside strings, comments and brackets
+fn split_top_level(source: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut index = 0;
+    while index < source.len() {
+        if let Some(next) = skip_literal(source, index) {
+            index = next;
+            continue;
+        }
+        let Some(c) = source[index..].chars().next() else {
+            break;
+        };
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&source[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    parts.push(&source[start..]);
+    parts
+}
+
+/// The inside of an object literal `{ … }`
+fn object_body(value: &str) -> Option<&str> {
+    value.trim().strip_prefix('{')?.strip_suffix('}')
+}
+
+/// The items of an array literal `[ … ]`, or `None` if `value` isn't one
+fn array_items(value: &str) -> Option<Vec<&str>> {
+    let inner = value.trim().strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        split_top_level(inner, ',')
+            .into_iter()
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+/// The `key: value` pairs of an object body (keys unquoted)
+fn properties(body: &str) -> Vec<(String, &str)> {
+    split_top_level(body, ',')
+        .into_iter()
+        .filter_map(|entry| {
+            let colon = find_outside_strings(entry, ':')?;
+            let key = entry[..colon].trim();
+            let key = string_literal(key).unwrap_or_else(|| key.to_string());
+            Some((key, entry[colon + 1..].trim()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plop_generator_with_prompts_and_folder() {
+        let plopfile = r#"
+            // Components
+            module.exports = (plop) => {
+              plop.setGenerator("component", {
+                description: "A React component, with tests",
+                prompts: [
+                  { type: "input", name: "name", message: "Component name?" },
+                  { type: "list", name: "style", message: "Styling?", choices: ["css", { name: "SCSS", value: "scss" }] },
+                  { type: "confirm", name: "withTests", message: "Add tests?", default: true },
+                ],
+                actions: [
+                  { type: "add", path: "src/components/{{kebabCase name}}/{{kebabCase name}}.tsx", templateFile: "plop/component.hbs" },
+                  { type: "add", path: "src/components/{{kebabCase name}}/index.ts", template: "export * from './{{kebabCase name}}';\n" },
+                  { type: "modify", path: "src/index.ts", pattern: /$/, template: "" },
+                ],
+              });
+              plop.setGenerator('hook', { actions: (data) => [] });
+            };
+        "#;
+
+        let generators = parse_plopfile(plopfile);
+        assert_eq!(generators.len(), 2);
+        assert_eq!(generators[1].todos.len(), 1);
+
+        let template = convert_plop_generator(&generators[0], |file| {
+            (file == "plop/component.hbs")
+                .then(|| "{{#if withTests}}// tested{{/if}}\n{{pkg 'name'}}\n".to_string())
+        });
+        let paths: Vec<&str> = template.files.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(paths, vec!["$FILE_NAME.tsx", "index.ts"]);
+        assert!(template.create_folder);
+        assert_eq!(template.filename_case, Some(CaseStyle::Kebab));
+        assert_eq!(template.folder_case, Some(CaseStyle::Kebab));
+
+        let messages: Vec<&str> = template.todos.iter().map(|t| t.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.contains("'modify' actions")));
+        assert!(messages.iter().any(|m| m.contains("{{pkg 'name'}}")));
+        assert!(messages.iter().any(|m| m.contains("src/components")));
+
+        let conf = template.conf();
+        assert!(conf.contains("filename_case=kebab\nfolder_case=kebab\n"));
+        assert!(conf.contains("description=A React component, with tests\n"));
+        assert!(conf.contains("style=css\nstyle_options=css,scss\nstyle_prompt=Styling?\n"));
+        assert!(conf.contains("withTests=true\nwithTests_type=boolean\n"));
+    }
+
+    #[test]
+    fn test_hygen_action_without_folder() {
+        let files = vec![
+            (
+                "new/hook.ejs.t".to_string(),
+                "---\nto: src/hooks/use<%= Name %>.ts\nunless_exists: true\n---\n\
+                 <% if (kind === 'query') { -%>\nimport { useQuery } from 'react-query';\n<% } else { -%>\n\
+                 import { useState } from 'react';\n<% } -%>\nexport function use<%= Name %>() {}\n"
+                    .to_string(),
+            ),
+            (
+                "new/inject.ejs.t".to_string(),
+                "---\nto: src/hooks/index.ts\ninject: true\n---\nexport * from './x';\n".to_string(),
+            ),
+        ];
+        let template = convert_hygen_action(
+            "hook",
+            &files,
+            Some("module.exports = [{ type: 'input', name: 'kind', default: 'state' }]"),
+        );
+
+        assert!(!template.create_folder);
+        assert_eq!(template.files.len(), 1);
+        assert_eq!(template.files[0].0, "use$FILE_NAME.ts");
+        assert_eq!(
+            template.files[0].1,
+            "{{#if (eq kind \"query\")}}import { useQuery } from 'react-query';\n{{else}}\
+             import { useState } from 'react';\n{{/if}}export function use{{pascal_case name}}() {}\n"
+        );
+        assert!(template.extra_variables.is_empty());
+        assert!(template.conf().contains("[options]\nkind=state\n"));
+        assert!(template
+            .todos
+            .iter()
+            .any(|t| t.message.contains("injecting")));
+    }
+
+    #[test]
+    fn test_yeoman_generator_with_prompt_variable_and_copies() {
+        let index = r#"
+            const Generator = require('yeoman-generator');
+            const _ = require('lodash');
+
+            const questions = [
+              { type: 'input', name: 'name', message: 'Page name?', default: this.appname },
+              { type: 'list', name: 'layout', message: 'Layout?', choices: ['full', 'centered'] },
+            ];
+
+            module.exports = class extends Generator {
+              prompting() {
+                return this.prompt(questions).then((props) => { this.props = props; });
+              }
+
+              writing() {
+                this.fs.copyTpl(
+                  this.templatePath('page', 'page.tsx'),
+                  this.destinationPath('src/pages', this.props.name, `${this.props.name}.tsx`),
+                  { name: this.props.name, title: _.startCase(this.props.name) }
+                );
+                this.fs.copy(this.templatePath('page.css'), this.destinationPath(`src/pages/${this.props.name}/styles.css`));
+                this.fs.copyTpl(this.templatePath('**/*.md'), this.destinationPath('docs'));
+                this.fs.extendJSON(this.destinationPath('package.json'), {});
+              }
+            };
+        "#;
+        let template = convert_yeoman_generator("page", index, |file| {
+            match file {
+            "page/page.tsx" => Some(
+                "<% if (layout === 'centered') { %>// centered\n<% } %>export const <%= name %> = '<%= title %>';\n"
+                    .to_string(),
+            ),
+            "page.css" => Some(".page { }\n".to_string()),
+            _ => None,
+        }
+        });
+
+        assert_eq!(template.prompts.len(), 1);
+        assert_eq!(template.prompts[0].choices, vec!["full", "centered"]);
+        assert!(template.create_folder);
+        let paths: Vec<&str> = template.files.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(paths, vec!["$FILE_NAME.tsx", "styles.css"]);
+        assert_eq!(
+            template.files[0].1,
+            "{{#if (eq layout \"centered\")}}// centered\n{{/if}}export const {{name}} = '{{title}}';\n"
+        );
+        assert_eq!(template.files[1].1, ".page { }\n");
+        assert_eq!(
+            template.extra_variables,
+            BTreeSet::from(["title".to_string()])
+        );
+
+        let messages: Vec<&str> = template.todos.iter().map(|t| t.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.contains("'title' was computed")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("'**/*.md' isn't a single")));
+        assert!(messages.iter().any(|m| m.contains("'fs.extendJSON'")));
+        assert!(template
+            .todos
+            .iter()
+            .any(|t| t.location == "page/index.js:23"));
+    }
+}
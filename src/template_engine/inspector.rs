@@ -5,18 +5,47 @@
 
 use colored::*;
 
-use super::config::{TemplateConfig, TemplateMetadata, VariableOption};
+use super::config::{RenderEngine, TemplateConfig, TemplateMetadata, VariableOption};
+use super::description::TemplateDescription;
+use super::matrix::TemplatePreview;
+use super::rules::VariableRule;
+use super::usage::TemplateUse;
+use crate::ui::{self, symbols, t};
 
 /// Print template header with name
-pub fn print_template_header(name: &str, metadata: &TemplateMetadata) {
-    println!("\n{} {}", "📋 Template:".bold(), name.cyan().bold());
-    println!("{}", "=".repeat(50).cyan());
-    println!();
+pub fn print_template_header(name: &str, metadata: &TemplateMetadata, engine: RenderEngine) {
+    ui::show!(
+        "\n{} {} {}",
+        symbols::LIST,
+        t!("label.template").bold(),
+        name.cyan().bold()
+    );
+    ui::show!("{}", "=".repeat(50).cyan());
+    ui::show!();
 
     if !metadata.description.is_empty() {
-        println!("{}", "Description:".bold());
-        println!("  {}", metadata.description);
-        println!();
+        ui::show!("{}", t!("label.description").bold());
+        ui::show!("  {}", metadata.description);
+        ui::show!();
+    }
+
+    if !metadata.tags.is_empty() {
+        ui::show!("{} {}", t!("inspect.tags").bold(), metadata.tags.join(", "));
+        ui::show!();
+    }
+
+    if let Some(version) = &metadata.requires_cli {
+        ui::show!(
+            "{} cli-frontend >= {}",
+            t!("inspect.requires").bold(),
+            version
+        );
+        ui::show!();
+    }
+
+    if engine != RenderEngine::Handlebars {
+        ui::show!("{} {}", t!("inspect.engine").bold(), engine.as_str());
+        ui::show!();
     }
 }
 
@@ -25,36 +54,62 @@ pub fn print_required_variables(
     options: &std::collections::HashMap<String, VariableOption>,
     variables: &std::collections::HashMap<String, String>,
 ) {
+    // Grouped variables first (by `{var}_group`), then the ungrouped ones
     let mut sorted_metadata: Vec<_> = Vec::with_capacity(options.len());
     sorted_metadata.extend(options.iter());
-    sorted_metadata.sort_by_key(|(name, _)| *name);
+    sorted_metadata.sort_by_key(|(name, metadata)| {
+        (metadata.group.is_empty(), metadata.group.as_str(), *name)
+    });
 
+    let mut current_group = "";
     for (var_name, metadata) in sorted_metadata {
+        if metadata.group != current_group {
+            current_group = &metadata.group;
+            let heading = if current_group.is_empty() {
+                t!("label.other_group")
+            } else {
+                current_group
+            };
+            ui::show!("  {}", heading.bold().underline());
+            ui::show!();
+        }
+
         let default_value = variables.get(var_name).map(|s| s.as_str()).unwrap_or("");
 
-        print!("  {} ", format!("--var {}=<value>", var_name).yellow());
+        let flag = format!("--var {}=<value>", var_name).yellow();
+        if metadata.required {
+            ui::show!("  {} {}", flag, t!("inspect.required").red());
+        } else {
+            ui::show!("  {}", flag);
+        }
 
         if !metadata.possible_values.is_empty() {
-            println!();
-            println!(
+            ui::show!(
                 "    {}: {}",
-                "Options".bold(),
+                t!("inspect.options").bold(),
                 metadata.possible_values.join(", ")
             );
         } else if metadata.var_type == "boolean" {
-            println!();
-            println!("    {}: boolean", "Type".bold());
+            ui::show!("    {}: boolean", t!("inspect.type").bold());
         }
 
         if !default_value.is_empty() {
-            println!("    {}: {}", "Default".bold(), default_value.green());
+            ui::show!(
+                "    {}: {}",
+                t!("inspect.default").bold(),
+                default_value.green()
+            );
         }
 
         if !metadata.description.is_empty() {
-            println!("    {}: {}", "Description".bold(), metadata.description);
+            ui::show!(
+                "    {}: {}",
+                t!("inspect.description").bold(),
+                metadata.description
+            );
         }
 
-        println!();
+        ui::show!();
     }
 }
 
@@ -73,15 +128,15 @@ pub fn print_optional_variables(
     simple_vars.sort_by_key(|(name, _)| *name);
 
     for (var_name, value) in simple_vars {
-        println!("  {} ", format!("--var {}=<string>", var_name).yellow());
-        println!("    {}: {}", "Default".bold(), value.green());
-        println!();
+        ui::show!("  {} ", format!("--var {}=<string>", var_name).yellow());
+        ui::show!("    {}: {}", t!("inspect.default").bold(), value.green());
+        ui::show!();
     }
 }
 
 /// Print file filters showing which files will be generated
 pub fn print_file_filters(filters: &std::collections::HashMap<String, String>) {
-    println!("{}", "Files Generated:".bold().cyan());
+    ui::show!("{}", t!("inspect.files_generated").bold().cyan());
 
     // Pre-allocate capacity for efficiency
     let capacity = filters.len() / 3 + 1; // Estimate equal distribution
@@ -100,45 +155,219 @@ pub fn print_file_filters(filters: &std::collections::HashMap<String, String>) {
 
     always_files.sort();
     for file in always_files {
-        println!("  {} {} {}", "✓".green(), file.bold(), "(always)".dimmed());
+        ui::show!(
+            "  {} {} {}",
+            symbols::CHECK.as_str().green(),
+            file.bold(),
+            t!("inspect.always").dimmed()
+        );
     }
 
     default_files.sort();
     for file in default_files {
-        println!(
+        ui::show!(
             "  {} {} {}",
-            "○".yellow(),
+            symbols::PENDING.as_str().yellow(),
             file.bold(),
-            "(default)".dimmed()
+            t!("inspect.by_default").dimmed()
         );
     }
 
     conditional_files.sort_by(|a, b| a.0.cmp(&b.0));
     for (file, condition) in conditional_files {
         let condition_display = format_condition(&condition);
-        println!(
+        ui::show!(
             "  {} {} {}",
-            "○".yellow(),
+            symbols::PENDING.as_str().yellow(),
             file.bold(),
             condition_display.dimmed()
         );
     }
 
-    println!();
+    ui::show!();
+}
+
+/// Print `[rules]` between variables
+pub fn print_rules(rules: &[VariableRule]) {
+    ui::show!("{}", t!("inspect.variable_rules").bold().cyan());
+    for rule in rules {
+        ui::show!("  {} {}", symbols::BULLET.as_str().yellow(), rule);
+    }
+    ui::show!();
+}
+
+/// Print the files and lines each variable combination generates
+pub fn print_preview(preview: &TemplatePreview) {
+    ui::show!(
+        "{} {}",
+        t!("inspect.preview").bold().cyan(),
+        t!("inspect.sample_name", name = preview.sample_name).dimmed()
+    );
+
+    let width = preview
+        .combinations
+        .iter()
+        .map(|c| c.combination.label().len())
+        .max()
+        .unwrap_or_default();
+    for combination in &preview.combinations {
+        let label = format!("{:width$}", combination.combination.label());
+        match &combination.error {
+            None => ui::show!(
+                "  {} {}  {}",
+                symbols::BULLET.as_str().yellow(),
+                label,
+                t!(
+                    "inspect.files_and_lines",
+                    files = combination.files,
+                    lines = combination.lines
+                )
+            ),
+            Some(error) => ui::show!(
+                "  {} {}  {}",
+                symbols::CROSS.as_str().red(),
+                label,
+                error.dimmed()
+            ),
+        }
+    }
+
+    if let Some(((min_files, max_files), (min_lines, max_lines))) = preview.range() {
+        ui::show!(
+            "  {} {}",
+            t!("inspect.range").bold(),
+            t!(
+                "inspect.files_and_lines",
+                files = format!("{}{}{}", min_files, symbols::DASH, max_files),
+                lines = format!("{}{}{}", min_lines, symbols::DASH, max_lines)
+            )
+        );
+    }
+    if preview.combinations.len() < preview.total {
+        ui::show!(
+            "  {}",
+            t!(
+                "inspect.showing_combinations",
+                shown = preview.combinations.len(),
+                total = preview.total
+            )
+            .dimmed()
+        );
+    }
+    ui::show!();
+}
+
+/// Print one condensed entry per template (`--describe-all`)
+pub fn print_template_summaries(descriptions: &[TemplateDescription]) {
+    for description in descriptions {
+        match description.title.as_str() {
+            "" => ui::show!("{}", description.name.bold().cyan()),
+            title => ui::show!(
+                "{} {}",
+                description.name.bold().cyan(),
+                format!("({})", title).dimmed()
+            ),
+        }
+        if !description.description.is_empty() {
+            ui::show!("  {}", description.description);
+        }
+        if !description.tags.is_empty() {
+            ui::show!(
+                "  {} {}",
+                t!("inspect.tags").bold(),
+                description.tags.join(", ")
+            );
+        }
+
+        if !description.variables.is_empty() {
+            let variables: Vec<String> = description
+                .variables
+                .iter()
+                .map(|(name, variable)| {
+                    let mut summary = match variable.default.as_deref() {
+                        Some(default) if !default.is_empty() => format!("{}={}", name, default),
+                        _ => name.clone(),
+                    };
+                    if variable.required {
+                        summary.push('*');
+                    }
+                    summary
+                })
+                .collect();
+            ui::show!(
+                "  {} {}",
+                t!("label.variables").bold(),
+                variables.join(", ")
+            );
+        }
+
+        let conditional = description
+            .file_filters
+            .values()
+            .filter(|condition| !matches!(condition.as_str(), "always" | "default"))
+            .count();
+        ui::show!(
+            "  {} {}",
+            t!("label.files").bold(),
+            t!(
+                "inspect.conditional_files",
+                count = description.files.len(),
+                conditional = conditional
+            )
+        );
+        ui::show!();
+    }
+
+    ui::show!(
+        "{}",
+        t!("inspect.template_count", count = descriptions.len()).dimmed()
+    );
 }
 
 /// Print usage examples for the template
+/// Print the commands of a template's recent uses, if any
+pub fn print_recent_uses(template_type: &str, uses: &[TemplateUse]) {
+    if uses.is_empty() {
+        return;
+    }
+    ui::show!("{}", t!("inspect.recently_used").bold().magenta());
+    ui::show!();
+    for used in uses {
+        ui::show!("  {}", used.command(template_type).cyan());
+    }
+    ui::show!();
+}
+
 pub fn print_usage_examples(template_type: &str, config: &TemplateConfig) {
-    println!("{}", "Usage Examples:".bold().magenta());
-    println!();
+    ui::show!("{}", t!("inspect.usage_examples").bold().magenta());
+    ui::show!();
+
+    // Examples written in the template's [examples] section replace the synthesized ones
+    if !config.examples.is_empty() {
+        for example in &config.examples {
+            ui::show!("  {} {}", "#".dimmed(), example.name);
+            if !example.command.is_empty() {
+                ui::show!("  {}", example.command.cyan());
+            }
+            if !example.files.is_empty() {
+                ui::show!(
+                    "    {}: {}",
+                    t!("inspect.files").bold(),
+                    example.files.join(", ").dimmed()
+                );
+            }
+            ui::show!();
+        }
+        return;
+    }
 
-    println!("  {} Basic (with defaults)", "#".dimmed());
-    println!(
+    ui::show!("  {} {}", "#".dimmed(), t!("inspect.example_basic"));
+    ui::show!(
         "  {} ComponentName --type {}",
         "cli-frontend".cyan(),
         template_type
     );
-    println!();
+    ui::show!();
 
     let mut example_count = 0;
     const MAX_EXAMPLES: usize = 3;
@@ -154,15 +383,22 @@ pub fn print_usage_examples(template_type: &str, config: &TemplateConfig) {
             } else {
                 "true"
             };
-            println!("  {} With {}={}", "#".dimmed(), var_name, value);
-            println!(
+            ui::show!(
+                "  {} {}",
+                "#".dimmed(),
+                t!(
+                    "inspect.example_with",
+                    assignment = format!("{}={}", var_name, value)
+                )
+            );
+            ui::show!(
                 "  {} ComponentName --type {} --var {}={}",
                 "cli-frontend".cyan(),
                 template_type,
                 var_name,
                 value
             );
-            println!();
+            ui::show!();
             example_count += 1;
         }
     }
@@ -179,27 +415,29 @@ pub fn print_usage_examples(template_type: &str, config: &TemplateConfig) {
                 .iter()
                 .find(|v| Some(v.as_str()) != current_value)
                 .unwrap_or(&metadata.possible_values[0]);
-            println!("  {} With {}={}", "#".dimmed(), var_name, example_value);
-            println!(
+            ui::show!(
+                "  {} {}",
+                "#".dimmed(),
+                t!(
+                    "inspect.example_with",
+                    assignment = format!("{}={}", var_name, example_value)
+                )
+            );
+            ui::show!(
                 "  {} ComponentName --type {} --var {}={}",
                 "cli-frontend".cyan(),
                 template_type,
                 var_name,
                 example_value
             );
-            println!();
+            ui::show!();
             example_count += 1;
         }
     }
 
     // Full featured example
     if config.options_metadata.len() >= 2 {
-        println!("  {} Full featured", "#".dimmed());
-        print!(
-            "  {} ComponentName --type {}",
-            "cli-frontend".cyan(),
-            template_type
-        );
+        ui::show!("  {} {}", "#".dimmed(), t!("inspect.example_full"));
         let mut var_examples = Vec::with_capacity(3);
         for (var_name, metadata) in config.options_metadata.iter().take(3) {
             if !metadata.possible_values.is_empty() {
@@ -212,14 +450,20 @@ pub fn print_usage_examples(template_type: &str, config: &TemplateConfig) {
                 var_examples.push(format!("--var {}=true", var_name));
             }
         }
+        let mut command = format!(
+            "  {} ComponentName --type {}",
+            "cli-frontend".cyan(),
+            template_type
+        );
         for example in var_examples {
-            print!(" {}", example);
+            command.push(' ');
+            command.push_str(&example);
         }
-        println!();
-        println!();
+        ui::show!("{}", command);
+        ui::show!();
     }
 
-    println!();
+    ui::show!();
 }
 
 /// Format a file condition for display
@@ -300,10 +544,13 @@ mod tests {
         let metadata = TemplateMetadata {
             name: "Test Template".to_string(),
             description: "".to_string(),
+            tags: Vec::new(),
+            requires_cli: None,
+            internal: false,
         };
 
         // Just verify it doesn't panic
-        print_template_header("component", &metadata);
+        print_template_header("component", &metadata, RenderEngine::Handlebars);
     }
 
     #[test]
@@ -311,10 +558,13 @@ mod tests {
         let metadata = TemplateMetadata {
             name: "Component Template".to_string(),
             description: "React component with TypeScript".to_string(),
+            tags: vec!["component".to_string()],
+            requires_cli: Some("1.0".to_string()),
+            internal: false,
         };
 
         // Just verify it doesn't panic
-        print_template_header("component", &metadata);
+        print_template_header("component", &metadata, RenderEngine::MiniJinja);
     }
 
     #[test]
@@ -326,6 +576,7 @@ mod tests {
                 var_type: "enum".to_string(),
                 possible_values: vec!["scss".to_string(), "css".to_string()],
                 description: "Style approach".to_string(),
+                ..Default::default()
             },
         );
 
@@ -335,6 +586,23 @@ mod tests {
         print_required_variables(&options, &variables);
     }
 
+    #[test]
+    fn test_print_required_variables_grouped() {
+        let mut options = HashMap::new();
+        for (name, group) in [("style", "Styling"), ("with_tests", "Testing"), ("api", "")] {
+            options.insert(
+                name.to_string(),
+                VariableOption {
+                    group: group.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Just verify it doesn't panic
+        print_required_variables(&options, &HashMap::new());
+    }
+
     #[test]
     fn test_print_required_variables_boolean() {
         let mut options = HashMap::new();
@@ -344,6 +612,7 @@ mod tests {
                 var_type: "boolean".to_string(),
                 possible_values: vec![],
                 description: "Include test files".to_string(),
+                ..Default::default()
             },
         );
 
@@ -362,6 +631,7 @@ mod tests {
                 var_type: "enum".to_string(),
                 possible_values: vec!["scss".to_string(), "css".to_string()],
                 description: "Style approach".to_string(),
+                ..Default::default()
             },
         );
 
@@ -445,6 +715,7 @@ mod tests {
                 var_type: "boolean".to_string(),
                 possible_values: vec![],
                 description: "Include tests".to_string(),
+                ..Default::default()
             },
         );
 
@@ -465,6 +736,7 @@ mod tests {
                     "styled-components".to_string(),
                 ],
                 description: "Style approach".to_string(),
+                ..Default::default()
             },
         );
 
@@ -484,4 +756,53 @@ mod tests {
         // "_stories" is a known multiword suffix, so it gets special treatment
         assert_eq!(result, "(--var with=true)");
     }
+
+    #[test]
+    fn test_print_preview_with_failed_combination() {
+        use crate::template_engine::matrix::{Combination, CombinationPreview};
+
+        let combination = |changes: &[&str]| Combination {
+            vars: HashMap::new(),
+            changes: changes.iter().map(|c| c.to_string()).collect(),
+        };
+        let preview = TemplatePreview {
+            sample_name: "Example".to_string(),
+            total: 8,
+            combinations: vec![
+                CombinationPreview {
+                    combination: combination(&[]),
+                    files: 3,
+                    lines: 80,
+                    error: None,
+                },
+                CombinationPreview {
+                    combination: combination(&["with_stories=true", "with_tests=false"]),
+                    files: 0,
+                    lines: 0,
+                    error: Some("with_stories requires with_tests".to_string()),
+                },
+            ],
+        };
+        assert_eq!(preview.range(), Some(((3, 3), (80, 80))));
+
+        // Just verify it doesn't panic
+        print_preview(&preview);
+    }
+
+    #[test]
+    fn test_print_template_summaries() {
+        let mut config = TemplateConfig::default();
+        config
+            .variables
+            .insert("style".to_string(), "scss".to_string());
+        config.file_filters.insert(
+            "$FILE_NAME.spec.tsx".to_string(),
+            "var_with_tests".to_string(),
+        );
+        let description = TemplateDescription::new("component", &config, vec![]);
+
+        // Just verify it doesn't panic
+        print_template_summaries(&[description]);
+        print_template_summaries(&[]);
+    }
 }
@@ -0,0 +1,202 @@
+//! User-edited regions carried over when a file is regenerated.
+//!
+//! A template can mark regions that belong to the user once generated:
+//!
+//! ```text
+//! // cli-frontend:keep-start handlers
+//! const onClick = () => {};
+//! // cli-frontend:keep-end
+//! ```
+//!
+//! When the file is generated over an existing copy, the lines between the
+//! markers in the existing file replace those of the new rendering, so
+//! templates can be upgraded without losing the code users wrote there.
+//! The markers work inside any comment syntax (`//`, `#`, `<!-- -->`, ...).
+//! Regions are matched by the name after `keep-start`, and unnamed ones by
+//! their order; a region missing from the existing file keeps the template's
+//! content.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Marker opening a kept region, optionally followed by its name
+pub const KEEP_START: &str = "cli-frontend:keep-start";
+/// Marker closing a kept region
+pub const KEEP_END: &str = "cli-frontend:keep-end";
+
+/// A kept region's name: the word after the start marker, or its position
+/// among the unnamed regions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RegionKey {
+    Named(String),
+    Unnamed(usize),
+}
+
+/// A closed region in a file, as byte ranges of its body
+struct Region {
+    key: RegionKey,
+    body: std::ops::Range<usize>,
+}
+
+/// `rendered` with the kept regions of `existing` carried over, or `None`
+/// when there is nothing to carry over
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::keep::preserve_kept_regions;
+///
+/// let existing = "v1\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n";
+/// let rendered = "v2\n// cli-frontend:keep-start\ntodo();\n// cli-frontend:keep-end\n";
+/// assert_eq!(
+///     preserve_kept_regions(existing, rendered).unwrap(),
+///     "v2\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n"
+/// );
+/// assert_eq!(preserve_kept_regions("v1\n", "v2\n"), None);
+/// ```
+pub fn preserve_kept_regions(existing: &str, rendered: &str) -> Option<String> {
+    if !rendered.contains(KEEP_START) {
+        return None;
+    }
+
+    let kept: HashMap<RegionKey, &str> = regions(existing)
+        .into_iter()
+        .map(|region| (region.key, &existing[region.body]))
+        .collect();
+
+    let mut output = String::with_capacity(rendered.len());
+    let mut copied = 0;
+    let mut changed = false;
+    for region in regions(rendered) {
+        let Some(body) = kept.get(&region.key) else {
+            continue;
+        };
+        output.push_str(&rendered[copied..region.body.start]);
+        output.push_str(body);
+        copied = region.body.end;
+        changed |= *body != &rendered[region.body];
+    }
+    output.push_str(&rendered[copied..]);
+
+    changed.then_some(output)
+}
+
+/// `content` with the lines inside its kept regions removed, markers kept
+///
+/// Stamps hash this, so edits inside kept regions don't count as changes.
+pub fn without_kept_regions(content: &str) -> Cow<'_, str> {
+    let regions = regions(content);
+    if regions.is_empty() {
+        return Cow::Borrowed(content);
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut copied = 0;
+    for region in regions {
+        output.push_str(&content[copied..region.body.start]);
+        copied = region.body.end;
+    }
+    output.push_str(&content[copied..]);
+    Cow::Owned(output)
+}
+
+/// Closed kept regions of `content`, in order
+///
+/// An unclosed start marker, or one opened inside another region, is
+/// treated as plain content.
+fn regions(content: &str) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut unnamed = 0;
+    let mut open: Option<(RegionKey, usize)> = None;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_end = offset + line.len();
+        match &open {
+            None => {
+                if let Some(position) = line.find(KEEP_START) {
+                    let name = line[position + KEEP_START.len()..]
+                        .split_whitespace()
+                        .next()
+                        .filter(|word| !word.starts_with(['-', '*']));
+                    let key = match name {
+                        Some(name) => RegionKey::Named(name.to_string()),
+                        None => {
+                            unnamed += 1;
+                            RegionKey::Unnamed(unnamed - 1)
+                        }
+                    };
+                    open = Some((key, line_end));
+                }
+            }
+            Some((_, start)) if line.contains(KEEP_END) => {
+                let start = *start;
+                let (key, _) = open.take().unwrap();
+                regions.push(Region {
+                    key,
+                    body: start..offset,
+                });
+            }
+            Some(_) => {}
+        }
+        offset = line_end;
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kept_regions_match_by_name_and_order() {
+        let existing = "\
+<!-- cli-frontend:keep-start footer -->
+<p>custom footer</p>
+<!-- cli-frontend:keep-end -->
+# cli-frontend:keep-start
+custom first
+# cli-frontend:keep-end
+";
+        let rendered = "\
+header v2
+# cli-frontend:keep-start
+default first
+# cli-frontend:keep-end
+# cli-frontend:keep-start
+default second
+# cli-frontend:keep-end
+<!-- cli-frontend:keep-start footer -->
+<p>default footer</p>
+<!-- cli-frontend:keep-end -->
+";
+        assert_eq!(
+            preserve_kept_regions(existing, rendered).unwrap(),
+            "\
+header v2
+# cli-frontend:keep-start
+custom first
+# cli-frontend:keep-end
+# cli-frontend:keep-start
+default second
+# cli-frontend:keep-end
+<!-- cli-frontend:keep-start footer -->
+<p>custom footer</p>
+<!-- cli-frontend:keep-end -->
+"
+        );
+
+        // Unchanged or unclosed regions leave the rendering as it is
+        assert_eq!(preserve_kept_regions(rendered, rendered), None);
+        let unclosed = "// cli-frontend:keep-start\nmine\n";
+        assert_eq!(
+            preserve_kept_regions(unclosed, "// cli-frontend:keep-start\nnew\n"),
+            None
+        );
+        assert_eq!(
+            without_kept_regions("a\n# cli-frontend:keep-start\nmine\n# cli-frontend:keep-end\nb"),
+            "a\n# cli-frontend:keep-start\n# cli-frontend:keep-end\nb"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! License metadata for generated code (`license` in the config file).
+//!
+//! The configured SPDX identifier is available to templates as `{{license}}`
+//! and through the `spdx_header` helper, and `--license-file` writes its
+//! text into packages generated with `--as-package`. Only short permissive
+//! licenses have bundled texts; other identifiers are still stamped into
+//! files and `package.json`.
+
+/// Name of the license file written into packages
+pub const LICENSE_FILE: &str = "LICENSE";
+
+/// The SPDX line naming `license`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::license::spdx_line;
+///
+/// assert_eq!(spdx_line("MIT"), "SPDX-License-Identifier: MIT");
+/// ```
+pub fn spdx_line(license: &str) -> String {
+    format!("SPDX-License-Identifier: {}", license)
+}
+
+/// Text of the license `spdx`, for copyright `holder` in `year`, or `None`
+/// if no text is bundled for it
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::license::license_text;
+///
+/// let text = license_text("mit", "2026", "Acme").unwrap();
+/// assert!(text.starts_with("MIT License\n\nCopyright (c) 2026 Acme\n"));
+/// assert!(license_text("GPL-3.0-only", "2026", "Acme").is_none());
+/// ```
+pub fn license_text(spdx: &str, year: &str, holder: &str) -> Option<String> {
+    let body = match spdx.to_ascii_uppercase().as_str() {
+        "MIT" => MIT,
+        "ISC" => ISC,
+        "0BSD" => ZERO_BSD,
+        _ => return None,
+    };
+    Some(
+        body.replace("{year}", year)
+            .replace("{holder}", holder)
+            .trim_start()
+            .to_string(),
+    )
+}
+
+const MIT: &str = r#"
+MIT License
+
+Copyright (c) {year} {holder}
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+"#;
+
+const ISC: &str = r#"
+ISC License
+
+Copyright (c) {year} {holder}
+
+Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted, provided that the above
+copyright notice and this permission notice appear in all copies.
+
+THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+"#;
+
+const ZERO_BSD: &str = r#"
+Copyright (C) {year} by {holder}
+
+Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted.
+
+THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+"#;
@@ -0,0 +1,461 @@
+//! Template linting (`cli-frontend lint-template`).
+//!
+//! Cross-references the variables a template declares in its `.conf` with
+//! the Handlebars expressions in its files and the conditions in `[files]`.
+//! The analysis is purely textual, so it needs no rendering or file access.
+//! Variable usage is only checked for Handlebars templates; `engine=minijinja`
+//! templates get the `[files]` checks.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use super::config::{RenderEngine, TemplateConfig};
+use super::renderer::create_template_data;
+
+/// Data added by command line flags (`--figma-json`, `--tokens`,
+/// `--as-package`), which render empty when the flag isn't given
+const FLAG_DATA: [&str; 3] = ["figma", "tokens", "package"];
+
+/// What kind of problem a [`LintFinding`] reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// Declared in `.conf` but never referenced
+    UnusedVariable,
+    /// Referenced but neither declared nor built in
+    UndeclaredVariable,
+    /// A `[files]` entry for a file the template doesn't contain
+    MissingFilterFile,
+    /// A `[files]` condition that can't be evaluated
+    InvalidFilterCondition,
+}
+
+/// A problem found in a template
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LintFinding {
+    pub kind: LintKind,
+    /// The variable or file the finding is about
+    pub subject: String,
+    /// `file:line` (or `.conf`) where it was found
+    pub location: String,
+    pub message: String,
+}
+
+/// Lint a template from its parsed config and files
+///
+/// `files` are paths relative to the template directory with their text
+/// contents, or `None` for binary and `[raw]` files, which are not scanned.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::lint::{lint_template, LintKind};
+/// use cli_frontend::template_engine::TemplateConfig;
+///
+/// let mut config = TemplateConfig::default();
+/// config.variables.insert("with_tests".to_string(), "true".to_string());
+///
+/// let files = vec![("$FILE_NAME.ts".to_string(), Some("{{pascal_name}} {{styel}}".to_string()))];
+/// let findings = lint_template(&config, &files);
+///
+/// let kinds: Vec<LintKind> = findings.iter().map(|f| f.kind).collect();
+/// assert_eq!(kinds, vec![LintKind::UnusedVariable, LintKind::UndeclaredVariable]);
+/// ```
+pub fn lint_template(
+    config: &TemplateConfig,
+    files: &[(String, Option<String>)],
+) -> Vec<LintFinding> {
+    let declared: BTreeSet<&str> = config
+        .variables
+        .keys()
+        .chain(config.options_metadata.keys())
+        .map(String::as_str)
+        .collect();
+    let builtins = builtin_names(config);
+
+    let mut used = BTreeSet::new();
+    let mut findings = Vec::new();
+
+    let scan_expressions = config.engine == RenderEngine::Handlebars;
+
+    for (path, content) in files.iter().filter(|_| scan_expressions) {
+        let Some(content) = content else { continue };
+
+        for reference in variable_references(content) {
+            match resolve_variable(&reference.name, &declared) {
+                Some(variable) => {
+                    used.insert(variable);
+                }
+                None if builtins.contains(reference.name.as_str()) || reference.in_scope_block => {}
+                None => findings.push(LintFinding {
+                    kind: LintKind::UndeclaredVariable,
+                    subject: reference.name.clone(),
+                    location: format!("{}:{}", path, reference.line),
+                    message: format!(
+                        "'{}' is not declared in .conf and renders empty",
+                        reference.name
+                    ),
+                }),
+            }
+        }
+    }
+
+    let file_names: BTreeSet<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+    let mut filters: Vec<(&String, &String)> = config.file_filters.iter().collect();
+    filters.sort();
+
+    for (file, condition) in filters {
+        if !file_names.contains(file.as_str()) {
+            findings.push(LintFinding {
+                kind: LintKind::MissingFilterFile,
+                subject: file.clone(),
+                location: ".conf [files]".to_string(),
+                message: format!(
+                    "Filter refers to '{}', which the template doesn't contain",
+                    file
+                ),
+            });
+        }
+
+        match filter_variable(condition, &declared) {
+            Ok(Some(variable)) => {
+                used.insert(variable);
+            }
+            Ok(None) => {}
+            Err(message) => findings.push(LintFinding {
+                kind: LintKind::InvalidFilterCondition,
+                subject: file.clone(),
+                location: ".conf [files]".to_string(),
+                message,
+            }),
+        }
+    }
+
+    for variable in declared.difference(&used).filter(|_| scan_expressions) {
+        findings.push(LintFinding {
+            kind: LintKind::UnusedVariable,
+            subject: variable.to_string(),
+            location: ".conf [options]".to_string(),
+            message: format!(
+                "'{}' is declared but not used by any file or filter",
+                variable
+            ),
+        });
+    }
+
+    findings.sort();
+    findings
+}
+
+/// A variable referenced from a Handlebars expression
+#[derive(Debug, PartialEq, Eq)]
+struct VariableReference {
+    name: String,
+    line: usize,
+    /// Inside `{{#each}}`/`{{#with}}`, where names may refer to the block context
+    in_scope_block: bool,
+}
+
+/// Names the template can use without declaring them
+fn builtin_names(config: &TemplateConfig) -> BTreeSet<String> {
+    // Route templates also get `route_path` and `route_params`, and global
+    // `[variables]` arrive as data
+    let config = TemplateConfig {
+        filename_case: config.filename_case,
+        data: config.data.clone(),
+        ..Default::default()
+    };
+    let data = create_template_data("Name", &config);
+    data.as_object()
+        .into_iter()
+        .flat_map(|data| data.keys().cloned())
+        .chain(FLAG_DATA.iter().map(|name| name.to_string()))
+        .collect()
+}
+
+/// Map a referenced name to the declared variable it reads, including the
+/// generated `{var}_bool` and `{var}_is_{value}` helpers
+fn resolve_variable<'a>(name: &str, declared: &BTreeSet<&'a str>) -> Option<&'a str> {
+    if let Some(variable) = declared.get(name) {
+        return Some(variable);
+    }
+
+    if let Some(variable) = name.strip_suffix("_bool").and_then(|v| declared.get(v)) {
+        return Some(variable);
+    }
+
+    name.match_indices("_is_")
+        .find_map(|(index, _)| declared.get(&name[..index]))
+        .copied()
+}
+
+/// The declared variable a `[files]` condition reads, if any
+fn filter_variable<'a>(
+    condition: &str,
+    declared: &BTreeSet<&'a str>,
+) -> Result<Option<&'a str>, String> {
+    let condition = condition.trim();
+    if condition == "always" || condition == "default" {
+        return Ok(None);
+    }
+
+    let Some(var_part) = condition.strip_prefix("var_") else {
+        return Err(format!(
+            "Unknown condition '{}' (expected always, default, var_NAME or var_NAME_VALUE)",
+            condition
+        ));
+    };
+
+    if let Some(variable) = declared.get(var_part) {
+        return Ok(Some(variable));
+    }
+
+    declared
+        .iter()
+        .find(|variable| {
+            var_part
+                .strip_prefix(**variable)
+                .is_some_and(|rest| rest.starts_with('_'))
+        })
+        .map(|variable| Some(*variable))
+        .ok_or_else(|| format!("Condition '{}' refers to an undeclared variable", condition))
+}
+
+/// Variables referenced by the Handlebars expressions in `content`
+fn variable_references(content: &str) -> Vec<VariableReference> {
+    let mut references = Vec::new();
+    let mut blocks: Vec<String> = Vec::new();
+    let mut rest = content;
+    let mut offset = 0;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let (inner, consumed) = if let Some(comment) = after.strip_prefix("!--") {
+            let end = comment.find("--}}").map_or(comment.len(), |e| e + 4);
+            ("", 3 + end)
+        } else {
+            let end = after.find("}}").unwrap_or(after.len());
+            let consumed = end + 2 + usize::from(after[end..].starts_with("}}}"));
+            (&after[..end], consumed)
+        };
+
+        let line = content[..offset + start].matches('\n').count() + 1;
+        let expression = inner.trim_start_matches('{').trim_matches('~').trim();
+        let in_scope_block = blocks.iter().any(|b| b == "each" || b == "with");
+
+        let names: Vec<String> = match expression.chars().next() {
+            None | Some('!') | Some('>') => Vec::new(),
+            Some('/') => {
+                blocks.pop();
+                Vec::new()
+            }
+            Some('#') | Some('^') => {
+                let tokens = tokenize(&expression[1..]);
+                blocks.push(tokens.first().map(|t| t.text.clone()).unwrap_or_default());
+                call_arguments(&tokens)
+            }
+            _ => {
+                let tokens = tokenize(expression);
+                match tokens.as_slice() {
+                    [first, rest @ ..] if first.text == "else" => call_arguments(rest),
+                    [single] => vec![single.text.clone()],
+                    tokens => call_arguments(tokens),
+                }
+            }
+        };
+
+        references.extend(
+            names
+                .iter()
+                .filter_map(|name| root_identifier(name))
+                .map(|name| VariableReference {
+                    name,
+                    line,
+                    in_scope_block,
+                }),
+        );
+
+        let advanced = (start + 2 + consumed).min(rest.len());
+        offset += advanced;
+        rest = &rest[advanced..];
+    }
+
+    references
+}
+
+/// A token of a Handlebars expression
+#[derive(Debug)]
+struct Token {
+    text: String,
+    /// First token inside a `( ... )` sub-expression, i.e. a helper name
+    is_helper: bool,
+}
+
+/// Split an expression on whitespace and parentheses, keeping string literals whole
+fn tokenize(expression: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut after_paren = false;
+
+    let flush = |current: &mut String, after_paren: &mut bool, tokens: &mut Vec<Token>| {
+        if !current.is_empty() {
+            tokens.push(Token {
+                text: std::mem::take(current),
+                is_helper: *after_paren,
+            });
+            *after_paren = false;
+        }
+    };
+
+    for c in expression.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => {
+                current.push(c);
+                quote = None;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                current.push(c);
+                quote = Some(c);
+            }
+            (None, '(') => {
+                flush(&mut current, &mut after_paren, &mut tokens);
+                after_paren = true;
+            }
+            (None, ')') => flush(&mut current, &mut after_paren, &mut tokens),
+            (None, c) if c.is_whitespace() => flush(&mut current, &mut after_paren, &mut tokens),
+            (None, c) => current.push(c),
+        }
+    }
+    flush(&mut current, &mut after_paren, &mut tokens);
+
+    tokens
+}
+
+/// Arguments of a helper call (`helper arg key=value (sub arg)`)
+fn call_arguments(tokens: &[Token]) -> Vec<String> {
+    tokens
+        .iter()
+        .skip(1)
+        .filter(|token| !token.is_helper)
+        .map(|token| match token.text.split_once('=') {
+            Some((_, value)) => value.to_string(),
+            None => token.text.clone(),
+        })
+        .collect()
+}
+
+/// The root name of a path expression, or `None` for literals and context paths
+fn root_identifier(path: &str) -> Option<String> {
+    if path.starts_with('@') || path.starts_with("../") || path.starts_with("this") {
+        return None;
+    }
+
+    let root = path.split(['.', '/']).next()?;
+    let first = root.chars().next()?;
+    if !(first.is_alphabetic() || first == '_') {
+        return None;
+    }
+    if matches!(root, "true" | "false" | "null" | "undefined" | "as") {
+        return None;
+    }
+
+    Some(root.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::config::VariableOption;
+
+    fn text(path: &str, content: &str) -> (String, Option<String>) {
+        (path.to_string(), Some(content.to_string()))
+    }
+
+    #[test]
+    fn test_variable_references_skip_helpers_literals_and_comments() {
+        let content = "{{pascal_case name}}\n\
+                       {{!-- {{ignored}} --}}\n\
+                       {{#if (eq style \"scss\")}}{{else}}{{/if}}\n\
+                       {{#each items}}{{label}}{{/each}}\n\
+                       {{{raw_html}}} {{env \"HOME\"}} {{uuid}}";
+
+        let names: Vec<(String, usize, bool)> = variable_references(content)
+            .into_iter()
+            .map(|r| (r.name, r.line, r.in_scope_block))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("name".to_string(), 1, false),
+                ("style".to_string(), 3, false),
+                ("items".to_string(), 4, false),
+                ("label".to_string(), 4, true),
+                ("raw_html".to_string(), 5, false),
+                ("uuid".to_string(), 5, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_reports_unused_undeclared_and_filter_problems() {
+        let mut config = TemplateConfig::default();
+        for (name, value) in [("style", "scss"), ("with_tests", "true"), ("author", "")] {
+            config.variables.insert(name.to_string(), value.to_string());
+        }
+        config.options_metadata.insert(
+            "style".to_string(),
+            VariableOption {
+                var_type: "enum".to_string(),
+                possible_values: vec!["scss".to_string(), "css".to_string()],
+                description: String::new(),
+                ..Default::default()
+            },
+        );
+        for (file, condition) in [
+            ("$FILE_NAME.spec.tsx", "var_with_tests"),
+            ("$FILE_NAME.module.scss", "var_style_scss"),
+            ("$FILE_NAME.stories.tsx", "var_with_stories"),
+        ] {
+            config
+                .file_filters
+                .insert(file.to_string(), condition.to_string());
+        }
+
+        let files = vec![
+            text("$FILE_NAME.tsx", "{{#if style_is_scss}}{{/if}}\n{{authr}}"),
+            text(
+                "$FILE_NAME.spec.tsx",
+                "{{pascal_name}} {{#each figma.props}}{{name}}{{/each}}",
+            ),
+            ("logo.png".to_string(), None),
+        ];
+
+        let summary: Vec<(LintKind, String)> = lint_template(&config, &files)
+            .into_iter()
+            .map(|f| (f.kind, f.subject))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (LintKind::UnusedVariable, "author".to_string()),
+                (LintKind::UndeclaredVariable, "authr".to_string()),
+                (
+                    LintKind::MissingFilterFile,
+                    "$FILE_NAME.module.scss".to_string()
+                ),
+                (
+                    LintKind::MissingFilterFile,
+                    "$FILE_NAME.stories.tsx".to_string()
+                ),
+                (
+                    LintKind::InvalidFilterCondition,
+                    "$FILE_NAME.stories.tsx".to_string()
+                ),
+            ]
+        );
+    }
+}
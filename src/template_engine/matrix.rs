@@ -0,0 +1,271 @@
+//! Variable matrix of a template.
+//!
+//! Boolean variables and variables with `{var}_options` are the axes of the
+//! matrix; each combination of their values is one way the template can be
+//! generated. Every axis starts at the template's default, so the first
+//! combination is what a plain `cli-frontend Name --type template` produces
+//! and the following ones change the least-significant axis first.
+//!
+//! Free-form variables are left at their defaults. Templates with many
+//! options have large matrices, so callers take at most a capped number of
+//! combinations.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::config::TemplateConfig;
+
+/// Most combinations rendered for a preview
+pub const MAX_COMBINATIONS: usize = 32;
+
+/// Name the preview renders with
+pub const SAMPLE_NAME: &str = "Example";
+
+/// Value given to required free-form variables that have no default
+pub const SAMPLE_VALUE: &str = "example";
+
+/// A variable and the values it can take, default first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixAxis {
+    pub variable: String,
+    pub values: Vec<String>,
+}
+
+/// Values for one combination of the matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Combination {
+    /// Every axis variable with its value in this combination
+    pub vars: HashMap<String, String>,
+    /// `name=value` for the axes away from their default, in axis order
+    pub changes: Vec<String>,
+}
+
+impl Combination {
+    /// Short description, e.g. `style=css with_tests=false` or `defaults`
+    pub fn label(&self) -> String {
+        if self.changes.is_empty() {
+            "defaults".to_string()
+        } else {
+            self.changes.join(" ")
+        }
+    }
+
+    /// Folder name for the combination, e.g. `style-css__with_tests-false`
+    pub fn dir_name(&self) -> String {
+        self.label()
+            .replace(' ', "__")
+            .replace('=', "-")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "_-.".contains(c) {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    }
+}
+
+/// What one combination generates, from a trial render
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CombinationPreview {
+    pub combination: Combination,
+    pub files: usize,
+    /// Lines across the generated text files
+    pub lines: usize,
+    /// Why the combination can't be generated, e.g. a `[rules]` conflict
+    pub error: Option<String>,
+}
+
+/// Trial renders across a template's variable matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplatePreview {
+    pub sample_name: String,
+    /// Combinations in the whole matrix, including those not rendered
+    pub total: usize,
+    pub combinations: Vec<CombinationPreview>,
+}
+
+impl TemplatePreview {
+    /// Smallest and largest `(files, lines)` among the renderable combinations
+    pub fn range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let rendered = || self.combinations.iter().filter(|c| c.error.is_none());
+        let files = (
+            rendered().map(|c| c.files).min()?,
+            rendered().map(|c| c.files).max()?,
+        );
+        let lines = (
+            rendered().map(|c| c.lines).min()?,
+            rendered().map(|c| c.lines).max()?,
+        );
+        Some((files, lines))
+    }
+}
+
+/// The axes of a template's variable matrix, sorted by variable name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariableMatrix {
+    pub axes: Vec<MatrixAxis>,
+}
+
+impl VariableMatrix {
+    /// Axes for the boolean and enumerated variables of `config`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::config::VariableOption;
+    /// use cli_frontend::template_engine::matrix::VariableMatrix;
+    /// use cli_frontend::template_engine::TemplateConfig;
+    ///
+    /// let mut config = TemplateConfig::default();
+    /// config.variables.insert("style".to_string(), "css".to_string());
+    /// config.options_metadata.insert("style".to_string(), VariableOption {
+    ///     possible_values: vec!["scss".to_string(), "css".to_string()],
+    ///     ..Default::default()
+    /// });
+    /// config.options_metadata.insert("with_tests".to_string(), VariableOption {
+    ///     var_type: "boolean".to_string(),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let matrix = VariableMatrix::from_config(&config);
+    /// assert_eq!(matrix.len(), 4);
+    ///
+    /// let labels: Vec<String> = matrix.combinations(3).iter().map(|c| c.label()).collect();
+    /// assert_eq!(labels, ["defaults", "with_tests=true", "style=scss"]);
+    /// ```
+    pub fn from_config(config: &TemplateConfig) -> Self {
+        let mut axes: Vec<MatrixAxis> = config
+            .options_metadata
+            .iter()
+            .filter_map(|(variable, option)| {
+                let default = config.variables.get(variable).map(String::as_str);
+                let values = if option.var_type == "boolean" {
+                    if default.is_some_and(is_truthy) {
+                        vec!["true".to_string(), "false".to_string()]
+                    } else {
+                        vec!["false".to_string(), "true".to_string()]
+                    }
+                } else if !option.possible_values.is_empty() {
+                    let mut values = option.possible_values.clone();
+                    if let Some(index) = values.iter().position(|v| Some(v.as_str()) == default) {
+                        let default = values.remove(index);
+                        values.insert(0, default);
+                    }
+                    values
+                } else {
+                    return None;
+                };
+                Some(MatrixAxis {
+                    variable: variable.clone(),
+                    values,
+                })
+            })
+            .collect();
+        axes.sort_by(|a, b| a.variable.cmp(&b.variable));
+
+        Self { axes }
+    }
+
+    /// Number of combinations (saturating)
+    pub fn len(&self) -> usize {
+        self.axes.iter().fold(1usize, |total, axis| {
+            total.saturating_mul(axis.values.len())
+        })
+    }
+
+    /// Whether the matrix has no axes, leaving the defaults as its only combination
+    #[allow(dead_code)] // Public API
+    pub fn is_empty(&self) -> bool {
+        self.axes.is_empty()
+    }
+
+    /// The first `limit` combinations, starting with the defaults
+    pub fn combinations(&self, limit: usize) -> Vec<Combination> {
+        (0..self.len().min(limit))
+            .map(|mut index| {
+                let mut indices = vec![0; self.axes.len()];
+                // The last axis changes fastest
+                for (slot, axis) in indices.iter_mut().zip(&self.axes).rev() {
+                    *slot = index % axis.values.len();
+                    index /= axis.values.len();
+                }
+
+                let mut combination = Combination {
+                    vars: HashMap::new(),
+                    changes: Vec::new(),
+                };
+                for (axis, &value) in self.axes.iter().zip(&indices) {
+                    let value = &axis.values[value];
+                    if value != &axis.values[0] {
+                        combination
+                            .changes
+                            .push(format!("{}={}", axis.variable, value));
+                    }
+                    combination
+                        .vars
+                        .insert(axis.variable.clone(), value.clone());
+                }
+                combination
+            })
+            .collect()
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(value.to_lowercase().as_str(), "true" | "yes" | "1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_engine::config::VariableOption;
+
+    #[test]
+    fn test_matrix_starts_at_defaults_and_skips_free_form_variables() {
+        let mut config = TemplateConfig::default();
+        for (name, var_type) in [("with_tests", "boolean"), ("with_stories", "boolean")] {
+            config.options_metadata.insert(
+                name.to_string(),
+                VariableOption {
+                    var_type: var_type.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        config
+            .variables
+            .insert("with_tests".to_string(), "true".to_string());
+        config.options_metadata.insert(
+            "aria_role".to_string(),
+            VariableOption {
+                description: "ARIA role".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let matrix = VariableMatrix::from_config(&config);
+        assert_eq!(matrix.axes.len(), 2);
+        assert_eq!(matrix.axes[1].values, ["true", "false"]);
+
+        let combinations = matrix.combinations(MAX_COMBINATIONS);
+        let labels: Vec<String> = combinations.iter().map(Combination::label).collect();
+        assert_eq!(
+            labels,
+            [
+                "defaults",
+                "with_tests=false",
+                "with_stories=true",
+                "with_stories=true with_tests=false",
+            ]
+        );
+        assert_eq!(combinations[3].vars["with_tests"], "false");
+        assert_eq!(
+            combinations[3].dir_name(),
+            "with_stories-true__with_tests-false"
+        );
+        assert!(VariableMatrix::default().combinations(1)[0].vars.is_empty());
+    }
+}
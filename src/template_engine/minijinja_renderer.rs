@@ -0,0 +1,302 @@
+//! MiniJinja implementation of the TemplateRenderer trait.
+//!
+//! Templates opt in with `engine=minijinja` in their `.conf`, so templates
+//! ported from Jinja, Nunjucks or Tera based generators keep their syntax.
+//! They get the same data as Handlebars templates; the case helpers are
+//! filters (`{{ name | kebab_case }}`), `timestamp`, `uuid` and `env`
+//! are functions, `comment` is a filter for `{% filter comment %}` blocks
+//! and `spdx_header()` is a function.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use minijinja::{Environment, Error, ErrorKind, State, UndefinedBehavior};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::config::TemplateConfig;
+use super::explain::{record_call, HelperCalls};
+use super::header::comment_lines;
+use super::helpers::comment_delimiters;
+use super::license::spdx_line;
+use super::naming::{to_camel_case, to_kebab_case, to_pascal_case, to_snake_case};
+use super::renderer::RenderFailure;
+use super::renderer_trait::TemplateRenderer;
+
+/// MiniJinja implementation of TemplateRenderer
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::{MiniJinjaRenderer, TemplateRenderer};
+/// use serde_json::json;
+///
+/// let renderer = MiniJinjaRenderer::new();
+/// let result = renderer.render(
+///     "{% if with_state %}{{ name | pascal_case }}{% endif %}",
+///     &json!({"name": "user_card", "with_state": true})
+/// ).unwrap();
+/// assert_eq!(result, "UserCard");
+/// ```
+pub struct MiniJinjaRenderer {
+    env: Environment<'static>,
+}
+
+impl MiniJinjaRenderer {
+    /// Create a new MiniJinjaRenderer with all filters and functions registered
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        // Generated files keep their final newline, as with Handlebars
+        env.set_keep_trailing_newline(true);
+
+        for (name, filter) in CASE_FILTERS {
+            env.add_filter(name, move |s: String| filter(&s));
+        }
+        env.add_function("timestamp", timestamp);
+        env.add_function("uuid", || Uuid::new_v4().to_string());
+        env.add_function("env", |name: String| {
+            std::env::var(name).unwrap_or_default()
+        });
+        env.add_filter("comment", comment);
+        env.add_function("spdx_header", spdx_header);
+
+        Self { env }
+    }
+
+    /// Create a renderer set up for rendering with `config`
+    ///
+    /// Like `create_handlebars_for`: strict mode follows `strict_variables`,
+    /// and `timestamp()` / `uuid()` render empty when the template disables them.
+    pub fn for_config(config: &TemplateConfig) -> Self {
+        let mut renderer = Self::new();
+        if config.strict_variables {
+            renderer
+                .env
+                .set_undefined_behavior(UndefinedBehavior::Strict);
+        }
+        if !config.enable_timestamps {
+            renderer
+                .env
+                .add_function("timestamp", |_: Option<String>| String::new());
+        }
+        if !config.enable_uuid {
+            renderer.env.add_function("uuid", String::new);
+        }
+        if !config.env_policy.is_unrestricted() {
+            let policy = config.env_policy.clone();
+            renderer.env.add_function("env", move |name: String| {
+                policy.read(&name).map_err(|denied| {
+                    Error::new(ErrorKind::InvalidOperation, denied.to_string()).with_source(denied)
+                })
+            });
+        }
+        renderer
+    }
+
+    /// Like [`Self::for_config`], with every filter and function call
+    /// recorded into `calls` (`--explain`)
+    pub fn traced(config: &TemplateConfig, calls: &HelperCalls) -> Self {
+        let mut renderer = Self::for_config(config);
+
+        for (name, filter) in CASE_FILTERS {
+            let calls = calls.clone();
+            renderer.env.add_filter(name, move |s: String| {
+                let output = filter(&s);
+                record_call(&calls, name, vec![Value::String(s)], &output);
+                output
+            });
+        }
+
+        let enabled = config.enable_timestamps;
+        let timestamp_calls = calls.clone();
+        renderer
+            .env
+            .add_function("timestamp", move |format: Option<String>| {
+                let output = if enabled {
+                    timestamp(format.clone())
+                } else {
+                    String::new()
+                };
+                let inputs = format.map(Value::String).into_iter().collect();
+                record_call(&timestamp_calls, "timestamp", inputs, &output);
+                output
+            });
+
+        let enabled = config.enable_uuid;
+        let uuid_calls = calls.clone();
+        renderer.env.add_function("uuid", move || {
+            let output = if enabled {
+                Uuid::new_v4().to_string()
+            } else {
+                String::new()
+            };
+            record_call(&uuid_calls, "uuid", Vec::new(), &output);
+            output
+        });
+
+        let policy = config.env_policy.clone();
+        let env_calls = calls.clone();
+        renderer.env.add_function("env", move |name: String| {
+            let output = policy.read(&name).map_err(|denied| {
+                Error::new(ErrorKind::InvalidOperation, denied.to_string()).with_source(denied)
+            })?;
+            record_call(&env_calls, "env", vec![Value::String(name)], &output);
+            Ok::<_, Error>(output)
+        });
+
+        renderer
+    }
+}
+
+/// A case helper, as a filter
+type CaseFilter = fn(&str) -> String;
+
+/// The case helpers, as filters
+const CASE_FILTERS: [(&str, CaseFilter); 5] = [
+    ("pascal_case", |s| to_pascal_case(s).into_owned()),
+    ("snake_case", |s| to_snake_case(s).into_owned()),
+    ("kebab_case", |s| to_kebab_case(s).into_owned()),
+    ("camel_case", |s| to_camel_case(s).into_owned()),
+    ("upper_case", |s| s.to_uppercase()),
+];
+
+impl Default for MiniJinjaRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRenderer for MiniJinjaRenderer {
+    /// On failure the error is a [`RenderFailure`] with the line and a
+    /// snippet of the offending region.
+    fn render(&self, template: &str, data: &Value) -> Result<String> {
+        self.env
+            .render_str(template, data)
+            .map_err(|e| RenderFailure::new(template, &e, 1).into())
+    }
+}
+
+/// `text` as comments in the syntax of the file being generated, like the
+/// Handlebars `comment` helper
+fn comment(state: &State, text: String) -> String {
+    match comment_delimiters(&comment_data(state)) {
+        Some((open, close)) => comment_lines(&text, &open, &close, "\n"),
+        None => String::new(),
+    }
+}
+
+/// `spdx_header(license)`, like the Handlebars `spdx_header` helper
+fn spdx_header(state: &State, license: Option<String>) -> String {
+    let data = comment_data(state);
+    let license =
+        license.unwrap_or_else(|| data["license"].as_str().unwrap_or_default().to_string());
+    match comment_delimiters(&data) {
+        Some((open, close)) if !license.is_empty() => {
+            format!("{}{}{}", open, spdx_line(&license), close)
+        }
+        _ => String::new(),
+    }
+}
+
+/// The data [`comment_delimiters`] and `spdx_header` read, from the render state
+fn comment_data(state: &State) -> Value {
+    let data = ["comment_prefix", "comment_suffix", "license"].map(|key| {
+        let value = state
+            .lookup(key)
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        (key.to_string(), Value::String(value))
+    });
+    Value::Object(data.into_iter().collect())
+}
+
+/// `timestamp(format)` with the formats of the Handlebars `timestamp` helper
+fn timestamp(format: Option<String>) -> String {
+    let now: DateTime<Utc> = Utc::now();
+    match format.as_deref().unwrap_or("ISO") {
+        "date" => now.format("%Y-%m-%d").to_string(),
+        "time" => now.format("%H:%M:%S").to_string(),
+        "datetime" => now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "unix" => now.timestamp().to_string(),
+        _ => now.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_minijinja_renderer_filters_and_functions() {
+        let renderer = MiniJinjaRenderer::new();
+        let result = renderer
+            .render(
+                "{{ name | pascal_case }} - {{ name | snake_case }} - {{ name | kebab_case }}\n",
+                &json!({"name": "HelloWorld"}),
+            )
+            .unwrap();
+        assert_eq!(result, "HelloWorld - hello_world - hello-world\n");
+
+        let uuid = renderer.render("{{ uuid() }}", &json!({})).unwrap();
+        assert_eq!(uuid.len(), 36);
+        let unix = renderer
+            .render("{{ timestamp('unix') }}", &json!({}))
+            .unwrap();
+        assert!(unix.parse::<i64>().is_ok());
+    }
+
+    #[test]
+    fn test_minijinja_renderer_comment_filter() {
+        let renderer = MiniJinjaRenderer::new();
+        let template = "{% filter comment %}Copyright {{ company }}\n{% endfilter %}x";
+        let data = |prefix: &str, suffix: &str| json!({"company": "Acme", "comment_prefix": prefix, "comment_suffix": suffix});
+        assert_eq!(
+            renderer.render(template, &data("/*", "*/")).unwrap(),
+            "/* Copyright Acme */\nx"
+        );
+        assert_eq!(renderer.render(template, &data("", "")).unwrap(), "x");
+        assert_eq!(
+            renderer
+                .render("{{ spdx_header('MIT') }}", &data("#", ""))
+                .unwrap(),
+            "# SPDX-License-Identifier: MIT"
+        );
+    }
+
+    #[test]
+    fn test_minijinja_renderer_for_config() {
+        let config = TemplateConfig {
+            strict_variables: true,
+            enable_uuid: false,
+            ..Default::default()
+        };
+        let renderer = MiniJinjaRenderer::for_config(&config);
+        assert_eq!(
+            renderer.render("id: {{ uuid() }}", &json!({})).unwrap(),
+            "id: "
+        );
+
+        let error = renderer
+            .render("ok\n{{ missing }}\n", &json!({}))
+            .unwrap_err();
+        let failure = error.downcast_ref::<RenderFailure>().unwrap();
+        assert_eq!(failure.line, Some(2));
+        assert!(failure.snippet.contains("{{ missing }}"));
+    }
+
+    #[test]
+    fn test_minijinja_renderer_traced() {
+        let calls = HelperCalls::default();
+        let renderer = MiniJinjaRenderer::traced(&TemplateConfig::default(), &calls);
+        let result = renderer
+            .render("{{ name | kebab_case }}", &json!({"name": "UserCard"}))
+            .unwrap();
+        assert_eq!(result, "user-card");
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].helper, "kebab_case");
+        assert_eq!(calls[0].inputs, [json!("UserCard")]);
+        assert_eq!(calls[0].output, "user-card");
+    }
+}
@@ -13,12 +13,14 @@
 //! # Example
 //!
 //! ```no_run
+//! # #[cfg(feature = "runtime")]
+//! # mod example {
 //! use cli_frontend::template_engine::TemplateEngine;
 //! use std::path::PathBuf;
 //! use std::collections::HashMap;
 //!
 //! # #[tokio::main]
-//! # async fn main() -> anyhow::Result<()> {
+//! # pub async fn main() -> anyhow::Result<()> {
 //! let engine = TemplateEngine::new(
 //!     PathBuf::from("./templates"),
 //!     PathBuf::from("./output")
@@ -33,46 +35,233 @@
 //! ).await?;
 //! # Ok(())
 //! # }
+//! # }
+//! # fn main() {}
 //! ```
 
+pub mod a11y;
+#[cfg(feature = "runtime")]
+pub mod author;
+#[cfg(feature = "runtime")]
+pub mod cache;
+#[cfg(feature = "runtime")]
+pub mod cancel;
+#[cfg(feature = "runtime")]
+pub mod clipboard;
 pub mod config;
+#[cfg(feature = "runtime")]
+mod conflict;
+mod description;
+pub mod diff;
+#[cfg(feature = "runtime")]
+mod docs;
+#[cfg(feature = "runtime")]
+mod drift;
+#[cfg(feature = "runtime")]
+pub mod editor;
+pub mod explain;
+pub mod figma;
+pub mod formatting;
+#[cfg(feature = "runtime")]
+pub mod front_matter;
+#[cfg(feature = "runtime")]
+mod generated;
+#[cfg(feature = "runtime")]
 mod generator;
+pub mod golden;
 mod handlebars_renderer;
+pub mod header;
 pub mod helpers;
+pub mod ignore;
+pub mod import;
+#[cfg(feature = "runtime")]
 mod inspector;
+pub mod keep;
+pub mod license;
+pub mod lint;
+pub mod matrix;
+mod minijinja_renderer;
 pub mod naming;
+#[cfg(feature = "runtime")]
+mod pack_manifest;
+pub mod patch;
+#[cfg(feature = "runtime")]
+pub mod path_vars;
+#[cfg(feature = "runtime")]
+mod process;
+#[cfg(feature = "runtime")]
+mod profile;
 pub mod renderer;
 mod renderer_trait;
+#[cfg(feature = "runtime")]
+pub mod resume;
+#[cfg(feature = "runtime")]
+mod rollback;
+pub mod rules;
+#[cfg(feature = "runtime")]
+mod staging;
+pub mod stamp;
+pub mod startup;
+pub mod suggest;
+#[cfg(feature = "runtime")]
+pub mod sync;
+pub mod tokens;
+pub mod toml_config;
+pub mod tree;
+#[cfg(feature = "runtime")]
+pub mod upgrade;
+#[cfg(feature = "runtime")]
+pub mod usage;
+#[cfg(feature = "runtime")]
+mod verify;
+pub mod workspace;
 
 // Re-export public types
 pub use config::TemplateConfig;
 #[allow(unused_imports)] // Used in doctests
-pub use config::{TemplateMetadata, VariableOption};
+pub use config::{TemplateExample, TemplateMetadata, VariableOption};
+#[cfg(feature = "runtime")]
+pub use conflict::ConflictPolicy;
+#[cfg(feature = "runtime")]
+#[allow(unused_imports)] // ManifestEntry is public API
+pub use conflict::{GenerationManifest, ManifestEntry, MANIFEST_FILE};
+pub use description::TemplateDescription;
+#[allow(unused_imports)] // Public API
+pub use description::{IssueSeverity, VariableDescription, VariableIssue};
+#[cfg(feature = "runtime")]
+pub use drift::{DriftedFile, TemplateStatus};
+#[allow(unused_imports)] // Public API
+pub use explain::{FileTrace, HelperCall, RenderTrace};
+#[cfg(feature = "runtime")]
+pub use generated::{primary_file, GeneratedFile, GenerationPlan, MatrixRender};
 #[allow(unused_imports)] // Public API for future use
 pub use handlebars_renderer::HandlebarsRenderer;
+#[allow(unused_imports)] // Public API
+pub use minijinja_renderer::MiniJinjaRenderer;
+#[cfg(feature = "runtime")]
+#[allow(unused_imports)] // Public API
+pub use pack_manifest::{ArchitectureDescription, PackManifest, PACK_MANIFEST_VERSION};
+#[cfg(feature = "runtime")]
+pub use profile::RunProfile;
 #[allow(unused_imports)] // Public API for future use
 pub use renderer_trait::TemplateRenderer;
+#[cfg(feature = "runtime")]
+pub use verify::verify_files;
 
-use anyhow::{Context, Result};
-use colored::*;
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use tokio::fs;
-use walkdir::WalkDir;
-
-use crate::config::{ArchitectureConfig, Config};
-use generator::{
-    evaluate_file_condition, merge_variables, prepare_output_directory, validate_template_exists,
+// The engine itself (file access, caching, generation) needs the runtime
+#[cfg(feature = "runtime")]
+use {
+    crate::ui::{self, symbols, t},
+    anyhow::{Context, Result},
+    author::Author,
+    cache::TemplateCache,
+    cancel::{is_cancellation, Cancellation},
+    colored::*,
+    config::{Framework, RenderEngine},
+    docs::{render_catalog, TemplateDoc},
+    explain::HelperCalls,
+    formatting::{EditorConfigRules, LineEndings},
+    front_matter::FrontMatter,
+    generated::RenderedFile,
+    generator::{
+        apply_variable_rules, ensure_cli_version, ensure_files_to_generate,
+        ensure_required_variables, evaluate_file_condition, list_all_template_names,
+        merge_variables, output_directory, output_mode, resolve_output_subdir,
+        resolve_path_variables, template_hash, validate_template_exists, walk_template_dir,
+        PACK_SEPARATOR,
+    },
+    header::prepend_header,
+    ignore::GitIgnore,
+    inspector::{
+        print_file_filters, print_optional_variables, print_preview, print_recent_uses,
+        print_required_variables, print_rules, print_template_header, print_template_summaries,
+        print_usage_examples,
+    },
+    lint::LintFinding,
+    matrix::{
+        CombinationPreview, TemplatePreview, VariableMatrix, MAX_COMBINATIONS, SAMPLE_NAME,
+        SAMPLE_VALUE,
+    },
+    naming::{
+        apply_cased_filename_replacements, interpolate_name_pattern, platform_suffix,
+        process_smart_names, CaseStyle, NATIVE_PLATFORMS, PLATFORM_PLACEHOLDER,
+    },
+    patch::{relative_import_path, PatchOutcome},
+    renderer::{
+        create_handlebars_for, create_template_data, create_traced_handlebars,
+        determine_output_path, is_binary_file, prepare_template_content, render_compiled,
+        render_template_chunked, resolve_path_tokens, RENDER_CHUNK_SIZE,
+    },
+    resume::FeatureProgress,
+    rollback::WriteJournal,
+    stamp::{comment_syntax, Stamp},
+    startup::{check_templates_dir, StartupWarning, StartupWarningKind},
+    std::collections::BTreeMap,
+    std::path::{Path, PathBuf},
+    std::sync::{Arc, OnceLock},
+    std::time::Instant,
+    tokio::sync::{OwnedSemaphorePermit, Semaphore},
+    tokio::task::JoinHandle,
+    toml_config::{
+        is_internal_template, is_template_config_file, TEMPLATE_CONF_FILE, TEMPLATE_TOML_FILE,
+    },
+    upgrade::{FileUpgrade, UpgradeOutcome},
+    usage::UsageHistory,
+    workspace::{package_data, Workspace, PACKAGE_TEMPLATE},
 };
-use inspector::{
-    print_file_filters, print_optional_variables, print_required_variables, print_template_header,
-    print_usage_examples,
-};
-use naming::{apply_smart_filename_replacements, apply_smart_replacements, process_smart_names};
-use renderer::{
-    create_handlebars, create_template_data, determine_output_path, read_template, render_template,
-    write_output,
+
+#[cfg(feature = "runtime")]
+use crate::config::{
+    ArchitectureConfig, ArchitecturePatch, ArchitectureStructure, Config, FEATURE_INDEX_FILE,
 };
+#[cfg(feature = "runtime")]
+use crate::error::{CliError, TemplateCandidate};
+#[cfg(feature = "runtime")]
+use crate::file_system::{FileSystem, TokioFileSystem};
+
+/// A template file scheduled for rendering
+#[cfg(feature = "runtime")]
+struct FileJob {
+    template_file: PathBuf,
+    output_file: PathBuf,
+    /// Listed under `[raw]`: copy verbatim
+    raw: bool,
+    /// Unix mode to give the output (`[permissions]`, or an executable template)
+    mode: Option<u32>,
+    /// `on_conflict` from the file's front matter
+    conflict: Option<ConflictPolicy>,
+}
+
+/// Spawned renders of planned files, aborted if dropped before joining
+///
+/// Keeps renders from running on after their run failed or was cancelled.
+#[cfg(feature = "runtime")]
+struct RenderTasks(Vec<JoinHandle<Result<RenderedFile>>>);
+
+#[cfg(feature = "runtime")]
+impl Drop for RenderTasks {
+    fn drop(&mut self) {
+        for task in &self.0 {
+            task.abort();
+        }
+    }
+}
+
+/// Feature structures rendered at the same time
+#[cfg(feature = "runtime")]
+const MAX_PARALLEL_STRUCTURES: usize = 4;
+
+/// One structure of a feature, planned for rendering
+#[cfg(feature = "runtime")]
+struct PlannedStructure<'a> {
+    feature: &'a str,
+    structure: &'a ArchitectureStructure,
+    /// Directory the structure's files go into
+    path: PathBuf,
+    jobs: Vec<FileJob>,
+    config: Arc<TemplateConfig>,
+    stamp: Option<Stamp>,
+}
 
 /// Engine for processing and generating templates.
 ///
@@ -101,11 +290,56 @@ use renderer::{
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "runtime")]
 pub struct TemplateEngine {
     templates_dir: PathBuf,
+    /// Named template roots, for `pack:template` names
+    packs: BTreeMap<String, PathBuf>,
+    /// Named output directories architecture structures can generate into
+    output_roots: BTreeMap<String, PathBuf>,
+    /// List `internal=true` templates too (`--include-internal`)
+    include_internal: bool,
     output_dir: PathBuf,
+    /// Baseline template configuration (from global config) that `.conf` files override
+    defaults: TemplateConfig,
+    /// Parsed configs and compiled templates, invalidated by file mtime
+    cache: Arc<TemplateCache>,
+    /// Backend that generated files are written to
+    fs: Arc<dyn FileSystem>,
+    /// Receives per-phase timings when profiling (`--profile-run`)
+    profile: Option<Arc<RunProfile>>,
+    /// Receives every file's helper calls when explaining (`--explain`)
+    trace: Option<Arc<RenderTrace>>,
+    /// Render every template in strict mode, regardless of its `.conf` (`--strict`)
+    strict_variables: bool,
+    conflict_policy: ConflictPolicy,
+    /// Leave out timestamps and UUIDs so renders are reproducible (`verify-golden`)
+    frozen_output: bool,
+    /// Environment that replaces the templates' `environment` (`--env`)
+    environment: Option<String>,
+    /// Skip files that fail and keep the rest instead of rolling back (`--keep-going`)
+    keep_going: bool,
+    /// Render every file before failing and report all failures (`--all-errors`)
+    all_errors: bool,
+    /// Mark generated files with their template and content hash (`--stamp`)
+    stamp: bool,
+    /// Skip the structures a failed feature run completed (`--resume`)
+    resume: bool,
+    /// Variables given for every structure of a feature run (`--var`)
+    feature_variables: std::collections::HashMap<String, String>,
+    /// Write the license's text into generated packages (`--license-file`)
+    license_file: bool,
+    /// List generated files matched by `.gitignore` too (`--show-all`)
+    show_all: bool,
+    /// Recent uses of each template, shown by `describe_template`
+    usage_file: Option<PathBuf>,
+    /// Who generated the files, resolved when first rendering
+    author: OnceLock<Author>,
+    /// Stop runs on Ctrl-C or after `--timeout`
+    cancellation: Cancellation,
 }
 
+#[cfg(feature = "runtime")]
 impl TemplateEngine {
     /// Creates a new TemplateEngine instance.
     ///
@@ -132,10 +366,408 @@ impl TemplateEngine {
     pub fn new(templates_dir: PathBuf, output_dir: PathBuf) -> Result<Self> {
         Ok(Self {
             templates_dir,
+            packs: BTreeMap::new(),
+            output_roots: BTreeMap::new(),
+            include_internal: false,
             output_dir,
+            defaults: TemplateConfig::default(),
+            cache: Arc::new(TemplateCache::new()),
+            fs: Arc::new(TokioFileSystem),
+            profile: None,
+            trace: None,
+            strict_variables: false,
+            conflict_policy: ConflictPolicy::default(),
+            frozen_output: false,
+            environment: None,
+            keep_going: false,
+            all_errors: false,
+            stamp: false,
+            resume: false,
+            feature_variables: Default::default(),
+            license_file: false,
+            show_all: false,
+            usage_file: None,
+            author: OnceLock::new(),
+            cancellation: Cancellation::default(),
         })
     }
 
+    /// Sets the baseline template configuration.
+    ///
+    /// Every template's `.conf` is parsed on top of these defaults, which is how
+    /// global settings from `.cli-frontend.conf` (such as `filename_case`) reach
+    /// templates that don't override them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::{TemplateConfig, TemplateEngine};
+    /// # use cli_frontend::template_engine::naming::CaseStyle;
+    /// # use std::path::PathBuf;
+    /// let defaults = TemplateConfig {
+    ///     filename_case: Some(CaseStyle::Kebab),
+    ///     ..Default::default()
+    /// };
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?
+    ///     .with_defaults(defaults);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_defaults(mut self, defaults: TemplateConfig) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Shares a template cache with other engines.
+    ///
+    /// Each engine has its own cache by default; sharing one lets batch
+    /// generations across output directories reuse parsed `.conf` files and
+    /// compiled templates. Engines with different defaults each get configs
+    /// parsed on top of their own.
+    #[allow(dead_code)] // Public API
+    pub fn with_cache(mut self, cache: Arc<TemplateCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets the backend generated files are written to.
+    ///
+    /// Defaults to [`TokioFileSystem`], which writes to disk. Templates are
+    /// still read from `templates_dir` on the local disk.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::file_system::MemoryFileSystem;
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # use std::sync::Arc;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let fs = Arc::new(MemoryFileSystem::new());
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?
+    ///     .with_file_system(fs.clone());
+    ///
+    /// engine.generate("Button", "component", true, Default::default()).await?;
+    /// assert!(!fs.files().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(dead_code)] // Public API
+    pub fn with_file_system(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Adds named template packs, each a directory of templates.
+    ///
+    /// A pack's templates are available as `pack:template`, and by their
+    /// own name when neither the templates directory nor another pack has
+    /// a template of that name (`[packs]` in the config file).
+    pub fn with_packs(mut self, packs: BTreeMap<String, PathBuf>) -> Self {
+        self.packs = packs;
+        self
+    }
+
+    /// Adds named output roots (`[roots]` in the config file).
+    ///
+    /// An architecture structure with `output_root` generates under the
+    /// root of that name, in the same feature folder it would get under
+    /// the output directory.
+    pub fn with_output_roots(mut self, roots: BTreeMap<String, PathBuf>) -> Self {
+        self.output_roots = roots;
+        self
+    }
+
+    /// Lists templates marked `internal=true` in their `[metadata]` too.
+    ///
+    /// Internal templates are fragments meant to be used by architectures;
+    /// [`list_templates`](Self::list_templates) leaves them out by default.
+    /// Architectures and [`template_dir`](Self::template_dir) find them
+    /// either way.
+    pub fn with_include_internal(mut self, include_internal: bool) -> Self {
+        self.include_internal = include_internal;
+        self
+    }
+
+    /// Directory of the template `template_type`, which may name its pack
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::TemplateNotFound`] for unknown templates and
+    /// [`CliError::AmbiguousTemplate`] when several packs have a template
+    /// asked for without a pack.
+    pub fn template_dir(&self, template_type: &str) -> Result<PathBuf> {
+        validate_template_exists(&self.templates_dir, &self.packs, template_type)
+    }
+
+    /// Sanity-checks the templates directory before generating.
+    ///
+    /// Warns when `templates_dir` is missing, unreadable, empty or looks
+    /// like a project root, and when the config of `template_type`, if
+    /// given, can't be read or parsed. Only the requested template is
+    /// parsed, so the check stays fast; unknown templates are left to the
+    /// generation itself to report.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?;
+    ///
+    /// for warning in engine.startup_warnings(Some("component")) {
+    ///     eprintln!("Warning: {}", warning.message);
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn startup_warnings(&self, template_type: Option<&str>) -> Vec<StartupWarning> {
+        let mut warnings = check_templates_dir(&self.templates_dir);
+        let Some(template_dir) = template_type.and_then(|t| self.template_dir(t).ok()) else {
+            return warnings;
+        };
+
+        let toml_path = template_dir.join(TEMPLATE_TOML_FILE);
+        let conf_path = template_dir.join(TEMPLATE_CONF_FILE);
+        let path = if toml_path.exists() {
+            toml_path
+        } else if conf_path.exists() {
+            conf_path
+        } else {
+            return warnings;
+        };
+        let parsed = std::fs::read_to_string(&path)
+            .context("Could not read it")
+            .and_then(|content| {
+                if path.ends_with(TEMPLATE_TOML_FILE) {
+                    self.parse_template_toml(&content)
+                } else {
+                    self.parse_template_config(&content)
+                }
+            });
+        if let Err(error) = parsed {
+            warnings.push(StartupWarning::new(
+                StartupWarningKind::InvalidConfig,
+                &path,
+                format!("{}: {:#}", path.display(), error),
+            ));
+        }
+        warnings
+    }
+
+    /// The templates `template_type` could refer to, with their descriptions
+    ///
+    /// One candidate when the name resolves; one per pack when several
+    /// packs provide it, to choose from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::TemplateNotFound`] for unknown templates.
+    pub async fn template_candidates(&self, template_type: &str) -> Result<Vec<TemplateCandidate>> {
+        let mut candidates = match self.template_dir(template_type) {
+            Ok(path) => vec![TemplateCandidate {
+                name: template_type.to_string(),
+                path,
+                description: String::new(),
+            }],
+            Err(error) => match error.downcast::<CliError>()? {
+                CliError::AmbiguousTemplate { candidates, .. } => candidates,
+                error => return Err(error.into()),
+            },
+        };
+        for candidate in &mut candidates {
+            // A template whose config doesn't load is still a candidate
+            if let Ok(description) = self.template_description(&candidate.name).await {
+                candidate.description = description.description;
+            }
+        }
+        Ok(candidates)
+    }
+
+    /// Records per-phase timings of generation runs into `profile`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::{RunProfile, TemplateEngine};
+    /// # use std::path::PathBuf;
+    /// # use std::sync::Arc;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let profile = Arc::new(RunProfile::new());
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?
+    ///     .with_profile(profile.clone());
+    ///
+    /// engine.generate("Button", "component", true, Default::default()).await?;
+    /// for (phase, elapsed) in profile.phases() {
+    ///     println!("{}: {:?}", phase, elapsed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_profile(mut self, profile: Arc<RunProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Records the helper calls made while rendering each file into `trace`.
+    ///
+    /// Files render uncached while tracing, since the helpers are wrapped
+    /// per file.
+    pub fn with_trace(mut self, trace: Arc<RenderTrace>) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Fails rendering on undefined variables for every template.
+    ///
+    /// Templates can opt in individually with `strict_variables=true` in
+    /// their `.conf`; this forces strict mode regardless.
+    pub fn with_strict_variables(mut self, strict: bool) -> Self {
+        self.strict_variables = strict;
+        self
+    }
+
+    /// Makes renders reproducible for golden-output comparisons.
+    ///
+    /// Timestamp and UUID variables render empty and `environment` is always
+    /// `development`, whatever the templates' `.conf` or `NODE_ENV` say.
+    pub fn with_frozen_output(mut self, frozen: bool) -> Self {
+        self.frozen_output = frozen;
+        self
+    }
+
+    /// Generates for `environment` instead of `NODE_ENV` or the templates'
+    /// `environment` setting, applying its `[options.ENVIRONMENT]` overrides.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?
+    ///     .with_environment(Some("staging".to_string()));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_environment(mut self, environment: Option<String>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Keeps generating when a file fails to render or write.
+    ///
+    /// By default the first failure rolls the run back: new files and
+    /// folders are removed and replaced files restored. With `keep_going`,
+    /// failing files are skipped, the others are kept, and the run still
+    /// returns an error naming the first failure.
+    pub fn with_keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Reports every file that fails to render instead of only the first.
+    ///
+    /// By default a run stops at the first failing file. With `all_errors`,
+    /// every file is rendered and the run fails with one
+    /// [`CliError::RenderErrors`] listing each failing file and its error.
+    /// Nothing is written either way.
+    pub fn with_all_errors(mut self, all_errors: bool) -> Self {
+        self.all_errors = all_errors;
+        self
+    }
+
+    /// Stops generation runs on Ctrl-C or a timeout.
+    ///
+    /// A cancelled run aborts its outstanding renders and is rolled back
+    /// like a failed one, even with keep-going. See [`cancel`].
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Continues the feature run that failed in the output directory.
+    ///
+    /// Structures recorded as completed by a failed keep-going run (see
+    /// [`resume`]) are skipped instead of generated again.
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Passes variables to every structure of a feature run.
+    ///
+    /// They win over the architecture's `shared` variables and the
+    /// structures' `params`, like `--var` does for a single template.
+    pub fn with_feature_variables(
+        mut self,
+        variables: std::collections::HashMap<String, String>,
+    ) -> Self {
+        self.feature_variables = variables;
+        self
+    }
+
+    /// Lists every generated file, including the ones `.gitignore` matches.
+    ///
+    /// By default the summary after generating leaves out ignored files,
+    /// such as build artifacts, and only says how many were hidden.
+    pub fn with_show_all(mut self, show_all: bool) -> Self {
+        self.show_all = show_all;
+        self
+    }
+
+    /// Reads recent uses of templates from `usage_file`.
+    ///
+    /// `describe_template` shows them as "Recently used like:" examples
+    /// before the synthesized ones.
+    pub fn with_usage_file(mut self, usage_file: Option<PathBuf>) -> Self {
+        self.usage_file = usage_file;
+        self
+    }
+
+    /// Stamps generated files with a comment naming their template.
+    ///
+    /// The stamp records the template's hash and the file's own content
+    /// hash, so `cli-frontend drift` can find files that were edited by
+    /// hand or generated from an older template. See [`stamp`].
+    pub fn with_stamp(mut self, stamp: bool) -> Self {
+        self.stamp = stamp;
+        self
+    }
+
+    /// Writes a [`license::LICENSE_FILE`] into packages generated with
+    /// [`generate_package`](Self::generate_package).
+    ///
+    /// The text is the one bundled for the `license` in the defaults' data,
+    /// with the `author` or `company` variable as the copyright holder.
+    pub fn with_license_file(mut self, license_file: bool) -> Self {
+        self.license_file = license_file;
+        self
+    }
+
+    /// Sets how existing files that would change are handled.
+    ///
+    /// The default overwrites them, as generation always has.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::{ConflictPolicy, TemplateEngine};
+    /// # use std::path::PathBuf;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?
+    ///     .with_conflict_policy(ConflictPolicy::Skip);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    /// Record the time since `start` under `phase` when profiling
+    fn record_phase(&self, phase: &'static str, start: Instant) {
+        if let Some(profile) = &self.profile {
+            profile.record_since(phase, start);
+        }
+    }
+
     /// Checks if a template type exists in the templates directory.
     ///
     /// # Arguments
@@ -158,13 +790,15 @@ impl TemplateEngine {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn template_exists(&self, template_type: &str) -> bool {
-        self.templates_dir.join(template_type).exists()
+        self.template_dir(template_type).is_ok()
     }
 
     /// Lists all available template types.
     ///
     /// Scans the templates directory and returns a sorted vector of template names.
-    /// Hidden directories (starting with '.') are excluded.
+    /// Hidden directories (starting with '.') are excluded, and so are
+    /// internal templates unless [`with_include_internal`](Self::with_include_internal)
+    /// is set. The templates of each pack follow, as `pack:template`.
     ///
     /// # Returns
     ///
@@ -183,24 +817,14 @@ impl TemplateEngine {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn list_templates(&self) -> Result<Vec<String>> {
-        let mut templates = Vec::new();
-
-        if !self.templates_dir.exists() {
-            return Ok(templates);
-        }
-
-        for entry in std::fs::read_dir(&self.templates_dir)? {
-            let entry = entry?;
-            if entry.file_type()?.is_dir() {
-                if let Some(name) = entry.file_name().to_str() {
-                    if !name.starts_with('.') {
-                        templates.push(name.to_string());
-                    }
-                }
-            }
+        let mut templates = list_all_template_names(&self.templates_dir, &self.packs)?;
+        if !self.include_internal {
+            templates.retain(|template| {
+                !self
+                    .template_dir(template)
+                    .is_ok_and(|dir| is_internal_template(&dir))
+            });
         }
-
-        templates.sort();
         Ok(templates)
     }
 
@@ -218,7 +842,7 @@ impl TemplateEngine {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` on success.
+    /// Returns the paths of the files written.
     ///
     /// # Errors
     ///
@@ -254,137 +878,1086 @@ impl TemplateEngine {
         template_type: &str,
         create_folder: bool,
         cli_vars: std::collections::HashMap<String, String>,
-    ) -> Result<()> {
-        let template_dir = validate_template_exists(&self.templates_dir, template_type)?;
+    ) -> Result<Vec<PathBuf>> {
+        let mut journal = WriteJournal::default();
+        let result = self
+            .cancellation
+            .run(self.generate_in(
+                &mut journal,
+                &self.output_dir,
+                name,
+                template_type,
+                create_folder,
+                cli_vars,
+                serde_json::Map::new(),
+            ))
+            .await;
+        self.finish_run(journal, result).await
+    }
+
+    /// Generates a template as a standalone, publishable package.
+    ///
+    /// The package goes in a kebab-case folder of the output directory: the
+    /// `package` template layer renders `package.json`, `tsconfig.json` and
+    /// the build config at its root, and `template_type` renders into `src/`.
+    /// Both layers receive the package name and workspace settings detected
+    /// from the enclosing monorepo as `package` in the template data.
+    ///
+    /// Returns the paths of the files written by both layers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either template is missing or fails to generate.
+    pub async fn generate_package(
+        &self,
+        name: &str,
+        template_type: &str,
+        cli_vars: std::collections::HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let package_dir = self.output_dir.join(naming::to_kebab_case(name).as_ref());
+        let workspace = Workspace::detect(&package_dir);
+        let data = package_data(name, &package_dir, workspace.as_ref());
+
+        match &workspace {
+            Some(workspace) => {
+                ui::status!(
+                    "{} {}",
+                    symbols::PACKAGE.as_str().bold(),
+                    t!(
+                        "generate.packaging_workspace",
+                        name = data["name"].as_str().unwrap_or_default().bold(),
+                        manager = workspace.manager,
+                        root = workspace.root.display()
+                    )
+                );
+                if !workspace.covers(&package_dir) {
+                    ui::warning!(
+                        "Warning: {} is not matched by the workspace packages ({}), add it to use the package from the workspace",
+                        package_dir.display(),
+                        workspace.packages.join(", ")
+                    );
+                }
+            }
+            None => ui::status!(
+                "{} {}",
+                symbols::PACKAGE.as_str().bold(),
+                t!(
+                    "generate.packaging",
+                    name = data["name"].as_str().unwrap_or_default().bold()
+                )
+            ),
+        }
+
+        let license = self
+            .license_file
+            .then(|| self.license_file_for(name, &package_dir))
+            .transpose()?;
+        let extra_data = serde_json::Map::from_iter([("package".to_string(), data)]);
+        let mut journal = WriteJournal::default();
+        let run = async {
+            let mut written = self
+                .generate_in(
+                    &mut journal,
+                    &package_dir,
+                    name,
+                    PACKAGE_TEMPLATE,
+                    false,
+                    cli_vars.clone(),
+                    extra_data.clone(),
+                )
+                .await
+                .context("Failed to generate the package layer")?;
+            written.extend(
+                self.generate_in(
+                    &mut journal,
+                    &package_dir.join("src"),
+                    name,
+                    template_type,
+                    false,
+                    cli_vars,
+                    extra_data,
+                )
+                .await?,
+            );
+            if let Some(license) = license {
+                written.extend(self.write_rendered(&mut journal, vec![license]).await?);
+            }
+            Ok(written)
+        };
+        let result = self.cancellation.run(run).await;
+
+        self.finish_run(journal, result).await
+    }
+
+    /// The license file of the package `name` in `package_dir`
+    fn license_file_for(&self, name: &str, package_dir: &Path) -> Result<RenderedFile> {
+        let data = &self.defaults.data;
+        let value = |key: &str| data.get(key).and_then(|value| value.as_str());
+        let Some(spdx) = value("license").filter(|license| !license.is_empty()) else {
+            anyhow::bail!("--license-file needs a license, set license=<SPDX id> in the config");
+        };
+        // The resolved author, unless there is none
+        let holder = Some(self.author().name.as_str())
+            .filter(|author| !author.is_empty())
+            .or(value("company"))
+            .map_or_else(|| format!("The {} authors", name), str::to_string);
+        let year = chrono::Utc::now().format("%Y").to_string();
+        let Some(content) = license::license_text(spdx, &year, &holder) else {
+            anyhow::bail!(
+                "No bundled text for license '{}' (available: MIT, ISC, 0BSD); add a {} to the package template instead",
+                spdx,
+                license::LICENSE_FILE
+            );
+        };
+        Ok(RenderedFile::Text {
+            path: package_dir.join(license::LICENSE_FILE),
+            content,
+            mode: None,
+            conflict: None,
+        })
+    }
+
+    /// [`generate`](Self::generate) into `output_dir`, with `extra_data`
+    /// added to the template data, recording what it writes in `journal`
+    #[allow(clippy::too_many_arguments)]
+    async fn generate_in(
+        &self,
+        journal: &mut WriteJournal,
+        output_dir: &Path,
+        name: &str,
+        template_type: &str,
+        create_folder: bool,
+        cli_vars: std::collections::HashMap<String, String>,
+        extra_data: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Vec<PathBuf>> {
+        let start = Instant::now();
+        let template_dir = self.template_dir(template_type)?;
         let mut template_config = self.load_template_config(template_type).await?;
+        ensure_cli_version(template_type, &template_config)?;
+        template_config.data.extend(extra_data);
+        let stamp = self.stamp_for(&template_dir, name, &cli_vars)?;
+        let explicit: Vec<String> = cli_vars.keys().cloned().collect();
         merge_variables(cli_vars, &mut template_config);
+        apply_variable_rules(&mut template_config, &explicit)?;
+        ensure_required_variables(&template_config)?;
+        self.ensure_files_to_generate(template_type, &template_config)?;
+        self.record_phase("load template config", start);
 
-        let output_path = prepare_output_directory(&self.output_dir, name, create_folder).await?;
+        let start = Instant::now();
+        let base_dir =
+            resolve_output_subdir(output_dir, template_config.output_subdir.as_deref(), name)?;
+        let output_path = output_directory(
+            &base_dir,
+            name,
+            create_folder && template_config.create_folder,
+            template_config.folder_case,
+        );
+        resolve_path_variables(&mut template_config, &output_path)?;
+        journal.staging.stage_dir(&output_path);
+        self.record_phase("prepare output", start);
 
-        self.process_template_directory(&template_dir, &output_path, name, &template_config)
+        let written = self
+            .process_template_directory(
+                journal,
+                &template_dir,
+                &output_path,
+                name,
+                &template_config,
+                stamp.as_ref(),
+            )
             .await?;
-        self.show_generated_files(&output_path).await?;
+        self.show_generated_files(&output_path, &written);
 
-        Ok(())
+        Ok(written)
     }
 
-    /// Generates a complete feature with a specific architecture pattern.
-    ///
-    /// Creates a full feature structure following an architectural pattern
-    /// (e.g., Clean Architecture, Redux, MVC). Each architecture defines
-    /// a directory structure with specific templates for each layer.
-    ///
-    /// # Arguments
-    ///
-    /// * `name` - The name of the feature (e.g., "Authentication", "PaymentSystem")
-    /// * `architecture` - Optional architecture name. If None, uses default from config
-    /// * `create_folder` - Whether to create a subfolder with the feature name
-    /// * `config` - Application configuration containing architecture definitions
-    ///
-    /// # Returns
+    /// Checks that `vars` leave the template something to generate.
     ///
-    /// Returns `Ok(())` on success.
+    /// Generation runs the same check before creating any folder; calling it
+    /// first lets callers offer the suggested variable changes instead.
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - The architecture configuration doesn't exist
-    /// - Required templates are missing
-    /// - Directory creation fails
-    /// - Template processing fails
+    /// Returns [`CliError::NothingToGenerate`] when the `[files]` conditions
+    /// exclude every file, or an error if the template can't be loaded or
+    /// `vars` break its `[rules]`.
+    pub async fn check_files_to_generate(
+        &self,
+        template_type: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<()> {
+        self.template_dir(template_type)?;
+        let mut template_config = self.load_template_config(template_type).await?;
+        let explicit: Vec<String> = vars.keys().cloned().collect();
+        merge_variables(vars.clone(), &mut template_config);
+        apply_variable_rules(&mut template_config, &explicit)?;
+        self.ensure_files_to_generate(template_type, &template_config)
+    }
+
+    /// Describes what [`generate`](Self::generate) would do, without rendering
+    /// or writing anything.
+    ///
+    /// Runs the same variable merging, `[rules]` and `[files]` checks as
+    /// generation, so its errors are the ones generation would report.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use cli_frontend::template_engine::TemplateEngine;
-    /// # use cli_frontend::config::Config;
     /// # use std::path::PathBuf;
+    /// # use std::collections::HashMap;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// let engine = TemplateEngine::new(
-    ///     PathBuf::from("./templates"),
-    ///     PathBuf::from("./src/features")
-    /// )?;
-    ///
-    /// let config = Config::load(&None).await?;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?;
     ///
-    /// // Generate with Clean Architecture
-    /// engine.generate_feature(
-    ///     "PaymentSystem",
-    ///     Some("clean-architecture"),
-    ///     true,
-    ///     &config
-    /// ).await?;
+    /// let plan = engine.plan_generation("Button", "component", true, &HashMap::new()).await?;
+    /// println!("{} files into {}", plan.file_count, plan.output_path.display());
     /// # Ok(())
     /// # }
     /// ```
+    pub async fn plan_generation(
+        &self,
+        name: &str,
+        template_type: &str,
+        create_folder: bool,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<GenerationPlan> {
+        let template_dir = self.template_dir(template_type)?;
+        let mut template_config = self.load_template_config(template_type).await?;
+        ensure_cli_version(template_type, &template_config)?;
+        let explicit: Vec<String> = vars.keys().cloned().collect();
+        template_config.variables.extend(vars.clone());
+        apply_variable_rules(&mut template_config, &explicit)?;
+        ensure_required_variables(&template_config)?;
+        self.ensure_files_to_generate(template_type, &template_config)?;
+
+        let base_dir = resolve_output_subdir(
+            &self.output_dir,
+            template_config.output_subdir.as_deref(),
+            name,
+        )?;
+        let output_path = output_directory(
+            &base_dir,
+            name,
+            create_folder && template_config.create_folder,
+            template_config.folder_case,
+        );
+        resolve_path_variables(&mut template_config, &output_path)?;
+        let jobs = Self::plan_template_directory(&template_dir, &output_path, &template_config)?;
+
+        Ok(GenerationPlan {
+            output_path,
+            variables: template_config.variables.into_iter().collect(),
+            file_count: jobs.len(),
+        })
+    }
+
+    /// The config and data a template renders `name` with, without
+    /// rendering any file (`cli-frontend repl`).
+    ///
+    /// Runs the variable merging and `[rules]` of generation, and resolves
+    /// path variables against the folder generation would write to.
+    pub async fn template_data(
+        &self,
+        name: &str,
+        template_type: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<(TemplateConfig, serde_json::Value)> {
+        self.template_dir(template_type)?;
+        let mut template_config = self.load_template_config(template_type).await?;
+        let explicit: Vec<String> = vars.keys().cloned().collect();
+        merge_variables(vars.clone(), &mut template_config);
+        apply_variable_rules(&mut template_config, &explicit)?;
+
+        let base_dir = resolve_output_subdir(
+            &self.output_dir,
+            template_config.output_subdir.as_deref(),
+            name,
+        )?;
+        let output_path = output_directory(
+            &base_dir,
+            name,
+            template_config.create_folder,
+            template_config.folder_case,
+        );
+        resolve_path_variables(&mut template_config, &output_path)?;
+        template_config.data.extend(self.author().to_data());
+
+        let data = create_template_data(name, &template_config);
+        Ok((template_config, data))
+    }
+
+    /// Renders a template into memory without touching the output directory.
+    ///
+    /// Runs the same pipeline as [`generate`](Self::generate) with a folder
+    /// named after the item (unless the template sets `create_folder=false`), but returns the files instead of writing them, so
+    /// editors and services can embed the engine as a scaffolding library.
+    /// Paths are relative to the engine's output directory and sorted.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name for the generated code (e.g., "Button", "useAuth")
+    /// * `template_type` - The type of template to use (e.g., "component", "hook")
+    /// * `vars` - Variables overriding the template's defaults
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template doesn't exist, can't be read, or fails
+    /// to render.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # use std::collections::HashMap;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?;
+    ///
+    /// for file in engine.render_to_memory("Button", "component", HashMap::new()).await? {
+    ///     println!("{} ({} bytes)", file.path.display(), file.contents.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn render_to_memory(
+        &self,
+        name: &str,
+        template_type: &str,
+        vars: std::collections::HashMap<String, String>,
+    ) -> Result<Vec<GeneratedFile>> {
+        let template_dir = self.template_dir(template_type)?;
+        let mut template_config = self.load_template_config(template_type).await?;
+        ensure_cli_version(template_type, &template_config)?;
+        let explicit: Vec<String> = vars.keys().cloned().collect();
+        template_config.variables.extend(vars);
+        apply_variable_rules(&mut template_config, &explicit)?;
+        ensure_required_variables(&template_config)?;
+
+        let base_dir = resolve_output_subdir(
+            &self.output_dir,
+            template_config.output_subdir.as_deref(),
+            name,
+        )?;
+        let output_path = output_directory(
+            &base_dir,
+            name,
+            template_config.create_folder,
+            template_config.folder_case,
+        );
+        resolve_path_variables(&mut template_config, &output_path)?;
+
+        let config_arc = Arc::new(Self::resolve_line_endings(&template_config, &output_path));
+        let jobs = Self::plan_template_directory(&template_dir, &output_path, &config_arc)?;
+
+        let mut files = Vec::new();
+        for file in self.render_jobs(jobs, name, config_arc).await? {
+            let (path, contents) = file.into_contents().await?;
+            let path = path
+                .strip_prefix(&self.output_dir)
+                .map(Path::to_path_buf)
+                .unwrap_or(path);
+            files.push(GeneratedFile { path, contents });
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Generates a complete feature with a specific architecture pattern.
+    ///
+    /// Creates a full feature structure following an architectural pattern
+    /// (e.g., Clean Architecture, Redux, MVC). Each architecture defines
+    /// a directory structure with specific templates for each layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the feature (e.g., "Authentication", "PaymentSystem")
+    /// * `architecture` - Optional architecture name. If None, uses default from config
+    /// * `create_folder` - Whether to create a subfolder with the feature name
+    /// * `config` - Application configuration containing architecture definitions
+    ///
+    /// # Returns
+    ///
+    /// Returns the paths of the files written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The architecture configuration doesn't exist
+    /// - Required templates are missing
+    /// - Directory creation fails
+    /// - Template processing fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::config::Config;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(
+    ///     PathBuf::from("./templates"),
+    ///     PathBuf::from("./src/features")
+    /// )?;
+    ///
+    /// let config = Config::load(&None).await?;
+    ///
+    /// // Generate with Clean Architecture
+    /// engine.generate_feature(
+    ///     "PaymentSystem",
+    ///     Some("clean-architecture"),
+    ///     true,
+    ///     &config
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(dead_code)] // Public API
     pub async fn generate_feature(
         &self,
         name: &str,
         architecture: Option<&str>,
         create_folder: bool,
         config: &Config,
-    ) -> Result<()> {
+    ) -> Result<Vec<PathBuf>> {
+        self.generate_features(&[name.to_string()], architecture, create_folder, config)
+            .await
+    }
+
+    /// Generates several features with the same architecture in one run.
+    ///
+    /// The architecture is loaded and validated once, and the features are
+    /// written together: if one fails, none is kept (unless keep-going is
+    /// set). The structure of every feature is shown at the end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name is listed twice or two names generate into
+    /// the same folder, or for any error of
+    /// [`generate_feature`](Self::generate_feature).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::config::Config;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src/features"))?;
+    /// let config = Config::load(&None).await?;
+    ///
+    /// let names = ["Auth", "Billing", "Orders"].map(String::from);
+    /// engine.generate_features(&names, Some("clean-architecture"), true, &config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_features(
+        &self,
+        names: &[String],
+        architecture: Option<&str>,
+        create_folder: bool,
+        config: &Config,
+    ) -> Result<Vec<PathBuf>> {
+        let architecture_name = architecture.unwrap_or(config.default_architecture());
+        let mut progress = FeatureProgress::new(architecture_name);
+        let mut journal = WriteJournal::default();
+        let result = self
+            .cancellation
+            .run(self.generate_features_in(
+                &mut journal,
+                &mut progress,
+                names,
+                architecture,
+                create_folder,
+                config,
+            ))
+            .await;
+        let result = self.finish_run(journal, result).await;
+
+        // Structures of a failed run stay written only with keep-going
+        let fs = self.fs.as_ref();
+        match &result {
+            Ok(_) => FeatureProgress::clear(fs, &self.output_dir).await?,
+            Err(error) if self.keep_going && !progress.is_empty() && !is_cancellation(error) => {
+                progress.save(fs, &self.output_dir).await?;
+                ui::warning!(
+                    "{} Kept {} completed structure(s); run again with --resume to continue",
+                    symbols::HINT.as_str(),
+                    progress.len()
+                );
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// [`generate_features`](Self::generate_features), recording what it
+    /// writes in `journal` and the structures it completes in `progress`
+    async fn generate_features_in(
+        &self,
+        journal: &mut WriteJournal,
+        progress: &mut FeatureProgress,
+        names: &[String],
+        architecture: Option<&str>,
+        create_folder: bool,
+        config: &Config,
+    ) -> Result<Vec<PathBuf>> {
+        let features = self.feature_folders(names, create_folder)?;
+        let arch_config = self.load_feature_architecture(architecture, config).await?;
+
+        if self.resume {
+            match FeatureProgress::load(self.fs.as_ref(), &self.output_dir).await? {
+                None => ui::warning!(
+                    "Warning: No interrupted run to resume in {}, generating everything",
+                    self.output_dir.display()
+                ),
+                Some(previous) if previous.architecture != progress.architecture => {
+                    anyhow::bail!(
+                        "The interrupted run used the '{}' architecture; resume it with --architecture {}",
+                        previous.architecture,
+                        previous.architecture
+                    )
+                }
+                Some(previous) => *progress = previous,
+            }
+        }
+
+        ui::status!(
+            "{} {}",
+            symbols::LAYOUT.as_str().bold(),
+            t!(
+                "generate.using_architecture",
+                name = arch_config.name.bold()
+            )
+        );
+
+        // Plan each pending structure of each feature
+        let start = Instant::now();
+        let label = |name: &str, structure: &ArchitectureStructure| {
+            if features.len() > 1 {
+                format!("{} {}", name, structure.path)
+            } else {
+                structure.path.clone()
+            }
+        };
+        let mut planned = Vec::new();
+        let mut failures: Vec<(String, anyhow::Error)> = Vec::new();
+        for (name, output_path) in &features {
+            journal.staging.stage_dir(output_path);
+            let shared = arch_config.shared_variables(name);
+            for structure in &arch_config.structure {
+                if progress.is_complete(name, structure) {
+                    ui::status!(
+                        "{} {}",
+                        symbols::SKIP.as_str().yellow(),
+                        t!(
+                            "generate.structure_resumed",
+                            path = output_path.join(&structure.path).display()
+                        )
+                    );
+                    continue;
+                }
+                match self
+                    .plan_feature_structure(name, structure, output_path, &shared)
+                    .await
+                {
+                    Ok(plan) => planned.push(plan),
+                    Err(error) => failures.push((label(name, structure), error)),
+                }
+            }
+        }
+        self.record_phase("plan files", start);
+
+        // Render the structures concurrently, then stage them in order so
+        // conflict prompts come one at a time
+        let start = Instant::now();
+        let renders = self.render_structures(&mut planned).await;
+        self.record_phase("render", start);
+
+        let start = Instant::now();
+        let mut written = Vec::new();
+        for (plan, results) in planned.into_iter().zip(renders) {
+            let before = journal.failures();
+            let mut rendered = Vec::with_capacity(results.len());
+            let mut errors = Vec::new();
+            for result in results {
+                match result {
+                    Ok(file) => rendered.push(file),
+                    Err(e) if self.keep_going => journal.record_failure(e),
+                    Err(e) => errors.push(e),
+                }
+            }
+            if !self.all_errors {
+                errors.truncate(1);
+            }
+            if let Some(error) = Self::render_failures(errors) {
+                failures.push((label(plan.feature, plan.structure), error));
+            }
+            // Without keep-going, a failure anywhere means nothing is kept
+            if !failures.is_empty() && !self.keep_going {
+                continue;
+            }
+
+            if !plan.structure.path.is_empty() || plan.structure.output_root.is_some() {
+                journal.staging.stage_dir(&plan.path);
+            }
+            let rendered = match &plan.stamp {
+                Some(stamp) => rendered
+                    .into_iter()
+                    .map(|file| file.stamped(stamp))
+                    .collect(),
+                None => rendered,
+            };
+            let files = self
+                .write_rendered(journal, rendered)
+                .await
+                .with_context(|| {
+                    format!("Failed to generate structure: {}", plan.structure.path)
+                })?;
+            if journal.failures() == before {
+                progress.complete(plan.feature, plan.structure);
+            }
+            written.extend(files);
+        }
+        self.record_phase("write", start);
+        if let Some(error) = Self::structure_failures(failures) {
+            return Err(error);
+        }
+
+        // Re-export the layers' public symbols from each feature root, once
+        // every layer is in place
+        for (name, output_path) in &features {
+            let Some(index) = arch_config.feature_index(name) else {
+                continue;
+            };
+            let complete = arch_config
+                .structure
+                .iter()
+                .all(|structure| progress.is_complete(name, structure));
+            if !complete {
+                continue;
+            }
+            let file = RenderedFile::Text {
+                path: output_path.join(FEATURE_INDEX_FILE),
+                content: index,
+                mode: None,
+                conflict: None,
+            };
+            let files = self
+                .write_rendered(journal, vec![file])
+                .await
+                .context("Failed to write the feature index")?;
+            written.extend(files);
+        }
+
+        // Show generated files
+        self.show_generated_feature_files(&features, &arch_config, &written);
+
+        // Register the features in existing project files
+        for (name, output_path) in &features {
+            for patch in &arch_config.patches {
+                self.apply_architecture_patch(journal, name, patch, output_path)
+                    .await
+                    .with_context(|| format!("Failed to patch {}", patch.file))?;
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Paths of the files [`generate_features`](Self::generate_features)
+    /// would write, sorted, without writing anything (`--tree`).
+    ///
+    /// Output names are resolved in memory, including front matter
+    /// filenames and conditions; files are not rendered.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`generate_features`](Self::generate_features)
+    /// that happen before rendering.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::config::Config;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src/features"))?;
+    /// let config = Config::load(&None).await?;
+    ///
+    /// let names = ["Auth".to_string()];
+    /// for path in engine.plan_features(&names, Some("clean-architecture"), true, &config).await? {
+    ///     println!("{}", path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan_features(
+        &self,
+        names: &[String],
+        architecture: Option<&str>,
+        create_folder: bool,
+        config: &Config,
+    ) -> Result<Vec<PathBuf>> {
+        let features = self.feature_folders(names, create_folder)?;
+        let arch_config = self.load_feature_architecture(architecture, config).await?;
+
+        let mut files = Vec::new();
+        for (name, output_path) in &features {
+            let shared = arch_config.shared_variables(name);
+            for structure in &arch_config.structure {
+                let plan = self
+                    .plan_feature_structure(name, structure, output_path, &shared)
+                    .await?;
+                files.extend(plan.jobs.into_iter().map(|job| job.output_file));
+            }
+            if arch_config.feature_index(name).is_some() {
+                files.push(output_path.join(FEATURE_INDEX_FILE));
+            }
+        }
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Output folder of each feature, rejecting names that share one
+    fn feature_folders<'a>(
+        &self,
+        names: &'a [String],
+        create_folder: bool,
+    ) -> Result<Vec<(&'a str, PathBuf)>> {
+        let mut features: Vec<(&str, PathBuf)> = Vec::with_capacity(names.len());
+        for name in names {
+            let output_path = if create_folder {
+                self.output_dir
+                    .join(naming::folder_name(name, self.defaults.folder_case))
+            } else {
+                self.output_dir.clone()
+            };
+            if let Some((other, _)) = features.iter().find(|(_, path)| *path == output_path) {
+                if other == name {
+                    anyhow::bail!("Feature '{}' is listed more than once", name);
+                }
+                anyhow::bail!(
+                    "Features '{}' and '{}' would both generate into {}",
+                    other,
+                    name,
+                    output_path.display()
+                );
+            }
+            features.push((name, output_path));
+        }
+
+        Ok(features)
+    }
+
+    /// Load an architecture, suggesting a close name when it's unknown
+    async fn load_feature_architecture(
+        &self,
+        architecture: Option<&str>,
+        config: &Config,
+    ) -> Result<ArchitectureConfig> {
         let architecture_name = architecture.unwrap_or(config.default_architecture());
 
+        // Point at the intended architecture before falling back to the default
+        let known = config.list_architectures().unwrap_or_default();
+        let suggestion = if architecture_name == "default"
+            || known.iter().any(|known| known == architecture_name)
+        {
+            None
+        } else {
+            suggest::did_you_mean(architecture_name, known.iter().map(String::as_str))
+        };
+        if let Some(suggestion) = suggestion {
+            ui::warning!(
+                "Warning: Unknown architecture '{}', did you mean '{}'?",
+                architecture_name,
+                suggestion
+            );
+        }
+
         // Load architecture configuration
+        let start = Instant::now();
         let arch_config = config
             .load_architecture(architecture_name)
             .await
-            .with_context(|| format!("Failed to load architecture: {}", architecture_name))?;
+            .with_context(|| CliError::ConfigError {
+                message: match suggestion {
+                    Some(suggestion) => format!(
+                        "Failed to load architecture: {} (did you mean '{}'?)",
+                        architecture_name, suggestion
+                    ),
+                    None => format!("Failed to load architecture: {}", architecture_name),
+                },
+            })?;
+        self.record_phase("load architecture", start);
 
-        println!(
-            "{} Using {} architecture",
-            "📐".bold(),
-            arch_config.name.bold()
-        );
+        // Fail before generating any layer rather than halfway through
+        let missing = arch_config.missing_templates(|template| self.template_exists(template));
+        if !missing.is_empty() {
+            return Err(CliError::ConfigError {
+                message: format!(
+                    "Architecture '{}' uses templates missing from {}: {}",
+                    architecture_name,
+                    self.templates_dir.display(),
+                    missing.join(", ")
+                ),
+            }
+            .into());
+        }
+        Ok(arch_config)
+    }
 
-        // Determine output path
-        let output_path = if create_folder {
-            self.output_dir.join(name)
-        } else {
-            self.output_dir.clone()
-        };
+    /// Displays detailed information about a template.
+    ///
+    /// Shows template metadata, available variables with types and defaults,
+    /// file generation rules, and usage examples. This is useful for exploring
+    /// templates before using them.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_type` - Name of the template to describe
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(
+    ///     PathBuf::from("./templates"),
+    ///     PathBuf::from("./output")
+    /// )?;
+    ///
+    /// // Show detailed information about the component template
+    /// engine.describe_template("component").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn describe_template(&self, template_type: &str) -> Result<()> {
+        let config = self
+            .load_template_config_for_describe(template_type)
+            .await?;
+
+        print_template_header(template_type, &config.metadata, config.engine);
+
+        if !config.options_metadata.is_empty() || !config.variables.is_empty() {
+            ui::show!("{}", t!("inspect.template_variables").bold().green());
+            ui::show!();
+            print_required_variables(&config.options_metadata, &config.variables);
+            print_optional_variables(&config.variables, &config.options_metadata);
+        }
+
+        if !config.file_filters.is_empty() {
+            print_file_filters(&config.file_filters);
+        }
+
+        if !config.rules.is_empty() {
+            print_rules(&config.rules);
+        }
+
+        print_preview(&self.preview_template(template_type, SAMPLE_NAME).await?);
+
+        if let Some(usage_file) = &self.usage_file {
+            print_recent_uses(
+                template_type,
+                UsageHistory::load(usage_file).recent(template_type),
+            );
+        }
+        print_usage_examples(template_type, &config);
+
+        Ok(())
+    }
+
+    /// Renders a template in memory for each combination of its variables.
+    ///
+    /// Boolean and enumerated variables span the matrix, which is capped at
+    /// [`MAX_COMBINATIONS`](matrix::MAX_COMBINATIONS) renders starting from
+    /// the defaults. Each combination records how many files it generates
+    /// and their line count, or why it can't be generated (for instance a
+    /// `[rules]` conflict). Nothing is written to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template doesn't exist or its `.conf` cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("."))?;
+    ///
+    /// for preview in engine.preview_template("component", "Button").await?.combinations {
+    ///     println!("{}: {} files", preview.combination.label(), preview.files);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn preview_template(
+        &self,
+        template_type: &str,
+        sample_name: &str,
+    ) -> Result<TemplatePreview> {
+        let matrix = self
+            .render_matrix(sample_name, template_type, MAX_COMBINATIONS)
+            .await?;
+
+        let combinations = matrix
+            .renders
+            .into_iter()
+            .map(|(combination, files)| match files {
+                Ok(files) => CombinationPreview {
+                    combination,
+                    files: files.len(),
+                    lines: files
+                        .iter()
+                        .filter_map(GeneratedFile::as_text)
+                        .map(|text| text.lines().count())
+                        .sum(),
+                    error: None,
+                },
+                Err(e) => CombinationPreview {
+                    combination,
+                    files: 0,
+                    lines: 0,
+                    error: Some(format!("{:#}", e)),
+                },
+            })
+            .collect();
+
+        Ok(TemplatePreview {
+            sample_name: sample_name.to_string(),
+            total: matrix.total,
+            combinations,
+        })
+    }
+
+    /// Renders a template in memory for the first `limit` combinations of
+    /// its boolean and enumerated variables.
+    ///
+    /// Combinations start from the template defaults (see
+    /// [`VariableMatrix`](matrix::VariableMatrix)). Required variables
+    /// without a default get a placeholder value. A combination that can't
+    /// be generated, for instance because of a `[rules]` conflict, keeps
+    /// its error instead of failing the whole matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template doesn't exist or its `.conf` cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("."))?;
+    ///
+    /// let matrix = engine.render_matrix("Button", "component", 16).await?;
+    /// for (combination, files) in &matrix.renders {
+    ///     println!("{}: {} files", combination.label(), files.as_ref().map_or(0, Vec::len));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn render_matrix(
+        &self,
+        name: &str,
+        template_type: &str,
+        limit: usize,
+    ) -> Result<MatrixRender> {
+        let config = self
+            .load_template_config_for_describe(template_type)
+            .await?;
+        let matrix = VariableMatrix::from_config(&config);
+        let missing = config.missing_required_variables();
 
-        // Create output directory
-        fs::create_dir_all(&output_path).await.with_context(|| {
-            format!(
-                "Could not create output directory: {}",
-                output_path.display()
-            )
-        })?;
+        let mut renders = Vec::new();
+        for combination in matrix.combinations(limit) {
+            let mut vars = combination.vars.clone();
+            for name in &missing {
+                vars.entry(name.to_string())
+                    .or_insert_with(|| SAMPLE_VALUE.to_string());
+            }
 
-        // Generate each structure defined in the architecture
-        for structure in &arch_config.structure {
-            self.generate_feature_structure(name, structure, &output_path)
-                .await
-                .with_context(|| format!("Failed to generate structure: {}", structure.path))?;
+            let files = self.render_to_memory(name, template_type, vars).await;
+            renders.push((combination, files));
         }
 
-        // Show generated files
-        self.show_generated_feature_files(&output_path, &arch_config)
-            .await?;
+        Ok(MatrixRender {
+            total: matrix.len(),
+            renders,
+        })
+    }
 
+    /// Prints a condensed summary of every template.
+    ///
+    /// One entry per template with its title, description, variables (with
+    /// defaults) and file counts, for auditing a whole template pack without
+    /// running [`describe_template`](Self::describe_template) for each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a template's `.conf` cannot be read.
+    pub async fn describe_all_templates(&self) -> Result<()> {
+        let descriptions = self.template_descriptions().await?;
+        print_template_summaries(&descriptions);
         Ok(())
     }
 
-    /// Displays detailed information about a template.
+    /// Prints the condensed summary of the templates matching a search.
     ///
-    /// Shows template metadata, available variables with types and defaults,
-    /// file generation rules, and usage examples. This is useful for exploring
-    /// templates before using them.
+    /// See [`TemplateDescription::matches`] for how `query` and `tag` are
+    /// matched. Nothing is printed when no template matches.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `template_type` - Name of the template to describe
+    /// Returns the number of matching templates.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns `Ok(())` on success.
+    /// Returns an error if a template's `.conf` cannot be read.
+    pub async fn describe_matching_templates(
+        &self,
+        query: &str,
+        tag: Option<&str>,
+    ) -> Result<usize> {
+        let matches: Vec<TemplateDescription> = self
+            .template_descriptions()
+            .await?
+            .into_iter()
+            .filter(|description| description.matches(query, tag))
+            .collect();
+        if !matches.is_empty() {
+            print_template_summaries(&matches);
+        }
+        Ok(matches.len())
+    }
+
+    /// Describes every template as structured data, sorted by name.
     ///
     /// # Errors
     ///
-    /// Returns an error if the template doesn't exist.
+    /// Returns an error if a template's `.conf` cannot be read.
     ///
     /// # Example
     ///
@@ -393,61 +1966,466 @@ impl TemplateEngine {
     /// # use std::path::PathBuf;
     /// # #[tokio::main]
     /// # async fn main() -> anyhow::Result<()> {
-    /// let engine = TemplateEngine::new(
-    ///     PathBuf::from("./templates"),
-    ///     PathBuf::from("./output")
-    /// )?;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    /// for description in engine.template_descriptions().await? {
+    ///     println!("{}: {} variables", description.name, description.variables.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn template_descriptions(&self) -> Result<Vec<TemplateDescription>> {
+        let mut descriptions = Vec::new();
+        for name in self.list_templates()? {
+            descriptions.push(self.template_description(&name).await?);
+        }
+        Ok(descriptions)
+    }
+
+    /// Describes a template as structured data.
     ///
-    /// // Show detailed information about the component template
-    /// engine.describe_template("component").await?;
+    /// The machine-readable counterpart of [`describe_template`](Self::describe_template),
+    /// used by the HTTP and editor integrations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template doesn't exist or its `.conf` cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    /// let description = engine.template_description("component").await?;
+    /// println!("{}", serde_json::to_string_pretty(&description)?);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn describe_template(&self, template_type: &str) -> Result<()> {
+    pub async fn template_description(&self, template_type: &str) -> Result<TemplateDescription> {
         let config = self
             .load_template_config_for_describe(template_type)
             .await?;
+        let files = self.list_template_files(template_type)?;
 
-        print_template_header(template_type, &config.metadata);
+        Ok(TemplateDescription::new(template_type, &config, files))
+    }
 
-        if !config.options_metadata.is_empty() || !config.variables.is_empty() {
-            println!("{}", "Template Variables (use --var):".bold().green());
-            println!();
-            print_required_variables(&config.options_metadata, &config.variables);
-            print_optional_variables(&config.variables, &config.options_metadata);
+    /// Finds the stamped files under `root` and how each drifted.
+    ///
+    /// Files generated with [`with_stamp`](Self::with_stamp) are checked
+    /// for hand edits and for templates that changed since. Hidden
+    /// directories, `node_modules` and `target` aren't scanned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::{Path, PathBuf};
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./src"))?;
+    ///
+    /// for file in engine.drift(Path::new("./src"))? {
+    ///     if file.has_drifted() {
+    ///         println!("{} ({})", file.path.display(), file.stamp.template);
+    ///     }
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn drift(&self, root: &Path) -> Result<Vec<DriftedFile>> {
+        drift::scan(
+            root,
+            &self.templates_dir,
+            &self.packs,
+            self.defaults.walk_limits,
+        )
+    }
+
+    /// Plans regenerating every stamped file under `root` whose template
+    /// changed since it was generated (`cli-frontend upgrade --all`).
+    ///
+    /// Each file is rendered again from the template, name and `--var`
+    /// values in its stamp, keeping its kept regions and re-stamped with the
+    /// current template hash. Files edited by hand are left alone. Nothing is
+    /// written; the outcomes carry the new contents and their diffs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::template_engine::upgrade::UpgradeOutcome;
+    /// # use std::path::{Path, PathBuf};
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("."))?;
+    ///
+    /// for upgrade in engine.plan_upgrade(Path::new(".")).await? {
+    ///     if let UpgradeOutcome::Upgraded { diff, .. } = &upgrade.outcome {
+    ///         print!("{}", diff);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan_upgrade(&self, root: &Path) -> Result<Vec<FileUpgrade>> {
+        type Rendering = std::result::Result<(Vec<GeneratedFile>, Stamp), String>;
+        type RenderKey = (String, String, std::collections::BTreeMap<String, String>);
+        let mut renderings: std::collections::HashMap<RenderKey, Rendering> = Default::default();
+        let mut upgrades = Vec::new();
+
+        for file in drift::scan(
+            root,
+            &self.templates_dir,
+            &self.packs,
+            self.defaults.walk_limits,
+        )? {
+            let outcome = match file.template {
+                TemplateStatus::Missing => UpgradeOutcome::TemplateMissing,
+                TemplateStatus::Current => UpgradeOutcome::Current,
+                TemplateStatus::Outdated if file.modified => UpgradeOutcome::Modified,
+                TemplateStatus::Outdated => {
+                    let stamp = &file.stamp;
+                    let key = (
+                        stamp.template.clone(),
+                        stamp.name.clone(),
+                        stamp.vars.clone(),
+                    );
+                    if !renderings.contains_key(&key) {
+                        let rendering = self.render_for_upgrade(stamp).await;
+                        renderings.insert(key.clone(), rendering.map_err(|e| format!("{:#}", e)));
+                    }
+                    match &renderings[&key] {
+                        Err(error) => UpgradeOutcome::Failed(error.clone()),
+                        Ok((rendered, stamp)) => {
+                            match upgrade::find_rendered(&file.path, rendered) {
+                                None => UpgradeOutcome::NotGenerated,
+                                Some(generated) => match generated.as_text() {
+                                    None => UpgradeOutcome::NotGenerated,
+                                    Some(text) => {
+                                        let path = root.join(&file.path);
+                                        let existing = tokio::fs::read_to_string(&path)
+                                            .await
+                                            .with_context(|| {
+                                                format!("Could not read file: {}", path.display())
+                                            })?;
+                                        upgrade::upgrade_file(&file.path, &existing, text, stamp)
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            };
+            upgrades.push(FileUpgrade {
+                package: upgrade::package_of(root, &file.path),
+                path: file.path,
+                template: file.stamp.template,
+                outcome,
+            });
         }
 
-        if !config.file_filters.is_empty() {
-            print_file_filters(&config.file_filters);
+        Ok(upgrades)
+    }
+
+    /// Files the template in `stamp` generates now, with the stamp to put
+    /// on them
+    async fn render_for_upgrade(&self, stamp: &Stamp) -> Result<(Vec<GeneratedFile>, Stamp)> {
+        let template_dir = self.template_dir(&stamp.template)?;
+        let current = Stamp::new(
+            &stamp.template,
+            &stamp.name,
+            &template_hash(&template_dir, self.defaults.walk_limits)?,
+        )
+        .with_vars(stamp.vars.clone());
+        let vars = stamp.vars.clone().into_iter().collect();
+        let rendered = self
+            .render_to_memory(&stamp.name, &stamp.template, vars)
+            .await?;
+        Ok((rendered, current))
+    }
+
+    /// Lints a template's variable declarations, usages and file filters.
+    ///
+    /// Reports variables declared in `.conf` but never used, variables used
+    /// but never declared, and `[files]` entries for missing files or with
+    /// invalid conditions. Binary and `[raw]` files are not scanned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    ///
+    /// for finding in engine.lint_template("component").await? {
+    ///     println!("{}: {}", finding.location, finding.message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lint_template(&self, template_type: &str) -> Result<Vec<LintFinding>> {
+        let template_dir = self.template_dir(template_type)?;
+        let config = self.load_template_config(template_type).await?;
+
+        let mut files = Vec::new();
+        for path in self.list_template_files(template_type)? {
+            let full_path = template_dir.join(&path);
+            let content = if config.is_raw_file(&path) || is_binary_file(&full_path).await? {
+                None
+            } else {
+                Some(
+                    tokio::fs::read_to_string(&full_path)
+                        .await
+                        .with_context(|| format!("Could not read {}", full_path.display()))?,
+                )
+            };
+            files.push((path, content));
         }
 
-        print_usage_examples(template_type, &config);
+        Ok(lint::lint_template(&config, &files))
+    }
 
-        Ok(())
+    /// Converts a template's `.conf` into `template.toml` content.
+    ///
+    /// Settings equal to the engine defaults are left out, and comments in
+    /// the `.conf` are not carried over. Writing the result is up to the
+    /// caller; once `template.toml` exists the `.conf` is no longer read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    ///
+    /// let toml = engine.migrate_conf("component").await?;
+    /// std::fs::write("./templates/component/template.toml", toml)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn migrate_conf(&self, template_type: &str) -> Result<String> {
+        let template_dir = self.template_dir(template_type)?;
+        let conf_path = template_dir.join(TEMPLATE_CONF_FILE);
+        if !conf_path.exists() {
+            anyhow::bail!("Template '{}' has no .conf to migrate", template_type);
+        }
+
+        let content = tokio::fs::read_to_string(&conf_path)
+            .await
+            .with_context(|| format!("Could not read {}", conf_path.display()))?;
+        let config = self.parse_template_config(&content)?;
+        toml_config::to_template_toml(&config, &self.defaults)
+    }
+
+    /// Builds a Markdown catalog of every template and architecture.
+    ///
+    /// The catalog lists each template's metadata, variables, generated files
+    /// and example invocations, followed by the structure of every architecture.
+    /// It is what `cli-frontend docs` writes to `TEMPLATES.md`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Application configuration used to locate architectures
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a template config or architecture file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::config::Config;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let config = Config::load(&None).await?;
+    /// let engine = TemplateEngine::new(config.templates_dir().clone(), PathBuf::from("."))?;
+    ///
+    /// let markdown = engine.generate_docs(&config).await?;
+    /// std::fs::write("TEMPLATES.md", markdown)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_docs(&self, config: &Config) -> Result<String> {
+        let mut templates = Vec::new();
+        for name in self.list_templates()? {
+            let template_config = self.load_template_config(&name).await?;
+            let files = self.list_template_files(&name)?;
+            templates.push(TemplateDoc {
+                name,
+                config: template_config,
+                files,
+            });
+        }
+
+        let mut architectures = Vec::new();
+        for name in config.list_architectures()? {
+            let arch_config = config
+                .load_architecture(&name)
+                .await
+                .with_context(|| format!("Failed to load architecture: {}", name))?;
+            architectures.push((name, arch_config));
+        }
+
+        Ok(render_catalog(&templates, &architectures))
+    }
+
+    /// Describes every template and architecture as one serializable document.
+    ///
+    /// The structured counterpart of [`generate_docs`](Self::generate_docs),
+    /// written by `cli-frontend export-manifest` for tools that need the pack
+    /// without parsing `.conf` files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a template config or architecture file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use cli_frontend::config::Config;
+    /// # use std::path::PathBuf;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let config = Config::load(&None).await?;
+    /// let engine = TemplateEngine::new(config.templates_dir().clone(), PathBuf::from("."))?;
+    ///
+    /// let manifest = engine.pack_manifest(&config).await?;
+    /// println!("{}", serde_json::to_string_pretty(&manifest)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pack_manifest(&self, config: &Config) -> Result<PackManifest> {
+        let mut architectures = Vec::new();
+        for name in config.list_architectures()? {
+            let arch_config = config
+                .load_architecture(&name)
+                .await
+                .with_context(|| format!("Failed to load architecture: {}", name))?;
+            architectures.push(ArchitectureDescription::new(&name, arch_config));
+        }
+
+        // Internal templates too, as the pack's architectures may use them
+        let mut templates = Vec::new();
+        for name in list_all_template_names(&self.templates_dir, &self.packs)? {
+            templates.push(self.template_description(&name).await?);
+        }
+
+        Ok(PackManifest::new(templates, architectures))
     }
 
     // ============ Private Methods ============
 
-    /// Load template configuration from .conf file if exists
-    async fn load_template_config(&self, template_type: &str) -> Result<TemplateConfig> {
-        let config_path = self.templates_dir.join(template_type).join(".conf");
+    /// List template files relative to the template directory (excluding
+    /// `.conf` and `template.toml`)
+    fn list_template_files(&self, template_type: &str) -> Result<Vec<String>> {
+        let template_dir = self.template_dir(template_type)?;
+        let mut files = Vec::new();
 
-        if !config_path.exists() {
-            return Ok(TemplateConfig::default());
+        for entry in walk_template_dir(&template_dir, self.defaults.walk_limits) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative_path = entry
+                .path()
+                .strip_prefix(&template_dir)
+                .context("Could not get relative path")?;
+            let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+            if !is_template_config_file(&relative_path) {
+                files.push(relative_path);
+            }
         }
 
-        let content = fs::read_to_string(&config_path).await.with_context(|| {
-            format!("Could not read template config: {}", config_path.display())
-        })?;
+        files.sort();
+        Ok(files)
+    }
 
-        let config = self.parse_template_config(&content)?;
+    /// Fail unless the `[files]` and front matter conditions leave a file to generate
+    fn ensure_files_to_generate(
+        &self,
+        template_type: &str,
+        template_config: &TemplateConfig,
+    ) -> Result<()> {
+        let template_dir = self.template_dir(template_type)?;
+        let files = self.list_template_files(template_type)?;
+        let mut config = template_config.clone();
+        for file in files
+            .iter()
+            .filter(|file| !template_config.is_raw_file(file))
+        {
+            if let Some(condition) =
+                FrontMatter::read(&template_dir.join(file))?.and_then(|front| front.condition)
+            {
+                config.file_filters.insert(file.clone(), condition);
+            }
+        }
+        ensure_files_to_generate(template_type, &files, &config)
+    }
+
+    /// Load template configuration from `template.toml`, or else `.conf`, if either exists
+    async fn load_template_config(&self, template_type: &str) -> Result<TemplateConfig> {
+        let template_dir = self.template_dir(template_type)?;
+        let toml_path = template_dir.join(TEMPLATE_TOML_FILE);
+        let conf_path = template_dir.join(TEMPLATE_CONF_FILE);
+
+        let mut config = if toml_path.exists() {
+            self.cache
+                .load_config(&toml_path, &self.defaults, |content| {
+                    self.parse_template_toml(content)
+                })
+                .await
+                .with_context(|| format!("Invalid template config: {}", toml_path.display()))?
+        } else if conf_path.exists() {
+            self.cache
+                .load_config(&conf_path, &self.defaults, |content| {
+                    self.parse_template_config(content)
+                })
+                .await?
+        } else {
+            self.defaults.clone()
+        };
 
+        // Namespaced templates (`rn/component`) always target their framework
+        if template_type.contains('/') {
+            if let Some(framework) = template_dir.parent().and_then(Framework::of_namespace) {
+                config.framework = framework;
+            }
+        }
+        if self.frozen_output {
+            config.enable_timestamps = false;
+            config.enable_uuid = false;
+            config.environment = "development".to_string();
+        }
+        if let Some(environment) = &self.environment {
+            config.environment = environment.clone();
+        }
+        config.apply_environment_overrides();
         Ok(config)
     }
 
-    /// Parse template configuration from INI-like format with sections
-    fn parse_template_config(&self, content: &str) -> Result<TemplateConfig> {
-        let mut config = TemplateConfig::default();
+    /// Parses `.conf` content (INI-like format with sections) on top of the engine defaults
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    ///
+    /// let config = engine.parse_template_config("[options]\nstyle=scss\n")?;
+    /// assert_eq!(config.variables["style"], "scss");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[allow(dead_code)] // Public API
+    pub fn parse_template_config(&self, content: &str) -> Result<TemplateConfig> {
+        let mut config = self.defaults.clone();
         let mut current_section = String::new();
 
         for line in content.lines() {
@@ -462,6 +2440,20 @@ impl TemplateEngine {
                 continue;
             }
 
+            // `[raw]` entries may be bare paths
+            if current_section == "raw" && !line.contains('=') {
+                config.raw_files.push(line.replace('\\', "/"));
+                continue;
+            }
+
+            if current_section == "rules" {
+                match line.parse() {
+                    Ok(rule) => config.rules.push(rule),
+                    Err(e) => ui::warning!("Warning: Ignoring rule '{}': {}", line, e),
+                }
+                continue;
+            }
+
             if let Some((key, value)) = line.split_once('=') {
                 let key = key.trim();
                 let value = value.split('#').next().unwrap_or(value);
@@ -470,11 +2462,36 @@ impl TemplateEngine {
                 match current_section.as_str() {
                     "metadata" => Self::parse_metadata_section(&mut config, key, value),
                     "options" => Self::parse_options_section(&mut config, key, value),
+                    "examples" => Self::parse_examples_section(&mut config, key, value),
+                    "raw" => {
+                        if value != "false" {
+                            config.raw_files.push(key.replace('\\', "/"));
+                        }
+                    }
                     "files" => {
+                        // Normalize path separators so filters match on every platform
                         config
                             .file_filters
+                            .insert(key.replace('\\', "/"), value.to_string());
+                    }
+                    section if section.starts_with("options.") => {
+                        let environment = section["options.".len()..].trim();
+                        config
+                            .environment_overrides
+                            .entry(environment.to_string())
+                            .or_default()
                             .insert(key.to_string(), value.to_string());
                     }
+                    "permissions" => match u32::from_str_radix(value, 8) {
+                        Ok(mode) if mode <= 0o7777 => {
+                            config.permissions.insert(key.replace('\\', "/"), mode);
+                        }
+                        _ => ui::warning!(
+                            "Warning: Ignoring permissions for '{}': '{}' is not an octal mode",
+                            key,
+                            value
+                        ),
+                    },
                     _ => Self::parse_root_config(&mut config, key, value),
                 }
             }
@@ -483,6 +2500,25 @@ impl TemplateEngine {
         Ok(config)
     }
 
+    /// Parses `template.toml` content on top of the engine defaults
+    ///
+    /// See [`toml_config`] for the format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cli_frontend::template_engine::TemplateEngine;
+    /// # use std::path::PathBuf;
+    /// let engine = TemplateEngine::new(PathBuf::from("./templates"), PathBuf::from("./output"))?;
+    ///
+    /// let config = engine.parse_template_toml("[options]\nstyle = \"scss\"\n")?;
+    /// assert_eq!(config.variables["style"], "scss");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse_template_toml(&self, content: &str) -> Result<TemplateConfig> {
+        toml_config::parse_template_toml(content, self.defaults.clone())
+    }
+
     /// Parse options section of template config
     fn parse_options_section(config: &mut TemplateConfig, key: &str, value: &str) {
         if let Some(var_name) = key.strip_suffix("_options") {
@@ -509,16 +2545,85 @@ impl TemplateEngine {
                 .entry(var_name.to_string())
                 .or_default()
                 .description = value.to_string();
+        } else if let Some(var_name) = key.strip_suffix("_group") {
+            config
+                .options_metadata
+                .entry(var_name.to_string())
+                .or_default()
+                .group = value.to_string();
+        } else if let Some(var_name) = key.strip_suffix("_prompt") {
+            config
+                .options_metadata
+                .entry(var_name.to_string())
+                .or_default()
+                .prompt = value.to_string();
+        } else if let Some(var_name) = key.strip_suffix("_required") {
+            config
+                .options_metadata
+                .entry(var_name.to_string())
+                .or_default()
+                .required = value.parse().unwrap_or(true);
         } else {
             config.variables.insert(key.to_string(), value.to_string());
         }
     }
 
+    /// Parse examples section of template config (`Name=command`, `Name.files=a, b`)
+    fn parse_examples_section(config: &mut TemplateConfig, key: &str, value: &str) {
+        let (name, is_files) = match key.strip_suffix(".files") {
+            Some(name) => (name.trim(), true),
+            None => (key, false),
+        };
+
+        let index = match config.examples.iter().position(|e| e.name == name) {
+            Some(index) => index,
+            None => {
+                config.examples.push(TemplateExample {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                config.examples.len() - 1
+            }
+        };
+
+        let example = &mut config.examples[index];
+        if is_files {
+            example.files = value
+                .split(',')
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect();
+        } else {
+            example.command = value.to_string();
+        }
+    }
+
     /// Parse metadata section of template config
     fn parse_metadata_section(config: &mut TemplateConfig, key: &str, value: &str) {
         match key {
             "name" => config.metadata.name = value.to_string(),
             "description" => config.metadata.description = value.to_string(),
+            "internal" => config.metadata.internal = value.parse().unwrap_or(false),
+            "tags" => {
+                config.metadata.tags.clear();
+                for tag in value.split(',').map(|t| t.trim().to_lowercase()) {
+                    if !tag.is_empty() && !config.metadata.tags.contains(&tag) {
+                        config.metadata.tags.push(tag);
+                    }
+                }
+            }
+            // `requires_cli>=1.4` splits at its `=` into `requires_cli>` and `1.4`
+            "requires_cli" | "requires_cli>" => {
+                let version = value.trim_start_matches(">=").trim();
+                if config::version_at_least(version, version).is_some() {
+                    config.metadata.requires_cli = Some(version.to_string());
+                } else {
+                    ui::warning!(
+                        "Warning: Ignoring requires_cli: '{}' is not a version",
+                        value
+                    );
+                }
+            }
             _ => {}
         }
     }
@@ -529,6 +2634,40 @@ impl TemplateEngine {
             "environment" => config.environment = value.to_string(),
             "enable_timestamps" => config.enable_timestamps = value.parse().unwrap_or(true),
             "enable_uuid" => config.enable_uuid = value.parse().unwrap_or(true),
+            "filename_case" => config.filename_case = Self::parse_case_setting(key, value),
+            "folder_case" => config.folder_case = Self::parse_case_setting(key, value),
+            "editorconfig" => config.editorconfig = value.parse().unwrap_or(true),
+            "file_header" => {
+                config.file_header = Some(crate::config::header_lines(value))
+                    .filter(|header| !header.is_empty() && header != "false")
+            }
+            "create_folder" => config.create_folder = value.parse().unwrap_or(true),
+            "strict_variables" => config.strict_variables = value.parse().unwrap_or(true),
+            "max_template_size" => match config::parse_byte_size(value) {
+                Some(size) => config.max_template_size = size,
+                None => ui::warning!(
+                    "Warning: Invalid max_template_size '{}', expected a size like 25MB",
+                    value
+                ),
+            },
+            "line_endings" => config.line_endings = Self::parse_line_endings_setting(value),
+            "engine" => match RenderEngine::parse(value) {
+                Some(engine) => config.engine = engine,
+                None => ui::warning!(
+                    "Warning: Unknown engine '{}', expected handlebars or minijinja",
+                    value
+                ),
+            },
+            "framework" => match Framework::parse(value) {
+                Some(framework) => config.framework = framework,
+                None => ui::warning!(
+                    "Warning: Unknown framework '{}', expected react or rn",
+                    value
+                ),
+            },
+            "output_subdir" => {
+                config.output_subdir = Some(value.to_string()).filter(|v| !v.is_empty())
+            }
             _ => {
                 if let Some(var_name) = key.strip_prefix("var_") {
                     config
@@ -539,42 +2678,54 @@ impl TemplateEngine {
         }
     }
 
+    /// Parse a case policy setting, warning about unknown values
+    fn parse_case_setting(key: &str, value: &str) -> Option<CaseStyle> {
+        let style = CaseStyle::parse(value);
+        if style.is_none() {
+            ui::warning!(
+                "Warning: Unknown {} '{}', expected pascal, camel, kebab, snake or route",
+                key,
+                value
+            );
+        }
+        style
+    }
+
+    /// Parse a line ending setting, warning about unknown values
+    fn parse_line_endings_setting(value: &str) -> Option<LineEndings> {
+        let endings = LineEndings::parse(value);
+        if endings.is_none() {
+            ui::warning!(
+                "Warning: Unknown line_endings '{}', expected lf, crlf or auto",
+                value
+            );
+        }
+        endings
+    }
+
     /// Load template configuration for describe command
     async fn load_template_config_for_describe(
         &self,
         template_type: &str,
     ) -> Result<TemplateConfig> {
-        if !self.template_exists(template_type) {
-            anyhow::bail!(
-                "Template '{}' not found.\n\nRun {} to see available templates.",
-                template_type.red(),
-                "cli-frontend --list".cyan()
-            );
-        }
+        self.template_dir(template_type)?;
         self.load_template_config(template_type).await
     }
 
-    /// Process template directory for standard generation
-    async fn process_template_directory(
-        &self,
+    /// Collect the template files to generate, honoring `[files]` filters
+    /// and each file's front matter
+    fn plan_template_directory(
         template_dir: &Path,
         output_path: &Path,
-        name: &str,
         template_config: &TemplateConfig,
-    ) -> Result<()> {
-        let mut tasks = Vec::new();
-        let config_arc = Arc::new(template_config.clone());
+    ) -> Result<Vec<FileJob>> {
+        let mut jobs = Vec::new();
 
         // Walk through all files in template directory
-        for entry in WalkDir::new(template_dir) {
-            let entry = entry.context("Error walking template directory")?;
+        for entry in walk_template_dir(template_dir, template_config.walk_limits) {
+            let entry = entry?;
 
             if entry.file_type().is_file() {
-                // Skip .conf files
-                if entry.file_name() == ".conf" {
-                    continue;
-                }
-
                 let relative_path = entry
                     .path()
                     .strip_prefix(template_dir)
@@ -583,155 +2734,820 @@ impl TemplateEngine {
                 // Get the filename as a string for filter matching
                 let filename = relative_path.to_str().unwrap_or("").replace('\\', "/"); // Normalize path separators
 
-                // Check if this file should be generated based on filters
-                let should_generate = if !config_arc.file_filters.is_empty() {
-                    // If file_filters exist, check if there's a condition for this file
-                    if let Some(condition) = config_arc.file_filters.get(&filename) {
-                        evaluate_file_condition(condition, &config_arc.variables)
-                    } else {
-                        // No explicit filter for this file, default to true
-                        true
-                    }
+                // Skip .conf and template.toml files
+                if is_template_config_file(&filename) {
+                    continue;
+                }
+
+                let raw = template_config.is_raw_file(&filename);
+                let front = if raw {
+                    FrontMatter::default()
                 } else {
-                    // No file_filters defined, generate all files
-                    true
+                    FrontMatter::read(entry.path())?.unwrap_or_default()
+                };
+
+                // Check if this file should be generated: its front matter
+                // condition wins over its `[files]` entry, and files without
+                // either are always generated
+                let condition = front
+                    .condition
+                    .as_ref()
+                    .or_else(|| template_config.file_filters.get(&filename));
+                if let Some(condition) = condition {
+                    if !evaluate_file_condition(condition, &template_config.variables) {
+                        continue;
+                    }
+                }
+
+                let mode = output_mode(entry.path(), &filename, template_config);
+                let output_name = match &front.filename {
+                    Some(name) => match filename.rsplit_once('/') {
+                        Some((dir, _)) => format!("{}/{}", dir, name),
+                        None => name.clone(),
+                    },
+                    None => filename.clone(),
                 };
 
-                if !should_generate {
+                // `$FILE_NAME.$PLATFORM.tsx` becomes one file per native platform
+                if output_name.contains(PLATFORM_PLACEHOLDER) {
+                    for platform in NATIVE_PLATFORMS {
+                        jobs.push(FileJob {
+                            template_file: entry.path().to_path_buf(),
+                            output_file: output_path
+                                .join(output_name.replace(PLATFORM_PLACEHOLDER, platform)),
+                            raw,
+                            mode,
+                            conflict: front.on_conflict,
+                        });
+                    }
                     continue;
                 }
 
-                let template_file = entry.path().to_path_buf();
-                let output_file = output_path.join(relative_path);
-
-                // Process file asynchronously - use Arc::clone for cheap reference counting
-                let name_clone = name.to_string();
-                let config_ref = Arc::clone(&config_arc);
-                let task = tokio::spawn(async move {
-                    Self::process_template_file_with_config(
-                        &template_file,
-                        &output_file,
-                        &name_clone,
-                        &config_ref,
-                    )
-                    .await
+                jobs.push(FileJob {
+                    template_file: entry.path().to_path_buf(),
+                    output_file: output_path.join(output_name),
+                    raw,
+                    mode,
+                    conflict: front.on_conflict,
                 });
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Render every planned file concurrently
+    ///
+    /// Fails with the first failing file, or with every failing file
+    /// when reporting all errors.
+    async fn render_jobs(
+        &self,
+        jobs: Vec<FileJob>,
+        name: &str,
+        template_config: Arc<TemplateConfig>,
+    ) -> Result<Vec<RenderedFile>> {
+        if !self.all_errors {
+            // Returning early drops the tasks, aborting the files still rendering
+            let mut tasks = self.spawn_renders(jobs, name, template_config, None);
+            let mut rendered = Vec::with_capacity(tasks.0.len());
+            for task in tasks.0.iter_mut() {
+                rendered.push(task.await??);
+            }
+            return Ok(rendered);
+        }
 
-                tasks.push(task);
+        let results = self.render_each(jobs, name, template_config).await;
+        let mut rendered = Vec::with_capacity(results.len());
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                Ok(file) => rendered.push(file),
+                Err(error) => errors.push(error),
             }
         }
+        match Self::render_failures(errors) {
+            Some(error) => Err(error),
+            None => Ok(rendered),
+        }
+    }
 
-        // Wait for all files to be processed
-        for task in tasks {
-            task.await??;
+    /// One error for the files that failed to render: a single failure as
+    /// is, several grouped into a [`CliError::RenderErrors`]
+    fn render_failures(mut errors: Vec<anyhow::Error>) -> Option<anyhow::Error> {
+        if errors.len() > 1 {
+            let errors = errors.iter().map(|error| format!("{:#}", error)).collect();
+            return Some(CliError::RenderErrors { errors }.into());
         }
+        errors.pop()
+    }
 
-        Ok(())
+    /// Render planned files concurrently, keeping each file's own result
+    async fn render_each(
+        &self,
+        jobs: Vec<FileJob>,
+        name: &str,
+        template_config: Arc<TemplateConfig>,
+    ) -> Vec<Result<RenderedFile>> {
+        Self::join_renders(self.spawn_renders(jobs, name, template_config, None)).await
+    }
+
+    /// The author of the files, from the configured `author_name` and
+    /// `author_email` or else git and the OS user, resolved once
+    fn author(&self) -> &Author {
+        self.author.get_or_init(|| {
+            let data = &self.defaults.data;
+            let value = |key: &str| data.get(key).and_then(|value| value.as_str());
+            Author::resolve(
+                value("author_name"),
+                value("author_email"),
+                &self.output_dir,
+            )
+        })
+    }
+
+    /// Start rendering each planned file; each task holds `slot`, if given,
+    /// until its file is rendered
+    fn spawn_renders(
+        &self,
+        jobs: Vec<FileJob>,
+        name: &str,
+        template_config: Arc<TemplateConfig>,
+        slot: Option<Arc<OwnedSemaphorePermit>>,
+    ) -> RenderTasks {
+        let mut config = (*template_config).clone();
+        config.strict_variables |= self.strict_variables;
+        config.data.extend(self.author().to_data());
+        let template_config = Arc::new(config);
+
+        let mut tasks = Vec::new();
+
+        for job in jobs {
+            // Process file asynchronously - use Arc::clone for cheap reference counting
+            let name_clone = name.to_string();
+            let config_ref = Arc::clone(&template_config);
+            let cache = Arc::clone(&self.cache);
+            let trace = self.trace.clone();
+            let slot = slot.clone();
+            let task = ui::spawn(async move {
+                let _slot = slot;
+                Self::render_template_file(&job, &name_clone, &config_ref, &cache, trace.as_deref())
+                    .await
+            });
+
+            tasks.push(task);
+        }
+
+        RenderTasks(tasks)
+    }
+
+    /// Wait for all files to be processed
+    async fn join_renders(mut tasks: RenderTasks) -> Vec<Result<RenderedFile>> {
+        let mut rendered = Vec::with_capacity(tasks.0.len());
+        for task in tasks.0.iter_mut() {
+            rendered.push(task.await.map_err(anyhow::Error::from).and_then(|r| r));
+        }
+
+        rendered
     }
 
-    /// Process a single template file with configuration
-    async fn process_template_file_with_config(
-        template_file: &Path,
-        output_file: &Path,
+    /// Process template directory for standard generation
+    async fn process_template_directory(
+        &self,
+        journal: &mut WriteJournal,
+        template_dir: &Path,
+        output_path: &Path,
         name: &str,
         template_config: &TemplateConfig,
-    ) -> Result<()> {
-        let template_content = read_template(template_file).await?;
-        let handlebars = create_handlebars();
-        let data = create_template_data(name, template_config);
+        stamp: Option<&Stamp>,
+    ) -> Result<Vec<PathBuf>> {
+        let start = Instant::now();
+        let config_arc = Arc::new(Self::resolve_line_endings(template_config, output_path));
+        let jobs = Self::plan_template_directory(template_dir, output_path, &config_arc)?;
+        self.record_phase("plan files", start);
 
-        let processed_names = process_smart_names(name);
-        let processed_content = apply_smart_replacements(&template_content, name, &processed_names);
+        self.render_and_write(journal, jobs, name, config_arc, stamp)
+            .await
+    }
+
+    /// Name of the template in `template_dir`, qualified with its pack if
+    /// it comes from one
+    fn template_name(&self, template_dir: &Path) -> String {
+        let relative = |root: &Path| {
+            template_dir
+                .strip_prefix(root)
+                .ok()
+                .map(|path| path.to_string_lossy().replace('\\', "/"))
+        };
+        relative(&self.templates_dir)
+            .or_else(|| {
+                self.packs.iter().find_map(|(pack, dir)| {
+                    relative(dir).map(|name| format!("{}{}{}", pack, PACK_SEPARATOR, name))
+                })
+            })
+            .unwrap_or_else(|| template_dir.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// Stamp for files generated from `template_dir` for `name`, if stamping
+    fn stamp_for(
+        &self,
+        template_dir: &Path,
+        name: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<Option<Stamp>> {
+        if !self.stamp {
+            return Ok(None);
+        }
+        let template = self.template_name(template_dir);
+        let stamp = Stamp::new(
+            &template,
+            name,
+            &template_hash(template_dir, self.defaults.walk_limits)?,
+        )
+        .with_vars(vars.clone());
+        Ok(Some(stamp))
+    }
+
+    /// Render planned files and write them, timing each phase
+    ///
+    /// With `keep_going`, files that fail to render are left out and
+    /// recorded in `journal` instead of failing the run. Text files are
+    /// stamped with `stamp`, if given.
+    async fn render_and_write(
+        &self,
+        journal: &mut WriteJournal,
+        jobs: Vec<FileJob>,
+        name: &str,
+        template_config: Arc<TemplateConfig>,
+        stamp: Option<&Stamp>,
+    ) -> Result<Vec<PathBuf>> {
+        let start = Instant::now();
+        let rendered = if self.keep_going {
+            let mut rendered = Vec::new();
+            for result in self.render_each(jobs, name, template_config).await {
+                match result {
+                    Ok(file) => rendered.push(file),
+                    Err(error) => journal.record_failure(error),
+                }
+            }
+            rendered
+        } else {
+            self.render_jobs(jobs, name, template_config).await?
+        };
+        let rendered = match stamp {
+            Some(stamp) => rendered
+                .into_iter()
+                .map(|file| file.stamped(stamp))
+                .collect(),
+            None => rendered,
+        };
+        self.record_phase("render", start);
+
+        let start = Instant::now();
+        let written = self.write_rendered(journal, rendered).await?;
+        self.record_phase("write", start);
+
+        Ok(written)
+    }
+
+    /// Stage rendered files for the engine's file system, returning their paths
+    ///
+    /// Unless the conflict policy is to overwrite, existing files are compared
+    /// first: unchanged ones are left alone and differing ones are kept,
+    /// replaced or merged as the policy decides. A file's front matter
+    /// policy overrides the run's. Kept files are not returned.
+    /// Nothing is written until the run commits `journal`.
+    async fn write_rendered(
+        &self,
+        journal: &mut WriteJournal,
+        files: Vec<RenderedFile>,
+    ) -> Result<Vec<PathBuf>> {
+        let merging = files
+            .iter()
+            .any(|file| self.policy_for(file) == ConflictPolicy::Merge);
+        let manifest_path = self.output_dir.join(MANIFEST_FILE);
+        let previous_manifest = if merging {
+            self.fs.read_existing(&manifest_path).await?
+        } else {
+            None
+        };
+        let mut manifest = merging.then(|| {
+            previous_manifest
+                .as_deref()
+                .map(GenerationManifest::parse)
+                .unwrap_or_default()
+        });
+
+        let mut written = Vec::with_capacity(files.len());
+        for file in files {
+            match self.stage_file(journal, &file, manifest.as_mut()).await {
+                Ok(true) => written.push(file.path().to_path_buf()),
+                Ok(false) => {}
+                Err(error) if self.keep_going => journal.record_failure(error),
+                Err(error) => return Err(error),
+            }
+        }
+
+        if let Some(manifest) = manifest {
+            journal
+                .staging
+                .stage(
+                    &manifest_path,
+                    &manifest.to_json()?,
+                    previous_manifest,
+                    None,
+                )
+                .await?;
+        }
+        Ok(written)
+    }
+
+    /// Stage one file under the conflict policy
+    ///
+    /// Kept regions of an existing file are carried into the new rendering
+    /// first. Returns `false` when the policy kept a differing existing file.
+    async fn stage_file(
+        &self,
+        journal: &mut WriteJournal,
+        file: &RenderedFile,
+        manifest: Option<&mut GenerationManifest>,
+    ) -> Result<bool> {
+        let path = file.path();
+        let policy = self.policy_for(file);
+        let existing = self.fs.read_existing(path).await?;
+        let kept = existing
+            .as_deref()
+            .and_then(|existing| file.with_kept_regions(existing));
+        if policy == ConflictPolicy::Overwrite && kept.is_none() {
+            file.stage(&mut journal.staging, existing).await?;
+            return Ok(true);
+        }
+
+        let contents = match &kept {
+            Some(kept) => std::borrow::Cow::Borrowed(kept.as_bytes()),
+            None => file.contents().await?,
+        };
+        let key = path
+            .strip_prefix(&self.output_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let replacement = match &existing {
+            Some(existing) if *existing == *contents => None,
+            Some(existing) => {
+                let original = manifest.as_ref().and_then(|m| m.original(&key));
+                let replacement = policy.resolve(path, original, existing, &contents)?;
+                if replacement.is_none() {
+                    // Kept: the recorded original stays the merge base
+                    return Ok(false);
+                }
+                replacement
+            }
+            None => Some(contents.to_vec()),
+        };
 
-        let rendered_content = render_template(&handlebars, &processed_content, &data)?;
-        let final_output_path = determine_output_path(output_file, name, &processed_names)?;
+        if let Some(replacement) = replacement {
+            journal
+                .staging
+                .stage(path, &replacement, existing, file.mode())
+                .await?;
+        }
+        if let Some(manifest) = manifest {
+            manifest.record(key, &contents);
+        }
+        Ok(true)
+    }
 
-        write_output(&final_output_path, &rendered_content).await
+    /// Conflict policy for `file`: its front matter's, or the run's
+    fn policy_for(&self, file: &RenderedFile) -> ConflictPolicy {
+        file.conflict().unwrap_or(self.conflict_policy)
     }
 
-    /// Generate a single structure part of a feature
-    async fn generate_feature_structure(
+    /// Commit a run's staged files, or discard them if it failed
+    ///
+    /// Files land only after every file has rendered and staged, so a
+    /// failed run writes nothing, unless `keep_going` commits the files
+    /// staged before the failure. A commit that fails partway is rolled
+    /// back: new files and folders are removed and replaced files restored.
+    /// Folders a successful run left empty, because every file was filtered
+    /// out or kept, are removed as well. A cancelled run is always rolled
+    /// back, and so is a commit cancelled partway.
+    async fn finish_run(
         &self,
+        mut journal: WriteJournal,
+        result: Result<Vec<PathBuf>>,
+    ) -> Result<Vec<PathBuf>> {
+        let fs = self.fs.as_ref();
+        let result = match result {
+            Ok(written) => {
+                let committed = self.cancellation.run(journal.commit(fs)).await;
+                // A cancelled commit didn't get to remove the staging directory
+                journal.staging.discard().await;
+                committed.map(|()| written)
+            }
+            Err(error) if self.keep_going && !is_cancellation(&error) => {
+                if let Err(commit_error) = journal.commit(fs).await {
+                    ui::warning!("Warning: {:#}", commit_error);
+                }
+                Err(error)
+            }
+            Err(error) => {
+                journal.staging.discard().await;
+                Err(error)
+            }
+        };
+
+        match result {
+            Ok(written) => {
+                journal.remove_empty_dirs(fs).await?;
+                match journal.take_failures() {
+                    None => Ok(written),
+                    Some((first, count)) => Err(first.context(format!(
+                        "{} file(s) failed to generate; kept the {} written",
+                        count,
+                        written.len()
+                    ))),
+                }
+            }
+            Err(error) if self.keep_going && !is_cancellation(&error) => Err(error),
+            Err(error) => {
+                if let Err(rollback_error) = journal.roll_back(fs).await {
+                    ui::warning!("Warning: Could not roll back: {:#}", rollback_error);
+                } else if journal.written() > 0 {
+                    ui::warning!(
+                        "{} Rolled back {} file(s) written before the error",
+                        symbols::UNDO.as_str().yellow(),
+                        journal.written()
+                    );
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Render a single template file with configuration
+    ///
+    /// Files listed under `[raw]` or detected as binary are passed through
+    /// verbatim; only their filename is substituted. With a `trace`, the
+    /// file's helper calls are recorded into it, even when rendering fails.
+    async fn render_template_file(
+        job: &FileJob,
         name: &str,
+        template_config: &TemplateConfig,
+        cache: &TemplateCache,
+        trace: Option<&RenderTrace>,
+    ) -> Result<RenderedFile> {
+        let processed_names = process_smart_names(name);
+        let mut data = create_template_data(name, template_config);
+        let final_output_path = resolve_path_tokens(
+            &determine_output_path(
+                &job.output_file,
+                name,
+                &processed_names,
+                template_config.filename_case,
+            )?,
+            name,
+            &data,
+            template_config,
+        )?;
+
+        if job.raw || is_binary_file(&job.template_file).await? {
+            return Ok(RenderedFile::Raw {
+                path: final_output_path,
+                source: job.template_file.clone(),
+                mode: job.mode,
+            });
+        }
+
+        let (template_content, stamp) = cache
+            .read_template(&job.template_file, template_config.max_template_size)
+            .await?;
+        // Platform-specific files (`Button.ios.tsx`) render for that platform
+        if let Some(platform) = final_output_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(platform_suffix)
+        {
+            data["platform"] = platform.into();
+        }
+        let (comment_prefix, comment_suffix) =
+            comment_syntax(&final_output_path).unwrap_or_default();
+        data["comment_prefix"] = comment_prefix.trim_end().into();
+        data["comment_suffix"] = comment_suffix.trim_start().into();
+
+        let template_content = front_matter::strip(&template_content);
+        let processed_content = prepare_template_content(template_content, name, template_config);
+
+        // Large templates are split instead of compiled whole, so only small ones are cached
+        let rendered_content = if let Some(trace) = trace {
+            let calls = HelperCalls::default();
+            let rendered = if template_config.engine == RenderEngine::MiniJinja {
+                MiniJinjaRenderer::traced(template_config, &calls).render(&processed_content, &data)
+            } else {
+                let handlebars = create_traced_handlebars(template_config, &calls);
+                render_template_chunked(&handlebars, &processed_content, &data, RENDER_CHUNK_SIZE)
+            };
+            trace.record(job.template_file.clone(), final_output_path.clone(), &calls);
+            rendered
+        } else if template_config.engine == RenderEngine::MiniJinja {
+            MiniJinjaRenderer::for_config(template_config).render(&processed_content, &data)
+        } else if processed_content.len() > RENDER_CHUNK_SIZE {
+            let handlebars = create_handlebars_for(template_config);
+            render_template_chunked(&handlebars, &processed_content, &data, RENDER_CHUNK_SIZE)
+        } else {
+            let mut handlebars = create_handlebars_for(template_config);
+            cache
+                .compiled_template(
+                    &job.template_file,
+                    stamp,
+                    name,
+                    template_config.folder_case,
+                    &processed_content,
+                )
+                .and_then(|template| render_compiled(&mut handlebars, template, &data))
+        }
+        .map_err(|e| CliError::render(&job.template_file, &processed_content, &e))?;
+
+        // The banner goes above the template's own content, as a comment
+        let rendered_content = match &template_config.file_header {
+            Some(header) => {
+                let header = create_handlebars_for(template_config)
+                    .render_template(header, &data)
+                    .context("Failed to render file_header")?;
+                prepend_header(&final_output_path, &rendered_content, &header)
+                    .unwrap_or(rendered_content)
+            }
+            None => rendered_content,
+        };
+
+        let rendered_content = if template_config.editorconfig {
+            let rules = EditorConfigRules::for_file(&final_output_path);
+            if rules.is_empty() {
+                rendered_content
+            } else {
+                rules.apply(&rendered_content)
+            }
+        } else {
+            rendered_content
+        };
+
+        let content = match template_config.line_endings {
+            Some(endings) => endings.apply(&rendered_content).into_owned(),
+            None => rendered_content,
+        };
+
+        Ok(RenderedFile::Text {
+            path: final_output_path,
+            content,
+            mode: job.mode,
+            conflict: job.conflict,
+        })
+    }
+
+    /// Clone a template config with `line_endings=auto` resolved for `output_path`
+    fn resolve_line_endings(
+        template_config: &TemplateConfig,
+        output_path: &Path,
+    ) -> TemplateConfig {
+        let mut config = template_config.clone();
+        config.line_endings = config
+            .line_endings
+            .and_then(|endings| endings.resolve(output_path));
+        config
+    }
+
+    /// Plan the files of one structure of a feature, with the
+    /// architecture's `shared` variables of the feature, the structure's
+    /// `params` and the run's feature variables
+    async fn plan_feature_structure<'a>(
+        &self,
+        feature: &'a str,
+        structure: &'a ArchitectureStructure,
+        base_output_path: &Path,
+        shared: &std::collections::HashMap<String, String>,
+    ) -> Result<PlannedStructure<'a>> {
+        let (path, template_dir) = self.feature_structure_paths(structure, base_output_path)?;
+        let prefix = self.process_filename_pattern(&structure.filename_pattern, feature);
+        let template_config = self.load_template_config(&structure.template).await?;
+        ensure_cli_version(&structure.template, &template_config)?;
+        let mut config = Self::resolve_line_endings(&template_config, &path);
+        // The structure's own params win over the feature's shared
+        // variables, and the run's variables win over both
+        let mut vars = shared.clone();
+        vars.extend(structure.params_for(feature));
+        vars.extend(self.feature_variables.clone());
+        let explicit: Vec<String> = vars.keys().cloned().collect();
+        config.variables.extend(vars.clone());
+        apply_variable_rules(&mut config, &explicit)?;
+        ensure_required_variables(&config)?;
+        let jobs = self.plan_feature_files(&template_dir, &path, feature, &prefix, &config)?;
+        let config = Arc::new(config);
+        let stamp = self.stamp_for(&template_dir, feature, &vars)?;
+        Ok(PlannedStructure {
+            feature,
+            structure,
+            path,
+            jobs,
+            config,
+            stamp,
+        })
+    }
+
+    /// One error for every structure that failed, the first one's cause
+    /// with the others listed after it
+    fn structure_failures(failures: Vec<(String, anyhow::Error)>) -> Option<anyhow::Error> {
+        let mut failures = failures.into_iter();
+        let (first, error) = failures.next()?;
+        let others: Vec<String> = failures
+            .map(|(structure, error)| format!("{}: {:#}", structure, error))
+            .collect();
+        Some(if others.is_empty() {
+            error.context(format!("Failed to generate structure: {}", first))
+        } else {
+            error.context(format!(
+                "Failed to generate structure: {} ({} more failed: {})",
+                first,
+                others.len(),
+                others.join("; ")
+            ))
+        })
+    }
+
+    /// Render planned structures concurrently, keeping each file's result
+    ///
+    /// At most [`MAX_PARALLEL_STRUCTURES`] structures render at a time; the
+    /// files of each one render concurrently, as in any generation.
+    async fn render_structures(
+        &self,
+        planned: &mut [PlannedStructure<'_>],
+    ) -> Vec<Vec<Result<RenderedFile>>> {
+        let slots = Arc::new(Semaphore::new(MAX_PARALLEL_STRUCTURES));
+        let mut structures = Vec::with_capacity(planned.len());
+        for plan in planned.iter_mut() {
+            // Held by the structure's tasks until its last file rendered
+            let slot = slots.clone().acquire_owned().await.ok().map(Arc::new);
+            let jobs = std::mem::take(&mut plan.jobs);
+            structures.push(self.spawn_renders(jobs, plan.feature, plan.config.clone(), slot));
+        }
+
+        let mut rendered = Vec::with_capacity(structures.len());
+        for tasks in structures {
+            rendered.push(Self::join_renders(tasks).await);
+        }
+        rendered
+    }
+
+    /// Output directory of a feature structure and its template directory
+    fn feature_structure_paths(
+        &self,
         structure: &crate::config::ArchitectureStructure,
         base_output_path: &Path,
-    ) -> Result<()> {
-        // Create the specific path for this structure
-        let structure_path = if structure.path.is_empty() {
-            base_output_path.to_path_buf()
+    ) -> Result<(PathBuf, PathBuf)> {
+        let structure_path = self.structure_output_path(structure, base_output_path)?;
+
+        // Get template directory
+        let template_dir = self
+            .template_dir(&structure.template)
+            .with_context(|| format!("No template for structure '{}'", structure.path))?;
+
+        Ok((structure_path, template_dir))
+    }
+
+    /// Output directory of a feature structure whose feature folder is
+    /// `base_output_path`, under its `output_root` if it names one
+    ///
+    /// # Errors
+    ///
+    /// Fails when `output_root` isn't one of the configured roots.
+    fn structure_output_path(
+        &self,
+        structure: &ArchitectureStructure,
+        base_output_path: &Path,
+    ) -> Result<PathBuf> {
+        let base = match &structure.output_root {
+            None => base_output_path.to_path_buf(),
+            Some(root) => {
+                let Some(root_dir) = self.output_roots.get(root) else {
+                    let known: Vec<&str> = self.output_roots.keys().map(String::as_str).collect();
+                    anyhow::bail!(
+                        "Unknown output root '{}' for structure '{}'; configured roots: {}",
+                        root,
+                        structure.path,
+                        if known.is_empty() {
+                            "none (add them under [roots] in the config)".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    );
+                };
+                // The same feature folder, under the other root
+                let feature_folder = base_output_path
+                    .strip_prefix(&self.output_dir)
+                    .unwrap_or(Path::new(""));
+                root_dir.join(feature_folder)
+            }
+        };
+
+        Ok(if structure.path.is_empty() {
+            base
         } else {
-            base_output_path.join(&structure.path)
+            base.join(&structure.path)
+        })
+    }
+
+    /// Apply an architecture patch to the nearest `patch.file` above the feature
+    ///
+    /// Missing files are skipped quietly, since architectures list one
+    /// patch per supported bundler config.
+    async fn apply_architecture_patch(
+        &self,
+        journal: &mut WriteJournal,
+        name: &str,
+        patch: &ArchitecturePatch,
+        output_path: &Path,
+    ) -> Result<()> {
+        let mut found = None;
+        for dir in output_path.ancestors() {
+            let path = dir.join(&patch.file);
+            if let Some(content) = self.fs.read_existing(&path).await? {
+                found = Some((dir, path, content));
+                break;
+            }
+        }
+        let Some((dir, path, content)) = found else {
+            return Ok(());
+        };
+        let Ok(content) = String::from_utf8(content) else {
+            ui::warning!(
+                "Warning: Skipping patch of {}: not a text file",
+                path.display()
+            );
+            return Ok(());
         };
 
-        // Create directory if needed
-        if !structure.path.is_empty() {
-            fs::create_dir_all(&structure_path).await.with_context(|| {
-                format!(
-                    "Could not create structure directory: {}",
-                    structure_path.display()
-                )
-            })?;
-        }
+        let insertion = interpolate_name_pattern(&patch.insert, name, &process_smart_names(name))
+            .replace("{feature_path}", &relative_import_path(dir, output_path));
 
-        // Get template directory
-        let template_dir = self.templates_dir.join(&structure.template);
-
-        if !template_dir.exists() {
-            return Err(anyhow::anyhow!(
-                "Template '{}' not found for structure '{}'. Expected at: {}",
-                structure.template,
-                structure.path,
-                template_dir.display()
-            ));
+        match patch::insert_at_anchor(&content, &patch.anchor, &insertion, patch.position) {
+            PatchOutcome::Inserted(patched) => {
+                journal
+                    .staging
+                    .stage(&path, patched.as_bytes(), Some(content.into_bytes()), None)
+                    .await?;
+                let description = if patch.description.is_empty() {
+                    String::new()
+                } else {
+                    format!(" - {}", patch.description)
+                };
+                ui::status!(
+                    "{} {}",
+                    symbols::PLUGIN.as_str().bold(),
+                    t!(
+                        "generate.patched",
+                        path = path.display().to_string().green(),
+                        description = description
+                    )
+                );
+            }
+            PatchOutcome::AlreadyPresent => ui::status!(
+                "{} {}",
+                symbols::SKIP.as_str().yellow(),
+                t!(
+                    "generate.already_registered",
+                    path = path.display(),
+                    name = name
+                )
+            ),
+            PatchOutcome::AnchorNotFound => ui::warning!(
+                "Warning: Could not patch {}: no line contains '{}'",
+                path.display(),
+                patch.anchor
+            ),
         }
-
-        // Process filename pattern
-        let processed_filename = self.process_filename_pattern(&structure.filename_pattern, name);
-
-        // Process all template files
-        self.process_feature_template_directory(
-            &template_dir,
-            &structure_path,
-            name,
-            &processed_filename,
-        )
-        .await?;
-
         Ok(())
     }
 
     /// Process filename pattern with smart replacements
     fn process_filename_pattern(&self, pattern: &str, name: &str) -> String {
-        let smart_names = process_smart_names(name);
-
-        let mut result = pattern.to_string();
-
-        // Replace specific patterns
-        result = result.replace("use{name}", &smart_names.hook_name);
-        result = result.replace("{name}Context", &smart_names.context_name);
-        result = result.replace("{name}Provider", &smart_names.provider_name);
-        result = result.replace("{name}Page", &smart_names.page_name);
-
-        // Replace remaining {name}
-        result = result.replace("{name}", name);
-
-        result
+        interpolate_name_pattern(pattern, name, &process_smart_names(name))
     }
 
-    /// Process template directory for feature generation
-    async fn process_feature_template_directory(
+    /// Files a feature structure's template directory generates into
+    /// `output_path` with `config`
+    fn plan_feature_files(
         &self,
         template_dir: &Path,
         output_path: &Path,
         name: &str,
         filename_prefix: &str,
-    ) -> Result<()> {
-        let mut tasks = Vec::new();
+        config: &TemplateConfig,
+    ) -> Result<Vec<FileJob>> {
+        let mut jobs = Vec::new();
         let smart_names = process_smart_names(name);
 
         // Walk through all files in template directory
-        for entry in WalkDir::new(template_dir) {
-            let entry = entry.context("Error walking template directory")?;
+        for entry in walk_template_dir(template_dir, self.defaults.walk_limits) {
+            let entry = entry?;
 
             if entry.file_type().is_file() {
                 let relative_path = entry
@@ -740,127 +3556,198 @@ impl TemplateEngine {
                     .context("Could not get relative path")?;
 
                 let template_file = entry.path().to_path_buf();
+                let relative_str = relative_path.to_string_lossy().replace('\\', "/");
+                if is_template_config_file(&relative_str) {
+                    continue;
+                }
+                let raw = config.is_raw_file(&relative_str);
+                let front = if raw {
+                    FrontMatter::default()
+                } else {
+                    FrontMatter::read(&template_file)?.unwrap_or_default()
+                };
+                // As for templates, the front matter condition wins over the
+                // file's `[files]` entry
+                let condition = front
+                    .condition
+                    .as_ref()
+                    .or_else(|| config.file_filters.get(&relative_str));
+                if let Some(condition) = condition {
+                    if !evaluate_file_condition(condition, &config.variables) {
+                        continue;
+                    }
+                }
 
-                // Process output filename - use the pattern from the original template name
-                let output_filename = if let Some(original_name) = relative_path.file_name() {
-                    let original_str = original_name.to_str().unwrap_or("");
-
+                // Process output filename - use the pattern from the front
+                // matter or the original template name
+                let pattern = match &front.filename {
+                    Some(filename) => Some(filename.as_str()),
+                    None => relative_path
+                        .file_name()
+                        .map(|original| original.to_str().unwrap_or("")),
+                };
+                let output_filename = if let Some(pattern) = pattern {
                     // Apply smart filename replacements using the actual filename pattern
-                    apply_smart_filename_replacements(original_str, name, &smart_names)
+                    apply_cased_filename_replacements(
+                        pattern,
+                        name,
+                        &smart_names,
+                        config.filename_case,
+                    )
                 } else {
                     format!("{}.ts", filename_prefix)
                 };
 
                 let output_file = output_path.join(output_filename);
+                let mode = output_mode(&template_file, &relative_str, config);
 
-                // Process file asynchronously
-                let name_clone = name.to_string();
-                let task = tokio::spawn(async move {
-                    Self::process_template_file(&template_file, &output_file, &name_clone).await
+                jobs.push(FileJob {
+                    template_file,
+                    output_file,
+                    raw,
+                    mode,
+                    conflict: front.on_conflict,
                 });
-
-                tasks.push(task);
             }
         }
+        Ok(jobs)
+    }
 
-        // Wait for all files to be processed
-        for task in tasks {
-            task.await??;
-        }
+    /// Names of written files located directly in `dir`, sorted
+    fn files_in(dir: &Path, written: &[PathBuf]) -> Vec<String> {
+        let mut files: Vec<String> = written
+            .iter()
+            .filter(|path| path.parent() == Some(dir))
+            .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+            .collect();
+        files.sort();
+        files
+    }
 
-        Ok(())
+    /// Names of written files located directly in `dir` to list, sorted,
+    /// and how many were left out because `.gitignore` matches them
+    fn listed_files_in(&self, dir: &Path, written: &[PathBuf]) -> (Vec<String>, usize) {
+        let files = Self::files_in(dir, written);
+        if self.show_all {
+            return (files, 0);
+        }
+        let ignore = GitIgnore::load(dir);
+        let total = files.len();
+        let listed: Vec<String> = files
+            .into_iter()
+            .filter(|file| !ignore.is_ignored(&dir.join(file), false))
+            .collect();
+        let hidden = total - listed.len();
+        (listed, hidden)
     }
 
-    /// Process template file (backward compatibility)
-    async fn process_template_file(
-        template_file: &Path,
-        output_file: &Path,
-        name: &str,
-    ) -> Result<()> {
-        // Use default config for backward compatibility
-        let default_config = TemplateConfig::default();
-        Self::process_template_file_with_config(template_file, output_file, name, &default_config)
-            .await
+    /// Say how many generated files `.gitignore` kept out of the listing
+    fn show_hidden_files(hidden: usize) {
+        if hidden > 0 {
+            ui::status!("{}", t!("generate.files_hidden", count = hidden).dimmed());
+        }
     }
 
     /// Show generated files for standard generation
-    async fn show_generated_files(&self, output_path: &Path) -> Result<()> {
-        let mut files = Vec::new();
-
-        for entry in WalkDir::new(output_path).max_depth(1) {
-            let entry = entry.context("Error reading output directory")?;
-
-            if entry.file_type().is_file() {
-                if let Some(filename) = entry.file_name().to_str() {
-                    files.push(filename.to_string());
-                }
-            }
-        }
+    fn show_generated_files(&self, output_path: &Path, written: &[PathBuf]) {
+        let (files, hidden) = self.listed_files_in(output_path, written);
 
         if !files.is_empty() {
-            println!("{}", "Files created:".bold());
+            ui::status!("{}", t!("generate.files_created").bold());
             for file in files {
-                println!("  - {}", file.green());
+                ui::status!("  - {}", file.green());
             }
         }
-
-        Ok(())
+        Self::show_hidden_files(hidden);
     }
 
     /// Show generated feature files with architecture info
-    async fn show_generated_feature_files(
+    ///
+    /// With several features, each one's structure is listed under its folder.
+    fn show_generated_feature_files(
         &self,
-        output_path: &Path,
+        features: &[(&str, PathBuf)],
         arch_config: &ArchitectureConfig,
-    ) -> Result<()> {
-        println!("{}", "📁 Feature structure created:".bold());
-        println!("  Architecture: {}", arch_config.name.green());
-        println!("  Description: {}", arch_config.description);
-        println!();
+        written: &[PathBuf],
+    ) {
+        ui::status!(
+            "{} {}",
+            symbols::FOLDER,
+            t!("generate.feature_structure").bold()
+        );
+        ui::status!(
+            "  {} {}",
+            t!("label.architecture"),
+            arch_config.name.green()
+        );
+        ui::status!("  {} {}", t!("label.description"), arch_config.description);
+        ui::status!();
 
         // Show structure
-        for structure in &arch_config.structure {
-            println!("  📂 {} - {}", structure.path.blue(), structure.description);
+        let indent = if features.len() > 1 { "  " } else { "" };
+        let mut hidden = 0;
+        for (name, output_path) in features {
+            if features.len() > 1 {
+                ui::status!(
+                    "  {} {} ({})",
+                    symbols::FOLDER,
+                    name.bold(),
+                    output_path.display()
+                );
+            }
+            for structure in &arch_config.structure {
+                ui::status!(
+                    "{}  {} {} - {}",
+                    indent,
+                    symbols::DIRECTORY,
+                    structure.path.blue(),
+                    structure.description
+                );
 
-            // List files in this structure
-            let structure_path = if structure.path.is_empty() {
-                output_path.to_path_buf()
-            } else {
-                output_path.join(&structure.path)
-            };
+                // List files in this structure
+                let Ok(structure_path) = self.structure_output_path(structure, output_path) else {
+                    continue;
+                };
 
-            if structure_path.exists() {
-                if let Ok(entries) = std::fs::read_dir(&structure_path) {
-                    for entry in entries.flatten() {
-                        if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-                            if let Some(filename) = entry.file_name().to_str() {
-                                println!("     📄 {}", filename.green());
-                            }
-                        }
-                    }
+                let (files, ignored) = self.listed_files_in(&structure_path, written);
+                hidden += ignored;
+                for filename in files {
+                    ui::status!("{}     {} {}", indent, symbols::FILE, filename.green());
                 }
             }
+            let index = output_path.join(FEATURE_INDEX_FILE);
+            let listed = arch_config
+                .structure
+                .iter()
+                .any(|s| s.path.is_empty() && s.output_root.is_none());
+            if !listed && written.contains(&index) {
+                ui::status!(
+                    "{}  {} {}",
+                    indent,
+                    symbols::FILE,
+                    FEATURE_INDEX_FILE.green()
+                );
+            }
         }
+        Self::show_hidden_files(hidden);
 
-        println!();
-        println!("{}", "Benefits:".bold());
+        ui::status!();
+        ui::status!("{}", t!("generate.benefits").bold());
         for benefit in &arch_config.benefits {
-            println!("  ✅ {}", benefit);
+            ui::status!("  {} {}", symbols::SUCCESS, benefit);
         }
 
         if !arch_config.limitations.is_empty() {
-            println!();
-            println!("{}", "Considerations:".bold());
+            ui::status!();
+            ui::status!("{}", t!("generate.considerations").bold());
             for limitation in &arch_config.limitations {
-                println!("  ⚠️  {}", limitation);
+                ui::status!("  {}  {}", symbols::WARNING, limitation);
             }
         }
-
-        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "runtime"))]
 mod tests {
     use super::*;
 
@@ -872,4 +3759,783 @@ mod tests {
         assert!(config.variables.is_empty());
         assert!(config.file_filters.is_empty());
     }
+
+    #[test]
+    fn test_parse_output_subdir() {
+        let engine = TemplateEngine::new(PathBuf::from("templates"), PathBuf::from(".")).unwrap();
+
+        let config = engine
+            .parse_template_config("output_subdir={kebab_name}/components\n")
+            .unwrap();
+        assert_eq!(
+            config.output_subdir.as_deref(),
+            Some("{kebab_name}/components")
+        );
+
+        let config = engine.parse_template_config("output_subdir=\n").unwrap();
+        assert!(config.output_subdir.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_environment_overrides_replace_defaults() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("service");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.ts"),
+            "const url = '{{api_url}}';",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "environment=development\n\n[options]\napi_url=http://localhost\n\n\
+             [options.production]\napi_url=https://api.acme.com\n",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let files = engine
+            .render_to_memory("Auth", "service", Default::default())
+            .await
+            .unwrap();
+        assert_eq!(files[0].as_text(), Some("const url = 'http://localhost';"));
+
+        let engine = engine.with_environment(Some("production".to_string()));
+        let files = engine
+            .render_to_memory("Auth", "service", Default::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            files[0].as_text(),
+            Some("const url = 'https://api.acme.com';")
+        );
+
+        let vars = std::collections::HashMap::from([("api_url".to_string(), "/api".to_string())]);
+        let files = engine
+            .render_to_memory("Auth", "service", vars)
+            .await
+            .unwrap();
+        assert_eq!(files[0].as_text(), Some("const url = '/api';"));
+    }
+
+    #[test]
+    fn test_parse_examples_section() {
+        let engine = TemplateEngine::new(PathBuf::from("templates"), PathBuf::from(".")).unwrap();
+
+        let config = engine
+            .parse_template_config(
+                "[examples]\n\
+                 Minimal.files=Button/Button.tsx\n\
+                 Minimal=cli-frontend Button --type component --var style=none\n\
+                 Styled=cli-frontend Card --type component\n",
+            )
+            .unwrap();
+        assert_eq!(
+            config.examples,
+            vec![
+                TemplateExample {
+                    name: "Minimal".to_string(),
+                    command: "cli-frontend Button --type component --var style=none".to_string(),
+                    files: vec!["Button/Button.tsx".to_string()],
+                },
+                TemplateExample {
+                    name: "Styled".to_string(),
+                    command: "cli-frontend Card --type component".to_string(),
+                    files: vec![],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_to_memory_does_not_write() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("hook");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("use$FILE_NAME.ts"),
+            "export function use$FILE_NAME() { return {{with_state}}; }",
+        )
+        .unwrap();
+        std::fs::write(template_dir.join(".conf"), "[options]\nwith_state=false\n").unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let engine =
+            TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone()).unwrap();
+
+        let mut vars = std::collections::HashMap::new();
+        vars.insert("with_state".to_string(), "true".to_string());
+        let files = engine.render_to_memory("Auth", "hook", vars).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, PathBuf::from("Auth/useAuth.ts"));
+        assert_eq!(
+            files[0].as_text(),
+            Some("export function useAuth() { return true; }")
+        );
+        assert!(!output_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_engine_matches_renderer_trait_output() {
+        use super::renderer::{create_template_data, prepare_template_content, render_preview};
+
+        let content = "// {{pascal_case name}} $FILE_NAME {{kebab_name}}\n\
+                       {{#if style_is_scss}}import './$FILE_NAME.scss';{{/if}}\n\
+                       {{#if with_tests_bool}}export const tested = true;{{/if}}\n";
+        let conf = "[options]\nstyle=scss\nstyle_options=scss,css\nwith_tests=false\nwith_tests_type=boolean\n";
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), content).unwrap();
+        std::fs::write(template_dir.join(".conf"), conf).unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let mut vars = std::collections::HashMap::new();
+        vars.insert("with_tests".to_string(), "true".to_string());
+        let files = engine
+            .render_to_memory("UserCard", "component", vars.clone())
+            .await
+            .unwrap();
+
+        // The engine, the wasm preview and the TemplateRenderer trait share one rendering path
+        let mut config = engine.parse_template_config(conf).unwrap();
+        config.variables.extend(vars);
+        let expected = "// UserCard UserCard user-card\n\
+                        import './UserCard.scss';\n\
+                        export const tested = true;\n";
+        assert_eq!(files[0].as_text(), Some(expected));
+        assert_eq!(
+            render_preview(content, "UserCard", &config).unwrap(),
+            expected
+        );
+        assert_eq!(
+            HandlebarsRenderer::new()
+                .render(
+                    &prepare_template_content(content, "UserCard", &config),
+                    &create_template_data("UserCard", &config),
+                )
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_minijinja_engine_renders_jinja_templates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "{% if style_is_scss %}import './$FILE_NAME.scss';\n{% endif %}\
+             export const {{ name | pascal_case }} = '{{ name | kebab_case }}';\n",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "engine=minijinja\n[options]\nstyle=scss\nstyle_options=scss,css\n",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let files = engine
+            .render_to_memory("UserCard", "component", Default::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            files[0].as_text(),
+            Some("import './UserCard.scss';\nexport const UserCard = 'user-card';\n")
+        );
+
+        let description = engine.template_description("component").await.unwrap();
+        assert_eq!(description.engine, "minijinja");
+        // Jinja expressions aren't scanned, so `name` isn't reported as undeclared
+        assert!(engine.lint_template("component").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_directory_names_resolve_tokens() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("feature");
+        let nested = template_dir.join("{{kebab_name}}").join("components");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(template_dir.join("$FILE_NAME")).unwrap();
+        std::fs::write(
+            nested.join("$FILE_NAME.tsx"),
+            "export const $FILE_NAME = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME").join("{{snake_name}}.py"),
+            "",
+        )
+        .unwrap();
+        std::fs::write(template_dir.join(".conf"), "create_folder=false\n").unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let files = engine
+            .render_to_memory("UserCard", "feature", Default::default())
+            .await
+            .unwrap();
+        let mut paths: Vec<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("UserCard/user_card.py"),
+                PathBuf::from("user-card/components/UserCard.tsx"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_memory_file_system() {
+        use crate::file_system::MemoryFileSystem;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "export const $FILE_NAME = () => null;",
+        )
+        .unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone());
+
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs.read(&output_dir.join("Button").join("Button.tsx")),
+            Some(b"export const Button = () => null;".to_vec())
+        );
+        assert!(!output_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_rn_namespace_renders_platform_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let namespace_dir = temp_dir.path().join("templates").join("native");
+        let template_dir = namespace_dir.join("widget");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("templates").join("rn").join("card")).unwrap();
+        std::fs::write(namespace_dir.join(config::NAMESPACE_FILE), "react-native\n").unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "{{framework}} {{platform}}",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.$PLATFORM.tsx"),
+            "{{platform}} {{#if is_native}}native{{/if}}",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().join("templates"), PathBuf::from("out")).unwrap();
+        // Only the marked directory is a namespace, whatever it is called
+        assert_eq!(engine.list_templates().unwrap(), ["native/widget", "rn"]);
+
+        let files = engine
+            .render_to_memory("Button", "native/widget", Default::default())
+            .await
+            .unwrap();
+        let rendered: Vec<(String, &str)> = files
+            .iter()
+            .map(|f| (f.path.display().to_string(), f.as_text().unwrap()))
+            .collect();
+        assert_eq!(
+            rendered,
+            [
+                ("Button/Button.android.tsx".to_string(), "android native"),
+                ("Button/Button.ios.tsx".to_string(), "ios native"),
+                ("Button/Button.tsx".to_string(), "rn native"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skip_conflict_policy_keeps_changed_files() {
+        use crate::file_system::{FileSystem, MemoryFileSystem};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "v2").unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.css"), ".root {}").unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let edited = output_dir.join("Button").join("Button.tsx");
+        fs.write(&edited, b"v1 with edits").await.unwrap();
+
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone())
+            .with_conflict_policy(ConflictPolicy::Skip);
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fs.read(&edited), Some(b"v1 with edits".to_vec()));
+        assert_eq!(
+            fs.read(&output_dir.join("Button").join("Button.css")),
+            Some(b".root {}".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regeneration_keeps_marked_regions() {
+        use crate::file_system::{FileSystem, MemoryFileSystem};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "// v2\n// cli-frontend:keep-start\n// your code\n// cli-frontend:keep-end\n",
+        )
+        .unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let edited = output_dir.join("Button").join("Button.tsx");
+        fs.write(
+            &edited,
+            b"// v1\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n",
+        )
+        .await
+        .unwrap();
+
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone());
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs.read(&edited),
+            Some(
+                b"// v2\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n".to_vec()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_front_matter_overrides_file_settings() {
+        use crate::file_system::{FileSystem, MemoryFileSystem};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "---\non_conflict: skip\n---\nv2",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join("test.tsx"),
+            "---\nfilename: $FILE_NAME.test.tsx\n---\ntest('{{name}}');\n",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.stories.tsx"),
+            "---\ncondition: var_with_stories\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[files]\n$FILE_NAME.stories.tsx=always\n",
+        )
+        .unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let edited = output_dir.join("Button").join("Button.tsx");
+        fs.write(&edited, b"v1 with edits").await.unwrap();
+
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone());
+        let written = engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(written, [output_dir.join("Button").join("Button.test.tsx")]);
+        assert_eq!(fs.read(&edited), Some(b"v1 with edits".to_vec()));
+        assert_eq!(fs.read(&written[0]), Some(b"test('Button');\n".to_vec()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_generation_keeps_modes_and_follows_symlinks() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("cli");
+        std::fs::create_dir_all(template_dir.join("bin")).unwrap();
+        let script = template_dir.join("bin").join("run.sh");
+        std::fs::write(&script, "#!/bin/sh\necho {{name}}\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::write(template_dir.join("bin").join("env.sh"), "export A=1\n").unwrap();
+        std::fs::write(temp_dir.path().join("shared.md"), "# {{name}}\n").unwrap();
+        symlink(
+            temp_dir.path().join("shared.md"),
+            template_dir.join("README.md"),
+        )
+        .unwrap();
+        symlink(
+            temp_dir.path().join("missing"),
+            template_dir.join("broken.ts"),
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[permissions]\nbin/env.sh=700\n",
+        )
+        .unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .generate("Tool", "cli", true, Default::default())
+            .await
+            .unwrap();
+
+        let mode = |file: &str| {
+            let path = output_dir.join("Tool").join(file);
+            std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+        };
+        assert_eq!(mode("bin/run.sh"), 0o755);
+        assert_eq!(mode("bin/env.sh"), 0o700);
+        assert_eq!(
+            std::fs::read_to_string(output_dir.join("Tool").join("README.md")).unwrap(),
+            "# Tool\n"
+        );
+        assert!(!output_dir.join("Tool").join("broken.ts").exists());
+    }
+
+    #[tokio::test]
+    async fn test_templateignore_skips_matching_files() {
+        use crate::file_system::MemoryFileSystem;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(template_dir.join("__pycache__")).unwrap();
+        std::fs::create_dir_all(template_dir.join("docs")).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};").unwrap();
+        std::fs::write(template_dir.join(".$FILE_NAME.tsx.swp"), "swap").unwrap();
+        std::fs::write(template_dir.join("__pycache__").join("gen.pyc"), "").unwrap();
+        std::fs::write(template_dir.join("docs").join("AUTHORING.md"), "notes").unwrap();
+        std::fs::write(
+            template_dir.join(".templateignore"),
+            "*.swp\n__pycache__/\n/docs/\n",
+        )
+        .unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone());
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        let files: Vec<PathBuf> = fs.files().into_keys().collect();
+        assert_eq!(files, [output_dir.join("Button").join("Button.tsx")]);
+        assert_eq!(
+            engine.list_template_files("component").unwrap(),
+            ["$FILE_NAME.tsx"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requires_cli_blocks_generation_on_older_binaries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};").unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[metadata]\nrequires_cli>=99.0\n",
+        )
+        .unwrap();
+
+        let engine = TemplateEngine::new(
+            temp_dir.path().join("templates"),
+            temp_dir.path().join("out"),
+        )
+        .unwrap();
+        let error = engine
+            .render_to_memory("Button", "component", Default::default())
+            .await
+            .unwrap_err();
+        let cli_error = CliError::classify(&error).unwrap();
+        assert_eq!(cli_error.exit_code(), 10);
+        assert!(cli_error.hint().contains("Upgrade cli-frontend to 99.0"));
+
+        // Describing still works, so users can see what the template needs
+        let description = engine.template_description("component").await.unwrap();
+        assert_eq!(description.requires_cli.as_deref(), Some("99.0"));
+
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[metadata]\nrequires_cli=>=1.0\n",
+        )
+        .unwrap();
+        let engine = TemplateEngine::new(
+            temp_dir.path().join("templates"),
+            temp_dir.path().join("out"),
+        )
+        .unwrap();
+        assert!(engine
+            .render_to_memory("Button", "component", Default::default())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_failed_generation_rolls_back_unless_keep_going() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};").unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.css"), "{{undefined_var}}").unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let engine = |keep_going: bool| {
+            TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+                .unwrap()
+                .with_strict_variables(true)
+                .with_keep_going(keep_going)
+        };
+
+        let result = engine(false)
+            .generate("Button", "component", true, Default::default())
+            .await;
+        assert!(result.is_err());
+        assert!(!output_dir.exists());
+
+        let error = engine(true)
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("1 file(s) failed to generate"));
+        assert!(output_dir.join("Button").join("Button.tsx").exists());
+        assert!(!output_dir.join("Button").join("Button.css").exists());
+    }
+
+    #[tokio::test]
+    async fn test_merge_conflict_policy_keeps_user_edits() {
+        use crate::file_system::{FileSystem, MemoryFileSystem};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("templates").join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        let template_file = template_dir.join("$FILE_NAME.tsx");
+        std::fs::write(&template_file, "import a;\n\nfn one() {}\n\nfn two() {}\n").unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let fs = Arc::new(MemoryFileSystem::new());
+        let engine = TemplateEngine::new(temp_dir.path().join("templates"), output_dir.clone())
+            .unwrap()
+            .with_file_system(fs.clone())
+            .with_conflict_policy(ConflictPolicy::Merge);
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+        assert!(fs.read(&output_dir.join(MANIFEST_FILE)).is_some());
+
+        // The user edits the file, then the template changes elsewhere
+        let generated = output_dir.join("Button").join("Button.tsx");
+        fs.write(
+            &generated,
+            b"import a;\nimport b;\n\nfn one() {}\n\nfn two() {}\n",
+        )
+        .await
+        .unwrap();
+        std::fs::write(
+            &template_file,
+            "import a;\n\nfn one() {}\n\nfn two(x: u8) {}\n",
+        )
+        .unwrap();
+
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs.read(&generated).unwrap(),
+            b"import a;\nimport b;\n\nfn one() {}\n\nfn two(x: u8) {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_variables_reject_undefined_variables() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("hook");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("use$FILE_NAME.ts"),
+            "export const store = '{{stroe}}';",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let files = engine
+            .render_to_memory("Auth", "hook", Default::default())
+            .await
+            .unwrap();
+        assert_eq!(files[0].as_text(), Some("export const store = '';"));
+
+        let error = engine
+            .with_strict_variables(true)
+            .render_to_memory("Auth", "hook", Default::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CliError>(),
+            Some(CliError::RenderError { line: Some(1), message, .. }) if message.contains("stroe")
+        ));
+
+        std::fs::write(template_dir.join(".conf"), "strict_variables=true\n").unwrap();
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        assert!(engine
+            .render_to_memory("Auth", "hook", Default::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_required_variables_must_be_set() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("service");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.ts"),
+            "const url = '{{api_url}}';",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[options]\napi_url_required=true\napi_url_description=Base URL\n",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let error = engine
+            .render_to_memory("Auth", "service", Default::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CliError>(),
+            Some(CliError::InvalidVariable { name, .. }) if name == "api_url"
+        ));
+
+        let vars = std::collections::HashMap::from([("api_url".to_string(), "/api".to_string())]);
+        let files = engine
+            .render_to_memory("Auth", "service", vars)
+            .await
+            .unwrap();
+        assert_eq!(files[0].as_text(), Some("const url = '/api';"));
+    }
+
+    #[tokio::test]
+    async fn test_rules_apply_after_merging_variables() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("$FILE_NAME.tsx"),
+            "{{#if with_css_module_bool}}import styles from './x.module.css';{{/if}}",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.join(".conf"),
+            "[options]\nstyle=css\nwith_css_module=true\nwith_css_module_type=boolean\nwith_stories=false\nwith_tests=true\n\n\
+             [rules]\nstyle=none implies !with_css_module\nwith_stories requires with_tests\n",
+        )
+        .unwrap();
+
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), PathBuf::from("out")).unwrap();
+        let vars = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+
+        let files = engine
+            .render_to_memory("Card", "component", vars(&[("style", "none")]))
+            .await
+            .unwrap();
+        assert_eq!(files[0].as_text(), Some(""));
+
+        let error = engine
+            .render_to_memory(
+                "Card",
+                "component",
+                vars(&[("with_stories", "true"), ("with_tests", "false")]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CliError>(),
+            Some(CliError::InvalidVariable { name, .. }) if name == "with_tests"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_records_profile_phases() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let template_dir = temp_dir.path().join("component");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(template_dir.join("$FILE_NAME.tsx"), "{{pascal_name}}").unwrap();
+
+        let profile = Arc::new(RunProfile::new());
+        let engine =
+            TemplateEngine::new(temp_dir.path().to_path_buf(), temp_dir.path().join("out"))
+                .unwrap()
+                .with_profile(profile.clone());
+
+        engine
+            .generate("Button", "component", true, Default::default())
+            .await
+            .unwrap();
+
+        let phases: Vec<&str> = profile.phases().iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(
+            phases,
+            vec![
+                "load template config",
+                "prepare output",
+                "plan files",
+                "render",
+                "write"
+            ]
+        );
+    }
 }
@@ -13,8 +13,208 @@
 //! assert_eq!(to_snake_case("HelloWorld").as_ref(), "hello_world");
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
+/// Case convention applied to the name part of generated filenames.
+///
+/// Configured with `filename_case` (globally in `.cli-frontend.conf` or per
+/// template in `.conf`) for teams that mandate e.g. `user-profile.tsx` instead
+/// of `UserProfile.tsx`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::CaseStyle;
+///
+/// let style = CaseStyle::parse("kebab").unwrap();
+/// assert_eq!(style.apply("UserProfile"), "user-profile");
+/// assert_eq!(style.apply("useAuth"), "use-auth");
+///
+/// let route = CaseStyle::parse("route").unwrap();
+/// assert_eq!(route.apply("users/:id/edit"), "users.$id.edit");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseStyle {
+    Pascal,
+    Camel,
+    Kebab,
+    Snake,
+    /// Remix / React Router flat route notation, see [`route_filename`]
+    Route,
+}
+
+impl CaseStyle {
+    /// Parse a case style name (`pascal`, `camel`, `kebab`, `snake`, `route`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "pascal" | "pascalcase" => Some(Self::Pascal),
+            "camel" | "camelcase" => Some(Self::Camel),
+            "kebab" | "kebab-case" => Some(Self::Kebab),
+            "snake" | "snake_case" => Some(Self::Snake),
+            "route" | "flat-route" => Some(Self::Route),
+            _ => None,
+        }
+    }
+
+    /// Name of the style as written in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pascal => "pascal",
+            Self::Camel => "camel",
+            Self::Kebab => "kebab",
+            Self::Snake => "snake",
+            Self::Route => "route",
+        }
+    }
+
+    /// Convert a string to this case style
+    ///
+    /// Word boundaries are detected through snake_case first so mixed-case
+    /// inputs like `useAuth` keep their boundaries (`UseAuth`, not `Useauth`).
+    /// Route names keep their segments as written.
+    pub fn apply(&self, s: &str) -> String {
+        if *self == Self::Route {
+            return route_filename(s);
+        }
+
+        let snake = to_snake_case(s);
+        match self {
+            Self::Pascal => to_pascal_case(&snake).into_owned(),
+            Self::Camel => to_camel_case(&snake).into_owned(),
+            Self::Kebab => to_kebab_case(&snake).into_owned(),
+            Self::Snake | Self::Route => snake.into_owned(),
+        }
+    }
+}
+
+/// A segment of a route path such as `users/:id/edit`
+enum RouteSegment<'a> {
+    Static(&'a str),
+    /// `(lang)` or `lang?`
+    OptionalStatic(&'a str),
+    /// `:id` / `$id`, or `:id?` / `($id)` when optional
+    Param {
+        name: &'a str,
+        optional: bool,
+    },
+    /// `*` or `$`
+    Splat,
+}
+
+fn route_segments(path: &str) -> Vec<RouteSegment<'_>> {
+    path.split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let (inner, wrapped) = match segment.strip_prefix('(').and_then(|s| s.strip_suffix(')'))
+            {
+                Some(inner) => (inner, true),
+                None => (segment, false),
+            };
+            let (inner, optional) = match inner.strip_suffix('?') {
+                Some(inner) => (inner, true),
+                None => (inner, wrapped),
+            };
+
+            match inner {
+                "*" | "$" => RouteSegment::Splat,
+                _ => match inner.strip_prefix(':').or_else(|| inner.strip_prefix('$')) {
+                    Some(name) => RouteSegment::Param { name, optional },
+                    None if optional => RouteSegment::OptionalStatic(inner),
+                    None => RouteSegment::Static(inner),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Flat route filename for a route path, as used by Remix and React Router
+///
+/// Segments are joined with `.`, `:param` becomes `$param`, optional
+/// segments are wrapped in parentheses, `*` becomes the `$` splat, a
+/// trailing `index` becomes `_index` and literal dots are escaped as `[.]`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::route_filename;
+///
+/// assert_eq!(route_filename("users/:id/edit"), "users.$id.edit");
+/// assert_eq!(route_filename(":lang?/docs/*"), "($lang).docs.$");
+/// assert_eq!(route_filename("users/index"), "users._index");
+/// assert_eq!(route_filename("sitemap.xml"), "sitemap[.]xml");
+/// ```
+pub fn route_filename(path: &str) -> String {
+    let segments = route_segments(path);
+    if segments.is_empty() {
+        return "_index".to_string();
+    }
+
+    let last = segments.len() - 1;
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| match segment {
+            RouteSegment::Static("index") if i == last => "_index".to_string(),
+            RouteSegment::Static(text) => text.replace('.', "[.]"),
+            RouteSegment::OptionalStatic(text) => format!("({})", text.replace('.', "[.]")),
+            RouteSegment::Param {
+                name,
+                optional: false,
+            } => format!("${}", name),
+            RouteSegment::Param {
+                name,
+                optional: true,
+            } => format!("(${})", name),
+            RouteSegment::Splat => "$".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// URL pattern for a route path, e.g. `/users/:id/edit`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::route_path;
+///
+/// assert_eq!(route_path("users/$id/edit"), "/users/:id/edit");
+/// assert_eq!(route_path("users/index"), "/users");
+/// ```
+pub fn route_path(path: &str) -> String {
+    let segments = route_segments(path);
+    let last = segments.len().saturating_sub(1);
+    let parts: Vec<String> = segments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, segment)| match segment {
+            RouteSegment::Static("index") if i == last => None,
+            RouteSegment::Static(text) => Some(text.to_string()),
+            RouteSegment::OptionalStatic(text) => Some(format!("{}?", text)),
+            RouteSegment::Param { name, optional } => {
+                Some(format!(":{}{}", name, if *optional { "?" } else { "" }))
+            }
+            RouteSegment::Splat => Some("*".to_string()),
+        })
+        .collect();
+
+    format!("/{}", parts.join("/"))
+}
+
+/// Names of the dynamic segments of a route path, e.g. `["id"]`
+pub fn route_params(path: &str) -> Vec<String> {
+    route_segments(path)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            RouteSegment::Param { name, .. } => Some(name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Smart name variations for React-specific patterns.
 ///
 /// This struct holds different name variations commonly used in React development,
@@ -279,6 +479,71 @@ fn is_kebab_case(s: &str) -> bool {
         && s.chars().any(|c| c.is_alphabetic())
 }
 
+/// Resolves the name of the folder created for a generated item.
+///
+/// Without a `folder_case` policy the raw name is used, matching the
+/// historical behavior. The result is also exposed to templates as the
+/// `$FOLDER_NAME` token and the `folder_name` data key.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::{folder_name, CaseStyle};
+///
+/// assert_eq!(folder_name("UserProfile", None), "UserProfile");
+/// assert_eq!(folder_name("UserProfile", Some(CaseStyle::Kebab)), "user-profile");
+/// ```
+pub fn folder_name(name: &str, folder_case: Option<CaseStyle>) -> String {
+    match folder_case {
+        Some(style) => style.apply(name),
+        None => name.to_string(),
+    }
+}
+
+/// Interpolates `{...}` placeholders in a path or filename pattern.
+///
+/// This is the syntax used by architecture `filename_pattern` entries and the
+/// template-level `output_subdir` setting. Smart patterns (`use{name}`,
+/// `{name}Context`, `{name}Provider`, `{name}Page`) resolve first, then the
+/// case variants (`{pascal_name}`, `{camel_name}`, `{kebab_name}`,
+/// `{snake_name}`) and finally the raw `{name}`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::{interpolate_name_pattern, process_smart_names};
+///
+/// let smart_names = process_smart_names("UserProfile");
+/// assert_eq!(
+///     interpolate_name_pattern("{kebab_name}/components", "UserProfile", &smart_names),
+///     "user-profile/components"
+/// );
+/// assert_eq!(
+///     interpolate_name_pattern("{name}Context", "UserProfile", &smart_names),
+///     "UserProfileContext"
+/// );
+/// ```
+pub fn interpolate_name_pattern(pattern: &str, name: &str, smart_names: &SmartNames) -> String {
+    let mut result = pattern.to_string();
+
+    // Replace specific patterns
+    result = result.replace("use{name}", &smart_names.hook_name);
+    result = result.replace("{name}Context", &smart_names.context_name);
+    result = result.replace("{name}Provider", &smart_names.provider_name);
+    result = result.replace("{name}Page", &smart_names.page_name);
+
+    // Replace case variants
+    result = result.replace("{pascal_name}", &to_pascal_case(name));
+    result = result.replace("{camel_name}", &to_camel_case(name));
+    result = result.replace("{kebab_name}", &to_kebab_case(name));
+    result = result.replace("{snake_name}", &to_snake_case(name));
+
+    // Replace remaining {name}
+    result = result.replace("{name}", name);
+
+    result
+}
+
 /// Processes a name into smart names for React patterns.
 ///
 /// Generates appropriate names for hooks (useX), contexts (XContext),
@@ -394,10 +659,42 @@ pub fn apply_smart_replacements(content: &str, name: &str, smart_names: &SmartNa
     result
 }
 
+/// Platforms a `$PLATFORM` template file is generated for
+pub const NATIVE_PLATFORMS: [&str; 2] = ["ios", "android"];
+
+/// Filename placeholder expanded into one file per entry of [`NATIVE_PLATFORMS`]
+pub const PLATFORM_PLACEHOLDER: &str = "$PLATFORM";
+
+/// The React Native platform suffix of a filename, if any
+///
+/// Metro resolves `Button.ios.tsx` and `Button.android.tsx` (and `.native`
+/// / `.web`) in place of `Button.tsx`, so the suffix is the segment right
+/// before the extension.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::platform_suffix;
+///
+/// assert_eq!(platform_suffix("Button.ios.tsx"), Some("ios"));
+/// assert_eq!(platform_suffix("useAuth.android.ts"), Some("android"));
+/// assert_eq!(platform_suffix("Button.spec.tsx"), None);
+/// ```
+pub fn platform_suffix(filename: &str) -> Option<&str> {
+    let mut segments = filename.rsplit('.');
+    let _extension = segments.next()?;
+    let suffix = segments.next()?;
+    // A bare `ios.tsx` is a name, not a suffix
+    segments.next()?;
+    matches!(suffix, "ios" | "android" | "native" | "web").then_some(suffix)
+}
+
 /// Applies smart filename replacements.
 ///
 /// Replaces patterns in filenames with appropriate smart names.
 /// Converts filenames like `use$FILE_NAME.ts` to `useAuth.ts`.
+/// Platform suffixes (`.ios.tsx`, `.android.tsx`) are kept as written, even
+/// under a case policy, so React Native can resolve them.
 ///
 /// # Arguments
 ///
@@ -417,22 +714,68 @@ pub fn apply_smart_replacements(content: &str, name: &str, smart_names: &SmartNa
 /// let smart_names = process_smart_names("Auth");
 /// let result = apply_smart_filename_replacements("use$FILE_NAME.ts", "Auth", &smart_names);
 /// assert_eq!(result, "useAuth.ts");
+///
+/// let result = apply_smart_filename_replacements("$FILE_NAME.ios.tsx", "Auth", &smart_names);
+/// assert_eq!(result, "Auth.ios.tsx");
 /// ```
+#[allow(dead_code)] // Public API
 pub fn apply_smart_filename_replacements(
     filename: &str,
     name: &str,
     smart_names: &SmartNames,
 ) -> String {
+    apply_cased_filename_replacements(filename, name, smart_names, None)
+}
+
+/// Applies smart filename replacements with an optional case policy.
+///
+/// Without a policy this behaves like [`apply_smart_filename_replacements`]:
+/// smart names are used as-is and `$FILE_NAME` becomes PascalCase. With a
+/// policy, every substituted name is converted to that case while the rest of
+/// the filename (suffixes, extensions) is left untouched.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::naming::{
+///     apply_cased_filename_replacements, process_smart_names, CaseStyle,
+/// };
+///
+/// let smart_names = process_smart_names("UserProfile");
+/// let result = apply_cased_filename_replacements(
+///     "$FILE_NAME.component.tsx",
+///     "UserProfile",
+///     &smart_names,
+///     Some(CaseStyle::Kebab),
+/// );
+/// assert_eq!(result, "user-profile.component.tsx");
+/// ```
+pub fn apply_cased_filename_replacements(
+    filename: &str,
+    name: &str,
+    smart_names: &SmartNames,
+    case: Option<CaseStyle>,
+) -> String {
+    let cased = |value: &str| match case {
+        Some(style) => style.apply(value),
+        None => value.to_string(),
+    };
+
     let mut result = filename.to_string();
 
     // Replace specific patterns in filenames first
-    result = result.replace("use$FILE_NAME", &smart_names.hook_name);
-    result = result.replace("$FILE_NAMEContext", &smart_names.context_name);
-    result = result.replace("$FILE_NAMEProvider", &smart_names.provider_name);
-    result = result.replace("$FILE_NAMEPage", &smart_names.page_name);
-
-    // Replace remaining $FILE_NAME with PascalCase name
-    result = result.replace("$FILE_NAME", &to_pascal_case(name));
+    result = result.replace("use$FILE_NAME", &cased(&smart_names.hook_name));
+    result = result.replace("$FILE_NAMEContext", &cased(&smart_names.context_name));
+    result = result.replace("$FILE_NAMEProvider", &cased(&smart_names.provider_name));
+    result = result.replace("$FILE_NAMEPage", &cased(&smart_names.page_name));
+
+    // Replace remaining $FILE_NAME with PascalCase name (or the configured case);
+    // route names need their raw segments
+    let file_name = match case {
+        Some(CaseStyle::Route) => route_filename(name),
+        _ => cased(&to_pascal_case(name)),
+    };
+    result = result.replace("$FILE_NAME", &file_name);
 
     result
 }
@@ -470,6 +813,108 @@ mod tests {
         assert_eq!(to_kebab_case("hello_world"), "hello-world");
     }
 
+    #[test]
+    fn test_case_style_parse() {
+        assert_eq!(CaseStyle::parse("kebab"), Some(CaseStyle::Kebab));
+        assert_eq!(CaseStyle::parse("PASCAL"), Some(CaseStyle::Pascal));
+        assert_eq!(CaseStyle::parse("camelCase"), Some(CaseStyle::Camel));
+        assert_eq!(CaseStyle::parse("snake"), Some(CaseStyle::Snake));
+        assert_eq!(CaseStyle::parse("shouting"), None);
+    }
+
+    #[test]
+    fn test_route_filename_and_params() {
+        assert_eq!(route_filename("users/:id/edit"), "users.$id.edit");
+        assert_eq!(route_filename("/users/$id/"), "users.$id");
+        assert_eq!(route_filename("(en)/blog/:slug?"), "(en).blog.($slug)");
+        assert_eq!(route_filename(""), "_index");
+        assert_eq!(route_path("(en)/blog/:slug?"), "/en?/blog/:slug?");
+        assert_eq!(route_path("/"), "/");
+        assert_eq!(route_params("orgs/:org/repos/$repo/*"), ["org", "repo"]);
+
+        let smart_names = process_smart_names("users/:id/edit");
+        assert_eq!(
+            apply_cased_filename_replacements(
+                "$FILE_NAME.tsx",
+                "users/:id/edit",
+                &smart_names,
+                Some(CaseStyle::Route)
+            ),
+            "users.$id.edit.tsx"
+        );
+    }
+
+    #[test]
+    fn test_case_style_apply_keeps_word_boundaries() {
+        assert_eq!(CaseStyle::Pascal.apply("useAuth"), "UseAuth");
+        assert_eq!(CaseStyle::Camel.apply("UserProfile"), "userProfile");
+        assert_eq!(CaseStyle::Kebab.apply("AuthContext"), "auth-context");
+        assert_eq!(CaseStyle::Snake.apply("AuthPage"), "auth_page");
+    }
+
+    #[test]
+    fn test_apply_cased_filename_replacements() {
+        let smart_names = process_smart_names("Auth");
+
+        assert_eq!(
+            apply_cased_filename_replacements("use$FILE_NAME.ts", "Auth", &smart_names, None),
+            "useAuth.ts"
+        );
+        assert_eq!(
+            apply_cased_filename_replacements(
+                "use$FILE_NAME.test.ts",
+                "Auth",
+                &smart_names,
+                Some(CaseStyle::Kebab)
+            ),
+            "use-auth.test.ts"
+        );
+        assert_eq!(
+            apply_cased_filename_replacements(
+                "$FILE_NAMEContext.tsx",
+                "Auth",
+                &smart_names,
+                Some(CaseStyle::Camel)
+            ),
+            "authContext.tsx"
+        );
+    }
+
+    #[test]
+    fn test_folder_name() {
+        assert_eq!(folder_name("UserProfile", None), "UserProfile");
+        assert_eq!(
+            folder_name("UserProfile", Some(CaseStyle::Kebab)),
+            "user-profile"
+        );
+        assert_eq!(
+            folder_name("user_profile", Some(CaseStyle::Pascal)),
+            "UserProfile"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_name_pattern() {
+        let smart_names = process_smart_names("UserProfile");
+
+        assert_eq!(
+            interpolate_name_pattern("{kebab_name}/components", "UserProfile", &smart_names),
+            "user-profile/components"
+        );
+        assert_eq!(
+            interpolate_name_pattern("{snake_name}/{camel_name}", "UserProfile", &smart_names),
+            "user_profile/userProfile"
+        );
+        assert_eq!(
+            interpolate_name_pattern("{name}Context", "UserProfile", &smart_names),
+            "UserProfileContext"
+        );
+        assert_eq!(
+            interpolate_name_pattern("static/dir", "UserProfile", &smart_names),
+            "static/dir"
+        );
+    }
+
     #[test]
     fn test_process_smart_names() {
         let names = process_smart_names("auth");
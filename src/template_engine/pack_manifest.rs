@@ -0,0 +1,70 @@
+//! One JSON document describing a whole template pack.
+//!
+//! `cli-frontend export-manifest` writes a [`PackManifest`] so portals, docs
+//! sites and editor extensions can list templates, their variables and
+//! `[files]` filters, and the architectures without parsing `.conf` files.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::description::TemplateDescription;
+use crate::config::{ArchitectureConfig, ArchitecturePatch, ArchitectureStructure};
+
+/// Version of the manifest format, bumped when fields change incompatibly
+pub const PACK_MANIFEST_VERSION: u32 = 1;
+
+/// Every template and architecture of a pack
+#[derive(Debug, Clone, Serialize)]
+pub struct PackManifest {
+    pub manifest_version: u32,
+    /// Version of the CLI that exported the manifest
+    pub cli_version: String,
+    pub templates: Vec<TemplateDescription>,
+    pub architectures: Vec<ArchitectureDescription>,
+}
+
+/// An architecture, as listed in a [`PackManifest`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchitectureDescription {
+    /// Architecture file name without `.json` (what users pass to `--architecture`)
+    pub name: String,
+    /// Human-readable name from the JSON `name`
+    pub title: String,
+    pub description: String,
+    pub benefits: Vec<String>,
+    pub limitations: Vec<String>,
+    /// Folders the feature is made of and the template each uses
+    pub structure: Vec<ArchitectureStructure>,
+    pub patches: Vec<ArchitecturePatch>,
+    /// Variables derived for every structure, as `{name}` patterns
+    pub shared: BTreeMap<String, String>,
+}
+
+impl ArchitectureDescription {
+    pub fn new(name: &str, config: ArchitectureConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            title: config.name,
+            description: config.description,
+            benefits: config.benefits,
+            limitations: config.limitations,
+            structure: config.structure,
+            patches: config.patches,
+            shared: config.shared,
+        }
+    }
+}
+
+impl PackManifest {
+    pub fn new(
+        templates: Vec<TemplateDescription>,
+        architectures: Vec<ArchitectureDescription>,
+    ) -> Self {
+        Self {
+            manifest_version: PACK_MANIFEST_VERSION,
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            templates,
+            architectures,
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Anchored insertions into existing project files.
+//!
+//! Architectures can register a new feature in files the generator doesn't
+//! own, such as the module federation `exposes` of `webpack.config.js` or
+//! `vite.config.ts`. Each patch names an anchor, a piece of text on the line
+//! to insert next to:
+//!
+//! ```js
+//! new ModuleFederationPlugin({
+//!   exposes: {                          // anchor "exposes: {"
+//!     './Checkout': './src/features/Checkout/shell',
+//!   },
+//! })
+//! ```
+//!
+//! Inserted lines take the indentation of their new neighbours, and patching
+//! the same file twice is a no-op.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+
+/// Where a patch goes relative to its anchor line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InsertPosition {
+    /// On the lines after the anchor, e.g. first entry of an object
+    #[default]
+    After,
+    /// On the lines before the anchor, e.g. last entry before a closing brace
+    Before,
+}
+
+/// Result of [`insert_at_anchor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchOutcome {
+    /// The patched file content
+    Inserted(String),
+    /// Every inserted line is already in the file
+    AlreadyPresent,
+    /// No line contains the anchor
+    AnchorNotFound,
+}
+
+/// Insert `insertion` next to the first line containing `anchor`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::patch::{insert_at_anchor, InsertPosition, PatchOutcome};
+///
+/// let config = "exposes: {\n  './Cart': './src/Cart',\n},\n";
+/// let entry = "'./Checkout': './src/Checkout',";
+///
+/// let PatchOutcome::Inserted(patched) = insert_at_anchor(config, "exposes: {", entry, InsertPosition::After) else {
+///     panic!("anchor not found");
+/// };
+/// assert_eq!(patched, "exposes: {\n  './Checkout': './src/Checkout',\n  './Cart': './src/Cart',\n},\n");
+/// assert_eq!(insert_at_anchor(&patched, "exposes: {", entry, InsertPosition::After), PatchOutcome::AlreadyPresent);
+/// ```
+pub fn insert_at_anchor(
+    content: &str,
+    anchor: &str,
+    insertion: &str,
+    position: InsertPosition,
+) -> PatchOutcome {
+    let new_lines: Vec<&str> = insertion
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if new_lines.is_empty()
+        || new_lines
+            .iter()
+            .all(|new| content.lines().any(|line| line.trim() == new.trim()))
+    {
+        return PatchOutcome::AlreadyPresent;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(anchor_index) = lines.iter().position(|line| line.contains(anchor)) else {
+        return PatchOutcome::AnchorNotFound;
+    };
+
+    let anchor_indent = indentation(lines[anchor_index]);
+    let nested_indent = |neighbour: Option<&&str>| {
+        // Match a neighbour nested deeper than the anchor (an existing
+        // entry), otherwise indent one level past the anchor
+        match neighbour.map(|line| indentation(line)) {
+            Some(indent) if indent.len() > anchor_indent.len() => indent.to_string(),
+            _ => format!("{}{}", anchor_indent, indent_unit(&lines)),
+        }
+    };
+    let (index, indent) = match position {
+        InsertPosition::After => {
+            let next = lines[anchor_index + 1..]
+                .iter()
+                .find(|line| !line.trim().is_empty());
+            (anchor_index + 1, nested_indent(next))
+        }
+        // Before a closing line the new lines belong inside the block
+        InsertPosition::Before
+            if lines[anchor_index]
+                .trim_start()
+                .starts_with(['}', ']', ')']) =>
+        {
+            let previous = lines[..anchor_index]
+                .iter()
+                .rfind(|line| !line.trim().is_empty());
+            (anchor_index, nested_indent(previous))
+        }
+        InsertPosition::Before => (anchor_index, anchor_indent.to_string()),
+    };
+
+    let common = new_lines
+        .iter()
+        .map(|line| indentation(line).len())
+        .min()
+        .unwrap_or_default();
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let mut patched: Vec<String> = lines[..index].iter().map(|line| line.to_string()).collect();
+    patched.extend(insertion.lines().map(|line| {
+        if line.trim().is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", indent, &line[common..])
+        }
+    }));
+    patched.extend(lines[index..].iter().map(|line| line.to_string()));
+
+    let mut text = patched.join(newline);
+    if content.ends_with('\n') {
+        text.push_str(newline);
+    }
+    PatchOutcome::Inserted(text)
+}
+
+/// `./`-prefixed import path of `target` from a file in `dir`, which must
+/// be one of its ancestors
+///
+/// ```
+/// use cli_frontend::template_engine::patch::relative_import_path;
+/// use std::path::Path;
+///
+/// let feature = Path::new("./src/features/Checkout");
+/// assert_eq!(relative_import_path(Path::new("."), feature), "./src/features/Checkout");
+/// assert_eq!(relative_import_path(Path::new("./src"), feature), "./features/Checkout");
+/// ```
+pub fn relative_import_path(dir: &Path, target: &Path) -> String {
+    let relative = target.strip_prefix(dir).unwrap_or(target);
+    let segments: Vec<String> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(segment.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    if segments.is_empty() {
+        ".".to_string()
+    } else {
+        format!("./{}", segments.join("/"))
+    }
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// One level of indentation as used by the file: a tab, or the smallest
+/// space indent (two spaces when the file has none)
+fn indent_unit(lines: &[&str]) -> String {
+    if lines.iter().any(|line| line.starts_with('\t')) {
+        return "\t".to_string();
+    }
+    let width = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line).len())
+        .filter(|&width| width > 0)
+        .min()
+        .unwrap_or(2);
+    " ".repeat(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEBPACK: &str = "module.exports = {\n  plugins: [\n    new ModuleFederationPlugin({\n      name: 'shop',\n      exposes: {},\n      remotes: {\n      },\n    }),\n  ],\n};\n";
+
+    #[test]
+    fn test_insert_before_closing_line_and_into_empty_block() {
+        let PatchOutcome::Inserted(patched) = insert_at_anchor(
+            WEBPACK,
+            "remotes: {",
+            "cart: 'cart@http://localhost:3001/remoteEntry.js',",
+            InsertPosition::After,
+        ) else {
+            panic!("expected an insertion");
+        };
+        assert!(patched.contains(
+            "      remotes: {\n        cart: 'cart@http://localhost:3001/remoteEntry.js',\n      },\n"
+        ));
+
+        let PatchOutcome::Inserted(patched) = insert_at_anchor(
+            &patched,
+            "}),",
+            "filename: 'remoteEntry.js',",
+            InsertPosition::Before,
+        ) else {
+            panic!("expected an insertion");
+        };
+        assert!(patched.contains("      },\n      filename: 'remoteEntry.js',\n    }),"));
+    }
+
+    #[test]
+    fn test_missing_anchor_and_crlf() {
+        assert_eq!(
+            insert_at_anchor(WEBPACK, "shared: {", "react: {},", InsertPosition::After),
+            PatchOutcome::AnchorNotFound
+        );
+
+        let crlf = "exposes: {\r\n\t'./A': './A',\r\n}";
+        assert_eq!(
+            insert_at_anchor(crlf, "exposes", "'./B': './B',", InsertPosition::After),
+            PatchOutcome::Inserted("exposes: {\r\n\t'./B': './B',\r\n\t'./A': './A',\r\n}".into())
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Variables that name existing files.
+//!
+//! A variable declared with `{var}_type=path` must name an existing file or
+//! directory, or be a glob matching at least one file, relative to the
+//! working directory. Generation fails otherwise, and templates get derived
+//! values under `{var}_path`:
+//!
+//! - `absolute` - absolute path of the (first) match
+//! - `relative_to_output` - the match from the generated folder, `./` or
+//!   `../` prefixed and `/`-separated, ready for an import
+//! - `stem` - file name without its extension
+//! - `matches` - `relative_to_output` of every match, for globs
+//!
+//! `{{var}}` itself keeps the value as given.
+
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::ignore::matches_glob;
+
+/// `var_type` of variables resolved by [`resolve_path_value`]
+pub const PATH_TYPE: &str = "path";
+
+/// Values derived from a `path` variable, exposed as `{var}_path`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathValue {
+    pub absolute: String,
+    pub relative_to_output: String,
+    pub stem: String,
+    pub matches: Vec<String>,
+}
+
+/// Resolve a `path` variable's value against the working directory
+///
+/// `output_dir` is the folder files are generated into. Returns a message
+/// for [`crate::error::CliError::InvalidVariable`] when the path doesn't
+/// exist or the glob matches no file.
+pub fn resolve_path_value(value: &str, output_dir: &Path) -> Result<PathValue, String> {
+    let matches = if is_glob(value) {
+        let matches = glob_files(value);
+        if matches.is_empty() {
+            return Err(format!("no file matches '{}'", value));
+        }
+        matches
+    } else {
+        let path = PathBuf::from(value);
+        if !path.exists() {
+            return Err(format!("'{}' does not exist", value));
+        }
+        vec![path]
+    };
+
+    let output_dir = absolute(output_dir);
+    let matches: Vec<PathBuf> = matches.iter().map(|path| absolute(path)).collect();
+    let first = &matches[0];
+
+    Ok(PathValue {
+        absolute: first.display().to_string(),
+        relative_to_output: relative_path(&output_dir, first),
+        stem: first
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        matches: matches
+            .iter()
+            .map(|path| relative_path(&output_dir, path))
+            .collect(),
+    })
+}
+
+fn is_glob(value: &str) -> bool {
+    value.contains(['*', '?', '['])
+}
+
+/// Files matching `pattern`, sorted; the walk starts at its last
+/// directory without glob characters
+fn glob_files(pattern: &str) -> Vec<PathBuf> {
+    let pattern = pattern.replace('\\', "/");
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let literal = segments.iter().take_while(|s| !is_glob(s)).count();
+    let base = match segments[..literal].join("/") {
+        base if base.is_empty() && literal > 0 => "/".to_string(),
+        base if base.is_empty() => ".".to_string(),
+        base => base,
+    };
+    let rest = &segments[literal..];
+    let max_depth = if rest.contains(&"**") {
+        usize::MAX
+    } else {
+        rest.len()
+    };
+    let rest = rest.join("/");
+
+    let mut files: Vec<PathBuf> = WalkDir::new(&base)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry.path().strip_prefix(&base).is_ok_and(|relative| {
+                matches_glob(&rest, &relative.to_string_lossy().replace('\\', "/"))
+            })
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// `path` made absolute against the working directory, with `.` and `..`
+/// resolved lexically
+fn absolute(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// `/`-separated path of `target` from `dir`, both absolute
+fn relative_path(dir: &Path, target: &Path) -> String {
+    let dir: Vec<Component> = dir.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = dir.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        // Different drives
+        return target.iter().collect::<PathBuf>().display().to_string();
+    }
+
+    let mut parts = vec!["..".to_string(); dir.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+    );
+    match parts.first().map(String::as_str) {
+        Some("..") => parts.join("/"),
+        Some(_) => format!("./{}", parts.join("/")),
+        None => ".".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_relative_path() {
+        let root = absolute(Path::new("/project"));
+        assert_eq!(
+            relative_path(
+                &root.join("src/components/Card"),
+                &root.join("src/models/User.ts")
+            ),
+            "../../models/User.ts"
+        );
+        assert_eq!(
+            relative_path(&root.join("src"), &root.join("src/models/User.ts")),
+            "./models/User.ts"
+        );
+        assert_eq!(relative_path(&root, &root), ".");
+    }
+
+    #[test]
+    fn test_resolve_path_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let models = temp_dir.path().join("src").join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(models.join("User.ts"), "").unwrap();
+        std::fs::write(models.join("Order.ts"), "").unwrap();
+        let output = temp_dir.path().join("src").join("components").join("Card");
+
+        let user = models.join("User.ts");
+        let value = resolve_path_value(&user.display().to_string(), &output).unwrap();
+        assert_eq!(value.absolute, absolute(&user).display().to_string());
+        assert_eq!(value.relative_to_output, "../../models/User.ts");
+        assert_eq!(value.stem, "User");
+        assert_eq!(value.matches, ["../../models/User.ts"]);
+
+        let glob = format!("{}/*.ts", models.display());
+        let value = resolve_path_value(&glob, &output).unwrap();
+        assert_eq!(value.stem, "Order");
+        assert_eq!(
+            value.matches,
+            ["../../models/Order.ts", "../../models/User.ts"]
+        );
+
+        let missing = models.join("Missing.ts").display().to_string();
+        assert!(resolve_path_value(&missing, &output)
+            .unwrap_err()
+            .contains("does not exist"));
+        let glob = format!("{}/*.tsx", models.display());
+        assert!(resolve_path_value(&glob, &output)
+            .unwrap_err()
+            .contains("no file matches"));
+    }
+}
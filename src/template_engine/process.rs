@@ -0,0 +1,75 @@
+//! Running user-configured commands through the shell.
+//!
+//! `--verify` and `--open` both run a command line from the configuration
+//! (or the environment) with generated paths appended. The command goes
+//! through `sh -c`, or `cmd /C` on Windows, so the paths are quoted for
+//! that shell.
+
+use std::path::PathBuf;
+use tokio::process::Command;
+
+/// `command` run by the platform's shell
+#[cfg(windows)]
+pub(super) fn shell(command: &str) -> Command {
+    let mut process = Command::new("cmd");
+    process.args(["/C", command]);
+    process
+}
+
+/// `command` run by the platform's shell
+#[cfg(not(windows))]
+pub(super) fn shell(command: &str) -> Command {
+    let mut process = Command::new("sh");
+    process.args(["-c", command]);
+    process
+}
+
+/// Paths as shell arguments, quoted when needed
+pub(super) fn quote_paths(paths: &[PathBuf]) -> String {
+    let quote = if cfg!(windows) { quote_cmd } else { quote_sh };
+    paths
+        .iter()
+        .map(|path| {
+            let path = path.to_string_lossy();
+            if path
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "/\\._-:".contains(c))
+            {
+                path.into_owned()
+            } else {
+                quote(&path)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `arg` single-quoted for `sh`
+fn quote_sh(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// `arg` double-quoted for `cmd`: quotes are doubled, and `%` is escaped
+/// outside the quotes so it cannot expand a variable
+fn quote_cmd(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('"', "\"\"").replace('%', "\"^%\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_paths_leaves_plain_paths_alone() {
+        let paths = [PathBuf::from("src/Button.tsx"), PathBuf::from("C:\\x.ts")];
+        assert_eq!(quote_paths(&paths), "src/Button.tsx C:\\x.ts");
+    }
+
+    #[test]
+    fn test_quote_escapes_the_shell_specials() {
+        assert_eq!(quote_sh("it's here"), r"'it'\''s here'");
+        assert_eq!(quote_cmd("my dir"), "\"my dir\"");
+        assert_eq!(quote_cmd("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_cmd("100%PATH%"), "\"100\"^%\"PATH\"^%\"\"");
+    }
+}
@@ -0,0 +1,100 @@
+//! Per-phase timing of a generation run (`--profile-run`).
+//!
+//! The engine records how long each phase of a generation takes into a
+//! shared [`RunProfile`], which the CLI reports once the run is done. Phases
+//! are wall-clock times; files are rendered concurrently, so "render" covers
+//! all files rather than their sum.
+
+use colored::*;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::ui::{self, symbols};
+
+/// Accumulated wall-clock time per phase, in first-recorded order
+#[derive(Debug, Default)]
+pub struct RunProfile {
+    phases: Mutex<Vec<(&'static str, Duration)>>,
+}
+
+impl RunProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to `phase`, so phases repeated per structure are summed
+    pub fn record(&self, phase: &'static str, elapsed: Duration) {
+        let mut phases = self.phases.lock().unwrap();
+        match phases.iter_mut().find(|(name, _)| *name == phase) {
+            Some((_, total)) => *total += elapsed,
+            None => phases.push((phase, elapsed)),
+        }
+    }
+
+    /// Record the time elapsed since `start` under `phase`
+    pub fn record_since(&self, phase: &'static str, start: Instant) {
+        self.record(phase, start.elapsed());
+    }
+
+    /// Recorded phases and their total durations
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::RunProfile;
+    /// use std::time::Duration;
+    ///
+    /// let profile = RunProfile::new();
+    /// profile.record("render", Duration::from_millis(2));
+    /// profile.record("write", Duration::from_millis(1));
+    /// profile.record("render", Duration::from_millis(3));
+    ///
+    /// assert_eq!(
+    ///     profile.phases(),
+    ///     vec![("render", Duration::from_millis(5)), ("write", Duration::from_millis(1))]
+    /// );
+    /// ```
+    pub fn phases(&self) -> Vec<(&'static str, Duration)> {
+        self.phases.lock().unwrap().clone()
+    }
+
+    /// Write the timing breakdown with each phase's share of the total to `out`
+    pub fn write_report(&self, out: &mut dyn Write, total: Duration) -> std::io::Result<()> {
+        ui::show_to!(out)?;
+        ui::show_to!(out, "{} Timing breakdown:", symbols::TIMER.as_str().bold())?;
+
+        let total_secs = total.as_secs_f64().max(f64::EPSILON);
+        for (phase, elapsed) in self.phases() {
+            ui::show_to!(
+                out,
+                "  {:<20} {:>10}  {:>5.1}%",
+                phase,
+                format_duration(elapsed),
+                elapsed.as_secs_f64() / total_secs * 100.0
+            )?;
+        }
+        ui::show_to!(
+            out,
+            "  {:<20} {:>10}",
+            "total".bold(),
+            format_duration(total).bold()
+        )
+    }
+}
+
+/// Format a duration in milliseconds with microsecond precision
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500 ms");
+        assert_eq!(format_duration(Duration::ZERO), "0.000 ms");
+    }
+}
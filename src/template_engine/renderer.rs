@@ -8,23 +8,40 @@
 //! - `create_handlebars()` - Initialize Handlebars with all helpers
 //! - `create_template_data()` - Build data context with all variables
 //! - `render_template()` - Render template with Handlebars
+//! - `render_preview()` - Render template content in memory with the template's engine
+//! - `render_with_data()` - Render content against already built template data
 //! - `read_template()` - Read template file contents
 //! - `write_output()` - Write rendered content to file
+//! - `is_binary_file()` / `copy_raw_output()` - Pass binary assets through unrendered
+//!
+//! The file functions need the `runtime` feature; the rest is the pure
+//! rendering core that also builds for wasm32.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use handlebars::Handlebars;
+use handlebars::{Handlebars, HelperDef, Template};
 use serde_json::json;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "runtime")]
 use tokio::fs;
 use uuid::Uuid;
 
-use super::config::{TemplateConfig, VariableOption};
+use super::a11y::a11y_data;
+#[cfg(feature = "runtime")]
+use super::config::DEFAULT_MAX_TEMPLATE_SIZE;
+use super::config::{RenderEngine, TemplateConfig, VariableOption};
+use super::explain::{HelperCalls, TracedHelper};
+#[cfg(feature = "runtime")]
+use super::formatting::LineEndings;
 use super::handlebars_renderer::HandlebarsRenderer;
+use super::helpers::{cli_helpers, disabled_helper, restricted_env_helper, EnvDenied};
+use super::minijinja_renderer::MiniJinjaRenderer;
 use super::naming::{
-    apply_smart_filename_replacements, process_smart_names, to_camel_case, to_kebab_case,
-    to_pascal_case, to_snake_case, SmartNames,
+    apply_cased_filename_replacements, apply_smart_replacements, folder_name, process_smart_names,
+    route_params, route_path, to_camel_case, to_kebab_case, to_pascal_case, to_snake_case,
+    CaseStyle, SmartNames,
 };
+use super::renderer_trait::TemplateRenderer;
 
 /// Creates a Handlebars instance with all helpers registered.
 ///
@@ -48,10 +65,71 @@ use super::naming::{
 /// ).unwrap();
 /// assert_eq!(result, "HelloWorld");
 /// ```
+#[allow(dead_code)] // Public API
 pub fn create_handlebars() -> Handlebars<'static> {
     HandlebarsRenderer::create_handlebars_instance()
 }
 
+/// Creates a Handlebars instance set up for rendering with `config`.
+///
+/// Applies strict mode, and swaps the `timestamp` and `uuid` helpers for
+/// empty ones when the template disables them, since helpers take
+/// precedence over the `{{timestamp}}`/`{{uuid}}` variables.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::renderer::create_handlebars_for;
+/// use cli_frontend::template_engine::TemplateConfig;
+/// use serde_json::json;
+///
+/// let config = TemplateConfig { enable_uuid: false, ..Default::default() };
+/// let handlebars = create_handlebars_for(&config);
+/// assert_eq!(handlebars.render_template("id: {{uuid}}", &json!({})).unwrap(), "id: ");
+/// ```
+pub fn create_handlebars_for(config: &TemplateConfig) -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(config.strict_variables);
+    for (name, helper) in helpers_for(config) {
+        handlebars.register_helper(name, helper);
+    }
+    handlebars
+}
+
+/// Like [`create_handlebars_for`], with every helper call recorded into `calls` (`--explain`)
+pub fn create_traced_handlebars(
+    config: &TemplateConfig,
+    calls: &HelperCalls,
+) -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(config.strict_variables);
+    for (name, helper) in helpers_for(config) {
+        handlebars.register_helper(
+            name,
+            Box::new(TracedHelper::new(name, helper, calls.clone())),
+        );
+    }
+    handlebars
+}
+
+/// The CLI's helpers, with those `config` disables or restricts swapped out
+fn helpers_for(config: &TemplateConfig) -> Vec<(&'static str, Box<dyn HelperDef + Send + Sync>)> {
+    cli_helpers()
+        .into_iter()
+        .map(|(name, helper)| {
+            let helper: Box<dyn HelperDef + Send + Sync> = match name {
+                "timestamp" if !config.enable_timestamps => Box::new(disabled_helper),
+                "uuid" if !config.enable_uuid => Box::new(disabled_helper),
+                "env" if !config.env_policy.is_unrestricted() => {
+                    Box::new(restricted_env_helper(config.env_policy.clone()))
+                }
+                _ => helper,
+            };
+            (name, helper)
+        })
+        .collect()
+}
+
 /// Check if a value is truthy
 fn is_truthy(value: &str) -> bool {
     matches!(value.to_lowercase().as_str(), "true" | "yes" | "1")
@@ -108,7 +186,9 @@ pub fn generate_boolean_helpers(
 /// - Name variations (pascal, camel, snake, kebab)
 /// - Smart names (hook, context, provider, page)
 /// - Timestamps and UUIDs
-/// - User-defined variables from config
+/// - The target framework (`framework`, `is_native`, `platform`)
+/// - `route_path` and `route_params` for route templates (`filename_case=route`)
+/// - Imported data (e.g. `figma`, `tokens`) and user-defined variables from config
 /// - Dynamic boolean helpers
 ///
 /// # Arguments
@@ -148,6 +228,7 @@ pub fn create_template_data(name: &str, config: &TemplateConfig) -> serde_json::
         "context_name": processed_names.context_name,
         "provider_name": processed_names.provider_name,
         "page_name": processed_names.page_name,
+        "folder_name": folder_name(name, config.folder_case),
         "environment": config.environment,
         "timestamp": if config.enable_timestamps { now.to_rfc3339() } else { "".to_string() },
         "timestamp_iso": if config.enable_timestamps { now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string() } else { "".to_string() },
@@ -158,32 +239,389 @@ pub fn create_template_data(name: &str, config: &TemplateConfig) -> serde_json::
         "uuid_simple": if config.enable_uuid { current_uuid.simple().to_string() } else { "".to_string() },
         "version": env!("CARGO_PKG_VERSION"),
         "generator_name": "CLI Frontend Generator",
-        "generated": true
+        "generated": true,
+        "framework": config.framework.as_str(),
+        "is_native": config.framework.is_native(),
+        "platform": config.framework.default_platform(),
+        "comment_prefix": "",
+        "comment_suffix": "",
+        "license": "",
+        "author_name": "",
+        "author_email": "",
+        "a11y": a11y_data(name, config.variables.get("aria_role").map(String::as_str))
     });
 
     if let Some(data_map) = data.as_object_mut() {
+        data_map.extend(config.data.clone());
         for (key, value) in &config.variables {
             data_map.insert(key.clone(), serde_json::Value::String(value.clone()));
         }
         generate_boolean_helpers(&config.variables, &config.options_metadata, data_map);
+
+        // `author` is the variable templates used before `author_name`; each
+        // stands in for the other
+        match config.variables.get("author") {
+            Some(author) if !config.variables.contains_key("author_name") => {
+                data_map.insert("author_name".to_string(), author.clone().into());
+            }
+            _ if !data_map.contains_key("author") => {
+                let author_name = data_map["author_name"].clone();
+                data_map.insert("author".to_string(), author_name);
+            }
+            _ => {}
+        }
+
+        // Route templates (`filename_case=route`) get the URL and its params
+        if config.filename_case == Some(CaseStyle::Route) {
+            data_map.insert("route_path".to_string(), route_path(name).into());
+            data_map.insert("route_params".to_string(), route_params(name).into());
+        }
     }
 
     data
 }
 
+/// Apply name substitutions (`$FILE_NAME`, smart names, `$FOLDER_NAME`) to template content
+pub fn prepare_template_content(content: &str, name: &str, config: &TemplateConfig) -> String {
+    apply_smart_replacements(content, name, &process_smart_names(name))
+        .replace("$FOLDER_NAME", &folder_name(name, config.folder_case))
+}
+
+/// Render template content for `name` entirely in memory
+///
+/// Runs the same substitutions, data building and Handlebars rendering as
+/// the engine, without touching the file system, so it is available without
+/// the `runtime` feature (e.g. for a browser playground on wasm32).
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::renderer::render_preview;
+/// use cli_frontend::template_engine::TemplateConfig;
+///
+/// let mut config = TemplateConfig::default();
+/// config.variables.insert("with_state".to_string(), "true".to_string());
+///
+/// let output = render_preview(
+///     "export function use$FILE_NAME() {{#if with_state}}/* state */{{/if}}",
+///     "Auth",
+///     &config,
+/// )?;
+/// assert_eq!(output, "export function useAuth() /* state */");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[allow(dead_code)] // Public API
+pub fn render_preview(content: &str, name: &str, config: &TemplateConfig) -> Result<String> {
+    let data = create_template_data(name, config);
+    let content = prepare_template_content(content, name, config);
+    if config.engine == RenderEngine::MiniJinja {
+        return MiniJinjaRenderer::for_config(config).render(&content, &data);
+    }
+
+    let handlebars = create_handlebars_for(config);
+    if content.len() > RENDER_CHUNK_SIZE {
+        render_template_chunked(&handlebars, &content, &data, RENDER_CHUNK_SIZE)
+    } else {
+        render_template(&handlebars, &content, &data)
+    }
+}
+
+/// Render `content` against already built `data` with the template's engine
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::renderer::{create_template_data, render_with_data};
+/// use cli_frontend::template_engine::TemplateConfig;
+///
+/// let config = TemplateConfig::default();
+/// let data = create_template_data("user_card", &config);
+/// assert_eq!(render_with_data("{{pascal_case name}}", &config, &data)?, "UserCard");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn render_with_data(
+    content: &str,
+    config: &TemplateConfig,
+    data: &serde_json::Value,
+) -> Result<String> {
+    if config.engine == RenderEngine::MiniJinja {
+        return MiniJinjaRenderer::for_config(config).render(content, data);
+    }
+    render_template(&create_handlebars_for(config), content, data)
+}
+
 /// Render template with handlebars
+///
+/// On failure the error is a [`RenderFailure`] with the line, column and a
+/// snippet of the offending region.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::renderer::{create_handlebars, render_template, RenderFailure};
+///
+/// let handlebars = create_handlebars();
+/// let error = render_template(&handlebars, "ok\n{{#if flag}}\n", &serde_json::json!({}))
+///     .unwrap_err();
+///
+/// let failure = error.downcast_ref::<RenderFailure>().unwrap();
+/// assert_eq!(failure.line, Some(3));
+/// assert!(failure.snippet.contains("{{#if flag}}"));
+/// ```
 pub fn render_template(
     handlebars: &Handlebars,
     content: &str,
     data: &serde_json::Value,
+) -> Result<String> {
+    render_template_from_line(handlebars, content, data, 1)
+}
+
+/// Render `content`, numbering error positions from `first_line`
+fn render_template_from_line(
+    handlebars: &Handlebars,
+    content: &str,
+    data: &serde_json::Value,
+    first_line: usize,
 ) -> Result<String> {
     handlebars
         .render_template(content, data)
-        .with_context(|| "Template rendering failed")
+        .map_err(|e| RenderFailure::new(content, &e, first_line).into())
+}
+
+/// Lines of context shown before the failing line in a [`RenderFailure`]
+const SNIPPET_CONTEXT_LINES: usize = 2;
+
+/// A Handlebars or MiniJinja parse or render error located in the template source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderFailure {
+    /// 1-based line of the error, when the engine reports one
+    pub line: Option<usize>,
+    /// 1-based column of the error, when the engine reports one
+    pub column: Option<usize>,
+    /// The engine's description of the problem
+    pub reason: String,
+    /// Numbered source lines around the error, with a caret under the column
+    pub snippet: String,
+    /// The variable the `env` helper refused, when that failed the render
+    pub env_denied: Option<EnvDenied>,
+}
+
+impl RenderFailure {
+    /// Locate `error` in `content`, whose first line is numbered `first_line`
+    pub fn new(
+        content: &str,
+        error: &(dyn std::error::Error + 'static),
+        first_line: usize,
+    ) -> Self {
+        let (line, column, reason) = error_position(error);
+        let line = line.map(|line| line + first_line - 1);
+        let snippet = line
+            .map(|line| template_snippet(content, first_line, line, column))
+            .unwrap_or_default();
+        let env_denied = std::iter::successors(Some(error), |e| e.source())
+            .find_map(|e| e.downcast_ref::<EnvDenied>())
+            .cloned();
+
+        Self {
+            line,
+            column,
+            reason,
+            snippet,
+            env_denied,
+        }
+    }
+}
+
+impl std::fmt::Display for RenderFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(
+                f,
+                "Template rendering failed at line {}, column {}: {}",
+                line, column, self.reason
+            )?,
+            (Some(line), None) => write!(
+                f,
+                "Template rendering failed at line {}: {}",
+                line, self.reason
+            )?,
+            _ => write!(f, "Template rendering failed: {}", self.reason)?,
+        }
+
+        if !self.snippet.is_empty() {
+            write!(f, "\n{}", self.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RenderFailure {}
+
+/// Line, column and reason of the innermost located Handlebars or MiniJinja error
+fn error_position(
+    error: &(dyn std::error::Error + 'static),
+) -> (Option<usize>, Option<usize>, String) {
+    let mut current = Some(error);
+
+    while let Some(e) = current {
+        if let Some(e) = e.downcast_ref::<handlebars::TemplateError>() {
+            return (e.line_no, e.column_no, e.reason().to_string());
+        }
+        if let Some(e) = e.downcast_ref::<handlebars::RenderError>() {
+            if e.line_no.is_some() || std::error::Error::source(e).is_none() {
+                return (e.line_no, e.column_no, e.desc.clone());
+            }
+        }
+        if let Some(e) = e.downcast_ref::<minijinja::Error>() {
+            let reason = match e.detail() {
+                Some(detail) => format!("{}: {}", e.kind(), detail),
+                None => e.kind().to_string(),
+            };
+            return (e.line(), None, reason);
+        }
+        current = e.source();
+    }
+
+    (None, None, error.to_string())
+}
+
+/// Numbered lines of `content` ending at `line`, with a caret under `column`
+fn template_snippet(
+    content: &str,
+    first_line: usize,
+    line: usize,
+    column: Option<usize>,
+) -> String {
+    let start = line.saturating_sub(SNIPPET_CONTEXT_LINES).max(first_line);
+    let width = line.to_string().len();
+    let mut snippet = String::new();
+
+    for (number, text) in content
+        .split('\n')
+        .map(|text| text.trim_end_matches('\r'))
+        .enumerate()
+        .map(|(i, text)| (i + first_line, text))
+        .skip_while(|(number, _)| *number < start)
+        .take_while(|(number, _)| *number <= line)
+    {
+        let numbered = format!("{:>width$} | {}", number, text, width = width);
+        snippet.push_str(numbered.trim_end());
+        snippet.push('\n');
+    }
+
+    if let Some(column) = column {
+        snippet.push_str(&format!(
+            "{:>width$} | {}^\n",
+            "",
+            " ".repeat(column.saturating_sub(1)),
+            width = width
+        ));
+    }
+
+    snippet.trim_end().to_string()
+}
+
+/// Render a pre-compiled template (see `TemplateCache::compiled_template`)
+pub fn render_compiled(
+    handlebars: &mut Handlebars,
+    template: Template,
+    data: &serde_json::Value,
+) -> Result<String> {
+    const NAME: &str = "__compiled";
+    handlebars.register_template(NAME, template);
+    let rendered = handlebars
+        .render(NAME, data)
+        .with_context(|| "Template rendering failed");
+    handlebars.unregister_template(NAME);
+    rendered
+}
+
+/// Templates larger than this are rendered in chunks when possible
+pub const RENDER_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Render a large template in newline-aligned chunks
+///
+/// Handlebars parsing time grows faster than linearly with template size, so
+/// big templates made of plain text and inline expressions (typically fixture
+/// data) are split into chunks of roughly `chunk_size` bytes and rendered one
+/// by one. Output is identical to [`render_template`]. Templates using blocks,
+/// partials, comments or whitespace control can't be split safely and are
+/// rendered in one pass.
+pub fn render_template_chunked(
+    handlebars: &Handlebars,
+    content: &str,
+    data: &serde_json::Value,
+    chunk_size: usize,
+) -> Result<String> {
+    if content.len() <= chunk_size || !can_render_in_chunks(content) {
+        return render_template(handlebars, content, data);
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut start = 0;
+    let mut first_line = 1;
+
+    while start < content.len() {
+        let end = next_chunk_end(content, start, chunk_size);
+        let chunk = &content[start..end];
+        output.push_str(&render_template_from_line(
+            handlebars, chunk, data, first_line,
+        )?);
+        first_line += chunk.matches('\n').count();
+        start = end;
+    }
+
+    Ok(output)
+}
+
+/// Whether a template only uses constructs that render independently per line
+fn can_render_in_chunks(content: &str) -> bool {
+    const BLOCK_MARKERS: [&str; 8] = ["{{#", "{{/", "{{else", "{{^", "{{>", "{{!", "{{~", "~}}"];
+    !BLOCK_MARKERS.iter().any(|marker| content.contains(marker))
+}
+
+/// Find the end of the chunk starting at `start`: the first newline after
+/// `chunk_size` bytes that isn't inside an open `{{ ... }}` expression
+fn next_chunk_end(content: &str, start: usize, chunk_size: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = start;
+
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{{") {
+            depth += 1;
+            i += 2;
+            continue;
+        }
+        if bytes[i..].starts_with(b"}}") {
+            depth = depth.saturating_sub(1);
+            i += 2;
+            continue;
+        }
+        if bytes[i] == b'\n' && depth == 0 && i + 1 - start >= chunk_size {
+            return i + 1;
+        }
+        i += 1;
+    }
+
+    bytes.len()
 }
 
 /// Read template file content with optimized buffering
+#[cfg(feature = "runtime")]
+#[allow(dead_code)] // Public API
 pub async fn read_template(path: &Path) -> Result<String> {
+    read_template_with_limit(path, DEFAULT_MAX_TEMPLATE_SIZE).await
+}
+
+/// Read template file content, refusing files larger than `max_size` bytes
+///
+/// Rendering needs the whole file (and a rendered copy) in memory, so very
+/// large files fail early with a hint instead of exhausting memory. Files
+/// that don't need rendering can be listed under `[raw]`, which copies them
+/// without loading them.
+#[cfg(feature = "runtime")]
+pub async fn read_template_with_limit(path: &Path, max_size: u64) -> Result<String> {
     use tokio::io::AsyncReadExt;
 
     let file = fs::File::open(path)
@@ -196,6 +634,17 @@ pub async fn read_template(path: &Path) -> Result<String> {
         .await
         .with_context(|| format!("Could not get file metadata: {}", path.display()))?;
 
+    if metadata.len() > max_size {
+        anyhow::bail!(
+            "Template file {} is {} bytes, over the {} byte limit for rendering.\n\
+             List it under [raw] in the template's .conf to copy it without rendering, \
+             or raise max_template_size.",
+            path.display(),
+            metadata.len(),
+            max_size
+        );
+    }
+
     let mut buffer = String::with_capacity(metadata.len() as usize);
     let mut reader = tokio::io::BufReader::new(file);
 
@@ -207,16 +656,82 @@ pub async fn read_template(path: &Path) -> Result<String> {
     Ok(buffer)
 }
 
+/// Number of leading bytes inspected when sniffing for binary content
+#[cfg(feature = "runtime")]
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// Check whether a template file is binary (and must not be rendered)
+///
+/// Like git, looks for a NUL byte in the first few kilobytes; content that
+/// is not valid UTF-8 there is treated as binary too.
+#[cfg(feature = "runtime")]
+pub async fn is_binary_file(path: &Path) -> Result<bool> {
+    use tokio::io::AsyncReadExt;
+
+    let mut file = fs::File::open(path)
+        .await
+        .with_context(|| format!("Could not read template file: {}", path.display()))?;
+
+    let mut buffer = vec![0u8; BINARY_SNIFF_LEN];
+    let mut len = 0;
+    while len < buffer.len() {
+        let read = file
+            .read(&mut buffer[len..])
+            .await
+            .with_context(|| format!("Could not read template file: {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        len += read;
+    }
+
+    Ok(is_binary(&buffer[..len]))
+}
+
+/// Check whether a byte prefix looks like binary content
+#[cfg(feature = "runtime")]
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => false,
+        // A multi-byte character cut off by the sniff window is still text
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Copy a template file verbatim to the output path
+#[cfg(feature = "runtime")]
+#[allow(dead_code)] // Public API
+pub async fn copy_raw_output(source: &Path, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Could not create parent directory: {}", parent.display()))?;
+    }
+
+    fs::copy(source, path)
+        .await
+        .with_context(|| format!("Could not copy file to: {}", path.display()))?;
+
+    Ok(())
+}
+
 /// Determine final output path with smart filename replacements
+///
+/// `filename_case` applies the template's case policy to the substituted
+/// name; `None` keeps the default PascalCase/smart-name behavior.
 pub fn determine_output_path(
     base: &Path,
     name: &str,
     processed_names: &SmartNames,
+    filename_case: Option<CaseStyle>,
 ) -> Result<PathBuf> {
     let output_filename = base
         .file_name()
         .and_then(|n| n.to_str())
-        .map(|n| apply_smart_filename_replacements(n, name, processed_names))
+        .map(|n| apply_cased_filename_replacements(n, name, processed_names, filename_case))
         .context("Invalid output filename")?;
 
     Ok(base
@@ -225,20 +740,117 @@ pub fn determine_output_path(
         .join(output_filename))
 }
 
+/// Resolve tokens in the directories (and `{{...}}` in the file name) of an output path
+///
+/// Directories named with `$FILE_NAME` take the name in the template's
+/// `folder_case`, and `{{...}}` expressions render with the template data,
+/// so a template can lay out `{{kebab_name}}/components/$FILE_NAME.tsx`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::renderer::{create_template_data, resolve_path_tokens};
+/// use cli_frontend::template_engine::TemplateConfig;
+/// use std::path::Path;
+///
+/// let config = TemplateConfig::default();
+/// let data = create_template_data("UserCard", &config);
+/// let path = resolve_path_tokens(
+///     Path::new("out/{{kebab_name}}/$FILE_NAME/UserCard.tsx"),
+///     "UserCard",
+///     &data,
+///     &config,
+/// )?;
+/// assert_eq!(path, Path::new("out/user-card/UserCard/UserCard.tsx"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn resolve_path_tokens(
+    path: &Path,
+    name: &str,
+    data: &serde_json::Value,
+    config: &TemplateConfig,
+) -> Result<PathBuf> {
+    let has_tokens = |segment: &str| segment.contains("{{") || segment.contains("$FILE_NAME");
+    if !path.to_str().is_some_and(has_tokens) {
+        return Ok(path.to_path_buf());
+    }
+
+    let file_name = path.file_name().and_then(|n| n.to_str());
+    let mut resolved = PathBuf::new();
+    for component in path.parent().unwrap_or(Path::new("")).components() {
+        match component.as_os_str().to_str() {
+            Some(segment) if has_tokens(segment) => {
+                let segment = segment.replace("$FILE_NAME", &folder_name(name, config.folder_case));
+                resolved.push(render_path_segment(&segment, data, config)?);
+            }
+            _ => resolved.push(component),
+        }
+    }
+    match file_name {
+        Some(file_name) if file_name.contains("{{") => {
+            resolved.push(render_path_segment(file_name, data, config)?)
+        }
+        _ => resolved.push(path.file_name().context("Invalid output filename")?),
+    }
+    Ok(resolved)
+}
+
+/// Render the `{{...}}` of one path segment, which must stay a single name
+fn render_path_segment(
+    segment: &str,
+    data: &serde_json::Value,
+    config: &TemplateConfig,
+) -> Result<String> {
+    if !segment.contains("{{") {
+        return Ok(segment.to_string());
+    }
+    let rendered = if config.engine == RenderEngine::MiniJinja {
+        MiniJinjaRenderer::for_config(config).render(segment, data)
+    } else {
+        render_template(&create_handlebars_for(config), segment, data)
+    }
+    .with_context(|| format!("Could not resolve path segment '{}'", segment))?;
+
+    let rendered = rendered.trim();
+    if rendered.is_empty() || rendered == "." || rendered == ".." || rendered.contains(['/', '\\'])
+    {
+        anyhow::bail!(
+            "Path segment '{}' resolved to '{}', which is not a valid file or directory name",
+            segment,
+            rendered
+        );
+    }
+    Ok(rendered.to_string())
+}
+
 /// Write output file with content
-pub async fn write_output(path: &Path, content: &str) -> Result<()> {
+///
+/// When `line_endings` is set, every line ending is converted to it before
+/// writing; otherwise the rendered endings are kept.
+#[cfg(feature = "runtime")]
+#[allow(dead_code)] // Public API
+pub async fn write_output(
+    path: &Path,
+    content: &str,
+    line_endings: Option<LineEndings>,
+) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .await
             .with_context(|| format!("Could not create parent directory: {}", parent.display()))?;
     }
 
-    fs::write(path, content)
+    let content = match line_endings {
+        Some(endings) => endings.apply(content),
+        None => content.into(),
+    };
+
+    fs::write(path, content.as_bytes())
         .await
         .with_context(|| format!("Could not write output file: {}", path.display()))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "runtime"))]
 mod tests {
     use super::*;
     use tempfile::TempDir;
@@ -265,7 +877,9 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let nested_path = temp_dir.path().join("nested").join("dir").join("file.txt");
 
-        write_output(&nested_path, "test content").await.unwrap();
+        write_output(&nested_path, "test content", None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&nested_path).await.unwrap();
         assert_eq!(content, "test content");
@@ -276,7 +890,9 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let output_path = temp_dir.path().join("output.txt");
 
-        write_output(&output_path, "Hello World!").await.unwrap();
+        write_output(&output_path, "Hello World!", None)
+            .await
+            .unwrap();
 
         let content = fs::read_to_string(&output_path).await.unwrap();
         assert_eq!(content, "Hello World!");
@@ -298,6 +914,55 @@ mod tests {
         assert_eq!(result, "hello_world");
     }
 
+    #[test]
+    fn test_render_failure_reports_position_and_snippet() {
+        let handlebars = create_handlebars();
+        let content = "line one\nline two\nline three\nconst x = {{missing_helper name}};\n";
+        let error = render_template(&handlebars, content, &json!({})).unwrap_err();
+
+        let failure = error.downcast_ref::<RenderFailure>().unwrap();
+        assert_eq!(failure.line, Some(4));
+        assert_eq!(
+            failure.snippet,
+            "2 | line two\n3 | line three\n4 | const x = {{missing_helper name}};\n  |           ^"
+        );
+        assert!(error
+            .to_string()
+            .starts_with("Template rendering failed at line 4, column 11"));
+    }
+
+    #[test]
+    fn test_chunked_render_failure_reports_absolute_line() {
+        let handlebars = create_handlebars();
+        let mut content = "{{name}}\n".repeat(100);
+        content.push_str("{{missing_helper name}}\n");
+
+        let error =
+            render_template_chunked(&handlebars, &content, &json!({"name": "x"}), 64).unwrap_err();
+        let failure = error.downcast_ref::<RenderFailure>().unwrap();
+        assert_eq!(failure.line, Some(101));
+        assert!(failure.snippet.contains("101 | {{missing_helper name}}"));
+    }
+
+    #[test]
+    fn test_render_preview_matches_engine_substitutions() {
+        let mut config = TemplateConfig {
+            folder_case: Some(CaseStyle::Kebab),
+            ..Default::default()
+        };
+        config
+            .variables
+            .insert("style".to_string(), "scss".to_string());
+
+        let output = render_preview(
+            "$FOLDER_NAME/$FILE_NAME.{{style}} {{pascal_name}}",
+            "user profile",
+            &config,
+        )
+        .unwrap();
+        assert_eq!(output, "user-profile/user profile.scss UserProfile");
+    }
+
     #[test]
     fn test_create_template_data_basic() {
         let config = TemplateConfig::default();
@@ -310,6 +975,7 @@ mod tests {
         assert_eq!(data["camel_name"], "myComponent");
         assert_eq!(data["upper_name"], "MYCOMPONENT");
         assert_eq!(data["generated"], true);
+        assert_eq!(data["folder_name"], "MyComponent");
         assert!(data["timestamp"].as_str().is_some());
         assert!(data["uuid"].as_str().is_some());
     }
@@ -331,6 +997,30 @@ mod tests {
         assert_eq!(data["name"], "TestComponent");
     }
 
+    #[test]
+    fn test_create_template_data_aliases_author_and_author_name() {
+        let mut config = TemplateConfig::default();
+        config.data.insert("author_name".to_string(), "Ana".into());
+        let data = create_template_data("Button", &config);
+        assert_eq!(data["author"], "Ana");
+
+        // `--var author=...` still names the author
+        config
+            .variables
+            .insert("author".to_string(), "Bo".to_string());
+        let data = create_template_data("Button", &config);
+        assert_eq!(data["author_name"], "Bo");
+
+        config
+            .variables
+            .insert("author_name".to_string(), "Cy".to_string());
+        let data = create_template_data("Button", &config);
+        assert_eq!(
+            (&data["author"], &data["author_name"]),
+            (&"Bo".into(), &"Cy".into())
+        );
+    }
+
     #[test]
     fn test_render_template_basic() {
         let handlebars = create_handlebars();
@@ -361,7 +1051,7 @@ mod tests {
         let name = "MyComponent";
         let processed_names = process_smart_names(name);
 
-        let result = determine_output_path(base, name, &processed_names).unwrap();
+        let result = determine_output_path(base, name, &processed_names, None).unwrap();
         assert_eq!(result.file_name().unwrap(), "MyComponent.tsx");
     }
 
@@ -371,10 +1061,62 @@ mod tests {
         let name = "MyHook";
         let processed_names = process_smart_names(name);
 
-        let result = determine_output_path(base, name, &processed_names).unwrap();
+        let result = determine_output_path(base, name, &processed_names, None).unwrap();
         assert_eq!(result.file_name().unwrap(), "useMyHook.ts");
     }
 
+    #[test]
+    fn test_determine_output_path_with_filename_case() {
+        let base = Path::new("output/$FILE_NAME.component.tsx");
+        let name = "UserProfile";
+        let processed_names = process_smart_names(name);
+
+        let result =
+            determine_output_path(base, name, &processed_names, Some(CaseStyle::Kebab)).unwrap();
+        assert_eq!(result.file_name().unwrap(), "user-profile.component.tsx");
+    }
+
+    #[test]
+    fn test_resolve_path_tokens_in_directories() {
+        let config = TemplateConfig {
+            folder_case: Some(CaseStyle::Kebab),
+            ..Default::default()
+        };
+        let mut data = create_template_data("UserProfile", &config);
+        data["feature"] = json!("accounts");
+
+        let path = resolve_path_tokens(
+            Path::new("out/{{feature}}/$FILE_NAME/components/{{snake_name}}.rs"),
+            "UserProfile",
+            &data,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            path,
+            Path::new("out/accounts/user-profile/components/user_profile.rs")
+        );
+
+        // Plain paths are left alone
+        let plain = Path::new("out/src/index.ts");
+        assert_eq!(
+            resolve_path_tokens(plain, "UserProfile", &data, &config).unwrap(),
+            plain
+        );
+
+        data["feature"] = json!("");
+        let error = resolve_path_tokens(
+            Path::new("out/{{feature}}/a.ts"),
+            "UserProfile",
+            &data,
+            &config,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("not a valid file or directory name"));
+    }
+
     #[test]
     fn test_generate_boolean_helpers() {
         let mut variables = std::collections::HashMap::new();
@@ -388,6 +1130,7 @@ mod tests {
                 var_type: "enum".to_string(),
                 possible_values: vec!["scss".to_string(), "css".to_string(), "none".to_string()],
                 description: "Style approach".to_string(),
+                ..Default::default()
             },
         );
         options_metadata.insert(
@@ -396,6 +1139,7 @@ mod tests {
                 var_type: "boolean".to_string(),
                 possible_values: vec![],
                 description: "Include tests".to_string(),
+                ..Default::default()
             },
         );
 
@@ -452,6 +1196,7 @@ mod tests {
                 var_type: "enum".to_string(),
                 possible_values: vec!["scss".to_string(), "styled-components".to_string()],
                 description: "Style approach".to_string(),
+                ..Default::default()
             },
         );
 
@@ -462,4 +1207,87 @@ mod tests {
         assert_eq!(data_map["style_is_styled_components"], true);
         assert_eq!(data_map["style_is_scss"], false);
     }
+
+    #[tokio::test]
+    async fn test_is_binary_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let text_path = temp_dir.path().join("text.tsx");
+        let png_path = temp_dir.path().join("icon.png");
+
+        fs::write(&text_path, "export const ñ = 1;").await.unwrap();
+        fs::write(
+            &png_path,
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00],
+        )
+        .await
+        .unwrap();
+
+        assert!(!is_binary_file(&text_path).await.unwrap());
+        assert!(is_binary_file(&png_path).await.unwrap());
+        assert!(is_binary(&[0xFF, 0xFE, b'a']));
+        assert!(!is_binary("é".as_bytes()[..1].as_ref()));
+    }
+
+    #[tokio::test]
+    async fn test_copy_raw_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("font.woff2");
+        let dest = temp_dir.path().join("out").join("font.woff2");
+        let bytes = [0u8, 1, 2, 255];
+
+        fs::write(&source, bytes).await.unwrap();
+        copy_raw_output(&source, &dest).await.unwrap();
+
+        assert_eq!(fs::read(&dest).await.unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn test_read_template_with_limit_rejects_large_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let template_path = temp_dir.path().join("fixture.json");
+
+        fs::write(&template_path, "x".repeat(64)).await.unwrap();
+
+        assert!(read_template_with_limit(&template_path, 64).await.is_ok());
+        let error = read_template_with_limit(&template_path, 63)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("over the 63 byte limit"));
+        assert!(error.contains("[raw]"));
+    }
+
+    #[test]
+    fn test_render_template_chunked_matches_single_pass() {
+        let handlebars = create_handlebars();
+        let data = json!({"name": "user_profile"});
+        let content = "{ \"owner\": \"{{pascal_case name}}\", \"id\": 1 },\n".repeat(200);
+
+        let single = render_template(&handlebars, &content, &data).unwrap();
+        let chunked = render_template_chunked(&handlebars, &content, &data, 256).unwrap();
+
+        assert_eq!(chunked, single);
+        assert!(chunked.starts_with("{ \"owner\": \"UserProfile\""));
+    }
+
+    #[test]
+    fn test_render_template_chunked_keeps_blocks_whole() {
+        let handlebars = create_handlebars();
+        let data = json!({"flag": true});
+        let content = format!("{{{{#if flag}}}}\n{}{{{{/if}}}}\n", "line\n".repeat(100));
+
+        let rendered = render_template_chunked(&handlebars, &content, &data, 16).unwrap();
+        assert_eq!(
+            rendered,
+            render_template(&handlebars, &content, &data).unwrap()
+        );
+        assert!(!can_render_in_chunks(&content));
+    }
+
+    #[test]
+    fn test_next_chunk_end_skips_open_expressions() {
+        let content = "aaaa {{name\n}} bbbb\ncccc\n";
+        // The first newline is inside `{{ ... }}`, so the chunk ends at the second
+        assert_eq!(next_chunk_end(content, 0, 1), 20);
+    }
 }
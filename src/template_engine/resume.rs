@@ -0,0 +1,145 @@
+//! Progress of a feature run that failed partway (`--resume`).
+//!
+//! With `--keep-going`, a feature whose structure N fails keeps the
+//! structures that did complete. Those are recorded in [`RESUME_FILE`] in
+//! the output directory, so running again with `--resume` continues from
+//! the failed structure instead of generating the written layers again.
+//! The file is removed once a run completes.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::ArchitectureStructure;
+use crate::file_system::FileSystem;
+
+/// File recording the progress of a failed feature run
+pub const RESUME_FILE: &str = ".cli-frontend-resume.json";
+
+/// Structures a feature run completed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureProgress {
+    /// Architecture the run used
+    pub architecture: String,
+    /// Completed structures of each feature, by feature name
+    pub completed: BTreeMap<String, Vec<CompletedStructure>>,
+}
+
+/// A structure, identified by its path and template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedStructure {
+    pub path: String,
+    pub template: String,
+}
+
+impl FeatureProgress {
+    pub fn new(architecture: &str) -> Self {
+        Self {
+            architecture: architecture.to_string(),
+            completed: BTreeMap::new(),
+        }
+    }
+
+    /// The progress recorded in `dir`, if a run left one
+    pub async fn load(fs: &dyn FileSystem, dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(RESUME_FILE);
+        let Some(bytes) = fs.read_existing(&path).await? else {
+            return Ok(None);
+        };
+        let progress = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(Some(progress))
+    }
+
+    pub async fn save(&self, fs: &dyn FileSystem, dir: &Path) -> Result<()> {
+        let path = dir.join(RESUME_FILE);
+        let json = serde_json::to_vec_pretty(self).context("Could not serialize progress")?;
+        fs.write(&path, &json)
+            .await
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Remove the progress recorded in `dir`, if any
+    pub async fn clear(fs: &dyn FileSystem, dir: &Path) -> Result<()> {
+        let path = dir.join(RESUME_FILE);
+        if fs.exists(&path) {
+            fs.remove_file(&path)
+                .await
+                .with_context(|| format!("Could not remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Whether `structure` of `feature` was completed
+    pub fn is_complete(&self, feature: &str, structure: &ArchitectureStructure) -> bool {
+        self.completed.get(feature).is_some_and(|structures| {
+            structures
+                .iter()
+                .any(|done| done.path == structure.path && done.template == structure.template)
+        })
+    }
+
+    /// Record `structure` of `feature` as completed
+    pub fn complete(&mut self, feature: &str, structure: &ArchitectureStructure) {
+        if !self.is_complete(feature, structure) {
+            self.completed
+                .entry(feature.to_string())
+                .or_default()
+                .push(CompletedStructure {
+                    path: structure.path.clone(),
+                    template: structure.template.clone(),
+                });
+        }
+    }
+
+    /// Number of completed structures across features
+    pub fn len(&self) -> usize {
+        self.completed.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::MemoryFileSystem;
+
+    fn structure(path: &str, template: &str) -> ArchitectureStructure {
+        ArchitectureStructure {
+            path: path.to_string(),
+            template: template.to_string(),
+            filename_pattern: "{name}".to_string(),
+            description: String::new(),
+            exports: Default::default(),
+            params: Default::default(),
+            output_root: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_roundtrip() {
+        let fs = MemoryFileSystem::new();
+        let dir = Path::new("/out");
+        assert_eq!(FeatureProgress::load(&fs, dir).await.unwrap(), None);
+
+        let mut progress = FeatureProgress::new("layers");
+        progress.complete("Auth", &structure("ui", "view"));
+        progress.complete("Auth", &structure("ui", "view"));
+        assert_eq!(progress.len(), 1);
+        assert!(progress.is_complete("Auth", &structure("ui", "view")));
+        assert!(!progress.is_complete("Auth", &structure("ui", "store")));
+        assert!(!progress.is_complete("Billing", &structure("ui", "view")));
+
+        progress.save(&fs, dir).await.unwrap();
+        assert_eq!(
+            FeatureProgress::load(&fs, dir).await.unwrap(),
+            Some(progress)
+        );
+        FeatureProgress::clear(&fs, dir).await.unwrap();
+        assert_eq!(FeatureProgress::load(&fs, dir).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Undo log for a generation run.
+//!
+//! A run writes many files, and a failure halfway through (a render error,
+//! a conflict, a full disk) used to leave half a component behind.
+//! [`WriteJournal`] stages the run's files (see [`super::staging`]) and
+//! commits them together, recording the directories it creates and the
+//! files it writes, with their previous contents, so a commit that fails
+//! partway can be rolled back and folders that end up empty can be removed
+//! after a successful run.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::staging::Staging;
+use crate::file_system::FileSystem;
+
+/// Directories and files touched by one generation run
+#[derive(Default)]
+pub struct WriteJournal {
+    /// Files and directories waiting to be committed
+    pub staging: Staging,
+    /// Directories that didn't exist before the run, parents first
+    created_dirs: Vec<PathBuf>,
+    /// Files written by the run, with their contents beforehand (`None` if new)
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    /// Files skipped because they failed (`--keep-going`)
+    failures: Vec<anyhow::Error>,
+}
+
+impl WriteJournal {
+    /// Record the missing directories of `dir`; call before creating it
+    pub fn track_dir(&mut self, fs: &dyn FileSystem, dir: &Path) {
+        let missing: Vec<PathBuf> = dir
+            .ancestors()
+            .take_while(|ancestor| !ancestor.as_os_str().is_empty() && !fs.exists(ancestor))
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in missing.into_iter().rev() {
+            if !self.created_dirs.contains(&dir) {
+                self.created_dirs.push(dir);
+            }
+        }
+    }
+
+    /// Record a file about to be written, with its current contents
+    pub fn track_file(&mut self, fs: &dyn FileSystem, path: &Path, previous: Option<Vec<u8>>) {
+        if let Some(parent) = path.parent() {
+            self.track_dir(fs, parent);
+        }
+        if !self.files.iter().any(|(tracked, _)| tracked == path) {
+            self.files.push((path.to_path_buf(), previous));
+        }
+    }
+
+    /// Record a file that failed, so the run can carry on without it
+    pub fn record_failure(&mut self, error: anyhow::Error) {
+        crate::ui::warning!("Warning: {:#}", error);
+        self.failures.push(error);
+    }
+
+    /// The first recorded failure and how many there were
+    pub fn take_failures(&mut self) -> Option<(anyhow::Error, usize)> {
+        let count = self.failures.len();
+        let first = self.failures.drain(..).next()?;
+        Some((first, count))
+    }
+
+    /// Number of failures recorded so far
+    pub fn failures(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Number of files written so far
+    pub fn written(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Move everything staged into place
+    ///
+    /// Nothing is written unless the staged files pass the collision checks.
+    /// The staging directory is removed whether or not the commit succeeds.
+    pub async fn commit(&mut self, fs: &dyn FileSystem) -> Result<()> {
+        let result = self.commit_staged(fs).await;
+        self.staging.discard().await;
+        result
+    }
+
+    async fn commit_staged(&mut self, fs: &dyn FileSystem) -> Result<()> {
+        self.staging.check_collisions(fs).await?;
+
+        for dir in self.staging.dirs().to_vec() {
+            self.track_dir(fs, &dir);
+            fs.create_dir_all(&dir).await?;
+        }
+        for index in 0..self.staging.files().len() {
+            let file = &self.staging.files()[index];
+            let (target, staged, mode) = (file.target.clone(), file.staged.clone(), file.mode);
+            self.track_file(fs, &target, file.previous.clone());
+            fs.move_file(&staged, &target).await?;
+            if let Some(mode) = mode {
+                fs.set_permissions(&target, mode).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restore replaced files, delete new ones and remove the directories
+    /// the run created
+    pub async fn roll_back(&self, fs: &dyn FileSystem) -> Result<()> {
+        for (path, previous) in self.files.iter().rev() {
+            match previous {
+                Some(contents) => fs.write(path, contents).await?,
+                None => fs.remove_file(path).await?,
+            }
+        }
+        self.remove_empty_dirs(fs).await
+    }
+
+    /// Remove the directories the run created that are still empty
+    pub async fn remove_empty_dirs(&self, fs: &dyn FileSystem) -> Result<()> {
+        for dir in self.created_dirs.iter().rev() {
+            fs.remove_empty_dir(dir).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::TokioFileSystem;
+
+    #[tokio::test]
+    async fn test_commit_moves_staged_files_into_place() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let target = temp_dir.path().join("Button").join("Button.tsx");
+
+        let fs = TokioFileSystem;
+        let mut journal = WriteJournal::default();
+        journal
+            .staging
+            .stage(&target, b"export {};", None, Some(0o755))
+            .await
+            .unwrap();
+        assert!(!target.exists());
+
+        journal.commit(&fs).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "export {};");
+        assert_eq!(journal.written(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roll_back_restores_and_removes_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let existing = temp_dir.path().join("index.ts");
+        std::fs::write(&existing, "before").unwrap();
+        let folder = temp_dir.path().join("Button");
+        let created = folder.join("styles").join("Button.css");
+
+        let fs = TokioFileSystem;
+        let mut journal = WriteJournal::default();
+        journal.track_dir(&fs, &folder);
+        fs.create_dir_all(&folder).await.unwrap();
+        journal.track_file(&fs, &created, None);
+        fs.write(&created, b"new").await.unwrap();
+        journal.track_file(&fs, &existing, Some(b"before".to_vec()));
+        fs.write(&existing, b"after").await.unwrap();
+        assert_eq!(journal.written(), 2);
+
+        journal.roll_back(&fs).await.unwrap();
+        assert!(!folder.exists());
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "before");
+    }
+}
@@ -0,0 +1,260 @@
+//! Variable dependency rules (`[rules]` in .conf).
+//!
+//! Rules relate two variable conditions and are checked, in declaration
+//! order, once `--var` values have been merged over the template defaults:
+//!
+//! ```ini
+//! [rules]
+//! with_stories requires with_tests
+//! style=none implies !with_css_module
+//! ssr=true conflicts with_portal
+//! ```
+//!
+//! A bare name holds when the variable is truthy, `!name` when it is not,
+//! and `name=value` when it equals `value`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::CliError;
+
+/// How a rule's consequence relates to its condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    /// The consequence must hold whenever the condition does
+    Requires,
+    /// The consequence is set whenever the condition holds
+    Implies,
+    /// The condition and the consequence can't both hold
+    Conflicts,
+}
+
+/// A test on a single variable: `name`, `!name` or `name=value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableCondition {
+    pub variable: String,
+    /// Expected value; `None` tests truthiness
+    pub value: Option<String>,
+    /// Written as `!name`: holds when the variable is not truthy
+    pub negated: bool,
+}
+
+/// A `[rules]` entry such as `with_stories requires with_tests`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableRule {
+    pub condition: VariableCondition,
+    pub kind: RuleKind,
+    pub consequence: VariableCondition,
+}
+
+impl VariableCondition {
+    fn holds(&self, vars: &HashMap<String, String>) -> bool {
+        let value = vars.get(&self.variable).map(String::as_str);
+        match &self.value {
+            Some(expected) => value == Some(expected.as_str()),
+            None => value.is_some_and(is_truthy) != self.negated,
+        }
+    }
+
+    /// The value that makes this condition hold
+    fn satisfying_value(&self) -> String {
+        match &self.value {
+            Some(value) => value.clone(),
+            None => (!self.negated).to_string(),
+        }
+    }
+}
+
+impl FromStr for VariableCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negated, s) = match s.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (variable, value) = match s.split_once('=') {
+            Some((variable, value)) => (variable.trim(), Some(value.trim().to_string())),
+            None => (s.trim(), None),
+        };
+
+        if variable.is_empty() || (negated && value.is_some()) {
+            return Err(format!("invalid condition '{}'", s));
+        }
+
+        Ok(Self {
+            variable: variable.to_string(),
+            value,
+            negated,
+        })
+    }
+}
+
+impl fmt::Display for VariableCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}={}", self.variable, value),
+            None if self.negated => write!(f, "!{}", self.variable),
+            None => write!(f, "{}", self.variable),
+        }
+    }
+}
+
+impl FromStr for VariableRule {
+    type Err = String;
+
+    /// Parse `<condition> requires|implies|conflicts <condition>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let [condition, kind, consequence] = parts.as_slice() else {
+            return Err(format!(
+                "expected '<condition> requires|implies|conflicts <condition>', got '{}'",
+                s
+            ));
+        };
+
+        let kind = match *kind {
+            "requires" => RuleKind::Requires,
+            "implies" => RuleKind::Implies,
+            "conflicts" => RuleKind::Conflicts,
+            other => return Err(format!("unknown rule kind '{}'", other)),
+        };
+
+        Ok(Self {
+            condition: condition.parse()?,
+            kind,
+            consequence: consequence.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for VariableRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            RuleKind::Requires => "requires",
+            RuleKind::Implies => "implies",
+            RuleKind::Conflicts => "conflicts",
+        };
+        write!(f, "{} {} {}", self.condition, kind, self.consequence)
+    }
+}
+
+/// Check `rules` against the merged `vars`, applying `implies` rules
+///
+/// `explicit` holds the variables passed on the command line: an `implies`
+/// rule overrides template defaults but reports an error instead of
+/// silently overriding a value the user asked for.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::rules::{apply_rules, VariableRule};
+/// use std::collections::HashMap;
+///
+/// let rules: Vec<VariableRule> = vec!["style=none implies !with_css_module".parse().unwrap()];
+/// let mut vars = HashMap::from([
+///     ("style".to_string(), "none".to_string()),
+///     ("with_css_module".to_string(), "true".to_string()),
+/// ]);
+///
+/// apply_rules(&rules, &mut vars, &[]).unwrap();
+/// assert_eq!(vars["with_css_module"], "false");
+///
+/// vars.insert("with_css_module".to_string(), "true".to_string());
+/// assert!(apply_rules(&rules, &mut vars, &["with_css_module"]).is_err());
+/// ```
+pub fn apply_rules(
+    rules: &[VariableRule],
+    vars: &mut HashMap<String, String>,
+    explicit: &[&str],
+) -> Result<(), CliError> {
+    for rule in rules {
+        if !rule.condition.holds(vars) {
+            continue;
+        }
+
+        let consequence_holds = rule.consequence.holds(vars);
+        let violated = match rule.kind {
+            RuleKind::Requires => !consequence_holds,
+            RuleKind::Conflicts => consequence_holds,
+            RuleKind::Implies if consequence_holds => false,
+            RuleKind::Implies => {
+                let variable = &rule.consequence.variable;
+                let passed = explicit.contains(&variable.as_str());
+                if !passed {
+                    vars.insert(variable.clone(), rule.consequence.satisfying_value());
+                }
+                passed
+            }
+        };
+
+        if violated {
+            let variable = &rule.consequence.variable;
+            return Err(CliError::InvalidVariable {
+                name: variable.clone(),
+                message: format!(
+                    "{}, but {} is '{}'",
+                    rule,
+                    variable,
+                    vars.get(variable).map(String::as_str).unwrap_or_default()
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(value.to_lowercase().as_str(), "true" | "yes" | "1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_rule_round_trips() {
+        for rule in [
+            "with_stories requires with_tests",
+            "style=none implies !with_css_module",
+            "ssr=true conflicts with_portal",
+        ] {
+            assert_eq!(rule.parse::<VariableRule>().unwrap().to_string(), rule);
+        }
+        assert!("with_stories needs with_tests"
+            .parse::<VariableRule>()
+            .is_err());
+        assert!("with_stories requires".parse::<VariableRule>().is_err());
+        assert!("!style=none requires x".parse::<VariableRule>().is_err());
+    }
+
+    #[test]
+    fn test_requires_and_conflicts_report_contradictions() {
+        let rules: Vec<VariableRule> = ["with_stories requires with_tests", "ssr conflicts portal"]
+            .iter()
+            .map(|r| r.parse().unwrap())
+            .collect();
+
+        let mut ok = vars(&[("with_stories", "true"), ("with_tests", "yes")]);
+        assert!(apply_rules(&rules, &mut ok, &[]).is_ok());
+
+        let mut missing = vars(&[("with_stories", "true"), ("with_tests", "false")]);
+        let error = apply_rules(&rules, &mut missing, &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid variable 'with_tests': with_stories requires with_tests, but with_tests is 'false'"
+        );
+
+        let mut conflicting = vars(&[("ssr", "1"), ("portal", "true")]);
+        assert!(apply_rules(&rules, &mut conflicting, &[]).is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Staging area for transactional generation.
+//!
+//! Generated files are first written to a temporary directory outside the
+//! project. Only once every file has rendered and staged are they checked
+//! for collisions and moved into place, so a failed run leaves nothing in
+//! the project.
+//!
+//! The staging directory is a scoped temp directory: it is removed when the
+//! run's [`Staging`] is dropped, so a cancelled run doesn't leave it behind
+//! either. Release builds abort on panic without dropping anything, so each
+//! run also sweeps staging directories that earlier runs left for more than
+//! [`STALE_STAGING_AGE`].
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::fs;
+
+use crate::error::CliError;
+use crate::file_system::FileSystem;
+
+/// Prefix of staging directory names in the system temp directory
+const STAGING_PREFIX: &str = "cli-frontend-staging-";
+
+/// Age after which a staging directory is taken to be left by a crashed run
+pub const STALE_STAGING_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A file waiting in the staging directory
+pub struct StagedFile {
+    /// Where the file goes
+    pub target: PathBuf,
+    /// Where it was staged
+    pub staged: PathBuf,
+    /// Contents of `target` when the file was planned (`None` if it didn't exist)
+    pub previous: Option<Vec<u8>>,
+    /// Unix mode to give `target`, `None` for the default
+    pub mode: Option<u32>,
+}
+
+/// Files and directories staged by one generation run
+#[derive(Default)]
+pub struct Staging {
+    /// Created with the first staged file, removed on drop
+    dir: Option<TempDir>,
+    files: Vec<StagedFile>,
+    /// Directories to create even if no file lands in them
+    dirs: Vec<PathBuf>,
+}
+
+impl Staging {
+    /// Stage `contents` for `target`
+    pub async fn stage(
+        &mut self,
+        target: &Path,
+        contents: &[u8],
+        previous: Option<Vec<u8>>,
+        mode: Option<u32>,
+    ) -> Result<()> {
+        let staged = self.next_path().await?;
+        fs::write(&staged, contents)
+            .await
+            .with_context(|| format!("Could not stage file: {}", target.display()))?;
+        self.push(target, staged, previous, mode);
+        Ok(())
+    }
+
+    /// Stage a copy of the local file `source` for `target`
+    pub async fn stage_copy(
+        &mut self,
+        target: &Path,
+        source: &Path,
+        previous: Option<Vec<u8>>,
+        mode: Option<u32>,
+    ) -> Result<()> {
+        let staged = self.next_path().await?;
+        fs::copy(source, &staged)
+            .await
+            .with_context(|| format!("Could not stage file: {}", target.display()))?;
+        self.push(target, staged, previous, mode);
+        Ok(())
+    }
+
+    /// Stage a directory to create on commit
+    pub fn stage_dir(&mut self, dir: &Path) {
+        if !self.dirs.iter().any(|staged| staged == dir) {
+            self.dirs.push(dir.to_path_buf());
+        }
+    }
+
+    /// Staged directories, in staging order
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Staged files, in staging order
+    pub fn files(&self) -> &[StagedFile] {
+        &self.files
+    }
+
+    /// Check that every staged file can land where it was planned
+    ///
+    /// Fails when two files share a target, when a target's parent is a
+    /// file, or when a target changed on disk after it was planned.
+    pub async fn check_collisions(&self, fs: &dyn FileSystem) -> Result<()> {
+        for (index, file) in self.files.iter().enumerate() {
+            let target = file.target.display();
+            if self.files[..index].iter().any(|f| f.target == file.target) {
+                return Err(collision(format!("Two template files generate {}", target)));
+            }
+
+            let parent = file.target.ancestors().skip(1).find(|a| fs.exists(a));
+            if let Some(parent) = parent.filter(|p| !p.as_os_str().is_empty()) {
+                if matches!(fs.read_existing(parent).await, Ok(Some(_))) {
+                    return Err(collision(format!(
+                        "Cannot generate {}: {} is a file",
+                        target,
+                        parent.display()
+                    )));
+                }
+            }
+
+            if fs.read_existing(&file.target).await? != file.previous {
+                return Err(collision(format!(
+                    "{} changed while it was being generated",
+                    target
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the staging directory and everything left in it
+    pub async fn discard(&mut self) {
+        if let Some(dir) = self.dir.take() {
+            // Staged files outside the project; a leftover only costs temp space
+            let _ = fs::remove_dir_all(dir.path()).await;
+        }
+    }
+
+    async fn next_path(&mut self) -> Result<PathBuf> {
+        if self.dir.is_none() {
+            remove_stale_dirs(&std::env::temp_dir(), STALE_STAGING_AGE).await;
+        }
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => self.dir.insert(
+                tempfile::Builder::new()
+                    .prefix(STAGING_PREFIX)
+                    .tempdir()
+                    .context("Could not create staging directory")?,
+            ),
+        };
+        Ok(dir.path().join(self.files.len().to_string()))
+    }
+
+    fn push(
+        &mut self,
+        target: &Path,
+        staged: PathBuf,
+        previous: Option<Vec<u8>>,
+        mode: Option<u32>,
+    ) {
+        self.files.push(StagedFile {
+            target: target.to_path_buf(),
+            staged,
+            previous,
+            mode,
+        });
+    }
+}
+
+/// Remove staging directories in `parent` last modified more than `max_age` ago
+///
+/// Best effort: a directory that can't be read or removed is left for the
+/// next run.
+async fn remove_stale_dirs(parent: &Path, max_age: Duration) {
+    let Ok(mut entries) = fs::read_dir(parent).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(STAGING_PREFIX)
+        {
+            continue;
+        }
+        let stale = match entry.metadata().await.and_then(|meta| meta.modified()) {
+            Ok(modified) => modified.elapsed().is_ok_and(|age| age >= max_age),
+            Err(_) => false,
+        };
+        if stale {
+            let _ = fs::remove_dir_all(entry.path()).await;
+        }
+    }
+}
+
+fn collision(message: String) -> anyhow::Error {
+    CliError::IoError { message }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::MemoryFileSystem;
+
+    #[tokio::test]
+    async fn test_check_collisions() {
+        let fs = MemoryFileSystem::new();
+        fs.write(Path::new("out/index.ts"), b"export {};")
+            .await
+            .unwrap();
+
+        let mut staging = Staging::default();
+        staging
+            .stage(Path::new("out/Button/Button.tsx"), b"a", None, None)
+            .await
+            .unwrap();
+        staging
+            .stage(
+                Path::new("out/index.ts"),
+                b"b",
+                Some(b"export {};".to_vec()),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(staging.check_collisions(&fs).await.is_ok());
+
+        fs.write(Path::new("out/index.ts"), b"edited")
+            .await
+            .unwrap();
+        let error = staging.check_collisions(&fs).await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("changed while it was being generated"));
+
+        staging
+            .stage(Path::new("out/index.ts/Button.tsx"), b"c", None, None)
+            .await
+            .unwrap();
+        fs.write(Path::new("out/index.ts"), b"export {};")
+            .await
+            .unwrap();
+        let error = staging.check_collisions(&fs).await.unwrap_err();
+        assert!(error.to_string().contains("out/index.ts is a file"));
+
+        staging
+            .stage(Path::new("out/Button/Button.tsx"), b"d", None, None)
+            .await
+            .unwrap();
+        staging.files.remove(2);
+        let error = staging.check_collisions(&fs).await.unwrap_err();
+        assert!(error.to_string().contains("Two template files generate"));
+
+        let dir = staging.dir.as_ref().unwrap().path().to_path_buf();
+        staging.discard().await;
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_stale_dirs_sweeps_only_old_staging_directories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let left = temp_dir.path().join(format!("{}crashed", STAGING_PREFIX));
+        let other = temp_dir.path().join("other-tool");
+        std::fs::create_dir_all(left.join("0")).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+
+        remove_stale_dirs(temp_dir.path(), STALE_STAGING_AGE).await;
+        assert!(left.exists(), "a run in progress keeps its directory");
+
+        remove_stale_dirs(temp_dir.path(), Duration::ZERO).await;
+        assert!(!left.exists());
+        assert!(other.exists());
+    }
+
+    #[tokio::test]
+    async fn test_panicking_run_removes_staging_directory() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let run = tokio::spawn(async move {
+            let mut staging = Staging::default();
+            staging
+                .stage(Path::new("out/Button.tsx"), b"a", None, None)
+                .await
+                .unwrap();
+            sender
+                .send(staging.dir.as_ref().unwrap().path().to_path_buf())
+                .unwrap();
+            panic!("render failed");
+        });
+
+        assert!(run.await.unwrap_err().is_panic());
+        let dir = receiver.recv().unwrap();
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,313 @@
+//! Ownership stamps in generated files (`--stamp`, `cli-frontend drift`).
+//!
+//! A stamped file starts with a comment naming the template it came from,
+//! the CLI version, a hash of the template directory and a hash of the
+//! file's own content:
+//!
+//! ```text
+//! // @generated by cli-frontend 1.4.0 template=component name=Button template_hash=1f0c... hash=9a4e...
+//! ```
+//!
+//! Comparing the content hash with the file finds manual edits, and the
+//! template hash with the current template pack finds files generated from
+//! an older template. The comment uses the file type's syntax; formats
+//! without comments (JSON) and unknown extensions aren't stamped.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::keep::without_kept_regions;
+
+/// Text that marks a stamp comment
+pub const STAMP_MARKER: &str = "@generated by cli-frontend";
+
+/// Where a file came from, and the hash of what was generated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Stamp {
+    /// Template type, as passed to `--type`
+    pub template: String,
+    /// Name the file was generated for
+    pub name: String,
+    /// Variables passed with `--var`, so the file can be regenerated alike
+    pub vars: BTreeMap<String, String>,
+    /// Version of the CLI that generated it
+    pub cli_version: String,
+    /// Hash of the template directory at generation time
+    pub template_hash: String,
+    /// Hash of the generated content, without the stamp and the bodies of
+    /// kept regions; empty until the stamp is applied
+    pub hash: String,
+}
+
+impl Stamp {
+    /// Stamp for files generated from `template` by this CLI version
+    pub fn new(template: &str, name: &str, template_hash: &str) -> Self {
+        Self {
+            template: template.to_string(),
+            name: name.to_string(),
+            vars: BTreeMap::new(),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            template_hash: template_hash.to_string(),
+            hash: String::new(),
+        }
+    }
+
+    /// Records the `--var` values the files were generated with
+    pub fn with_vars(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.vars = vars.into_iter().collect();
+        self
+    }
+
+    /// Parse the stamp in a comment line
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::stamp::Stamp;
+    ///
+    /// let stamp = Stamp::parse(
+    ///     "/* @generated by cli-frontend 1.4.0 template=component name=Button vars=style:scss template_hash=ab hash=cd */",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(stamp.template, "component");
+    /// assert_eq!(stamp.name, "Button");
+    /// assert_eq!(stamp.vars["style"], "scss");
+    /// assert_eq!(stamp.cli_version, "1.4.0");
+    /// assert!(Stamp::parse("// generated by hand").is_none());
+    /// ```
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = &line[line.find(STAMP_MARKER)? + STAMP_MARKER.len()..];
+        let mut words = rest.split_whitespace();
+        let mut stamp = Self::new("", "", "");
+        stamp.cli_version = words.next()?.to_string();
+        for (key, value) in words.filter_map(|word| word.split_once('=')) {
+            match key {
+                "template" => stamp.template = unescape(value),
+                "name" => stamp.name = unescape(value),
+                "vars" => {
+                    stamp.vars = value
+                        .split(',')
+                        .filter_map(|pair| pair.split_once(':'))
+                        .map(|(key, value)| (unescape(key), unescape(value)))
+                        .collect()
+                }
+                "template_hash" => stamp.template_hash = value.to_string(),
+                "hash" => stamp.hash = value.to_string(),
+                _ => {}
+            }
+        }
+        (!stamp.template.is_empty() && !stamp.hash.is_empty()).then_some(stamp)
+    }
+
+    /// The stamp's text, without comment delimiters
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "{} {} template={} name={}",
+            STAMP_MARKER,
+            self.cli_version,
+            escape(&self.template),
+            escape(&self.name)
+        );
+        if !self.vars.is_empty() {
+            let vars: Vec<String> = self
+                .vars
+                .iter()
+                .map(|(key, value)| format!("{}:{}", escape(key), escape(value)))
+                .collect();
+            text.push_str(&format!(" vars={}", vars.join(",")));
+        }
+        text.push_str(&format!(
+            " template_hash={} hash={}",
+            self.template_hash, self.hash
+        ));
+        text
+    }
+
+    /// Whether `content` (without its stamp) differs from what was generated
+    ///
+    /// Edits inside kept regions don't count.
+    pub fn is_modified(&self, content: &str) -> bool {
+        hash_generated(content) != self.hash
+    }
+}
+
+/// Opening and closing comment delimiters for the file at `path`, or
+/// `None` if it can't hold a comment
+pub fn comment_syntax(path: &Path) -> Option<(&'static str, &'static str)> {
+    let file_name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if file_name.starts_with(".env") || file_name == ".gitignore" || file_name == "dockerfile" {
+        return Some(("# ", ""));
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "mts" | "cts" | "scss" | "less" | "rs"
+        | "go" | "java" | "kt" | "swift" | "dart" | "graphql" | "gql" => Some(("// ", "")),
+        "css" => Some(("/* ", " */")),
+        "html" | "vue" | "svelte" | "md" | "mdx" | "svg" | "xml" | "astro" => {
+            Some(("<!-- ", " -->"))
+        }
+        "yaml" | "yml" | "toml" | "sh" | "py" | "rb" | "ini" | "conf" | "prisma" => {
+            Some(("# ", ""))
+        }
+        _ => None,
+    }
+}
+
+/// `content` with a comment for `stamp` and the content's hash, or `None`
+/// if the file type can't hold a comment
+///
+/// The stamp goes on the first line, or the second after a shebang, and
+/// follows the content's line endings.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::stamp::{read_stamp, stamp_content, Stamp};
+/// use std::path::Path;
+///
+/// let stamp = Stamp::new("component", "Button", "ab");
+/// let content = "export const Button = () => null;\n";
+/// let stamped = stamp_content(Path::new("Button.tsx"), content, &stamp).unwrap();
+/// assert!(stamped.starts_with("// @generated by cli-frontend"));
+///
+/// let (stamp, body) = read_stamp(&stamped).unwrap();
+/// assert_eq!(body, content);
+/// assert!(!stamp.is_modified(&body));
+/// assert!(stamp_content(Path::new("package.json"), "{}", &stamp).is_none());
+/// ```
+pub fn stamp_content(path: &Path, content: &str, stamp: &Stamp) -> Option<String> {
+    let (open, close) = comment_syntax(path)?;
+    let stamp = Stamp {
+        hash: hash_generated(content),
+        ..stamp.clone()
+    };
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let comment = format!("{}{}{}{}", open, stamp.text(), close, line_ending);
+
+    let split = if content.starts_with("#!") {
+        content.find('\n').map_or(content.len(), |end| end + 1)
+    } else {
+        0
+    };
+    let mut stamped = String::with_capacity(content.len() + comment.len());
+    stamped.push_str(&content[..split]);
+    if split > 0 && !content[..split].ends_with('\n') {
+        stamped.push_str(line_ending);
+    }
+    stamped.push_str(&comment);
+    stamped.push_str(&content[split..]);
+    Some(stamped)
+}
+
+/// The stamp in `content` and the content without it, if it has one
+///
+/// Only the first two lines are searched, so a shebang can come first.
+pub fn read_stamp(content: &str) -> Option<(Stamp, String)> {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n').take(2) {
+        if let Some(stamp) = Stamp::parse(line) {
+            let mut body = String::with_capacity(content.len() - line.len());
+            body.push_str(&content[..offset]);
+            body.push_str(&content[offset + line.len()..]);
+            return Some((stamp, body));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Hash of generated content, leaving out what users may edit in kept regions
+fn hash_generated(content: &str) -> String {
+    content_hash(without_kept_regions(content).as_bytes())
+}
+
+/// `value` with everything but letters, digits and `-_./@+` percent-encoded,
+/// so it can't end the comment or split the stamp's fields
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_./@+".contains(&byte) {
+            escaped.push(char::from(byte));
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 3;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// 64-bit FNV-1a hash as hex, stable across platforms and Rust versions
+pub fn content_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_roundtrip_after_shebang_and_crlf() {
+        let stamp = Stamp::new("cli", "Setup", "ab").with_vars([
+            ("label".to_string(), "a, b: */".to_string()),
+            ("with_tests".to_string(), "true".to_string()),
+        ]);
+        let content = "#!/bin/sh\r\necho hi\r\n";
+        let stamped = stamp_content(Path::new("bin/setup.sh"), content, &stamp).unwrap();
+        let lines: Vec<&str> = stamped.split("\r\n").collect();
+        assert_eq!(lines[0], "#!/bin/sh");
+        assert!(lines[1].starts_with("# @generated by cli-frontend"));
+        assert_eq!(lines[2], "echo hi");
+
+        let (parsed, body) = read_stamp(&stamped).unwrap();
+        assert_eq!(body, content);
+        assert_eq!(parsed.vars, stamp.vars);
+        assert_eq!(parsed.template_hash, "ab");
+        assert!(!parsed.is_modified(&body));
+        assert!(parsed.is_modified("#!/bin/sh\r\necho bye\r\n"));
+
+        let css = stamp_content(Path::new("a.module.css"), "", &stamp).unwrap();
+        assert!(css.starts_with("/* @generated") && css.ends_with(" */\n"));
+        assert!(read_stamp("a\nb\n// @generated by cli-frontend 1.0 template=c hash=1").is_none());
+    }
+
+    #[test]
+    fn test_edits_in_kept_regions_are_not_modifications() {
+        let content = "a\n// cli-frontend:keep-start\n// yours\n// cli-frontend:keep-end\n";
+        let stamp = Stamp::new("c", "A", "ab");
+        let stamped = stamp_content(Path::new("a.ts"), content, &stamp).unwrap();
+        let (stamp, body) = read_stamp(&stamped).unwrap();
+        assert!(!stamp.is_modified(&body.replace("// yours", "mine();")));
+        assert!(stamp.is_modified(&body.replace("a\n", "b\n")));
+    }
+}
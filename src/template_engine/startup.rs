@@ -0,0 +1,141 @@
+//! Sanity checks on the templates directory, run before generating.
+//!
+//! Catches the mistakes that otherwise surface as confusing errors much
+//! later: a `templates_dir` that doesn't exist, has no templates, or points
+//! at a project instead of a folder of templates. The checks only look at
+//! the directory's top level, so they stay fast however big it is.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Files that mark a project root rather than a templates directory
+const PROJECT_MARKERS: &[&str] = &["package.json", "Cargo.toml", "node_modules", ".git"];
+
+/// What a [`StartupWarning`] is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupWarningKind {
+    /// `templates_dir` doesn't exist
+    MissingDir,
+    /// `templates_dir` exists but can't be listed
+    UnreadableDir,
+    /// `templates_dir` has no template folders
+    EmptyDir,
+    /// `templates_dir` looks like a project root
+    ProjectDir,
+    /// The requested template's `.conf` or `template.toml` can't be used
+    InvalidConfig,
+}
+
+/// A problem with the templates directory found before generating
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StartupWarning {
+    pub kind: StartupWarningKind,
+    /// The directory or config file the warning is about
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl StartupWarning {
+    pub fn new(kind: StartupWarningKind, path: &Path, message: String) -> Self {
+        Self {
+            kind,
+            path: path.to_path_buf(),
+            message,
+        }
+    }
+}
+
+/// Check that `templates_dir` exists, can be read and holds templates
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::startup::{check_templates_dir, StartupWarningKind};
+/// use std::path::Path;
+///
+/// let warnings = check_templates_dir(Path::new("/no/such/templates"));
+/// assert_eq!(warnings[0].kind, StartupWarningKind::MissingDir);
+/// ```
+pub fn check_templates_dir(templates_dir: &Path) -> Vec<StartupWarning> {
+    let warning = |kind, message| vec![StartupWarning::new(kind, templates_dir, message)];
+    if !templates_dir.exists() {
+        return warning(
+            StartupWarningKind::MissingDir,
+            format!(
+                "templates_dir {} does not exist; set templates_dir in the config",
+                templates_dir.display()
+            ),
+        );
+    }
+    let entries = match std::fs::read_dir(templates_dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            return warning(
+                StartupWarningKind::UnreadableDir,
+                format!(
+                    "templates_dir {} can't be read: {}",
+                    templates_dir.display(),
+                    error
+                ),
+            )
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let mut has_templates = false;
+    let mut markers = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if PROJECT_MARKERS.contains(&name.as_str()) {
+            markers.push(name);
+        } else if !name.starts_with('.') && entry.path().is_dir() {
+            has_templates = true;
+        }
+    }
+    if !markers.is_empty() {
+        markers.sort();
+        warnings.push(StartupWarning::new(
+            StartupWarningKind::ProjectDir,
+            templates_dir,
+            format!(
+                "templates_dir {} looks like a project (it has {}); it should hold one folder per template",
+                templates_dir.display(),
+                markers.join(", ")
+            ),
+        ));
+    }
+    if !has_templates {
+        warnings.push(StartupWarning::new(
+            StartupWarningKind::EmptyDir,
+            templates_dir,
+            format!("templates_dir {} has no templates", templates_dir.display()),
+        ));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_templates_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let kinds = |dir: &Path| -> Vec<StartupWarningKind> {
+            check_templates_dir(dir).iter().map(|w| w.kind).collect()
+        };
+        assert_eq!(kinds(root), [StartupWarningKind::EmptyDir]);
+
+        std::fs::create_dir_all(root.join("component")).unwrap();
+        std::fs::create_dir_all(root.join(".cache")).unwrap();
+        assert!(kinds(root).is_empty());
+
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        let warnings = check_templates_dir(root);
+        assert_eq!(warnings[0].kind, StartupWarningKind::ProjectDir);
+        assert!(warnings[0].message.contains("package.json"));
+    }
+}
@@ -0,0 +1,76 @@
+//! "Did you mean" suggestions for mistyped names.
+//!
+//! Template types, architectures and `--var` keys are matched against the
+//! known names by edit distance, so `--type compnent` can point at
+//! `component` instead of only listing everything available.
+
+/// The known name closest to `input`, if it is close enough to be a typo
+///
+/// Matching is case-insensitive and allows one edit per three characters
+/// of `input`, rounded up. Ties go to the first candidate.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::suggest::did_you_mean;
+///
+/// let templates = ["component", "hook", "service"];
+/// assert_eq!(did_you_mean("compnent", templates), Some("component"));
+/// assert_eq!(did_you_mean("widget", templates), None);
+/// ```
+pub fn did_you_mean<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let input = input.to_lowercase();
+    let max_distance = input.chars().count().div_ceil(3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(&input, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Number of single-character insertions, deletions and substitutions
+/// needed to turn `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "hook"), 4);
+        assert_eq!(levenshtein("style", "style"), 0);
+    }
+
+    #[test]
+    fn test_did_you_mean_prefers_closest_match() {
+        let names = ["clean-architecture", "mvc", "mvvm", "redux"];
+        assert_eq!(did_you_mean("mvcc", names), Some("mvc"));
+        assert_eq!(did_you_mean("Redux", names), Some("redux"));
+        assert_eq!(
+            did_you_mean("clean-architectur", names),
+            Some("clean-architecture")
+        );
+        assert_eq!(did_you_mean("hexagonal", names), None);
+    }
+}
@@ -0,0 +1,178 @@
+//! Declarative component specs for `cli-frontend sync-components`.
+//!
+//! A spec directory holds one YAML file per component:
+//!
+//! ```yaml
+//! name: UserCard
+//! type: component
+//! vars:
+//!   style: scss
+//!   with_tests: true
+//! ```
+//!
+//! The hash of each spec is recorded in [`SYNC_STATE_FILE`] next to the
+//! specs, so a sync only regenerates the components whose spec changed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::stamp::content_hash;
+
+/// File in the spec directory recording the hash of each synced spec
+pub const SYNC_STATE_FILE: &str = ".cli-frontend-sync.json";
+
+/// One component to generate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub template_type: String,
+    /// Template variables, as `--var` would pass them
+    #[serde(default, deserialize_with = "scalar_vars")]
+    pub vars: BTreeMap<String, String>,
+    /// Overrides `create_folder` from the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_folder: Option<bool>,
+}
+
+impl ComponentSpec {
+    /// Parse a YAML spec
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::sync::ComponentSpec;
+    ///
+    /// let spec = ComponentSpec::parse("name: UserCard\ntype: component\nvars:\n  with_tests: true\n")?;
+    /// assert_eq!(spec.template_type, "component");
+    /// assert_eq!(spec.vars["with_tests"], "true");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(content)?)
+    }
+
+    /// Hash of the spec's values, so reformatting the YAML doesn't count as a change
+    pub fn hash(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        content_hash(&json)
+    }
+
+    /// Variables in the form `TemplateEngine::generate` takes
+    pub fn cli_vars(&self) -> HashMap<String, String> {
+        self.vars.clone().into_iter().collect()
+    }
+}
+
+/// YAML scalars (strings, numbers, booleans) as strings
+fn scalar_vars<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let values = BTreeMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+    values
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_yaml::Value::String(s) => s,
+                serde_yaml::Value::Bool(b) => b.to_string(),
+                serde_yaml::Value::Number(n) => n.to_string(),
+                serde_yaml::Value::Null => String::new(),
+                _ => {
+                    return Err(serde::de::Error::custom(format!(
+                        "variable '{}' must be a string, number or boolean",
+                        key
+                    )))
+                }
+            };
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Read every `*.yaml` / `*.yml` spec in `dir`, keyed by file name and sorted
+pub fn read_specs(dir: &Path) -> Result<Vec<(String, ComponentSpec)>> {
+    let mut specs = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read spec directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_yaml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        if !is_yaml || !path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let spec = ComponentSpec::parse(&content)
+            .with_context(|| format!("Invalid component spec {}", path.display()))?;
+        let key = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        specs.push((key, spec));
+    }
+    specs.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(specs)
+}
+
+/// Hash of each spec as of its last successful sync, keyed by file name
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    pub specs: BTreeMap<String, String>,
+}
+
+impl SyncState {
+    /// Load the state of `dir`, starting over if it is missing or invalid
+    pub fn load(dir: &Path) -> Self {
+        let Ok(bytes) = std::fs::read(dir.join(SYNC_STATE_FILE)) else {
+            return Self::default();
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            crate::ui::warning!("Warning: Ignoring invalid {}: {}", SYNC_STATE_FILE, e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(SYNC_STATE_FILE);
+        let json = serde_json::to_vec_pretty(self).context("Could not serialize sync state")?;
+        std::fs::write(&path, json).with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Whether `spec` was synced from `key` and hasn't changed since
+    pub fn is_current(&self, key: &str, spec: &ComponentSpec) -> bool {
+        self.specs.get(key) == Some(&spec.hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_hash_ignores_formatting() {
+        let spec = ComponentSpec::parse(
+            "name: UserCard\ntype: component\nvars:\n  with_tests: true\n  style: scss\n",
+        )
+        .unwrap();
+        let reformatted = ComponentSpec::parse(
+            "# Profile card\ntype: component\nname: \"UserCard\"\nvars: { style: scss, with_tests: 'true' }\n",
+        )
+        .unwrap();
+        assert_eq!(spec.hash(), reformatted.hash());
+
+        let changed =
+            ComponentSpec::parse("name: UserCard\ntype: component\nvars:\n  style: css\n").unwrap();
+        assert_ne!(spec.hash(), changed.hash());
+
+        assert!(ComponentSpec::parse("name: A\ntype: component\nvars:\n  list: [1, 2]\n").is_err());
+        assert!(ComponentSpec::parse("name: A\ntemplate: component\n").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! Design tokens (`--tokens`).
+//!
+//! Parses a [W3C design tokens](https://tr.designtokens.org/format/) file
+//! into a flat list that style templates can turn into SCSS variables, CSS
+//! custom properties or a styled-components theme:
+//!
+//! ```json
+//! {
+//!   "color": {
+//!     "$type": "color",
+//!     "primary": { "$value": "#0055ff", "$description": "Brand color" },
+//!     "link": { "$value": "{color.primary}" }
+//!   },
+//!   "spacing": { "$type": "dimension", "sm": { "$value": "8px" } }
+//! }
+//! ```
+//!
+//! Groups pass `$type` down to their tokens, aliases such as
+//! `{color.primary}` are resolved, and structured values (dimensions,
+//! font families, cubic Béziers) are rendered as CSS. Templates receive the
+//! tokens as `tokens.all`, grouped by type (`tokens.color`,
+//! `tokens.dimension`, ...) and by top-level group (`tokens.groups.spacing`).
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::naming::{to_camel_case, to_kebab_case};
+
+/// Alias chains longer than this are reported as circular
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// A resolved design token
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DesignToken {
+    /// Dotted path in the tokens file, e.g. `color.primary`
+    pub path: String,
+    /// kebab-case name for SCSS/CSS variables, e.g. `color-primary`
+    pub name: String,
+    /// camelCase name for JavaScript, e.g. `colorPrimary`
+    pub var_name: String,
+    /// `$type`, inherited from groups or the aliased token; empty if unknown
+    #[serde(rename = "type")]
+    pub token_type: String,
+    /// CSS value with aliases resolved (render with `{{{value}}}`)
+    pub value: String,
+    /// `value` as a quoted JavaScript string literal
+    pub js_value: String,
+    pub description: String,
+    /// `$value` as written in the file
+    pub raw: Value,
+}
+
+/// Tokens parsed from a design tokens file, sorted by path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DesignTokens {
+    pub tokens: Vec<DesignToken>,
+}
+
+/// A token before alias resolution
+struct RawToken {
+    path: String,
+    token_type: String,
+    description: String,
+    value: Value,
+}
+
+impl DesignTokens {
+    /// Parse a W3C design tokens file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::tokens::DesignTokens;
+    ///
+    /// let tokens = DesignTokens::parse(r##"{
+    ///     "color": {
+    ///         "$type": "color",
+    ///         "primary": {"$value": "#0055ff"},
+    ///         "link": {"$value": "{color.primary}"}
+    ///     },
+    ///     "spacing": {"sm": {"$type": "dimension", "$value": {"value": 8, "unit": "px"}}}
+    /// }"##)?;
+    ///
+    /// let link = &tokens.tokens[0];
+    /// assert_eq!((link.name.as_str(), link.value.as_str()), ("color-link", "#0055ff"));
+    /// assert_eq!(tokens.tokens[2].value, "8px");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(json: &str) -> Result<Self> {
+        let root: Value = serde_json::from_str(json).context("Invalid design tokens JSON")?;
+        let root = root
+            .as_object()
+            .ok_or_else(|| anyhow!("Design tokens file must contain a JSON object"))?;
+
+        let mut raw = Vec::new();
+        collect_tokens(root, "", "", &mut raw);
+
+        let tokens = raw
+            .iter()
+            .map(|token| resolve_token(token, &raw))
+            .collect::<Result<_>>()?;
+        Ok(Self { tokens })
+    }
+
+    /// Template data exposed as `tokens`
+    pub fn to_data(&self) -> Value {
+        let mut by_type: Map<String, Value> = Map::new();
+        let mut groups: Map<String, Value> = Map::new();
+
+        for token in &self.tokens {
+            let token_value = serde_json::to_value(token).unwrap_or_default();
+            if !token.token_type.is_empty() {
+                push(&mut by_type, &token.token_type, token_value.clone());
+            }
+            let group = token.path.split('.').next().unwrap_or_default();
+            push(&mut groups, group, token_value);
+        }
+
+        let mut data = by_type;
+        data.insert(
+            "all".to_string(),
+            serde_json::to_value(&self.tokens).unwrap_or_default(),
+        );
+        data.insert("groups".to_string(), Value::Object(groups));
+        Value::Object(data)
+    }
+}
+
+fn push(map: &mut Map<String, Value>, key: &str, value: Value) {
+    if let Value::Array(items) = map
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        items.push(value);
+    }
+}
+
+/// Walk a group, collecting tokens with the `$type` inherited from parents
+fn collect_tokens(
+    group: &Map<String, Value>,
+    path: &str,
+    inherited_type: &str,
+    out: &mut Vec<RawToken>,
+) {
+    let group_type = group
+        .get("$type")
+        .and_then(Value::as_str)
+        .unwrap_or(inherited_type);
+
+    for (key, child) in group {
+        let Some(child) = child.as_object().filter(|_| !key.starts_with('$')) else {
+            continue;
+        };
+        let child_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+
+        match child.get("$value") {
+            Some(value) => out.push(RawToken {
+                path: child_path,
+                token_type: child
+                    .get("$type")
+                    .and_then(Value::as_str)
+                    .unwrap_or(group_type)
+                    .to_string(),
+                description: child
+                    .get("$description")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                value: value.clone(),
+            }),
+            None => collect_tokens(child, &child_path, group_type, out),
+        }
+    }
+}
+
+fn resolve_token(token: &RawToken, all: &[RawToken]) -> Result<DesignToken> {
+    let (value, aliased_type) = resolve_value(&token.value, all, 0)
+        .with_context(|| format!("Could not resolve design token '{}'", token.path))?;
+    let token_type = if token.token_type.is_empty() {
+        aliased_type
+    } else {
+        token.token_type.clone()
+    };
+
+    let name = token
+        .path
+        .split('.')
+        .map(|segment| to_kebab_case(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    Ok(DesignToken {
+        path: token.path.clone(),
+        var_name: to_camel_case(&name).into_owned(),
+        name,
+        token_type,
+        js_value: serde_json::to_string(&value).unwrap_or_default(),
+        value,
+        description: token.description.clone(),
+        raw: token.value.clone(),
+    })
+}
+
+/// CSS text for a `$value`, plus the type of the token it aliases (if any)
+fn resolve_value(value: &Value, all: &[RawToken], depth: usize) -> Result<(String, String)> {
+    if depth > MAX_ALIAS_DEPTH {
+        bail!("circular alias");
+    }
+
+    match value {
+        Value::String(text) => {
+            // A whole-value alias also carries the referenced token's type
+            if let Some(path) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+                if !path.contains(['{', '}']) {
+                    let target = find_token(all, path)?;
+                    let (value, aliased_type) = resolve_value(&target.value, all, depth + 1)?;
+                    let token_type = if target.token_type.is_empty() {
+                        aliased_type
+                    } else {
+                        target.token_type.clone()
+                    };
+                    return Ok((value, token_type));
+                }
+            }
+            Ok((resolve_embedded_aliases(text, all, depth)?, String::new()))
+        }
+        Value::Number(number) => Ok((number.to_string(), String::new())),
+        Value::Array(items) => {
+            let parts = items
+                .iter()
+                .map(|item| resolve_value(item, all, depth + 1).map(|(value, _)| value))
+                .collect::<Result<Vec<_>>>()?;
+            if items.iter().all(Value::is_number) && items.len() == 4 {
+                Ok((format!("cubic-bezier({})", parts.join(", ")), String::new()))
+            } else {
+                // Font family stacks
+                let families: Vec<String> = parts
+                    .into_iter()
+                    .map(|family| {
+                        if family.contains(' ') {
+                            format!("\"{}\"", family)
+                        } else {
+                            family
+                        }
+                    })
+                    .collect();
+                Ok((families.join(", "), String::new()))
+            }
+        }
+        Value::Object(object) => {
+            if let (Some(number), Some(unit)) = (object.get("value"), object.get("unit")) {
+                let (number, _) = resolve_value(number, all, depth + 1)?;
+                return Ok((
+                    format!("{}{}", number, unit.as_str().unwrap_or_default()),
+                    String::new(),
+                ));
+            }
+            if let Some(hex) = object.get("hex").and_then(Value::as_str) {
+                return Ok((hex.to_string(), String::new()));
+            }
+            // Composite tokens (shadow, typography, ...) are left to templates via `raw`
+            Ok((value.to_string(), String::new()))
+        }
+        Value::Bool(flag) => Ok((flag.to_string(), String::new())),
+        Value::Null => Ok((String::new(), String::new())),
+    }
+}
+
+/// Replace `{path}` references inside a larger value, e.g. `{spacing.sm} {spacing.md}`
+fn resolve_embedded_aliases(text: &str, all: &[RawToken], depth: usize) -> Result<String> {
+    let mut resolved = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        resolved.push_str(&rest[..start]);
+        let target = find_token(all, &rest[start + 1..start + end])?;
+        resolved.push_str(&resolve_value(&target.value, all, depth + 1)?.0);
+        rest = &rest[start + end + 1..];
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+fn find_token<'a>(all: &'a [RawToken], path: &str) -> Result<&'a RawToken> {
+    all.iter()
+        .find(|token| token.path == path)
+        .ok_or_else(|| anyhow!("unknown token reference '{{{}}}'", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_inherits_types_and_resolves_aliases() {
+        let tokens = DesignTokens::parse(
+            r#"{
+                "font": {
+                    "body": {"$type": "fontFamily", "$value": ["Inter Var", "sans-serif"]}
+                },
+                "spacing": {
+                    "$type": "dimension",
+                    "sm": {"$value": "8px", "$description": "Tight gaps"},
+                    "inset": {"$value": "{spacing.sm} {spacing.sm}"}
+                },
+                "easing": {"$type": "cubicBezier", "out": {"$value": [0, 0, 0.58, 1]}},
+                "link": {"$value": "{spacing.sm}"}
+            }"#,
+        )
+        .unwrap();
+
+        let summary: Vec<(&str, &str, &str)> = tokens
+            .tokens
+            .iter()
+            .map(|t| (t.name.as_str(), t.token_type.as_str(), t.value.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("easing-out", "cubicBezier", "cubic-bezier(0, 0, 0.58, 1)"),
+                ("font-body", "fontFamily", "\"Inter Var\", sans-serif"),
+                ("link", "dimension", "8px"),
+                ("spacing-inset", "dimension", "8px 8px"),
+                ("spacing-sm", "dimension", "8px"),
+            ]
+        );
+        assert_eq!(tokens.tokens[1].js_value, r#""\"Inter Var\", sans-serif""#);
+        assert_eq!(tokens.tokens[4].description, "Tight gaps");
+
+        let data = tokens.to_data();
+        assert_eq!(data["all"].as_array().unwrap().len(), 5);
+        assert_eq!(data["dimension"].as_array().unwrap().len(), 3);
+        assert_eq!(data["groups"]["spacing"][0]["var_name"], "spacingInset");
+    }
+
+    #[test]
+    fn test_parse_rejects_broken_aliases() {
+        let error = DesignTokens::parse(r#"{"a": {"$value": "{b}"}}"#).unwrap_err();
+        assert!(format!("{:#}", error).contains("unknown token reference '{b}'"));
+
+        let error =
+            DesignTokens::parse(r#"{"a": {"$value": "{b}"}, "b": {"$value": "{a}"}}"#).unwrap_err();
+        assert!(format!("{:#}", error).contains("circular alias"));
+    }
+}
@@ -0,0 +1,610 @@
+//! `template.toml`, the TOML form of a template's configuration.
+//!
+//! A template directory may hold a `template.toml` instead of a `.conf`;
+//! when both exist the TOML file wins. It carries the same settings as the
+//! INI format, but with real types instead of suffix conventions
+//! (`style_options`, `style_required`) and with unknown keys reported as
+//! errors instead of being ignored:
+//!
+//! ```toml
+//! filename_case = "kebab"
+//! raw = ["assets/"]
+//! rules = ["style=none implies !with_css_module"]
+//!
+//! [metadata]
+//! name = "React Component"
+//! tags = ["component", "ui"]
+//! requires_cli = "1.4"
+//!
+//! [options]
+//! with_tests = true
+//!
+//! [options.style]
+//! default = "scss"
+//! type = "enum"
+//! values = ["scss", "css", "none"]
+//! group = "Styling"
+//!
+//! [environments.production]
+//! api_url = "https://api.acme.com"
+//!
+//! [files]
+//! "$FILE_NAME.spec.tsx" = "var_with_tests"
+//!
+//! [permissions]
+//! "bin/setup.sh" = "755"
+//!
+//! [[examples]]
+//! name = "Styled with tests"
+//! command = "cli-frontend Button --type component --var style=scss"
+//! files = ["Button/Button.tsx"]
+//! ```
+//!
+//! `cli-frontend template migrate-conf <template>` writes one from an
+//! existing `.conf` with [`to_template_toml`].
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::config::{
+    parse_byte_size, version_at_least, Framework, RenderEngine, TemplateConfig, TemplateExample,
+    VariableOption,
+};
+use super::formatting::LineEndings;
+use super::naming::CaseStyle;
+
+/// Name of the TOML configuration file inside a template directory
+pub const TEMPLATE_TOML_FILE: &str = "template.toml";
+
+/// Name of the INI configuration file inside a template directory
+pub const TEMPLATE_CONF_FILE: &str = ".conf";
+
+/// Whether a template file (relative, `/`-separated) configures the
+/// template rather than being generated
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::toml_config::is_template_config_file;
+///
+/// assert!(is_template_config_file("template.toml"));
+/// assert!(is_template_config_file(".conf"));
+/// assert!(!is_template_config_file("config/template.toml"));
+/// assert!(!is_template_config_file("$FILE_NAME.tsx"));
+/// ```
+pub fn is_template_config_file(relative_path: &str) -> bool {
+    relative_path == TEMPLATE_TOML_FILE
+        || relative_path.rsplit('/').next() == Some(TEMPLATE_CONF_FILE)
+}
+
+/// Whether the template in `template_dir` sets `internal = true` in its
+/// `[metadata]`
+///
+/// Reads only that key, so listings can leave internal templates out
+/// without loading every config.
+pub fn is_internal_template(template_dir: &Path) -> bool {
+    if let Ok(content) = std::fs::read_to_string(template_dir.join(TEMPLATE_TOML_FILE)) {
+        return content
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|table| table.get("metadata")?.get("internal")?.as_bool())
+            .unwrap_or(false);
+    }
+    let Ok(content) = std::fs::read_to_string(template_dir.join(TEMPLATE_CONF_FILE)) else {
+        return false;
+    };
+    let mut in_metadata = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            in_metadata = line == "[metadata]";
+        } else if let Some((key, value)) = line.split_once('=').filter(|_| in_metadata) {
+            if key.trim() == "internal" {
+                let value = value.split('#').next().unwrap_or(value);
+                return value.trim().trim_matches('"').trim_matches('\'') == "true";
+            }
+        }
+    }
+    false
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateToml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enable_timestamps: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enable_uuid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    framework: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename_case: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder_case: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_subdir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_endings: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    editorconfig: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    create_folder: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strict_variables: Option<bool>,
+    /// Bytes, or a size such as `"25MB"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_template_size: Option<toml::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    raw: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<MetadataToml>,
+    /// A default value, or an [`OptionToml`] table
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    options: BTreeMap<String, toml::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    files: BTreeMap<String, String>,
+    /// Variable defaults per environment (`[environments.production]`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    environments: BTreeMap<String, BTreeMap<String, toml::Value>>,
+    /// Octal modes written as strings (`"755"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    permissions: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    examples: Vec<TemplateExample>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct MetadataToml {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_cli: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    internal: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct OptionToml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<toml::Value>,
+    #[serde(rename = "type", default, skip_serializing_if = "String::is_empty")]
+    var_type: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    values: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    group: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    prompt: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    required: bool,
+}
+
+/// Parse `template.toml` content on top of `defaults`
+///
+/// Unlike `.conf`, unknown keys and invalid values are errors.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::toml_config::parse_template_toml;
+/// use cli_frontend::template_engine::TemplateConfig;
+///
+/// let config = parse_template_toml(
+///     "[options]\nwith_tests = true\n\n[options.style]\ndefault = \"scss\"\nvalues = [\"scss\", \"css\"]\n",
+///     TemplateConfig::default(),
+/// )?;
+/// assert_eq!(config.variables["with_tests"], "true");
+/// assert_eq!(config.options_metadata["style"].possible_values, ["scss", "css"]);
+///
+/// assert!(parse_template_toml("filename_cse = \"kebab\"\n", TemplateConfig::default()).is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn parse_template_toml(content: &str, defaults: TemplateConfig) -> Result<TemplateConfig> {
+    let toml: TemplateToml = toml::from_str(content)?;
+    let mut config = defaults;
+
+    if let Some(environment) = toml.environment {
+        config.environment = environment;
+    }
+    if let Some(enable_timestamps) = toml.enable_timestamps {
+        config.enable_timestamps = enable_timestamps;
+    }
+    if let Some(enable_uuid) = toml.enable_uuid {
+        config.enable_uuid = enable_uuid;
+    }
+    if let Some(engine) = toml.engine {
+        config.engine = RenderEngine::parse(&engine).with_context(|| {
+            format!("Unknown engine '{engine}', expected handlebars or minijinja")
+        })?;
+    }
+    if let Some(framework) = toml.framework {
+        config.framework = Framework::parse(&framework)
+            .with_context(|| format!("Unknown framework '{framework}', expected react or rn"))?;
+    }
+    if let Some(case) = toml.filename_case {
+        config.filename_case = Some(parse_case("filename_case", &case)?);
+    }
+    if let Some(case) = toml.folder_case {
+        config.folder_case = Some(parse_case("folder_case", &case)?);
+    }
+    if let Some(output_subdir) = toml.output_subdir {
+        config.output_subdir = Some(output_subdir).filter(|v| !v.is_empty());
+    }
+    if let Some(endings) = toml.line_endings {
+        config.line_endings = Some(LineEndings::parse(&endings).with_context(|| {
+            format!("Unknown line_endings '{endings}', expected lf, crlf or auto")
+        })?);
+    }
+    if let Some(editorconfig) = toml.editorconfig {
+        config.editorconfig = editorconfig;
+    }
+    if let Some(create_folder) = toml.create_folder {
+        config.create_folder = create_folder;
+    }
+    if let Some(strict_variables) = toml.strict_variables {
+        config.strict_variables = strict_variables;
+    }
+    if let Some(size) = toml.max_template_size {
+        config.max_template_size = match &size {
+            toml::Value::Integer(bytes) => u64::try_from(*bytes).ok(),
+            toml::Value::String(size) => parse_byte_size(size),
+            _ => None,
+        }
+        .with_context(|| {
+            format!("Invalid max_template_size {size}, expected bytes or a size like \"25MB\"")
+        })?;
+    }
+
+    config
+        .raw_files
+        .extend(toml.raw.iter().map(|path| path.replace('\\', "/")));
+    for rule in &toml.rules {
+        config.rules.push(
+            rule.parse()
+                .map_err(|e| anyhow::anyhow!("Invalid rule '{rule}': {e}"))?,
+        );
+    }
+
+    if let Some(metadata) = toml.metadata {
+        config.metadata.name = metadata.name;
+        config.metadata.description = metadata.description;
+        config.metadata.internal = metadata.internal;
+        config.metadata.tags.clear();
+        for tag in metadata.tags.iter().map(|t| t.trim().to_lowercase()) {
+            if !tag.is_empty() && !config.metadata.tags.contains(&tag) {
+                config.metadata.tags.push(tag);
+            }
+        }
+        if let Some(version) = metadata.requires_cli {
+            let version = version.trim_start_matches(">=").trim();
+            if version_at_least(version, version).is_none() {
+                bail!("Invalid requires_cli: '{version}' is not a version");
+            }
+            config.metadata.requires_cli = Some(version.to_string());
+        }
+    }
+
+    for (name, value) in toml.options {
+        let option = match value {
+            toml::Value::Table(_) => value
+                .try_into::<OptionToml>()
+                .with_context(|| format!("Invalid option '{name}'"))?,
+            value => OptionToml {
+                default: Some(value),
+                ..Default::default()
+            },
+        };
+
+        if let Some(default) = option.default {
+            let default = scalar_to_string(&default)
+                .with_context(|| format!("Invalid default for option '{name}'"))?;
+            config.variables.insert(name.clone(), default);
+        }
+        let declared = !option.var_type.is_empty()
+            || !option.values.is_empty()
+            || !option.description.is_empty()
+            || !option.group.is_empty()
+            || !option.prompt.is_empty()
+            || option.required;
+        if declared {
+            config.options_metadata.insert(
+                name,
+                VariableOption {
+                    var_type: option.var_type,
+                    possible_values: option.values,
+                    description: option.description,
+                    required: option.required,
+                    group: option.group,
+                    prompt: option.prompt,
+                },
+            );
+        }
+    }
+
+    for (environment, overrides) in toml.environments {
+        let mut variables = std::collections::HashMap::new();
+        for (name, value) in overrides {
+            let value = scalar_to_string(&value).with_context(|| {
+                format!("Invalid value for '{name}' in [environments.{environment}]")
+            })?;
+            variables.insert(name, value);
+        }
+        config.environment_overrides.insert(environment, variables);
+    }
+    for (path, condition) in toml.files {
+        config
+            .file_filters
+            .insert(path.replace('\\', "/"), condition);
+    }
+    for (path, mode) in toml.permissions {
+        match u32::from_str_radix(&mode, 8) {
+            Ok(mode) if mode <= 0o7777 => {
+                config.permissions.insert(path.replace('\\', "/"), mode);
+            }
+            _ => bail!("Invalid permissions for '{path}': '{mode}' is not an octal mode"),
+        }
+    }
+    config.examples.extend(toml.examples);
+
+    Ok(config)
+}
+
+/// Render `config` as `template.toml`, leaving out settings equal to `defaults`
+///
+/// Used to migrate `.conf` files: parsing the result with
+/// [`parse_template_toml`] on the same defaults gives back `config`.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::toml_config::to_template_toml;
+/// use cli_frontend::template_engine::TemplateConfig;
+///
+/// let mut config = TemplateConfig::default();
+/// config.variables.insert("style".to_string(), "scss".to_string());
+/// config.create_folder = false;
+///
+/// let toml = to_template_toml(&config, &TemplateConfig::default())?;
+/// assert_eq!(toml, "create_folder = false\n\n[options]\nstyle = \"scss\"\n");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn to_template_toml(config: &TemplateConfig, defaults: &TemplateConfig) -> Result<String> {
+    fn changed<T: PartialEq>(value: T, default: T) -> Option<T> {
+        (value != default).then_some(value)
+    }
+
+    let metadata = &config.metadata;
+    let has_metadata = !metadata.name.is_empty()
+        || !metadata.description.is_empty()
+        || !metadata.tags.is_empty()
+        || metadata.requires_cli.is_some()
+        || metadata.internal;
+
+    let mut options = BTreeMap::new();
+    for (name, value) in &config.variables {
+        options.insert(name.clone(), scalar_from_string(value));
+    }
+    for (name, option) in &config.options_metadata {
+        let table = OptionToml {
+            default: options.remove(name),
+            var_type: option.var_type.clone(),
+            values: option.possible_values.clone(),
+            description: option.description.clone(),
+            group: option.group.clone(),
+            prompt: option.prompt.clone(),
+            required: option.required,
+        };
+        options.insert(name.clone(), toml::Value::try_from(table)?);
+    }
+
+    let toml = TemplateToml {
+        environment: changed(&config.environment, &defaults.environment).cloned(),
+        enable_timestamps: changed(config.enable_timestamps, defaults.enable_timestamps),
+        enable_uuid: changed(config.enable_uuid, defaults.enable_uuid),
+        engine: changed(config.engine, defaults.engine).map(|e| e.as_str().to_string()),
+        framework: changed(config.framework, defaults.framework).map(|f| f.as_str().to_string()),
+        filename_case: changed(config.filename_case, defaults.filename_case)
+            .flatten()
+            .map(|c| c.as_str().to_string()),
+        folder_case: changed(config.folder_case, defaults.folder_case)
+            .flatten()
+            .map(|c| c.as_str().to_string()),
+        output_subdir: changed(&config.output_subdir, &defaults.output_subdir)
+            .cloned()
+            .flatten(),
+        line_endings: changed(config.line_endings, defaults.line_endings)
+            .flatten()
+            .map(|l| l.as_str().to_string()),
+        editorconfig: changed(config.editorconfig, defaults.editorconfig),
+        create_folder: changed(config.create_folder, defaults.create_folder),
+        strict_variables: changed(config.strict_variables, defaults.strict_variables),
+        max_template_size: changed(config.max_template_size, defaults.max_template_size)
+            .map(|size| toml::Value::Integer(size as i64)),
+        raw: config.raw_files.clone(),
+        rules: config.rules.iter().map(|rule| rule.to_string()).collect(),
+        metadata: has_metadata.then(|| MetadataToml {
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            tags: metadata.tags.clone(),
+            requires_cli: metadata.requires_cli.clone(),
+            internal: metadata.internal,
+        }),
+        options,
+        files: config
+            .file_filters
+            .iter()
+            .map(|(path, condition)| (path.clone(), condition.clone()))
+            .collect(),
+        environments: config
+            .environment_overrides
+            .iter()
+            .map(|(environment, overrides)| {
+                let overrides = overrides
+                    .iter()
+                    .map(|(name, value)| (name.clone(), scalar_from_string(value)))
+                    .collect();
+                (environment.clone(), overrides)
+            })
+            .collect(),
+        permissions: config
+            .permissions
+            .iter()
+            .map(|(path, mode)| (path.clone(), format!("{mode:o}")))
+            .collect(),
+        examples: config.examples.clone(),
+    };
+
+    Ok(toml::to_string(&toml)?)
+}
+
+fn parse_case(key: &str, value: &str) -> Result<CaseStyle> {
+    CaseStyle::parse(value).with_context(|| {
+        format!("Unknown {key} '{value}', expected pascal, camel, kebab, snake or route")
+    })
+}
+
+/// A variable value as written in `.conf`: booleans and numbers are strings
+fn scalar_to_string(value: &toml::Value) -> Result<String> {
+    match value {
+        toml::Value::String(value) => Ok(value.clone()),
+        toml::Value::Boolean(value) => Ok(value.to_string()),
+        toml::Value::Integer(value) => Ok(value.to_string()),
+        toml::Value::Float(value) => Ok(value.to_string()),
+        _ => bail!("expected a string, boolean or number, found {value}"),
+    }
+}
+
+/// `true`/`false` as TOML booleans, anything else as a string
+fn scalar_from_string(value: &str) -> toml::Value {
+    match value {
+        "true" => toml::Value::Boolean(true),
+        "false" => toml::Value::Boolean(false),
+        _ => toml::Value::String(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE_TOML: &str = r#"
+filename_case = "kebab"
+engine = "minijinja"
+max_template_size = "25MB"
+raw = ["assets/"]
+rules = ["style=none implies !with_css_module"]
+
+[metadata]
+name = "React Component"
+tags = ["UI", "component", "ui"]
+requires_cli = ">=1.4"
+
+[options]
+with_tests = true
+retries = 3
+
+[options.style]
+default = "scss"
+type = "enum"
+values = ["scss", "css", "none"]
+group = "Styling"
+required = true
+
+[environments.production]
+retries = 5
+
+[files]
+"$FILE_NAME.spec.tsx" = "var_with_tests"
+
+[permissions]
+"bin/setup.sh" = "755"
+
+[[examples]]
+name = "Styled"
+command = "cli-frontend Button --type component --var style=scss"
+files = ["Button/Button.tsx"]
+"#;
+
+    #[test]
+    fn test_parse_template_toml() {
+        let config = parse_template_toml(TEMPLATE_TOML, TemplateConfig::default()).unwrap();
+
+        assert_eq!(config.filename_case, Some(CaseStyle::Kebab));
+        assert_eq!(config.engine, RenderEngine::MiniJinja);
+        assert_eq!(config.max_template_size, 25 * 1024 * 1024);
+        assert_eq!(config.raw_files, ["assets/"]);
+        assert_eq!(config.rules.len(), 1);
+        assert_eq!(config.metadata.tags, ["ui", "component"]);
+        assert_eq!(config.metadata.requires_cli.as_deref(), Some("1.4"));
+        assert_eq!(config.variables["with_tests"], "true");
+        assert_eq!(config.variables["retries"], "3");
+        assert_eq!(config.variables["style"], "scss");
+        assert!(!config.options_metadata.contains_key("with_tests"));
+        let style = &config.options_metadata["style"];
+        assert_eq!(style.var_type, "enum");
+        assert_eq!(style.possible_values, ["scss", "css", "none"]);
+        assert_eq!(style.group, "Styling");
+        assert!(style.required);
+        assert_eq!(config.file_filters["$FILE_NAME.spec.tsx"], "var_with_tests");
+        assert_eq!(config.environment_overrides["production"]["retries"], "5");
+        assert_eq!(config.permissions["bin/setup.sh"], 0o755);
+        assert_eq!(config.examples[0].files, ["Button/Button.tsx"]);
+    }
+
+    #[test]
+    fn test_parse_template_toml_rejects_invalid_settings() {
+        for content in [
+            "filenme_case = \"kebab\"\n",
+            "filename_case = \"shouting\"\n",
+            "[options.style]\ndefult = \"scss\"\n",
+            "[options]\nstyle = [\"scss\"]\n",
+            "[permissions]\n\"bin/setup.sh\" = \"rwx\"\n",
+            "rules = [\"style requires\"]\n",
+        ] {
+            assert!(
+                parse_template_toml(content, TemplateConfig::default()).is_err(),
+                "{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_template_toml_round_trips() {
+        let defaults = TemplateConfig::default();
+        let config = parse_template_toml(TEMPLATE_TOML, defaults.clone()).unwrap();
+
+        let toml = to_template_toml(&config, &defaults).unwrap();
+        let parsed = parse_template_toml(&toml, defaults).unwrap();
+
+        assert_eq!(parsed.filename_case, config.filename_case);
+        assert_eq!(parsed.engine, config.engine);
+        assert_eq!(parsed.max_template_size, config.max_template_size);
+        assert_eq!(parsed.raw_files, config.raw_files);
+        assert_eq!(parsed.rules, config.rules);
+        assert_eq!(parsed.metadata.tags, config.metadata.tags);
+        assert_eq!(parsed.variables, config.variables);
+        assert_eq!(
+            parsed.options_metadata["style"].possible_values,
+            config.options_metadata["style"].possible_values
+        );
+        assert_eq!(parsed.file_filters, config.file_filters);
+        assert_eq!(parsed.environment_overrides, config.environment_overrides);
+        assert_eq!(parsed.permissions, config.permissions);
+        assert_eq!(parsed.examples, config.examples);
+    }
+}
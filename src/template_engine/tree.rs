@@ -0,0 +1,95 @@
+//! Directory trees of would-be output (`--tree`).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Nested directory entries, directories and files sorted by name
+#[derive(Default)]
+struct Node {
+    children: BTreeMap<String, Node>,
+}
+
+/// Draw `files` as a tree under `root`, one entry per line
+///
+/// Directories end with `/`. Paths outside `root` are drawn from their
+/// first component.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::tree::render_tree;
+/// use std::path::{Path, PathBuf};
+///
+/// let files = [
+///     PathBuf::from("src/Auth/hooks/useAuth.ts"),
+///     PathBuf::from("src/Auth/types.ts"),
+/// ];
+/// assert_eq!(
+///     render_tree(Path::new("src"), &files),
+///     "src/\n└── Auth/\n    ├── hooks/\n    │   └── useAuth.ts\n    └── types.ts\n"
+/// );
+/// ```
+pub fn render_tree(root: &Path, files: &[PathBuf]) -> String {
+    let mut tree = Node::default();
+    for file in files {
+        let relative = file.strip_prefix(root).unwrap_or(file);
+        let mut node = &mut tree;
+        for component in relative.components() {
+            node = node
+                .children
+                .entry(component.as_os_str().to_string_lossy().into_owned())
+                .or_default();
+        }
+    }
+
+    let mut output = format!(
+        "{}/\n",
+        root.to_string_lossy().trim_end_matches(['/', '\\'])
+    );
+    draw(&tree, "", &mut output);
+    output
+}
+
+fn draw(node: &Node, prefix: &str, output: &mut String) {
+    let count = node.children.len();
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let last = index + 1 == count;
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let suffix = if child.children.is_empty() { "" } else { "/" };
+        output.push_str(&format!("{}{}{}{}\n", prefix, branch, name, suffix));
+        draw(child, &format!("{}{}", prefix, indent), output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tree_nests_siblings() {
+        let files = [
+            PathBuf::from("out/Billing/index.ts"),
+            PathBuf::from("out/Auth/components/Auth.tsx"),
+            PathBuf::from("out/Auth/components/index.ts"),
+            PathBuf::from("out/Auth/index.ts"),
+        ];
+        assert_eq!(
+            render_tree(Path::new("out/"), &files),
+            "\
+out/
+├── Auth/
+│   ├── components/
+│   │   ├── Auth.tsx
+│   │   └── index.ts
+│   └── index.ts
+└── Billing/
+    └── index.ts
+"
+        );
+        assert_eq!(render_tree(Path::new("out"), &[]), "out/\n");
+    }
+}
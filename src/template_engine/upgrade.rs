@@ -0,0 +1,195 @@
+//! Regenerating stamped files with the current template pack
+//! (`cli-frontend upgrade --all`).
+//!
+//! Every stamped file whose template changed since it was generated is
+//! rendered again from the name and `--var` values in its stamp. Kept
+//! regions carry over, files edited by hand elsewhere are left alone, and
+//! the changes are collected as a patch set grouped by the package each
+//! file belongs to, so a monorepo-wide template upgrade can be reviewed
+//! package by package.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::diff::unified_diff;
+use super::keep::preserve_kept_regions;
+use super::stamp::{read_stamp, stamp_content, Stamp};
+use super::GeneratedFile;
+
+/// What upgrading a stamped file does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeOutcome {
+    /// Regenerated with the current template; `diff` is the change as a
+    /// unified diff relative to the scanned directory
+    Upgraded { contents: String, diff: String },
+    /// Already matches the current template
+    Current,
+    /// Edited by hand outside its kept regions, so left alone
+    Modified,
+    /// Its template is no longer in the template pack
+    TemplateMissing,
+    /// The current template no longer generates it
+    NotGenerated,
+    /// Rendering the current template failed
+    Failed(String),
+}
+
+/// A stamped file and what upgrading it does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileUpgrade {
+    /// Path relative to the scanned directory
+    pub path: PathBuf,
+    /// Closest directory with a `package.json`, relative to the scanned
+    /// directory (empty for the directory itself)
+    pub package: PathBuf,
+    pub template: String,
+    pub outcome: UpgradeOutcome,
+}
+
+/// The rendered file `path` corresponds to: the one whose path is the
+/// longest suffix of it, or else the one with its file name
+pub fn find_rendered<'a>(path: &Path, rendered: &'a [GeneratedFile]) -> Option<&'a GeneratedFile> {
+    rendered
+        .iter()
+        .filter(|file| path.ends_with(&file.path))
+        .max_by_key(|file| file.path.components().count())
+        .or_else(|| {
+            rendered
+                .iter()
+                .find(|file| file.path.file_name() == path.file_name())
+        })
+}
+
+/// The upgrade of `existing` (a stamped file at `path`) to `rendered`
+///
+/// Kept regions of `existing` carry over and the result is stamped with
+/// `stamp`. Returns [`UpgradeOutcome::Current`] when nothing changes.
+pub fn upgrade_file(path: &Path, existing: &str, rendered: &str, stamp: &Stamp) -> UpgradeOutcome {
+    let body = read_stamp(existing).map_or_else(|| existing.to_string(), |(_, body)| body);
+    let rendered = preserve_kept_regions(&body, rendered).unwrap_or_else(|| rendered.to_string());
+    let contents = stamp_content(path, &rendered, stamp).unwrap_or(rendered);
+
+    let label = path.to_string_lossy().replace('\\', "/");
+    let diff = unified_diff(
+        existing,
+        &contents,
+        &format!("a/{}", label),
+        &format!("b/{}", label),
+    );
+    if diff.is_empty() {
+        UpgradeOutcome::Current
+    } else {
+        UpgradeOutcome::Upgraded { contents, diff }
+    }
+}
+
+/// Closest directory with a `package.json` containing `path`, from `root`
+/// down, relative to `root`
+pub fn package_of(root: &Path, path: &Path) -> PathBuf {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| root.join(dir).join("package.json").is_file())
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Upgrades grouped by package, in path order
+pub fn by_package(upgrades: &[FileUpgrade]) -> BTreeMap<&Path, Vec<&FileUpgrade>> {
+    let mut packages: BTreeMap<&Path, Vec<&FileUpgrade>> = BTreeMap::new();
+    for upgrade in upgrades {
+        packages
+            .entry(upgrade.package.as_path())
+            .or_default()
+            .push(upgrade);
+    }
+    packages
+}
+
+/// Write the diffs of upgraded files as one patch, with a comment line
+/// before each package's diffs
+///
+/// The patch applies with `git apply` from the scanned directory.
+pub fn write_patch_set(upgrades: &[FileUpgrade], out: &mut dyn Write) -> std::io::Result<()> {
+    for (package, upgrades) in by_package(upgrades) {
+        let diffs: Vec<&str> = upgrades
+            .iter()
+            .filter_map(|upgrade| match &upgrade.outcome {
+                UpgradeOutcome::Upgraded { diff, .. } => Some(diff.as_str()),
+                _ => None,
+            })
+            .collect();
+        if diffs.is_empty() {
+            continue;
+        }
+        writeln!(out, "# Package: {}", package_label(package))?;
+        for diff in diffs {
+            write!(out, "{}", diff)?;
+        }
+    }
+    Ok(())
+}
+
+/// `package` for display, `.` for the scanned directory itself
+pub fn package_label(package: &Path) -> String {
+    if package.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        package.to_string_lossy().replace('\\', "/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated(path: &str) -> GeneratedFile {
+        GeneratedFile {
+            path: PathBuf::from(path),
+            contents: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_rendered_prefers_longest_suffix() {
+        let rendered = [generated("Button/Button.tsx"), generated("Button.tsx")];
+        let found = find_rendered(Path::new("src/Button/Button.tsx"), &rendered).unwrap();
+        assert_eq!(found.path, PathBuf::from("Button/Button.tsx"));
+        let found = find_rendered(Path::new("lib/Button.tsx"), &rendered[..1]).unwrap();
+        assert_eq!(found.path, PathBuf::from("Button/Button.tsx"));
+        assert!(find_rendered(Path::new("src/Card.tsx"), &rendered).is_none());
+    }
+
+    #[test]
+    fn test_upgrade_file_keeps_regions_and_restamps() {
+        let path = Path::new("src/Button.tsx");
+        let old = Stamp::new("component", "Button", "old");
+        let existing = stamp_content(
+            path,
+            "v1\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n",
+            &old,
+        )
+        .unwrap();
+        let new = Stamp::new("component", "Button", "new");
+        let rendered = "v2\n// cli-frontend:keep-start\n// cli-frontend:keep-end\n";
+
+        let UpgradeOutcome::Upgraded { contents, diff } =
+            upgrade_file(path, &existing, rendered, &new)
+        else {
+            panic!("expected an upgrade");
+        };
+        let (stamp, body) = read_stamp(&contents).unwrap();
+        assert_eq!(stamp.template_hash, "new");
+        assert_eq!(
+            body,
+            "v2\n// cli-frontend:keep-start\nmine();\n// cli-frontend:keep-end\n"
+        );
+        assert!(diff.starts_with("--- a/src/Button.tsx\n+++ b/src/Button.tsx\n"));
+        assert!(diff.contains("\n-v1\n") && diff.contains("\n+v2\n"));
+
+        assert_eq!(
+            upgrade_file(path, &contents, rendered, &new),
+            UpgradeOutcome::Current
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Recent uses of each template, shown by `--describe`.
+//!
+//! After a successful generation the name and the variables passed are
+//! recorded in the `usage_file` from the config (`~/.cli-template/usage.json`
+//! by default). Only the last [`MAX_RECENT_USES`] distinct uses of each
+//! template are kept, and nothing leaves the machine. `--describe` shows them
+//! as "Recently used like:" examples, which tend to be more telling than the
+//! synthesized ones.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// How many uses of each template are kept
+pub const MAX_RECENT_USES: usize = 5;
+
+/// One successful generation of a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateUse {
+    pub name: String,
+    /// The variables passed with `--var` or answered in the wizard
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+impl TemplateUse {
+    /// The command that repeats this use of `template_type`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::template_engine::usage::TemplateUse;
+    ///
+    /// let used = TemplateUse {
+    ///     name: "UserCard".to_string(),
+    ///     variables: [("style".to_string(), "scss".to_string())].into(),
+    /// };
+    /// assert_eq!(
+    ///     used.command("component"),
+    ///     "cli-frontend UserCard --type component --var style=scss"
+    /// );
+    /// ```
+    pub fn command(&self, template_type: &str) -> String {
+        let mut command = format!(
+            "cli-frontend {} --type {}",
+            quote(&self.name),
+            template_type
+        );
+        for (key, value) in &self.variables {
+            command.push_str(&format!(" --var {}", quote(&format!("{}={}", key, value))));
+        }
+        command
+    }
+}
+
+/// Quote `arg` for the shell when it has spaces or is empty
+fn quote(arg: &str) -> String {
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Recent uses by template name, most recent first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageHistory {
+    pub templates: BTreeMap<String, Vec<TemplateUse>>,
+}
+
+impl UsageHistory {
+    /// Load the history in `path`, starting over if it is missing or invalid
+    pub fn load(path: &Path) -> Self {
+        let Ok(bytes) = std::fs::read(path) else {
+            return Self::default();
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            crate::ui::warning!("Warning: Ignoring invalid {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Could not create directory: {}", parent.display()))?;
+        }
+        let json = serde_json::to_vec_pretty(self).context("Could not serialize usage history")?;
+        std::fs::write(path, json).with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Put `used` first among the uses of `template_type`
+    ///
+    /// A repeat of an earlier use moves it to the front instead of adding a
+    /// duplicate.
+    pub fn record(&mut self, template_type: &str, used: TemplateUse) {
+        let uses = self.templates.entry(template_type.to_string()).or_default();
+        uses.retain(|earlier| *earlier != used);
+        uses.insert(0, used);
+        uses.truncate(MAX_RECENT_USES);
+    }
+
+    /// The recent uses of `template_type`, most recent first
+    pub fn recent(&self, template_type: &str) -> &[TemplateUse] {
+        self.templates.get(template_type).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn used(name: &str) -> TemplateUse {
+        TemplateUse {
+            name: name.to_string(),
+            variables: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_the_latest_distinct_uses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("usage").join("usage.json");
+        let mut history = UsageHistory::load(&path);
+        assert!(history.recent("component").is_empty());
+
+        for name in ["A", "B", "C", "D", "E", "F", "B"] {
+            history.record("component", used(name));
+        }
+        history.save(&path).unwrap();
+
+        let history = UsageHistory::load(&path);
+        let names: Vec<&str> = history
+            .recent("component")
+            .iter()
+            .map(|u| u.name.as_str())
+            .collect();
+        assert_eq!(names, ["B", "F", "E", "D", "C"]);
+        assert!(history.recent("hook").is_empty());
+    }
+}
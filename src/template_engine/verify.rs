@@ -0,0 +1,175 @@
+//! Post-generation verification (`--verify`).
+//!
+//! Type-checks freshly generated files so a broken template is caught when
+//! it is used rather than at the next build. By default the TypeScript
+//! files among them are checked with `tsc --noEmit` through a temporary
+//! tsconfig extending the project's own, so compiler options and path
+//! aliases apply while only the generated files (and what they import) are
+//! checked. `verify_command` in the configuration replaces tsc, with
+//! `{files}` standing for the generated files.
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use super::process::{quote_paths, shell};
+use crate::error::CliError;
+use crate::ui::{self, symbols, t};
+
+/// Temporary tsconfig written next to the project's `tsconfig.json`
+const VERIFY_TSCONFIG: &str = "tsconfig.cli-frontend-verify.json";
+
+const TYPESCRIPT_EXTENSIONS: [&str; 2] = ["ts", "tsx"];
+
+/// Check generated `files` with `command`, or with tsc when there is none
+///
+/// Errors with [`CliError::VerifyFailed`] after printing the command's
+/// output when it fails.
+pub async fn verify_files(files: &[PathBuf], command: Option<&str>) -> Result<()> {
+    match command {
+        Some(command) => {
+            let command = command.replace("{files}", &quote_paths(files));
+            run_check(&command, None, files.len()).await
+        }
+        None => verify_typescript(files).await,
+    }
+}
+
+async fn verify_typescript(files: &[PathBuf]) -> Result<()> {
+    let sources = files
+        .iter()
+        .filter(|file| {
+            file.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| TYPESCRIPT_EXTENSIONS.contains(&extension))
+        })
+        .map(std::path::absolute)
+        .collect::<std::io::Result<Vec<_>>>()?;
+    if sources.is_empty() {
+        ui::status!(
+            "{} {}",
+            symbols::INFO.as_str().blue(),
+            t!("verify.nothing_to_verify")
+        );
+        return Ok(());
+    }
+
+    let project = sources[0].parent().and_then(|dir| {
+        dir.ancestors()
+            .find(|dir| dir.join("tsconfig.json").is_file())
+    });
+    let Some(project) = project else {
+        // Outside a TypeScript project: check the files on their own
+        let command = format!(
+            "npx --no tsc --noEmit --skipLibCheck --esModuleInterop --jsx react-jsx {}",
+            quote_paths(&sources)
+        );
+        return run_check(&command, None, sources.len()).await;
+    };
+
+    let relative: Vec<String> = sources
+        .iter()
+        .map(|source| {
+            let relative = source.strip_prefix(project).unwrap_or(source);
+            format!("./{}", relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    let config = serde_json::json!({
+        "extends": "./tsconfig.json",
+        "compilerOptions": { "noEmit": true },
+        "include": [],
+        "files": relative,
+    });
+    let config_path = project.join(VERIFY_TSCONFIG);
+    tokio::fs::write(&config_path, serde_json::to_vec_pretty(&config)?)
+        .await
+        .with_context(|| format!("Could not write {}", config_path.display()))?;
+
+    let command = format!("npx --no tsc -p {}", VERIFY_TSCONFIG);
+    let result = run_check(&command, Some(project), sources.len()).await;
+    if let Err(e) = tokio::fs::remove_file(&config_path).await {
+        ui::warning!("Warning: Could not remove {}: {}", config_path.display(), e);
+    }
+    result
+}
+
+/// Run `command` through the shell, reporting its output when it fails
+async fn run_check(command: &str, dir: Option<&Path>, files: usize) -> Result<()> {
+    ui::status!(
+        "{} {}",
+        symbols::INSPECT.as_str().bold(),
+        t!("verify.start", count = files, command = command.dimmed())
+    );
+
+    let mut process = shell(command);
+    // npx must not stop to ask before installing a missing tsc
+    process.stdin(Stdio::null());
+    if let Some(dir) = dir {
+        process.current_dir(dir);
+    }
+    let output = process
+        .output()
+        .await
+        .with_context(|| format!("Could not run verify command: {}", command))?;
+
+    if output.status.success() {
+        ui::status!(
+            "{} {}",
+            symbols::SUCCESS.as_str().green(),
+            t!("verify.passed")
+        );
+        return Ok(());
+    }
+
+    let mut report = String::from_utf8_lossy(&output.stdout).into_owned();
+    report.push_str(&String::from_utf8_lossy(&output.stderr));
+    for line in report.lines() {
+        ui::warning!("  {}", line);
+    }
+
+    Err(CliError::VerifyFailed {
+        command: command.to_string(),
+        errors: report
+            .lines()
+            .filter(|line| line.contains("error TS"))
+            .count(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_verify_command_receives_files_and_reports_failure() {
+        let files = [
+            PathBuf::from("src/Button.tsx"),
+            PathBuf::from("my dir/x.ts"),
+        ];
+        assert_eq!(quote_paths(&files[..1]), "src/Button.tsx");
+
+        assert!(verify_files(
+            &files,
+            Some("printf '%s\\n' {files} | grep -qx 'my dir/x.ts'")
+        )
+        .await
+        .is_ok());
+
+        let error = verify_files(
+            &files,
+            Some("echo 'Button.tsx(1,1): error TS2304: x' && false"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CliError>(),
+            Some(&CliError::VerifyFailed {
+                command: "echo 'Button.tsx(1,1): error TS2304: x' && false".to_string(),
+                errors: 1,
+            })
+        );
+    }
+}
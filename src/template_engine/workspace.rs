@@ -0,0 +1,311 @@
+//! Monorepo workspace detection for `--as-package`.
+//!
+//! Finds the closest ancestor of the package directory that declares a
+//! workspace, either `pnpm-workspace.yaml` or a `package.json` with
+//! `workspaces`, and derives what a new package needs from it: the npm
+//! scope of the root package, the base `tsconfig` to extend and whether the
+//! workspace globs already cover the package.
+
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use super::naming::to_kebab_case;
+
+/// Template layer rendered at the root of a package (`package.json`, ...)
+pub const PACKAGE_TEMPLATE: &str = "package";
+
+/// Base configs a package `tsconfig.json` can extend, in order of preference
+const BASE_TSCONFIGS: [&str; 2] = ["tsconfig.base.json", "tsconfig.json"];
+
+/// Package manager that owns a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+}
+
+impl PackageManager {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Yarn => "yarn",
+            Self::Pnpm => "pnpm",
+        }
+    }
+
+    /// Version range for depending on another package of the workspace
+    pub fn workspace_range(self) -> &'static str {
+        match self {
+            Self::Npm => "*",
+            Self::Yarn | Self::Pnpm => "workspace:*",
+        }
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A detected monorepo workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub manager: PackageManager,
+    /// Package globs, e.g. `packages/*`
+    pub packages: Vec<String>,
+    /// Scope of the root package name, e.g. `@acme`
+    pub scope: Option<String>,
+    /// Base tsconfig at the root, e.g. `tsconfig.base.json`
+    pub tsconfig: Option<String>,
+}
+
+impl Workspace {
+    /// The workspace enclosing `dir` (which need not exist yet), if any
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let dir = std::path::absolute(dir).ok()?;
+        dir.ancestors().find_map(Self::at)
+    }
+
+    /// The workspace declared in `root`, if it declares one
+    fn at(root: &Path) -> Option<Self> {
+        let package_json: Option<Value> = std::fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+        let (manager, packages) = match std::fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+            Ok(content) => (PackageManager::Pnpm, pnpm_packages(&content)),
+            Err(_) => {
+                let workspaces = package_json.as_ref()?.get("workspaces")?;
+                // Either a list of globs or `{ "packages": [...] }`
+                let globs = workspaces.get("packages").unwrap_or(workspaces);
+                let manager = if root.join("yarn.lock").exists() {
+                    PackageManager::Yarn
+                } else {
+                    PackageManager::Npm
+                };
+                (manager, string_array(globs))
+            }
+        };
+
+        let scope = package_json
+            .as_ref()
+            .and_then(|package| package["name"].as_str())
+            .and_then(|name| name.strip_prefix('@'))
+            .and_then(|name| name.split_once('/'))
+            .map(|(scope, _)| format!("@{}", scope));
+        let tsconfig = BASE_TSCONFIGS
+            .iter()
+            .find(|name| root.join(name).is_file())
+            .map(|name| name.to_string());
+
+        Some(Self {
+            root: root.to_path_buf(),
+            manager,
+            packages,
+            scope,
+            tsconfig,
+        })
+    }
+
+    /// Whether a package in `dir` is matched by the workspace globs
+    pub fn covers(&self, dir: &Path) -> bool {
+        let Some(relative) = relative_segments(&self.root, dir) else {
+            return false;
+        };
+        self.packages.iter().any(|glob| {
+            let pattern: Vec<&str> = glob
+                .trim_start_matches("./")
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            glob_matches(&pattern, &relative)
+        })
+    }
+}
+
+/// Template data exposed as `package` when generating with `--as-package`
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::template_engine::workspace::package_data;
+/// use std::path::Path;
+///
+/// let data = package_data("DatePicker", Path::new("date-picker"), None);
+/// assert_eq!(data["name"], "date-picker");
+/// assert_eq!(data["in_workspace"], false);
+/// ```
+pub fn package_data(name: &str, dir: &Path, workspace: Option<&Workspace>) -> Value {
+    let kebab = to_kebab_case(name);
+    let Some(workspace) = workspace else {
+        return json!({
+            "name": kebab,
+            "scope": "",
+            "in_workspace": false,
+            "manager": "npm",
+            "workspace_range": "*",
+            "tsconfig_extends": "",
+        });
+    };
+
+    let package_name = match &workspace.scope {
+        Some(scope) => format!("{}/{}", scope, kebab),
+        None => kebab.into_owned(),
+    };
+    // `../..` from the package back to the root
+    let up = relative_segments(&workspace.root, dir)
+        .map(|segments| vec![".."; segments.len()].join("/"))
+        .unwrap_or_default();
+    let tsconfig_extends = match &workspace.tsconfig {
+        Some(tsconfig) if !up.is_empty() => format!("{}/{}", up, tsconfig),
+        _ => String::new(),
+    };
+
+    json!({
+        "name": package_name,
+        "scope": workspace.scope.clone().unwrap_or_default(),
+        "in_workspace": true,
+        "manager": workspace.manager.as_str(),
+        "workspace_range": workspace.manager.workspace_range(),
+        "tsconfig_extends": tsconfig_extends,
+    })
+}
+
+/// Entries of the `packages:` list in `pnpm-workspace.yaml`
+fn pnpm_packages(content: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.trim().trim_matches(|c| c == '"' || c == '\'');
+            // Negated globs only exclude packages
+            if !item.is_empty() && !item.starts_with('!') {
+                packages.push(item.to_string());
+            }
+        }
+    }
+
+    packages
+}
+
+fn string_array(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Path segments of `dir` (made absolute) below `root`
+fn relative_segments(root: &Path, dir: &Path) -> Option<Vec<String>> {
+    let dir = std::path::absolute(dir).ok()?;
+    let relative = dir.strip_prefix(root).ok()?;
+    Some(
+        relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(segment) => Some(segment.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Match path segments against a workspace glob (`*` within a segment, `**` across)
+fn glob_matches(pattern: &[&str], segments: &[String]) -> bool {
+    match (pattern.split_first(), segments.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            glob_matches(rest, segments)
+                || (!segments.is_empty() && glob_matches(pattern, &segments[1..]))
+        }
+        (Some((glob, rest)), Some((segment, remaining))) => {
+            segment_matches(glob, segment) && glob_matches(rest, remaining)
+        }
+        _ => false,
+    }
+}
+
+fn segment_matches(glob: &str, segment: &str) -> bool {
+    match glob.split_once('*') {
+        None => glob == segment,
+        Some((prefix, rest)) => segment.strip_prefix(prefix).is_some_and(|tail| {
+            (0..=tail.len())
+                .filter(|&i| tail.is_char_boundary(i))
+                .any(|i| segment_matches(rest, &tail[i..]))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_pnpm_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\n  - \"!**/test/**\"\ncatalog:\n  - react\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("package.json"), r#"{"name": "@acme/monorepo"}"#).unwrap();
+        std::fs::write(root.join("tsconfig.base.json"), "{}").unwrap();
+
+        let dir = root.join("packages").join("date-picker");
+        let workspace = Workspace::detect(&dir).unwrap();
+        assert_eq!(workspace.manager, PackageManager::Pnpm);
+        assert_eq!(workspace.packages, ["packages/*"]);
+        assert!(workspace.covers(&dir));
+        assert!(!workspace.covers(&root.join("libs").join("date-picker")));
+
+        let data = package_data("DatePicker", &dir, Some(&workspace));
+        assert_eq!(data["name"], "@acme/date-picker");
+        assert_eq!(data["workspace_range"], "workspace:*");
+        assert_eq!(data["tsconfig_extends"], "../../tsconfig.base.json");
+    }
+
+    #[test]
+    fn test_detect_package_json_workspaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "design-system", "workspaces": {"packages": ["components/**"]}}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("yarn.lock"), "").unwrap();
+
+        let dir = root.join("components").join("forms").join("select");
+        let workspace = Workspace::detect(&dir).unwrap();
+        assert_eq!(workspace.manager, PackageManager::Yarn);
+        assert_eq!(workspace.scope, None);
+        assert!(workspace.covers(&dir));
+        assert_eq!(
+            package_data("Select", &dir, Some(&workspace))["name"],
+            "select"
+        );
+
+        assert!(glob_matches(&["ui-*"], &["ui-button".to_string()]));
+        assert!(!glob_matches(&["ui-*"], &["button".to_string()]));
+    }
+}
@@ -0,0 +1,332 @@
+//! Translated messages for the wizard, template inspection and generation output.
+//!
+//! Messages live in one catalog of `(key, English, Spanish)` entries and are
+//! looked up with [`t!`]; `{name}` placeholders in a message are filled from
+//! the macro's `name = value` arguments. The language comes from
+//! `CLI_FRONTEND_LANG`, then `lang` in the configuration, then the locale
+//! (`LC_ALL`, `LC_MESSAGES`, `LANG`), and falls back to English.
+
+use std::fmt;
+
+/// Language of the CLI's messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    /// Parse a language name, code or locale (`es`, `es_MX.UTF-8`, `spanish`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cli_frontend::ui::i18n::Lang;
+    ///
+    /// assert_eq!(Lang::parse("es_AR.UTF-8"), Some(Lang::Es));
+    /// assert_eq!(Lang::parse("English"), Some(Lang::En));
+    /// assert_eq!(Lang::parse("C"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" | "english" => Some(Self::En),
+            "es" | "spanish" | "español" | "espanol" => Some(Self::Es),
+            _ => None,
+        }
+    }
+}
+
+/// The language messages are printed in, from the current run's [`super::Context`]
+pub fn lang() -> Lang {
+    super::setting(|context| context.lang)
+}
+
+/// Pick the language from the environment and the configured `lang`
+pub fn detect(configured: Option<&str>) -> Lang {
+    detect_with(configured, |key| std::env::var(key).ok())
+}
+
+fn detect_with(configured: Option<&str>, var: impl Fn(&str) -> Option<String>) -> Lang {
+    let set = |key: &str| var(key).filter(|value| !value.is_empty());
+
+    if let Some(lang) = set("CLI_FRONTEND_LANG").and_then(|value| Lang::parse(&value)) {
+        return lang;
+    }
+    if let Some(lang) = configured.and_then(Lang::parse) {
+        return lang;
+    }
+    // The first locale variable that is set decides, like for other programs
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|key| set(key))
+        .and_then(|locale| Lang::parse(&locale))
+        .unwrap_or_default()
+}
+
+/// Look up a message by key, with `{name}` placeholders filled in
+macro_rules! t {
+    ($key:literal) => {
+        $crate::ui::i18n::text($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::ui::i18n::format(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+pub(crate) use t;
+
+/// The message for `key` in the current language
+///
+/// Unknown keys come back unchanged, so a missing entry shows up in the
+/// output instead of failing the run.
+pub fn text(key: &'static str) -> &'static str {
+    let Some(&(_, en, es)) = MESSAGES.iter().find(|(k, _, _)| *k == key) else {
+        return key;
+    };
+    match lang() {
+        Lang::En => en,
+        Lang::Es => es,
+    }
+}
+
+/// The message for `key` with each `{name}` replaced by its value
+pub fn format(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(text(key).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+#[rustfmt::skip]
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // Labels shared by the wizard, the inspector and the pre-flight summary
+    ("label.architecture", "Architecture:", "Arquitectura:"),
+    ("label.description", "Description:", "Descripción:"),
+    ("label.files", "Files:", "Archivos:"),
+    ("label.name", "Name:", "Nombre:"),
+    ("label.output", "Output:", "Salida:"),
+    ("label.template", "Template:", "Plantilla:"),
+    ("label.type", "Type:", "Tipo:"),
+    ("label.variables", "Variables:", "Variables:"),
+    ("label.other_group", "Other", "Otras"),
+    ("label.yes", "Yes", "Sí"),
+    ("label.no", "No", "No"),
+
+    // Wizard
+    ("wizard.title", "CLI Frontend Generator Wizard", "Asistente de CLI Frontend Generator"),
+    ("wizard.intro", "Let's create something amazing! I'll guide you through the process.", "¡Creemos algo increíble! Te guiaré durante el proceso."),
+    ("wizard.cancel_hint", "Press ESC at any time to cancel.", "Pulsa ESC en cualquier momento para cancelar."),
+    ("wizard.canceled", "Wizard canceled. See you next time!", "Asistente cancelado. ¡Hasta la próxima!"),
+    ("wizard.kind_template", "Template (component, hook, service, etc.)", "Plantilla (componente, hook, servicio, etc.)"),
+    ("wizard.kind_feature", "Complete Feature (with architecture)", "Feature completa (con arquitectura)"),
+    ("wizard.what_to_generate", "What do you want to generate?", "¿Qué quieres generar?"),
+    ("wizard.select_template", "Select template type:", "Selecciona el tipo de plantilla:"),
+    ("wizard.select_architecture", "Select architecture pattern:", "Selecciona el patrón de arquitectura:"),
+    ("wizard.enter_name", "Enter the {kind} name:", "Nombre del {kind}:"),
+    ("wizard.name_empty", "Name cannot be empty", "El nombre no puede estar vacío"),
+    ("wizard.name_too_short", "Name must be at least 2 characters long", "El nombre debe tener al menos 2 caracteres"),
+    ("wizard.name_invalid", "Name should contain only letters, numbers, and underscores", "El nombre solo puede contener letras, números y guiones bajos"),
+    ("wizard.customize_variables", "Customize template variables?", "¿Personalizar las variables de la plantilla?"),
+    ("wizard.required_variables", "Required Variables:", "Variables obligatorias:"),
+    ("wizard.keep_variables", "Keep my variables", "Mantener mis variables"),
+    ("wizard.change_variable", "Change a variable to include files?", "¿Cambiar una variable para incluir archivos?"),
+    ("wizard.select_candidate", "Several packs provide '{name}'. Which one?", "Varios packs tienen '{name}'. ¿Cuál usar?"),
+    ("wizard.confirm_generation", "Generate these files?", "¿Generar estos archivos?"),
+    ("bootstrap.prompt", "There are no templates in {dir} yet. Set them up now?", "Aún no hay plantillas en {dir}. ¿Configurarlas ahora?"),
+    ("bootstrap.builtin", "Install the built-in templates", "Instalar las plantillas incluidas"),
+    ("bootstrap.clone", "Clone {repo}", "Clonar {repo}"),
+    ("bootstrap.skip", "Not now", "Ahora no"),
+    ("bootstrap.installed", "Installed {count} template files in {dir}", "Se instalaron {count} archivos de plantillas en {dir}"),
+    ("bootstrap.cloned", "Cloned {repo} into {dir}", "Se clonó {repo} en {dir}"),
+    ("wizard.value_for", "Value for {name}:", "Valor de {name}:"),
+    ("wizard.value_required", "A value is required", "Se requiere un valor"),
+    ("wizard.additional_options", "Additional Options:", "Opciones adicionales:"),
+    ("wizard.create_folder", "Create in new folder?", "¿Crear en una carpeta nueva?"),
+    ("wizard.custom_output_dir", "Use custom output directory?", "¿Usar un directorio de salida personalizado?"),
+    ("wizard.output_dir_path", "Enter output directory path:", "Ruta del directorio de salida:"),
+    ("wizard.summary", "Summary:", "Resumen:"),
+    ("wizard.create_folder_label", "Create folder:", "Crear carpeta:"),
+    ("wizard.output_dir_label", "Output directory:", "Directorio de salida:"),
+    ("wizard.generating", "Generating...", "Generando..."),
+    ("wizard.help_component", "PascalCase (e.g., UserProfile, Navigation, ButtonGroup)", "PascalCase (p. ej., UserProfile, Navigation, ButtonGroup)"),
+    ("wizard.help_hook", "camelCase starting with 'use' (e.g., useAuth, useLocalStorage)", "camelCase empezando por 'use' (p. ej., useAuth, useLocalStorage)"),
+    ("wizard.help_service", "PascalCase ending with 'Service' (e.g., ApiService, UserService)", "PascalCase terminado en 'Service' (p. ej., ApiService, UserService)"),
+    ("wizard.help_context", "PascalCase ending with 'Context' (e.g., AuthContext, ThemeContext)", "PascalCase terminado en 'Context' (p. ej., AuthContext, ThemeContext)"),
+    ("wizard.help_page", "PascalCase (e.g., HomePage, UserProfile, Dashboard)", "PascalCase (p. ej., HomePage, UserProfile, Dashboard)"),
+    ("wizard.help_store", "camelCase or PascalCase (e.g., userStore, AuthStore)", "camelCase o PascalCase (p. ej., userStore, AuthStore)"),
+    ("wizard.help_feature", "PascalCase for the feature name (e.g., UserManagement, Authentication)", "PascalCase para el nombre de la feature (p. ej., UserManagement, Authentication)"),
+    ("wizard.help_other", "Choose a descriptive name for your template", "Elige un nombre descriptivo para tu plantilla"),
+
+    // Template inspection (--describe, --describe-all)
+    ("inspect.tags", "Tags:", "Etiquetas:"),
+    ("inspect.requires", "Requires:", "Requiere:"),
+    ("inspect.engine", "Engine:", "Motor:"),
+    ("inspect.template_variables", "Template Variables (use --var):", "Variables de la plantilla (usa --var):"),
+    ("inspect.required", "(required)", "(obligatoria)"),
+    ("inspect.options", "Options", "Opciones"),
+    ("inspect.type", "Type", "Tipo"),
+    ("inspect.default", "Default", "Por defecto"),
+    ("inspect.description", "Description", "Descripción"),
+    ("inspect.files", "Files", "Archivos"),
+    ("inspect.files_generated", "Files Generated:", "Archivos generados:"),
+    ("inspect.always", "(always)", "(siempre)"),
+    ("inspect.by_default", "(default)", "(por defecto)"),
+    ("inspect.variable_rules", "Variable Rules:", "Reglas de variables:"),
+    ("inspect.preview", "Generation Preview", "Vista previa de la generación"),
+    ("inspect.sample_name", "(sample name {name}):", "(nombre de ejemplo {name}):"),
+    ("inspect.files_and_lines", "{files} file(s), ~{lines} LOC", "{files} archivo(s), ~{lines} LOC"),
+    ("inspect.range", "Range:", "Rango:"),
+    ("inspect.showing_combinations", "Showing {shown} of {total} combinations", "Mostrando {shown} de {total} combinaciones"),
+    ("inspect.conditional_files", "{count} ({conditional} conditional)", "{count} ({conditional} condicionales)"),
+    ("inspect.template_count", "{count} template(s); required variables are marked with *", "{count} plantilla(s); las variables obligatorias se marcan con *"),
+    ("inspect.usage_examples", "Usage Examples:", "Ejemplos de uso:"),
+    ("inspect.recently_used", "Recently used like:", "Usada recientemente así:"),
+    ("inspect.example_basic", "Basic (with defaults)", "Básico (valores por defecto)"),
+    ("inspect.example_with", "With {assignment}", "Con {assignment}"),
+    ("inspect.example_full", "Full featured", "Completo"),
+
+    // Generation
+    ("generate.figma_imported", "Imported Figma component '{name}' ({count} properties)", "Componente de Figma '{name}' importado ({count} propiedades)"),
+    ("generate.feature_start", "Generating feature '{name}' with {architecture} architecture...", "Generando la feature '{name}' con la arquitectura {architecture}..."),
+    ("generate.feature_done", "Feature '{name}' generated successfully with {architecture} architecture!", "¡Feature '{name}' generada correctamente con la arquitectura {architecture}!"),
+    ("generate.features_start", "Generating {count} features ({names}) with {architecture} architecture...", "Generando {count} features ({names}) con la arquitectura {architecture}..."),
+    ("generate.features_done", "{count} features generated successfully with {architecture} architecture!", "¡{count} features generadas correctamente con la arquitectura {architecture}!"),
+    ("generate.start", "Generating {kind} '{name}'...", "Generando {kind} '{name}'..."),
+    ("generate.done", "{kind} '{name}' generated successfully!", "¡{kind} '{name}' generado correctamente!"),
+    ("generate.preflight", "Pre-flight summary", "Resumen previo"),
+    ("generate.nothing_written", "Nothing was written.", "No se escribió nada."),
+    ("generate.copied", "Copied {path} ({count} lines) to the clipboard", "{path} copiado al portapapeles ({count} líneas)"),
+    ("generate.not_copied", "{count} other generated file(s) were not copied", "No se copiaron otros {count} archivo(s) generado(s)"),
+    ("generate.opening", "Opening {path} with {command}", "Abriendo {path} con {command}"),
+    ("generate.archive_written", "Archive written: {path} ({count} files)", "Archivo comprimido escrito: {path} ({count} archivos)"),
+    ("generate.packaging_workspace", "Packaging {name} in the {manager} workspace at {root}", "Empaquetando {name} en el workspace de {manager} en {root}"),
+    ("generate.packaging", "Packaging {name} (no workspace found)", "Empaquetando {name} (no se encontró un workspace)"),
+    ("generate.using_architecture", "Using {name} architecture", "Usando la arquitectura {name}"),
+    ("generate.template_config", "Using template config: environment={environment}", "Usando la configuración de la plantilla: environment={environment}"),
+    ("generate.active_variables", "Active variables:", "Variables activas:"),
+    ("generate.patched", "Patched {path}{description}", "Modificado {path}{description}"),
+    ("generate.already_registered", "{path} already registers {name}", "{path} ya registra {name}"),
+    ("generate.structure_resumed", "Skipped {path}, completed by the interrupted run", "Se omitió {path}, completado por la ejecución interrumpida"),
+    ("generate.files_created", "Files created:", "Archivos creados:"),
+    ("generate.files_hidden", "{count} file(s) matched by .gitignore not listed (--show-all lists them)", "{count} archivo(s) ignorados por .gitignore sin listar (--show-all los lista)"),
+    ("generate.feature_structure", "Feature structure created:", "Estructura de la feature creada:"),
+    ("generate.benefits", "Benefits:", "Beneficios:"),
+    ("generate.considerations", "Considerations:", "Consideraciones:"),
+
+    // Existing files and verification
+    ("conflict.overwrite", "Overwrite {path}?", "¿Sobrescribir {path}?"),
+    ("conflict.kept", "Kept existing file (differs from template): {path}", "Se mantuvo el archivo existente (difiere de la plantilla): {path}"),
+    ("conflict.kept_binary", "Kept existing binary file (differs from template): {path}", "Se mantuvo el archivo binario existente (difiere de la plantilla): {path}"),
+    ("conflict.merged", "Merged {path}", "Fusionado {path}"),
+    ("conflict.merged_with_conflicts", "Merged {path} with {count} conflict(s), resolve the markers by hand", "Fusionado {path} con {count} conflicto(s); resuelve los marcadores a mano"),
+    ("conflict.binary_differs", "Binary file differs", "El archivo binario difiere"),
+    ("conflict.only_line_endings", "Only line endings or trailing newlines differ", "Solo difieren los finales de línea o los saltos de línea finales"),
+    ("verify.nothing_to_verify", "No TypeScript files to verify", "No hay archivos TypeScript que verificar"),
+    ("verify.start", "Verifying {count} generated file(s): {command}", "Verificando {count} archivo(s) generado(s): {command}"),
+    ("verify.passed", "Generated files passed verification", "Los archivos generados pasaron la verificación"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalog_keys_are_unique_and_translations_match() {
+        let mut keys = HashSet::new();
+        for (key, en, es) in MESSAGES {
+            assert!(keys.insert(key), "duplicate key {}", key);
+            assert_eq!(
+                placeholders(en),
+                placeholders(es),
+                "placeholders of {}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_used_key_is_in_the_catalog() {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut dirs = vec![src];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                for (i, _) in source.match_indices("t!(\"") {
+                    // Skip `format!(`, `print!(` and other macros ending in `t`
+                    let before = source[..i].chars().next_back().unwrap_or(' ');
+                    if before.is_alphanumeric() || before == '_' {
+                        continue;
+                    }
+                    let key = source[i + 4..].split('"').next().unwrap();
+                    assert!(
+                        MESSAGES.iter().any(|(k, _, _)| *k == key),
+                        "{} uses unknown message {}",
+                        path.display(),
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_prefers_override_then_config_then_locale() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(detect_with(None, env(&[])), Lang::En);
+        assert_eq!(detect_with(None, env(&[("LANG", "es_ES.UTF-8")])), Lang::Es);
+        assert_eq!(
+            detect_with(None, env(&[("LC_ALL", "C"), ("LANG", "es_ES.UTF-8")])),
+            Lang::En
+        );
+        assert_eq!(
+            detect_with(Some("es"), env(&[("LANG", "en_US.UTF-8")])),
+            Lang::Es
+        );
+        assert_eq!(
+            detect_with(Some("es"), env(&[("CLI_FRONTEND_LANG", "en")])),
+            Lang::En
+        );
+    }
+
+    #[test]
+    fn test_format_fills_placeholders() {
+        assert_eq!(
+            format(
+                "generate.start",
+                &[("kind", &"component"), ("name", &"Button")]
+            ),
+            "Generating component 'Button'..."
+        );
+        assert_eq!(text("missing.key"), "missing.key");
+    }
+}
@@ -0,0 +1,436 @@
+//! Terminal output settings shared by every printing path.
+//!
+//! `--quiet` silences progress output: errors and warnings still go to
+//! stderr, and output that was asked for (`--describe`, `search`, `--list`)
+//! still prints. `--no-emoji`, or a non-empty `NO_COLOR`, strips emoji from
+//! everything printed, for CI logs and terminals that can't render them.
+//! A terminal that can't show UTF-8 at all gets the ASCII fallbacks of
+//! [`symbols`] instead.
+//!
+//! The settings and the streams they print to belong to one run: a
+//! [`Context`] built from the command line and entered with
+//! [`Context::scope`], so runs embedded in another program (or several in
+//! one test binary) don't share them. Outside a run, output goes to the
+//! process streams with the settings of the environment. Printing goes
+//! through the macros: [`status!`] for progress, [`show!`] for requested
+//! output, [`warning!`] for warnings on stderr, and [`status_to!`] /
+//! [`show_to!`] for the same into a writer. Machine-readable output (JSON,
+//! manifests, patches, scripts) goes through [`data!`] / [`data_to!`],
+//! which write it exactly as given.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+
+pub mod i18n;
+pub mod symbols;
+
+#[allow(unused_imports)]
+pub(crate) use i18n::t;
+use i18n::Lang;
+
+/// A stream output is written to, shared by everything printing in a run
+#[derive(Clone)]
+pub struct Output(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl Output {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(writer))))
+    }
+
+    /// The process's standard output
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+
+    /// The process's standard error
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Box<dyn Write + Send>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lock().write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.lock().write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().flush()
+    }
+}
+
+/// An in-memory [`Output`] target, for capturing what a run prints
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::ui::{Buffer, Output};
+/// use std::io::Write;
+///
+/// let buffer = Buffer::default();
+/// let mut output = Output::new(buffer.clone());
+/// writeln!(output, "done").unwrap();
+/// assert_eq!(buffer.contents(), "done\n");
+/// ```
+#[derive(Clone, Default)]
+pub struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    /// Everything written so far
+    pub fn contents(&self) -> String {
+        let bytes = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut bytes = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Output settings and streams of one run
+#[derive(Clone)]
+pub struct Context {
+    quiet: bool,
+    no_emoji: bool,
+    ascii_only: bool,
+    lang: Lang,
+    stdout: Output,
+    stderr: Output,
+}
+
+tokio::task_local! {
+    static CONTEXT: Context;
+}
+
+impl Context {
+    /// Settings for `--quiet` and `--no-emoji`; `NO_COLOR` turns emoji off
+    /// as well, a terminal without UTF-8 support gets ASCII only, and the
+    /// language comes from the environment until [`Context::with_lang`]
+    pub fn new(quiet: bool, no_emoji: bool, stdout: Output, stderr: Output) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self {
+            quiet,
+            no_emoji: no_emoji || no_color,
+            ascii_only: !symbols::terminal_supports_unicode(),
+            lang: i18n::detect(None),
+            stdout,
+            stderr,
+        }
+    }
+
+    /// The same settings, printing messages in `lang`
+    pub fn with_lang(self, lang: Lang) -> Self {
+        Self { lang, ..self }
+    }
+
+    /// The context of the current run, or the process default outside one
+    pub fn current() -> Self {
+        CONTEXT
+            .try_with(Context::clone)
+            .unwrap_or_else(|_| fallback().clone())
+    }
+
+    /// Run `future` with this context; tasks it spawns through [`spawn`]
+    /// keep it
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CONTEXT.scope(self, future).await
+    }
+}
+
+/// The context outside a run: the process streams and the environment's settings
+fn fallback() -> &'static Context {
+    static FALLBACK: OnceLock<Context> = OnceLock::new();
+    FALLBACK.get_or_init(|| {
+        let (stdout, stderr) = (Printed { stderr: false }, Printed { stderr: true });
+        Context::new(false, false, Output::new(stdout), Output::new(stderr))
+    })
+}
+
+/// The process streams through `print!`, which test harnesses capture
+struct Printed {
+    stderr: bool,
+}
+
+impl Write for Printed {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        if self.stderr {
+            eprint!("{}", text);
+        } else {
+            print!("{}", text);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+/// Read a setting of the current context
+fn setting<R>(get: impl Fn(&Context) -> R) -> R {
+    CONTEXT.try_with(&get).unwrap_or_else(|_| get(fallback()))
+}
+
+/// `tokio::spawn` that keeps the current run's context in the task
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(Context::current().scope(future))
+}
+
+/// Whether progress output is silenced
+pub fn is_quiet() -> bool {
+    setting(|context| context.quiet)
+}
+
+/// Whether emoji are printed
+pub fn emoji_enabled() -> bool {
+    setting(|context| !context.no_emoji) && !ascii_only()
+}
+
+/// Whether symbols print as their ASCII fallbacks
+pub fn ascii_only() -> bool {
+    setting(|context| context.ascii_only)
+}
+
+/// Print a progress line, unless `--quiet`
+macro_rules! status {
+    () => {
+        $crate::ui::print_status(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::ui::print_status(format_args!($($arg)*))
+    };
+}
+
+/// Print a line of output that was asked for
+macro_rules! show {
+    () => {
+        $crate::ui::print_line(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::ui::print_line(format_args!($($arg)*))
+    };
+}
+
+/// Print a warning line to stderr
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        $crate::ui::print_warning(format_args!($($arg)*))
+    };
+}
+
+/// Write a progress line to a writer, unless `--quiet`
+macro_rules! status_to {
+    ($out:expr, $($arg:tt)*) => {
+        $crate::ui::write_status(&mut *$out, format_args!($($arg)*))
+    };
+}
+
+/// Write a line of requested output to a writer
+macro_rules! show_to {
+    ($out:expr) => {
+        $crate::ui::write_line(&mut *$out, format_args!(""))
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $crate::ui::write_line(&mut *$out, format_args!($($arg)*))
+    };
+}
+
+/// Print a line of data (JSON, a manifest, a script) exactly as given
+macro_rules! data {
+    ($($arg:tt)*) => {
+        $crate::ui::print_data(format_args!($($arg)*))
+    };
+}
+
+/// Write a line of data to a writer exactly as given
+macro_rules! data_to {
+    ($out:expr, $($arg:tt)*) => {
+        $crate::ui::write_data(&mut *$out, format_args!($($arg)*))
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {data, data_to, show, show_to, status, status_to, warning};
+
+/// Print `args` as a line, unless `--quiet` (used by [`status!`])
+pub fn print_status(args: fmt::Arguments) {
+    if !is_quiet() {
+        print_line(args);
+    }
+}
+
+/// Print `args` as a line (used by [`show!`])
+pub fn print_line(args: fmt::Arguments) {
+    // Output is best effort, like `println!` but without panicking on a closed pipe
+    let _ = write_line(&mut setting(|context| context.stdout.clone()), args);
+}
+
+/// Print `args` as a line to stderr (used by [`warning!`])
+pub fn print_warning(args: fmt::Arguments) {
+    let _ = write_line(&mut setting(|context| context.stderr.clone()), args);
+}
+
+/// Write `args` as a line to `out`, unless `--quiet` (used by [`status_to!`])
+pub fn write_status(out: &mut dyn Write, args: fmt::Arguments) -> io::Result<()> {
+    if is_quiet() {
+        return Ok(());
+    }
+    write_line(out, args)
+}
+
+/// Write `args` as a line to `out` (used by [`show_to!`])
+pub fn write_line(out: &mut dyn Write, args: fmt::Arguments) -> io::Result<()> {
+    // One write per line, so lines printed by concurrent tasks don't interleave
+    out.write_all(format!("{}\n", plain(&args.to_string())).as_bytes())
+}
+
+/// Print `args` as a line, untouched by `--no-emoji` or the ASCII
+/// fallbacks (used by [`data!`])
+pub fn print_data(args: fmt::Arguments) {
+    let _ = write_data(&mut setting(|context| context.stdout.clone()), args);
+}
+
+/// Write `args` as a line to `out`, untouched by `--no-emoji` or the ASCII
+/// fallbacks (used by [`data_to!`])
+pub fn write_data(out: &mut dyn Write, args: fmt::Arguments) -> io::Result<()> {
+    out.write_all(format!("{}\n", args).as_bytes())
+}
+
+/// `text` as it should be printed: in ASCII, or without emoji when they are off
+pub fn plain(text: &str) -> Cow<'_, str> {
+    if ascii_only() {
+        symbols::to_ascii(text)
+    } else if emoji_enabled() {
+        Cow::Borrowed(text)
+    } else {
+        strip_emoji(text)
+    }
+}
+
+/// Remove emoji, with the space that followed each one
+///
+/// Check and cross marks (`✓`, `✗`) are text symbols and stay, and ANSI
+/// color codes around an emoji are kept.
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::ui::strip_emoji;
+///
+/// assert_eq!(strip_emoji("✅ Done"), "Done");
+/// assert_eq!(strip_emoji("  ⚠️  Check this"), "   Check this");
+/// assert_eq!(strip_emoji("  ✗ failed"), "  ✗ failed");
+/// ```
+pub fn strip_emoji(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_emoji) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut drop_space = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if is_emoji(c) {
+            drop_space = true;
+        } else if c == '\x1b' {
+            // Keep escape sequences whole, so the space after the emoji is still found
+            output.push(c);
+            for c in chars.by_ref() {
+                output.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if drop_space && c == ' ' {
+            drop_space = false;
+        } else {
+            drop_space = false;
+            output.push(c);
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Emoji and the characters that join or style them
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // Pictographs, emoticons, transport, symbols
+        | 0x2600..=0x26FF // Miscellaneous symbols (⚠, ⚙)
+        | 0x2700..=0x2712 | 0x2719..=0x27BF // Dingbats, except ✓ ✔ ✕ ✖ ✗ ✘
+        | 0x2139 // ℹ
+        | 0x23E9..=0x23FA // ⏱ and other clocks
+        | 0x2B50 | 0x2B55
+        | 0xFE0F | 0x200D // Variation selector and zero-width joiner
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_emoji_keeps_colors_and_text_symbols() {
+        assert_eq!(
+            strip_emoji("\x1b[1m🚀\x1b[0m Generating 'Button'..."),
+            "\x1b[1m\x1b[0mGenerating 'Button'..."
+        );
+        assert_eq!(strip_emoji("🧙‍♂️ Wizard"), "Wizard");
+        assert_eq!(strip_emoji("  · ✓ kept"), "  · ✓ kept");
+        assert!(matches!(strip_emoji("plain text"), Cow::Borrowed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_runs_keep_their_own_context() {
+        let (loud, quiet) = (Buffer::default(), Buffer::default());
+        let errors = Buffer::default();
+        let context = |quiet: bool, stdout: &Buffer| {
+            Context::new(
+                quiet,
+                false,
+                Output::new(stdout.clone()),
+                Output::new(errors.clone()),
+            )
+            .with_lang(Lang::Es)
+        };
+
+        let print = || async {
+            status!("progress");
+            spawn(async { show!("from a task in {:?}", i18n::lang()) })
+                .await
+                .unwrap();
+            warning!("careful");
+        };
+        tokio::join!(
+            context(false, &loud).scope(print()),
+            context(true, &quiet).scope(print()),
+        );
+
+        assert_eq!(loud.contents(), "progress\nfrom a task in Es\n");
+        assert_eq!(quiet.contents(), "from a task in Es\n");
+        assert_eq!(errors.contents(), "careful\ncareful\n");
+        assert_eq!(i18n::lang(), i18n::detect(None));
+    }
+}
@@ -0,0 +1,256 @@
+//! Decorative symbols with ASCII fallbacks.
+//!
+//! Output is decorated with emoji and a few other non-ASCII marks (`✓`,
+//! `→`, `•`). A terminal that doesn't decode UTF-8 — the classic Windows
+//! console, a `C` locale, the Linux virtual console — shows them as garbage
+//! like `üìÅ`. When [`terminal_supports_unicode`] says so, [`crate::ui`]
+//! switches to ASCII and every [`Symbol`] prints its fallback instead.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// A decorative mark and what to print in its place on an ASCII terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub unicode: &'static str,
+    pub ascii: &'static str,
+}
+
+impl Symbol {
+    const fn new(unicode: &'static str, ascii: &'static str) -> Self {
+        Self { unicode, ascii }
+    }
+
+    /// The form the terminal can show
+    pub fn as_str(&self) -> &'static str {
+        if super::ascii_only() {
+            self.ascii
+        } else {
+            self.unicode
+        }
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub const SUCCESS: Symbol = Symbol::new("✅", "[ok]");
+pub const CHECK: Symbol = Symbol::new("✓", "+");
+pub const CROSS: Symbol = Symbol::new("✗", "x");
+pub const WARNING: Symbol = Symbol::new("⚠️", "!");
+pub const INFO: Symbol = Symbol::new("ℹ️", "i");
+pub const SKIP: Symbol = Symbol::new("⏭️", "-");
+pub const UNDO: Symbol = Symbol::new("↩️", "<-");
+pub const ARROW: Symbol = Symbol::new("→", "->");
+pub const BULLET: Symbol = Symbol::new("•", "*");
+pub const DOT: Symbol = Symbol::new("·", "-");
+pub const DASH: Symbol = Symbol::new("–", "-");
+pub const PENDING: Symbol = Symbol::new("○", "o");
+pub const LIST: Symbol = Symbol::new("📋", "*");
+pub const SEARCH: Symbol = Symbol::new("🔍", "*");
+pub const INSPECT: Symbol = Symbol::new("🔎", "*");
+pub const LAUNCH: Symbol = Symbol::new("🚀", ">");
+pub const PACKAGE: Symbol = Symbol::new("📦", "*");
+pub const FOLDER: Symbol = Symbol::new("📁", "*");
+pub const DIRECTORY: Symbol = Symbol::new("📂", "+");
+pub const FILE: Symbol = Symbol::new("📄", "-");
+pub const GOODBYE: Symbol = Symbol::new("👋", "*");
+pub const ARCHITECTURE: Symbol = Symbol::new("🏗️", "*");
+pub const WIZARD: Symbol = Symbol::new("🧙‍♂️", "*");
+pub const DOCS: Symbol = Symbol::new("📚", "*");
+pub const TEST: Symbol = Symbol::new("🧪", "*");
+pub const SNAPSHOT: Symbol = Symbol::new("📸", "*");
+pub const DOCTOR: Symbol = Symbol::new("🩺", "*");
+pub const IMPORT: Symbol = Symbol::new("📥", "*");
+pub const SYNC: Symbol = Symbol::new("🔄", "*");
+pub const DESIGN: Symbol = Symbol::new("🎨", "*");
+pub const NOTE: Symbol = Symbol::new("📝", "*");
+pub const SERVER: Symbol = Symbol::new("🌐", "*");
+pub const HINT: Symbol = Symbol::new("💡", "?");
+pub const TIMER: Symbol = Symbol::new("⏱️", "*");
+pub const LAYOUT: Symbol = Symbol::new("📐", "*");
+pub const PLUGIN: Symbol = Symbol::new("🔌", "*");
+pub const SETTINGS: Symbol = Symbol::new("⚙️", "*");
+pub const TOOL: Symbol = Symbol::new("🔧", "*");
+pub const MERGE: Symbol = Symbol::new("🔀", "*");
+pub const EDIT: Symbol = Symbol::new("✏️", "*");
+
+const ALL: &[Symbol] = &[
+    SUCCESS,
+    CHECK,
+    CROSS,
+    WARNING,
+    INFO,
+    SKIP,
+    UNDO,
+    ARROW,
+    BULLET,
+    DOT,
+    DASH,
+    PENDING,
+    LIST,
+    SEARCH,
+    INSPECT,
+    LAUNCH,
+    PACKAGE,
+    FOLDER,
+    DIRECTORY,
+    FILE,
+    GOODBYE,
+    ARCHITECTURE,
+    WIZARD,
+    DOCS,
+    TEST,
+    SNAPSHOT,
+    DOCTOR,
+    IMPORT,
+    SYNC,
+    DESIGN,
+    NOTE,
+    SERVER,
+    HINT,
+    TIMER,
+    LAYOUT,
+    PLUGIN,
+    SETTINGS,
+    TOOL,
+    MERGE,
+    EDIT,
+];
+
+/// Whether the terminal can show UTF-8, judged from the environment
+///
+/// On Windows only terminals that announce themselves (Windows Terminal,
+/// VS Code, ConEmu, mintty) are trusted; the console host alone is not.
+/// Elsewhere the locale decides, and the Linux virtual console never can.
+pub fn terminal_supports_unicode() -> bool {
+    supports_unicode(cfg!(windows), |key| std::env::var(key).ok())
+}
+
+fn supports_unicode(windows: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+    let set = |key: &str| var(key).filter(|value| !value.is_empty());
+
+    if windows {
+        return ["WT_SESSION", "TERM_PROGRAM", "ConEmuANSI", "TERM"]
+            .iter()
+            .any(|key| set(key).is_some());
+    }
+    if set("TERM").as_deref() == Some("linux") {
+        return false;
+    }
+
+    match ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|key| set(key))
+    {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+/// Replace known symbols with their ASCII fallbacks and drop other emoji
+///
+/// # Example
+///
+/// ```
+/// use cli_frontend::ui::symbols::to_ascii;
+///
+/// assert_eq!(to_ascii("📁 Feature structure created:"), "* Feature structure created:");
+/// assert_eq!(to_ascii("  ⚠️  Check this"), "  !  Check this");
+/// assert_eq!(to_ascii("🦀 Ferris"), "Ferris");
+/// ```
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(symbol) = ALL.iter().find(|symbol| rest.starts_with(symbol.unicode)) {
+            output.push_str(symbol.ascii);
+            rest = &rest[symbol.unicode.len()..];
+        } else if let Some(base) = ALL.iter().find(|symbol| symbol.unicode.starts_with(c)) {
+            // The same mark written with or without its variation selector
+            output.push_str(base.ascii);
+            rest = skip_modifiers(&rest[c.len_utf8()..]);
+        } else if super::is_emoji(c) {
+            rest = skip_modifiers(&rest[c.len_utf8()..]);
+            rest = rest.strip_prefix(' ').unwrap_or(rest);
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// `text` past the variation selectors and joined characters of an emoji
+fn skip_modifiers(mut text: &str) -> &str {
+    loop {
+        let mut chars = text.chars();
+        match chars.next() {
+            Some('\u{FE0F}') => text = chars.as_str(),
+            Some('\u{200D}') => {
+                chars.next();
+                text = chars.as_str();
+            }
+            _ => return text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_supports_unicode_from_environment() {
+        assert!(supports_unicode(false, env(&[])));
+        assert!(supports_unicode(false, env(&[("LANG", "en_US.UTF-8")])));
+        assert!(!supports_unicode(false, env(&[("LANG", "C")])));
+        assert!(!supports_unicode(
+            false,
+            env(&[("LC_ALL", "POSIX"), ("LANG", "en_US.UTF-8")])
+        ));
+        assert!(supports_unicode(
+            false,
+            env(&[("LC_ALL", ""), ("LANG", "de_DE.utf8")])
+        ));
+        assert!(!supports_unicode(
+            false,
+            env(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")])
+        ));
+        assert!(!supports_unicode(true, env(&[])));
+        assert!(supports_unicode(true, env(&[("WT_SESSION", "1")])));
+    }
+
+    #[test]
+    fn test_to_ascii_replaces_every_symbol() {
+        for symbol in ALL {
+            assert!(symbol.ascii.is_ascii());
+            assert_eq!(to_ascii(symbol.unicode), symbol.ascii);
+        }
+        assert_eq!(to_ascii("⚠ bare"), "! bare");
+        assert_eq!(to_ascii("🧙‍♂️ Wizard"), "* Wizard");
+        assert_eq!(
+            to_ascii("\x1b[32m✓\x1b[0m done → next"),
+            "\x1b[32m+\x1b[0m done -> next"
+        );
+        assert_eq!(to_ascii("Café"), "Café");
+    }
+}
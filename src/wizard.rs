@@ -1,10 +1,16 @@
 use anyhow::Result;
 use colored::*;
 use inquire::{validator::Validation, Confirm, InquireError, Select, Text};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use crate::bootstrap::{clone_starter_templates, install_default_templates};
 use crate::cli::Args;
 use crate::config::Config;
+use crate::error::TemplateCandidate;
+use crate::template_engine::toml_config::is_internal_template;
+use crate::template_engine::{TemplateDescription, TemplateEngine, VariableDescription};
+use crate::ui::{self, symbols, t};
 
 /// Configuration captured from the interactive wizard
 #[derive(Debug, Clone)]
@@ -14,6 +20,8 @@ pub struct WizardConfig {
     pub architecture: Option<String>,
     pub create_folder: bool,
     pub output_dir: Option<PathBuf>,
+    /// Template variables as `KEY=VALUE`, like `--var`
+    pub vars: Vec<String>,
 }
 
 /// Types of generation available in the wizard
@@ -26,20 +34,29 @@ enum GenerationType {
 impl GenerationType {
     fn as_display_string(&self) -> String {
         match self {
-            GenerationType::Template => "📄 Template (component, hook, service, etc.)".to_string(),
-            GenerationType::Feature => "🏗️  Complete Feature (with architecture)".to_string(),
+            GenerationType::Template => format!("{} {}", symbols::FILE, t!("wizard.kind_template")),
+            GenerationType::Feature => {
+                format!("{}  {}", symbols::ARCHITECTURE, t!("wizard.kind_feature"))
+            }
         }
     }
 }
 
 /// Main wizard entry point
-pub async fn run_wizard(config: &Config) -> Result<WizardConfig> {
+///
+/// Internal templates are only offered when `include_internal` is set.
+pub async fn run_wizard(config: &Config, include_internal: bool) -> Result<WizardConfig> {
     display_welcome();
 
     let generation_type = handle_prompt_result(prompt_generation_type())?;
 
     let wizard_config = match generation_type {
-        GenerationType::Template => run_template_wizard(config)?,
+        GenerationType::Template => {
+            let mut wizard_config = run_template_wizard(config, include_internal)?;
+            wizard_config.vars =
+                prompt_template_variables(config, &wizard_config.template_type).await?;
+            wizard_config
+        }
         GenerationType::Feature => run_feature_wizard(config)?,
     };
 
@@ -54,28 +71,59 @@ impl From<WizardConfig> for Args {
             name: Some(config.name),
             template_type: Some(config.template_type),
             architecture: config.architecture,
+            tree: false,
+            resume: false,
             no_folder: !config.create_folder,
+            as_package: false,
+            license_file: false,
             output_dir: config.output_dir,
+            output_archive: None,
+            audit_log: None,
+            stdout: false,
+            copy: false,
+            strict: false,
+            no_env_helpers: false,
+            env: None,
+            on_conflict: None,
+            keep_going: false,
+            all_errors: false,
+            timeout: None,
+            stamp: false,
+            yes: false,
+            quiet: false,
+            no_emoji: false,
+            verify: false,
+            open: false,
+            profile_run: false,
+            show_all: false,
+            explain: None,
             config: None,
             list: false,
-            vars: Vec::new(), // Wizard doesn't support vars yet (could be added as future enhancement)
+            tag: None,
+            include_internal: false,
+            vars: config.vars,
+            figma_json: None,
+            tokens: None,
             describe: None,
+            describe_all: false,
+            json: false,
+            command: None,
         }
     }
 }
 
 /// Display welcome message
 fn display_welcome() {
-    println!("{}", "🧙‍♂️ CLI Frontend Generator Wizard".bold().cyan());
-    println!("{}", "=====================================".cyan());
-    println!("Let's create something amazing! I'll guide you through the process.");
-    println!("{}", "Press ESC at any time to cancel.".dimmed());
-    println!();
+    ui::show!("{} {}", symbols::WIZARD, t!("wizard.title").bold().cyan());
+    ui::show!("{}", "=====================================".cyan());
+    ui::show!("{}", t!("wizard.intro"));
+    ui::show!("{}", t!("wizard.cancel_hint").dimmed());
+    ui::show!();
 }
 
 /// Handle user cancellation gracefully
 fn handle_cancellation() -> ! {
-    println!("\n{}", "👋 Wizard canceled. See you next time!".yellow());
+    ui::show!("\n{} {}", symbols::GOODBYE, t!("wizard.canceled").yellow());
     std::process::exit(0);
 }
 
@@ -93,10 +141,10 @@ fn prompt_generation_type() -> std::result::Result<GenerationType, InquireError>
     let options = [GenerationType::Template, GenerationType::Feature];
     let display_options: Vec<String> = options.iter().map(|opt| opt.as_display_string()).collect();
 
-    let selection = Select::new("What do you want to generate?", display_options).prompt()?;
+    let selection = Select::new(t!("wizard.what_to_generate"), display_options).prompt()?;
 
     // Map display string back to enum
-    if selection.contains("Template") {
+    if selection == GenerationType::Template.as_display_string() {
         Ok(GenerationType::Template)
     } else {
         Ok(GenerationType::Feature)
@@ -104,7 +152,7 @@ fn prompt_generation_type() -> std::result::Result<GenerationType, InquireError>
 }
 
 /// Run wizard flow for template generation
-fn run_template_wizard(config: &Config) -> Result<WizardConfig> {
+fn run_template_wizard(config: &Config, include_internal: bool) -> Result<WizardConfig> {
     // Get available templates
     let templates = Args::discover_templates(config.templates_dir());
 
@@ -113,10 +161,14 @@ fn run_template_wizard(config: &Config) -> Result<WizardConfig> {
     }
 
     // Remove 'feature' from templates list as it's handled separately
-    let template_options: Vec<String> = templates.into_iter().filter(|t| t != "feature").collect();
+    let template_options: Vec<String> = templates
+        .into_iter()
+        .filter(|t| t != "feature")
+        .filter(|t| include_internal || !is_internal_template(&config.templates_dir().join(t)))
+        .collect();
 
     let template_type =
-        handle_prompt_result(Select::new("Select template type:", template_options).prompt())?;
+        handle_prompt_result(Select::new(t!("wizard.select_template"), template_options).prompt())?;
 
     let name = prompt_name_with_suggestions(&template_type)?;
     let (create_folder, output_dir) = prompt_additional_options(config)?;
@@ -127,6 +179,7 @@ fn run_template_wizard(config: &Config) -> Result<WizardConfig> {
         architecture: None,
         create_folder,
         output_dir,
+        vars: Vec::new(),
     })
 }
 
@@ -141,8 +194,9 @@ fn run_feature_wizard(config: &Config) -> Result<WizardConfig> {
         ));
     }
 
-    let architecture =
-        handle_prompt_result(Select::new("Select architecture pattern:", architectures).prompt())?;
+    let architecture = handle_prompt_result(
+        Select::new(t!("wizard.select_architecture"), architectures).prompt(),
+    )?;
 
     let name = prompt_name_with_suggestions("feature")?;
     let (create_folder, output_dir) = prompt_additional_options(config)?;
@@ -153,6 +207,7 @@ fn run_feature_wizard(config: &Config) -> Result<WizardConfig> {
         architecture: Some(architecture),
         create_folder,
         output_dir,
+        vars: Vec::new(),
     })
 }
 
@@ -161,19 +216,15 @@ fn prompt_name_with_suggestions(template_type: &str) -> Result<String> {
     let help_text = get_naming_help(template_type);
 
     let name = handle_prompt_result(
-        Text::new(&format!("Enter the {} name:", template_type))
+        Text::new(&t!("wizard.enter_name", kind = template_type))
             .with_help_message(help_text)
             .with_validator(|input: &str| {
                 if input.trim().is_empty() {
-                    Ok(Validation::Invalid("Name cannot be empty".into()))
+                    Ok(Validation::Invalid(t!("wizard.name_empty").into()))
                 } else if input.trim().len() < 2 {
-                    Ok(Validation::Invalid(
-                        "Name must be at least 2 characters long".into(),
-                    ))
+                    Ok(Validation::Invalid(t!("wizard.name_too_short").into()))
                 } else if !is_valid_name(input.trim()) {
-                    Ok(Validation::Invalid(
-                        "Name should contain only letters, numbers, and underscores".into(),
-                    ))
+                    Ok(Validation::Invalid(t!("wizard.name_invalid").into()))
                 } else {
                     Ok(Validation::Valid)
                 }
@@ -184,25 +235,202 @@ fn prompt_name_with_suggestions(template_type: &str) -> Result<String> {
     Ok(name.trim().to_string())
 }
 
+/// Offer to set the template's variables, asking group by group
+async fn prompt_template_variables(config: &Config, template_type: &str) -> Result<Vec<String>> {
+    let engine = TemplateEngine::new(config.templates_dir().clone(), config.output_dir().clone())?
+        .with_defaults(config.template_defaults());
+    let description = engine.template_description(template_type).await?;
+
+    if description.variables.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let customize = handle_prompt_result(
+        Confirm::new(t!("wizard.customize_variables"))
+            .with_default(false)
+            .prompt(),
+    )?;
+    if !customize {
+        return Ok(Vec::new());
+    }
+
+    let mut vars = Vec::new();
+    for (group, variables) in description.grouped_variables() {
+        let heading = if group.is_empty() {
+            t!("label.other_group")
+        } else {
+            group
+        };
+        ui::show!("\n{}", heading.bold());
+
+        for (name, variable) in variables {
+            let value = prompt_variable(name, variable)?;
+            vars.push(format!("{}={}", name, value));
+        }
+    }
+
+    Ok(vars)
+}
+
+/// Ask for each required variable that has no value yet
+pub fn prompt_required_variables(
+    description: &TemplateDescription,
+    missing: &[String],
+) -> Result<HashMap<String, String>> {
+    ui::show!("\n{}", t!("wizard.required_variables").bold());
+
+    let mut values = HashMap::new();
+    for name in missing {
+        let value = prompt_variable(name, &description.variables[name])?;
+        values.insert(name.clone(), value);
+    }
+
+    Ok(values)
+}
+
+/// Offer the `NAME=VALUE` changes that would give the template files to generate
+///
+/// Returns `None` when the user keeps their variables.
+pub fn prompt_variable_change(suggestions: &[String]) -> Result<Option<String>> {
+    let keep = t!("wizard.keep_variables");
+
+    let mut options = suggestions.to_vec();
+    options.push(keep.to_string());
+    let selection =
+        handle_prompt_result(Select::new(t!("wizard.change_variable"), options).prompt())?;
+
+    Ok(Some(selection).filter(|s| s != keep))
+}
+
+/// Choose between templates of the same name from different packs
+///
+/// Returns the `pack:template` name of the chosen one.
+pub fn select_template_candidate(name: &str, candidates: &[TemplateCandidate]) -> Result<String> {
+    let options: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+    let selection = handle_prompt_result(
+        Select::new(&t!("wizard.select_candidate", name = name), options).raw_prompt(),
+    )?;
+    Ok(candidates[selection.index].name.clone())
+}
+
+/// Offer to fill the empty `templates_dir` with the built-in templates or
+/// the `starter` repository
+///
+/// Returns whether templates were installed.
+pub fn offer_bootstrap(templates_dir: &Path, starter: Option<&str>) -> Result<bool> {
+    let mut options = vec![t!("bootstrap.builtin").to_string()];
+    if let Some(repo) = starter {
+        options.push(t!("bootstrap.clone", repo = repo));
+    }
+    options.push(t!("bootstrap.skip").to_string());
+    let selection = handle_prompt_result(
+        Select::new(
+            &t!("bootstrap.prompt", dir = templates_dir.display()),
+            options,
+        )
+        .raw_prompt(),
+    )?;
+
+    let message = match (selection.index, starter) {
+        (0, _) => {
+            let count = install_default_templates(templates_dir)?;
+            t!(
+                "bootstrap.installed",
+                count = count,
+                dir = templates_dir.display()
+            )
+        }
+        (1, Some(repo)) => {
+            clone_starter_templates(repo, templates_dir)?;
+            t!(
+                "bootstrap.cloned",
+                repo = repo,
+                dir = templates_dir.display()
+            )
+        }
+        _ => return Ok(false),
+    };
+    ui::status!("{} {}", symbols::SUCCESS, message);
+    Ok(true)
+}
+
+/// Ask whether to go ahead after the pre-flight summary
+pub fn confirm_generation() -> Result<bool> {
+    handle_prompt_result(
+        Confirm::new(t!("wizard.confirm_generation"))
+            .with_default(true)
+            .prompt(),
+    )
+}
+
+/// Ask for one variable using its `{var}_prompt` text, options and default
+fn prompt_variable(name: &str, variable: &VariableDescription) -> Result<String> {
+    let message = if variable.prompt.is_empty() {
+        t!("wizard.value_for", name = name)
+    } else {
+        variable.prompt.clone()
+    };
+    let help = Some(variable.description.as_str()).filter(|d| !d.is_empty());
+    let default = variable.default.as_deref();
+
+    if !variable.values.is_empty() {
+        let start = default
+            .and_then(|d| variable.values.iter().position(|v| v == d))
+            .unwrap_or(0);
+        let mut prompt = Select::new(&message, variable.values.clone()).with_starting_cursor(start);
+        if let Some(help) = help {
+            prompt = prompt.with_help_message(help);
+        }
+        return handle_prompt_result(prompt.prompt());
+    }
+
+    if variable.var_type == "boolean" {
+        let default = default.is_some_and(|d| matches!(d, "true" | "yes" | "1"));
+        let mut prompt = Confirm::new(&message).with_default(default);
+        if let Some(help) = help {
+            prompt = prompt.with_help_message(help);
+        }
+        return Ok(handle_prompt_result(prompt.prompt())?.to_string());
+    }
+
+    let mut prompt = Text::new(&message);
+    if let Some(default) = default.filter(|d| !d.is_empty()) {
+        prompt = prompt.with_default(default);
+    }
+    if let Some(help) = help {
+        prompt = prompt.with_help_message(help);
+    }
+    if variable.required {
+        prompt = prompt.with_validator(|input: &str| {
+            if input.trim().is_empty() {
+                Ok(Validation::Invalid(t!("wizard.value_required").into()))
+            } else {
+                Ok(Validation::Valid)
+            }
+        });
+    }
+    Ok(handle_prompt_result(prompt.prompt())?.trim().to_string())
+}
+
 /// Prompt for additional options (folder creation, output directory)
 fn prompt_additional_options(config: &Config) -> Result<(bool, Option<PathBuf>)> {
-    println!("\n{}", "Additional Options:".bold());
+    ui::show!("\n{}", t!("wizard.additional_options").bold());
 
     let create_folder = handle_prompt_result(
-        Confirm::new("Create in new folder?")
+        Confirm::new(t!("wizard.create_folder"))
             .with_default(config.create_folder())
             .prompt(),
     )?;
 
     let use_custom_dir = handle_prompt_result(
-        Confirm::new("Use custom output directory?")
+        Confirm::new(t!("wizard.custom_output_dir"))
             .with_default(false)
             .prompt(),
     )?;
 
     let output_dir = if use_custom_dir {
         let dir_input = handle_prompt_result(
-            Text::new("Enter output directory path:")
+            Text::new(t!("wizard.output_dir_path"))
                 .with_default(&config.output_dir().to_string_lossy())
                 .prompt(),
         )?;
@@ -217,14 +445,14 @@ fn prompt_additional_options(config: &Config) -> Result<(bool, Option<PathBuf>)>
 /// Get context-aware naming help for different template types
 fn get_naming_help(template_type: &str) -> &'static str {
     match template_type {
-        "component" => "PascalCase (e.g., UserProfile, Navigation, ButtonGroup)",
-        "hook" => "camelCase starting with 'use' (e.g., useAuth, useLocalStorage)",
-        "service" => "PascalCase ending with 'Service' (e.g., ApiService, UserService)",
-        "context" => "PascalCase ending with 'Context' (e.g., AuthContext, ThemeContext)",
-        "page" => "PascalCase (e.g., HomePage, UserProfile, Dashboard)",
-        "store" => "camelCase or PascalCase (e.g., userStore, AuthStore)",
-        "feature" => "PascalCase for the feature name (e.g., UserManagement, Authentication)",
-        _ => "Choose a descriptive name for your template",
+        "component" => t!("wizard.help_component"),
+        "hook" => t!("wizard.help_hook"),
+        "service" => t!("wizard.help_service"),
+        "context" => t!("wizard.help_context"),
+        "page" => t!("wizard.help_page"),
+        "store" => t!("wizard.help_store"),
+        "feature" => t!("wizard.help_feature"),
+        _ => t!("wizard.help_other"),
     }
 }
 
@@ -235,25 +463,49 @@ fn is_valid_name(name: &str) -> bool {
 
 /// Display summary of what will be generated
 fn display_summary(config: &WizardConfig) {
-    println!("\n{}", "📋 Summary:".bold().green());
-    println!("  {} {}", "Name:".bold(), config.name);
-    println!("  {} {}", "Type:".bold(), config.template_type);
+    ui::show!(
+        "\n{} {}",
+        symbols::LIST,
+        t!("wizard.summary").bold().green()
+    );
+    ui::show!("  {} {}", t!("label.name").bold(), config.name);
+    ui::show!("  {} {}", t!("label.type").bold(), config.template_type);
 
     if let Some(arch) = &config.architecture {
-        println!("  {} {}", "Architecture:".bold(), arch);
+        ui::show!("  {} {}", t!("label.architecture").bold(), arch);
     }
 
-    println!(
+    ui::show!(
         "  {} {}",
-        "Create folder:".bold(),
-        if config.create_folder { "Yes" } else { "No" }
+        t!("wizard.create_folder_label").bold(),
+        if config.create_folder {
+            t!("label.yes")
+        } else {
+            t!("label.no")
+        }
     );
 
     if let Some(dir) = &config.output_dir {
-        println!("  {} {}", "Output directory:".bold(), dir.display());
+        ui::show!(
+            "  {} {}",
+            t!("wizard.output_dir_label").bold(),
+            dir.display()
+        );
     }
 
-    println!("\n{}", "🚀 Generating...".bold().yellow());
+    if !config.vars.is_empty() {
+        ui::show!(
+            "  {} {}",
+            t!("label.variables").bold(),
+            config.vars.join(", ")
+        );
+    }
+
+    ui::show!(
+        "\n{} {}",
+        symbols::LAUNCH,
+        t!("wizard.generating").bold().yellow()
+    );
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@
  * 
  * @generated {{generated}}
  * @timestamp {{timestamp}}
- * @author {{author}}
+ * @author {{author_name}}
  */
 
 import { {{pascal_name}}Service, {{pascal_name}}Entity, {{pascal_name}}Response } from './{{pascal_name}}.service';
@@ -198,7 +198,7 @@ describe('{{pascal_name}}Service', () => {
       // API Version: {{api_version}}
       // Base URL: {{base_url}}
       // Timeout: {{timeout}}
-      // Author: {{author}}
+      // Author: {{author_name}}
       // License: {{license}}
       // Description: {{description}}
       
@@ -0,0 +1,21 @@
+import React from 'react';
+import { render } from '@testing-library/react';
+import { axe, toHaveNoViolations } from 'jest-axe';
+import { $FILE_NAME } from './$FILE_NAME';
+
+expect.extend(toHaveNoViolations);
+
+describe('$FILE_NAME accessibility', () => {
+  it('has no axe violations', async () => {
+    const { container } = render(<$FILE_NAME />);
+    expect(await axe(container)).toHaveNoViolations();
+  });
+{{#if a11y.role}}
+
+  // Suggested role: {{a11y.role}}{{#if a11y.element}} (prefer a native <{{{a11y.element}}}>){{/if}}, see ACCESSIBILITY.md
+  it.todo('is exposed with the {{a11y.role}} role');
+{{#each a11y.keyboard}}
+  it.todo('{{{this}}}');
+{{/each}}
+{{/if}}
+});
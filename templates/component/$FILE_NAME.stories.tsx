@@ -4,7 +4,7 @@ import { $FILE_NAME } from './$FILE_NAME';
 
 /**
  * $FILE_NAME Storybook Stories
- * @author {{author}}
+ * @author {{author_name}}
  * Generated: {{date}}
  */
 
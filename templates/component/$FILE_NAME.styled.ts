@@ -3,9 +3,18 @@ import styled from 'styled-components';
 /**
  * Styled $FILE_NAME Component
  * @description Styled components for $FILE_NAME
- * @author {{author}}
+ * @author {{author_name}}
  */
 
+{{#if tokens}}
+/** Design tokens */
+export const tokens = {
+{{#each tokens.all}}
+  {{var_name}}: {{{js_value}}},
+{{/each}}
+} as const;
+
+{{/if}}
 export const Styled$FILE_NAME = styled.div`
   /* Base styles for $FILE_NAME component */
   display: block;
@@ -8,11 +8,15 @@ import { Styled$FILE_NAME } from "./$FILE_NAME.styled";
 
 /**
  * Props interface for $FILE_NAME component
- * @author {{author}}
+ * @author {{author_name}}
  * @generated {{date}}
  */
 export interface $FILE_NAMEProps {
+{{#each figma.props}}
+  {{name}}?: {{{ts_type}}};
+{{else}}
   // Add your props here
+{{/each}}
   className?: string;
   children?: React.ReactNode;
 }
@@ -1,7 +1,7 @@
 /**
  * $FILE_NAME Component Barrel Export
  * @description Central export point for $FILE_NAME component and related utilities
- * @author {{author}}
+ * @author {{author_name}}
  * Generated: {{date}}
  */
 
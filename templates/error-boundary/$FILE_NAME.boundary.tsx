@@ -5,7 +5,7 @@
  * @generated {{generated}}
  * @generator {{generator_name}} v{{version}}
  * @timestamp {{timestamp}}
- * @author {{author}}
+ * @author {{author_name}}
  * @license {{license}}
  *
  * Environment: {{environment}}
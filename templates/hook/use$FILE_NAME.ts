@@ -2,7 +2,7 @@ import { useState } from 'react';
 
 /**
  * Custom hook: use$FILE_NAME
- * @author {{author}}
+ * @author {{author_name}}
  * @generated {{date}}
  *
  * Generated with tests: {{with_tests}}
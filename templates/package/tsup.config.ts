@@ -0,0 +1,16 @@
+/**
+ * Build config for {{{package.name}}}
+ * Generated: {{date}}
+ */
+
+import { defineConfig } from 'tsup';
+
+export default defineConfig({
+  entry: ['src/index.ts'],
+  format: ['esm', 'cjs'],
+  dts: true,
+  sourcemap: true,
+  clean: true,
+  // Provided by the consuming app
+  external: ['react', 'react-dom'],
+});
@@ -0,0 +1,17 @@
+import React from "react";
+import { Text, View } from "react-native";
+import { styles } from "./$FILE_NAME.styles";
+import type { $FILE_NAMEProps } from "./$FILE_NAME.types";
+
+/**
+ * $FILE_NAME Component ({{platform}})
+ *
+ * Metro resolves this file instead of $FILE_NAME.tsx on {{platform}}.
+ */
+export const $FILE_NAME: React.FC<$FILE_NAMEProps> = ({ style, children }) => (
+  <View style={[styles.container, style]}>
+    {children ?? <Text>$FILE_NAME Component ({{platform}})</Text>}
+  </View>
+);
+
+export default $FILE_NAME;
@@ -0,0 +1,8 @@
+import { StyleSheet } from "react-native";
+
+// $FILE_NAME Component Styles
+export const styles = StyleSheet.create({
+  container: {
+    // Base styles for $FILE_NAME component
+  },
+});
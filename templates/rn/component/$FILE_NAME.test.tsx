@@ -0,0 +1,20 @@
+import React from "react";
+import { Text } from "react-native";
+import { render, screen } from "@testing-library/react-native";
+import { $FILE_NAME } from "./$FILE_NAME";
+
+describe("$FILE_NAME", () => {
+  it("renders without crashing", () => {
+    render(<$FILE_NAME />);
+    expect(screen.getByText(/$FILE_NAME Component/)).toBeTruthy();
+  });
+
+  it("renders children when provided", () => {
+    render(
+      <$FILE_NAME>
+        <Text>Custom child content</Text>
+      </$FILE_NAME>
+    );
+    expect(screen.getByText("Custom child content")).toBeTruthy();
+  });
+});
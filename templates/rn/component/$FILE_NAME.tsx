@@ -0,0 +1,21 @@
+import React from "react";
+import { Text, View } from "react-native";
+import { styles } from "./$FILE_NAME.styles";
+import type { $FILE_NAMEProps } from "./$FILE_NAME.types";
+
+/**
+ * $FILE_NAME Component
+ *
+{{#if with_platform_files_bool}}
+ * Shared implementation; Metro prefers $FILE_NAME.ios.tsx and
+ * $FILE_NAME.android.tsx on those platforms.
+{{/if}}
+ * Tests included: {{with_tests}}
+ */
+export const $FILE_NAME: React.FC<$FILE_NAMEProps> = ({ style, children }) => (
+  <View style={[styles.container, style]}>
+    {children ?? <Text>$FILE_NAME Component</Text>}
+  </View>
+);
+
+export default $FILE_NAME;
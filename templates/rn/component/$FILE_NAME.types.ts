@@ -0,0 +1,12 @@
+import type { StyleProp, ViewStyle } from "react-native";
+
+/**
+ * Props interface for $FILE_NAME component
+ * @author {{author_name}}
+ * @generated {{date}}
+ */
+export interface $FILE_NAMEProps {
+  // Add your props here
+  style?: StyleProp<ViewStyle>;
+  children?: React.ReactNode;
+}
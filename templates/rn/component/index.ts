@@ -0,0 +1,8 @@
+/**
+ * $FILE_NAME Component Barrel Export
+ * @author {{author_name}}
+ * Generated: {{date}}
+ */
+
+export { $FILE_NAME } from "./$FILE_NAME";
+export type { $FILE_NAMEProps } from "./$FILE_NAME.types";
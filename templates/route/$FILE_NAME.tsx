@@ -0,0 +1,48 @@
+import type { {{#if with_action_bool}}ActionFunctionArgs, {{/if}}LoaderFunctionArgs } from "@remix-run/node";
+import { {{#if with_error_boundary_bool}}isRouteErrorResponse, useRouteError, {{/if}}useLoaderData } from "@remix-run/react";
+
+/**
+ * Route {{route_path}}
+ * @author {{author_name}}
+ * @generated {{date}}
+ */
+
+export async function loader({ params }: LoaderFunctionArgs) {
+{{#each route_params}}
+  const {{this}} = params.{{this}};
+{{/each}}
+  return { {{#each route_params}}{{this}}{{#unless @last}}, {{/unless}}{{/each}} };
+}
+{{#if with_action_bool}}
+
+export async function action({ request, params }: ActionFunctionArgs) {
+  const formData = await request.formData();
+  // Handle the submission
+  return { ok: true };
+}
+{{/if}}
+
+export default function {{pascal_name}}Route() {
+  const data = useLoaderData<typeof loader>();
+
+  return (
+    <div>
+      <h1>{{route_path}}</h1>
+      <pre>{JSON.stringify(data, null, 2)}</pre>
+    </div>
+  );
+}
+{{#if with_error_boundary_bool}}
+
+export function ErrorBoundary() {
+  const error = useRouteError();
+
+  return isRouteErrorResponse(error) ? (
+    <h1>
+      {error.status} {error.statusText}
+    </h1>
+  ) : (
+    <h1>Something went wrong</h1>
+  );
+}
+{{/if}}
@@ -2,7 +2,12 @@ use assert_cmd::Command;
 use predicates::prelude::*;
 
 fn get_cli_command() -> Command {
-    Command::new(env!("CARGO_BIN_EXE_cli-frontend"))
+    // Assertions match the English messages, whatever the machine's locale
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_cli-frontend"));
+    cmd.env("CLI_FRONTEND_LANG", "en");
+    // Keep the usage history and other per-user files out of the real home
+    cmd.env("HOME", env!("CARGO_TARGET_TMPDIR"));
+    cmd
 }
 
 #[test]
@@ -44,7 +49,88 @@ fn test_cli_describe_component() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("Template:"));
+        .stdout(predicate::str::contains("Template:"))
+        .stdout(predicate::str::contains("Generation Preview"))
+        .stdout(predicate::str::contains("with_tests=false"));
+}
+
+#[test]
+fn test_cli_describe_all_json() {
+    let mut cmd = get_cli_command();
+    cmd.args(["--describe-all", "--json"]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let descriptions: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let component = descriptions
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|description| description["name"] == "component")
+        .unwrap();
+    assert_eq!(component["variables"]["style"]["default"], "scss");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--json");
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_cli_list_by_tag_and_search() {
+    let mut cmd = get_cli_command();
+    cmd.args(["--list", "--tag", "react-native"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("rn/component"))
+        .stdout(predicate::str::contains(
+            "Tags: component, ui, react-native",
+        ))
+        .stdout(predicate::str::contains("hook").not());
+
+    let mut cmd = get_cli_command();
+    cmd.args(["search", "custom hook"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("React Custom Hook"))
+        .stdout(predicate::str::contains("1 template(s)"));
+
+    let mut cmd = get_cli_command();
+    cmd.args(["search", "hook", "--tag", "routing"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "No templates match 'hook' with tag 'routing'",
+    ));
+
+    let mut cmd = get_cli_command();
+    cmd.args(["--tag", "form"]);
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_cli_doctor_reports_search_order() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates_dir = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates_dir.join("widget")).unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!("templates_dir={}\n", templates_dir.display()),
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("doctor");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Config file:"))
+        .stdout(predicate::str::contains("(1 found, set in config file)"))
+        .stdout(predicate::str::contains("Template search order:"))
+        .stdout(predicate::str::contains("./templates"));
+
+    std::fs::remove_dir(templates_dir.join("widget")).unwrap();
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("doctor");
+    cmd.assert()
+        .code(7)
+        .stderr(predicate::str::contains("No templates found"));
 }
 
 #[test]
@@ -75,3 +161,3020 @@ fn test_cli_with_var_flag() {
         .success()
         .stdout(predicate::str::contains("--var"));
 }
+
+#[test]
+fn test_cli_docs_writes_catalog() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output = temp_dir.path().join("TEMPLATES.md");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("docs").arg("--output").arg(&output);
+
+    cmd.assert().success();
+
+    let catalog = std::fs::read_to_string(&output).unwrap();
+    assert!(catalog.contains("# Template Catalog"));
+    assert!(catalog.contains("### component"));
+    assert!(catalog.contains("## Architectures"));
+}
+
+#[test]
+fn test_cli_copies_binary_files_verbatim() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("asset");
+    std::fs::create_dir_all(&template_dir).unwrap();
+
+    let png = [
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, b'{', b'{',
+    ];
+    std::fs::write(template_dir.join("$FILE_NAME.png"), png).unwrap();
+    std::fs::write(
+        template_dir.join("$FILE_NAME.ts"),
+        "export const {{name}} = 1;",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.arg("Logo")
+        .arg("--type")
+        .arg("asset")
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+
+    cmd.assert().success();
+
+    assert_eq!(std::fs::read(output.join("Logo/Logo.png")).unwrap(), png);
+    assert_eq!(
+        std::fs::read_to_string(output.join("Logo/Logo.ts")).unwrap(),
+        "export const Logo = 1;"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_open_launches_configured_editor() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};").unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.spec.tsx"), "test();").unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nopen_after_generate=echo opened\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.args(["Card", "--type", "widget", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "opened {}",
+            output.join("Card").display()
+        )));
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Chip", "--type", "widget", "--no-folder", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "opened {}",
+            output.join("Chip.tsx").display()
+        )));
+}
+
+#[test]
+fn test_cli_output_archive() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("hook");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("use$FILE_NAME.ts"),
+        "export function use$FILE_NAME() {}",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let output = temp_dir.path().join("out");
+    let archive = temp_dir.path().join("scaffold.zip");
+    let mut cmd = get_cli_command();
+    cmd.arg("Auth")
+        .arg("--type")
+        .arg("hook")
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .arg("--output-archive")
+        .arg(&archive);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Archive written"));
+
+    assert!(!output.exists());
+    let zip = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+    assert_eq!(
+        zip.file_names().collect::<Vec<_>>(),
+        vec!["Auth/useAuth.ts"]
+    );
+}
+
+#[test]
+fn test_cli_stdout_prints_single_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export function use$FILE_NAME() {}\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(templates.join("pair")).unwrap();
+    std::fs::write(templates.join("pair").join("a.ts"), "a").unwrap();
+    std::fs::write(templates.join("pair").join("b.ts"), "b").unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Auth", "--type", "hook", "--stdout", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout("export function useAuth() {}\n");
+    assert!(!output.exists());
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Auth", "--type", "pair", "--stdout", "--config"])
+        .arg(&config);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("exactly one file"));
+
+    // --copy renders in memory too, so it can't be combined with --stdout
+    let mut cmd = get_cli_command();
+    cmd.args(["Auth", "--type", "hook", "--stdout", "--copy", "--config"])
+        .arg(&config);
+    cmd.assert().code(2);
+}
+
+#[test]
+#[cfg(all(unix, not(target_os = "macos")))]
+fn test_cli_copy_uses_a_clipboard_tool_or_prints_without_one() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export function use$FILE_NAME() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.test.ts"),
+        "test\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!("templates_dir={}\nauthor_name=Ana\n", templates.display()),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    // No wl-copy, xclip or xsel to find
+    let bin = temp_dir.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let copy = |bin: &std::path::Path| {
+        let mut cmd = get_cli_command();
+        cmd.env("PATH", bin)
+            .env_remove("WAYLAND_DISPLAY")
+            .env("CLIPBOARD_FILE", temp_dir.path().join("clipboard"))
+            .args(["Auth", "--type", "hook", "--copy", "--config"])
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output);
+        cmd.assert().success()
+    };
+
+    copy(&bin)
+        .stdout("export function useAuth() {}\n")
+        .stderr(predicate::str::contains("--copy found no clipboard tool"));
+    assert!(!output.exists());
+
+    // A stand-in xclip that keeps what it reads on stdin
+    let xclip = bin.join("xclip");
+    std::fs::write(
+        &xclip,
+        "#!/bin/sh\nwhile IFS= read -r line; do printf '%s\\n' \"$line\"; done > \"$CLIPBOARD_FILE\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&xclip, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    copy(&bin).stdout(predicate::str::contains("Copied"));
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("clipboard")).unwrap(),
+        "export function useAuth() {}\n"
+    );
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_cli_figma_json_seeds_name_variables_and_props() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join(".conf"),
+        "[options]\nsize=md\nsize_options=sm,md,lg\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "$FILE_NAME size={{size}}\n{{#each figma.props}}{{name}}?: {{{ts_type}}};\n{{/each}}",
+    )
+    .unwrap();
+
+    let figma = temp_dir.path().join("button.json");
+    std::fs::write(
+        &figma,
+        r#"{"nodes": {"1:2": {"document": {
+            "name": "Forms/Icon Button",
+            "type": "COMPONENT_SET",
+            "componentPropertyDefinitions": {
+                "Size": {"type": "VARIANT", "defaultValue": "LG", "variantOptions": ["SM", "LG"]},
+                "Label#3:0": {"type": "TEXT", "defaultValue": "Save"}
+            }
+        }}}}"#,
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.args(["--type", "widget", "--stdout", "--figma-json"])
+        .arg(&figma)
+        .arg("--config")
+        .arg(&config);
+    cmd.assert()
+        .success()
+        .stdout("IconButton size=lg\nlabel?: string;\nsize?: \"SM\" | \"LG\";\n");
+}
+
+#[test]
+fn test_cli_as_package_uses_workspace_scope() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::create_dir_all(templates.join("package")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "export const $FILE_NAME = '{{{package.name}}}';\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("package").join("package.json"),
+        "{\"name\": \"{{{package.name}}}\", \"extends\": \"{{{package.tsconfig_extends}}}\"}\n",
+    )
+    .unwrap();
+
+    let repo = temp_dir.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    std::fs::write(
+        repo.join("package.json"),
+        r#"{"name": "@acme/repo", "workspaces": ["packages/*"]}"#,
+    )
+    .unwrap();
+    std::fs::write(repo.join("tsconfig.json"), "{}").unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.args(["DatePicker", "--type", "widget", "--as-package", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(repo.join("packages"));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("npm workspace"));
+
+    let package = repo.join("packages").join("date-picker");
+    assert_eq!(
+        std::fs::read_to_string(package.join("package.json")).unwrap(),
+        "{\"name\": \"@acme/date-picker\", \"extends\": \"../../tsconfig.json\"}\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(package.join("src").join("DatePicker.tsx")).unwrap(),
+        "export const DatePicker = '@acme/date-picker';\n"
+    );
+}
+
+#[test]
+fn test_cli_license_stamps_spdx_and_writes_license_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::create_dir_all(templates.join("package")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "{{spdx_header}}\nexport const $FILE_NAME = 1;\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("package").join("package.json"),
+        "{\"license\": \"{{license}}\"}\n",
+    )
+    .unwrap();
+    let run = |license: &str| {
+        let config = temp_dir.path().join("test.conf");
+        std::fs::write(
+            &config,
+            format!(
+                "templates_dir={}\nlicense={}\nauthor_name=Ana Díaz\n[variables]\ncompany=Acme\n",
+                templates.display(),
+                license
+            ),
+        )
+        .unwrap();
+        let mut cmd = get_cli_command();
+        cmd.args([
+            "DatePicker",
+            "--type",
+            "widget",
+            "--as-package",
+            "--license-file",
+        ])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(temp_dir.path().join(license));
+        cmd.assert()
+    };
+
+    run("MIT").success();
+    let package = temp_dir.path().join("MIT").join("date-picker");
+    assert_eq!(
+        std::fs::read_to_string(package.join("src").join("DatePicker.tsx")).unwrap(),
+        "// SPDX-License-Identifier: MIT\nexport const DatePicker = 1;\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(package.join("package.json")).unwrap(),
+        "{\"license\": \"MIT\"}\n"
+    );
+    let license = std::fs::read_to_string(package.join("LICENSE")).unwrap();
+    assert!(license.starts_with("MIT License\n\nCopyright (c) "));
+    // The configured author holds the copyright, ahead of the company
+    assert!(license.lines().nth(2).unwrap().ends_with(" Ana Díaz"));
+
+    run("GPL-3.0-only")
+        .failure()
+        .stderr(predicate::str::contains(
+            "No bundled text for license 'GPL-3.0-only'",
+        ));
+    assert!(!temp_dir
+        .path()
+        .join("GPL-3.0-only")
+        .join("date-picker")
+        .exists());
+}
+
+#[test]
+fn test_cli_author_comes_from_config_unless_passed() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.ts"),
+        "// {{author_name}} <{{author_email}}>\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nauthor_name=Ana\nauthor_email=ana@acme.dev\n",
+            templates.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    let run = |vars: &[&str]| {
+        let mut cmd = get_cli_command();
+        cmd.args([
+            "Card",
+            "--type",
+            "widget",
+            "--no-folder",
+            "--on-conflict",
+            "overwrite",
+        ])
+        .args(vars)
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+        cmd.assert().success();
+        std::fs::read_to_string(output.join("Card.ts")).unwrap()
+    };
+
+    assert_eq!(run(&[]), "// Ana <ana@acme.dev>\n");
+    assert_eq!(run(&["--var", "author_name=Bo"]), "// Bo <ana@acme.dev>\n");
+    assert_eq!(run(&["--var", "author=Cy"]), "// Cy <ana@acme.dev>\n");
+
+    // Without git or an OS user name the author is still named
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "Card",
+        "--type",
+        "widget",
+        "--no-folder",
+        "--on-conflict",
+        "overwrite",
+    ])
+    .arg("--config")
+    .arg(&config)
+    .arg("--output-dir")
+    .arg(&output)
+    .env("PATH", temp_dir.path())
+    .env_remove("USER")
+    .env_remove("USERNAME");
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Card.ts")).unwrap(),
+        "// Frontend Team <>\n"
+    );
+}
+
+#[test]
+fn test_cli_profile_run_prints_timings() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export function use$FILE_NAME() {}\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Auth", "--type", "hook", "--profile-run", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(temp_dir.path().join("out"));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Timing breakdown"))
+        .stdout(predicate::str::contains("render"))
+        .stdout(predicate::str::contains("total"));
+}
+
+#[test]
+fn test_cli_exit_codes_distinguish_failures() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "{{#if x}}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join(".conf"),
+        "[options]\nstyle=css\nstyle_options=css,scss\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let run = |args: &[&str]| {
+        let mut cmd = get_cli_command();
+        cmd.args(args)
+            .arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output);
+        cmd.assert()
+    };
+
+    run(&["Auth", "--type", "widget"])
+        .code(3)
+        .stderr(predicate::str::contains("Available templates: hook"));
+    run(&["Auth", "--type", "hook", "--var", "style=less"])
+        .code(4)
+        .stderr(predicate::str::contains("Invalid variable 'style'"));
+    run(&["Auth", "--type", "hook"])
+        .code(5)
+        .stderr(predicate::str::contains("use$FILE_NAME.ts:2"));
+    run(&["Auth", "--type", "hokk"])
+        .code(3)
+        .stderr(predicate::str::contains("Did you mean 'hook'?"));
+    run(&["Auth", "--type", "hook", "--var", "stlye=css"])
+        .stderr(predicate::str::contains("did you mean 'style'?"));
+}
+
+#[test]
+fn test_cli_matrix_renders_each_combination() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "export const {{pascal_name}} = '{{size}}';\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.spec.tsx"),
+        "test\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("widget").join(".conf"),
+        "[options]\nsize=sm\nsize_options=sm,lg\nwith_tests=false\nwith_tests_type=boolean\n\n\
+         [files]\n$FILE_NAME.spec.tsx=var_with_tests\n\n[rules]\nsize=lg conflicts with_tests\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("matrix");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["matrix", "widget", "--name", "Chip", "-o"])
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("04-size-lg__with_tests-true"))
+        .stdout(predicate::str::contains("skipped"));
+
+    let large = output.join("03-size-lg").join("Chip").join("Chip.tsx");
+    assert_eq!(
+        std::fs::read_to_string(large).unwrap(),
+        "export const Chip = 'lg';\n"
+    );
+    assert!(output
+        .join("02-with_tests-true")
+        .join("Chip")
+        .join("Chip.spec.tsx")
+        .exists());
+    assert!(!output.join("04-size-lg__with_tests-true").exists());
+}
+
+#[test]
+fn test_cli_explains_when_nothing_would_be_generated() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("story");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.stories.tsx"), "export {};").unwrap();
+    std::fs::write(
+        template_dir.join(".conf"),
+        "[options]\nwith_stories=false\nwith_stories_type=boolean\n\n[files]\n$FILE_NAME.stories.tsx=var_with_stories\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let output = temp_dir.path().join("out");
+    let generate = |vars: &[&str]| {
+        let mut cmd = get_cli_command();
+        cmd.args(["Button", "--type", "story", "--config"])
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output);
+        for var in vars {
+            cmd.args(["--var", var]);
+        }
+        cmd.assert()
+    };
+
+    generate(&[])
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains(
+            "$FILE_NAME.stories.tsx (var_with_stories: with_stories is 'false')",
+        ))
+        .stderr(predicate::str::contains("--var with_stories=true"));
+    assert!(!output.join("Button").exists());
+
+    generate(&["with_stories=true"]).success();
+    assert!(output.join("Button/Button.stories.tsx").exists());
+}
+
+#[test]
+fn test_cli_verify_golden_detects_drift() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "// {{timestamp}} {{uuid}}\nexport const {{hook_name}} = () => {};\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join(".conf"),
+        "enable_timestamps=true\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let golden = temp_dir.path().join("golden");
+    let verify = |update: bool| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .args(["verify-golden", "hook"])
+            .arg(&golden);
+        if update {
+            cmd.arg("--update");
+        }
+        cmd.assert()
+    };
+
+    verify(false)
+        .failure()
+        .stderr(predicate::str::contains("--update"));
+    verify(true).success();
+    let golden_file = golden
+        .join("01-defaults")
+        .join("Example")
+        .join("useExample.ts");
+    assert_eq!(
+        std::fs::read_to_string(&golden_file).unwrap(),
+        "//  \nexport const useExample = () => {};\n"
+    );
+    verify(false)
+        .success()
+        .stdout(predicate::str::contains("matches its golden output"));
+
+    std::fs::write(&golden_file, "export const useOld = () => {};\n").unwrap();
+    verify(false)
+        .code(9)
+        .stdout(predicate::str::contains("+export const useExample"));
+}
+
+#[test]
+fn test_cli_lint_template_reports_findings() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export const {{camel_name}} = '{{stroe}}';\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join(".conf"),
+        "[options]\nwith_state=true\n\n[files]\nuse$FILE_NAME.test.ts=var_with_state\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["lint-template", "hook"]);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("use$FILE_NAME.ts:1"))
+        .stdout(predicate::str::contains("'stroe' is not declared"))
+        .stdout(predicate::str::contains("use$FILE_NAME.test.ts"));
+}
+
+#[test]
+fn test_cli_template_migrate_conf_to_toml() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    let hook = templates.join("hook");
+    std::fs::create_dir_all(&hook).unwrap();
+    std::fs::write(hook.join("use$FILE_NAME.ts"), "export {};\n").unwrap();
+    std::fs::write(hook.join("use$FILE_NAME.test.ts"), "test();\n").unwrap();
+    std::fs::write(
+        hook.join(".conf"),
+        "[metadata]\nname=Hook\n\n[options]\nwith_tests=true\nwith_tests_type=boolean\n\n\
+         [files]\nuse$FILE_NAME.test.ts=var_with_tests\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["template", "migrate-conf", "hook"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Migrated 'hook'"));
+
+    let toml = std::fs::read_to_string(hook.join("template.toml")).unwrap();
+    assert!(toml.contains("[options.with_tests]"));
+    assert!(toml.contains("\"use$FILE_NAME.test.ts\" = \"var_with_tests\""));
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["template", "migrate-conf", "hook"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    // template.toml is read instead of .conf and is not generated
+    std::fs::write(hook.join(".conf"), "[options]\nwith_tests=false\n").unwrap();
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.arg("Auth")
+        .args(["--type", "hook"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+
+    assert!(output.join("Auth").join("useAuth.test.ts").exists());
+    assert!(!output.join("Auth").join("template.toml").exists());
+}
+
+#[test]
+fn test_cli_path_variables_must_exist() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("form")).unwrap();
+    std::fs::write(
+        templates.join("form").join("$FILE_NAME.tsx"),
+        "import type { {{model_path.stem}} } from '{{model_path.relative_to_output}}';\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("form").join(".conf"),
+        "[options]\nmodel=\nmodel_type=path\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("src").join("models")).unwrap();
+    std::fs::write(
+        temp_dir.path().join("src").join("models").join("User.ts"),
+        "export interface User {}\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.current_dir(temp_dir.path())
+        .arg("UserForm")
+        .args(["--type", "form", "--output-dir", "src/components"])
+        .arg("--config")
+        .arg(&config)
+        .args(["--var", "model=src/models/*.ts"]);
+    cmd.assert().success();
+
+    let generated =
+        std::fs::read_to_string(temp_dir.path().join("src/components/UserForm/UserForm.tsx"))
+            .unwrap();
+    assert_eq!(
+        generated,
+        "import type { User } from '../../models/User.ts';\n"
+    );
+
+    let mut cmd = get_cli_command();
+    cmd.current_dir(temp_dir.path())
+        .arg("OrderForm")
+        .args(["--type", "form", "--output-dir", "src/components"])
+        .arg("--config")
+        .arg(&config)
+        .args(["--var", "model=src/models/Order.ts"]);
+    cmd.assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains(
+            "'src/models/Order.ts' does not exist",
+        ));
+    assert!(!temp_dir.path().join("src/components/OrderForm").exists());
+}
+
+#[test]
+fn test_cli_global_variables_beneath_template_defaults() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "// {{company}} ({{license}}) by {{author}}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join(".conf"),
+        "[options]\nlicense=Apache-2.0\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n\n[variables]\ncompany=Acme\nlicense=MIT\nauthor=Team\n",
+            templates.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("Auth")
+        .args(["--type", "hook", "--var", "author=Ana"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+
+    assert_eq!(
+        std::fs::read_to_string(output.join("Auth").join("useAuth.ts")).unwrap(),
+        "// Acme (Apache-2.0) by Ana\n"
+    );
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["lint-template", "hook"]);
+    cmd.assert().success();
+}
+
+#[test]
+fn test_cli_explain_traces_helper_calls() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export const {{kebab_case name}} = {{#if (eq style \"scss\")}}1{{else}}0{{/if}};\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("hook").join(".conf"),
+        "[options]\nstyle=scss\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["UserCard", "--type", "hook", "--explain"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Helper trace:"))
+        .stdout(predicate::str::contains("kebab_case(\"UserCard\")"))
+        .stdout(predicate::str::contains("\"user-card\""))
+        .stdout(predicate::str::contains("eq(\"scss\", \"scss\")"));
+
+    let trace = temp_dir.path().join("trace.json");
+    let mut cmd = get_cli_command();
+    cmd.args(["Auth", "--type", "hook"])
+        .arg(format!("--explain={}", trace.display()))
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+
+    let files: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&trace).unwrap()).unwrap();
+    let calls = &files[0]["calls"];
+    assert_eq!(calls[0]["helper"], "kebab_case");
+    assert_eq!(calls[0]["inputs"][0], "Auth");
+    assert_eq!(calls[0]["output"], "auth");
+}
+
+#[test]
+fn test_cli_repl_evaluates_expressions() {
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "repl",
+        "component",
+        "--name",
+        "user_card",
+        "--var",
+        "style=css",
+    ])
+    .write_stdin("pascal_case name\nstyle_is_scss\n:data\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("component> UserCard"))
+        .stdout(predicate::str::contains("component> false"))
+        .stdout(predicate::str::contains("\"kebab_name\": \"user-card\""));
+
+    let mut cmd = get_cli_command();
+    cmd.args(["repl", "nonexistent_template"]).write_stdin("");
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_cli_env_helper_policy() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "// {{env \"CLI_TEST_SECRET_KEY\"}}\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!("templates_dir={}\nenv_deny=*SECRET*\n", templates.display()),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.env("CLI_TEST_SECRET_KEY", "hunter2")
+        .args(["Auth", "--type", "hook"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .failure()
+        .code(5)
+        .stderr(predicate::str::contains(
+            "Remove the pattern matching CLI_TEST_SECRET_KEY from env_deny",
+        ))
+        .stderr(predicate::str::contains("Handlebars syntax").not());
+    assert!(!output.join("Auth").join("useAuth.ts").exists());
+
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let mut cmd = get_cli_command();
+    cmd.env("CLI_TEST_SECRET_KEY", "hunter2")
+        .args(["Auth", "--type", "hook", "--no-env-helpers"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--no-env-helpers"));
+}
+
+#[test]
+fn test_cli_front_matter_overrides_filename_and_condition() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "export const {{pascal_case name}} = 1;\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("widget").join("spec.tsx"),
+        "---\nfilename: $FILE_NAME.spec.tsx\ncondition: var_with_tests\n---\ntest('{{name}}');\n",
+    )
+    .unwrap();
+
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Card", "--type", "widget"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+    assert!(!output.join("Card").join("Card.spec.tsx").exists());
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Card", "--type", "widget", "--var", "with_tests=true"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+    let spec = std::fs::read_to_string(output.join("Card").join("Card.spec.tsx")).unwrap();
+    assert_eq!(spec, "test('Card');\n");
+    assert!(!output.join("Card").join("spec.tsx").exists());
+}
+
+#[test]
+fn test_cli_drift_reports_edited_stamped_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "export const {{pascal_case name}} = 1;\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Card", "--type", "widget", "--stamp"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+    let generated = output.join("Card").join("Card.tsx");
+    let content = std::fs::read_to_string(&generated).unwrap();
+    assert!(content.starts_with("// @generated by cli-frontend"));
+    assert!(content.contains("template=widget name=Card"));
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("drift").arg(&output);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "1 stamped file(s) match their templates",
+    ));
+
+    std::fs::write(&generated, content.replace("= 1", "= 2")).unwrap();
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("drift").arg(&output);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Card.tsx"))
+        .stdout(predicate::str::contains("modified"))
+        .stderr(predicate::str::contains("1 of 1 stamped file(s) drifted"));
+}
+
+#[test]
+fn test_cli_feature_generates_several_features_in_one_run() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output = temp_dir.path().join("features");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth", "Billing"])
+        .args(["--architecture", "screaming-architecture"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Generating 2 features (Auth, Billing)",
+        ))
+        .stdout(predicate::str::contains("Billing ("))
+        .stdout(predicate::str::contains(
+            "2 features generated successfully with screaming-architecture architecture",
+        ));
+    assert!(output.join("Auth/hooks/useAuth.ts").is_file());
+    assert!(output.join("Billing/hooks/useBilling.ts").is_file());
+
+    // --tree only prints the tree without a terminal to confirm on
+    let preview = temp_dir.path().join("preview");
+    let mut cmd = get_cli_command();
+    cmd.arg("--output-dir")
+        .arg(&preview)
+        .args(["feature", "Orders", "--tree"])
+        .args(["--architecture", "screaming-architecture"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Orders/"))
+        .stdout(predicate::str::contains("useOrders.ts"))
+        .stdout(predicate::str::contains("Nothing was written."));
+    assert!(!preview.exists());
+
+    // Names sharing a folder are rejected before anything is written
+    let output = temp_dir.path().join("clash");
+    let mut cmd = get_cli_command();
+    cmd.arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Orders", "Orders"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("listed more than once"));
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_cli_list_marks_architectures_with_missing_templates() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(templates.join("widget").join("$FILE_NAME.ts"), "x").unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    let structure = |template: &str| {
+        format!(
+            r#"{{"path": "{0}", "template": "{0}", "filename_pattern": "{{name}}", "description": ""}}"#,
+            template
+        )
+    };
+    let architecture = |structures: &[&str]| {
+        format!(
+            r#"{{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [{}]}}"#,
+            structures
+                .iter()
+                .map(|template| structure(template))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+    std::fs::write(architectures.join("flat.json"), architecture(&["widget"])).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        architecture(&["widget", "ghost"]),
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("--list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "layers (missing templates: ghost)",
+        ))
+        .stdout(predicate::str::contains("flat (").not());
+
+    // Generation stops before writing the layers whose templates exist
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth", "--architecture", "layers"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("uses templates missing from"))
+        .stderr(predicate::str::contains("ghost"));
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_cli_feature_honours_files_conditions_of_structure_templates() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output = temp_dir.path().join("features");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth"])
+        .args(["--architecture", "screaming-architecture"]);
+    cmd.assert().success();
+
+    // with_a11y defaults to false, so neither accessibility file is generated
+    let components = output.join("Auth/components");
+    assert!(components.join("Auth.tsx").is_file());
+    assert!(!components.join("Auth.a11y.spec.tsx").exists());
+    assert!(!components.join("ACCESSIBILITY.md").exists());
+}
+
+#[test]
+fn test_cli_feature_resume_skips_completed_structures() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for template in ["view", "store"] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+    }
+    std::fs::write(
+        templates.join("view").join("$FILE_NAME.tsx"),
+        "export const View = 1;",
+    )
+    .unwrap();
+    let store = templates.join("store").join("$FILE_NAME.ts");
+    std::fs::write(&store, "{{undefined_var}}").unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "ui", "template": "view", "filename_pattern": "{name}", "description": ""},
+            {"path": "data", "template": "store", "filename_pattern": "{name}", "description": ""}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    let run = |flags: &[&str]| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(flags)
+            .args(["feature", "Auth", "--architecture", "layers"]);
+        cmd.assert()
+    };
+
+    run(&["--strict", "--keep-going"])
+        .failure()
+        .stderr(predicate::str::contains("run again with --resume"));
+    let view = output.join("Auth").join("ui").join("Auth.tsx");
+    assert!(view.exists());
+    let resume_file = output.join(".cli-frontend-resume.json");
+    assert!(resume_file.exists());
+
+    // The kept layer isn't generated again
+    std::fs::write(&view, "edited").unwrap();
+    std::fs::write(&store, "export const store = 1;").unwrap();
+    run(&["--resume"])
+        .success()
+        .stdout(predicate::str::contains("completed by the interrupted run"));
+    assert_eq!(std::fs::read_to_string(&view).unwrap(), "edited");
+    assert!(output.join("Auth").join("data").join("Auth.ts").exists());
+    assert!(!resume_file.exists());
+}
+
+#[test]
+fn test_cli_feature_reports_every_failing_structure() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for (template, content) in [
+        ("view", "export const View = 1;"),
+        ("store", "{{undefined_store}}"),
+        ("client", "{{undefined_client}}"),
+    ] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+        std::fs::write(templates.join(template).join("$FILE_NAME.ts"), content).unwrap();
+    }
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "ui", "template": "view", "filename_pattern": "{name}", "description": ""},
+            {"path": "data", "template": "store", "filename_pattern": "{name}", "description": ""},
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": ""}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .arg("--strict")
+        .args(["feature", "Auth", "--architecture", "layers"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to generate structure: data (1 more failed: api:",
+        ))
+        .stderr(predicate::str::contains("undefined_client"));
+    assert!(!output.join("Auth").exists());
+}
+
+#[test]
+fn test_cli_all_errors_lists_every_failing_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template).unwrap();
+    std::fs::write(template.join("$FILE_NAME.ts"), "{{#if}}").unwrap();
+    std::fs::write(template.join("$FILE_NAME.css"), "{{undefined_color}}").unwrap();
+    std::fs::write(template.join("$FILE_NAME.md"), "# {{name}}").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let run = |all_errors: bool| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .arg("--strict")
+            .args(["Card", "--type", "widget"]);
+        if all_errors {
+            cmd.arg("--all-errors");
+        }
+        cmd.assert().failure().code(5)
+    };
+
+    run(true)
+        .stderr(predicate::str::contains("2 files failed to render:"))
+        .stderr(predicate::str::contains("$FILE_NAME.ts"))
+        .stderr(predicate::str::contains("undefined_color"));
+    run(false).stderr(predicate::str::contains("files failed to render").not());
+    assert!(!output.join("Card").exists());
+}
+
+#[test]
+fn test_cli_timeout_rolls_back_the_run() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--output-dir").arg(temp_dir.path()).args([
+        "Button",
+        "--type",
+        "component",
+        "--timeout",
+        "0",
+    ]);
+    cmd.assert()
+        .failure()
+        .code(124)
+        .stderr(predicate::str::contains("Timed out after 0s"))
+        .stderr(predicate::str::contains("raise --timeout"));
+    assert!(!temp_dir.path().join("Button").exists());
+}
+
+#[test]
+fn test_cli_killed_generation_leaves_no_partial_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template = temp_dir.path().join("templates").join("icons");
+    std::fs::create_dir_all(&template).unwrap();
+    let content = format!("export const {{{{name}}}} = `{}`;\n", "x".repeat(64 * 1024));
+    for index in 0..200 {
+        std::fs::write(template.join(format!("$FILE_NAME{}.ts", index)), &content).unwrap();
+    }
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let expected = content.replace("{{name}}", "Set");
+
+    // Kill the run at different points; whatever reached the project is whole
+    for delay in [0, 20, 50, 100, 200] {
+        let output = temp_dir.path().join(format!("out-{}", delay));
+        let staging = temp_dir.path().join(format!("tmp-{}", delay));
+        std::fs::create_dir_all(&staging).unwrap();
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_cli-frontend"))
+            .env("TMPDIR", &staging)
+            .arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(["Set", "--type", "icons"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(delay));
+        let _ = child.kill();
+        child.wait().unwrap();
+
+        for entry in std::fs::read_dir(output.join("Set")).into_iter().flatten() {
+            let path = entry.unwrap().path();
+            assert_eq!(
+                std::fs::read_to_string(&path).unwrap(),
+                expected,
+                "{} is incomplete",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_cli_audit_log_records_every_written_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let log = temp_dir.path().join("audit").join("writes.jsonl");
+
+    for name in ["Button", "Card"] {
+        let mut cmd = get_cli_command();
+        cmd.arg("--output-dir")
+            .arg(temp_dir.path())
+            .arg("--audit-log")
+            .arg(&log)
+            .args([name, "--type", "component"]);
+        cmd.assert().success();
+    }
+
+    let records: Vec<serde_json::Value> = std::fs::read_to_string(&log)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    for name in ["Button", "Card"] {
+        let file = temp_dir.path().join(name).join(format!("{}.tsx", name));
+        let record = records
+            .iter()
+            .find(|record| record["path"] == file.to_str().unwrap())
+            .unwrap();
+        assert_eq!(record["action"], "move");
+        assert_eq!(record["bytes"], std::fs::read(&file).unwrap().len() as u64);
+        assert!(record["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|arg| arg == name));
+    }
+}
+
+#[test]
+fn test_cli_pack_qualified_template_names() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    for (dir, content) in [
+        ("templates/button", "export const Button = 1;"),
+        ("acme/card", "// acme card"),
+        ("ui/card", "// ui card"),
+        ("ui/badge", "// ui badge"),
+    ] {
+        std::fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        std::fs::write(temp_dir.path().join(dir).join("$FILE_NAME.ts"), content).unwrap();
+    }
+    std::fs::write(
+        temp_dir.path().join("acme/card/.conf"),
+        "[metadata]\ndescription=Acme product card\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n[packs]\nacme={}\nui={}\n",
+            temp_dir.path().join("templates").display(),
+            temp_dir.path().join("acme").display(),
+            temp_dir.path().join("ui").display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    let generate = |template: &str| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(["Promo", "--type", template, "--no-folder"]);
+        cmd.assert()
+    };
+
+    generate("acme:card").success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Promo.ts")).unwrap(),
+        "// acme card"
+    );
+    generate("card")
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Template 'card' is provided by several packs:",
+        ))
+        .stderr(predicate::str::contains(format!(
+            "acme:card ({})",
+            temp_dir.path().join("acme").join("card").display()
+        )))
+        .stderr(predicate::str::contains("Acme product card"))
+        .stderr(predicate::str::contains(
+            "--type acme:card or --type ui:card",
+        ));
+    generate("ui:button")
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("ui:badge"));
+    std::fs::remove_file(output.join("Promo.ts")).unwrap();
+    generate("badge").success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Promo.ts")).unwrap(),
+        "// ui badge"
+    );
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("--list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("acme:card"))
+        .stdout(predicate::str::contains("ui:badge"));
+}
+
+#[test]
+fn test_cli_internal_templates_are_hidden_without_include_internal() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for template in ["button", "layer"] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+        std::fs::write(templates.join(template).join("$FILE_NAME.ts"), "// file").unwrap();
+    }
+    std::fs::write(
+        templates.join("layer").join(".conf"),
+        "[metadata]\nname=Layer\ninternal=true\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("--list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("button"))
+        .stdout(predicate::str::contains("layer").not());
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["--list", "--include-internal"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("layer"));
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args(["Promo", "--type", "layer", "--no-folder"]);
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Template 'layer' is internal"))
+        .stderr(predicate::str::contains("--include-internal"));
+    assert!(!output.join("Promo.ts").exists());
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args([
+            "Promo",
+            "--type",
+            "layer",
+            "--no-folder",
+            "--include-internal",
+        ]);
+    cmd.assert().success();
+    assert!(output.join("Promo.ts").exists());
+}
+
+#[test]
+fn test_cli_generated_files_listing_respects_gitignore() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template).unwrap();
+    std::fs::write(template.join("$FILE_NAME.ts"), "// widget").unwrap();
+    std::fs::write(template.join("$FILE_NAME.build.log"), "build output").unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(project.join(".git")).unwrap();
+    std::fs::write(project.join(".gitignore"), "*.log\n").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let generate = |name: &str, show_all: bool| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&project)
+            .args([name, "--type", "widget"]);
+        if show_all {
+            cmd.arg("--show-all");
+        }
+        cmd.assert().success()
+    };
+
+    generate("Promo", false)
+        .stdout(predicate::str::contains("Promo.ts"))
+        .stdout(predicate::str::contains("Promo.build.log").not())
+        .stdout(predicate::str::contains(
+            "1 file(s) matched by .gitignore not listed",
+        ));
+    // Ignored files are still written
+    assert!(project.join("Promo/Promo.build.log").exists());
+
+    generate("Banner", true)
+        .stdout(predicate::str::contains("Banner.build.log"))
+        .stdout(predicate::str::contains("matched by .gitignore").not());
+}
+
+#[test]
+fn test_cli_warns_when_templates_dir_looks_like_a_project() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(project.join("widget")).unwrap();
+    std::fs::write(project.join("widget").join("$FILE_NAME.ts"), "// widget").unwrap();
+    std::fs::write(project.join("package.json"), "{}").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    let output = temp_dir.path().join("out");
+    let generate = |settings: &str| {
+        std::fs::write(
+            &config,
+            format!("templates_dir={}\n{}", project.display(), settings),
+        )
+        .unwrap();
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(["Promo", "--type", "widget", "--on-conflict", "overwrite"]);
+        cmd.assert().success()
+    };
+
+    generate("").stderr(predicate::str::contains(
+        "looks like a project (it has package.json)",
+    ));
+    generate("check_templates=false\n").stderr(predicate::str::contains("looks like").not());
+}
+
+#[test]
+fn test_cli_bootstraps_an_empty_templates_dir() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+    let output = temp_dir.path().join("out");
+    let generate = || {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(["Button", "--type", "component"]);
+        cmd.assert()
+    };
+
+    generate()
+        .code(7)
+        .stderr(predicate::str::contains("No templates found in"))
+        .stderr(predicate::str::contains("cli-frontend bootstrap"));
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("bootstrap");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("template files in"));
+    assert!(templates.join("component").join(".conf").is_file());
+
+    generate().success();
+    assert!(output.join("Button").join("Button.tsx").is_file());
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config").arg(&config).arg("bootstrap");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("already has templates"));
+}
+
+#[test]
+fn test_cli_feature_passes_shared_variables_to_every_structure() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for template in ["client", "hook"] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+        std::fs::write(
+            templates.join(template).join("$FILE_NAME.ts"),
+            "const base = '{{api_base_path}}';",
+        )
+        .unwrap();
+    }
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": ""},
+            {"path": "hooks", "template": "hook", "filename_pattern": "{name}", "description": ""}
+        ], "shared": {"api_base_path": "/api/{kebab_name}"}}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .arg("--strict")
+        .args(["feature", "UserProfile", "--architecture", "layers"]);
+    cmd.assert().success();
+    for file in ["api/UserProfile.ts", "hooks/UserProfile.ts"] {
+        assert_eq!(
+            std::fs::read_to_string(output.join("UserProfile").join(file)).unwrap(),
+            "const base = '/api/user-profile';"
+        );
+    }
+}
+
+#[test]
+fn test_cli_feature_rejects_structure_templates_needing_a_newer_cli() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("client")).unwrap();
+    std::fs::write(templates.join("client").join("$FILE_NAME.ts"), "export {};").unwrap();
+    std::fs::write(
+        templates.join("client").join("template.toml"),
+        "[metadata]\nrequires_cli = \"99.0\"\n",
+    )
+    .unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": ""}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth", "--architecture", "layers"]);
+    cmd.assert().code(10).stderr(predicate::str::contains(
+        "Template 'client' requires cli-frontend 99.0 or newer",
+    ));
+    assert!(!output.join("Auth").exists());
+}
+
+#[test]
+fn test_cli_feature_requires_the_variables_of_structure_templates() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("client")).unwrap();
+    std::fs::write(
+        templates.join("client").join("$FILE_NAME.ts"),
+        "const label = '{{label}}';",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("client").join(".conf"),
+        "[options]\nlabel_required=true\n",
+    )
+    .unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    let architecture = |params: &str| {
+        format!(
+            r#"{{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+                {{"path": "api", "template": "client", "filename_pattern": "{{name}}", "description": ""{}}}
+            ]}}"#,
+            params
+        )
+    };
+    std::fs::write(architectures.join("bare.json"), architecture("")).unwrap();
+    std::fs::write(
+        architectures.join("labelled.json"),
+        architecture(r#", "params": {"label": "{name}"}"#),
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth", "--architecture", "bare"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("label"))
+        .stderr(predicate::str::contains("required but not set"));
+    assert!(!output.join("Auth").exists());
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .args(["feature", "Auth", "--architecture", "labelled"]);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Auth/api/Auth.ts")).unwrap(),
+        "const label = 'Auth';"
+    );
+}
+
+#[test]
+fn test_cli_feature_applies_env_overrides_of_structure_templates() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("client")).unwrap();
+    std::fs::write(
+        templates.join("client").join("$FILE_NAME.ts"),
+        "const mode = '{{mode}}';",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("client").join(".conf"),
+        "[options]\nmode=dev\n\n[options.production]\nmode=prod\n",
+    )
+    .unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": ""}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+
+    for (env, mode) in [(None, "dev"), (Some("production"), "prod")] {
+        let output = temp_dir.path().join(env.unwrap_or("default"));
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(["Auth", "--type", "feature", "--architecture", "layers"]);
+        if let Some(env) = env {
+            cmd.args(["--env", env]);
+        }
+        cmd.assert().success();
+        assert_eq!(
+            std::fs::read_to_string(output.join("Auth/api/Auth.ts")).unwrap(),
+            format!("const mode = '{}';", mode)
+        );
+    }
+}
+
+#[test]
+fn test_cli_feature_accepts_generation_flags_after_the_subcommand() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("client")).unwrap();
+    std::fs::write(
+        templates.join("client").join("$FILE_NAME.ts"),
+        "const mode = '{{mode}}';\nconst scope = '{{scope}}';",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("client").join(".conf"),
+        "[options]\nmode=dev\n\n[options.production]\nmode=prod\n",
+    )
+    .unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": ""}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["feature", "Auth", "--architecture", "layers"])
+        .arg("--config")
+        .arg(&config)
+        .arg("-o")
+        .arg(&output)
+        .args(["--env", "production", "--var", "scope=admin", "--strict"])
+        .args(["--yes", "--quiet"]);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Auth/api/Auth.ts")).unwrap(),
+        "const mode = 'prod';\nconst scope = 'admin';"
+    );
+}
+
+#[test]
+fn test_cli_feature_passes_structure_params_to_their_template() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let barrel = temp_dir.path().join("templates").join("barrel");
+    std::fs::create_dir_all(&barrel).unwrap();
+    std::fs::write(barrel.join("index.ts"), "// {{layer}} of {{api_base_path}}").unwrap();
+    std::fs::write(barrel.join(".conf"), "[metadata]\ninternal=true\n").unwrap();
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "domain", "template": "barrel", "filename_pattern": "index", "description": "",
+             "params": {"layer": "domain"}},
+            {"path": "infrastructure", "template": "barrel", "filename_pattern": "index", "description": "",
+             "params": {"layer": "infrastructure", "api_base_path": "/v2/{kebab_name}"}}
+        ], "shared": {"api_base_path": "/api/{kebab_name}"}}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            temp_dir.path().join("templates").display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output)
+        .arg("--strict")
+        .args(["feature", "UserProfile", "--architecture", "layers"]);
+    cmd.assert().success();
+    let feature = output.join("UserProfile");
+    assert_eq!(
+        std::fs::read_to_string(feature.join("domain/index.ts")).unwrap(),
+        "// domain of /api/user-profile"
+    );
+    assert_eq!(
+        std::fs::read_to_string(feature.join("infrastructure/index.ts")).unwrap(),
+        "// infrastructure of /v2/user-profile"
+    );
+}
+
+#[test]
+fn test_cli_feature_generates_structures_into_output_roots() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for template in ["component", "spec"] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+        std::fs::write(templates.join(template).join("$FILE_NAME.ts"), template).unwrap();
+    }
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("split.json"),
+        r#"{"name": "Split", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "", "template": "component", "filename_pattern": "{name}", "description": ""},
+            {"path": "unit", "template": "spec", "filename_pattern": "{name}", "description": "",
+             "output_root": "tests"}
+        ]}"#,
+    )
+    .unwrap();
+    let write_config = |roots: &str| {
+        let config = temp_dir.path().join("test.conf");
+        std::fs::write(
+            &config,
+            format!(
+                "templates_dir={}\narchitectures_dir={}\n{}",
+                templates.display(),
+                architectures.display(),
+                roots
+            ),
+        )
+        .unwrap();
+        config
+    };
+    let src = temp_dir.path().join("src");
+    let tests = temp_dir.path().join("tests");
+    let generate = |config: &std::path::Path| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(config)
+            .arg("--output-dir")
+            .arg(&src)
+            .args(["feature", "Auth", "--architecture", "split"]);
+        cmd.assert()
+    };
+
+    generate(&write_config(""))
+        .failure()
+        .stderr(predicate::str::contains("Unknown output root 'tests'"));
+    assert!(!src.join("Auth").exists());
+
+    generate(&write_config(&format!(
+        "[roots]\ntests={}\n",
+        tests.display()
+    )))
+    .success();
+    assert_eq!(
+        std::fs::read_to_string(src.join("Auth/Auth.ts")).unwrap(),
+        "component"
+    );
+    assert_eq!(
+        std::fs::read_to_string(tests.join("Auth/unit/Auth.ts")).unwrap(),
+        "spec"
+    );
+    assert!(!src.join("Auth/unit").exists());
+}
+
+#[test]
+fn test_cli_feature_writes_index_of_structure_exports() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    for template in ["client", "hook"] {
+        std::fs::create_dir_all(templates.join(template)).unwrap();
+        std::fs::write(templates.join(template).join("$FILE_NAME.ts"), "").unwrap();
+    }
+    let architectures = temp_dir.path().join("architectures");
+    std::fs::create_dir_all(&architectures).unwrap();
+    std::fs::write(
+        architectures.join("layers.json"),
+        r#"{"name": "Layers", "description": "", "benefits": [], "limitations": [], "structure": [
+            {"path": "api", "template": "client", "filename_pattern": "{name}", "description": "",
+             "exports": {"{name}": ["{name}Client", "type {name}Request"]}},
+            {"path": "hooks", "template": "hook", "filename_pattern": "{name}", "description": "",
+             "exports": {"{name}": ["use{name}"]}}
+        ]}"#,
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\narchitectures_dir={}\n",
+            templates.display(),
+            architectures.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    let run = |flags: &[&str]| {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(&output)
+            .args(flags)
+            .args(["feature", "Auth", "--architecture", "layers"]);
+        cmd.assert().success()
+    };
+
+    run(&["--tree"]).stdout(predicate::str::contains("└── index.ts"));
+    run(&[]);
+    assert_eq!(
+        std::fs::read_to_string(output.join("Auth").join("index.ts")).unwrap(),
+        "export { AuthClient, type AuthRequest } from './api/Auth';\n\
+         export { useAuth } from './hooks/Auth';\n"
+    );
+}
+
+#[test]
+fn test_cli_file_header_is_commented_into_source_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.tsx"),
+        "export const {{name}} = 1;\n",
+    )
+    .unwrap();
+    std::fs::write(
+        templates.join("widget").join("$FILE_NAME.css"),
+        ".root {}\n",
+    )
+    .unwrap();
+    std::fs::write(templates.join("widget").join("package.json"), "{}\n").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nfile_header=Acme {{{{name}}}}\\nMIT\n",
+            templates.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Card", "--type", "widget", "--no-folder"])
+        .arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("Card.tsx")).unwrap(),
+        "// Acme Card\n// MIT\nexport const Card = 1;\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("Card.css")).unwrap(),
+        "/* Acme Card */\n/* MIT */\n.root {}\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("package.json")).unwrap(),
+        "{}\n"
+    );
+}
+
+#[test]
+fn test_cli_upgrade_all_regenerates_stamped_files_by_package() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("widget")).unwrap();
+    let template_file = templates.join("widget").join("$FILE_NAME.tsx");
+    std::fs::write(&template_file, "export const {{pascal_case name}} = 1;\n").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let root = temp_dir.path().join("repo");
+    for package in ["web", "admin"] {
+        let package_dir = root.join("packages").join(package);
+        std::fs::create_dir_all(&package_dir).unwrap();
+        std::fs::write(package_dir.join("package.json"), "{}").unwrap();
+        let mut cmd = get_cli_command();
+        cmd.args(["Card", "--type", "widget", "--stamp"])
+            .arg("--config")
+            .arg(&config)
+            .arg("--output-dir")
+            .arg(package_dir.join("src"));
+        cmd.assert().success();
+    }
+    let web_card = root.join("packages/web/src/Card/Card.tsx");
+    let admin_card = root.join("packages/admin/src/Card/Card.tsx");
+    let admin_content = std::fs::read_to_string(&admin_card)
+        .unwrap()
+        .replace("= 1", "= 42");
+    std::fs::write(&admin_card, &admin_content).unwrap();
+
+    std::fs::write(&template_file, "export const {{pascal_case name}} = 2;\n").unwrap();
+    let report = temp_dir.path().join("upgrade.patch");
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["upgrade", "--all", "--dry-run"])
+        .arg(&root)
+        .arg("--report")
+        .arg(&report);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("packages/admin"))
+        .stdout(predicate::str::contains("skipped, modified"))
+        .stdout(predicate::str::contains(
+            "1 of 2 stamped file(s) would be upgraded",
+        ));
+    let patch = std::fs::read_to_string(&report).unwrap();
+    assert!(patch.starts_with("# Package: packages/web\n--- a/packages/web/src/Card/Card.tsx"));
+    assert!(patch.contains("+export const Card = 2;"));
+    assert!(std::fs::read_to_string(&web_card).unwrap().contains("= 1;"));
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["upgrade", "--all"])
+        .arg(&root);
+    cmd.assert().success();
+    let upgraded = std::fs::read_to_string(&web_card).unwrap();
+    assert!(upgraded.contains("export const Card = 2;"));
+    assert_eq!(std::fs::read_to_string(&admin_card).unwrap(), admin_content);
+
+    // Upgraded files are stamped with the new template and no longer drift
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("drift")
+        .arg(root.join("packages/web"));
+    cmd.assert().success();
+}
+
+#[tokio::test]
+async fn test_run_api_captures_output_and_reports_files() {
+    use clap::Parser;
+    use cli_frontend::cli::Args;
+    use cli_frontend::ui::{Buffer, Output};
+    use cli_frontend::{run, RunIo};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(templates.join("hook")).unwrap();
+    std::fs::write(
+        templates.join("hook").join("use$FILE_NAME.ts"),
+        "export function use$FILE_NAME() {}\n",
+    )
+    .unwrap();
+
+    // Usage is only recorded on request, and never into the real home
+    let usage = temp_dir.path().join("usage.json");
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nusage_file={}\nlang=en\n",
+            templates.display(),
+            usage.display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let args = Args::parse_from([
+        "cli-frontend".as_ref(),
+        "Auth".as_ref(),
+        "--type".as_ref(),
+        "hook".as_ref(),
+        "--config".as_ref(),
+        config.as_os_str(),
+        "--output-dir".as_ref(),
+        output.as_os_str(),
+    ]);
+    let (stdout, stderr) = (Buffer::default(), Buffer::default());
+    let mut io = RunIo {
+        stdout: Output::new(stdout.clone()),
+        stderr: Output::new(stderr.clone()),
+        interactive: false,
+        record_usage: false,
+    };
+    let report = run(args, &mut io).await.unwrap();
+
+    assert_eq!(report.name.as_deref(), Some("Auth"));
+    assert_eq!(report.written.len(), 1);
+    assert!(report.written[0].ends_with("useAuth.ts"));
+    assert!(report.written[0].exists());
+    // The engine's per-file progress is captured along with the summary
+    let printed = stdout.contents();
+    assert!(printed.contains("hook 'Auth' generated successfully!"));
+    assert!(printed.contains("useAuth.ts"));
+    assert!(stderr.contents().is_empty());
+    assert!(!usage.exists());
+
+    // --describe and subcommands print into the same streams
+    let args = Args::parse_from([
+        "cli-frontend".as_ref(),
+        "--describe".as_ref(),
+        "hook".as_ref(),
+        "--config".as_ref(),
+        config.as_os_str(),
+    ]);
+    let stdout = Buffer::default();
+    let mut io = RunIo {
+        stdout: Output::new(stdout.clone()),
+        stderr: Output::new(stderr.clone()),
+        interactive: false,
+        record_usage: false,
+    };
+    run(args, &mut io).await.unwrap();
+    assert!(stdout.contents().contains("Template: hook"));
+
+    // Without a terminal there is no wizard to fall back to
+    let args = Args::parse_from([
+        "cli-frontend".as_ref(),
+        "--config".as_ref(),
+        config.as_os_str(),
+    ]);
+    let mut io = RunIo {
+        stdout: Output::new(Buffer::default()),
+        stderr: Output::new(Buffer::default()),
+        interactive: false,
+        record_usage: false,
+    };
+    let error = run(args, &mut io).await.unwrap_err();
+    assert!(error.to_string().contains("No name was provided"));
+
+    // Opting in records the use in the configured usage_file
+    let args = Args::parse_from([
+        "cli-frontend".as_ref(),
+        "Auth".as_ref(),
+        "--type".as_ref(),
+        "hook".as_ref(),
+        "--config".as_ref(),
+        config.as_os_str(),
+        "--output-dir".as_ref(),
+        output.as_os_str(),
+    ]);
+    let mut io = RunIo {
+        stdout: Output::new(Buffer::default()),
+        stderr: Output::new(Buffer::default()),
+        interactive: false,
+        record_usage: true,
+    };
+    run(args, &mut io).await.unwrap();
+    assert!(std::fs::read_to_string(&usage).unwrap().contains("Auth"));
+}
+
+#[test]
+fn test_cli_import_plop_and_hygen_generators() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let plop = temp_dir.path().join("plop");
+    std::fs::create_dir_all(plop.join("plop-templates")).unwrap();
+    std::fs::write(
+        plop.join("plopfile.js"),
+        r#"module.exports = (plop) => {
+  plop.setGenerator('widget', {
+    description: 'Dashboard widget',
+    prompts: [
+      { type: 'input', name: 'name', message: 'Widget name?' },
+      { type: 'confirm', name: 'withTitle', message: 'Show a title?', default: false },
+    ],
+    actions: [
+      { type: 'add', path: 'src/widgets/{{pascalCase name}}/{{pascalCase name}}.tsx', templateFile: 'plop-templates/widget.hbs' },
+    ],
+  });
+};
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        plop.join("plop-templates").join("widget.hbs"),
+        "export const {{pascalCase name}} = () => {{#if withTitle}}'{{titleCase name}}'{{else}}null{{/if}};\n",
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["import", "plop"])
+        .arg(&plop);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 'widget' (1 files)"))
+        .stdout(predicate::str::contains("widget.hbs:1"))
+        .stdout(predicate::str::contains(
+            "1 template(s) imported, 2 TODO(s)",
+        ));
+
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "SalesChart",
+        "--type",
+        "widget",
+        "--var",
+        "withTitle=true",
+        "--config",
+    ])
+    .arg(&config)
+    .arg("--output-dir")
+    .arg(&output);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("SalesChart").join("SalesChart.tsx")).unwrap(),
+        "export const SalesChart = () => '';\n"
+    );
+
+    // Importing again leaves the template alone unless forced
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["import", "plop"])
+        .arg(&plop);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Skipping 'widget'"));
+
+    let hygen = temp_dir.path().join("_templates").join("store").join("new");
+    std::fs::create_dir_all(&hygen).unwrap();
+    std::fs::write(
+        hygen.join("store.ejs.t"),
+        "---\nto: src/stores/<%= h.changeCase.camel(name) %>Store.ts\n---\n\
+         export const <%= h.changeCase.camel(name) %>Store = createStore({ persist: <%= persist %> });\n",
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["import", "hygen"])
+        .arg(temp_dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 'store' (1 files)"));
+
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "Cart",
+        "--type",
+        "store",
+        "--var",
+        "persist=true",
+        "--config",
+    ])
+    .arg(&config)
+    .arg("--output-dir")
+    .arg(&output);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("cartStore.ts")).unwrap(),
+        "export const cartStore = createStore({ persist: true });\n"
+    );
+}
+
+#[test]
+fn test_cli_import_yeoman_generator() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let templates = temp_dir.path().join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(&config, format!("templates_dir={}\n", templates.display())).unwrap();
+
+    let package = temp_dir.path().join("generator-acme");
+    let app = package.join("generators").join("app");
+    std::fs::create_dir_all(app.join("templates")).unwrap();
+    std::fs::write(
+        package.join("package.json"),
+        r#"{ "name": "generator-acme", "version": "1.0.0" }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        app.join("index.js"),
+        r#"module.exports = class extends Generator {
+  async prompting() {
+    this.props = await this.prompt([
+      { type: 'input', name: 'name', message: 'Module name?' },
+      { type: 'confirm', name: 'withLogger', message: 'Add a logger?', default: false },
+    ]);
+  }
+
+  writing() {
+    this.fs.copyTpl(
+      this.templatePath('module.ts'),
+      this.destinationPath(`src/${_.kebabCase(this.props.name)}.module.ts`),
+      this.props
+    );
+  }
+};
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        app.join("templates").join("module.ts"),
+        "export class <%= name %>Module {<% if (withLogger) { %> logger = true;<% } %> }\n",
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["import", "yeoman"])
+        .arg(&package);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 'acme' (1 files)"))
+        .stdout(predicate::str::contains("wrote under src"))
+        .stdout(predicate::str::contains(
+            "1 template(s) imported, 1 TODO(s)",
+        ));
+
+    let output = temp_dir.path().join("out");
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "Billing",
+        "--type",
+        "acme",
+        "--var",
+        "withLogger=true",
+        "--config",
+    ])
+    .arg(&config)
+    .arg("--output-dir")
+    .arg(&output);
+    cmd.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(output.join("billing.module.ts")).unwrap(),
+        "export class BillingModule { logger = true; }\n"
+    );
+}
+
+#[test]
+fn test_cli_sync_components_only_regenerates_changed_specs() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("$FILE_NAME.tsx"),
+        "export const $FILE_NAME = '{{color}}';\n",
+    )
+    .unwrap();
+    std::fs::write(template_dir.join(".conf"), "[options]\ncolor=red\n").unwrap();
+    let output = temp_dir.path().join("out");
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\noutput_dir={}\n",
+            temp_dir.path().join("templates").display(),
+            output.display()
+        ),
+    )
+    .unwrap();
+
+    let specs = temp_dir.path().join("specs");
+    std::fs::create_dir_all(&specs).unwrap();
+    std::fs::write(specs.join("badge.yaml"), "name: Badge\ntype: widget\n").unwrap();
+    std::fs::write(
+        specs.join("chart.yml"),
+        "name: Chart\ntype: widget\nvars:\n  color: blue\n",
+    )
+    .unwrap();
+
+    let sync = || {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .arg("sync-components")
+            .arg(&specs);
+        cmd
+    };
+
+    sync()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 synced, 0 unchanged"));
+    assert_eq!(
+        std::fs::read_to_string(output.join("Chart").join("Chart.tsx")).unwrap(),
+        "export const Chart = 'blue';\n"
+    );
+
+    // Only the edited spec is regenerated
+    std::fs::write(
+        specs.join("chart.yml"),
+        "name: Chart\ntype: widget\nvars:\n  color: green\n",
+    )
+    .unwrap();
+    std::fs::write(output.join("Badge").join("Badge.tsx"), "// edited\n").unwrap();
+    sync()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 synced, 1 unchanged"));
+    assert_eq!(
+        std::fs::read_to_string(output.join("Chart").join("Chart.tsx")).unwrap(),
+        "export const Chart = 'green';\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("Badge").join("Badge.tsx")).unwrap(),
+        "// edited\n"
+    );
+
+    std::fs::remove_file(specs.join("badge.yaml")).unwrap();
+    sync()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("badge.yaml was removed"))
+        .stdout(predicate::str::contains("0 synced, 1 unchanged"));
+}
+
+#[test]
+fn test_cli_prints_preflight_summary_before_writing() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};\n").unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.test.tsx"), "test();\n").unwrap();
+    std::fs::write(
+        template_dir.join(".conf"),
+        "[options]\nwith_tests=false\nstyle=css\n\n[files]\n$FILE_NAME.test.tsx=var_with_tests\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.args([
+        "Badge",
+        "--type",
+        "widget",
+        "--var",
+        "with_tests=true",
+        "--config",
+    ])
+    .arg(&config)
+    .arg("--output-dir")
+    .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-flight summary"))
+        .stdout(predicate::str::contains("Template:  widget"))
+        .stdout(predicate::str::contains(format!(
+            "Output:    {}",
+            output.join("Badge").display()
+        )))
+        .stdout(predicate::str::contains("Files:     2"))
+        .stdout(predicate::str::contains(
+            "Variables: style=css, with_tests=true",
+        ));
+    assert!(output.join("Badge").join("Badge.test.tsx").exists());
+}
+
+#[test]
+fn test_cli_quiet_and_no_emoji_output() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};\n").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+    let has_emoji = |text: &str| {
+        text.chars()
+            .any(|c| matches!(c as u32, 0x1F300..=0x1FAFF | 0x2600..=0x26FF))
+    };
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Quiet", "--type", "widget", "--quiet", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+    assert!(output.join("Quiet").join("Quiet.tsx").exists());
+
+    let mut cmd = get_cli_command();
+    cmd.args(["--quiet", "--config"])
+        .arg(&config)
+        .args(["--describe", "widget", "--json"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"name\": \"widget\""));
+
+    let mut cmd = get_cli_command();
+    cmd.args(["Plain", "--type", "widget", "--no-emoji", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    let plain = cmd.assert().success().get_output().stdout.clone();
+    let plain = String::from_utf8(plain).unwrap();
+    assert!(plain.contains("Pre-flight summary"));
+    assert!(!has_emoji(&plain), "emoji in output:\n{}", plain);
+
+    let mut cmd = get_cli_command();
+    cmd.env("NO_COLOR", "1")
+        .args(["--config"])
+        .arg(&config)
+        .arg("--list");
+    let listing = cmd.assert().success().get_output().stdout.clone();
+    let listing = String::from_utf8(listing).unwrap();
+    assert!(listing.contains("widget"));
+    assert!(!has_emoji(&listing), "emoji in output:\n{}", listing);
+}
+
+#[test]
+fn test_cli_describe_json_keeps_emoji_and_non_ascii_text() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join(".conf"),
+        "[metadata]\ndescription=🚀 Fast widget ✅ done, señal\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    for env in [("LC_ALL", "en_US.UTF-8"), ("LC_ALL", "C")] {
+        let mut cmd = get_cli_command();
+        cmd.env(env.0, env.1)
+            .args(["--no-emoji", "--config"])
+            .arg(&config)
+            .args(["--describe", "widget", "--json"]);
+        cmd.assert().success().stdout(predicate::str::contains(
+            "\"description\": \"🚀 Fast widget ✅ done, señal\"",
+        ));
+    }
+}
+
+#[test]
+fn test_cli_falls_back_to_ascii_without_utf8_locale() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("templates").join("widget")).unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.env("LC_ALL", "C")
+        .env_remove("NO_COLOR")
+        .args(["--config"])
+        .arg(&config)
+        .arg("--list");
+    let listing = cmd.assert().success().get_output().stdout.clone();
+    let listing = String::from_utf8(listing).unwrap();
+    assert!(listing.contains("* Available Templates:"), "{}", listing);
+    assert!(listing.contains("  * widget"), "{}", listing);
+    assert!(listing.is_ascii(), "non-ASCII output:\n{}", listing);
+}
+
+#[test]
+fn test_cli_prints_messages_in_configured_language() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};\n").unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nlang=es\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+    let output = temp_dir.path().join("out");
+
+    let mut cmd = get_cli_command();
+    cmd.env_remove("CLI_FRONTEND_LANG")
+        .args(["Badge", "--type", "widget", "--config"])
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Resumen previo"))
+        .stdout(predicate::str::contains("Plantilla: widget"))
+        .stdout(predicate::str::contains(
+            "¡widget 'Badge' generado correctamente!",
+        ));
+
+    // CLI_FRONTEND_LANG wins over the configuration
+    let mut cmd = get_cli_command();
+    cmd.args(["--config"])
+        .arg(&config)
+        .args(["--describe", "widget"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Usage Examples:"));
+
+    let mut cmd = get_cli_command();
+    cmd.env("CLI_FRONTEND_LANG", "es")
+        .args(["--config"])
+        .arg(&config)
+        .args(["--describe", "widget"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Ejemplos de uso:"));
+}
+
+#[test]
+fn test_cli_describe_shows_recent_uses() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(template_dir.join("$FILE_NAME.tsx"), "export {};\n").unwrap();
+    let usage_file = temp_dir.path().join("usage.json");
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\nusage_file={}\n",
+            temp_dir.path().join("templates").display(),
+            usage_file.display()
+        ),
+    )
+    .unwrap();
+    let describe = || {
+        let mut cmd = get_cli_command();
+        cmd.arg("--config")
+            .arg(&config)
+            .args(["--describe", "widget"]);
+        cmd.assert().success()
+    };
+
+    describe().stdout(predicate::str::contains("Recently used like:").not());
+
+    let mut cmd = get_cli_command();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(temp_dir.path().join("out"))
+        .args(["Badge", "--type", "widget", "--var", "tone=info"]);
+    cmd.assert().success();
+    assert!(usage_file.is_file());
+
+    describe()
+        .stdout(predicate::str::contains("Recently used like:"))
+        .stdout(predicate::str::contains(
+            "cli-frontend Badge --type widget --var tone=info",
+        ));
+}
+
+#[test]
+fn test_cli_completes_variable_values_from_the_template() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let template_dir = temp_dir.path().join("templates").join("widget");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join(".conf"),
+        "[options]\nstyle=css\nstyle_options=css,scss\n",
+    )
+    .unwrap();
+    let config = temp_dir.path().join("test.conf");
+    std::fs::write(
+        &config,
+        format!(
+            "templates_dir={}\n",
+            temp_dir.path().join("templates").display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = get_cli_command();
+    cmd.args(["complete", "--shell", "zsh", "--"]).arg(format!(
+        "cli-frontend Card --config {} --type widget --var style=s",
+        config.display()
+    ));
+    cmd.assert().success().stdout("style=scss\n");
+
+    let mut cmd = get_cli_command();
+    cmd.args(["completions", "bash"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "complete -o default -F _cli_frontend",
+    ));
+}
+
+#[test]
+fn test_cli_export_manifest() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output = temp_dir.path().join("manifest.json");
+
+    let mut cmd = get_cli_command();
+    cmd.arg("export-manifest").arg("--output").arg(&output);
+    cmd.assert().success();
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(manifest["manifest_version"], 1);
+    assert_eq!(manifest["cli_version"], env!("CARGO_PKG_VERSION"));
+
+    let templates = manifest["templates"].as_array().unwrap();
+    let component = templates
+        .iter()
+        .find(|template| template["name"] == "component")
+        .unwrap();
+    assert!(component["variables"].is_object());
+    assert!(component["file_filters"].is_object());
+
+    let architectures = manifest["architectures"].as_array().unwrap();
+    let screaming = architectures
+        .iter()
+        .find(|architecture| architecture["name"] == "screaming-architecture")
+        .unwrap();
+    assert!(!screaming["title"].as_str().unwrap().is_empty());
+    assert!(!screaming["structure"].as_array().unwrap().is_empty());
+
+    // Without --output the manifest goes to stdout
+    let mut cmd = get_cli_command();
+    cmd.arg("export-manifest");
+    let stdout = cmd.assert().success().get_output().stdout.clone();
+    let printed: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(printed, manifest);
+}